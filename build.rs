@@ -6,6 +6,8 @@ fn main() {
 
     generate_build_config(&project_dir);
     generate_embedded_frontend_assets(&project_dir);
+    generate_typescript_client(&project_dir);
+    generate_error_codes(&project_dir);
 
     let src_dir = format!("{}/thirdparty/webui-c-src/src", project_dir);
     let civetweb_dir = format!("{}/civetweb", src_dir);
@@ -185,3 +187,417 @@ pub const EMBEDDED_WEBUI_JS: &str = "";
         eprintln!("Warning: Failed to write embedded frontend assets: {}", e);
     }
 }
+
+fn generate_typescript_client(project_dir: &str) {
+    let manifest_path = format!("{}/src/core/presentation/webui/api.toml", project_dir);
+    println!("cargo:rerun-if-changed={}", manifest_path);
+
+    let manifest = match fs::read_to_string(&manifest_path).map(|c| c.parse::<toml::Value>()) {
+        Ok(Ok(manifest)) => manifest,
+        Ok(Err(e)) => {
+            eprintln!("Warning: Failed to parse {}: {}", manifest_path, e);
+            return;
+        }
+        Err(_) => return,
+    };
+
+    let (interfaces, _) = collect_interfaces(project_dir, manifest.get("types"));
+
+    // Shared entities go to a standalone .d.ts that the client re-exports
+    let mut entities = Vec::new();
+    if let Some(declarations) = manifest.get("declarations") {
+        let (declared, names) = collect_interfaces(project_dir, declarations.get("types"));
+        let imports: Vec<&str> = declarations
+            .get("imports")
+            .and_then(|i| i.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|i| i.as_str())
+            .collect();
+        let output = declarations
+            .get("output")
+            .and_then(|o| o.as_str())
+            .unwrap_or("frontend/src/generated/entities.d.ts");
+
+        write_if_changed(
+            &Path::new(project_dir).join(output),
+            &render_typescript_declarations(&imports, &declared),
+        );
+        entities = names;
+    }
+
+    let field = |value: &toml::Value, key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string())
+    };
+    let handlers: Vec<ClientHandler> = manifest
+        .get("handler")
+        .and_then(|h| h.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|h| {
+            Some(ClientHandler {
+                name: field(h, "name")?,
+                group: field(h, "group").unwrap_or_else(|| "default".to_string()),
+                method: field(h, "method")?,
+                request: field(h, "request"),
+                response: field(h, "response").unwrap_or_else(|| "unknown".to_string()),
+                event: field(h, "event"),
+            })
+        })
+        .collect();
+
+    let client = render_typescript_client(&entities, &interfaces, &handlers);
+    write_if_changed(
+        &Path::new(project_dir).join("frontend/src/generated/backend-client.ts"),
+        &client,
+    );
+}
+
+/// Mirror `ErrorCode` from src/core/error.rs as a TypeScript enum, with the
+/// descriptions and numbers from `ErrorCode::description` and the enum
+fn generate_error_codes(project_dir: &str) {
+    let source_path = format!("{}/src/core/error.rs", project_dir);
+    println!("cargo:rerun-if-changed={}", source_path);
+    let Ok(source) = fs::read_to_string(&source_path) else {
+        return;
+    };
+    write_if_changed(
+        &Path::new(project_dir).join("frontend/src/generated/error-codes.ts"),
+        &render_error_codes(&source),
+    );
+}
+
+fn render_error_codes(source: &str) -> String {
+    // (variant, number) in declaration order, with the group comments
+    let mut entries: Vec<Result<(String, String), String>> = Vec::new();
+    let mut wire_names: Vec<(String, String)> = Vec::new();
+    let mut descriptions: Vec<(String, String)> = Vec::new();
+    let mut in_enum = false;
+
+    for line in source.lines() {
+        let line = line.trim();
+        if line.starts_with("pub enum ErrorCode") {
+            in_enum = true;
+            continue;
+        }
+        if in_enum {
+            if line.starts_with('}') {
+                in_enum = false;
+            } else if let Some(comment) = line.strip_prefix("//") {
+                entries.push(Err(comment.trim().to_string()));
+            } else if let Some((variant, number)) = line.trim_end_matches(',').split_once('=') {
+                entries.push(Ok((variant.trim().to_string(), number.trim().to_string())));
+            }
+            continue;
+        }
+        let Some((variant, arm)) = line
+            .strip_prefix("ErrorCode::")
+            .and_then(|rest| rest.split_once(" => "))
+        else {
+            continue;
+        };
+        let quoted = |text: &str| {
+            text.split_once('"')
+                .and_then(|(_, rest)| rest.rsplit_once('"'))
+                .map(|(inner, _)| inner.to_string())
+        };
+        if arm.starts_with("write!") {
+            if let Some(name) = quoted(arm) {
+                wire_names.push((variant.to_string(), name));
+            }
+        } else if arm.starts_with('"') {
+            if let Some(description) = quoted(arm) {
+                descriptions.push((variant.to_string(), description));
+            }
+        }
+    }
+
+    let lookup = |list: &[(String, String)], variant: &str| {
+        list.iter()
+            .find(|(name, _)| name == variant)
+            .map(|(_, value)| value.replace('\'', "\\'"))
+    };
+    let variants: Vec<(&String, &String)> = entries
+        .iter()
+        .filter_map(|entry| entry.as_ref().ok().map(|(v, n)| (v, n)))
+        .collect();
+
+    let mut output = String::from(
+        "// Auto-generated by build.rs from ErrorCode in src/core/error.rs\n\
+         // Do not edit by hand.\n\n\
+         export enum ErrorCode {\n",
+    );
+    for (index, entry) in entries.iter().enumerate() {
+        match entry {
+            Err(comment) => {
+                if index > 0 {
+                    output.push('\n');
+                }
+                output.push_str(&format!("  // {}\n", comment));
+            }
+            Ok((variant, _)) => {
+                let wire = lookup(&wire_names, variant).unwrap_or_else(|| variant.to_uppercase());
+                output.push_str(&format!("  {} = '{}',\n", variant, wire));
+            }
+        }
+    }
+    output.push_str("}\n\n");
+
+    output.push_str("export const ERROR_CODE_NUMBERS: Record<ErrorCode, number> = {\n");
+    for (variant, number) in &variants {
+        output.push_str(&format!("  [ErrorCode.{}]: {},\n", variant, number));
+    }
+    output.push_str("};\n\n");
+
+    output.push_str("export const ERROR_CODE_DESCRIPTIONS: Record<ErrorCode, string> = {\n");
+    for (variant, _) in &variants {
+        let description = lookup(&descriptions, variant).unwrap_or_default();
+        output.push_str(&format!("  [ErrorCode.{}]: '{}',\n", variant, description));
+    }
+    output.push_str("};\n");
+    output
+}
+
+/// Convert every `{ source, structs }` entry to interfaces, returning the
+/// TypeScript source and the exported interface names
+fn collect_interfaces(project_dir: &str, entries: Option<&toml::Value>) -> (String, Vec<String>) {
+    let mut interfaces = String::new();
+    let mut names = Vec::new();
+    for types in entries.and_then(|t| t.as_array()).into_iter().flatten() {
+        let Some(source) = types.get("source").and_then(|s| s.as_str()) else {
+            continue;
+        };
+        let structs: Vec<&str> = types
+            .get("structs")
+            .and_then(|s| s.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|s| s.as_str())
+            .collect();
+        names.extend(structs.iter().map(|s| {
+            s.split_once(" as ")
+                .map_or(*s, |(_, alias)| alias)
+                .trim()
+                .to_string()
+        }));
+
+        let source_path = format!("{}/{}", project_dir, source);
+        println!("cargo:rerun-if-changed={}", source_path);
+        if let Ok(rust_source) = fs::read_to_string(&source_path) {
+            interfaces.push_str(&rust_structs_to_typescript(&rust_source, &structs));
+        }
+    }
+    (interfaces, names)
+}
+
+/// Only touch generated files when the output changes to keep the frontend build incremental
+fn write_if_changed(path: &Path, content: &str) {
+    if fs::read_to_string(path).ok().as_deref() == Some(content) {
+        return;
+    }
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Err(e) = fs::write(path, content) {
+        eprintln!("Warning: Failed to write {}: {}", path.display(), e);
+    }
+}
+
+struct ClientHandler {
+    name: String,
+    group: String,
+    method: String,
+    request: Option<String>,
+    response: String,
+    event: Option<String>,
+}
+
+fn rust_type_to_typescript(ty: &str) -> String {
+    let ty = ty.trim();
+
+    if let Some(inner) = ty.strip_prefix("Option<").and_then(|t| t.strip_suffix('>')) {
+        return format!("{} | null", rust_type_to_typescript(inner));
+    }
+    if let Some(inner) = ty.strip_prefix("Vec<").and_then(|t| t.strip_suffix('>')) {
+        let inner = rust_type_to_typescript(inner);
+        return if inner.contains(' ') {
+            format!("Array<{}>", inner)
+        } else {
+            format!("{}[]", inner)
+        };
+    }
+    if let Some(inner) = ty
+        .strip_prefix("HashMap<String,")
+        .and_then(|t| t.strip_suffix('>'))
+    {
+        return format!("Record<string, {}>", rust_type_to_typescript(inner));
+    }
+
+    match ty {
+        "String" | "&str" | "&'static str" | "DateTime<Utc>" => "string".to_string(),
+        "bool" => "boolean".to_string(),
+        "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "usize" | "isize" | "f32"
+        | "f64" => "number".to_string(),
+        "serde_json::Value" => "unknown".to_string(),
+        other => other.rsplit("::").next().unwrap_or(other).to_string(),
+    }
+}
+
+/// Extract the named `pub struct` definitions from Rust source as TypeScript interfaces.
+///
+/// Entries may rename the interface ("EventData as AppEvent"). `Option` fields
+/// marked `#[serde(skip_serializing_if = ...)]` become optional properties, and a
+/// `// ts: <type>` comment right above a field overrides its mapped type.
+fn rust_structs_to_typescript(source: &str, structs: &[&str]) -> String {
+    let mut output = String::new();
+    let mut current: Option<(String, Vec<String>)> = None;
+    let mut skipped_when_none = false;
+    let mut type_override: Option<String> = None;
+
+    for line in source.lines() {
+        let line = line.trim();
+
+        if let Some((name, fields)) = current.as_mut() {
+            if line.starts_with('}') {
+                output.push_str(&format!("export interface {} {{\n", name));
+                for field in fields.iter() {
+                    output.push_str(&format!("  {};\n", field));
+                }
+                output.push_str("}\n\n");
+                current = None;
+            } else if let Some(ty) = line.strip_prefix("// ts:") {
+                type_override = Some(ty.trim().to_string());
+            } else if line.starts_with("#[serde(") && line.contains("skip_serializing_if") {
+                skipped_when_none = true;
+            } else if let Some(field) = line.strip_prefix("pub ") {
+                if let Some((field_name, field_type)) = field.split_once(':') {
+                    let mut field_name = field_name.trim().to_string();
+                    let mut field_type = field_type.trim().trim_end_matches(',');
+                    if skipped_when_none {
+                        if let Some(inner) =
+                            field_type.strip_prefix("Option<").and_then(|t| t.strip_suffix('>'))
+                        {
+                            field_name.push('?');
+                            field_type = inner;
+                        }
+                    }
+                    let ts_type = type_override
+                        .take()
+                        .unwrap_or_else(|| rust_type_to_typescript(field_type));
+                    fields.push(format!("{}: {}", field_name, ts_type));
+                }
+                skipped_when_none = false;
+            }
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("pub struct ") {
+            let name = rest.trim_end_matches('{').trim();
+            let exported = structs.iter().find_map(|entry| match entry.split_once(" as ") {
+                Some((rust_name, alias)) => (rust_name.trim() == name).then(|| alias.trim()),
+                None => (*entry == name).then_some(*entry),
+            });
+            if let Some(exported) = exported {
+                current = Some((exported.to_string(), Vec::new()));
+            }
+        }
+    }
+
+    output
+}
+
+fn to_camel_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' || c == ':' {
+            upper = true;
+        } else if upper {
+            result.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn render_typescript_declarations(imports: &[&str], interfaces: &str) -> String {
+    let mut output = String::from(
+        "// Auto-generated by build.rs from src/core/presentation/webui/api.toml\n\
+         // Do not edit by hand.\n\n",
+    );
+    for import in imports {
+        output.push_str(import);
+        output.push('\n');
+    }
+    if !imports.is_empty() {
+        output.push('\n');
+    }
+    output.push_str(interfaces.trim_end());
+    output.push('\n');
+    output
+}
+
+fn render_typescript_client(
+    entities: &[String],
+    interfaces: &str,
+    handlers: &[ClientHandler],
+) -> String {
+    let mut output = String::from(
+        "// Auto-generated by build.rs from src/core/presentation/webui/api.toml\n\
+         // Do not edit by hand.\n\n\
+         import type { Result } from '../types';\n\
+         import { callBackend, registerResponseEvent } from '../viewmodels/api-client.viewmodel';\n",
+    );
+    if !entities.is_empty() {
+        let names = entities.join(", ");
+        output.push_str(&format!("import type {{ {} }} from './entities';\n\n", names));
+        output.push_str(&format!("export type {{ {} }};\n", names));
+    }
+    output.push('\n');
+    output.push_str(interfaces);
+
+    for handler in handlers {
+        if let Some(ref event) = handler.event {
+            output.push_str(&format!(
+                "registerResponseEvent('{}', '{}');\n",
+                handler.name, event
+            ));
+        }
+    }
+
+    let mut groups: Vec<(&str, Vec<&ClientHandler>)> = Vec::new();
+    for handler in handlers {
+        match groups.iter_mut().find(|(group, _)| *group == handler.group) {
+            Some((_, members)) => members.push(handler),
+            None => groups.push((&handler.group, vec![handler])),
+        }
+    }
+
+    output.push_str("\nexport const backend = {\n");
+    for (group, members) in groups {
+        output.push_str(&format!("  {}: {{\n", to_camel_case(group)));
+        for handler in members {
+            let (params, args) = match handler.request.as_deref() {
+                Some("string") => ("request: string".to_string(), ", request".to_string()),
+                Some(request) => (
+                    format!("request: {}", request),
+                    ", JSON.stringify(request)".to_string(),
+                ),
+                None => (String::new(), String::new()),
+            };
+            output.push_str(&format!(
+                "    {}: ({}): Promise<Result<{}>> =>\n      callBackend<{}>('{}'{}),\n",
+                handler.method, params, handler.response, handler.response, handler.name, args
+            ));
+        }
+        output.push_str("  },\n");
+    }
+    output.push_str("};\n");
+
+    output
+}
@@ -15,6 +15,13 @@ export class GlobalErrorHandler implements ErrorHandler {
       source: 'angular',
       title: this.extractTitle(error),
     });
+
+    // Angular catches these before window.onerror, so hand them to the
+    // backend log through the hook the backend injects
+    const win = window as unknown as {
+      __frontendLog?: (level: string, message: string, stack?: string, source?: string) => void;
+    };
+    win.__frontendLog?.('error', errorValue.message, errorValue.details, 'angular');
   }
 
   /**
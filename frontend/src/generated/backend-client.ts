@@ -0,0 +1,612 @@
+// Auto-generated by build.rs from src/core/presentation/webui/api.toml
+// Do not edit by hand.
+
+import type { Result } from '../types';
+import { callBackend, registerResponseEvent } from '../viewmodels/api-client.viewmodel';
+import type { User, Product, SystemInfo, AppEvent, ErrorValue, ErrorResponse } from './entities';
+
+export type { User, Product, SystemInfo, AppEvent, ErrorValue, ErrorResponse };
+
+export interface ErrorCodeInfo {
+  code: string;
+  number: number;
+  category: string;
+  description: string;
+}
+
+export interface SerializationStats {
+  format: string;
+  total_serializations: number;
+  total_deserializations: number;
+  total_bytes_sent: number;
+  total_bytes_received: number;
+  avg_serialization_time_us: number;
+  avg_deserialization_time_us: number;
+  p50_serialization_time_us: number;
+  p95_serialization_time_us: number;
+  p99_serialization_time_us: number;
+  p50_deserialization_time_us: number;
+  p95_deserialization_time_us: number;
+  p99_deserialization_time_us: number;
+  compression_ratio: number;
+}
+
+export interface CompressionStats {
+  handler: string;
+  compressed_count: number;
+  bytes_before: number;
+  bytes_after: number;
+  bytes_saved: number;
+}
+
+export interface FormatComparison {
+  format: string;
+  size_ratio: string;
+  speed_ratio: string;
+  readability: string;
+  browser_support: string;
+  use_case: string;
+}
+
+export interface NegotiateResponse {
+  format: string;
+  preferred: string;
+  supported: string[];
+}
+
+export interface SetFormatResponse {
+  format: string;
+  previous: string;
+}
+
+export interface SerializationStatsResponse {
+  active: string;
+  stats: SerializationStats[];
+  comparison: FormatComparison[];
+  compression: CompressionStats[];
+}
+
+export interface BusSubscription {
+  id: number;
+  topic: string;
+}
+
+export interface ServiceInfo {
+  type_name: string;
+  name: string | null;
+  lifetime: string;
+  created: boolean;
+  resolve_count: number;
+}
+
+export interface AppConfig {
+  app: AppSettings;
+  executable: ExecutableSettings;
+  database: DatabaseSettings;
+  window: WindowSettings;
+  logging: LoggingSettings;
+  communication: CommunicationSettings;
+  features: FeatureSettings;
+  remote: RemoteSettings | null;
+  dev: DevSettings | null;
+  event_bus: EventBusSettings | null;
+  secrets: SecretsSettings | null;
+  remote_config: RemoteConfigSettings | null;
+  security: SecuritySettings | null;
+  filesystem: FilesystemSettings | null;
+  network: NetworkSettings | null;
+  plugins: Record<string, unknown> | null;
+}
+
+export interface AppSettings {
+  name: string;
+  version: string;
+  description: string | null;
+  author: string | null;
+  website: string | null;
+}
+
+export interface ExecutableSettings {
+  name: string;
+}
+
+export interface DatabaseSettings {
+  path: string;
+  create_sample_data: boolean | null;
+  event_sourcing: boolean | null;
+  snapshot_interval: number | null;
+  persist_error_stats: boolean | null;
+}
+
+export interface WindowSettings {
+  title: string;
+  width: number | null;
+  height: number | null;
+  min_width: number | null;
+  min_height: number | null;
+  resizable: boolean | null;
+  x: number | null;
+  y: number | null;
+  maximized: boolean | null;
+  always_on_top: boolean | null;
+}
+
+export interface LoggingSettings {
+  level: string;
+  file: string;
+  append: boolean | null;
+  buffer_size: number | null;
+  slow_span_ms: number | null;
+  sinks: LogSinkSettings[] | null;
+  crash_dir: string | null;
+  crash_report_url: string | null;
+  queue_size: number | null;
+  console: ConsoleSettings | null;
+  redaction: RedactionSettings | null;
+}
+
+export interface RedactionSettings {
+  enabled: boolean | null;
+  emails: boolean | null;
+  tokens: boolean | null;
+  patterns: string[] | null;
+}
+
+export interface ConsoleSettings {
+  colors: boolean | null;
+  timestamps: boolean | null;
+  format: string | null;
+}
+
+export interface LogSinkSettings {
+  kind: string;
+  address: string | null;
+  url: string | null;
+  headers: Record<string, string> | null;
+  level: string | null;
+  batch_size: number | null;
+  flush_interval_ms: number | null;
+  max_retries: number | null;
+}
+
+export interface CommunicationSettings {
+  transport: string | null;
+  serialization: string | null;
+  compression_threshold: number | null;
+  backpressure: BackpressureSettings | null;
+  limits: PayloadLimitSettings | null;
+}
+
+export interface BackpressureSettings {
+  max_queue_depth: number | null;
+  max_in_flight: number | null;
+  policies: Record<string, string> | null;
+}
+
+export interface PayloadLimitSettings {
+  max_request_bytes: number | null;
+  max_response_bytes: number | null;
+  max_string_chars: number | null;
+  field_max_chars: Record<string, number> | null;
+}
+
+export interface EventBusSettings {
+  queue_capacity: number | null;
+  overflow: string | null;
+  overflow_by_event: Record<string, string> | null;
+  bridge_topics: string[] | null;
+}
+
+export interface RemoteSettings {
+  enabled: boolean | null;
+  bind_address: string | null;
+  port: number | null;
+  token: string | null;
+  allowed_ips: string[] | null;
+  max_connections: number | null;
+}
+
+export interface SecretsSettings {
+  service: string | null;
+  fallback_file: string | null;
+}
+
+export interface RemoteConfigSettings {
+  url: string | null;
+  signing_key: string | null;
+  cache_file: string | null;
+  timeout_secs: number | null;
+}
+
+export interface FilesystemSettings {
+  allowed_roots: string[] | null;
+}
+
+export interface NetworkSettings {
+  diagnostics_targets: string[] | null;
+  proxy: string | null;
+  no_proxy: string[] | null;
+  proxy_username: string | null;
+  proxy_password: string | null;
+}
+
+export interface SecuritySettings {
+  session_ttl_minutes: number | null;
+  session_max_age_hours: number | null;
+  session_signing_key: string | null;
+  totp_issuer: string | null;
+  totp_drift_steps: number | null;
+  max_failed_logins: number | null;
+  lockout_minutes: number | null;
+  password_policy: PasswordPolicySettings | null;
+  password_hashing: PasswordHashingSettings | null;
+  anonymous_role: string | null;
+  roles: Record<string, string[]> | null;
+  sign_bridge_messages: boolean | null;
+}
+
+export interface PasswordPolicySettings {
+  min_length: number | null;
+  require_lowercase: boolean | null;
+  require_uppercase: boolean | null;
+  require_digit: boolean | null;
+  require_symbol: boolean | null;
+  reject_common: boolean | null;
+  min_score: number | null;
+}
+
+export interface PasswordHashingSettings {
+  algorithm: string | null;
+  argon2_memory_kib: number | null;
+  argon2_iterations: number | null;
+  argon2_parallelism: number | null;
+  bcrypt_cost: number | null;
+  scrypt_log_n: number | null;
+  scrypt_r: number | null;
+  scrypt_p: number | null;
+}
+
+export interface DevSettings {
+  dev_server_url: string | null;
+  cert_dir: string | null;
+  cert_hosts: string[] | null;
+}
+
+export interface FeatureSettings {
+  dark_mode: boolean | null;
+  show_tray_icon: boolean | null;
+}
+
+export interface LogEntry {
+  seq: number;
+  timestamp: string;
+  level: string;
+  module: string;
+  message: string;
+  correlation_id?: string;
+}
+
+export interface BundleSummary {
+  path: string;
+  files: string[];
+  bytes: number;
+}
+
+export interface LogStreamStatus {
+  following: boolean;
+  after: number | null;
+}
+
+export interface LogExportResult {
+  cancelled: boolean;
+  bundle: BundleSummary | null;
+}
+
+export interface TargetLevel {
+  target: string;
+  level: string;
+}
+
+export interface LoggingRuntimeConfig {
+  level: string;
+  targets: TargetLevel[];
+  file: string;
+  sinks: string[];
+}
+
+export interface ConfigAuditEntry {
+  id: number;
+  key: string;
+  old_value: unknown | null;
+  new_value: unknown | null;
+  source: string;
+  changed_at: string;
+}
+
+export interface AuditEntry {
+  id: number;
+  event: string;
+  user_id: number | null;
+  subject: string | null;
+  detail: string | null;
+  created_at: string;
+}
+
+export interface ApiKeyRecord {
+  id: number;
+  name: string;
+  prefix: string;
+  scopes: string[];
+  created_by: number | null;
+  created_at: string;
+  last_used_at: string | null;
+  revoked_at: string | null;
+}
+
+export interface IssuedApiKey {
+  key: string;
+  record: ApiKeyRecord;
+}
+
+export interface DirEntryInfo {
+  name: string;
+  path: string;
+  relative_path: string;
+  kind: 'file' | 'dir' | 'symlink';
+  size: number;
+  modified: number | null;
+}
+
+export interface FsListing {
+  path: string;
+  entries: DirEntryInfo[];
+  truncated: boolean;
+  roots: string[];
+}
+
+export interface NetworkDiagnostics {
+  local_ip: string | null;
+  public_ip: string | null;
+  targets: TargetStatus[];
+}
+
+export interface TargetStatus {
+  target: string;
+  addresses: string[];
+  reachable: boolean;
+  latency_ms: number | null;
+  error: string | null;
+}
+
+export interface SettingsUpdate {
+  config: AppConfig;
+  changed: string[];
+  path: string;
+}
+
+export interface PanicReport {
+  message: string;
+  location: string;
+  thread: string;
+  timestamp: string;
+  backtrace: string;
+  crash_file: string | null;
+}
+
+export interface ErrorStat {
+  handler: string;
+  code: string;
+  count: number;
+  last_seen: string;
+}
+
+export interface ErrorStatsReport {
+  window_minutes: number;
+  total: number;
+  top: ErrorStat[];
+}
+
+export interface CrashReportSummary {
+  id: string;
+  kind: string;
+  message: string;
+  timestamp: string;
+  app_version: string;
+  path: string;
+}
+
+export interface CrashReportSubmitResult {
+  id: string;
+  submitted: boolean;
+}
+
+export interface SessionInfo {
+  session_id: string;
+  user_id: number;
+  role: string;
+  created_at: number;
+  expires_at: number;
+  refreshable_until: number;
+}
+
+export interface SessionToken {
+  token: string;
+  session: SessionInfo;
+}
+
+export interface TwoFactorStatus {
+  enabled: boolean;
+  pending: boolean;
+  recovery_codes_left: number;
+}
+
+export interface TwoFactorSetup {
+  secret: string;
+  otpauth_uri: string;
+  recovery_codes: string[];
+}
+
+registerResponseEvent('get_users', 'db_response');
+registerResponseEvent('create_user', 'user_create_response');
+registerResponseEvent('update_user', 'user_update_response');
+registerResponseEvent('delete_user', 'user_delete_response');
+registerResponseEvent('get_system_info', 'sysinfo_response');
+registerResponseEvent('is_elevated', 'is_elevated_response');
+registerResponseEvent('negotiate', 'negotiate_response');
+registerResponseEvent('set_serialization_format', 'set_serialization_format_response');
+registerResponseEvent('serialization_stats', 'serialization_stats_response');
+registerResponseEvent('get_openapi_spec', 'get_openapi_spec_response');
+registerResponseEvent('event_subscribe', 'event_subscribe_response');
+registerResponseEvent('event_unsubscribe', 'event_unsubscribe_response');
+registerResponseEvent('di_services', 'di_services_response');
+registerResponseEvent('settings_get', 'settings_get_response');
+registerResponseEvent('settings_set', 'settings_set_response');
+registerResponseEvent('settings_reset', 'settings_reset_response');
+registerResponseEvent('settings_history', 'settings_history_response');
+registerResponseEvent('logs_tail', 'logs_tail_response');
+registerResponseEvent('logs_stream', 'logs_stream_response');
+registerResponseEvent('logs_export', 'logs_export_response');
+registerResponseEvent('logging_set_level', 'logging_set_level_response');
+registerResponseEvent('logging_get_config', 'logging_get_config_response');
+registerResponseEvent('error_codes', 'error_codes_response');
+registerResponseEvent('error_stats', 'error_stats_report');
+registerResponseEvent('crash_report_list', 'crash_report_list_response');
+registerResponseEvent('crash_report_submit', 'crash_report_submit_response');
+registerResponseEvent('session_info', 'session_info_response');
+registerResponseEvent('session_refresh', 'session_refresh_response');
+registerResponseEvent('auth_login', 'auth_login_response');
+registerResponseEvent('auth_logout', 'auth_logout_response');
+registerResponseEvent('auth_change_password', 'auth_change_password_response');
+registerResponseEvent('two_factor_status', 'two_factor_status_response');
+registerResponseEvent('two_factor_setup', 'two_factor_setup_response');
+registerResponseEvent('two_factor_enable', 'two_factor_enable_response');
+registerResponseEvent('two_factor_disable', 'two_factor_disable_response');
+registerResponseEvent('vault_set', 'vault_set_response');
+registerResponseEvent('vault_get', 'vault_get_response');
+registerResponseEvent('api_key_create', 'api_key_create_response');
+registerResponseEvent('api_key_list', 'api_key_list_response');
+registerResponseEvent('api_key_revoke', 'api_key_revoke_response');
+registerResponseEvent('security_audit', 'security_audit_response');
+registerResponseEvent('fs_list', 'fs_list_response');
+registerResponseEvent('network_diagnostics', 'network_diagnostics_response');
+
+export const backend = {
+  users: {
+    getAll: (): Promise<Result<User[]>> =>
+      callBackend<User[]>('get_users'),
+    create: (request: string): Promise<Result<number>> =>
+      callBackend<number>('create_user', request),
+    update: (request: string): Promise<Result<number>> =>
+      callBackend<number>('update_user', request),
+    delete: (request: string): Promise<Result<number>> =>
+      callBackend<number>('delete_user', request),
+  },
+  system: {
+    getInfo: (): Promise<Result<Record<string, unknown>>> =>
+      callBackend<Record<string, unknown>>('get_system_info'),
+    isElevated: (): Promise<Result<boolean>> =>
+      callBackend<boolean>('is_elevated'),
+  },
+  serialization: {
+    negotiate: (request: { formats: string[] }): Promise<Result<NegotiateResponse>> =>
+      callBackend<NegotiateResponse>('negotiate', JSON.stringify(request)),
+    setFormat: (request: { format: string }): Promise<Result<SetFormatResponse>> =>
+      callBackend<SetFormatResponse>('set_serialization_format', JSON.stringify(request)),
+    getStats: (): Promise<Result<SerializationStatsResponse>> =>
+      callBackend<SerializationStatsResponse>('serialization_stats'),
+  },
+  api: {
+    getOpenApiSpec: (): Promise<Result<Record<string, unknown>>> =>
+      callBackend<Record<string, unknown>>('get_openapi_spec'),
+  },
+  events: {
+    subscribe: (request: { topic: string }): Promise<Result<BusSubscription>> =>
+      callBackend<BusSubscription>('event_subscribe', JSON.stringify(request)),
+    unsubscribe: (request: { id: number }): Promise<Result<BusSubscription>> =>
+      callBackend<BusSubscription>('event_unsubscribe', JSON.stringify(request)),
+  },
+  devtools: {
+    diServices: (): Promise<Result<ServiceInfo[]>> =>
+      callBackend<ServiceInfo[]>('di_services'),
+  },
+  settings: {
+    get: (): Promise<Result<AppConfig>> =>
+      callBackend<AppConfig>('settings_get'),
+    set: (request: { key: string; value: unknown }): Promise<Result<SettingsUpdate>> =>
+      callBackend<SettingsUpdate>('settings_set', JSON.stringify(request)),
+    reset: (request: { key?: string }): Promise<Result<SettingsUpdate>> =>
+      callBackend<SettingsUpdate>('settings_reset', JSON.stringify(request)),
+    history: (request: { key?: string; limit?: number }): Promise<Result<ConfigAuditEntry[]>> =>
+      callBackend<ConfigAuditEntry[]>('settings_history', JSON.stringify(request)),
+  },
+  logs: {
+    tail: (request: { level?: string; module?: string; search?: string; correlation_id?: string; after?: number; limit?: number }): Promise<Result<LogEntry[]>> =>
+      callBackend<LogEntry[]>('logs_tail', JSON.stringify(request)),
+    stream: (request: { follow: boolean; level?: string; module?: string; search?: string; correlation_id?: string; after?: number }): Promise<Result<LogStreamStatus>> =>
+      callBackend<LogStreamStatus>('logs_stream', JSON.stringify(request)),
+    export: (request: { path?: string }): Promise<Result<LogExportResult>> =>
+      callBackend<LogExportResult>('logs_export', JSON.stringify(request)),
+    setLevel: (request: { level?: string; target?: string }): Promise<Result<LoggingRuntimeConfig>> =>
+      callBackend<LoggingRuntimeConfig>('logging_set_level', JSON.stringify(request)),
+    getConfig: (): Promise<Result<LoggingRuntimeConfig>> =>
+      callBackend<LoggingRuntimeConfig>('logging_get_config'),
+  },
+  errors: {
+    getCodes: (): Promise<Result<ErrorCodeInfo[]>> =>
+      callBackend<ErrorCodeInfo[]>('error_codes'),
+    getStats: (request: { window_minutes?: number; limit?: number; handler?: string }): Promise<Result<ErrorStatsReport>> =>
+      callBackend<ErrorStatsReport>('error_stats', JSON.stringify(request)),
+    listCrashReports: (): Promise<Result<CrashReportSummary[]>> =>
+      callBackend<CrashReportSummary[]>('crash_report_list'),
+    submitCrashReport: (request: { id: string; consent: boolean }): Promise<Result<CrashReportSubmitResult>> =>
+      callBackend<CrashReportSubmitResult>('crash_report_submit', JSON.stringify(request)),
+  },
+  session: {
+    info: (): Promise<Result<SessionInfo>> =>
+      callBackend<SessionInfo>('session_info'),
+    refresh: (): Promise<Result<SessionToken>> =>
+      callBackend<SessionToken>('session_refresh'),
+  },
+  auth: {
+    login: (request: { email: string; password: string; code?: string }): Promise<Result<SessionToken>> =>
+      callBackend<SessionToken>('auth_login', JSON.stringify(request)),
+    logout: (): Promise<Result<boolean>> =>
+      callBackend<boolean>('auth_logout'),
+    changePassword: (request: { user_id?: number; current_password?: string; new_password: string }): Promise<Result<number>> =>
+      callBackend<number>('auth_change_password', JSON.stringify(request)),
+    twoFactorStatus: (): Promise<Result<TwoFactorStatus>> =>
+      callBackend<TwoFactorStatus>('two_factor_status'),
+    setupTwoFactor: (): Promise<Result<TwoFactorSetup>> =>
+      callBackend<TwoFactorSetup>('two_factor_setup'),
+    enableTwoFactor: (request: { code: string }): Promise<Result<TwoFactorStatus>> =>
+      callBackend<TwoFactorStatus>('two_factor_enable', JSON.stringify(request)),
+    disableTwoFactor: (request: { code: string }): Promise<Result<TwoFactorStatus>> =>
+      callBackend<TwoFactorStatus>('two_factor_disable', JSON.stringify(request)),
+  },
+  vault: {
+    set: (request: { key: string; value: unknown }): Promise<Result<null>> =>
+      callBackend<null>('vault_set', JSON.stringify(request)),
+    get: (request: { key: string }): Promise<Result<unknown>> =>
+      callBackend<unknown>('vault_get', JSON.stringify(request)),
+  },
+  apiKeys: {
+    create: (request: { name: string; scopes: string[] }): Promise<Result<IssuedApiKey>> =>
+      callBackend<IssuedApiKey>('api_key_create', JSON.stringify(request)),
+    list: (): Promise<Result<ApiKeyRecord[]>> =>
+      callBackend<ApiKeyRecord[]>('api_key_list'),
+    revoke: (request: { id: number }): Promise<Result<null>> =>
+      callBackend<null>('api_key_revoke', JSON.stringify(request)),
+  },
+  security: {
+    audit: (request: { event?: string; user_id?: number; subject?: string; since?: string; until?: string; limit?: number }): Promise<Result<AuditEntry[]>> =>
+      callBackend<AuditEntry[]>('security_audit', JSON.stringify(request)),
+  },
+  fs: {
+    list: (request: { path?: string; recursive?: boolean; glob?: string }): Promise<Result<FsListing>> =>
+      callBackend<FsListing>('fs_list', JSON.stringify(request)),
+  },
+  network: {
+    diagnostics: (request: { targets?: string[]; timeout_ms?: number; public_ip?: boolean }): Promise<Result<NetworkDiagnostics>> =>
+      callBackend<NetworkDiagnostics>('network_diagnostics', JSON.stringify(request)),
+  },
+};
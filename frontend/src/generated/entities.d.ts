@@ -0,0 +1,56 @@
+// Auto-generated by build.rs from src/core/presentation/webui/api.toml
+// Do not edit by hand.
+
+import type { ErrorCode } from '../types/error.types';
+
+export interface User {
+  id: number;
+  name: string;
+  email: string;
+  role: string;
+  status: string;
+  created_at: string;
+}
+
+export interface Product {
+  id: number;
+  name: string;
+  description: string | null;
+  price: number;
+  category: string;
+  stock: number;
+}
+
+export interface SystemInfo {
+  os_name: string;
+  os_version: string;
+  hostname: string;
+  cpu_cores: number;
+  local_ip: string | null;
+  current_pid: number;
+}
+
+export interface AppEvent {
+  event_type: string;
+  payload: unknown;
+  timestamp: number;
+  source: string | null;
+  target: string | null;
+  correlation_id?: string;
+}
+
+export interface ErrorValue {
+  code: ErrorCode;
+  message: string;
+  details?: string;
+  field?: string;
+  cause?: string;
+  context?: Record<string, string>;
+  correlation_id?: string;
+}
+
+export interface ErrorResponse {
+  success: false;
+  data: null;
+  error: ErrorValue;
+}
@@ -0,0 +1,127 @@
+// Auto-generated by build.rs from ErrorCode in src/core/error.rs
+// Do not edit by hand.
+
+export enum ErrorCode {
+  // Database errors (1000-1999)
+  DbConnectionFailed = 'DB_CONNECTION_FAILED',
+  DbQueryFailed = 'DB_QUERY_FAILED',
+  DbConstraintViolation = 'DB_CONSTRAINT_VIOLATION',
+  DbNotFound = 'DB_NOT_FOUND',
+  DbAlreadyExists = 'DB_ALREADY_EXISTS',
+  DbBusy = 'DB_BUSY',
+
+  // Configuration errors (2000-2999)
+  ConfigNotFound = 'CONFIG_NOT_FOUND',
+  ConfigInvalid = 'CONFIG_INVALID',
+  ConfigMissingField = 'CONFIG_MISSING_FIELD',
+
+  // Serialization errors (3000-3999)
+  SerializationFailed = 'SERIALIZATION_FAILED',
+  DeserializationFailed = 'DESERIALIZATION_FAILED',
+  InvalidFormat = 'INVALID_FORMAT',
+  PayloadTooLarge = 'PAYLOAD_TOO_LARGE',
+
+  // Validation errors (4000-4999)
+  ValidationFailed = 'VALIDATION_FAILED',
+  MissingRequiredField = 'MISSING_REQUIRED_FIELD',
+  InvalidFieldValue = 'INVALID_FIELD_VALUE',
+
+  // Not found errors (5000-5999)
+  ResourceNotFound = 'RESOURCE_NOT_FOUND',
+  UserNotFound = 'USER_NOT_FOUND',
+  EntityNotFound = 'ENTITY_NOT_FOUND',
+
+  // System errors (6000-6999)
+  LockPoisoned = 'LOCK_POISONED',
+  Timeout = 'TIMEOUT',
+  ServiceUnavailable = 'SERVICE_UNAVAILABLE',
+  InternalError = 'INTERNAL_ERROR',
+
+  // Plugin errors (7000-7999)
+  Plugin = 'PLUGIN',
+
+  // Authentication errors (8000-8999)
+  Unauthorized = 'UNAUTHORIZED',
+  SessionExpired = 'SESSION_EXPIRED',
+  TwoFactorRequired = 'TWO_FACTOR_REQUIRED',
+  InvalidTwoFactorCode = 'INVALID_TWO_FACTOR_CODE',
+  Forbidden = 'FORBIDDEN',
+  InvalidCredentials = 'INVALID_CREDENTIALS',
+  AccountLocked = 'ACCOUNT_LOCKED',
+  PathNotAllowed = 'PATH_NOT_ALLOWED',
+
+  // Custom/unknown
+  Unknown = 'UNKNOWN',
+}
+
+export const ERROR_CODE_NUMBERS: Record<ErrorCode, number> = {
+  [ErrorCode.DbConnectionFailed]: 1000,
+  [ErrorCode.DbQueryFailed]: 1001,
+  [ErrorCode.DbConstraintViolation]: 1002,
+  [ErrorCode.DbNotFound]: 1003,
+  [ErrorCode.DbAlreadyExists]: 1004,
+  [ErrorCode.DbBusy]: 1005,
+  [ErrorCode.ConfigNotFound]: 2000,
+  [ErrorCode.ConfigInvalid]: 2001,
+  [ErrorCode.ConfigMissingField]: 2002,
+  [ErrorCode.SerializationFailed]: 3000,
+  [ErrorCode.DeserializationFailed]: 3001,
+  [ErrorCode.InvalidFormat]: 3002,
+  [ErrorCode.PayloadTooLarge]: 3003,
+  [ErrorCode.ValidationFailed]: 4000,
+  [ErrorCode.MissingRequiredField]: 4001,
+  [ErrorCode.InvalidFieldValue]: 4002,
+  [ErrorCode.ResourceNotFound]: 5000,
+  [ErrorCode.UserNotFound]: 5001,
+  [ErrorCode.EntityNotFound]: 5002,
+  [ErrorCode.LockPoisoned]: 6000,
+  [ErrorCode.Timeout]: 6001,
+  [ErrorCode.ServiceUnavailable]: 6002,
+  [ErrorCode.InternalError]: 6999,
+  [ErrorCode.Plugin]: 7000,
+  [ErrorCode.Unauthorized]: 8000,
+  [ErrorCode.SessionExpired]: 8001,
+  [ErrorCode.TwoFactorRequired]: 8002,
+  [ErrorCode.InvalidTwoFactorCode]: 8003,
+  [ErrorCode.Forbidden]: 8004,
+  [ErrorCode.InvalidCredentials]: 8005,
+  [ErrorCode.AccountLocked]: 8006,
+  [ErrorCode.PathNotAllowed]: 8007,
+  [ErrorCode.Unknown]: 9999,
+};
+
+export const ERROR_CODE_DESCRIPTIONS: Record<ErrorCode, string> = {
+  [ErrorCode.DbConnectionFailed]: 'The database could not be opened or reached',
+  [ErrorCode.DbQueryFailed]: 'A database query failed',
+  [ErrorCode.DbConstraintViolation]: 'The change breaks a database constraint',
+  [ErrorCode.DbNotFound]: 'No database row matches the given key',
+  [ErrorCode.DbAlreadyExists]: 'A database row with the same key already exists',
+  [ErrorCode.DbBusy]: 'The database is locked by another connection; try again',
+  [ErrorCode.ConfigNotFound]: 'The configuration file could not be found',
+  [ErrorCode.ConfigInvalid]: 'The configuration has an invalid value',
+  [ErrorCode.ConfigMissingField]: 'The configuration is missing a required value',
+  [ErrorCode.SerializationFailed]: 'A value could not be serialized',
+  [ErrorCode.DeserializationFailed]: 'A payload could not be read as the expected type',
+  [ErrorCode.InvalidFormat]: 'The data is not in a supported format',
+  [ErrorCode.PayloadTooLarge]: 'The payload is larger than the configured limit',
+  [ErrorCode.ValidationFailed]: 'The input did not pass validation',
+  [ErrorCode.MissingRequiredField]: 'A required field is missing',
+  [ErrorCode.InvalidFieldValue]: 'A field has a value that is not allowed',
+  [ErrorCode.ResourceNotFound]: 'The requested resource does not exist',
+  [ErrorCode.UserNotFound]: 'The user does not exist',
+  [ErrorCode.EntityNotFound]: 'The requested entity does not exist',
+  [ErrorCode.LockPoisoned]: 'Shared state was left inconsistent by an earlier panic',
+  [ErrorCode.Timeout]: 'The operation did not finish in time',
+  [ErrorCode.ServiceUnavailable]: 'A remote service could not be reached or failed',
+  [ErrorCode.InternalError]: 'An unexpected backend error occurred',
+  [ErrorCode.Plugin]: 'A plugin failed',
+  [ErrorCode.Unauthorized]: 'Sign in to continue',
+  [ErrorCode.SessionExpired]: 'The session has expired or was signed out; sign in again',
+  [ErrorCode.TwoFactorRequired]: 'Enter the code from your authenticator app',
+  [ErrorCode.InvalidTwoFactorCode]: 'The authentication code is wrong or was already used',
+  [ErrorCode.Forbidden]: 'Your role doesn\'t allow this action',
+  [ErrorCode.InvalidCredentials]: 'The email or password is wrong',
+  [ErrorCode.AccountLocked]: 'Too many failed sign-ins; try again later',
+  [ErrorCode.PathNotAllowed]: 'That path is outside the folders the app may use',
+  [ErrorCode.Unknown]: 'An unknown error occurred',
+};
@@ -7,6 +7,7 @@ import { errorInterceptor, setupGlobalErrorInterception } from './core/error-int
 import { GlobalErrorHandler } from './core/global-error.handler';
 import { GlobalErrorService } from './core/global-error.service';
 import { environment } from './environments/environment';
+import { negotiateSerialization } from './viewmodels/api-client.viewmodel';
 import { EventBusViewModel } from './viewmodels/event-bus.viewmodel';
 import {
   backend,
@@ -66,6 +67,14 @@ try {
       }
       eventBus.publish('app:ready', { timestamp: Date.now() });
       logger.info('Angular bootstrap completed');
+
+      negotiateSerialization().then(result => {
+        if (result.ok) {
+          logger.info('Serialization format negotiated', { format: result.value.format });
+        } else {
+          logger.warn('Serialization negotiation failed, using JSON', { error: result.error });
+        }
+      });
     })
     .catch(err => {
       logger.error('Angular bootstrap failed', {}, err);
@@ -1,10 +1,12 @@
 import { describe, expect, it } from 'bun:test';
 import {
+  describeErrorCode,
   ErrorCode,
   type ErrorValue,
   err,
   internalError,
   isErr,
+  isErrorCode,
   isOk,
   notFoundError,
   ok,
@@ -32,6 +34,13 @@ describe('ErrorCode', () => {
     expect(ErrorCode.ResourceNotFound).toBe('RESOURCE_NOT_FOUND');
     expect(ErrorCode.UserNotFound).toBe('USER_NOT_FOUND');
   });
+  it('should describe backend codes and reject unknown ones', () => {
+    expect(isErrorCode('DB_QUERY_FAILED')).toBe(true);
+    expect(isErrorCode('NOT_A_CODE')).toBe(false);
+    expect(describeErrorCode(ErrorCode.PayloadTooLarge)).toBe(
+      'The payload is larger than the configured limit'
+    );
+  });
 });
 
 describe('Result type', () => {
@@ -2,78 +2,36 @@
 // "Errors as Values" pattern for TypeScript frontend
 // Structured error types that mirror the Rust backend error model
 
+import type { ErrorResponse, ErrorValue } from '../generated/entities';
+import { ERROR_CODE_DESCRIPTIONS, ERROR_CODE_NUMBERS, ErrorCode } from '../generated/error-codes';
+
 /**
- * Error codes matching the Rust backend ErrorCode enum
+ * Error codes matching the Rust backend ErrorCode enum, generated with their
+ * descriptions from src/core/error.rs by build.rs.
  * These codes enable programmatic error handling across the frontend-backend boundary
  */
-export enum ErrorCode {
-  // Database errors (1000-1999)
-  DbConnectionFailed = 'DB_CONNECTION_FAILED',
-  DbQueryFailed = 'DB_QUERY_FAILED',
-  DbConstraintViolation = 'DB_CONSTRAINT_VIOLATION',
-  DbNotFound = 'DB_NOT_FOUND',
-  DbAlreadyExists = 'DB_ALREADY_EXISTS',
-
-  // Configuration errors (2000-2999)
-  ConfigNotFound = 'CONFIG_NOT_FOUND',
-  ConfigInvalid = 'CONFIG_INVALID',
-  ConfigMissingField = 'CONFIG_MISSING_FIELD',
-
-  // Serialization errors (3000-3999)
-  SerializationFailed = 'SERIALIZATION_FAILED',
-  DeserializationFailed = 'DESERIALIZATION_FAILED',
-  InvalidFormat = 'INVALID_FORMAT',
-
-  // Validation errors (4000-4999)
-  ValidationFailed = 'VALIDATION_FAILED',
-  MissingRequiredField = 'MISSING_REQUIRED_FIELD',
-  InvalidFieldValue = 'INVALID_FIELD_VALUE',
-
-  // Not found errors (5000-5999)
-  ResourceNotFound = 'RESOURCE_NOT_FOUND',
-  UserNotFound = 'USER_NOT_FOUND',
-  EntityNotFound = 'ENTITY_NOT_FOUND',
-
-  // System errors (6000-6999)
-  LockPoisoned = 'LOCK_POISONED',
-  InternalError = 'INTERNAL_ERROR',
-
-  // Plugin errors (7000-7999)
-  Plugin = 'PLUGIN',
-
-  // Custom/unknown
-  Unknown = 'UNKNOWN',
-}
+export { ERROR_CODE_DESCRIPTIONS, ERROR_CODE_NUMBERS, ErrorCode };
 
 /**
- * Structured error value matching Rust ErrorValue
- * This is the base error type that flows through the application
+ * Whether a code from the backend is one this build knows about
  */
-export interface ErrorValue {
-  /** Machine-readable error code */
-  code: ErrorCode;
-  /** Human-readable message */
-  message: string;
-  /** Optional detailed technical information */
-  details?: string;
-  /** Optional field that caused the error (for validation errors) */
-  field?: string;
-  /** Optional underlying cause (for error chains) */
-  cause?: string;
-  /** Optional context key-value pairs */
-  context?: Record<string, string>;
+export function isErrorCode(code: unknown): code is ErrorCode {
+  return typeof code === 'string' && code in ERROR_CODE_NUMBERS;
 }
 
 /**
- * API response envelope for backend errors
- * Matches the Rust to_response() output
+ * Human-readable description of an error code
  */
-export interface ErrorResponse {
-  success: false;
-  data: null;
-  error: ErrorValue;
+export function describeErrorCode(code: ErrorCode): string {
+  return ERROR_CODE_DESCRIPTIONS[code] ?? ERROR_CODE_DESCRIPTIONS[ErrorCode.Unknown];
 }
 
+/**
+ * Structured error value and failure envelope, generated from the Rust
+ * ErrorValue / ErrorResponse structs (see src/core/presentation/webui/api.toml)
+ */
+export type { ErrorResponse, ErrorValue };
+
 /**
  * API response envelope for success
  */
@@ -306,6 +264,9 @@ export function toUserMessage(error: ErrorValue): string {
   if (error.code === ErrorCode.InvalidFormat) {
     return error.message || 'The data format is invalid.';
   }
+  if (error.code === ErrorCode.PayloadTooLarge) {
+    return 'Too much data for a single request. Try loading it in smaller pages.';
+  }
 
   // For validation errors (without field)
   if (error.code === ErrorCode.ValidationFailed) {
@@ -15,10 +15,92 @@ import {
   type Result,
   toResult,
 } from '../types';
+import type { AppEvent, User } from '../generated/entities';
 import { getLogger } from './logger.viewmodel';
 
 const logger = getLogger('api-client');
 
+let sessionToken: string | null = null;
+
+/**
+ * Set the session token sent with every backend call, e.g. after login or
+ * `backend.session.refresh()`; null after logout
+ */
+export function setSessionToken(token: string | null): void {
+  sessionToken = token;
+}
+
+/**
+ * Add the session token to a call's arguments: as `session_token` in a JSON
+ * object argument, as the only argument of a call that has none, or after
+ * a plain string argument such as `delete_user:7`
+ */
+function withSessionToken(args: unknown[]): unknown[] {
+  if (sessionToken === null) {
+    return args;
+  }
+  if (args.length === 0) {
+    return [JSON.stringify({ session_token: sessionToken })];
+  }
+  const [first, ...rest] = args;
+  if (typeof first === 'string' && first.startsWith('{')) {
+    try {
+      const payload = JSON.parse(first) as Record<string, unknown>;
+      return [JSON.stringify({ ...payload, session_token: sessionToken }), ...rest];
+    } catch {
+      return args;
+    }
+  }
+  if (typeof first === 'string' && rest.length === 0) {
+    return [first, sessionToken];
+  }
+  return args;
+}
+
+let bridgeKey: Promise<CryptoKey> | null = null;
+
+/**
+ * HMAC key the backend injects as `window.__BRIDGE_KEY` on connect when
+ * `[security] sign_bridge_messages` is on; taken off window once imported
+ */
+function getBridgeKey(): Promise<CryptoKey> | null {
+  const host = window as unknown as { __BRIDGE_KEY?: string };
+  const hex = host.__BRIDGE_KEY;
+  if (typeof hex === 'string' && hex.length > 0) {
+    delete host.__BRIDGE_KEY;
+    const bytes = new Uint8Array((hex.match(/../g) ?? []).map(byte => parseInt(byte, 16)));
+    bridgeKey = crypto.subtle.importKey('raw', bytes, { name: 'HMAC', hash: 'SHA-256' }, false, [
+      'sign',
+    ]);
+  }
+  return bridgeKey;
+}
+
+function toHex(bytes: Uint8Array): string {
+  return Array.from(bytes, byte => byte.toString(16).padStart(2, '0')).join('');
+}
+
+/**
+ * Wrap a call's first argument with a nonce and an HMAC over
+ * "<function>\n<nonce>\n<argument>", so the backend can tell calls from this
+ * app apart from other scripts in the WebView. Unchanged without a key.
+ */
+async function withBridgeSignature(functionName: string, args: unknown[]): Promise<unknown[]> {
+  const key = getBridgeKey();
+  if (key === null) {
+    return args;
+  }
+  const [first, ...rest] = args;
+  const payload = first === undefined ? null : String(first);
+  const nonce = toHex(crypto.getRandomValues(new Uint8Array(16)));
+  const message = new TextEncoder().encode(`${functionName}\n${nonce}\n${payload ?? ''}`);
+  const signature = toHex(new Uint8Array(await crypto.subtle.sign('HMAC', await key, message)));
+  return [
+    JSON.stringify({ bridge_payload: payload, bridge_nonce: nonce, bridge_signature: signature }),
+    ...rest,
+  ];
+}
+
 /**
  * Call a backend function and return a typed Result
  *
@@ -28,15 +110,16 @@ const logger = getLogger('api-client');
  */
 export async function callBackend<T>(functionName: string, ...args: unknown[]): Promise<Result<T>> {
   logger.debug(`Calling backend: ${functionName}`, { args: JSON.stringify(args) });
+  const callArgs = await withBridgeSignature(functionName, withSessionToken(args));
 
   return new Promise(resolve => {
     // Set up one-time event listener for the response
     const responseEventName = getResponseEventName(functionName);
 
-    const handler = (event: CustomEvent<ApiResponse<T>>) => {
+    const handler = async (event: CustomEvent<ApiResponse<T>>) => {
       logger.debug(`Received response for ${functionName}`);
 
-      const response = event.detail;
+      const response = await decodeDetail<T>(event.detail);
 
       if (isError(response)) {
         logger.warn(`Backend error: ${response.error.code}`, { error: response.error });
@@ -83,7 +166,7 @@ export async function callBackend<T>(functionName: string, ...args: unknown[]):
       }
 
       // Call the backend function with provided arguments
-      backendFn(...args);
+      backendFn(...callArgs);
       logger.debug(`Backend call initiated: ${functionName}`);
     } catch (error) {
       clearTimeout(timeoutId);
@@ -101,39 +184,41 @@ export async function callBackend<T>(functionName: string, ...args: unknown[]):
   });
 }
 
+// Map function names to their response events
+const responseEventMap: Record<string, string> = {
+  get_users: 'db_response',
+  create_user: 'user_create_response',
+  update_user: 'user_update_response',
+  delete_user: 'user_delete_response',
+  get_db_stats: 'stats_response',
+};
+
+/**
+ * Register the response event for a backend function (used by the generated client)
+ */
+export function registerResponseEvent(functionName: string, eventName: string): void {
+  responseEventMap[functionName] = eventName;
+}
+
 /**
  * Get the expected response event name for a backend function
  */
 function getResponseEventName(functionName: string): string {
-  // Map function names to their response events
-  const eventMap: Record<string, string> = {
-    get_users: 'db_response',
-    create_user: 'user_create_response',
-    update_user: 'user_update_response',
-    delete_user: 'user_delete_response',
-    get_db_stats: 'stats_response',
-  };
-
-  return eventMap[functionName] || `${functionName}_response`;
+  return responseEventMap[functionName] || `${functionName}_response`;
 }
 
 /**
  * User API functions
  */
-export interface User {
-  id: number;
-  name: string;
-  email: string;
-  role: string;
-  status: string;
-  created_at: string;
-}
+export type { User };
 
 export interface UserCreatePayload {
   name: string;
   email: string;
   role?: string;
   status?: string;
+  /** Initial password; must pass the backend's password policy */
+  password?: string;
 }
 
 export interface UserUpdatePayload {
@@ -142,6 +227,13 @@ export interface UserUpdatePayload {
   email?: string;
   role?: string;
   status?: string;
+  /** New password; must pass the backend's password policy */
+  password?: string;
+}
+
+/** Trailing `:password` segment of a user element string, when given */
+function passwordSegment(password?: string): string {
+  return password ? `:${password}` : '';
 }
 
 /**
@@ -158,7 +250,7 @@ export async function getUsers(): Promise<Result<User[]>> {
  * @returns Result with the new user ID on success
  */
 export async function createUser(payload: UserCreatePayload): Promise<Result<number>> {
-  const elementName = `create_user:${payload.name}:${payload.email}:${payload.role ?? 'User'}:${payload.status ?? 'Active'}`;
+  const elementName = `create_user:${payload.name}:${payload.email}:${payload.role ?? 'User'}:${payload.status ?? 'Active'}${passwordSegment(payload.password)}`;
   return callBackend<number>('create_user', elementName);
 }
 
@@ -169,7 +261,7 @@ export async function createUser(payload: UserCreatePayload): Promise<Result<num
  * @returns Result with rows updated count
  */
 export async function updateUser(payload: UserUpdatePayload): Promise<Result<number>> {
-  const elementName = `update_user:${payload.id}:${payload.name ?? ''}:${payload.email ?? ''}:${payload.role ?? ''}:${payload.status ?? ''}`;
+  const elementName = `update_user:${payload.id}:${payload.name ?? ''}:${payload.email ?? ''}:${payload.role ?? ''}:${payload.status ?? ''}${passwordSegment(payload.password)}`;
   return callBackend<number>('update_user', elementName);
 }
 
@@ -184,6 +276,188 @@ export async function deleteUser(id: number): Promise<Result<number>> {
   return callBackend<number>('delete_user', elementName);
 }
 
+/**
+ * Message schema version this frontend was built against. Requests without a
+ * `schema_version` are treated as v1 by the backend, which migrates older
+ * payloads to its current schema.
+ */
+export const SCHEMA_VERSION = 1;
+
+/**
+ * Serialization formats the frontend bridge can decode
+ */
+export const SUPPORTED_SERIALIZATION_FORMATS: string[] = ['json'];
+
+/**
+ * Binary response envelope sent by the backend for non-JSON formats
+ */
+interface EncodedDetail {
+  format: string;
+  encoding: 'base64';
+  /** Set when the backend gzipped a payload above its compression threshold */
+  compression?: 'gzip';
+  payload: string;
+}
+
+type PayloadDecoder = (bytes: Uint8Array) => unknown;
+
+const payloadDecoders: Record<string, PayloadDecoder> = {
+  // Only used for compressed JSON; uncompressed JSON arrives as a plain object
+  json: bytes => JSON.parse(new TextDecoder().decode(bytes)),
+};
+
+/**
+ * Register a decoder for a binary format (e.g. a MessagePack or CBOR library)
+ * so the backend may be switched to it
+ */
+export function registerPayloadDecoder(format: string, decoder: PayloadDecoder): void {
+  payloadDecoders[format] = decoder;
+  if (!SUPPORTED_SERIALIZATION_FORMATS.includes(format)) {
+    SUPPORTED_SERIALIZATION_FORMATS.push(format);
+  }
+}
+
+function isEncodedDetail(detail: unknown): detail is EncodedDetail {
+  const value = detail as Partial<EncodedDetail> | null;
+  return (
+    typeof value === 'object' &&
+    value !== null &&
+    value.encoding === 'base64' &&
+    typeof value.format === 'string' &&
+    typeof value.payload === 'string'
+  );
+}
+
+async function gunzip(bytes: Uint8Array): Promise<Uint8Array> {
+  const stream = new Blob([bytes]).stream().pipeThrough(new DecompressionStream('gzip'));
+  return new Uint8Array(await new Response(stream).arrayBuffer());
+}
+
+/**
+ * Unwrap a binary-encoded or compressed response, turning unknown formats into
+ * an error response
+ */
+async function decodeDetail<T>(detail: unknown): Promise<ApiResponse<T>> {
+  const response = await decodePayload<T>(detail);
+  const version = (response as { schema_version?: number } | null)?.schema_version;
+  if (typeof version === 'number' && version > SCHEMA_VERSION) {
+    logger.warn(`Backend schema v${version} is newer than frontend v${SCHEMA_VERSION}; rebuild the frontend`);
+  }
+  return response;
+}
+
+async function decodePayload<T>(detail: unknown): Promise<ApiResponse<T>> {
+  if (!isEncodedDetail(detail)) {
+    return detail as ApiResponse<T>;
+  }
+
+  const decoder = payloadDecoders[detail.format];
+  if (!decoder) {
+    logger.error(`No decoder registered for format: ${detail.format}`);
+    return {
+      success: false,
+      error: {
+        code: ErrorCode.InternalError,
+        message: `Unsupported response format: ${detail.format}`,
+        details: 'Register a decoder with registerPayloadDecoder()',
+      },
+    } as ApiResponse<T>;
+  }
+
+  let bytes = Uint8Array.from(atob(detail.payload), c => c.charCodeAt(0));
+  if (detail.compression === 'gzip') {
+    bytes = await gunzip(bytes);
+  }
+  return decoder(bytes) as ApiResponse<T>;
+}
+
+/**
+ * Listen for a backend response event and receive its `data` on success.
+ * Error responses are logged and not passed to the listener.
+ */
+export function onBackendEvent<T>(eventName: string, listener: (data: T) => void): void {
+  window.addEventListener(eventName, async (event: Event) => {
+    const response = await decodeDetail<T>((event as CustomEvent).detail);
+    if (isSuccess(response)) {
+      listener(response.data);
+    } else if (isError(response)) {
+      logger.warn(`Backend error on ${eventName}: ${response.error.code}`, { error: response.error });
+    }
+  });
+}
+
+/**
+ * Listen for a backend EventBus topic forwarded by the event bridge.
+ * The topic must be listed in `[event_bus] bridge_topics` of the app config,
+ * or this window must subscribe to it with `backend.events.subscribe({ topic })`.
+ */
+export function onBusEvent(topic: string, listener: (event: AppEvent) => void): void {
+  onBackendEvent<AppEvent>(`bus:${topic}`, listener);
+}
+
+export interface NegotiatedFormat {
+  format: string;
+  preferred: string;
+  supported: string[];
+}
+
+let activeSerializationFormat = 'json';
+
+/**
+ * Get the serialization format agreed with the backend
+ */
+export function getSerializationFormat(): string {
+  return activeSerializationFormat;
+}
+
+/**
+ * Advertise decodable formats to the backend and switch to the negotiated one
+ *
+ * @returns Result with the format selected by the backend
+ */
+export async function negotiateSerialization(): Promise<Result<NegotiatedFormat>> {
+  const result = await callBackend<NegotiatedFormat>(
+    'negotiate',
+    JSON.stringify({ formats: SUPPORTED_SERIALIZATION_FORMATS })
+  );
+  if (result.ok && SUPPORTED_SERIALIZATION_FORMATS.includes(result.value.format)) {
+    activeSerializationFormat = result.value.format;
+  }
+  return result;
+}
+
+export interface SetFormatResponse {
+  format: string;
+  previous: string;
+}
+
+/**
+ * Switch the backend response format at runtime (e.g. for benchmarking)
+ *
+ * @param format - json, messagepack or cbor; needs a registered decoder unless json
+ * @returns Result with the new and previous formats
+ */
+export async function setSerializationFormat(format: string): Promise<Result<SetFormatResponse>> {
+  if (!SUPPORTED_SERIALIZATION_FORMATS.includes(format)) {
+    return {
+      ok: false,
+      error: {
+        code: ErrorCode.InternalError,
+        message: `No decoder registered for format: ${format}`,
+      } as ErrorValue,
+    } as Result<SetFormatResponse, ErrorValue>;
+  }
+
+  const result = await callBackend<SetFormatResponse>(
+    'set_serialization_format',
+    JSON.stringify({ format })
+  );
+  if (result.ok) {
+    activeSerializationFormat = result.value.format;
+  }
+  return result;
+}
+
 /**
  * Example usage with different error handling strategies:
  *
@@ -3,6 +3,7 @@
 
 import { computed, signal } from '@angular/core';
 import { errorInterceptor } from '../core/error-interceptor';
+import { onBackendEvent } from './api-client.viewmodel';
 import { getLogger } from './logger.viewmodel';
 
 const logger = getLogger('error.dashboard');
@@ -67,21 +68,17 @@ class ErrorDashboardViewModel {
 
   private setupErrorEventListeners(): void {
     // Listen for backend error stats response
-    window.addEventListener('error_stats_response', (event: Event) => {
-      const customEvent = event as CustomEvent;
-      const stats = customEvent.detail as ErrorStats;
+    onBackendEvent<ErrorStats>('error_stats_response', stats => {
       this.updateStats(stats);
     });
 
     // Listen for recent errors response
-    window.addEventListener('recent_errors_response', (event: Event) => {
-      const customEvent = event as CustomEvent;
-      const { errors } = customEvent.detail as { errors: BackendErrorEntry[] };
+    onBackendEvent<{ errors: BackendErrorEntry[] }>('recent_errors_response', ({ errors }) => {
       this.updateRecentErrors(errors);
     });
 
     // Listen for error history cleared
-    window.addEventListener('error_history_cleared', () => {
+    onBackendEvent('error_history_cleared', () => {
       this.clearLocalState();
     });
   }
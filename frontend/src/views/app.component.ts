@@ -2,7 +2,10 @@ import { CommonModule } from '@angular/common';
 import { Component, computed, inject, type OnDestroy, type OnInit, signal } from '@angular/core';
 import { GlobalErrorService } from '../core/global-error.service';
 import { type WinBoxInstance, WinBoxService } from '../core/winbox.service';
+import { backend, type PanicReport } from '../generated/backend-client';
 import { type BottomPanelTab, type Card, TECH_CARDS, type WindowEntry } from '../models';
+import { ErrorCode } from '../types';
+import { onBackendEvent } from '../viewmodels/api-client.viewmodel';
 import { EventBusViewModel } from '../viewmodels/event-bus.viewmodel';
 import { getLogger } from '../viewmodels/logger.viewmodel';
 import { WindowStateViewModel } from '../viewmodels/window-state.viewmodel';
@@ -186,6 +189,22 @@ export class AppComponent implements OnInit, OnDestroy {
   ngOnInit(): void {
     this.windowState.init();
     this.initWebSocketMonitor();
+    onBackendEvent<PanicReport>('backend_panic', report => {
+      this.globalErrorService.report(
+        {
+          code: ErrorCode.InternalError,
+          message: report.message,
+          details: report.backtrace,
+          context: {
+            location: report.location,
+            thread: report.thread,
+            ...(report.crash_file ? { crash_file: report.crash_file } : {}),
+          },
+        },
+        { source: 'backend', title: 'The backend crashed' }
+      );
+    });
+    void this.offerCrashReports();
     this.appReadyUnsubscribe = this.eventBus.subscribe(
       'app:ready',
       (payload: unknown) => {
@@ -233,6 +252,26 @@ export class AppComponent implements OnInit, OnDestroy {
     this.logger.info('App component initialized', { cardsCount: this.cards.length });
   }
 
+  /** Ask once per crash report from an earlier run whether to send it */
+  private async offerCrashReports(): Promise<void> {
+    const result = await backend.errors.listCrashReports();
+    if (!result.ok) {
+      this.logger.warn('Could not list crash reports', { error: result.error });
+      return;
+    }
+    for (const report of result.value) {
+      const consent = confirm(
+        `The app crashed on ${new Date(report.timestamp).toLocaleString()}:\n${report.message}\n\n` +
+          'Send the crash report (version, OS, recent log lines, backtrace and plugin list) to the developers? ' +
+          'Cancel deletes it without sending.'
+      );
+      const answer = await backend.errors.submitCrashReport({ id: report.id, consent });
+      if (!answer.ok) {
+        this.logger.error('Crash report submission failed', { id: report.id, error: answer.error });
+      }
+    }
+  }
+
   ngOnDestroy(): void {
     this.appReadyUnsubscribe?.();
     if (typeof window !== 'undefined' && this.resizeHandler) {
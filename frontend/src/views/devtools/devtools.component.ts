@@ -6,6 +6,8 @@ import { CommonModule } from '@angular/common';
 import { ErrorDashboardVM } from '../../viewmodels/error-dashboard.viewmodel';
 import { errorInterceptor } from '../../core/error-interceptor';
 import { EventBusViewModel } from '../../viewmodels/event-bus.viewmodel';
+import { onBackendEvent } from '../../viewmodels/api-client.viewmodel';
+import { backend, type ErrorStat, type LogEntry } from '../../generated/backend-client';
 import { getLogger } from '../../viewmodels/logger.viewmodel';
 
 const logger = getLogger('devtools');
@@ -31,6 +33,27 @@ export interface BackendStats {
   uptime?: number;
 }
 
+export interface BackendEventTopicStats {
+  event_type: string;
+  listener_count: number;
+  pending: number;
+  dropped: number;
+  published: number;
+  handled: number;
+  failed: number;
+  avg_handler_ms: number;
+  max_handler_ms: number;
+}
+
+export interface BackendEventBusStats {
+  total_listeners: number;
+  total_published: number;
+  total_dropped: number;
+  total_filtered: number;
+  events_per_second: number;
+  event_types: BackendEventTopicStats[];
+}
+
 // Frontend stats interfaces
 export interface FrontendStats {
   memory?: {
@@ -141,6 +164,20 @@ export interface DevLogEntry {
               </div>
             </div>
 
+            <div class="panel-section">
+              <h4>🔥 Top Errors (Last Hour)</h4>
+              <div class="breakdown-list">
+                @for (stat of topErrors; track stat.handler + stat.code) {
+                  <div class="breakdown-item">
+                    <span class="breakdown-item__label">{{ stat.handler }} · {{ stat.code }}</span>
+                    <span class="breakdown-item__value">{{ stat.count }}</span>
+                  </div>
+                } @empty {
+                  <div class="empty-state">No handler errors in the last hour</div>
+                }
+              </div>
+            </div>
+
             <div class="panel-section">
               <h4>🔌 WebUI Bindings</h4>
               <div class="bindings-list">
@@ -154,17 +191,43 @@ export interface DevLogEntry {
             </div>
 
             <div class="panel-section">
-              <h4>📜 Backend Logs (Last 20)</h4>
+              <h4>📜 Backend Logs</h4>
+              <div class="log-filters">
+                <select [value]="logLevel" (change)="setLogLevel($any($event.target).value)">
+                  @for (level of logLevels; track level) {
+                    <option [value]="level">{{ level }}+</option>
+                  }
+                </select>
+                <input type="search" placeholder="Module prefix" [value]="logModule"
+                       (change)="setLogModule($any($event.target).value)" />
+                <input type="search" placeholder="Search messages" [value]="logSearch"
+                       (change)="setLogSearch($any($event.target).value)" />
+                @if (logCall) {
+                  <button class="log-call-filter" title="Show every call" (click)="setLogCall(undefined)">
+                    call {{ logCall.slice(0, 8) }} ✕
+                  </button>
+                }
+                <label class="log-follow">
+                  <input type="checkbox" [checked]="followLogs" (change)="toggleFollow()" />
+                  Follow
+                </label>
+              </div>
               <div class="logs-container">
-                @for (log of backendLogs; track log.timestamp) {
+                @for (log of backendLogs; track log.seq) {
                   <div class="log-entry" [class]="'log-entry--' + log.level">
                     <span class="log-entry__time">{{ formatTime(log.timestamp) }}</span>
                     <span class="log-entry__level">{{ log.level }}</span>
-                    <span class="log-entry__source">{{ log.source }}</span>
-                    <span class="log-entry__message">{{ log.message }}</span>
+                    <span class="log-entry__source" [title]="log.module">{{ log.module }}</span>
+                    <span class="log-entry__message" [title]="log.message">
+                      @if (log.correlation_id) {
+                        <button class="log-entry__call" title="Show only this call's lines"
+                                (click)="setLogCall(log.correlation_id)">{{ log.correlation_id.slice(0, 8) }}</button>
+                      }
+                      {{ log.message }}
+                    </span>
                   </div>
                 } @empty {
-                  <div class="empty-state">No backend logs available</div>
+                  <div class="empty-state">No backend logs match</div>
                 }
               </div>
             </div>
@@ -214,6 +277,39 @@ export interface DevLogEntry {
                   <span class="event-stat__value">{{ eventBusStats.enabled ? '✓' : '✗' }}</span>
                 </div>
               </div>
+              <h5>Backend Event Bus</h5>
+              <div class="event-stats">
+                <div class="event-stat">
+                  <span class="event-stat__label">Published:</span>
+                  <span class="event-stat__value">{{ backendEventBusStats.total_published }}</span>
+                </div>
+                <div class="event-stat">
+                  <span class="event-stat__label">Events/s:</span>
+                  <span class="event-stat__value">{{ backendEventBusStats.events_per_second.toFixed(2) }}</span>
+                </div>
+                <div class="event-stat">
+                  <span class="event-stat__label">Dropped:</span>
+                  <span class="event-stat__value">{{ backendEventBusStats.total_dropped }}</span>
+                </div>
+                <div class="event-stat">
+                  <span class="event-stat__label">Filtered:</span>
+                  <span class="event-stat__value">{{ backendEventBusStats.total_filtered }}</span>
+                </div>
+              </div>
+              <div class="events-list">
+                @for (topic of backendEventBusStats.event_types; track topic.event_type) {
+                  <div class="event-item">
+                    <span class="event-item__name">{{ topic.event_type }}</span>
+                    <span class="event-item__payload">
+                      {{ topic.published }} published · {{ topic.listener_count }} subscribers ·
+                      {{ topic.avg_handler_ms.toFixed(2) }} ms avg / {{ topic.max_handler_ms.toFixed(2) }} ms max ·
+                      {{ topic.dropped }} dropped · {{ topic.failed }} failed
+                    </span>
+                  </div>
+                } @empty {
+                  <div class="empty-state">No backend events published</div>
+                }
+              </div>
               <h5>Recent Events</h5>
               <div class="events-list">
                 @for (event of recentEvents; track event.id) {
@@ -356,6 +452,9 @@ export interface DevLogEntry {
                 <button type="button" class="action-btn" (click)="exportState()">
                   📤 Export State
                 </button>
+                <button type="button" class="action-btn" (click)="exportLogs()">
+                  🧾 Export Logs
+                </button>
                 <button type="button" class="action-btn" (click)="importState()">
                   📥 Import State
                 </button>
@@ -621,10 +720,55 @@ export interface DevLogEntry {
       border-left: 2px solid #6c757d;
     }
 
+    .log-filters {
+      display: flex;
+      gap: 6px;
+      margin-bottom: 8px;
+      font-size: 11px;
+    }
+
+    .log-filters select,
+    .log-filters input[type='search'] {
+      background: #1e1e1e;
+      color: #d4d4d4;
+      border: 1px solid #3c3c3c;
+      border-radius: 4px;
+      padding: 2px 6px;
+      font-size: 11px;
+    }
+
+    .log-filters input[type='search'] {
+      flex: 1;
+      min-width: 0;
+    }
+
+    .log-follow {
+      display: flex;
+      align-items: center;
+      gap: 4px;
+      color: #858585;
+    }
+
     .log-entry__time {
       color: #858585;
     }
 
+    .log-entry__call,
+    .log-call-filter {
+      background: #2d2d30;
+      color: #9cdcfe;
+      border: 1px solid #3c3c3c;
+      border-radius: 4px;
+      padding: 0 4px;
+      font-family: monospace;
+      font-size: 10px;
+      cursor: pointer;
+    }
+
+    .log-entry__call {
+      margin-right: 6px;
+    }
+
     .log-entry__level {
       text-transform: uppercase;
       font-weight: bold;
@@ -648,6 +792,9 @@ export interface DevLogEntry {
 
     .log-entry__source {
       color: #4ec9b0;
+      overflow: hidden;
+      text-overflow: ellipsis;
+      white-space: nowrap;
     }
 
     .log-entry__message {
@@ -918,6 +1065,8 @@ export interface DevLogEntry {
   `],
 })
 export class DevtoolsComponent implements OnInit, OnDestroy {
+  private static readonly MAX_BACKEND_LOGS = 200;
+
   activeTab = signal<'backend' | 'frontend' | 'events' | 'environment' | 'actions'>('backend');
   
   tabs = [
@@ -930,7 +1079,15 @@ export class DevtoolsComponent implements OnInit, OnDestroy {
 
   // Backend stats
   backendStats: BackendStats = {};
-  backendLogs: DevLogEntry[] = [];
+  topErrors: ErrorStat[] = [];
+  backendLogs: LogEntry[] = [];
+  logLevels = ['trace', 'debug', 'info', 'warn', 'error'];
+  logLevel = 'info';
+  logModule = '';
+  logSearch = '';
+  /** Correlation id of the frontend call whose lines are shown, if any */
+  logCall?: string;
+  followLogs = false;
   webuiBindings: string[] = [];
 
   // Frontend stats
@@ -942,6 +1099,14 @@ export class DevtoolsComponent implements OnInit, OnDestroy {
   frontendLogs: DevLogEntry[] = [];
   recentEvents: Array<{ id: number; name: string; payload: unknown; timestamp: number }> = [];
   eventBusStats = { listeners: 0, historySize: 0, enabled: true };
+  backendEventBusStats: BackendEventBusStats = {
+    total_listeners: 0,
+    total_published: 0,
+    total_dropped: 0,
+    total_filtered: 0,
+    events_per_second: 0,
+    event_types: [],
+  };
   errorStats = { total: 0, criticalCount: 0, bySource: new Map(), byCode: new Map() };
   performanceStats: { memory?: { jsHeapSize?: number; usedJSHeapSize?: number } } = {};
 
@@ -980,6 +1145,9 @@ export class DevtoolsComponent implements OnInit, OnDestroy {
     if (this.refreshInterval) {
       clearInterval(this.refreshInterval);
     }
+    if (this.followLogs) {
+      void backend.logs.stream({ follow: false });
+    }
   }
 
   setActiveTab(tabId: 'backend' | 'frontend' | 'events' | 'environment' | 'actions'): void {
@@ -999,20 +1167,21 @@ export class DevtoolsComponent implements OnInit, OnDestroy {
   }
 
   private setupEventListeners(): void {
+    onBackendEvent<{ entries: LogEntry[] }>('logs_stream_event', ({ entries }) => {
+      this.backendLogs = [...this.backendLogs, ...entries].slice(-DevtoolsComponent.MAX_BACKEND_LOGS);
+    });
+
     // Listen for backend stats
-    window.addEventListener('backend_stats_response', (event: Event) => {
-      const customEvent = event as CustomEvent;
-      this.backendStats = customEvent.detail as BackendStats;
+    onBackendEvent<BackendStats>('backend_stats_response', stats => {
+      this.backendStats = stats;
     });
 
     // Listen for DB pool stats
-    window.addEventListener('db_pool_stats_response', (event: Event) => {
-      const customEvent = event as CustomEvent;
-      const dbStats = customEvent.detail as {
-        connections: number;
-        idle_connections: number;
-        utilization: number;
-      };
+    onBackendEvent<{
+      connections: number;
+      idle_connections: number;
+      utilization: number;
+    }>('db_pool_stats_response', dbStats => {
       this.backendStats.database = {
         connections: dbStats.connections,
         idle_connections: dbStats.idle_connections,
@@ -1020,15 +1189,18 @@ export class DevtoolsComponent implements OnInit, OnDestroy {
       };
     });
 
+    // Listen for backend event bus metrics
+    onBackendEvent<BackendEventBusStats>('event_bus_stats_response', stats => {
+      this.backendEventBusStats = stats;
+    });
+
     // Listen for error stats
-    window.addEventListener('error_stats_response', (event: Event) => {
-      const customEvent = event as CustomEvent;
-      const errorStats = customEvent.detail as {
-        total: number;
-        errors: number;
-        warnings: number;
-        critical: number;
-      };
+    onBackendEvent<{
+      total: number;
+      errors: number;
+      warnings: number;
+      critical: number;
+    }>('error_stats_response', errorStats => {
       this.backendStats.errors = errorStats;
     });
   }
@@ -1051,14 +1223,31 @@ export class DevtoolsComponent implements OnInit, OnDestroy {
       win.get_error_stats('error_stats');
     }
 
+    // Most frequent handler errors of the last hour
+    void backend.errors.getStats({ window_minutes: 60, limit: 10 }).then(result => {
+      if (result.ok) {
+        this.topErrors = result.value.top;
+      }
+    });
+
+    // Request backend event bus metrics
+    if (typeof win.event_bus_stats === 'function') {
+      win.event_bus_stats();
+    }
+
     // Get WebUI bindings (static list)
     this.webuiBindings = [
       'open_folder', 'organize_images', 'increment_counter', 'reset_counter',
       'get_users', 'create_user', 'update_user', 'delete_user',
-      'get_system_info', 'log_message', 'get_backend_logs',
-      'event:publish', 'event:history', 'event:stats', 'event:clear_history',
+      'get_system_info', 'is_elevated',
+      'log_message', 'frontend_log', 'get_backend_logs', 'logs_tail', 'logs_stream',
+      'event:publish', 'event:history', 'event:stats', 'event_bus_stats', 'event:clear_history',
       'window_state_change', 'get_error_stats', 'get_recent_errors',
-      'clear_error_history', 'get_db_pool_stats'
+      'clear_error_history', 'error_stats', 'get_db_pool_stats',
+      'session_info', 'session_refresh', 'auth_login', 'auth_logout', 'auth_change_password',
+      'two_factor_status', 'two_factor_setup', 'two_factor_enable', 'two_factor_disable',
+      'vault_set', 'vault_get', 'api_key_create', 'api_key_list', 'api_key_revoke',
+      'security_audit', 'fs_list', 'network_diagnostics'
     ];
   }
 
@@ -1103,10 +1292,61 @@ export class DevtoolsComponent implements OnInit, OnDestroy {
       this.frontendLogs = logs;
     }
 
-    // Request backend logs
-    const win = window as unknown as Record<string, unknown>;
-    if (typeof win.get_backend_logs === 'function') {
-      win.get_backend_logs('backend_logs:20');
+    // While following, new backend logs arrive as logs_stream_event
+    if (!this.followLogs) {
+      void this.loadBackendLogs();
+    }
+  }
+
+  setLogLevel(level: string): void {
+    this.logLevel = level;
+    void this.reloadBackendLogs();
+  }
+
+  setLogModule(module: string): void {
+    this.logModule = module.trim();
+    void this.reloadBackendLogs();
+  }
+
+  setLogSearch(search: string): void {
+    this.logSearch = search.trim();
+    void this.reloadBackendLogs();
+  }
+
+  setLogCall(correlationId: string | undefined): void {
+    this.logCall = correlationId;
+    void this.reloadBackendLogs();
+  }
+
+  toggleFollow(): void {
+    this.followLogs = !this.followLogs;
+    void this.reloadBackendLogs();
+  }
+
+  private logFilter(): { level: string; module?: string; search?: string; correlation_id?: string } {
+    return {
+      level: this.logLevel,
+      module: this.logModule || undefined,
+      search: this.logSearch || undefined,
+      correlation_id: this.logCall,
+    };
+  }
+
+  private async loadBackendLogs(): Promise<void> {
+    const result = await backend.logs.tail({ ...this.logFilter(), limit: DevtoolsComponent.MAX_BACKEND_LOGS });
+    if (result.ok) {
+      this.backendLogs = result.value;
+    }
+  }
+
+  /** Reload with the current filters, then follow from the last record shown */
+  private async reloadBackendLogs(): Promise<void> {
+    await this.loadBackendLogs();
+    const after = this.backendLogs.at(-1)?.seq ?? 0;
+    if (this.followLogs) {
+      await backend.logs.stream({ follow: true, ...this.logFilter(), after });
+    } else {
+      await backend.logs.stream({ follow: false });
     }
   }
 
@@ -1183,6 +1423,17 @@ export class DevtoolsComponent implements OnInit, OnDestroy {
     URL.revokeObjectURL(url);
   }
 
+  /** Save a support bundle (logs, redacted config, system info) to attach to bug reports */
+  async exportLogs(): Promise<void> {
+    const result = await backend.logs.export({});
+    if (!result.ok) {
+      logger.error('Log export failed', { error: result.error });
+      alert(`Log export failed: ${result.error.message}`);
+    } else if (result.value.bundle) {
+      alert(`Support bundle saved to ${result.value.bundle.path}`);
+    }
+  }
+
   importState(): void {
     alert('Import functionality would open file picker');
   }
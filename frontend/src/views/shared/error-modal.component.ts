@@ -405,6 +405,7 @@ export class ErrorModalComponent {
       case ErrorCode.SerializationFailed:
       case ErrorCode.DeserializationFailed:
       case ErrorCode.InvalidFormat:
+      case ErrorCode.PayloadTooLarge:
         return '📦';
       case ErrorCode.InternalError:
       case ErrorCode.LockPoisoned:
@@ -0,0 +1,154 @@
+// macros/src/lib.rs
+// Derive macros for the application crate
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Field, Fields, GenericArgument, LitStr, PathArguments,
+    Type,
+};
+
+/// Implement `di::Injectable` by resolving every field from the container.
+///
+/// - `Arc<T>` resolves the shared `T`, whether `T` or `Arc<T>` was registered
+/// - `Arc<dyn Trait>` resolves the trait registration
+/// - any other type is resolved and cloned
+/// - `#[inject(name = "cache")]` resolves a named registration
+/// - `#[inject(default)]` uses `Default::default()` instead of the container
+///
+/// ```ignore
+/// #[derive(Injectable)]
+/// struct UserHandler {
+///     db: Arc<Database>,
+///     users: Arc<dyn UserRepository>,
+///     #[inject(name = "cache")]
+///     cache: Arc<Database>,
+///     config: AppConfig,
+/// }
+///
+/// let handler = UserHandler::inject(di::get_container())?;
+/// ```
+#[proc_macro_derive(Injectable, attributes(inject))]
+pub fn derive_injectable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Injectable can only be derived for structs",
+        ));
+    };
+    let body = match &data.fields {
+        Fields::Named(fields) => {
+            let fields = fields
+                .named
+                .iter()
+                .map(|field| {
+                    let ident = &field.ident;
+                    let value = resolve_field(field)?;
+                    Ok(quote! { #ident: #value })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote! { Self { #(#fields,)* } }
+        }
+        Fields::Unnamed(fields) => {
+            let fields = fields
+                .unnamed
+                .iter()
+                .map(resolve_field)
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote! { Self(#(#fields,)*) }
+        }
+        Fields::Unit => quote! { Self },
+    };
+
+    Ok(quote! {
+        impl #impl_generics crate::core::infrastructure::di::Injectable for #name #ty_generics #where_clause {
+            fn inject(
+                container: &crate::core::infrastructure::di::Container,
+            ) -> crate::core::error::AppResult<Self> {
+                Ok(#body)
+            }
+        }
+    })
+}
+
+/// How a field asked to be resolved with `#[inject(...)]`
+#[derive(Default)]
+struct InjectOptions {
+    name: Option<LitStr>,
+    default: bool,
+}
+
+fn inject_options(field: &Field) -> syn::Result<InjectOptions> {
+    let mut options = InjectOptions::default();
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("inject"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                options.name = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                options.default = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `name = \"...\"` or `default`"))
+            }
+        })?;
+    }
+    if options.default && options.name.is_some() {
+        return Err(syn::Error::new_spanned(
+            field,
+            "`default` and `name` can't be combined",
+        ));
+    }
+    Ok(options)
+}
+
+/// `T` for a field typed `Arc<T>`
+fn arc_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Arc" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
+fn resolve_field(field: &Field) -> syn::Result<TokenStream2> {
+    let options = inject_options(field)?;
+    if options.default {
+        return Ok(quote! { ::core::default::Default::default() });
+    }
+
+    let ty = &field.ty;
+    let shared = arc_inner(ty).filter(|inner| !matches!(inner, Type::TraitObject(_)));
+    Ok(match (options.name, shared) {
+        (Some(name), Some(inner)) => quote! { container.get_named::<#inner>(#name)? },
+        (Some(name), None) => {
+            quote! { ::core::clone::Clone::clone(&*container.get_named::<#ty>(#name)?) }
+        }
+        (None, Some(inner)) => quote! { container.resolve_shared::<#inner>()? },
+        (None, None) => quote! { container.resolve::<#ty>()? },
+    })
+}
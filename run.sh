@@ -192,6 +192,22 @@ run_app() {
     fi
 }
 
+# Run against the rspack dev server (HMR) instead of a dist/ build
+run_dev() {
+    local dev_url="${APP_DEV_SERVER_URL:-http://localhost:4200}"
+
+    print_step "Starting frontend dev server..."
+    (cd frontend && bun run dev) &
+    local dev_pid=$!
+    trap "kill $dev_pid 2>/dev/null" EXIT
+
+    build_rust
+    post_build
+
+    print_status "Loading UI from $dev_url"
+    APP_DEV_SERVER_URL="$dev_url" run_app
+}
+
 # Clean all build artifacts
 clean_all() {
     print_step "Cleaning all build artifacts..."
@@ -240,6 +256,7 @@ show_help() {
     echo "  --build-rust     Build Rust only"
     echo "  --release        Build release version"
     echo "  --run            Run the application (requires build)"
+    echo "  --dev            Run against the frontend dev server with HMR"
     echo "  --clean          Clean all build artifacts"
     echo "  --rebuild        Clean and rebuild everything"
     echo "  --help, -h       Show this help message"
@@ -279,6 +296,11 @@ main() {
         --run)
             run_app
             ;;
+        --dev)
+            check_prerequisites
+            install_frontend_deps
+            run_dev
+            ;;
         --clean)
             clean_all
             ;;
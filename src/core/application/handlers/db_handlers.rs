@@ -50,10 +50,10 @@ pub fn setup_db_handlers(window: &mut webui::Window) {
                     send_response(window, "db_response", &response);
                 }
                 Err(e) => {
-                    info!("Database error getting users: {}", e);
+                    info!("Database error getting users: {}", e.internal_details());
                     let response = serde_json::json!({
                         "success": false,
-                        "error": format!("Database error: {}", e)
+                        "error": e.public_message()
                     });
                     send_response(window, "db_response", &response);
                 }
@@ -106,10 +106,10 @@ pub fn setup_db_handlers(window: &mut webui::Window) {
                     send_response(window, "user_create_response", &response);
                 }
                 Err(e) => {
-                    info!("Insert error: {}", e);
+                    info!("Insert error: {}", e.internal_details());
                     let response = serde_json::json!({
                         "success": false,
-                        "error": format!("Insert error: {}", e)
+                        "error": e.public_message()
                     });
                     send_response(window, "user_create_response", &response);
                 }
@@ -183,10 +183,10 @@ pub fn setup_db_handlers(window: &mut webui::Window) {
                     send_response(window, "user_update_response", &response);
                 }
                 Err(e) => {
-                    info!("Update error: {}", e);
+                    info!("Update error: {}", e.internal_details());
                     let response = serde_json::json!({
                         "success": false,
-                        "error": format!("Update error: {}", e)
+                        "error": e.public_message()
                     });
                     send_response(window, "user_update_response", &response);
                 }
@@ -240,10 +240,10 @@ pub fn setup_db_handlers(window: &mut webui::Window) {
                     send_response(window, "user_delete_response", &response);
                 }
                 Err(e) => {
-                    info!("Delete error: {}", e);
+                    info!("Delete error: {}", e.internal_details());
                     let response = serde_json::json!({
                         "success": false,
-                        "error": format!("Delete error: {}", e)
+                        "error": e.public_message()
                     });
                     send_response(window, "user_delete_response", &response);
                 }
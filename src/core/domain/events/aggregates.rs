@@ -0,0 +1,156 @@
+#![allow(dead_code)]
+// Event-sourced aggregates: the domain events recorded for an entity and the
+// state they fold into
+
+use crate::core::domain::traits::Aggregate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UserEvent {
+    Created {
+        name: String,
+        email: String,
+        role: String,
+        status: String,
+    },
+    Updated {
+        name: Option<String>,
+        email: Option<String>,
+        role: Option<String>,
+        status: Option<String>,
+    },
+    Deleted,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserAggregate {
+    pub name: String,
+    pub email: String,
+    pub role: String,
+    pub status: String,
+    pub deleted: bool,
+}
+
+impl Aggregate for UserAggregate {
+    const TYPE: &'static str = "user";
+    type Event = UserEvent;
+
+    fn apply(&mut self, event: &UserEvent) {
+        match event {
+            UserEvent::Created {
+                name,
+                email,
+                role,
+                status,
+            } => {
+                *self = Self {
+                    name: name.clone(),
+                    email: email.clone(),
+                    role: role.clone(),
+                    status: status.clone(),
+                    deleted: false,
+                };
+            }
+            UserEvent::Updated {
+                name,
+                email,
+                role,
+                status,
+            } => {
+                if let Some(name) = name {
+                    self.name = name.clone();
+                }
+                if let Some(email) = email {
+                    self.email = email.clone();
+                }
+                if let Some(role) = role {
+                    self.role = role.clone();
+                }
+                if let Some(status) = status {
+                    self.status = status.clone();
+                }
+            }
+            UserEvent::Deleted => self.deleted = true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProductEvent {
+    Created {
+        name: String,
+        description: Option<String>,
+        price: f64,
+        category: String,
+        stock: i64,
+    },
+    Updated {
+        name: Option<String>,
+        description: Option<String>,
+        price: Option<f64>,
+        category: Option<String>,
+    },
+    StockAdjusted {
+        delta: i64,
+    },
+    Deleted,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProductAggregate {
+    pub name: String,
+    pub description: Option<String>,
+    pub price: f64,
+    pub category: String,
+    pub stock: i64,
+    pub deleted: bool,
+}
+
+impl Aggregate for ProductAggregate {
+    const TYPE: &'static str = "product";
+    type Event = ProductEvent;
+
+    fn apply(&mut self, event: &ProductEvent) {
+        match event {
+            ProductEvent::Created {
+                name,
+                description,
+                price,
+                category,
+                stock,
+            } => {
+                *self = Self {
+                    name: name.clone(),
+                    description: description.clone(),
+                    price: *price,
+                    category: category.clone(),
+                    stock: *stock,
+                    deleted: false,
+                };
+            }
+            ProductEvent::Updated {
+                name,
+                description,
+                price,
+                category,
+            } => {
+                if let Some(name) = name {
+                    self.name = name.clone();
+                }
+                if description.is_some() {
+                    self.description = description.clone();
+                }
+                if let Some(price) = price {
+                    self.price = *price;
+                }
+                if let Some(category) = category {
+                    self.category = category.clone();
+                }
+            }
+            ProductEvent::StockAdjusted { delta } => self.stock += delta,
+            ProductEvent::Deleted => self.deleted = true,
+        }
+    }
+}
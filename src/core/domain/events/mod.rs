@@ -0,0 +1,122 @@
+#![allow(dead_code)]
+use crate::core::domain::traits::EventTopic;
+use serde::{Deserialize, Serialize};
+
+pub mod aggregates;
+
+pub use aggregates::{UserAggregate, UserEvent};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserCreated {
+    pub id: i64,
+    pub name: String,
+    pub email: String,
+    pub role: String,
+    pub status: String,
+}
+
+impl EventTopic for UserCreated {
+    const TOPIC: &'static str = "user.created";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserUpdated {
+    pub id: i64,
+}
+
+impl EventTopic for UserUpdated {
+    const TOPIC: &'static str = "user.updated";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserDeleted {
+    pub id: i64,
+}
+
+impl EventTopic for UserDeleted {
+    const TOPIC: &'static str = "user.deleted";
+}
+
+/// The config file changed on disk and the reloaded `AppConfig` replaced the
+/// one in the DI container
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigChanged {
+    pub path: String,
+    /// Top-level sections that differ, e.g. "logging"
+    pub sections: Vec<String>,
+    /// Every setting that differs, with its old and new value
+    #[serde(default)]
+    pub changes: Vec<SettingChange>,
+}
+
+/// One setting that differs between two configs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettingChange {
+    /// Dotted key, e.g. "logging.level"
+    pub key: String,
+    /// `None` where the setting was unset
+    pub old_value: Option<serde_json::Value>,
+    pub new_value: Option<serde_json::Value>,
+}
+
+impl ConfigChanged {
+    pub fn touches(&self, section: &str) -> bool {
+        self.sections.iter().any(|s| s == section)
+    }
+}
+
+impl EventTopic for ConfigChanged {
+    const TOPIC: &'static str = "config.changed";
+}
+
+/// Something security relevant happened: a sign-in, a refused permission, a
+/// settings change. `security_audit` writes each one to the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecurityEvent {
+    /// What happened, e.g. "login_failed" or "permission_denied"
+    pub kind: String,
+    /// The user it happened to, when known
+    pub user_id: Option<i64>,
+    /// What it was about, e.g. the email a sign-in was tried with
+    pub subject: String,
+    pub detail: Option<String>,
+}
+
+impl EventTopic for SecurityEvent {
+    const TOPIC: &'static str = "security.audit";
+}
+
+/// Files under a path a `FileWatcher` watches changed. Changes within the
+/// watcher's debounce window arrive together, one per path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileChanged {
+    /// The watched path, canonicalized
+    pub path: String,
+    pub changes: Vec<FileChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileChange {
+    pub path: String,
+    pub kind: FileChangeKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChangeKind {
+    Created,
+    Modified,
+    /// Deleted, or renamed away
+    Removed,
+}
+
+impl FileChanged {
+    /// Whether `path` is among the changed paths
+    pub fn touches(&self, path: &str) -> bool {
+        self.changes.iter().any(|change| change.path == path)
+    }
+}
+
+impl EventTopic for FileChanged {
+    const TOPIC: &'static str = "file.changed";
+}
@@ -10,6 +10,20 @@ pub trait UserRepository: Send + Sync {
     fn delete(&self, id: i64) -> Result<()>;
 }
 
+/// A typed event payload published on the event bus under a fixed topic
+pub trait EventTopic {
+    const TOPIC: &'static str;
+}
+
+/// Entity state rebuilt by folding the domain events recorded for it
+pub trait Aggregate: Default {
+    /// Aggregate type the events are stored under, e.g. "user"
+    const TYPE: &'static str;
+    type Event;
+
+    fn apply(&mut self, event: &Self::Event);
+}
+
 pub trait ConfigRepository: Send + Sync {
     fn load_config(&self) -> Result<crate::core::domain::entities::AppConfig>;
     fn save_config(&self, config: &crate::core::domain::entities::AppConfig) -> Result<()>;
@@ -9,6 +9,11 @@
 
 use std::fmt;
 use std::collections::HashMap;
+use std::sync::PoisonError;
+use std::time::Duration;
+use log::warn;
+use rand::Rng;
+use schemars::JsonSchema;
 use serde::{Serialize, Deserialize};
 
 /// Error codes for programmatic handling and frontend-backend protocol
@@ -21,6 +26,7 @@ pub enum ErrorCode {
     DbConstraintViolation = 1002,
     DbNotFound = 1003,
     DbAlreadyExists = 1004,
+    DbBusy = 1005,
     
     // Configuration errors (2000-2999)
     ConfigNotFound = 2000,
@@ -31,6 +37,7 @@ pub enum ErrorCode {
     SerializationFailed = 3000,
     DeserializationFailed = 3001,
     InvalidFormat = 3002,
+    PayloadTooLarge = 3003,
     
     // Validation errors (4000-4999)
     ValidationFailed = 4000,
@@ -44,8 +51,23 @@ pub enum ErrorCode {
     
     // System errors (6000-6999)
     LockPoisoned = 6000,
+    Timeout = 6001,
+    ServiceUnavailable = 6002,
     InternalError = 6999,
     
+    // Plugin errors (7000-7999)
+    Plugin = 7000,
+    
+    // Authentication errors (8000-8999)
+    Unauthorized = 8000,
+    SessionExpired = 8001,
+    TwoFactorRequired = 8002,
+    InvalidTwoFactorCode = 8003,
+    Forbidden = 8004,
+    InvalidCredentials = 8005,
+    AccountLocked = 8006,
+    PathNotAllowed = 8007,
+    
     // Custom/unknown
     Unknown = 9999,
 }
@@ -58,12 +80,14 @@ impl fmt::Display for ErrorCode {
             ErrorCode::DbConstraintViolation => write!(f, "DB_CONSTRAINT_VIOLATION"),
             ErrorCode::DbNotFound => write!(f, "DB_NOT_FOUND"),
             ErrorCode::DbAlreadyExists => write!(f, "DB_ALREADY_EXISTS"),
+            ErrorCode::DbBusy => write!(f, "DB_BUSY"),
             ErrorCode::ConfigNotFound => write!(f, "CONFIG_NOT_FOUND"),
             ErrorCode::ConfigInvalid => write!(f, "CONFIG_INVALID"),
             ErrorCode::ConfigMissingField => write!(f, "CONFIG_MISSING_FIELD"),
             ErrorCode::SerializationFailed => write!(f, "SERIALIZATION_FAILED"),
             ErrorCode::DeserializationFailed => write!(f, "DESERIALIZATION_FAILED"),
             ErrorCode::InvalidFormat => write!(f, "INVALID_FORMAT"),
+            ErrorCode::PayloadTooLarge => write!(f, "PAYLOAD_TOO_LARGE"),
             ErrorCode::ValidationFailed => write!(f, "VALIDATION_FAILED"),
             ErrorCode::MissingRequiredField => write!(f, "MISSING_REQUIRED_FIELD"),
             ErrorCode::InvalidFieldValue => write!(f, "INVALID_FIELD_VALUE"),
@@ -71,12 +95,189 @@ impl fmt::Display for ErrorCode {
             ErrorCode::UserNotFound => write!(f, "USER_NOT_FOUND"),
             ErrorCode::EntityNotFound => write!(f, "ENTITY_NOT_FOUND"),
             ErrorCode::LockPoisoned => write!(f, "LOCK_POISONED"),
+            ErrorCode::Timeout => write!(f, "TIMEOUT"),
+            ErrorCode::ServiceUnavailable => write!(f, "SERVICE_UNAVAILABLE"),
             ErrorCode::InternalError => write!(f, "INTERNAL_ERROR"),
+            ErrorCode::Plugin => write!(f, "PLUGIN"),
+            ErrorCode::Unauthorized => write!(f, "UNAUTHORIZED"),
+            ErrorCode::SessionExpired => write!(f, "SESSION_EXPIRED"),
+            ErrorCode::TwoFactorRequired => write!(f, "TWO_FACTOR_REQUIRED"),
+            ErrorCode::InvalidTwoFactorCode => write!(f, "INVALID_TWO_FACTOR_CODE"),
+            ErrorCode::Forbidden => write!(f, "FORBIDDEN"),
+            ErrorCode::InvalidCredentials => write!(f, "INVALID_CREDENTIALS"),
+            ErrorCode::AccountLocked => write!(f, "ACCOUNT_LOCKED"),
+            ErrorCode::PathNotAllowed => write!(f, "PATH_NOT_ALLOWED"),
             ErrorCode::Unknown => write!(f, "UNKNOWN"),
         }
     }
 }
 
+impl ErrorCode {
+    /// Every code, in catalog order
+    pub const ALL: [ErrorCode; 33] = [
+        ErrorCode::DbConnectionFailed,
+        ErrorCode::DbQueryFailed,
+        ErrorCode::DbConstraintViolation,
+        ErrorCode::DbNotFound,
+        ErrorCode::DbAlreadyExists,
+        ErrorCode::DbBusy,
+        ErrorCode::ConfigNotFound,
+        ErrorCode::ConfigInvalid,
+        ErrorCode::ConfigMissingField,
+        ErrorCode::SerializationFailed,
+        ErrorCode::DeserializationFailed,
+        ErrorCode::InvalidFormat,
+        ErrorCode::PayloadTooLarge,
+        ErrorCode::ValidationFailed,
+        ErrorCode::MissingRequiredField,
+        ErrorCode::InvalidFieldValue,
+        ErrorCode::ResourceNotFound,
+        ErrorCode::UserNotFound,
+        ErrorCode::EntityNotFound,
+        ErrorCode::LockPoisoned,
+        ErrorCode::Timeout,
+        ErrorCode::ServiceUnavailable,
+        ErrorCode::InternalError,
+        ErrorCode::Plugin,
+        ErrorCode::Unauthorized,
+        ErrorCode::SessionExpired,
+        ErrorCode::TwoFactorRequired,
+        ErrorCode::InvalidTwoFactorCode,
+        ErrorCode::Forbidden,
+        ErrorCode::InvalidCredentials,
+        ErrorCode::AccountLocked,
+        ErrorCode::PathNotAllowed,
+        ErrorCode::Unknown,
+    ];
+
+    /// What the code means, for users and the generated frontend catalog.
+    /// build.rs reads these arms to write `frontend/src/generated/error-codes.ts`,
+    /// so keep each on one line.
+    pub fn description(&self) -> &'static str {
+        match self {
+            ErrorCode::DbConnectionFailed => "The database could not be opened or reached",
+            ErrorCode::DbQueryFailed => "A database query failed",
+            ErrorCode::DbConstraintViolation => "The change breaks a database constraint",
+            ErrorCode::DbNotFound => "No database row matches the given key",
+            ErrorCode::DbAlreadyExists => "A database row with the same key already exists",
+            ErrorCode::DbBusy => "The database is locked by another connection; try again",
+            ErrorCode::ConfigNotFound => "The configuration file could not be found",
+            ErrorCode::ConfigInvalid => "The configuration has an invalid value",
+            ErrorCode::ConfigMissingField => "The configuration is missing a required value",
+            ErrorCode::SerializationFailed => "A value could not be serialized",
+            ErrorCode::DeserializationFailed => "A payload could not be read as the expected type",
+            ErrorCode::InvalidFormat => "The data is not in a supported format",
+            ErrorCode::PayloadTooLarge => "The payload is larger than the configured limit",
+            ErrorCode::ValidationFailed => "The input did not pass validation",
+            ErrorCode::MissingRequiredField => "A required field is missing",
+            ErrorCode::InvalidFieldValue => "A field has a value that is not allowed",
+            ErrorCode::ResourceNotFound => "The requested resource does not exist",
+            ErrorCode::UserNotFound => "The user does not exist",
+            ErrorCode::EntityNotFound => "The requested entity does not exist",
+            ErrorCode::LockPoisoned => "Shared state was left inconsistent by an earlier panic",
+            ErrorCode::Timeout => "The operation did not finish in time",
+            ErrorCode::ServiceUnavailable => "A remote service could not be reached or failed",
+            ErrorCode::InternalError => "An unexpected backend error occurred",
+            ErrorCode::Plugin => "A plugin failed",
+            ErrorCode::Unauthorized => "Sign in to continue",
+            ErrorCode::SessionExpired => "The session has expired or was signed out; sign in again",
+            ErrorCode::TwoFactorRequired => "Enter the code from your authenticator app",
+            ErrorCode::InvalidTwoFactorCode => "The authentication code is wrong or was already used",
+            ErrorCode::Forbidden => "Your role doesn't allow this action",
+            ErrorCode::InvalidCredentials => "The email or password is wrong",
+            ErrorCode::AccountLocked => "Too many failed sign-ins; try again later",
+            ErrorCode::PathNotAllowed => "That path is outside the folders the app may use",
+            ErrorCode::Unknown => "An unknown error occurred",
+        }
+    }
+
+    /// Range the code belongs to, e.g. "database" for 1000-1999
+    pub fn category(&self) -> &'static str {
+        match self.number() {
+            1000..=1999 => "database",
+            2000..=2999 => "configuration",
+            3000..=3999 => "serialization",
+            4000..=4999 => "validation",
+            5000..=5999 => "not_found",
+            6000..=6999 => "system",
+            7000..=7999 => "plugin",
+            8000..=8999 => "auth",
+            _ => "unknown",
+        }
+    }
+
+    pub fn number(&self) -> u32 {
+        self.clone() as u32
+    }
+
+    /// Whether messages under this code are written for the user: bad
+    /// input, something that doesn't exist, or a failed sign-in. Messages
+    /// under any other code may quote SQL, paths or library errors, so the
+    /// frontend gets the code's description instead.
+    pub fn is_user_facing(&self) -> bool {
+        matches!(self.category(), "validation" | "not_found" | "auth")
+            || matches!(
+                self,
+                ErrorCode::DbNotFound
+                    | ErrorCode::DbAlreadyExists
+                    | ErrorCode::DbConstraintViolation
+                    | ErrorCode::DeserializationFailed
+                    | ErrorCode::InvalidFormat
+                    | ErrorCode::PayloadTooLarge
+            )
+    }
+
+    /// `DbBusy` when SQLite reports the database busy or locked, otherwise
+    /// `fallback`
+    pub fn for_sqlite(err: &rusqlite::Error, fallback: ErrorCode) -> Self {
+        match err.sqlite_error_code() {
+            Some(rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked) => {
+                ErrorCode::DbBusy
+            }
+            _ => fallback,
+        }
+    }
+
+    /// `Timeout` or `ServiceUnavailable` for failures worth retrying: no
+    /// answer, no connection, 5xx or 429. Otherwise `fallback`.
+    pub fn for_http(err: &reqwest::Error, fallback: ErrorCode) -> Self {
+        if err.is_timeout() {
+            return ErrorCode::Timeout;
+        }
+        let retryable_status = err
+            .status()
+            .is_some_and(|status| status.is_server_error() || status.as_u16() == 429);
+        if err.is_connect() || retryable_status {
+            ErrorCode::ServiceUnavailable
+        } else {
+            fallback
+        }
+    }
+}
+
+/// One `ErrorCode` as the `error_codes` handler lists it
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ErrorCodeInfo {
+    /// Wire name, e.g. "DB_QUERY_FAILED"
+    pub code: String,
+    pub number: u32,
+    pub category: String,
+    pub description: String,
+}
+
+/// The whole error code catalog
+pub fn error_catalog() -> Vec<ErrorCodeInfo> {
+    ErrorCode::ALL
+        .iter()
+        .map(|code| ErrorCodeInfo {
+            code: code.to_string(),
+            number: code.number(),
+            category: code.category().to_string(),
+            description: code.description().to_string(),
+        })
+        .collect()
+}
+
 /// Structured error value with metadata for cross-boundary communication
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorValue {
@@ -96,6 +297,9 @@ pub struct ErrorValue {
     /// Optional context key-value pairs
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<HashMap<String, String>>,
+    /// Correlation id of the call that failed, to find its log lines
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
 }
 
 impl ErrorValue {
@@ -107,6 +311,7 @@ impl ErrorValue {
             field: None,
             cause: None,
             context: None,
+            correlation_id: None,
         }
     }
 
@@ -133,22 +338,62 @@ impl ErrorValue {
         self
     }
 
-    /// Convert to API response format for frontend consumption
+    #[allow(dead_code)]
+    pub fn with_correlation_id(mut self, id: impl Into<String>) -> Self {
+        self.correlation_id = Some(id.into());
+        self
+    }
+
+    /// Message safe to show the user: the message itself for user-facing
+    /// codes, the code's description otherwise
+    pub fn public_message(&self) -> &str {
+        if self.code.is_user_facing() {
+            &self.message
+        } else {
+            self.code.description()
+        }
+    }
+
+    /// Everything known about the error, for logs only: message, details,
+    /// cause and context
+    pub fn internal_details(&self) -> String {
+        let mut text = self.to_string();
+        if let Some(ref field) = self.field {
+            text.push_str(&format!(" field={}", field));
+        }
+        if let Some(ref cause) = self.cause {
+            text.push_str(&format!(" cause={}", cause));
+        }
+        if let Some(ref context) = self.context {
+            let mut pairs: Vec<_> = context.iter().collect();
+            pairs.sort();
+            for (key, value) in pairs {
+                text.push_str(&format!(" {}={}", key, value));
+            }
+        }
+        text
+    }
+
+    /// Convert to API response format for frontend consumption. Only the
+    /// public message goes out; cause never does, and details and context
+    /// only for user-facing codes. `internal_details` has the rest.
     pub fn to_response(&self) -> serde_json::Value {
         let mut map = serde_json::Map::new();
         map.insert("code".to_string(), serde_json::json!(self.code.to_string()));
-        map.insert("message".to_string(), serde_json::json!(self.message));
-        if let Some(ref details) = self.details {
-            map.insert("details".to_string(), serde_json::json!(details));
-        }
+        map.insert("message".to_string(), serde_json::json!(self.public_message()));
         if let Some(ref field) = self.field {
             map.insert("field".to_string(), serde_json::json!(field));
         }
-        if let Some(ref cause) = self.cause {
-            map.insert("cause".to_string(), serde_json::json!(cause));
+        if self.code.is_user_facing() {
+            if let Some(ref details) = self.details {
+                map.insert("details".to_string(), serde_json::json!(details));
+            }
+            if let Some(ref context) = self.context {
+                map.insert("context".to_string(), serde_json::json!(context));
+            }
         }
-        if let Some(ref context) = self.context {
-            map.insert("context".to_string(), serde_json::json!(context));
+        if let Some(ref correlation_id) = self.correlation_id {
+            map.insert("correlation_id".to_string(), serde_json::json!(correlation_id));
         }
         serde_json::Value::Object(map)
     }
@@ -164,6 +409,35 @@ impl fmt::Display for ErrorValue {
     }
 }
 
+/// Failure envelope sent to the frontend: `{ success: false, data: null, error }`.
+///
+/// Mirrored into `frontend/src/generated/entities.d.ts`; the `// ts:` comments
+/// pin the literal types the frontend narrows `ApiResponse` on.
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct ErrorResponse {
+    // ts: false
+    pub success: bool,
+    // ts: null
+    pub data: Option<serde_json::Value>,
+    pub error: ErrorValue,
+}
+
+#[allow(dead_code)]
+impl ErrorResponse {
+    pub fn new(error: ErrorValue) -> Self {
+        Self {
+            success: false,
+            data: None,
+            error,
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        crate::core::rpc::RpcResponse::err(self.error.clone()).to_json()
+    }
+}
+
 /// Application error enum using structured error values
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -200,6 +474,31 @@ impl AppError {
     pub fn to_json(&self) -> serde_json::Value {
         self.to_value().to_response()
     }
+
+    /// The same variant with its value changed by `f`
+    pub fn map_value(self, f: impl FnOnce(ErrorValue) -> ErrorValue) -> AppError {
+        match self {
+            AppError::Database(v) => AppError::Database(f(v)),
+            AppError::DependencyInjection(v) => AppError::DependencyInjection(f(v)),
+            AppError::EventBus(v) => AppError::EventBus(f(v)),
+            AppError::Logging(v) => AppError::Logging(f(v)),
+            AppError::Configuration(v) => AppError::Configuration(f(v)),
+            AppError::Serialization(v) => AppError::Serialization(f(v)),
+            AppError::Validation(v) => AppError::Validation(f(v)),
+            AppError::NotFound(v) => AppError::NotFound(f(v)),
+            AppError::LockPoisoned(v) => AppError::LockPoisoned(f(v)),
+        }
+    }
+
+    /// See `ErrorValue::public_message`
+    pub fn public_message(&self) -> &str {
+        self.to_value().public_message()
+    }
+
+    /// See `ErrorValue::internal_details`
+    pub fn internal_details(&self) -> String {
+        self.to_value().internal_details()
+    }
 }
 
 impl fmt::Display for AppError {
@@ -213,7 +512,8 @@ impl std::error::Error for AppError {}
 // From implementations for common error types
 impl From<rusqlite::Error> for AppError {
     fn from(err: rusqlite::Error) -> Self {
-        let error_value = ErrorValue::new(ErrorCode::DbQueryFailed, err.to_string())
+        let code = ErrorCode::for_sqlite(&err, ErrorCode::DbQueryFailed);
+        let error_value = ErrorValue::new(code, err.to_string())
             .with_cause("SQLite operation failed");
         AppError::Database(error_value)
     }
@@ -227,6 +527,15 @@ impl From<std::io::Error> for AppError {
     }
 }
 
+impl<G> From<PoisonError<G>> for AppError {
+    fn from(err: PoisonError<G>) -> Self {
+        AppError::LockPoisoned(
+            ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire lock")
+                .with_cause(err.to_string()),
+        )
+    }
+}
+
 impl From<serde_json::Error> for AppError {
     fn from(err: serde_json::Error) -> Self {
         let error_value = ErrorValue::new(ErrorCode::SerializationFailed, err.to_string())
@@ -235,6 +544,130 @@ impl From<serde_json::Error> for AppError {
     }
 }
 
+/// Keeps what anyhow collected. An `AppError`, SQLite or I/O error anywhere
+/// in the chain decides the variant and code. The outermost message, the
+/// root cause, the whole chain and a captured backtrace go into the value.
+impl From<anyhow::Error> for AppError {
+    fn from(err: anyhow::Error) -> Self {
+        let base = if let Some(inner) = err.chain().find_map(|e| e.downcast_ref::<AppError>()) {
+            inner.clone()
+        } else if let Some(sqlite) = err.chain().find_map(|e| e.downcast_ref::<rusqlite::Error>()) {
+            let code = ErrorCode::for_sqlite(sqlite, ErrorCode::DbQueryFailed);
+            AppError::Database(ErrorValue::new(code, err.to_string()))
+        } else if err.chain().any(|e| e.is::<std::io::Error>()) {
+            AppError::Logging(ErrorValue::new(ErrorCode::InternalError, err.to_string()))
+        } else {
+            errors::internal(&err.to_string())
+        };
+
+        let chain: Vec<String> = err.chain().map(ToString::to_string).collect();
+        let backtrace = err.backtrace();
+        base.map_value(|mut value| {
+            if value.cause.is_none() && chain.len() > 1 {
+                value.cause = Some(err.root_cause().to_string());
+            }
+            if chain.len() > 1 {
+                value = value.with_context("chain", chain.join(": "));
+            }
+            if backtrace.status() == std::backtrace::BacktraceStatus::Captured {
+                value = value.with_context("backtrace", backtrace.to_string());
+            }
+            value
+        })
+    }
+}
+
+/// How `retry_with_backoff` retries: up to `max_attempts` calls in total,
+/// waiting `initial_delay` doubled after each failure (capped at
+/// `max_delay`), with a random part so callers that failed together don't
+/// retry together. Only errors `retry_on` accepts are retried.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub retry_on: fn(&AppError) -> bool,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, initial_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            initial_delay,
+            max_delay,
+            retry_on: is_transient,
+        }
+    }
+
+    pub fn retry_on(mut self, retry_on: fn(&AppError) -> bool) -> Self {
+        self.retry_on = retry_on;
+        self
+    }
+
+    /// Short waits for a database another connection holds locked
+    pub fn database() -> Self {
+        Self::new(5, Duration::from_millis(20), Duration::from_millis(500)).retry_on(is_db_busy)
+    }
+
+    /// Outbound HTTP: unreachable hosts, timeouts and 5xx answers
+    pub fn network() -> Self {
+        Self::new(3, Duration::from_millis(250), Duration::from_secs(4))
+    }
+
+    /// Wait before attempt `attempt + 1`: half the backoff plus a random
+    /// share of the other half
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .initial_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+        let half = backoff / 2;
+        let jitter = rand::thread_rng().gen_range(0..=half.as_millis() as u64);
+        half + Duration::from_millis(jitter)
+    }
+}
+
+/// SQLite reported the database busy or locked
+pub fn is_db_busy(error: &AppError) -> bool {
+    error.to_value().code == ErrorCode::DbBusy
+}
+
+/// Errors that may go away on their own: a busy database, a timeout or an
+/// unavailable remote service
+pub fn is_transient(error: &AppError) -> bool {
+    matches!(
+        error.to_value().code,
+        ErrorCode::DbBusy | ErrorCode::Timeout | ErrorCode::ServiceUnavailable
+    )
+}
+
+/// Call `op` until it succeeds, fails with an error `policy` doesn't retry,
+/// or runs out of attempts; the last error is returned. `op` gets the
+/// attempt number, starting at 1.
+pub fn retry_with_backoff<T>(
+    policy: &RetryPolicy,
+    mut op: impl FnMut(u32) -> AppResult<T>,
+) -> AppResult<T> {
+    let mut attempt = 1;
+    loop {
+        match op(attempt) {
+            Err(e) if attempt < policy.max_attempts && (policy.retry_on)(&e) => {
+                let delay = policy.delay(attempt);
+                log::debug!(
+                    "Attempt {}/{} failed, retrying in {:?}: {}",
+                    attempt,
+                    policy.max_attempts,
+                    delay,
+                    e
+                );
+                std::thread::sleep(delay);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
 /// Standard result type for application operations
 pub type AppResult<T> = Result<T, AppError>;
 
@@ -264,6 +697,90 @@ impl<T, E: fmt::Display> ToAppResult<T> for Result<T, E> {
     }
 }
 
+/// Combinators for results whose error converts into `AppError`, so a
+/// `map_err` block becomes one call:
+///
+/// ```ignore
+/// conn.prepare(sql).map_db_err("prepare users query").with_context("table", "users")?;
+/// self.state.lock().with_context("lock", "sessions")?;
+/// ```
+pub trait ErrorExt<T> {
+    /// `DbQueryFailed` (or `DbBusy`) with message "Failed to <operation>"
+    /// and the original error as cause
+    fn map_db_err(self, operation: &str) -> AppResult<T>;
+    /// Name the input field the error is about
+    #[allow(dead_code)]
+    fn with_field(self, field: &str) -> AppResult<T>;
+    fn with_context(self, key: &str, value: impl Into<String>) -> AppResult<T>;
+    /// Log the error's internal details at warn level, prefixed with
+    /// `context`, and pass the result on unchanged
+    #[allow(dead_code)]
+    fn log_warn(self, context: &str) -> AppResult<T>;
+}
+
+impl<T, E: Into<AppError>> ErrorExt<T> for Result<T, E> {
+    fn map_db_err(self, operation: &str) -> AppResult<T> {
+        self.map_err(|e| {
+            let source: AppError = e.into();
+            let source = source.to_value();
+            let code = match source.code {
+                ErrorCode::DbBusy => ErrorCode::DbBusy,
+                _ => ErrorCode::DbQueryFailed,
+            };
+            AppError::Database(
+                ErrorValue::new(code, format!("Failed to {}", operation))
+                    .with_cause(source.message.clone()),
+            )
+        })
+    }
+
+    fn with_field(self, field: &str) -> AppResult<T> {
+        self.map_err(|e| {
+            let e: AppError = e.into();
+            e.map_value(|value| value.with_field(field))
+        })
+    }
+
+    fn with_context(self, key: &str, value: impl Into<String>) -> AppResult<T> {
+        self.map_err(|e| {
+            let e: AppError = e.into();
+            e.map_value(|v| v.with_context(key, value))
+        })
+    }
+
+    fn log_warn(self, context: &str) -> AppResult<T> {
+        self.map_err(|e| {
+            let e: AppError = e.into();
+            warn!("{}: {}", context, e.internal_details());
+            e
+        })
+    }
+}
+
+/// Combinators turning a missing value into an `AppError`
+pub trait OptionExt<T> {
+    /// `ResourceNotFound` for `resource` `id`, e.g. "User not found: 7"
+    fn or_not_found(self, resource: &str, id: impl fmt::Display) -> AppResult<T>;
+    /// `MissingRequiredField` naming `field`
+    #[allow(dead_code)]
+    fn required(self, field: &str) -> AppResult<T>;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    fn or_not_found(self, resource: &str, id: impl fmt::Display) -> AppResult<T> {
+        self.ok_or_else(|| errors::not_found(resource, id))
+    }
+
+    fn required(self, field: &str) -> AppResult<T> {
+        self.ok_or_else(|| {
+            AppError::Validation(
+                ErrorValue::new(ErrorCode::MissingRequiredField, format!("{} is required", field))
+                    .with_field(field),
+            )
+        })
+    }
+}
+
 /// Helper functions for creating common errors
 pub mod errors {
     use super::*;
@@ -305,6 +822,18 @@ pub mod errors {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_error_response_envelope() {
+        let response = ErrorResponse::new(
+            ErrorValue::new(ErrorCode::UserNotFound, "missing").with_field("id"),
+        )
+        .to_json();
+        assert_eq!(response["success"], false);
+        assert!(response["data"].is_null());
+        assert_eq!(response["error"]["code"], "USER_NOT_FOUND");
+        assert_eq!(response["error"]["field"], "id");
+    }
+
     #[test]
     fn test_error_value_creation() {
         let error = ErrorValue::new(ErrorCode::DbNotFound, "User not found")
@@ -325,6 +854,152 @@ mod tests {
         assert!(json.get("message").is_some());
     }
 
+    #[test]
+    fn test_internal_errors_are_sanitized_for_the_frontend() {
+        let err = AppError::from(rusqlite::Error::InvalidColumnName(String::from("secret_col")));
+        let err = match err {
+            AppError::Database(value) => AppError::Database(
+                value.with_context("sql", "SELECT secret_col FROM users"),
+            ),
+            other => other,
+        };
+        let json = err.to_json();
+        assert_eq!(json["code"], "DB_QUERY_FAILED");
+        assert_eq!(json["message"], ErrorCode::DbQueryFailed.description());
+        assert!(json.get("cause").is_none());
+        assert!(json.get("context").is_none());
+        assert!(!json.to_string().contains("secret_col"));
+
+        let details = err.internal_details();
+        assert!(details.contains("secret_col"));
+        assert!(details.contains("sql=SELECT secret_col FROM users"));
+
+        let err = errors::db_not_found("User", 7);
+        assert_eq!(err.public_message(), "User not found: 7");
+        let json = err.to_json();
+        assert_eq!(json["field"], "id");
+        assert_eq!(json["context"]["entity"], "User");
+    }
+
+    #[test]
+    fn test_anyhow_chain_is_kept() {
+        use anyhow::Context;
+
+        let err: anyhow::Error = Err::<(), _>(rusqlite::Error::QueryReturnedNoRows)
+            .context("loading user 7")
+            .unwrap_err();
+        let err = AppError::from(err);
+        assert!(matches!(err, AppError::Database(_)));
+        let value = err.to_value();
+        assert_eq!(value.code, ErrorCode::DbQueryFailed);
+        assert_eq!(value.message, "loading user 7");
+        assert_eq!(value.cause.as_deref(), Some("Query returned no rows"));
+        assert_eq!(
+            value.context.as_ref().unwrap()["chain"],
+            "loading user 7: Query returned no rows"
+        );
+
+        let err: anyhow::Error = Err::<(), _>(errors::validation_failed("email", "Email is taken"))
+            .context("saving user")
+            .unwrap_err();
+        let err = AppError::from(err);
+        assert!(matches!(err, AppError::Validation(_)));
+        assert_eq!(err.public_message(), "Email is taken");
+        assert!(err.internal_details().contains("saving user"));
+
+        let err = AppError::from(anyhow::anyhow!("plain failure"));
+        assert_eq!(err.to_value().code, ErrorCode::InternalError);
+        assert!(err.to_value().cause.is_none());
+    }
+
+    #[test]
+    fn test_result_combinators() {
+        let failed: Result<(), rusqlite::Error> = Err(rusqlite::Error::InvalidQuery);
+        let err = failed
+            .map_db_err("count users")
+            .with_context("table", "users")
+            .log_warn("counting users")
+            .unwrap_err();
+        assert!(matches!(err, AppError::Database(_)));
+        let value = err.to_value();
+        assert_eq!(value.code, ErrorCode::DbQueryFailed);
+        assert_eq!(value.message, "Failed to count users");
+        assert!(value.cause.is_some());
+        assert_eq!(value.context.as_ref().unwrap()["table"], "users");
+
+        let err = Err::<(), _>(errors::validation_failed("email", "Email is invalid"))
+            .with_field("contact_email")
+            .unwrap_err();
+        assert_eq!(err.to_value().field.as_deref(), Some("contact_email"));
+
+        let poisoned: Result<(), PoisonError<()>> = Err(PoisonError::new(()));
+        let err = poisoned.with_context("lock", "counter").unwrap_err();
+        assert_eq!(err.to_value().code, ErrorCode::LockPoisoned);
+
+        assert_eq!(Some(3).required("age").unwrap(), 3);
+        let err = None::<i64>.required("age").unwrap_err();
+        assert_eq!(err.to_value().code, ErrorCode::MissingRequiredField);
+        assert_eq!(err.public_message(), "age is required");
+        let err = None::<i64>.or_not_found("User", 7).unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[test]
+    fn test_error_catalog_covers_every_code() {
+        let catalog = error_catalog();
+        assert_eq!(catalog.len(), ErrorCode::ALL.len());
+        let mut numbers: Vec<u32> = catalog.iter().map(|info| info.number).collect();
+        numbers.dedup();
+        assert_eq!(numbers.len(), catalog.len());
+
+        let plugin = catalog.iter().find(|info| info.code == "PLUGIN").unwrap();
+        assert_eq!(plugin.number, 7000);
+        assert_eq!(plugin.category, "plugin");
+        assert_eq!(ErrorCode::DbQueryFailed.category(), "database");
+        assert_eq!(ErrorCode::Unknown.category(), "unknown");
+    }
+
+    #[test]
+    fn test_retry_with_backoff() {
+        let busy = || AppError::Database(ErrorValue::new(ErrorCode::DbBusy, "database is locked"));
+        let policy = RetryPolicy::new(3, Duration::ZERO, Duration::ZERO);
+
+        let mut calls = 0;
+        let result = retry_with_backoff(&policy, |attempt| {
+            calls += 1;
+            if attempt < 3 { Err(busy()) } else { Ok(attempt) }
+        });
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(calls, 3);
+
+        calls = 0;
+        let result: AppResult<()> = retry_with_backoff(&policy, |_| {
+            calls += 1;
+            Err(busy())
+        });
+        assert!(is_db_busy(&result.unwrap_err()));
+        assert_eq!(calls, 3);
+
+        calls = 0;
+        let result: AppResult<()> = retry_with_backoff(&policy.retry_on(is_db_busy), |_| {
+            calls += 1;
+            Err(errors::validation_failed("name", "Name is required"))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_retry_delay_is_capped_and_jittered() {
+        let policy = RetryPolicy::new(10, Duration::from_millis(100), Duration::from_millis(400));
+        for _ in 0..20 {
+            let first = policy.delay(1);
+            assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+            let late = policy.delay(8);
+            assert!(late >= Duration::from_millis(200) && late <= Duration::from_millis(400));
+        }
+    }
+
     #[test]
     fn test_error_helpers() {
         let err = errors::db_not_found("User", 123);
@@ -0,0 +1,181 @@
+// src/core/infrastructure/api_keys.rs
+// API keys for automation clients calling handlers over the http_rest or
+// websocket transport. A client sends its key as `api_key` in the payload
+// instead of a `session_token`; `envelope::authorize` checks it here and
+// lets the call through when one of the key's scopes covers the handler's
+// permission. Keys are shown once when issued and stored only as a hash,
+// which `verify` compares in constant time.
+
+use log::{info, warn};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::config::AppConfig;
+use super::database::{ApiKeyRecord, Database};
+use super::rbac;
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::utils::crypto::CryptoUtils;
+use crate::utils::security::SecurityUtils;
+
+/// Every key starts with this, so leaked keys are easy to search for
+pub const KEY_PREFIX: &str = "rwk_";
+/// Role name callers authenticated by an API key act under
+pub const ROLE: &str = "ApiKey";
+
+const KEY_CHARS: usize = 43;
+/// Characters of the key kept in the clear to tell keys apart
+const SHOWN_CHARS: usize = KEY_PREFIX.len() + 8;
+
+/// A newly issued key; `key` is never shown again
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct IssuedApiKey {
+    pub key: String,
+    pub record: ApiKeyRecord,
+}
+
+fn invalid_key() -> AppError {
+    AppError::Validation(ErrorValue::new(
+        ErrorCode::Unauthorized,
+        "The API key is invalid or has been revoked",
+    ))
+}
+
+fn invalid_field(field: &str, message: &str) -> AppError {
+    AppError::Validation(ErrorValue::new(ErrorCode::InvalidFieldValue, message).with_field(field))
+}
+
+/// Whether the configured transport takes API keys; the WebView bridge
+/// only carries calls from the app's own frontend
+pub fn accepted(config: &AppConfig) -> bool {
+    matches!(config.get_transport(), "http_rest" | "websocket")
+}
+
+/// Issue a key named `name` granting `scopes`. `granted` are the issuer's
+/// own permissions; a key can't be given a scope they don't cover.
+pub fn issue(
+    db: &Database,
+    name: &str,
+    scopes: &[String],
+    granted: &[String],
+    created_by: Option<i64>,
+) -> AppResult<IssuedApiKey> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(invalid_field("name", "Give the API key a name"));
+    }
+    if scopes.is_empty() || scopes.iter().any(|s| s.is_empty() || s.contains(char::is_whitespace)) {
+        return Err(invalid_field("scopes", "List at least one scope, e.g. \"users:read\""));
+    }
+    if let Some(scope) = scopes.iter().find(|scope| !rbac::grants(granted, scope)) {
+        return Err(AppError::Validation(
+            ErrorValue::new(
+                ErrorCode::Forbidden,
+                format!("You can't grant {} to an API key", scope),
+            )
+            .with_field("scopes")
+            .with_context("scope", scope.as_str()),
+        ));
+    }
+
+    let key = format!("{}{}", KEY_PREFIX, CryptoUtils::random_token(KEY_CHARS));
+    let prefix = &key[..SHOWN_CHARS];
+    let id = db.insert_api_key(name, prefix, &CryptoUtils::sha256(&key), scopes, created_by)?;
+    info!("API key {} ({}) issued with scopes {}", id, prefix, scopes.join(" "));
+    let record = db.api_key(id)?.ok_or_else(invalid_key)?;
+    Ok(IssuedApiKey { key, record })
+}
+
+/// The record of `key`, if it exists and isn't revoked
+pub fn verify(db: &Database, key: &str) -> AppResult<ApiKeyRecord> {
+    if !key.starts_with(KEY_PREFIX) || key.len() <= SHOWN_CHARS || !key.is_ascii() {
+        return Err(invalid_key());
+    }
+    let hash = CryptoUtils::sha256(key);
+    let record = db
+        .api_keys_by_prefix(&key[..SHOWN_CHARS])?
+        .into_iter()
+        .find(|(_, stored)| SecurityUtils::ct_eq(stored.as_bytes(), hash.as_bytes()))
+        .map(|(record, _)| record)
+        .filter(|record| record.revoked_at.is_none())
+        .ok_or_else(invalid_key)?;
+    if let Err(e) = db.touch_api_key(record.id) {
+        warn!("Failed to note use of API key {}: {}", record.id, e);
+    }
+    Ok(record)
+}
+
+/// Ok when `record`'s scopes cover `permission`
+pub fn check_scope(record: &ApiKeyRecord, permission: &str) -> AppResult<()> {
+    if rbac::grants(&record.scopes, permission) {
+        return Ok(());
+    }
+    Err(AppError::Validation(
+        ErrorValue::new(
+            ErrorCode::Forbidden,
+            format!("This API key doesn't allow {}", permission),
+        )
+        .with_context("api_key", record.prefix.as_str())
+        .with_context("permission", permission),
+    ))
+}
+
+/// Revoke key `id`; returns its record as revoked
+pub fn revoke(db: &Database, id: i64) -> AppResult<ApiKeyRecord> {
+    let not_found = || {
+        AppError::NotFound(
+            ErrorValue::new(ErrorCode::ResourceNotFound, "No active API key with that id")
+                .with_context("id", id.to_string()),
+        )
+    };
+    if !db.revoke_api_key(id)? {
+        return Err(not_found());
+    }
+    info!("API key {} revoked", id);
+    db.api_key(id)?.ok_or_else(not_found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scopes(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_issue_verify_revoke() {
+        let db = Database::new(":memory:").expect("Failed to create database");
+        db.init_api_keys().expect("Failed to init api keys");
+        let admin = scopes(&["*"]);
+
+        let issued = issue(&db, "ci", &scopes(&["users:read"]), &admin, Some(1)).unwrap();
+        assert!(issued.key.starts_with(KEY_PREFIX));
+        assert!(issued.key.starts_with(&issued.record.prefix));
+        assert_eq!(db.api_keys().unwrap().len(), 1);
+
+        let record = verify(&db, &issued.key).unwrap();
+        assert_eq!(record.scopes, ["users:read"]);
+        assert!(check_scope(&record, "users:read").is_ok());
+        let denied = check_scope(&record, "users:delete").unwrap_err();
+        assert_eq!(denied.to_value().code, ErrorCode::Forbidden);
+        assert!(verify(&db, "rwk_not-a-key").is_err());
+        assert!(verify(&db, &format!("{}0", issued.key)).is_err());
+
+        assert!(revoke(&db, record.id).unwrap().revoked_at.is_some());
+        assert_eq!(verify(&db, &issued.key).unwrap_err().to_value().code, ErrorCode::Unauthorized);
+        assert!(revoke(&db, record.id).is_err());
+    }
+
+    #[test]
+    fn test_issue_limited_to_issuer_permissions() {
+        let db = Database::new(":memory:").expect("Failed to create database");
+        db.init_api_keys().expect("Failed to init api keys");
+        let user = scopes(&["users:read", "settings:read"]);
+
+        assert!(issue(&db, "report", &scopes(&["users:read"]), &user, None).is_ok());
+        let error = issue(&db, "admin", &scopes(&["users:*"]), &user, None).unwrap_err();
+        assert_eq!(error.to_value().code, ErrorCode::Forbidden);
+        assert!(issue(&db, " ", &scopes(&["users:read"]), &user, None).is_err());
+        assert!(issue(&db, "none", &[], &user, None).is_err());
+    }
+}
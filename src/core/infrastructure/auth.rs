@@ -0,0 +1,318 @@
+// src/core/infrastructure/auth.rs
+// Password sign-in against the users table. Failed sign-ins are counted per
+// user; `[security] max_failed_logins` in a row lock the account for
+// `lockout_minutes`. Every sign-in, failure, lockout, sign-out and password
+// change is published as a security event for the audit log. Hashes are
+// made as `[security.password_hashing]` says; older ones are upgraded when
+// their owner next signs in.
+
+use chrono::Utc;
+use log::{info, warn};
+use std::sync::Mutex;
+
+use super::config::AppConfig;
+use super::database::Database;
+use super::security_audit;
+use super::sessions::{SessionInfo, SessionManager, SessionToken};
+use super::two_factor;
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue, OptionExt};
+use crate::utils::crypto::PasswordUtils;
+
+fn invalid_credentials() -> AppError {
+    AppError::Validation(ErrorValue::new(
+        ErrorCode::InvalidCredentials,
+        "The email or password is wrong",
+    ))
+}
+
+/// `password` hashed the way `[security.password_hashing]` says
+fn hash(config: &AppConfig, password: &str) -> AppResult<String> {
+    config.get_password_hasher().hash(password).map_err(|e| {
+        AppError::Serialization(
+            ErrorValue::new(ErrorCode::InternalError, "Failed to hash password").with_cause(e),
+        )
+    })
+}
+
+/// Hash of a password nobody has, checked when there is no stored hash to
+/// check against so that a sign-in takes as long whether or not the email
+/// is known
+static DUMMY_HASH: Mutex<Option<String>> = Mutex::new(None);
+
+/// Spend the time verifying `password` would take, with the configured
+/// algorithm and cost
+fn verify_dummy(config: &AppConfig, password: &str) {
+    let dummy = {
+        let mut dummy = DUMMY_HASH.lock().unwrap_or_else(|e| e.into_inner());
+        let hasher = config.get_password_hasher();
+        if !dummy.as_deref().is_some_and(|h| hasher.is_current(h)) {
+            match hash(config, "no user has this password") {
+                Ok(h) => *dummy = Some(h),
+                Err(e) => warn!("Failed to make the dummy password hash: {}", e),
+            }
+        }
+        dummy.clone()
+    };
+    if let Some(dummy) = dummy {
+        PasswordUtils::verify_password(password, &dummy);
+    }
+}
+
+/// Count a failed sign-in for `user_id`, locking the account once it has
+/// failed `max_failed_logins` times in a row
+fn fail(db: &Database, config: &AppConfig, user_id: i64, email: &str, reason: &str) -> AppError {
+    security_audit::emit("login_failed", Some(user_id), email, Some(reason));
+    let max = config.get_max_failed_logins();
+    match db.record_failed_login(user_id) {
+        Ok(attempts) if max > 0 && attempts >= max => {
+            let until = Utc::now().timestamp() + config.get_lockout_duration().as_secs() as i64;
+            match db.lock_account(user_id, until) {
+                Ok(()) => {
+                    warn!("Account {} locked after {} failed sign-ins", email, attempts);
+                    security_audit::emit("account_locked", Some(user_id), email, None);
+                }
+                Err(e) => warn!("Failed to lock account {}: {}", email, e),
+            }
+        }
+        Ok(_) => {}
+        Err(e) => warn!("Failed to count failed sign-in for {}: {}", email, e),
+    }
+    invalid_credentials()
+}
+
+/// Sign in with `email` and `password`, plus a two-factor `code` when the
+/// user has two-factor sign-in on
+pub fn login(
+    db: &Database,
+    config: &AppConfig,
+    sessions: &SessionManager,
+    email: &str,
+    password: &str,
+    code: Option<&str>,
+) -> AppResult<SessionToken> {
+    let email = email.trim();
+    let Some(user) = db.get_user_by_email(email)? else {
+        verify_dummy(config, password);
+        security_audit::emit("login_failed", None, email, Some("unknown email"));
+        return Err(invalid_credentials());
+    };
+    let Some(credentials) = db.credentials(user.id)? else {
+        verify_dummy(config, password);
+        security_audit::emit("login_failed", Some(user.id), email, Some("no password set"));
+        return Err(invalid_credentials());
+    };
+
+    let now = Utc::now().timestamp();
+    if let Some(until) = credentials.locked_until.filter(|&until| until > now) {
+        security_audit::emit("login_failed", Some(user.id), email, Some("account locked"));
+        return Err(AppError::Validation(
+            ErrorValue::new(ErrorCode::AccountLocked, "Too many failed sign-ins; try again later")
+                .with_context("retry_after_secs", (until - now).to_string()),
+        ));
+    }
+    if !PasswordUtils::verify_password(password, &credentials.password_hash) {
+        return Err(fail(db, config, user.id, email, "wrong password"));
+    }
+    if user.status != "Active" {
+        security_audit::emit("login_failed", Some(user.id), email, Some("account inactive"));
+        return Err(AppError::Validation(ErrorValue::new(
+            ErrorCode::Unauthorized,
+            "This account is inactive",
+        )));
+    }
+    match two_factor::verify(db, config, user.id, code) {
+        Ok(()) => {}
+        // Password was right; the frontend asks for the code and tries again
+        Err(e) if e.to_value().code == ErrorCode::TwoFactorRequired => return Err(e),
+        Err(e) => {
+            fail(db, config, user.id, email, "wrong two-factor code");
+            return Err(e);
+        }
+    }
+
+    db.clear_failed_logins(user.id)?;
+    if !config.get_password_hasher().is_current(&credentials.password_hash) {
+        // Move the stored hash to the configured algorithm while the
+        // password is at hand; a failure leaves the old, still valid hash
+        match hash(config, password).and_then(|new| db.set_password_hash(user.id, &new)) {
+            Ok(()) => info!("Password hash of {} upgraded", email),
+            Err(e) => warn!("Failed to upgrade password hash of {}: {}", email, e),
+        }
+    }
+    let token = sessions.issue(user.id, &user.role)?;
+    security_audit::emit("login", Some(user.id), email, None);
+    info!("User {} signed in", email);
+    Ok(token)
+}
+
+/// End `session`
+pub fn logout(sessions: &SessionManager, session: &SessionInfo) -> AppResult<bool> {
+    let closed = sessions.revoke(&session.session_id)?;
+    security_audit::emit("logout", Some(session.user_id), &session.session_id, None);
+    Ok(closed)
+}
+
+/// Set `user_id`'s password. With `current_password` the user changes their
+/// own and it must match; without, it is an administrator's reset. Every
+/// session of the user ends, so they sign in again with the new password;
+/// returns how many were open. The new password must pass
+/// `[security.password_policy]`.
+pub fn change_password(
+    db: &Database,
+    config: &AppConfig,
+    sessions: &SessionManager,
+    user_id: i64,
+    current_password: Option<&str>,
+    new_password: &str,
+) -> AppResult<usize> {
+    let user = db.get_user_by_id(user_id)?.or_not_found("User", user_id)?;
+    if new_password.is_empty() {
+        return Err(AppError::Validation(
+            ErrorValue::new(ErrorCode::MissingRequiredField, "New password is required")
+                .with_field("new_password"),
+        ));
+    }
+    config.get_password_policy().validate(new_password, "new_password")?;
+    if let Some(current) = current_password {
+        let matches = db
+            .credentials(user_id)?
+            .is_some_and(|c| PasswordUtils::verify_password(current, &c.password_hash));
+        if !matches {
+            security_audit::emit("password_change_failed", Some(user_id), &user.email, None);
+            return Err(AppError::Validation(
+                ErrorValue::new(ErrorCode::InvalidCredentials, "The current password is wrong")
+                    .with_field("current_password"),
+            ));
+        }
+    }
+
+    db.set_password_hash(user_id, &hash(config, new_password)?)?;
+    let closed = sessions.revoke_user(user_id)?;
+    let detail = match current_password {
+        Some(_) => "changed by the user",
+        None => "reset by an administrator",
+    };
+    security_audit::emit("password_changed", Some(user_id), &user.email, Some(detail));
+    info!("Password of {} {}; {} sessions signed out", user.email, detail, closed);
+    Ok(closed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::infrastructure::config::SecuritySettings;
+    use crate::core::infrastructure::database::AuditFilter;
+    use crate::core::infrastructure::event_bus::GLOBAL_EVENT_BUS;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn code_for(err: AppError) -> ErrorCode {
+        err.to_value().code.clone()
+    }
+
+    #[test]
+    fn test_login_lockout_and_password_change() {
+        let db = Arc::new(Database::new(":memory:").expect("Failed to create database"));
+        db.init().expect("Failed to init schema");
+        db.init_two_factor().expect("Failed to init two factor");
+        db.init_credentials().expect("Failed to init credentials");
+        db.init_audit_log().expect("Failed to init audit log");
+        let _persisted = security_audit::persist(&GLOBAL_EVENT_BUS, Arc::clone(&db));
+        let config = AppConfig {
+            security: Some(SecuritySettings {
+                max_failed_logins: Some(2),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let sessions = SessionManager::new(
+            b"test-key",
+            Duration::from_secs(60),
+            Duration::from_secs(3600),
+        );
+        let user = db
+            .insert_user("Ada", "ada@example.com", "Admin", "Active")
+            .expect("Failed to insert user");
+
+        let login_with = |password: &str| {
+            login(&db, &config, &sessions, "ada@example.com", password, None)
+        };
+        let set_password = |current: Option<&str>, new: &str| {
+            change_password(&db, &config, &sessions, user, current, new)
+        };
+
+        let no_password = login_with("first password").unwrap_err();
+        assert_eq!(code_for(no_password), ErrorCode::InvalidCredentials);
+
+        let weak = set_password(None, "password").unwrap_err();
+        assert_eq!(code_for(weak), ErrorCode::ValidationFailed);
+        set_password(None, "first password").unwrap();
+        let token = login_with("first password").unwrap();
+        assert_eq!(sessions.validate(&token.token).unwrap().user_id, user);
+
+        let wrong = set_password(Some("nope"), "second password").unwrap_err();
+        assert_eq!(code_for(wrong), ErrorCode::InvalidCredentials);
+        set_password(Some("first password"), "second password").unwrap();
+        // Changing the password signs the user out
+        assert!(sessions.validate(&token.token).is_err());
+
+        for _ in 0..2 {
+            let failed = login_with("bad").unwrap_err();
+            assert_eq!(code_for(failed), ErrorCode::InvalidCredentials);
+        }
+        let locked = login_with("second password").unwrap_err();
+        assert_eq!(code_for(locked), ErrorCode::AccountLocked);
+
+        GLOBAL_EVENT_BUS.flush();
+        let events: Vec<String> = db
+            .audit_entries(&AuditFilter {
+                subject: Some("ada@example.com".to_string()),
+                ..Default::default()
+            })
+            .unwrap()
+            .into_iter()
+            .rev()
+            .map(|e| e.event)
+            .collect();
+        assert_eq!(
+            events,
+            [
+                "login_failed",
+                "password_changed",
+                "login",
+                "password_change_failed",
+                "password_changed",
+                "login_failed",
+                "login_failed",
+                "account_locked",
+                "login_failed",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_login_upgrades_hash_to_configured_algorithm() {
+        use crate::utils::crypto::{BcryptHasher, PasswordHasher};
+
+        let db = Database::new(":memory:").expect("Failed to create database");
+        db.init().expect("Failed to init schema");
+        db.init_two_factor().expect("Failed to init two factor");
+        db.init_credentials().expect("Failed to init credentials");
+        let config = AppConfig::default();
+        let sessions = SessionManager::new(
+            b"test-key",
+            Duration::from_secs(60),
+            Duration::from_secs(3600),
+        );
+        let user = db
+            .insert_user("Bob", "bob@example.com", "User", "Active")
+            .expect("Failed to insert user");
+        let legacy = BcryptHasher { cost: 4 }.hash("legacy password").unwrap();
+        db.set_password_hash(user, &legacy).unwrap();
+
+        login(&db, &config, &sessions, "bob@example.com", "legacy password", None).unwrap();
+        let stored = db.credentials(user).unwrap().unwrap().password_hash;
+        assert!(stored.starts_with("$argon2id$"));
+        assert!(login(&db, &config, &sessions, "bob@example.com", "legacy password", None).is_ok());
+    }
+}
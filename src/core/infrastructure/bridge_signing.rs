@@ -0,0 +1,197 @@
+// src/core/infrastructure/bridge_signing.rs
+// Optional HMAC signing of frontend calls (`[security] sign_bridge_messages`).
+// A random key is made at startup and injected into the app's own page on
+// connect. The frontend then wraps each call's first argument as
+// `{ bridge_payload, bridge_nonce, bridge_signature }`, the signature being
+// HMAC-SHA256 over "<handler>\n<nonce>\n<payload>". `bind_traced` checks it
+// for the call it runs; session and permission checked handlers refuse calls
+// whose signature is missing, wrong or replayed.
+
+use hmac::{Hmac, Mac};
+use log::warn;
+use sha2::Sha256;
+use std::cell::RefCell;
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::core::error::{ErrorCode, ErrorValue};
+
+/// Nonces remembered to refuse replays; the oldest are forgotten first
+const SEEN_NONCES: usize = 4096;
+
+#[derive(Default)]
+struct SeenNonces {
+    order: VecDeque<String>,
+    set: HashSet<String>,
+}
+
+pub struct BridgeSigner {
+    key: [u8; 32],
+    seen: Mutex<SeenNonces>,
+}
+
+impl BridgeSigner {
+    pub fn new(key: [u8; 32]) -> Self {
+        Self {
+            key,
+            seen: Mutex::new(SeenNonces::default()),
+        }
+    }
+
+    /// Key handed to the frontend, hex encoded
+    pub fn key_hex(&self) -> String {
+        hex::encode(self.key)
+    }
+
+    fn mac(&self, handler: &str, nonce: &str, payload: Option<&str>) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC takes any key size");
+        mac.update(format!("{}\n{}\n{}", handler, nonce, payload.unwrap_or("")).as_bytes());
+        mac
+    }
+
+    /// Hex signature of a call, as the frontend computes it
+    #[allow(dead_code)]
+    pub fn sign(&self, handler: &str, nonce: &str, payload: Option<&str>) -> String {
+        hex::encode(self.mac(handler, nonce, payload).finalize().into_bytes())
+    }
+
+    /// Check the first argument of a call to `handler`
+    pub fn verify(&self, handler: &str, raw: Option<&str>) -> Result<(), ErrorValue> {
+        let signed = raw.and_then(SignedPayload::parse).ok_or_else(|| rejected(handler))?;
+        let signature = hex::decode(&signed.signature).map_err(|_| rejected(handler))?;
+        self.mac(handler, &signed.nonce, signed.payload.as_deref())
+            .verify_slice(&signature)
+            .map_err(|_| rejected(handler))?;
+
+        let mut seen = self.seen.lock().map_err(|_| rejected(handler))?;
+        if !seen.set.insert(signed.nonce.clone()) {
+            warn!("Replayed bridge signature for '{}'", handler);
+            return Err(rejected(handler));
+        }
+        seen.order.push_back(signed.nonce);
+        if seen.order.len() > SEEN_NONCES {
+            if let Some(oldest) = seen.order.pop_front() {
+                seen.set.remove(&oldest);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The wrapped first argument of a signed call
+struct SignedPayload {
+    payload: Option<String>,
+    nonce: String,
+    signature: String,
+}
+
+impl SignedPayload {
+    fn parse(raw: &str) -> Option<Self> {
+        let value: serde_json::Value = serde_json::from_str(raw).ok()?;
+        let object = value.as_object()?;
+        let field = |name: &str| object.get(name)?.as_str().map(str::to_string);
+        Some(Self {
+            signature: field("bridge_signature")?,
+            nonce: field("bridge_nonce")?,
+            payload: field("bridge_payload"),
+        })
+    }
+}
+
+fn rejected(handler: &str) -> ErrorValue {
+    ErrorValue::new(ErrorCode::Unauthorized, "This call isn't signed by the app")
+        .with_context("handler", handler)
+}
+
+/// The first argument as the handler should read it: the wrapped payload of
+/// a signed call (`None` when the call had no argument), else `raw` itself
+pub fn unwrap_payload(raw: String) -> Option<String> {
+    match SignedPayload::parse(&raw) {
+        Some(signed) => signed.payload,
+        None => Some(raw),
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref GLOBAL_SIGNER: RwLock<Option<Arc<BridgeSigner>>> = RwLock::new(None);
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Result<(), ErrorValue>>> = const { RefCell::new(None) };
+}
+
+/// Turn signing on with a new random key, or off
+pub fn configure(enabled: bool) {
+    let signer = enabled.then(|| Arc::new(BridgeSigner::new(rand::random())));
+    if let Ok(mut current) = GLOBAL_SIGNER.write() {
+        *current = signer;
+    }
+}
+
+/// The signer when `[security] sign_bridge_messages` is on
+pub fn active() -> Option<Arc<BridgeSigner>> {
+    GLOBAL_SIGNER.read().ok().and_then(|signer| signer.clone())
+}
+
+/// Restores the previous call's result when dropped
+#[must_use = "the signature check is forgotten as soon as the guard is dropped"]
+pub struct CallGuard {
+    previous: Option<Result<(), ErrorValue>>,
+}
+
+impl Drop for CallGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Check the signature of the call to `handler` this thread is servicing,
+/// for `check_current` to report until the guard is dropped
+pub fn enter(handler: &str, raw: Option<&str>) -> CallGuard {
+    let result = active().map(|signer| signer.verify(handler, raw));
+    let previous = CURRENT.with(|current| current.replace(result));
+    CallGuard { previous }
+}
+
+/// Ok when signing is off or the call being serviced was signed
+pub fn check_current(handler: &str) -> Result<(), ErrorValue> {
+    if active().is_none() {
+        return Ok(());
+    }
+    CURRENT.with(|current| current.borrow().clone().unwrap_or_else(|| Err(rejected(handler))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signed(signer: &BridgeSigner, handler: &str, nonce: &str, payload: &str) -> String {
+        serde_json::json!({
+            "bridge_payload": payload,
+            "bridge_nonce": nonce,
+            "bridge_signature": signer.sign(handler, nonce, Some(payload)),
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_verify_signed_calls() {
+        let signer = BridgeSigner::new([7; 32]);
+        let call = signed(&signer, "settings_set", "n1", r#"{"key":"a"}"#);
+        assert!(signer.verify("settings_set", Some(&call)).is_ok());
+        // Same nonce again
+        let error = signer.verify("settings_set", Some(&call)).unwrap_err();
+        assert_eq!(error.code, ErrorCode::Unauthorized);
+
+        let other_handler = signed(&signer, "settings_get", "n2", "{}");
+        assert!(signer.verify("settings_set", Some(&other_handler)).is_err());
+        let forged = signed(&BridgeSigner::new([8; 32]), "settings_set", "n3", "{}");
+        assert!(signer.verify("settings_set", Some(&forged)).is_err());
+        assert!(signer.verify("settings_set", Some("{}")).is_err());
+        assert!(signer.verify("settings_set", None).is_err());
+
+        assert_eq!(unwrap_payload(call).as_deref(), Some(r#"{"key":"a"}"#));
+        assert_eq!(unwrap_payload("plain".into()).as_deref(), Some("plain"));
+    }
+}
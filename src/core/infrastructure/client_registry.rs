@@ -0,0 +1,201 @@
+// src/core/infrastructure/client_registry.rs
+// Registry of connected frontend clients (WebView windows and their connections)
+
+#![allow(dead_code)]
+
+use chrono::Utc;
+use log::info;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use crate::utils::serialization::SerializationFormat;
+
+/// A window the backend can push to; `connections` counts the WebView and
+/// any remote browsers currently attached to it
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ClientInfo {
+    pub window_id: usize,
+    pub label: String,
+    pub connections: usize,
+    pub registered_at: i64,
+    pub last_seen: i64,
+}
+
+impl ClientInfo {
+    pub fn is_connected(&self) -> bool {
+        self.connections > 0
+    }
+}
+
+pub struct ClientRegistry {
+    clients: Mutex<BTreeMap<usize, ClientInfo>>,
+    /// Response format each window negotiated; others get the codec default
+    formats: Mutex<HashMap<usize, SerializationFormat>>,
+}
+
+impl ClientRegistry {
+    pub fn new() -> Self {
+        Self {
+            clients: Mutex::new(BTreeMap::new()),
+            formats: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The response format `window_id` negotiated, if it did
+    pub fn format(&self, window_id: usize) -> Option<SerializationFormat> {
+        self.formats
+            .lock()
+            .ok()
+            .and_then(|formats| formats.get(&window_id).copied())
+    }
+
+    /// Encode responses to `window_id` as `format` until its page goes away
+    pub fn set_format(&self, window_id: usize, format: SerializationFormat) {
+        if let Ok(mut formats) = self.formats.lock() {
+            formats.insert(window_id, format);
+        }
+    }
+
+    fn clear_format(&self, window_id: usize) {
+        if let Ok(mut formats) = self.formats.lock() {
+            formats.remove(&window_id);
+        }
+    }
+
+    /// Register a window before it is shown
+    pub fn register_window(&self, window_id: usize, label: impl Into<String>) {
+        let now = Utc::now().timestamp_millis();
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.insert(
+                window_id,
+                ClientInfo {
+                    window_id,
+                    label: label.into(),
+                    connections: 0,
+                    registered_at: now,
+                    last_seen: now,
+                },
+            );
+        }
+    }
+
+    pub fn on_connected(&self, window_id: usize) {
+        let now = Utc::now().timestamp_millis();
+        if let Ok(mut clients) = self.clients.lock() {
+            let client = clients.entry(window_id).or_insert_with(|| ClientInfo {
+                window_id,
+                label: format!("window-{}", window_id),
+                connections: 0,
+                registered_at: now,
+                last_seen: now,
+            });
+            client.connections += 1;
+            client.last_seen = now;
+            info!(
+                "Client connected to window {} ({} connection(s))",
+                window_id, client.connections
+            );
+        }
+    }
+
+    /// Returns true when the last connection of the window went away; the
+    /// next page in it negotiates its format again
+    pub fn on_disconnected(&self, window_id: usize) -> bool {
+        let gone = {
+            let Ok(mut clients) = self.clients.lock() else {
+                return false;
+            };
+            let Some(client) = clients.get_mut(&window_id) else {
+                return false;
+            };
+            client.connections = client.connections.saturating_sub(1);
+            client.last_seen = Utc::now().timestamp_millis();
+            info!(
+                "Client disconnected from window {} ({} connection(s) left)",
+                window_id, client.connections
+            );
+            client.connections == 0
+        };
+        if gone {
+            self.clear_format(window_id);
+        }
+        gone
+    }
+
+    pub fn unregister_window(&self, window_id: usize) {
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.remove(&window_id);
+        }
+        self.clear_format(window_id);
+    }
+
+    /// Window ids with at least one live connection
+    pub fn connected_windows(&self) -> Vec<usize> {
+        self.clients
+            .lock()
+            .map(|clients| {
+                clients
+                    .values()
+                    .filter(|c| c.is_connected())
+                    .map(|c| c.window_id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn clients(&self) -> Vec<ClientInfo> {
+        self.clients
+            .lock()
+            .map(|clients| clients.values().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Default for ClientRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+lazy_static::lazy_static! {
+    pub static ref GLOBAL_CLIENT_REGISTRY: ClientRegistry = ClientRegistry::new();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_tracking() {
+        let registry = ClientRegistry::new();
+        registry.register_window(1, "main");
+        registry.register_window(2, "settings");
+        assert!(registry.connected_windows().is_empty());
+
+        registry.on_connected(1);
+        registry.on_connected(1);
+        registry.on_connected(2);
+        assert_eq!(registry.connected_windows(), vec![1, 2]);
+
+        assert!(!registry.on_disconnected(1));
+        assert!(registry.on_disconnected(2));
+        assert_eq!(registry.connected_windows(), vec![1]);
+        assert_eq!(registry.clients().len(), 2);
+    }
+
+    #[test]
+    fn test_formats_are_per_window() {
+        let registry = ClientRegistry::new();
+        registry.on_connected(1);
+        registry.on_connected(2);
+        registry.set_format(1, SerializationFormat::Cbor);
+        assert_eq!(registry.format(1), Some(SerializationFormat::Cbor));
+        assert_eq!(registry.format(2), None);
+
+        // A reloaded page starts over
+        assert!(registry.on_disconnected(1));
+        assert_eq!(registry.format(1), None);
+    }
+}
@@ -0,0 +1,173 @@
+// src/core/infrastructure/codec.rs
+// Shared response codec - encodes handler responses in the format of the
+// window they go to
+
+#![allow(dead_code)]
+
+use log::warn;
+use std::sync::{Arc, Mutex};
+
+use super::client_registry::GLOBAL_CLIENT_REGISTRY;
+use super::di;
+use crate::utils::serialization::{self, SerializationFormat};
+
+/// Encodes CustomEvent details in the serialization format of the receiving
+/// window: the one it negotiated, else the codec's default.
+///
+/// Clones share the default format, so the instance registered in the DI
+/// container can be switched at runtime and every handler picks it up.
+#[derive(Clone)]
+pub struct ResponseCodec {
+    format: Arc<Mutex<SerializationFormat>>,
+    /// Encoded payloads larger than this are gzip-compressed; 0 disables
+    compression_threshold: usize,
+}
+
+impl ResponseCodec {
+    pub fn new(format: SerializationFormat) -> Self {
+        Self {
+            format: Arc::new(Mutex::new(format)),
+            compression_threshold: 0,
+        }
+    }
+
+    pub fn with_compression_threshold(mut self, bytes: usize) -> Self {
+        self.compression_threshold = bytes;
+        self
+    }
+
+    /// The format windows that haven't negotiated get
+    pub fn format(&self) -> SerializationFormat {
+        self.format
+            .lock()
+            .map(|format| *format)
+            .unwrap_or(SerializationFormat::Json)
+    }
+
+    pub fn set_format(&self, format: SerializationFormat) {
+        if let Ok(mut current) = self.format.lock() {
+            *current = format;
+        }
+        SerializationFormat::set_selected(format);
+    }
+
+    /// The format responses to `window_id` are encoded in
+    pub fn format_for(&self, window_id: usize) -> SerializationFormat {
+        GLOBAL_CLIENT_REGISTRY
+            .format(window_id)
+            .unwrap_or_else(|| self.format())
+    }
+
+    /// Encode responses to `window_id` as `format`; other windows keep theirs
+    pub fn set_window_format(&self, window_id: usize, format: SerializationFormat) {
+        GLOBAL_CLIENT_REGISTRY.set_format(window_id, format);
+    }
+
+    /// Encode the detail of the `event_name` response to `window_id`.
+    ///
+    /// Small JSON details are passed through unchanged; binary formats are
+    /// wrapped as `{ format, encoding: "base64", payload }` for the frontend to
+    /// decode. Payloads above the compression threshold are gzipped first and
+    /// flagged with `compression: "gzip"`.
+    pub fn encode(
+        &self,
+        window_id: usize,
+        event_name: &str,
+        detail: &serde_json::Value,
+    ) -> serde_json::Value {
+        let format = self.format_for(window_id);
+        let bytes = match serialization::serialize_bytes(detail, format) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!("Failed to encode response as {}, sending JSON: {}", format, e);
+                return detail.clone();
+            }
+        };
+
+        match serialization::compress_payload(event_name, &bytes, self.compression_threshold) {
+            Some(compressed) => serde_json::json!({
+                "format": format.name(),
+                "encoding": "base64",
+                "compression": "gzip",
+                "payload": serialization::base64_encode(&compressed),
+            }),
+            None if format == SerializationFormat::Json => detail.clone(),
+            None => serde_json::json!({
+                "format": format.name(),
+                "encoding": "base64",
+                "payload": serialization::base64_encode(&bytes),
+            }),
+        }
+    }
+}
+
+impl Default for ResponseCodec {
+    fn default() -> Self {
+        Self::new(SerializationFormat::Json)
+    }
+}
+
+/// The codec registered in the DI container, or a JSON codec if none is
+pub fn shared() -> ResponseCodec {
+    di::get_container()
+        .resolve::<ResponseCodec>()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::compression::CompressionUtils;
+
+    // Window ids no other test registers
+    const WINDOW: usize = 9101;
+    const OTHER_WINDOW: usize = 9102;
+
+    #[test]
+    fn test_codec_clones_share_format() {
+        let codec = ResponseCodec::default();
+        let clone = codec.clone();
+        let detail = serde_json::json!({ "success": true, "data": 1 });
+
+        assert_eq!(clone.encode(WINDOW, "test_response", &detail), detail);
+
+        codec.set_format(SerializationFormat::Cbor);
+        let encoded = clone.encode(WINDOW, "test_response", &detail);
+        assert_eq!(encoded["format"], "cbor");
+        assert_eq!(encoded["encoding"], "base64");
+
+        let payload = encoded["payload"].as_str().unwrap();
+        let decoded: serde_json::Value =
+            serialization::deserialize(payload, SerializationFormat::Cbor).unwrap();
+        assert_eq!(decoded, detail);
+        codec.set_format(SerializationFormat::Json);
+    }
+
+    #[test]
+    fn test_window_format_leaves_other_windows_alone() {
+        let codec = ResponseCodec::default();
+        let detail = serde_json::json!({ "success": true, "data": 1 });
+
+        codec.set_window_format(OTHER_WINDOW, SerializationFormat::MessagePack);
+        assert_eq!(codec.encode(OTHER_WINDOW, "test_response", &detail)["format"], "messagepack");
+        assert_eq!(codec.encode(WINDOW + 2, "test_response", &detail), detail);
+        GLOBAL_CLIENT_REGISTRY.unregister_window(OTHER_WINDOW);
+        assert_eq!(codec.encode(OTHER_WINDOW, "test_response", &detail), detail);
+    }
+
+    #[test]
+    fn test_large_payloads_are_compressed() {
+        let codec = ResponseCodec::default().with_compression_threshold(256);
+        let detail = serde_json::json!({ "success": true, "data": "row ".repeat(1000) });
+
+        let encoded = codec.encode(WINDOW, "large_response", &detail);
+        assert_eq!(encoded["format"], "json");
+        assert_eq!(encoded["compression"], "gzip");
+
+        let compressed =
+            serialization::base64_decode(encoded["payload"].as_str().unwrap()).unwrap();
+        let bytes = CompressionUtils::decompress_gzip(&compressed).unwrap();
+        let decoded: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(decoded, detail);
+    }
+}
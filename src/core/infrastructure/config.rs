@@ -3,12 +3,45 @@
 
 #![allow(dead_code)]
 
-use serde::Deserialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 
-#[derive(Debug, Deserialize, Clone)]
+use super::remote_config;
+use super::sanitize;
+use crate::core::domain::events::SettingChange;
+use crate::core::error::{AppError, ErrorCode, ErrorValue};
+use crate::utils::serialization::SerializationFormat;
+use crate::utils::crypto::hashing::HASH_ALGORITHMS;
+use crate::utils::crypto::{Argon2Hasher, BcryptHasher, PasswordHasher, ScryptHasher};
+use crate::utils::network::NetworkUtils;
+use crate::utils::validation::PasswordPolicy;
+
+/// Environment variables with this prefix are layered over the config file,
+/// `__` separating the section from the key: `RUSTWEBUI__DATABASE__PATH`
+const ENV_PREFIX: &str = "RUSTWEBUI__";
+
+/// Command-line overrides (`--db-path` -> `database.path`), applied over the
+/// file and environment on every load, hot reloads included
+static ARG_OVERRIDES: OnceLock<Vec<(String, toml::Value)>> = OnceLock::new();
+
+/// Install the command-line overrides; call once, before the first `load`.
+/// Returns `false` if they were already set.
+pub fn set_arg_overrides(overrides: Vec<(String, toml::Value)>) -> bool {
+    ARG_OVERRIDES.set(overrides).is_ok()
+}
+
+pub const LOG_LEVELS: [&str; 5] = ["trace", "debug", "info", "warn", "error"];
+pub const TRANSPORTS: [&str; 3] = ["webview_ffi", "http_rest", "websocket"];
+pub const LOG_SINK_KINDS: [&str; 4] = ["syslog", "tcp", "udp", "http"];
+pub const CONSOLE_FORMATS: [&str; 2] = ["compact", "verbose"];
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
 pub struct AppConfig {
     pub app: AppSettings,
     pub executable: ExecutableSettings,
@@ -17,10 +50,21 @@ pub struct AppConfig {
     pub logging: LoggingSettings,
     pub communication: CommunicationSettings,
     pub features: FeatureSettings,
+    pub remote: Option<RemoteSettings>,
+    pub dev: Option<DevSettings>,
+    pub event_bus: Option<EventBusSettings>,
+    pub secrets: Option<SecretsSettings>,
+    pub remote_config: Option<RemoteConfigSettings>,
+    pub security: Option<SecuritySettings>,
+    pub filesystem: Option<FilesystemSettings>,
+    pub network: Option<NetworkSettings>,
+    /// `[plugins.<id>]` tables, kept as-is for each plugin to interpret
+    pub plugins: Option<HashMap<String, serde_json::Value>>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
 pub struct AppSettings {
+    /// Application name shown in the UI and logs
     pub name: String,
     pub version: String,
     pub description: Option<String>,
@@ -28,41 +72,329 @@ pub struct AppSettings {
     pub website: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
 pub struct ExecutableSettings {
+    /// Binary name; also the default keyring service for secrets
     pub name: String,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
 pub struct DatabaseSettings {
+    /// SQLite database file, relative to the executable or absolute
     pub path: String,
+    /// Insert sample users when the database is empty
     pub create_sample_data: Option<bool>,
+    /// Record user mutations as domain events for audit and undo
+    pub event_sourcing: Option<bool>,
+    /// Snapshot an entity's state every N events (0 = never)
+    pub snapshot_interval: Option<i64>,
+    /// Also keep per-handler error counts in the error_stats table, so the
+    /// `error_stats` handler can look back across restarts
+    pub persist_error_stats: Option<bool>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
 pub struct WindowSettings {
     pub title: String,
+    /// Initial size in pixels
     pub width: Option<u32>,
     pub height: Option<u32>,
+    /// Smallest size the window can be resized to
     pub min_width: Option<u32>,
     pub min_height: Option<u32>,
     pub resizable: Option<bool>,
+    /// Left edge in screen pixels; the window is centered unless `x` and `y`
+    /// are both set
+    pub x: Option<u32>,
+    pub y: Option<u32>,
+    pub maximized: Option<bool>,
+    /// Not supported by WebUI yet; logged and ignored
+    pub always_on_top: Option<bool>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
 pub struct LoggingSettings {
+    /// One of: trace, debug, info, warn, error
     pub level: String,
+    /// Log file, relative to the executable or absolute
     pub file: String,
+    /// Append to the log file instead of overwriting it
     pub append: Option<bool>,
+    /// Latest records kept in memory for the log viewer (0 disables it)
+    pub buffer_size: Option<usize>,
+    /// Handler calls and database operations taking at least this many
+    /// milliseconds are logged as warnings
+    pub slow_span_ms: Option<u64>,
+    /// Where records are forwarded besides the log file
+    pub sinks: Option<Vec<LogSinkSettings>>,
+    /// Where panic reports are written; defaults to "crashes" next to the
+    /// log file
+    pub crash_dir: Option<String>,
+    /// Where crash reports are POSTed once the user agrees to submit them
+    pub crash_report_url: Option<String>,
+    /// Records waiting for the log writer thread before debug and info
+    /// records are dropped (default 8192)
+    pub queue_size: Option<usize>,
+    /// How records are printed to the console
+    pub console: Option<ConsoleSettings>,
+    /// What is masked in log messages
+    pub redaction: Option<RedactionSettings>,
+}
+
+/// `[logging.redaction]`: masks user data and credentials in log messages
+/// before they are written, shown in the log viewer or shipped
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct RedactionSettings {
+    /// Turns every rule below off when false (default true)
+    pub enabled: Option<bool>,
+    /// Mask email addresses (default true)
+    pub emails: Option<bool>,
+    /// Mask `password=`, `token: ..`, `api_key` style values, bearer
+    /// tokens and JWTs (default true)
+    pub tokens: Option<bool>,
+    /// More regular expressions whose matches are masked, e.g. phone numbers
+    pub patterns: Option<Vec<String>>,
+}
+
+/// `[logging.console]`: console output while developing, e.g. under
+/// `cargo run`. The log file is always JSON.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct ConsoleSettings {
+    /// ANSI colors per level; by default on when stdout is a terminal and
+    /// NO_COLOR is not set
+    pub colors: Option<bool>,
+    /// Prefix each line with the local time (default false)
+    pub timestamps: Option<bool>,
+    /// "compact" (default): level, last module segment and message;
+    /// "verbose": adds the full module path, file:line and thread
+    pub format: Option<String>,
+}
+
+/// A `[[logging.sinks]]` entry: forward log records to syslog, a TCP or UDP
+/// collector, or an HTTP ingest URL, in batches
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct LogSinkSettings {
+    /// One of: syslog, tcp, udp, http
+    pub kind: String,
+    /// host:port for tcp and udp; for syslog a host:port (UDP, default
+    /// 127.0.0.1:514) or a local socket path such as /dev/log
+    pub address: Option<String>,
+    /// Ingest URL records are POSTed to as a JSON array (http only)
+    pub url: Option<String>,
+    /// HTTP headers such as Authorization; "secret:<name>" values are read
+    /// from the keyring
+    pub headers: Option<HashMap<String, String>>,
+    /// Least severe level forwarded; defaults to logging.level
+    pub level: Option<String>,
+    /// Records sent together (default 100)
+    pub batch_size: Option<usize>,
+    /// Longest a record waits for its batch to fill, in ms (default 1000)
+    pub flush_interval_ms: Option<u64>,
+    /// Retries of a failed batch before it is dropped (default 3)
+    pub max_retries: Option<u32>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
 pub struct CommunicationSettings {
+    /// One of: webview_ffi, http_rest, websocket
     pub transport: Option<String>,
+    /// Frontend message format: json, messagepack, cbor
     pub serialization: Option<String>,
+    /// Responses larger than this many bytes are gzip-compressed; 0 disables
+    pub compression_threshold: Option<usize>,
+    pub backpressure: Option<BackpressureSettings>,
+    pub limits: Option<PayloadLimitSettings>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct BackpressureSettings {
+    /// Pending pushes before the frontend is considered congested
+    pub max_queue_depth: Option<usize>,
+    /// Pushes delivered but not yet acknowledged by the frontend
+    pub max_in_flight: Option<usize>,
+    /// Event name -> policy ("buffer", "drop_oldest", "sample:<n>")
+    pub policies: Option<HashMap<String, String>>,
+}
+
+/// Largest payloads accepted from / pushed to the frontend, in bytes
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct PayloadLimitSettings {
+    pub max_request_bytes: Option<usize>,
+    pub max_response_bytes: Option<usize>,
+    /// Longest string accepted in a request field; 65536 by default
+    pub max_string_chars: Option<usize>,
+    /// Limits for particular fields, e.g. `"create_user.name" = 100` for one
+    /// handler or `name = 100` for every handler; the user name (100) and
+    /// email (254) have one by default
+    pub field_max_chars: Option<HashMap<String, usize>>,
+}
+
+/// Per-subscriber queue limits of the backend event bus
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct EventBusSettings {
+    /// Events queued per subscriber before its overflow strategy applies
+    pub queue_capacity: Option<usize>,
+    /// Default overflow strategy ("block", "drop_oldest", "coalesce")
+    pub overflow: Option<String>,
+    /// Event type -> overflow strategy
+    pub overflow_by_event: Option<HashMap<String, String>>,
+    /// Topics forwarded to the frontend as `bus:<topic>` CustomEvents
+    pub bridge_topics: Option<Vec<String>>,
+}
+
+/// LAN serving: the app listens on `bind_address` through an access-controlled
+/// gateway instead of only on localhost
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct RemoteSettings {
+    pub enabled: Option<bool>,
+    pub bind_address: Option<String>,
+    pub port: Option<u16>,
+    /// Access token clients must send; "secret:<name>" reads it from the keyring
+    pub token: Option<String>,
+    /// Client IPs or CIDR ranges allowed to connect
+    pub allowed_ips: Option<Vec<String>>,
+    /// Most clients served at once; more are turned away with 503
+    pub max_connections: Option<usize>,
+}
+
+/// Values such as `remote.token = "secret:remote_token"` are read from the
+/// OS keyring entry `remote_token` under `service`
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct SecretsSettings {
+    /// Keyring service name; defaults to the executable name
+    pub service: Option<String>,
+    /// Encrypted file secrets are kept in when the OS keyring is
+    /// unavailable; `secrets.enc` in the user data directory by default,
+    /// "" to use the keyring only
+    pub fallback_file: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+/// Fleet-managed config fetched over HTTPS and merged over this file;
+/// environment variables and command-line options still win over it
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct RemoteConfigSettings {
+    /// HTTPS URL of a TOML or JSON config document
+    pub url: Option<String>,
+    /// HMAC-SHA256 key the document must be signed with (X-Config-Signature
+    /// header); "secret:<name>" reads it from the keyring
+    pub signing_key: Option<String>,
+    /// Where the last verified document is cached for offline starts
+    pub cache_file: Option<String>,
+    pub timeout_secs: Option<u64>,
+}
+
+/// Folders file handlers may read and write
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct FilesystemSettings {
+    /// Paths outside these folders are refused with PATH_NOT_ALLOWED; a
+    /// leading "~/" is the home directory. The user's Documents and
+    /// Downloads folders by default, [] to allow none.
+    pub allowed_roots: Option<Vec<String>>,
+}
+
+/// Backing services the app depends on and the proxy outgoing HTTP uses
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct NetworkSettings {
+    /// "host:port" services `network_diagnostics` checks when the caller
+    /// names none, e.g. ["api.example.com:443"]
+    pub diagnostics_targets: Option<Vec<String>>,
+    /// "system" (the default) uses HTTP(S)_PROXY or the OS proxy settings,
+    /// "none" connects directly, and a URL such as "http://proxy.corp:8080"
+    /// uses that proxy
+    pub proxy: Option<String>,
+    /// Hosts reached without the proxy, on top of the detected ones, e.g.
+    /// ["localhost", ".corp.example.com"]
+    pub no_proxy: Option<Vec<String>>,
+    pub proxy_username: Option<String>,
+    /// "secret:<name>" reads it from the keyring
+    pub proxy_password: Option<String>,
+}
+
+/// Handler sessions issued on login, two-factor sign-in and the role
+/// permissions handlers check
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct SecuritySettings {
+    /// Minutes a session token is accepted before it must be refreshed
+    pub session_ttl_minutes: Option<u64>,
+    /// Hours after login a session can no longer be refreshed
+    pub session_max_age_hours: Option<u64>,
+    /// HMAC key session tokens are signed with; "secret:<name>" reads it
+    /// from the keyring. A random key is used when empty.
+    pub session_signing_key: Option<String>,
+    /// Issuer name authenticator apps list two-factor accounts under;
+    /// defaults to the app name
+    pub totp_issuer: Option<String>,
+    /// 30 second steps a TOTP code may be early or late by; 1 by default
+    pub totp_drift_steps: Option<u32>,
+    /// Failed sign-ins in a row that lock an account; 5 by default, 0 never
+    /// locks
+    pub max_failed_logins: Option<u32>,
+    /// Minutes a locked account refuses sign-ins; 15 by default
+    pub lockout_minutes: Option<u64>,
+    /// What new passwords must satisfy
+    pub password_policy: Option<PasswordPolicySettings>,
+    /// Algorithm and parameters new password hashes are made with
+    pub password_hashing: Option<PasswordHashingSettings>,
+    /// Role of calls made without a session; "Admin" by default, which keeps
+    /// a single-user app working without a login. "" requires sign-in.
+    pub anonymous_role: Option<String>,
+    /// Permissions of each role, e.g. `User = ["users:read"]`; "*" grants
+    /// everything and "users:*" every users permission
+    pub roles: Option<HashMap<String, Vec<String>>>,
+    /// Require an HMAC, keyed per run, on calls to session and permission
+    /// checked handlers so other scripts in the WebView can't make them
+    pub sign_bridge_messages: Option<bool>,
+}
+
+/// `[security.password_policy]`; unset values keep `PasswordPolicy::default()`
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct PasswordPolicySettings {
+    pub min_length: Option<usize>,
+    pub require_lowercase: Option<bool>,
+    pub require_uppercase: Option<bool>,
+    pub require_digit: Option<bool>,
+    pub require_symbol: Option<bool>,
+    /// Refuse passwords from the built-in list of common ones
+    pub reject_common: Option<bool>,
+    /// Lowest strength score (0-4) accepted; 0 turns the check off
+    pub min_score: Option<u8>,
+}
+
+/// `[security.password_hashing]`. Stored hashes of another algorithm or
+/// older parameters still verify and are replaced at the next sign-in.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct PasswordHashingSettings {
+    /// One of: argon2 (default), bcrypt, scrypt
+    pub algorithm: Option<String>,
+    /// Argon2id memory in KiB; 19456 by default
+    pub argon2_memory_kib: Option<u32>,
+    /// Argon2id passes; 2 by default
+    pub argon2_iterations: Option<u32>,
+    /// Argon2id lanes; 1 by default
+    pub argon2_parallelism: Option<u32>,
+    /// bcrypt cost, 4 to 31; 12 by default
+    pub bcrypt_cost: Option<u32>,
+    /// scrypt log2 of N; 17 by default
+    pub scrypt_log_n: Option<u8>,
+    /// scrypt block size; 8 by default
+    pub scrypt_r: Option<u32>,
+    /// scrypt parallelism; 1 by default
+    pub scrypt_p: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct DevSettings {
+    /// Load the frontend from this dev server (e.g. rspack with HMR) instead of dist/
+    pub dev_server_url: Option<String>,
+    /// Where `--dev-certs` keeps the development CA and localhost
+    /// certificate; `certs` in the user data directory by default
+    pub cert_dir: Option<String>,
+    /// Names the localhost certificate is valid for; localhost, 127.0.0.1
+    /// and ::1 by default
+    pub cert_hosts: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
 pub struct FeatureSettings {
     pub dark_mode: Option<bool>,
     pub show_tray_icon: Option<bool>,
@@ -84,6 +416,9 @@ impl Default for AppConfig {
             database: DatabaseSettings {
                 path: String::from("app.db"),
                 create_sample_data: Some(true),
+                event_sourcing: None,
+                snapshot_interval: None,
+                persist_error_stats: None,
             },
             window: WindowSettings {
                 title: String::from("Rust WebUI Application"),
@@ -92,27 +427,125 @@ impl Default for AppConfig {
                 min_width: Some(800),
                 min_height: Some(600),
                 resizable: Some(true),
+                x: None,
+                y: None,
+                maximized: Some(false),
+                always_on_top: Some(false),
             },
             logging: LoggingSettings {
                 level: String::from("info"),
                 file: String::from("application.log"),
                 append: Some(true),
+                buffer_size: None,
+                slow_span_ms: None,
+                sinks: None,
+                crash_dir: None,
+                crash_report_url: None,
+                queue_size: None,
+                console: None,
+                redaction: None,
             },
             communication: CommunicationSettings {
                 transport: Some(String::from("webview_ffi")),
                 serialization: Some(String::from("json")),
+                compression_threshold: None,
+                backpressure: None,
+                limits: None,
             },
             features: FeatureSettings {
                 dark_mode: Some(true),
                 show_tray_icon: Some(false),
             },
+            remote: None,
+            dev: None,
+            event_bus: None,
+            secrets: None,
+            remote_config: None,
+            security: None,
+            filesystem: None,
+            network: None,
+            plugins: None,
         }
     }
 }
 
+/// The `AppConfig` one handler call works with. It is a scoped service,
+/// resolved once per call, so a hot reload in the middle of a call can't
+/// hand it the old settings for one step and the new ones for the next.
+#[derive(Debug, Clone)]
+pub struct CallConfig(Arc<AppConfig>);
+
+impl CallConfig {
+    pub fn new(config: Arc<AppConfig>) -> Self {
+        Self(config)
+    }
+}
+
+impl Deref for CallConfig {
+    type Target = AppConfig;
+
+    fn deref(&self) -> &AppConfig {
+        &self.0
+    }
+}
+
 impl AppConfig {
-    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        // Try to find config file
+    /// Load the config file, or the defaults when there is none, with the
+    /// environment and command-line overrides applied. A file that can't be read or parsed,
+    /// or holds invalid values, is an error listing every problem.
+    pub fn load() -> Result<Self, Vec<AppError>> {
+        if let Some(config_path) = Self::config_path() {
+            let config = Self::load_from(&config_path)?;
+            println!("Loaded configuration from: {}", config_path.display());
+            return Ok(config);
+        }
+
+        let config = toml::Value::try_from(AppConfig::default())
+            .map_err(|e| e.to_string())
+            .and_then(|defaults| Self::with_overrides(defaults).map_err(|e| e.to_string()))
+            .map_err(|e| {
+                vec![AppError::Configuration(
+                    ErrorValue::new(ErrorCode::ConfigInvalid, "Invalid configuration override")
+                        .with_cause(e)
+                        .with_context("prefix", ENV_PREFIX),
+                )]
+            })?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Parse config file `content` with the overrides applied
+    fn parse(content: &str) -> Result<Self, toml::de::Error> {
+        Self::with_overrides(toml::from_str(content)?)
+    }
+
+    /// Layer the remote config, then the environment, then the command line,
+    /// over `config`
+    fn with_overrides(mut config: toml::Value) -> Result<Self, toml::de::Error> {
+        let apply = |config: &mut toml::Value| {
+            apply_env_overrides(config, env::vars());
+            apply_arg_overrides(config, ARG_OVERRIDES.get().into_iter().flatten());
+        };
+        apply(&mut config);
+        let local: Self = config.clone().try_into()?;
+        match remote_config::fetch(&local) {
+            Ok(Some(remote)) => {
+                merge_tables(&mut config, remote);
+                apply(&mut config);
+                config.try_into()
+            }
+            Ok(None) => Ok(local),
+            Err(e) => {
+                eprintln!("Warning: Ignoring remote config: {}", e);
+                Ok(local)
+            }
+        }
+    }
+
+    /// The config file `load` reads: the first default location that exists,
+    /// then the one next to the executable, else the `APP_CONFIG` environment
+    /// variable
+    pub fn config_path() -> Option<PathBuf> {
         let config_paths = [
             "app.config.toml",
             "config/app.config.toml",
@@ -120,43 +553,409 @@ impl AppConfig {
             "./config/app.config.toml",
         ];
 
-        let mut config_content = None;
-        let mut config_path = String::new();
+        config_paths
+            .iter()
+            .map(PathBuf::from)
+            .chain(Self::executable_config_path())
+            .chain(env::var("APP_CONFIG").ok().map(PathBuf::from))
+            .find(|path| path.exists())
+    }
+
+    /// `app.config.toml` in the executable's directory, where the first run
+    /// writes its template
+    pub fn executable_config_path() -> Option<PathBuf> {
+        let exe = env::current_exe().ok()?;
+        Some(exe.parent()?.join("app.config.toml"))
+    }
+
+    /// Parse and validate the config file at `path`
+    pub fn load_from(path: &Path) -> Result<Self, Vec<AppError>> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            vec![AppError::Configuration(
+                ErrorValue::new(ErrorCode::ConfigNotFound, "Failed to read config file")
+                    .with_cause(e.to_string())
+                    .with_context("path", path.display().to_string()),
+            )]
+        })?;
+        Self::from_toml(&content, path)
+    }
 
-        for path in &config_paths {
-            if Path::new(path).exists() {
-                config_content = Some(fs::read_to_string(path)?);
-                config_path = path.to_string();
-                break;
+    /// Parse and validate config file `content` destined for `path`
+    pub fn from_toml(content: &str, path: &Path) -> Result<Self, Vec<AppError>> {
+        let config = Self::parse(content).map_err(|e| {
+            vec![AppError::Configuration(
+                ErrorValue::new(ErrorCode::ConfigInvalid, "Failed to parse config file")
+                    .with_cause(e.to_string())
+                    .with_context("path", path.display().to_string()),
+            )]
+        })?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check the values the getters would otherwise quietly replace with a
+    /// default; every violation is returned, not just the first
+    pub fn validate(&self) -> Result<(), Vec<AppError>> {
+        let mut errors = Vec::new();
+        let mut invalid = |field: &str, value: &str, message: String| {
+            errors.push(AppError::Validation(
+                ErrorValue::new(ErrorCode::InvalidFieldValue, message)
+                    .with_field(field)
+                    .with_context("value", value),
+            ));
+        };
+
+        let level = &self.logging.level;
+        if !LOG_LEVELS.contains(&level.to_lowercase().as_str()) {
+            invalid(
+                "logging.level",
+                level,
+                format!(
+                    "logging.level '{}' is not one of: {}",
+                    level,
+                    LOG_LEVELS.join(", ")
+                ),
+            );
+        }
+
+        if self.logging.queue_size == Some(0) {
+            invalid(
+                "logging.queue_size",
+                "0",
+                String::from("logging.queue_size must be at least 1"),
+            );
+        }
+
+        if self.security.as_ref().and_then(|s| s.session_ttl_minutes) == Some(0) {
+            invalid(
+                "security.session_ttl_minutes",
+                "0",
+                String::from("security.session_ttl_minutes must be at least 1"),
+            );
+        }
+
+        let roles = self.security.as_ref().and_then(|s| s.roles.as_ref());
+        if let (Some(roles), Some(role)) = (roles, self.get_anonymous_role()) {
+            if !roles.contains_key(role) {
+                invalid(
+                    "security.anonymous_role",
+                    role,
+                    format!("security.anonymous_role '{}' is not in security.roles", role),
+                );
             }
         }
 
-        // Also check APP_CONFIG environment variable
-        if config_content.is_none() {
-            if let Ok(env_path) = env::var("APP_CONFIG") {
-                if Path::new(&env_path).exists() {
-                    config_content = Some(fs::read_to_string(&env_path)?);
-                    config_path = env_path;
-                }
+        let drift = self.get_totp_drift();
+        if drift > 10 {
+            invalid(
+                "security.totp_drift_steps",
+                &drift.to_string(),
+                String::from("security.totp_drift_steps must be at most 10"),
+            );
+        }
+
+        let policy = self.get_password_policy();
+        if policy.min_length == 0 {
+            invalid(
+                "security.password_policy.min_length",
+                "0",
+                String::from("security.password_policy.min_length must be at least 1"),
+            );
+        }
+        if policy.min_score > 4 {
+            invalid(
+                "security.password_policy.min_score",
+                &policy.min_score.to_string(),
+                String::from("security.password_policy.min_score must be between 0 and 4"),
+            );
+        }
+
+        let hashing = self.security.as_ref().and_then(|s| s.password_hashing.as_ref());
+        let algorithm = hashing.and_then(|h| h.algorithm.as_deref()).unwrap_or("argon2");
+        if !HASH_ALGORITHMS.contains(&algorithm) {
+            invalid(
+                "security.password_hashing.algorithm",
+                algorithm,
+                format!(
+                    "security.password_hashing.algorithm '{}' is not one of: {}",
+                    algorithm,
+                    HASH_ALGORITHMS.join(", ")
+                ),
+            );
+        } else if let Err(reason) = self.get_password_hasher().check() {
+            invalid(
+                "security.password_hashing",
+                algorithm,
+                format!("security.password_hashing: {}", reason),
+            );
+        }
+
+        let limits = self.communication.limits.as_ref();
+        if limits.and_then(|l| l.max_string_chars) == Some(0) {
+            invalid(
+                "communication.limits.max_string_chars",
+                "0",
+                String::from("communication.limits.max_string_chars must be at least 1"),
+            );
+        }
+        for (field, max) in limits.and_then(|l| l.field_max_chars.as_ref()).into_iter().flatten() {
+            if *max == 0 {
+                invalid(
+                    "communication.limits.field_max_chars",
+                    field,
+                    format!("communication.limits.field_max_chars.{} must be at least 1", field),
+                );
+            }
+        }
+
+        if self.security.as_ref().and_then(|s| s.lockout_minutes) == Some(0) {
+            invalid(
+                "security.lockout_minutes",
+                "0",
+                String::from("security.lockout_minutes must be at least 1"),
+            );
+        }
+
+        let console_format = self.get_console_format();
+        if !CONSOLE_FORMATS.contains(&console_format) {
+            invalid(
+                "logging.console.format",
+                console_format,
+                format!(
+                    "logging.console.format '{}' is not one of: {}",
+                    console_format,
+                    CONSOLE_FORMATS.join(", ")
+                ),
+            );
+        }
+
+        for (i, pattern) in self.get_redact_patterns().iter().enumerate() {
+            if regex::Regex::new(pattern).is_err() {
+                invalid(
+                    &format!("logging.redaction.patterns[{}]", i),
+                    pattern,
+                    format!("logging redaction pattern '{}' is not a valid regex", pattern),
+                );
             }
         }
 
-        // Try to parse TOML if config found
-        if let Some(content) = config_content {
-            match toml::from_str(&content) {
-                Ok(config) => {
-                    println!("Loaded configuration from: {}", config_path);
-                    return Ok(config);
+        for (i, sink) in self.get_log_sinks().iter().enumerate() {
+            let field = |name: &str| format!("logging.sinks[{}].{}", i, name);
+            if !LOG_SINK_KINDS.contains(&sink.kind.as_str()) {
+                invalid(
+                    &field("kind"),
+                    &sink.kind,
+                    format!(
+                        "logging sink kind '{}' is not one of: {}",
+                        sink.kind,
+                        LOG_SINK_KINDS.join(", ")
+                    ),
+                );
+            }
+            let missing = match sink.kind.as_str() {
+                "tcp" | "udp" if sink.address.is_none() => Some("address"),
+                "http" if sink.url.is_none() => Some("url"),
+                _ => None,
+            };
+            if let Some(name) = missing {
+                invalid(
+                    &field(name),
+                    "",
+                    format!("a {} logging sink needs {}", sink.kind, name),
+                );
+            }
+            if let Some(url) = sink.url.as_deref() {
+                if !url.starts_with("https://") && !url.starts_with("http://") {
+                    invalid(
+                        &field("url"),
+                        url,
+                        format!("logging sink url '{}' must be an http(s):// URL", url),
+                    );
                 }
-                Err(e) => {
-                    eprintln!("Warning: Failed to parse config file: {}", e);
-                    eprintln!("Using default configuration");
+            }
+            if let Some(level) = sink.level.as_deref() {
+                if !LOG_LEVELS.contains(&level.to_lowercase().as_str()) {
+                    invalid(
+                        &field("level"),
+                        level,
+                        format!(
+                            "logging sink level '{}' is not one of: {}",
+                            level,
+                            LOG_LEVELS.join(", ")
+                        ),
+                    );
                 }
             }
         }
 
-        // Return default config if no config file found or parsing failed
-        Ok(AppConfig::default())
+        let db_path = &self.database.path;
+        if db_path.trim().is_empty() {
+            invalid("database.path", db_path, String::from("database.path is empty"));
+        } else if db_path != ":memory:" {
+            let parent = Path::new(db_path)
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty());
+            if let Some(parent) = parent.filter(|parent| !parent.is_dir()) {
+                invalid(
+                    "database.path",
+                    db_path,
+                    format!("database.path directory '{}' does not exist", parent.display()),
+                );
+            }
+        }
+
+        if let Some(transport) = &self.communication.transport {
+            if !TRANSPORTS.contains(&transport.as_str()) {
+                invalid(
+                    "communication.transport",
+                    transport,
+                    format!(
+                        "communication.transport '{}' is not one of: {}",
+                        transport,
+                        TRANSPORTS.join(", ")
+                    ),
+                );
+            }
+        }
+
+        if let Some(serialization) = &self.communication.serialization {
+            match SerializationFormat::from_name(serialization) {
+                Some(format) if format.is_self_describing() => {}
+                Some(format) => invalid(
+                    "communication.serialization",
+                    serialization,
+                    format!(
+                        "communication.serialization '{}' can't encode frontend messages",
+                        format.name()
+                    ),
+                ),
+                None => invalid(
+                    "communication.serialization",
+                    serialization,
+                    format!("communication.serialization '{}' is unknown", serialization),
+                ),
+            }
+        }
+
+        if let Some(url) = self.get_remote_config_url() {
+            if !url.starts_with("https://") {
+                invalid(
+                    "remote_config.url",
+                    url,
+                    format!("remote_config.url '{}' must be an https:// URL", url),
+                );
+            }
+        }
+
+        let proxy = self.get_proxy();
+        let is_url = proxy.starts_with("https://") || proxy.starts_with("http://");
+        if !matches!(proxy, "system" | "none") && !is_url {
+            invalid(
+                "network.proxy",
+                proxy,
+                format!("network.proxy '{}' must be \"system\", \"none\" or a URL", proxy),
+            );
+        }
+
+        for target in self.get_diagnostics_targets() {
+            if let Err(e) = NetworkUtils::split_host_port(&target) {
+                invalid("network.diagnostics_targets", &target, e);
+            }
+        }
+
+        let (width, height) = self.get_window_size();
+        let (min_width, min_height) = self.get_min_window_size();
+        if width < min_width || height < min_height {
+            invalid(
+                "window.width",
+                &format!("{}x{}", width, height),
+                format!(
+                    "window size {}x{} is smaller than the minimum {}x{}",
+                    width, height, min_width, min_height
+                ),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Top-level sections (`[logging]`, `[database]`, ...) that differ from
+    /// `other`; plugin tables are reported one by one as `plugins.<id>`
+    pub fn changed_sections(&self, other: &AppConfig) -> Vec<String> {
+        let sections = [
+            ("app", self.app != other.app),
+            ("executable", self.executable != other.executable),
+            ("database", self.database != other.database),
+            ("window", self.window != other.window),
+            ("logging", self.logging != other.logging),
+            ("communication", self.communication != other.communication),
+            ("features", self.features != other.features),
+            ("remote", self.remote != other.remote),
+            ("dev", self.dev != other.dev),
+            ("event_bus", self.event_bus != other.event_bus),
+            ("secrets", self.secrets != other.secrets),
+            ("remote_config", self.remote_config != other.remote_config),
+            ("security", self.security != other.security),
+            ("network", self.network != other.network),
+        ];
+        let mut changed: Vec<String> = sections
+            .into_iter()
+            .filter(|(_, changed)| *changed)
+            .map(|(name, _)| name.to_string())
+            .collect();
+
+        let mut plugin_ids: Vec<&String> = self
+            .plugins
+            .iter()
+            .chain(&other.plugins)
+            .flat_map(HashMap::keys)
+            .collect();
+        plugin_ids.sort();
+        plugin_ids.dedup();
+        changed.extend(
+            plugin_ids
+                .into_iter()
+                .filter(|id| self.get_plugin_config(id) != other.get_plugin_config(id))
+                .map(|id| format!("plugins.{}", id)),
+        );
+        changed
+    }
+
+    /// Every setting that differs from `other`, by dotted key
+    pub fn changed_settings(&self, other: &AppConfig) -> Vec<SettingChange> {
+        match (serde_json::to_value(self), serde_json::to_value(other)) {
+            (Ok(old), Ok(new)) => diff_settings(&old, &new),
+            _ => Vec::new(),
+        }
+    }
+
+    /// What the `RUSTWEBUI__` variables ("env") and then the command-line
+    /// options ("cli") change relative to the config file, or the defaults
+    /// when there is none
+    pub fn override_changes() -> Vec<(&'static str, Vec<SettingChange>)> {
+        let file = match Self::config_path() {
+            Some(path) => fs::read_to_string(path)
+                .ok()
+                .and_then(|content| toml::from_str(&content).ok()),
+            None => toml::Value::try_from(AppConfig::default()).ok(),
+        };
+        let Some(file) = file else {
+            return Vec::new();
+        };
+        let mut with_env = file.clone();
+        apply_env_overrides(&mut with_env, env::vars());
+        let mut with_args = with_env.clone();
+        apply_arg_overrides(&mut with_args, ARG_OVERRIDES.get().into_iter().flatten());
+
+        let json = |config: &toml::Value| serde_json::to_value(config).unwrap_or_default();
+        vec![
+            ("env", diff_settings(&json(&file), &json(&with_env))),
+            ("cli", diff_settings(&json(&with_env), &json(&with_args))),
+        ]
     }
 
     pub fn get_app_name(&self) -> &str {
@@ -175,6 +974,18 @@ impl AppConfig {
         self.database.create_sample_data.unwrap_or(true)
     }
 
+    pub fn is_event_sourcing_enabled(&self) -> bool {
+        self.database.event_sourcing.unwrap_or(false)
+    }
+
+    pub fn get_snapshot_interval(&self) -> i64 {
+        self.database.snapshot_interval.unwrap_or(50)
+    }
+
+    pub fn should_persist_error_stats(&self) -> bool {
+        self.database.persist_error_stats.unwrap_or(false)
+    }
+
     pub fn get_window_title(&self) -> &str {
         &self.window.title
     }
@@ -191,6 +1002,89 @@ impl AppConfig {
         self.logging.append.unwrap_or(true)
     }
 
+    pub fn get_log_buffer_size(&self) -> usize {
+        self.logging.buffer_size.unwrap_or(1000)
+    }
+
+    pub fn get_log_queue_size(&self) -> usize {
+        self.logging.queue_size.unwrap_or(8192)
+    }
+
+    /// `None` leaves it to whether stdout is a terminal
+    pub fn get_console_colors(&self) -> Option<bool> {
+        self.logging.console.as_ref().and_then(|c| c.colors)
+    }
+
+    pub fn get_console_timestamps(&self) -> bool {
+        self.logging
+            .console
+            .as_ref()
+            .and_then(|c| c.timestamps)
+            .unwrap_or(false)
+    }
+
+    pub fn get_console_format(&self) -> &str {
+        self.logging
+            .console
+            .as_ref()
+            .and_then(|c| c.format.as_deref())
+            .unwrap_or("compact")
+    }
+
+    pub fn is_log_redaction_enabled(&self) -> bool {
+        self.logging
+            .redaction
+            .as_ref()
+            .and_then(|r| r.enabled)
+            .unwrap_or(true)
+    }
+
+    pub fn should_redact_emails(&self) -> bool {
+        self.logging
+            .redaction
+            .as_ref()
+            .and_then(|r| r.emails)
+            .unwrap_or(true)
+    }
+
+    pub fn should_redact_tokens(&self) -> bool {
+        self.logging
+            .redaction
+            .as_ref()
+            .and_then(|r| r.tokens)
+            .unwrap_or(true)
+    }
+
+    pub fn get_redact_patterns(&self) -> &[String] {
+        self.logging
+            .redaction
+            .as_ref()
+            .and_then(|r| r.patterns.as_deref())
+            .unwrap_or_default()
+    }
+
+    pub fn get_slow_span_ms(&self) -> u64 {
+        self.logging.slow_span_ms.unwrap_or(100)
+    }
+
+    pub fn get_log_sinks(&self) -> &[LogSinkSettings] {
+        self.logging.sinks.as_deref().unwrap_or_default()
+    }
+
+    pub fn get_crash_dir(&self) -> PathBuf {
+        match &self.logging.crash_dir {
+            Some(dir) => PathBuf::from(dir),
+            None => Path::new(self.get_log_file())
+                .parent()
+                .unwrap_or_else(|| Path::new(""))
+                .join("crashes"),
+        }
+    }
+
+    pub fn get_crash_report_url(&self) -> Option<&str> {
+        self.logging.crash_report_url.as_deref()
+    }
+
     pub fn get_transport(&self) -> &str {
         self.communication.transport.as_deref().unwrap_or("webview_ffi")
     }
@@ -199,10 +1093,407 @@ impl AppConfig {
         self.communication.serialization.as_deref().unwrap_or("json")
     }
 
+    pub fn get_compression_threshold(&self) -> usize {
+        self.communication.compression_threshold.unwrap_or(16 * 1024)
+    }
+
+    pub fn get_max_queue_depth(&self) -> usize {
+        self.communication
+            .backpressure
+            .as_ref()
+            .and_then(|b| b.max_queue_depth)
+            .unwrap_or(256)
+    }
+
+    pub fn get_max_in_flight(&self) -> usize {
+        self.communication
+            .backpressure
+            .as_ref()
+            .and_then(|b| b.max_in_flight)
+            .unwrap_or(32)
+    }
+
+    pub fn get_backpressure_policies(&self) -> HashMap<String, String> {
+        self.communication
+            .backpressure
+            .as_ref()
+            .and_then(|b| b.policies.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn get_max_request_bytes(&self) -> usize {
+        self.communication
+            .limits
+            .as_ref()
+            .and_then(|l| l.max_request_bytes)
+            .unwrap_or(1024 * 1024)
+    }
+
+    pub fn get_max_response_bytes(&self) -> usize {
+        self.communication
+            .limits
+            .as_ref()
+            .and_then(|l| l.max_response_bytes)
+            .unwrap_or(4 * 1024 * 1024)
+    }
+
+    pub fn get_max_string_chars(&self) -> usize {
+        self.communication
+            .limits
+            .as_ref()
+            .and_then(|l| l.max_string_chars)
+            .unwrap_or(sanitize::DEFAULT_MAX_CHARS)
+    }
+
+    pub fn get_field_max_chars(&self) -> HashMap<String, usize> {
+        self.communication
+            .limits
+            .as_ref()
+            .and_then(|l| l.field_max_chars.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn get_event_queue_capacity(&self) -> usize {
+        self.event_bus
+            .as_ref()
+            .and_then(|e| e.queue_capacity)
+            .unwrap_or(1024)
+    }
+
+    pub fn get_event_overflow(&self) -> String {
+        self.event_bus
+            .as_ref()
+            .and_then(|e| e.overflow.clone())
+            .unwrap_or_else(|| String::from("drop_oldest"))
+    }
+
+    pub fn get_event_overflow_strategies(&self) -> HashMap<String, String> {
+        self.event_bus
+            .as_ref()
+            .and_then(|e| e.overflow_by_event.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn get_event_bridge_topics(&self) -> Vec<String> {
+        self.event_bus
+            .as_ref()
+            .and_then(|e| e.bridge_topics.clone())
+            .unwrap_or_default()
+    }
+
     pub fn is_dark_mode(&self) -> bool {
         self.features.dark_mode.unwrap_or(true)
     }
 
+    pub fn is_remote_access_enabled(&self) -> bool {
+        self.remote
+            .as_ref()
+            .and_then(|r| r.enabled)
+            .unwrap_or(false)
+    }
+
+    pub fn get_remote_bind_address(&self) -> &str {
+        self.remote
+            .as_ref()
+            .and_then(|r| r.bind_address.as_deref())
+            .unwrap_or("0.0.0.0")
+    }
+
+    pub fn get_remote_port(&self) -> u16 {
+        self.remote.as_ref().and_then(|r| r.port).unwrap_or(8090)
+    }
+
+    /// Access token, `APP_REMOTE_TOKEN` taking precedence over the config file
+    pub fn get_remote_token(&self) -> Option<String> {
+        env::var("APP_REMOTE_TOKEN")
+            .ok()
+            .or_else(|| self.remote.as_ref().and_then(|r| r.token.clone()))
+            .filter(|t| !t.trim().is_empty())
+    }
+
+    pub fn get_remote_allowed_ips(&self) -> Vec<String> {
+        self.remote
+            .as_ref()
+            .and_then(|r| r.allowed_ips.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn get_remote_max_connections(&self) -> usize {
+        self.remote
+            .as_ref()
+            .and_then(|r| r.max_connections)
+            .unwrap_or(64)
+            .max(1)
+    }
+
+    /// Keyring service secrets are stored under, the executable name by default
+    pub fn get_secrets_service(&self) -> &str {
+        self.secrets
+            .as_ref()
+            .and_then(|s| s.service.as_deref())
+            .unwrap_or(&self.executable.name)
+    }
+
+    pub fn get_secrets_fallback_file(&self) -> Option<PathBuf> {
+        let fallback_file = self
+            .secrets
+            .as_ref()
+            .and_then(|s| s.fallback_file.as_deref());
+        match fallback_file {
+            Some("") => None,
+            Some(path) => Some(PathBuf::from(path)),
+            None => dirs::data_local_dir()
+                .map(|dir| dir.join(&self.executable.name).join("secrets.enc")),
+        }
+    }
+
+    pub fn get_remote_config_url(&self) -> Option<&str> {
+        self.remote_config
+            .as_ref()
+            .and_then(|r| r.url.as_deref())
+            .filter(|url| !url.trim().is_empty())
+    }
+
+    pub fn get_remote_config_signing_key(&self) -> Option<&str> {
+        self.remote_config
+            .as_ref()
+            .and_then(|r| r.signing_key.as_deref())
+            .filter(|key| !key.is_empty())
+    }
+
+    /// `cache_file`, else `remote-config.json` in the user cache directory
+    pub fn get_remote_config_cache_file(&self) -> Option<PathBuf> {
+        let cache_file = self
+            .remote_config
+            .as_ref()
+            .and_then(|r| r.cache_file.as_deref());
+        match cache_file {
+            Some(path) => Some(PathBuf::from(path)),
+            None => dirs::cache_dir()
+                .map(|dir| dir.join(&self.executable.name).join("remote-config.json")),
+        }
+    }
+
+    pub fn get_remote_config_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(
+            self.remote_config
+                .as_ref()
+                .and_then(|r| r.timeout_secs)
+                .unwrap_or(10),
+        )
+    }
+
+    pub fn get_session_ttl(&self) -> std::time::Duration {
+        let minutes = self
+            .security
+            .as_ref()
+            .and_then(|s| s.session_ttl_minutes)
+            .unwrap_or(30);
+        std::time::Duration::from_secs(minutes * 60)
+    }
+
+    pub fn get_session_max_age(&self) -> std::time::Duration {
+        let hours = self
+            .security
+            .as_ref()
+            .and_then(|s| s.session_max_age_hours)
+            .unwrap_or(12);
+        std::time::Duration::from_secs(hours * 60 * 60)
+    }
+
+    pub fn get_session_signing_key(&self) -> Option<&str> {
+        self.security
+            .as_ref()
+            .and_then(|s| s.session_signing_key.as_deref())
+            .filter(|key| !key.is_empty())
+    }
+
+    pub fn get_totp_issuer(&self) -> &str {
+        self.security
+            .as_ref()
+            .and_then(|s| s.totp_issuer.as_deref())
+            .filter(|issuer| !issuer.is_empty())
+            .unwrap_or(&self.app.name)
+    }
+
+    pub fn get_anonymous_role(&self) -> Option<&str> {
+        self.security
+            .as_ref()
+            .and_then(|s| s.anonymous_role.as_deref())
+            .map_or(Some("Admin"), |role| Some(role).filter(|r| !r.is_empty()))
+    }
+
+    /// `role`'s permissions; none for a role that isn't configured
+    pub fn get_role_permissions(&self, role: &str) -> Vec<String> {
+        match self.security.as_ref().and_then(|s| s.roles.as_ref()) {
+            Some(roles) => roles.get(role).cloned().unwrap_or_default(),
+            None => match role {
+                "Admin" => vec![String::from("*")],
+                "User" => vec![String::from("users:read"), String::from("settings:read")],
+                _ => Vec::new(),
+            },
+        }
+    }
+
+    pub fn is_bridge_signing_enabled(&self) -> bool {
+        self.security
+            .as_ref()
+            .and_then(|s| s.sign_bridge_messages)
+            .unwrap_or(false)
+    }
+
+    pub fn get_max_failed_logins(&self) -> u32 {
+        self.security
+            .as_ref()
+            .and_then(|s| s.max_failed_logins)
+            .unwrap_or(5)
+    }
+
+    pub fn get_lockout_duration(&self) -> std::time::Duration {
+        let minutes = self
+            .security
+            .as_ref()
+            .and_then(|s| s.lockout_minutes)
+            .unwrap_or(15);
+        std::time::Duration::from_secs(minutes * 60)
+    }
+
+    pub fn get_password_policy(&self) -> PasswordPolicy {
+        let defaults = PasswordPolicy::default();
+        let Some(p) = self.security.as_ref().and_then(|s| s.password_policy.as_ref()) else {
+            return defaults;
+        };
+        PasswordPolicy {
+            min_length: p.min_length.unwrap_or(defaults.min_length),
+            require_lowercase: p.require_lowercase.unwrap_or(defaults.require_lowercase),
+            require_uppercase: p.require_uppercase.unwrap_or(defaults.require_uppercase),
+            require_digit: p.require_digit.unwrap_or(defaults.require_digit),
+            require_symbol: p.require_symbol.unwrap_or(defaults.require_symbol),
+            reject_common: p.reject_common.unwrap_or(defaults.reject_common),
+            min_score: p.min_score.unwrap_or(defaults.min_score),
+        }
+    }
+
+    /// `[security.password_hashing]` as a hasher; Argon2id by default
+    pub fn get_password_hasher(&self) -> Box<dyn PasswordHasher> {
+        let h = self
+            .security
+            .as_ref()
+            .and_then(|s| s.password_hashing.clone())
+            .unwrap_or_default();
+        match h.algorithm.as_deref() {
+            Some("bcrypt") => Box::new(BcryptHasher {
+                cost: h.bcrypt_cost.unwrap_or(BcryptHasher::default().cost),
+            }),
+            Some("scrypt") => {
+                let defaults = ScryptHasher::default();
+                Box::new(ScryptHasher {
+                    log_n: h.scrypt_log_n.unwrap_or(defaults.log_n),
+                    r: h.scrypt_r.unwrap_or(defaults.r),
+                    p: h.scrypt_p.unwrap_or(defaults.p),
+                })
+            }
+            _ => {
+                let defaults = Argon2Hasher::default();
+                Box::new(Argon2Hasher {
+                    memory_kib: h.argon2_memory_kib.unwrap_or(defaults.memory_kib),
+                    iterations: h.argon2_iterations.unwrap_or(defaults.iterations),
+                    parallelism: h.argon2_parallelism.unwrap_or(defaults.parallelism),
+                })
+            }
+        }
+    }
+
+    pub fn get_totp_drift(&self) -> u32 {
+        self.security
+            .as_ref()
+            .and_then(|s| s.totp_drift_steps)
+            .unwrap_or(1)
+    }
+
+    /// Ids of the `[plugins.<id>]` tables, sorted
+    pub fn get_plugin_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.plugins.iter().flat_map(|p| p.keys().cloned()).collect();
+        ids.sort();
+        ids
+    }
+
+    /// The `[plugins.<id>]` table, if the config has one
+    pub fn get_plugin_config(&self, id: &str) -> Option<&serde_json::Value> {
+        self.plugins.as_ref().and_then(|plugins| plugins.get(id))
+    }
+
+    /// Dev server URL, `APP_DEV_SERVER_URL` taking precedence over the config file
+    pub fn get_dev_server_url(&self) -> Option<String> {
+        env::var("APP_DEV_SERVER_URL")
+            .ok()
+            .or_else(|| self.dev.as_ref().and_then(|d| d.dev_server_url.clone()))
+            .filter(|url| !url.trim().is_empty())
+    }
+
+    pub fn get_dev_cert_dir(&self) -> Option<PathBuf> {
+        match self.dev.as_ref().and_then(|d| d.cert_dir.as_deref()) {
+            Some(dir) if !dir.trim().is_empty() => Some(PathBuf::from(dir)),
+            _ => dirs::data_local_dir().map(|dir| dir.join(&self.executable.name).join("certs")),
+        }
+    }
+
+    /// Empty when unset, meaning `DevCertificates`' default hosts
+    pub fn get_dev_cert_hosts(&self) -> Vec<String> {
+        self.dev
+            .as_ref()
+            .and_then(|d| d.cert_hosts.clone())
+            .unwrap_or_default()
+    }
+
+    /// `[filesystem] allowed_roots` with "~/" expanded
+    pub fn get_allowed_roots(&self) -> Vec<PathBuf> {
+        let configured = self.filesystem.as_ref().and_then(|f| f.allowed_roots.as_ref());
+        let Some(roots) = configured else {
+            return [dirs::document_dir(), dirs::download_dir()]
+                .into_iter()
+                .flatten()
+                .collect();
+        };
+        roots
+            .iter()
+            .filter_map(|root| match root.strip_prefix("~/") {
+                Some(rest) => dirs::home_dir().map(|home| home.join(rest)),
+                None => Some(PathBuf::from(root)),
+            })
+            .collect()
+    }
+
+    /// `[network] diagnostics_targets`, empty when unset
+    pub fn get_diagnostics_targets(&self) -> Vec<String> {
+        self.network
+            .as_ref()
+            .and_then(|n| n.diagnostics_targets.clone())
+            .unwrap_or_default()
+    }
+
+    /// `[network] proxy`: "system", "none" or a proxy URL
+    pub fn get_proxy(&self) -> &str {
+        self.network
+            .as_ref()
+            .and_then(|n| n.proxy.as_deref())
+            .unwrap_or("system")
+    }
+
+    pub fn get_no_proxy(&self) -> Vec<String> {
+        self.network
+            .as_ref()
+            .and_then(|n| n.no_proxy.clone())
+            .unwrap_or_default()
+    }
+
+    /// Username and password (or its "secret:" reference) for the proxy
+    pub fn get_proxy_credentials(&self) -> Option<(&str, &str)> {
+        let network = self.network.as_ref()?;
+        let username = network.proxy_username.as_deref()?;
+        Some((username, network.proxy_password.as_deref().unwrap_or("")))
+    }
+
     pub fn get_executable_name(&self) -> &str {
         &self.executable.name
     }
@@ -224,6 +1515,146 @@ impl AppConfig {
     pub fn is_resizable(&self) -> bool {
         self.window.resizable.unwrap_or(true)
     }
+
+    /// Top-left corner to open the window at; `None` centers it
+    pub fn get_window_position(&self) -> Option<(u32, u32)> {
+        self.window.x.zip(self.window.y)
+    }
+
+    pub fn is_maximized(&self) -> bool {
+        self.window.maximized.unwrap_or(false)
+    }
+
+    pub fn is_always_on_top(&self) -> bool {
+        self.window.always_on_top.unwrap_or(false)
+    }
+}
+
+/// Set each `RUSTWEBUI__SECTION__KEY=value` pair of `vars` in `config`,
+/// creating tables as needed. Values are read as TOML (`true`, `8090`,
+/// `["10.0.0.0/8"]`), falling back to a plain string; a key that already
+/// holds a string stays one.
+fn apply_env_overrides(config: &mut toml::Value, vars: impl IntoIterator<Item = (String, String)>) {
+    for (name, raw) in vars {
+        let Some(path) = name.strip_prefix(ENV_PREFIX) else {
+            continue;
+        };
+        let keys: Vec<String> = path.split("__").map(str::to_lowercase).collect();
+        if keys.iter().any(String::is_empty) {
+            continue;
+        }
+        let (table, leaf) = match parent_table(config, &keys) {
+            Ok(found) => found,
+            Err(key) => {
+                eprintln!("Warning: Ignoring {}: {} is not a table", name, key);
+                continue;
+            }
+        };
+        let value = match table.get(leaf) {
+            Some(toml::Value::String(_)) => toml::Value::String(raw),
+            _ => parse_env_value(raw),
+        };
+        table.insert(leaf.to_string(), value);
+    }
+}
+
+/// Merge `over` into `base`: tables key by key, anything else replaced
+fn merge_tables(base: &mut toml::Value, over: toml::Value) {
+    match (base, over) {
+        (toml::Value::Table(base), toml::Value::Table(over)) => {
+            for (key, value) in over {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_tables(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, over) => *base = over,
+    }
+}
+
+/// Leaf values of `old` and `new` that differ, keyed "section.key"; nulls
+/// count as unset and arrays as single values
+fn diff_settings(old: &serde_json::Value, new: &serde_json::Value) -> Vec<SettingChange> {
+    fn flatten(
+        prefix: &str,
+        value: &serde_json::Value,
+        out: &mut BTreeMap<String, serde_json::Value>,
+    ) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map {
+                    let key = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", prefix, key)
+                    };
+                    flatten(&key, value, out);
+                }
+            }
+            serde_json::Value::Null => {}
+            value => {
+                out.insert(prefix.to_string(), value.clone());
+            }
+        }
+    }
+
+    let (mut old_values, mut new_values) = (BTreeMap::new(), BTreeMap::new());
+    flatten("", old, &mut old_values);
+    flatten("", new, &mut new_values);
+    let mut keys: Vec<&String> = old_values.keys().chain(new_values.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .filter(|key| old_values.get(*key) != new_values.get(*key))
+        .map(|key| SettingChange {
+            key: key.clone(),
+            old_value: old_values.get(key).cloned(),
+            new_value: new_values.get(key).cloned(),
+        })
+        .collect()
+}
+
+fn apply_arg_overrides<'a>(
+    config: &mut toml::Value,
+    overrides: impl IntoIterator<Item = &'a (String, toml::Value)>,
+) {
+    for (key, value) in overrides {
+        let keys: Vec<&str> = key.split('.').collect();
+        if let Ok((table, leaf)) = parent_table(config, &keys) {
+            table.insert(leaf.to_string(), value.clone());
+        }
+    }
+}
+
+/// The table holding the last of `keys` (`["logging", "level"]`) and that
+/// key, creating tables on the way; `Err` names a key that holds a value
+/// where a table was expected
+pub(crate) fn parent_table<'a, 'k, K: AsRef<str>>(
+    config: &'a mut toml::Value,
+    keys: &'k [K],
+) -> Result<(&'a mut toml::Table, &'k str), String> {
+    let Some((leaf, parents)) = keys.split_last() else {
+        return Err(String::new());
+    };
+    let mut table = config.as_table_mut().ok_or_else(String::new)?;
+    for key in parents {
+        table = table
+            .entry(key.as_ref())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| key.as_ref().to_string())?;
+    }
+    Ok((table, leaf.as_ref()))
+}
+
+fn parse_env_value(raw: String) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut parsed| parsed.remove("value"))
+        .unwrap_or(toml::Value::String(raw))
 }
 
 // Configuration for build-time access
@@ -279,5 +1710,272 @@ mod tests {
         assert!(config.is_dark_mode());
         assert!(config.is_resizable());
         assert_eq!(config.get_window_size(), (1200, 800));
+        assert_eq!(config.get_session_ttl().as_secs(), 30 * 60);
+        assert!(config.get_session_signing_key().is_none());
+        assert_eq!(config.get_totp_issuer(), "Rust WebUI Application");
+        assert_eq!(config.get_totp_drift(), 1);
+        assert_eq!(config.get_anonymous_role(), Some("Admin"));
+        assert_eq!(config.get_role_permissions("User"), ["users:read", "settings:read"]);
+        assert!(config.get_role_permissions("Guest").is_empty());
+
+        let mut keyring_only = config.clone();
+        keyring_only.secrets = Some(SecretsSettings {
+            service: None,
+            fallback_file: Some(String::new()),
+        });
+        assert!(keyring_only.get_secrets_fallback_file().is_none());
+    }
+
+    #[test]
+    fn test_changed_sections() {
+        let config = AppConfig::default();
+        assert!(config.changed_sections(&config.clone()).is_empty());
+
+        let mut changed = config.clone();
+        changed.logging.level = String::from("debug");
+        changed.database.create_sample_data = Some(false);
+        assert_eq!(config.changed_sections(&changed), ["database", "logging"]);
+    }
+
+    #[test]
+    fn test_validate_reports_every_violation() {
+        assert!(AppConfig::default().validate().is_ok());
+
+        let mut config = AppConfig::default();
+        config.logging.level = String::from("verbose");
+        config.database.path = String::from("/no/such/dir/app.db");
+        config.communication.transport = Some(String::from("carrier_pigeon"));
+        config.communication.serialization = Some(String::from("bincode"));
+        config.window.width = Some(640);
+        config.logging.queue_size = Some(0);
+        config.logging.console = Some(ConsoleSettings {
+            format: Some(String::from("pretty")),
+            ..Default::default()
+        });
+        config.logging.redaction = Some(RedactionSettings {
+            patterns: Some(vec![String::from(r"\d{3}-\d{4}"), String::from("(unclosed")]),
+            ..Default::default()
+        });
+        config.logging.sinks = Some(vec![
+            LogSinkSettings {
+                kind: String::from("http"),
+                url: Some(String::from("ftp://logs.example.com")),
+                ..Default::default()
+            },
+            LogSinkSettings {
+                kind: String::from("tcp"),
+                ..Default::default()
+            },
+        ]);
+        let errors = config.validate().unwrap_err();
+
+        let fields: Vec<_> = errors
+            .iter()
+            .map(|e| {
+                assert!(matches!(e, AppError::Validation(_)));
+                e.to_value().field.clone().unwrap_or_default()
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                "logging.level",
+                "logging.queue_size",
+                "logging.console.format",
+                "logging.redaction.patterns[1]",
+                "logging.sinks[0].url",
+                "logging.sinks[1].address",
+                "database.path",
+                "communication.transport",
+                "communication.serialization",
+                "window.width"
+            ]
+        );
+
+        config.database.path = String::from(":memory:");
+        config.logging.level = String::from("DEBUG");
+        config.window.width = None;
+        config.logging.sinks = None;
+        config.logging.queue_size = None;
+        config.logging.console = None;
+        config.logging.redaction = None;
+        assert_eq!(config.validate().unwrap_err().len(), 2);
+    }
+
+    #[test]
+    fn test_password_hasher_settings() {
+        let mut config = AppConfig::default();
+        assert_eq!(config.get_password_hasher().algorithm(), "argon2");
+
+        config.security = Some(SecuritySettings {
+            password_hashing: Some(PasswordHashingSettings {
+                algorithm: Some(String::from("bcrypt")),
+                bcrypt_cost: Some(10),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let hasher = config.get_password_hasher();
+        assert_eq!(hasher.algorithm(), "bcrypt");
+        assert!(hasher.is_current("$2b$10$abcdefghijklmnopqrstuv"));
+        assert!(config.validate().is_ok());
+
+        let hashing = config.security.as_mut().unwrap().password_hashing.as_mut().unwrap();
+        hashing.bcrypt_cost = Some(99);
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors[0].to_value().field.as_deref(), Some("security.password_hashing"));
+
+        let hashing = config.security.as_mut().unwrap().password_hashing.as_mut().unwrap();
+        hashing.algorithm = Some(String::from("md5"));
+        let errors = config.validate().unwrap_err();
+        assert_eq!(
+            errors[0].to_value().field.as_deref(),
+            Some("security.password_hashing.algorithm")
+        );
+    }
+
+    #[test]
+    fn test_plugin_sections() {
+        let content = r#"
+[app]
+name = "Rust WebUI Application"
+version = "1.0.0"
+
+[executable]
+name = "rustwebui-app"
+
+[database]
+path = "app.db"
+
+[window]
+title = "Rust WebUI Application"
+
+[logging]
+level = "info"
+file = "application.log"
+
+[communication]
+
+[features]
+
+[plugins.filewatcher]
+paths = ["./data"]
+debounce_ms = 250
+
+[plugins.updater]
+channel = "stable"
+"#;
+        let config: AppConfig = toml::from_str(content).unwrap();
+        let watcher = config.get_plugin_config("filewatcher").unwrap();
+        assert_eq!(watcher["paths"][0], "./data");
+        assert_eq!(watcher["debounce_ms"], 250);
+        assert!(config.get_plugin_config("missing").is_none());
+        assert_eq!(config.get_plugin_ids(), ["filewatcher", "updater"]);
+
+        let mut changed = config.clone();
+        let plugins = changed.plugins.as_mut().unwrap();
+        plugins.get_mut("updater").unwrap()["channel"] = serde_json::json!("beta");
+        plugins.insert(String::from("backup"), serde_json::json!({ "keep": 3 }));
+        assert_eq!(
+            config.changed_sections(&changed),
+            ["plugins.backup", "plugins.updater"]
+        );
+    }
+
+    #[test]
+    fn test_merge_tables() {
+        let mut local: toml::Value = toml::from_str(
+            "[logging]\nlevel = \"info\"\nfile = \"app.log\"\n[remote]\nallowed_ips = [\"10.0.0.1\"]\n",
+        )
+        .unwrap();
+        let fleet: toml::Value = toml::from_str(
+            "[logging]\nlevel = \"warn\"\n[remote]\nallowed_ips = [\"10.0.0.2\"]\n[window]\ntitle = \"Kiosk\"\n",
+        )
+        .unwrap();
+        merge_tables(&mut local, fleet);
+
+        assert_eq!(local["logging"]["level"].as_str(), Some("warn"));
+        assert_eq!(local["logging"]["file"].as_str(), Some("app.log"));
+        assert_eq!(local["remote"]["allowed_ips"].as_array().unwrap().len(), 1);
+        assert_eq!(local["remote"]["allowed_ips"][0].as_str(), Some("10.0.0.2"));
+        assert_eq!(local["window"]["title"].as_str(), Some("Kiosk"));
+    }
+
+    #[test]
+    fn test_changed_settings_by_key() {
+        let old = AppConfig::default();
+        let mut new = old.clone();
+        new.logging.level = String::from("debug");
+        new.remote = Some(RemoteSettings {
+            port: Some(9000),
+            ..Default::default()
+        });
+        new.features.dark_mode = None;
+
+        let changes = old.changed_settings(&new);
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.key.as_str(), c.old_value.clone(), c.new_value.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("features.dark_mode", Some(serde_json::json!(true)), None),
+                (
+                    "logging.level",
+                    Some(serde_json::json!("info")),
+                    Some(serde_json::json!("debug"))
+                ),
+                ("remote.port", None, Some(serde_json::json!(9000))),
+            ]
+        );
+        assert!(old.changed_settings(&old).is_empty());
+    }
+
+    #[test]
+    fn test_arg_overrides_win_over_env() {
+        let mut value = toml::Value::try_from(AppConfig::default()).unwrap();
+        apply_env_overrides(
+            &mut value,
+            [("RUSTWEBUI__LOGGING__LEVEL".to_string(), "warn".to_string())],
+        );
+        let args = [
+            (String::from("logging.level"), toml::Value::from("debug")),
+            (String::from("database.path"), toml::Value::from("/tmp/cli.db")),
+        ];
+        apply_arg_overrides(&mut value, &args);
+        let config: AppConfig = value.try_into().unwrap();
+
+        assert_eq!(config.get_log_level(), "debug");
+        assert_eq!(config.get_db_path(), "/tmp/cli.db");
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let mut value = toml::Value::try_from(AppConfig::default()).unwrap();
+        let vars = [
+            ("RUSTWEBUI__DATABASE__PATH", "/data/app.db"),
+            ("RUSTWEBUI__DATABASE__CREATE_SAMPLE_DATA", "false"),
+            ("RUSTWEBUI__APP__VERSION", "2"),
+            ("RUSTWEBUI__REMOTE__ENABLED", "true"),
+            ("RUSTWEBUI__REMOTE__PORT", "9000"),
+            ("RUSTWEBUI__REMOTE__ALLOWED_IPS", r#"["10.0.0.0/8"]"#),
+            ("RUSTWEBUI__APP__NAME__NESTED", "ignored"),
+            ("RUSTWEBUI__", "ignored"),
+            ("OTHER__DATABASE__PATH", "ignored"),
+        ];
+        apply_env_overrides(
+            &mut value,
+            vars.iter().map(|(k, v)| (k.to_string(), v.to_string())),
+        );
+        let config: AppConfig = value.try_into().unwrap();
+
+        assert_eq!(config.get_db_path(), "/data/app.db");
+        assert!(!config.should_create_sample_data());
+        assert_eq!(config.get_version(), "2");
+        assert_eq!(config.get_app_name(), "Rust WebUI Application");
+        assert!(config.is_remote_access_enabled());
+        assert_eq!(config.get_remote_port(), 9000);
+        assert_eq!(config.get_remote_allowed_ips(), ["10.0.0.0/8"]);
     }
 }
@@ -0,0 +1,231 @@
+// src/core/infrastructure/config_template.rs
+// First-run config file: AppConfig::default() written out as TOML with each
+// setting's doc comment above it, so users can see what there is to change
+
+#![allow(dead_code)]
+
+use serde_json::Value;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use super::config::AppConfig;
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+
+const HEADER: &str = "\
+# Application configuration
+#
+# Written on first run from the built-in defaults. Commented-out settings are
+# unset; remove the leading '# ' and give them a value to use them.
+# RUSTWEBUI__<SECTION>__<KEY> environment variables and command-line options
+# override this file.
+";
+
+/// Comment lines are wrapped at this many characters
+const WIDTH: usize = 78;
+
+/// `config` as a config file, documented from the `AppConfig` doc comments
+pub fn render(config: &AppConfig) -> AppResult<String> {
+    let values = toml::Value::try_from(config).map_err(serialization_error)?;
+    let schema =
+        serde_json::to_value(schemars::schema_for!(AppConfig)).map_err(serialization_error)?;
+    let mut out = String::from(HEADER);
+    render_table(&mut out, &schema, &schema, "", values.as_table(), false);
+    Ok(out)
+}
+
+/// Write the documented defaults to `path`; an existing file is left alone
+pub fn write_default(path: &Path) -> AppResult<()> {
+    let content = render(&AppConfig::default())?;
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|e| {
+            AppError::Configuration(
+                ErrorValue::new(
+                    ErrorCode::ConfigInvalid,
+                    "Failed to write default config file",
+                )
+                .with_cause(e.to_string())
+                .with_context("path", path.display().to_string()),
+            )
+        })
+}
+
+/// Plain settings first, then one `[section]` per nested table, as TOML
+/// requires. Tables with no value are written commented out.
+fn render_table(
+    out: &mut String,
+    root: &Value,
+    schema: &Value,
+    path: &str,
+    values: Option<&toml::Table>,
+    commented: bool,
+) {
+    let no_properties = serde_json::Map::new();
+    let properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&no_properties);
+    let (tables, settings): (Vec<_>, Vec<_>) = properties
+        .iter()
+        .partition(|(_, property)| is_table(resolve(root, property)));
+
+    for (key, property) in settings {
+        comment(out, description(root, property));
+        match values.and_then(|values| values.get(key)) {
+            Some(value) => line(out, commented, &format!("{} = {}", key, value)),
+            None => line(
+                out,
+                true,
+                &format!("{} = <{}>", key, type_name(resolve(root, property))),
+            ),
+        }
+    }
+
+    // Maps such as `[communication.backpressure.policies]` have no fixed keys
+    if properties.is_empty() {
+        for (key, value) in values.into_iter().flatten() {
+            line(out, commented, &format!("{} = {}", toml_key(key), value));
+        }
+    }
+
+    for (key, property) in tables {
+        let path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", path, key)
+        };
+        let values = values
+            .and_then(|values| values.get(key))
+            .and_then(toml::Value::as_table);
+        let commented = commented || values.is_none();
+        out.push('\n');
+        comment(out, description(root, property));
+        line(out, commented, &format!("[{}]", path));
+        render_table(out, root, resolve(root, property), &path, values, commented);
+    }
+}
+
+/// The schema `schema` stands for, looking through `$ref` and the
+/// `allOf: [T]` / `anyOf: [T, null]` wrappers schemars puts around options
+fn resolve<'a>(root: &'a Value, schema: &'a Value) -> &'a Value {
+    if let Some(name) = schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.strip_prefix("#/definitions/"))
+    {
+        return match root["definitions"].get(name) {
+            Some(definition) => resolve(root, definition),
+            None => schema,
+        };
+    }
+    let wrapped = ["allOf", "anyOf"]
+        .iter()
+        .filter_map(|key| schema.get(*key).and_then(Value::as_array))
+        .flatten()
+        .find(|inner| types(inner) != ["null"]);
+    match wrapped {
+        Some(inner) => resolve(root, inner),
+        None => schema,
+    }
+}
+
+fn types(schema: &Value) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(name)) => vec![name.as_str()],
+        Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn is_table(schema: &Value) -> bool {
+    types(schema).contains(&"object")
+}
+
+fn type_name(schema: &Value) -> &str {
+    types(schema)
+        .into_iter()
+        .find(|name| *name != "null")
+        .unwrap_or("value")
+}
+
+/// The field's own doc comment, else its type's
+fn description<'a>(root: &'a Value, property: &'a Value) -> Option<&'a str> {
+    property
+        .get("description")
+        .or_else(|| resolve(root, property).get("description"))
+        .and_then(Value::as_str)
+}
+
+/// A map key, quoted when it isn't a bare TOML key
+fn toml_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        key.to_string()
+    } else {
+        toml::Value::String(key.to_string()).to_string()
+    }
+}
+
+fn line(out: &mut String, commented: bool, text: &str) {
+    if commented {
+        out.push_str("# ");
+    }
+    out.push_str(text);
+    out.push('\n');
+}
+
+fn comment(out: &mut String, text: Option<&str>) {
+    let Some(text) = text else {
+        return;
+    };
+    let mut current = String::from("#");
+    for word in text.split_whitespace() {
+        if current.len() > 1 && current.len() + 1 + word.len() > WIDTH {
+            out.push_str(&current);
+            out.push('\n');
+            current = String::from("#");
+        }
+        current.push(' ');
+        current.push_str(word);
+    }
+    out.push_str(&current);
+    out.push('\n');
+}
+
+fn serialization_error(cause: impl ToString) -> AppError {
+    AppError::Serialization(
+        ErrorValue::new(
+            ErrorCode::SerializationFailed,
+            "Failed to render default config",
+        )
+        .with_cause(cause.to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_loads_as_the_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.config.toml");
+        write_default(&path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("# One of: trace, debug, info, warn, error\nlevel = \"info\""));
+        assert!(content.contains("# [remote]"));
+        assert!(content.contains("# token = <string>"));
+        assert_eq!(AppConfig::load_from(&path).unwrap(), AppConfig::default());
+
+        // Never overwrites a file the user may have edited
+        assert!(write_default(&path).is_err());
+    }
+}
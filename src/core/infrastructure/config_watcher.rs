@@ -0,0 +1,237 @@
+// src/core/infrastructure/config_watcher.rs
+// Config hot reload: watch the config file, re-parse it on change, swap the
+// AppConfig registered in the DI container and publish `config.changed`
+
+#![allow(dead_code)]
+
+use log::{info, warn};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::config::AppConfig;
+use super::di::{self, Container};
+use super::error_handler;
+use super::event_bus::{Subscription, GLOBAL_EVENT_BUS};
+use crate::core::domain::events::{ConfigChanged, FileChanged};
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::utils::file_ops::watcher::FileWatcher;
+
+/// A running watcher; shutting it down stops reloading
+pub struct ConfigWatcher {
+    path: PathBuf,
+    /// The watch on the config file's directory and the subscription that
+    /// reloads on its `FileChanged` events; dropping them stops both
+    running: Mutex<Option<(FileWatcher, Subscription<'static>)>>,
+}
+
+impl ConfigWatcher {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl di::Shutdown for ConfigWatcher {
+    fn shutdown(&self) -> AppResult<()> {
+        if let Ok(mut running) = self.running.lock() {
+            running.take();
+        }
+        Ok(())
+    }
+}
+
+/// Re-read `path` and, if it is valid and differs from the `AppConfig` in
+/// `container`, register it in place of the old one. A file that fails to
+/// parse or validate leaves the current config in place.
+pub fn reload(container: &Container, path: &Path) -> Result<Option<ConfigChanged>, Vec<AppError>> {
+    let config = AppConfig::load_from(path)?;
+    let current = container.resolve_arc::<AppConfig>().map_err(|e| vec![e])?;
+    let sections = current.changed_sections(&config);
+    if sections.is_empty() {
+        return Ok(None);
+    }
+    let changed = ConfigChanged {
+        path: path.display().to_string(),
+        sections,
+        changes: current.changed_settings(&config),
+    };
+    // Re-registering swaps the entry under the container's write lock, so a
+    // resolver sees either the old or the new config, never a mix
+    container.register_singleton(config).map_err(|e| vec![e])?;
+    Ok(Some(changed))
+}
+
+fn watch_error(path: &Path, cause: impl ToString) -> AppError {
+    AppError::Configuration(
+        ErrorValue::new(ErrorCode::ConfigInvalid, "Failed to watch config file")
+            .with_cause(cause.to_string())
+            .with_context("path", path.display().to_string()),
+    )
+}
+
+/// Reload `path` into `container`, publishing `ConfigChanged` if that
+/// changed something
+fn reload_and_publish(container: &Container, path: &Path) {
+    match reload(container, path) {
+        Ok(Some(changed)) => {
+            info!(
+                "Configuration reloaded from {}: {}",
+                changed.path,
+                changed.sections.join(", ")
+            );
+            if let Err(e) = GLOBAL_EVENT_BUS.publish_typed(&changed) {
+                error_handler::record_app_error("CONFIG", &e);
+            }
+        }
+        Ok(None) => {}
+        Err(errors) => {
+            for e in &errors {
+                error_handler::record_app_error("CONFIG", e);
+            }
+            warn!("Keeping the current configuration");
+        }
+    }
+}
+
+/// Watch `path` and reload it into `container` whenever it changes,
+/// publishing a `ConfigChanged` event for each reload that changed something
+pub fn start(container: &'static Container, path: &Path) -> AppResult<ConfigWatcher> {
+    let path = path.canonicalize().map_err(|e| watch_error(path, e))?;
+    let (Some(dir), Some(file_name)) = (path.parent(), path.file_name()) else {
+        return Err(watch_error(&path, "not a file path"));
+    };
+    let file_name = file_name.to_os_string();
+
+    // Watch the directory: editors that save by renaming replace the file,
+    // which would end a watch on the file itself. The watcher's debounce
+    // turns an editor's truncate, write and rename into one reload.
+    let watcher = FileWatcher::watch(dir, false).map_err(|e| watch_error(&path, e))?;
+    let root = watcher.path().display().to_string();
+    let reload_path = path.clone();
+    let subscription = GLOBAL_EVENT_BUS.subscribe_typed(move |event: FileChanged| {
+        let ours = event.path == root
+            && event
+                .changes
+                .iter()
+                .any(|c| Path::new(&c.path).file_name() == Some(&file_name));
+        if ours {
+            reload_and_publish(container, &reload_path);
+        }
+    });
+
+    info!("Watching {} for configuration changes", path.display());
+    Ok(ConfigWatcher {
+        path,
+        running: Mutex::new(Some((watcher, subscription))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn config_toml(level: &str) -> String {
+        format!(
+            r#"
+[app]
+name = "Rust WebUI Application"
+version = "1.0.0"
+
+[executable]
+name = "rustwebui-app"
+
+[database]
+path = "app.db"
+
+[window]
+title = "Rust WebUI Application"
+
+[logging]
+level = "{}"
+file = "application.log"
+
+[communication]
+
+[features]
+"#,
+            level
+        )
+    }
+
+    #[test]
+    fn test_reload_swaps_config_and_reports_sections() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(file.path(), config_toml("info")).unwrap();
+        let container = Container::new();
+        container
+            .register_singleton(AppConfig::load_from(file.path()).unwrap())
+            .unwrap();
+
+        // Unchanged file: nothing to publish
+        assert!(reload(&container, file.path()).unwrap().is_none());
+
+        fs::write(file.path(), config_toml("debug")).unwrap();
+        let changed = reload(&container, file.path()).unwrap().unwrap();
+        assert_eq!(changed.sections, ["logging"]);
+        assert!(changed.touches("logging"));
+        assert_eq!(changed.changes.len(), 1);
+        assert_eq!(changed.changes[0].key, "logging.level");
+        assert_eq!(
+            changed.changes[0].old_value,
+            Some(serde_json::json!("info"))
+        );
+        assert_eq!(
+            changed.changes[0].new_value,
+            Some(serde_json::json!("debug"))
+        );
+        assert_eq!(
+            container
+                .resolve_arc::<AppConfig>()
+                .unwrap()
+                .get_log_level(),
+            "debug"
+        );
+
+        // A broken or invalid file keeps the current config
+        fs::write(file.path(), "[logging\nlevel = ").unwrap();
+        assert!(reload(&container, file.path()).is_err());
+        fs::write(file.path(), config_toml("verbose")).unwrap();
+        assert!(reload(&container, file.path()).is_err());
+        assert_eq!(
+            container
+                .resolve_arc::<AppConfig>()
+                .unwrap()
+                .get_log_level(),
+            "debug"
+        );
+    }
+
+    #[test]
+    fn test_start_reloads_when_the_file_changes() {
+        use std::time::{Duration, Instant};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.config.toml");
+        fs::write(&path, config_toml("info")).unwrap();
+        let container: &'static Container = Box::leak(Box::new(Container::new()));
+        container
+            .register_singleton(AppConfig::load_from(&path).unwrap())
+            .unwrap();
+        let watcher = start(container, &path).expect("Failed to watch config");
+
+        fs::write(&path, config_toml("warn")).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while container.resolve_arc::<AppConfig>().unwrap().get_log_level() != "warn" {
+            assert!(Instant::now() < deadline, "Config was not reloaded");
+            GLOBAL_EVENT_BUS.flush();
+            std::thread::sleep(Duration::from_millis(50));
+        }
+
+        // After shutdown, edits are no longer picked up
+        di::Shutdown::shutdown(&watcher).unwrap();
+        fs::write(&path, config_toml("error")).unwrap();
+        std::thread::sleep(Duration::from_millis(500));
+        GLOBAL_EVENT_BUS.flush();
+        assert_eq!(container.resolve_arc::<AppConfig>().unwrap().get_log_level(), "warn");
+    }
+}
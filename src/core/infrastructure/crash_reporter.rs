@@ -0,0 +1,327 @@
+// src/core/infrastructure/crash_reporter.rs
+// Crash reports: a panic, or an error that stops startup, is saved as JSON
+// with the app version, OS, latest log records, backtrace and configured
+// plugins. Reports stay in the crash directory until the user, on a later
+// run, agrees to submit one to `[logging] crash_report_url` or discards it;
+// nothing leaves the machine without that consent.
+
+use chrono::Utc;
+use log::{error, info};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::config::AppConfig;
+use super::error_handler::PanicReport;
+use super::http_client;
+use super::logging::{LogEntry, LogQuery, GLOBAL_LOG_BUFFER};
+use crate::core::error::{
+    retry_with_backoff, AppError, AppResult, ErrorCode, ErrorValue, RetryPolicy,
+};
+
+/// Latest log records kept in a report
+pub const RECENT_LOG_RECORDS: usize = 100;
+
+/// Submitted reports are moved into this subdirectory of the crash directory
+const SUBMITTED_DIR: &str = "submitted";
+
+/// A crash as saved to `<crash_dir>/<id>.json` and submitted
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CrashReport {
+    /// File name without extension, e.g. "crash-20260102-030405-4242"
+    pub id: String,
+    /// "panic" or "fatal"
+    pub kind: String,
+    pub message: String,
+    /// file:line:column of a panic
+    pub location: Option<String>,
+    pub thread: Option<String>,
+    pub timestamp: String,
+    pub app_version: String,
+    /// Operating system and CPU architecture, e.g. "linux x86_64"
+    pub os: String,
+    pub backtrace: String,
+    /// Log records leading up to the crash, oldest first
+    pub recent_logs: Vec<LogEntry>,
+    /// Ids of the `[plugins.<id>]` tables in the config
+    pub active_plugins: Vec<String>,
+}
+
+/// A saved report waiting for the user to submit or discard it
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CrashReportSummary {
+    pub id: String,
+    pub kind: String,
+    pub message: String,
+    pub timestamp: String,
+    pub app_version: String,
+    pub path: String,
+}
+
+/// What `configure` takes from the config
+#[derive(Default)]
+struct Settings {
+    dir: Option<PathBuf>,
+    submit_url: Option<String>,
+    plugins: Vec<String>,
+}
+
+lazy_static::lazy_static! {
+    static ref SETTINGS: Mutex<Settings> = Mutex::new(Settings::default());
+}
+
+/// Take the crash directory, submit URL and plugin list from `config`.
+/// Until then reports go to the system temp directory.
+pub fn configure(config: &AppConfig) {
+    if let Ok(mut settings) = SETTINGS.lock() {
+        *settings = Settings {
+            dir: Some(config.get_crash_dir()),
+            submit_url: config.get_crash_report_url().map(str::to_string),
+            plugins: config.get_plugin_ids(),
+        };
+    }
+}
+
+/// Where reports are written and listed from
+pub fn crash_dir() -> PathBuf {
+    SETTINGS
+        .lock()
+        .ok()
+        .and_then(|settings| settings.dir.clone())
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+impl CrashReport {
+    /// A report of the running process; `id` is set when it is saved
+    pub fn new(kind: &str, message: impl Into<String>, backtrace: String) -> Self {
+        // Records still queued for the writer thread reach the buffer first
+        log::logger().flush();
+        let recent_logs = GLOBAL_LOG_BUFFER.tail(&LogQuery {
+            limit: Some(RECENT_LOG_RECORDS),
+            ..LogQuery::default()
+        });
+        let active_plugins = SETTINGS
+            .lock()
+            .map(|settings| settings.plugins.clone())
+            .unwrap_or_default();
+        Self {
+            id: String::new(),
+            kind: kind.to_string(),
+            message: message.into(),
+            location: None,
+            thread: None,
+            timestamp: Utc::now().to_rfc3339(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+            backtrace,
+            recent_logs,
+            active_plugins,
+        }
+    }
+
+    pub fn from_panic(panic: &PanicReport) -> Self {
+        Self {
+            location: Some(panic.location.clone()),
+            thread: Some(panic.thread.clone()),
+            timestamp: panic.timestamp.clone(),
+            ..Self::new("panic", panic.message.as_str(), panic.backtrace.clone())
+        }
+    }
+
+    fn summary(&self, path: &Path) -> CrashReportSummary {
+        CrashReportSummary {
+            id: self.id.clone(),
+            kind: self.kind.clone(),
+            message: self.message.clone(),
+            timestamp: self.timestamp.clone(),
+            app_version: self.app_version.clone(),
+            path: path.display().to_string(),
+        }
+    }
+}
+
+/// Save `report` as `crash-<time>-<pid>.json` in `dir`, setting its id
+pub fn write(dir: &Path, report: &mut CrashReport) -> AppResult<PathBuf> {
+    fs::create_dir_all(dir)?;
+    report.id = format!(
+        "crash-{}-{}",
+        Utc::now().format("%Y%m%d-%H%M%S"),
+        std::process::id()
+    );
+    let path = dir.join(format!("{}.json", report.id));
+    fs::write(&path, serde_json::to_vec_pretty(report)?)?;
+    Ok(path)
+}
+
+/// Save a panic report in the crash directory; `None` if it couldn't be
+/// written, which is logged
+pub fn record_panic(panic: &PanicReport) -> Option<PathBuf> {
+    save(CrashReport::from_panic(panic))
+}
+
+/// Save a report for an error that stops the app, e.g. a service that
+/// failed to start
+pub fn record_fatal(source: &str, err: &AppError) -> Option<PathBuf> {
+    let backtrace = format!("{:?}", backtrace::Backtrace::new());
+    save(CrashReport::new(
+        "fatal",
+        format!("{}: {}", source, err),
+        backtrace,
+    ))
+}
+
+fn save(mut report: CrashReport) -> Option<PathBuf> {
+    let dir = crash_dir();
+    match write(&dir, &mut report) {
+        Ok(path) => {
+            error!("Crash report written to {}", path.display());
+            Some(path)
+        }
+        Err(e) => {
+            error!("Failed to write crash report to {}: {}", dir.display(), e);
+            None
+        }
+    }
+}
+
+/// Reports in `dir` not yet submitted or discarded, oldest first
+pub fn pending(dir: &Path) -> Vec<CrashReportSummary> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut reports: Vec<CrashReportSummary> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let report: CrashReport = serde_json::from_slice(&fs::read(&path).ok()?).ok()?;
+            Some(report.summary(&path))
+        })
+        .collect();
+    reports.sort_by(|a, b| a.id.cmp(&b.id));
+    reports
+}
+
+/// Send report `id` to the configured URL, then move it to `submitted/`.
+/// Only call this once the user has agreed to send it.
+pub fn submit(dir: &Path, id: &str) -> AppResult<CrashReportSummary> {
+    let url = SETTINGS
+        .lock()
+        .ok()
+        .and_then(|settings| settings.submit_url.clone())
+        .ok_or_else(|| {
+            AppError::Configuration(ErrorValue::new(
+                ErrorCode::ConfigMissingField,
+                "No [logging] crash_report_url is configured",
+            ))
+        })?;
+    let path = report_path(dir, id)?;
+    let report: CrashReport = serde_json::from_slice(&fs::read(&path)?)?;
+
+    let client = http_client::shared()?;
+    retry_with_backoff(&RetryPolicy::network(), |_| {
+        client
+            .post(&url)
+            .timeout(Duration::from_secs(30))
+            .json(&report)
+            .send()
+            .and_then(reqwest::blocking::Response::error_for_status)
+            .map(|_| ())
+            .map_err(|e| submit_error(&url, e))
+    })?;
+
+    let submitted = dir.join(SUBMITTED_DIR);
+    fs::create_dir_all(&submitted)?;
+    let moved = submitted.join(format!("{}.json", id));
+    fs::rename(&path, &moved)?;
+    info!("Crash report {} submitted to {}", id, url);
+    Ok(report.summary(&moved))
+}
+
+/// Delete report `id` without sending it
+pub fn discard(dir: &Path, id: &str) -> AppResult<()> {
+    fs::remove_file(report_path(dir, id)?)?;
+    info!("Crash report {} discarded", id);
+    Ok(())
+}
+
+/// `<dir>/<id>.json`, refusing ids that aren't plain report names
+fn report_path(dir: &Path, id: &str) -> AppResult<PathBuf> {
+    let valid =
+        id.starts_with("crash-") && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    let path = dir.join(format!("{}.json", id));
+    if !valid || !path.is_file() {
+        return Err(AppError::NotFound(
+            ErrorValue::new(ErrorCode::ResourceNotFound, "No such crash report")
+                .with_context("id", id),
+        ));
+    }
+    Ok(path)
+}
+
+fn submit_error(url: &str, err: reqwest::Error) -> AppError {
+    let code = ErrorCode::for_http(&err, ErrorCode::InternalError);
+    AppError::Logging(
+        ErrorValue::new(code, "Failed to submit crash report")
+            .with_cause(err.to_string())
+            .with_context("url", url),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> CrashReport {
+        let panic = PanicReport {
+            message: String::from("index out of bounds"),
+            location: String::from("src/main.rs:10:5"),
+            thread: String::from("main"),
+            timestamp: String::from("2026-01-02T03:04:05+00:00"),
+            backtrace: String::from("   0: rustwebui_app::main"),
+            crash_file: None,
+        };
+        CrashReport::from_panic(&panic)
+    }
+
+    #[test]
+    fn test_written_reports_are_pending_until_discarded() {
+        let dir = tempfile::tempdir().unwrap();
+        let crashes = dir.path().join("crashes");
+        assert!(pending(&crashes).is_empty());
+
+        let mut report = report();
+        let path = write(&crashes, &mut report).unwrap();
+        assert!(report.id.starts_with("crash-"));
+        assert_eq!(path.file_stem().unwrap().to_string_lossy(), report.id);
+
+        let saved: CrashReport = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(saved.kind, "panic");
+        assert_eq!(saved.location.as_deref(), Some("src/main.rs:10:5"));
+        assert_eq!(saved.app_version, env!("CARGO_PKG_VERSION"));
+        assert!(saved.os.starts_with(std::env::consts::OS));
+
+        let listed = pending(&crashes);
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, report.id);
+        assert_eq!(listed[0].message, "index out of bounds");
+
+        discard(&crashes, &report.id).unwrap();
+        assert!(pending(&crashes).is_empty());
+    }
+
+    #[test]
+    fn test_report_ids_cannot_leave_the_crash_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("config.json"), "{}").unwrap();
+        for id in ["../config", "config", "crash-../../config", "crash-missing"] {
+            assert!(matches!(
+                discard(dir.path(), id),
+                Err(AppError::NotFound(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,148 @@
+// src/core/infrastructure/database/api_keys.rs
+// API keys issued to automation clients. Only a SHA-256 hash of each key is
+// stored, with the scopes it grants; a revoked key keeps its row so the
+// audit trail can still name it. Keys are looked up by their prefix and the
+// hash is compared by the caller, in constant time.
+
+use rusqlite::{params, OptionalExtension, Row};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::connection::Database;
+use crate::core::error::{AppError, ErrorCode, ErrorValue};
+
+type DbResult<T> = Result<T, AppError>;
+
+/// An issued key as handlers and the frontend see it; never the key itself
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ApiKeyRecord {
+    pub id: i64,
+    pub name: String,
+    /// First characters of the key, to tell keys apart
+    pub prefix: String,
+    /// Permissions the key grants, e.g. "users:read" or "settings:*"
+    pub scopes: Vec<String>,
+    /// The user who issued it, when known
+    pub created_by: Option<i64>,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+    /// Set once the key is revoked; it is refused from then on
+    pub revoked_at: Option<String>,
+}
+
+const COLUMNS: &str =
+    "id, name, prefix, scopes, created_by, created_at, last_used_at, revoked_at";
+
+fn record(row: &Row) -> rusqlite::Result<ApiKeyRecord> {
+    let scopes: String = row.get(3)?;
+    Ok(ApiKeyRecord {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        prefix: row.get(2)?,
+        scopes: scopes.split_whitespace().map(str::to_string).collect(),
+        created_by: row.get(4)?,
+        created_at: row.get(5)?,
+        last_used_at: row.get(6)?,
+        revoked_at: row.get(7)?,
+    })
+}
+
+impl Database {
+    /// Create the api_keys table
+    #[tracing::instrument(skip_all)]
+    pub fn init_api_keys(&self) -> DbResult<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS api_keys (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                prefix TEXT NOT NULL,
+                key_hash TEXT NOT NULL UNIQUE,
+                scopes TEXT NOT NULL,
+                created_by INTEGER,
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                last_used_at TEXT,
+                revoked_at TEXT
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_api_keys_prefix ON api_keys(prefix)",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Store a new key by its hash; returns its id
+    pub fn insert_api_key(
+        &self,
+        name: &str,
+        prefix: &str,
+        key_hash: &str,
+        scopes: &[String],
+        created_by: Option<i64>,
+    ) -> DbResult<i64> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "INSERT INTO api_keys (name, prefix, key_hash, scopes, created_by)
+             VALUES (?, ?, ?, ?, ?)",
+            params![name, prefix, key_hash, scopes.join(" "), created_by],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Key `id`, revoked or not
+    pub fn api_key(&self, id: i64) -> DbResult<Option<ApiKeyRecord>> {
+        let conn = self.get_conn()?;
+        let sql = format!("SELECT {} FROM api_keys WHERE id = ?", COLUMNS);
+        Ok(conn.query_row(&sql, [id], record).optional()?)
+    }
+
+    /// Keys shown as `prefix`, revoked or not, each with its stored hash
+    pub fn api_keys_by_prefix(&self, prefix: &str) -> DbResult<Vec<(ApiKeyRecord, String)>> {
+        let conn = self.get_conn()?;
+        let sql = format!("SELECT {}, key_hash FROM api_keys WHERE prefix = ?", COLUMNS);
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map([prefix], |row| Ok((record(row)?, row.get(8)?)))?;
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(|e| {
+            AppError::Database(
+                ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to read API keys")
+                    .with_cause(e.to_string()),
+            )
+        })
+    }
+
+    /// Every issued key, newest first
+    pub fn api_keys(&self) -> DbResult<Vec<ApiKeyRecord>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM api_keys ORDER BY id DESC", COLUMNS))?;
+        let rows = stmt.query_map([], record)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(|e| {
+            AppError::Database(
+                ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to read API keys")
+                    .with_cause(e.to_string()),
+            )
+        })
+    }
+
+    /// Note that key `id` was just used
+    pub fn touch_api_key(&self, id: i64) -> DbResult<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE api_keys SET last_used_at = datetime('now') WHERE id = ?",
+            [id],
+        )?;
+        Ok(())
+    }
+
+    /// Revoke key `id`; false when there is no such key or it already was
+    pub fn revoke_api_key(&self, id: i64) -> DbResult<bool> {
+        let conn = self.get_conn()?;
+        let rows = conn.execute(
+            "UPDATE api_keys SET revoked_at = datetime('now')
+             WHERE id = ? AND revoked_at IS NULL",
+            [id],
+        )?;
+        Ok(rows > 0)
+    }
+}
@@ -0,0 +1,135 @@
+// src/core/infrastructure/database/audit_log.rs
+// Audit trail of security events: sign-ins, failed sign-ins, lockouts,
+// sign-outs, password changes, refused permissions, settings changes and raw
+// SQL, with the user and what they were about
+
+use rusqlite::params;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::connection::Database;
+use crate::core::error::{AppError, ErrorCode, ErrorValue};
+
+type DbResult<T> = Result<T, AppError>;
+
+/// One recorded security event
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct AuditEntry {
+    pub id: i64,
+    /// What happened, e.g. "login" or "login_failed"
+    pub event: String,
+    /// The user it happened to, when known
+    pub user_id: Option<i64>,
+    /// What the event was about, e.g. the email a sign-in was tried with
+    pub subject: Option<String>,
+    pub detail: Option<String>,
+    pub created_at: String,
+}
+
+/// Which events `audit_entries` returns; unset fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct AuditFilter {
+    /// Only events of this kind, e.g. "login_failed"
+    pub event: Option<String>,
+    pub user_id: Option<i64>,
+    /// Only events whose subject contains this text
+    pub subject: Option<String>,
+    /// Only events at or after this UTC time, "YYYY-MM-DD HH:MM:SS" or a date
+    pub since: Option<String>,
+    /// Only events before this UTC time
+    pub until: Option<String>,
+    /// At most this many, newest first; 100 by default
+    pub limit: Option<u32>,
+}
+
+impl Database {
+    /// Create the audit_log table
+    #[tracing::instrument(skip_all)]
+    pub fn init_audit_log(&self) -> DbResult<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS audit_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                event TEXT NOT NULL,
+                user_id INTEGER,
+                subject TEXT,
+                detail TEXT,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_audit_log_event ON audit_log(event)",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Record one security event
+    #[tracing::instrument(skip(self, detail))]
+    pub fn record_audit(
+        &self,
+        event: &str,
+        user_id: Option<i64>,
+        subject: Option<&str>,
+        detail: Option<&str>,
+    ) -> DbResult<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "INSERT INTO audit_log (event, user_id, subject, detail) VALUES (?, ?, ?, ?)",
+            params![event, user_id, subject, detail],
+        )?;
+        Ok(())
+    }
+
+    /// The latest `limit` events, newest first
+    #[allow(dead_code)]
+    pub fn audit_log(&self, limit: i64) -> DbResult<Vec<AuditEntry>> {
+        self.audit_entries(&AuditFilter {
+            limit: Some(u32::try_from(limit).unwrap_or(u32::MAX)),
+            ..Default::default()
+        })
+    }
+
+    /// The latest events matching `filter`, newest first
+    #[tracing::instrument(skip(self))]
+    pub fn audit_entries(&self, filter: &AuditFilter) -> DbResult<Vec<AuditEntry>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, event, user_id, subject, detail, created_at
+             FROM audit_log
+             WHERE (?1 IS NULL OR event = ?1)
+               AND (?2 IS NULL OR user_id = ?2)
+               AND (?3 IS NULL OR instr(subject, ?3) > 0)
+               AND (?4 IS NULL OR created_at >= ?4)
+               AND (?5 IS NULL OR created_at < ?5)
+             ORDER BY id DESC
+             LIMIT ?6",
+        )?;
+        let query = params![
+            filter.event,
+            filter.user_id,
+            filter.subject,
+            filter.since,
+            filter.until,
+            filter.limit.unwrap_or(100),
+        ];
+        let rows = stmt.query_map(query, |row| {
+            Ok(AuditEntry {
+                id: row.get(0)?,
+                event: row.get(1)?,
+                user_id: row.get(2)?,
+                subject: row.get(3)?,
+                detail: row.get(4)?,
+                created_at: row.get(5)?,
+            })
+        })?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(|e| {
+            AppError::Database(
+                ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to read audit log")
+                    .with_cause(e.to_string()),
+            )
+        })
+    }
+}
@@ -0,0 +1,164 @@
+// src/core/infrastructure/database/config_audit.rs
+// Audit trail of settings changes: which key changed, from what to what,
+// and whether the UI, the config file, an environment variable or a
+// command-line option changed it
+
+use rusqlite::params;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::connection::Database;
+use crate::core::domain::events::SettingChange;
+use crate::core::error::{AppError, ErrorCode, ErrorValue};
+
+type DbResult<T> = Result<T, AppError>;
+
+/// One recorded settings change
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ConfigAuditEntry {
+    pub id: i64,
+    /// Dotted key, e.g. "logging.level"
+    pub key: String,
+    /// `None` where the setting was unset
+    pub old_value: Option<serde_json::Value>,
+    pub new_value: Option<serde_json::Value>,
+    /// "ui", "file", "env" or "cli"
+    pub source: String,
+    pub changed_at: String,
+}
+
+fn to_json(value: &Option<serde_json::Value>) -> Option<String> {
+    value.as_ref().map(serde_json::Value::to_string)
+}
+
+fn from_json(value: Option<String>) -> Option<serde_json::Value> {
+    value.map(|v| serde_json::from_str(&v).unwrap_or(serde_json::Value::String(v)))
+}
+
+impl Database {
+    /// Create the config_audit table
+    #[tracing::instrument(skip_all)]
+    pub fn init_config_audit(&self) -> DbResult<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS config_audit (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                key TEXT NOT NULL,
+                old_value TEXT,
+                new_value TEXT,
+                source TEXT NOT NULL,
+                changed_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_config_audit_key ON config_audit(key)",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Record `changes`, all made by `source`, in one transaction
+    #[tracing::instrument(skip(self, changes))]
+    pub fn record_config_changes(&self, source: &str, changes: &[SettingChange]) -> DbResult<()> {
+        let mut conn = self.get_conn()?;
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO config_audit (key, old_value, new_value, source) VALUES (?, ?, ?, ?)",
+            )?;
+            for change in changes {
+                stmt.execute(params![
+                    change.key,
+                    to_json(&change.old_value),
+                    to_json(&change.new_value),
+                    source
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// The latest `limit` changes, newest first; only those to `key` when given
+    #[tracing::instrument(skip(self))]
+    pub fn config_audit(&self, key: Option<&str>, limit: i64) -> DbResult<Vec<ConfigAuditEntry>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, key, old_value, new_value, source, changed_at
+             FROM config_audit
+             WHERE ?1 IS NULL OR key = ?1
+             ORDER BY id DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![key, limit], |row| {
+            Ok(ConfigAuditEntry {
+                id: row.get(0)?,
+                key: row.get(1)?,
+                old_value: from_json(row.get(2)?),
+                new_value: from_json(row.get(3)?),
+                source: row.get(4)?,
+                changed_at: row.get(5)?,
+            })
+        })?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(|e| {
+            AppError::Database(
+                ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to read config audit")
+                    .with_cause(e.to_string()),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_config_changes_are_recorded_newest_first() {
+        let db = Database::new(":memory:").expect("Failed to create database");
+        db.init_config_audit().expect("Failed to init config audit");
+
+        db.record_config_changes(
+            "cli",
+            &[SettingChange {
+                key: String::from("logging.level"),
+                old_value: Some(json!("info")),
+                new_value: Some(json!("debug")),
+            }],
+        )
+        .expect("Failed to record changes");
+        db.record_config_changes(
+            "ui",
+            &[SettingChange {
+                key: String::from("remote.port"),
+                old_value: None,
+                new_value: Some(json!(9000)),
+            }],
+        )
+        .expect("Failed to record changes");
+
+        let entries = db.config_audit(None, 10).expect("Failed to read audit");
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|e| (e.key.as_str(), e.source.as_str(), e.old_value.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("remote.port", "ui", None),
+                ("logging.level", "cli", Some(json!("info")))
+            ]
+        );
+        assert_eq!(entries[0].new_value, Some(json!(9000)));
+
+        let level = db
+            .config_audit(Some("logging.level"), 10)
+            .expect("Failed to read audit");
+        assert_eq!(level.len(), 1);
+        assert_eq!(level[0].new_value, Some(json!("debug")));
+        assert_eq!(db.config_audit(None, 1).unwrap().len(), 1);
+    }
+}
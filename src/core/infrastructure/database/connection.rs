@@ -4,11 +4,16 @@
 use log::{error, info};
 use r2d2::{Pool, PooledConnection};
 use r2d2_sqlite::SqliteConnectionManager;
-use rusqlite::{Connection, Result as SqliteResult, ToSql};
+use rusqlite::{Connection, OpenFlags, Result as SqliteResult, ToSql};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
 use std::time::Duration;
 
-use crate::core::error::{AppResult, ErrorValue, ErrorCode, AppError};
+use crate::core::error::{retry_with_backoff, AppResult, ErrorValue, ErrorCode, AppError, RetryPolicy};
+use crate::core::infrastructure::di::Shutdown;
+use crate::core::infrastructure::security_audit;
 
+use super::event_store::EventStoreOptions;
 use super::models::QueryResult;
 
 /// Connection pool configuration
@@ -35,6 +40,8 @@ pub struct Database {
     pool: Pool<SqliteConnectionManager>,
     #[allow(dead_code)]
     config: DbPoolConfig,
+    /// Set once the event store is initialized
+    pub(super) event_store: OnceLock<EventStoreOptions>,
 }
 
 impl Database {
@@ -53,14 +60,32 @@ impl Database {
         );
 
         // Configure SQLite connection manager
-        let manager = SqliteConnectionManager::file(db_path);
+        let in_memory = db_path == ":memory:";
+        let manager = if in_memory {
+            // Each connection to ":memory:" would open its own empty
+            // database; a named shared-cache one is seen by the whole pool
+            static NEXT_MEMORY_DB: AtomicUsize = AtomicUsize::new(0);
+            let id = NEXT_MEMORY_DB.fetch_add(1, Ordering::Relaxed);
+            SqliteConnectionManager::file(format!("file:memdb{}?mode=memory&cache=shared", id))
+                .with_flags(OpenFlags::default() | OpenFlags::SQLITE_OPEN_URI)
+        } else {
+            SqliteConnectionManager::file(db_path)
+        };
 
         // Build connection pool
-        let pool = Pool::builder()
+        let mut builder = Pool::builder()
             .max_size(config.max_size)
             .min_idle(Some(config.min_size))
             .connection_timeout(config.connection_timeout)
-            .idle_timeout(config.idle_timeout)
+            .idle_timeout(config.idle_timeout);
+        if in_memory {
+            // The database is gone once its last connection closes
+            builder = builder
+                .min_idle(Some(config.min_size.max(1)))
+                .idle_timeout(None)
+                .max_lifetime(None);
+        }
+        let pool = builder
             .build(manager)
             .map_err(|e: r2d2::Error| {
                 AppError::Database(
@@ -75,7 +100,11 @@ impl Database {
 
         info!("Database connection pool created successfully: {}", db_path);
 
-        Ok(Self { pool, config })
+        Ok(Self {
+            pool,
+            config,
+            event_store: OnceLock::new(),
+        })
     }
 
     /// Get a connection from the pool
@@ -90,6 +119,7 @@ impl Database {
     }
 
     /// Initialize the database schema
+    #[tracing::instrument(skip_all)]
     pub fn init(&self) -> AppResult<()> {
         let conn = self.get_conn()?;
 
@@ -137,6 +167,7 @@ impl Database {
     }
 
     /// Execute a raw SELECT query and return results as JSON
+    #[tracing::instrument(skip_all)]
     pub fn query(&self, sql: &str, params: &[&dyn ToSql]) -> AppResult<QueryResult> {
         let conn = self.get_conn()?;
         
@@ -166,37 +197,47 @@ impl Database {
         Ok(QueryResult::success(data, "Query executed successfully"))
     }
 
-    /// Execute a raw INSERT, UPDATE, or DELETE query
+    /// Execute a raw INSERT, UPDATE, or DELETE query, retried while another
+    /// connection holds the database locked. Each one is published as an
+    /// `sql_executed` security event.
     #[allow(dead_code)]
+    #[tracing::instrument(skip_all)]
     pub fn execute(&self, sql: &str, params: &[&dyn ToSql]) -> AppResult<QueryResult> {
-        let conn = self.get_conn()?;
-        let rows_affected = conn.execute(sql, params)?;
+        let rows_affected = retry_with_backoff(&RetryPolicy::database(), |_| {
+            Ok(self.get_conn()?.execute(sql, params)?)
+        })?;
+        let detail = format!("{} rows affected", rows_affected);
+        security_audit::emit("sql_executed", None, sql.trim(), Some(&detail));
 
         Ok(QueryResult::success(vec![], "Query executed successfully")
             .with_rows_affected(rows_affected))
     }
 
-    /// Execute within a transaction
+    /// Execute within a transaction. When the database is busy the
+    /// transaction is rolled back and `f` runs again in a new one.
     #[allow(dead_code)]
-    pub fn transaction<F, T>(&self, f: F) -> AppResult<T>
+    #[tracing::instrument(skip_all)]
+    pub fn transaction<F, T>(&self, mut f: F) -> AppResult<T>
     where
-        F: FnOnce(&Connection) -> AppResult<T>,
+        F: FnMut(&Connection) -> AppResult<T>,
     {
-        let conn = self.get_conn()?;
-        
-        conn.execute("BEGIN", [])?;
-        
-        match f(&conn) {
-            Ok(result) => {
+        retry_with_backoff(&RetryPolicy::database(), |_| {
+            let conn = self.get_conn()?;
+
+            conn.execute("BEGIN", [])?;
+
+            match f(&conn).and_then(|result| {
                 conn.execute("COMMIT", [])?;
                 Ok(result)
+            }) {
+                Ok(result) => Ok(result),
+                Err(e) => {
+                    conn.execute("ROLLBACK", [])?;
+                    error!("Transaction rolled back due to error: {}", e);
+                    Err(e)
+                }
             }
-            Err(e) => {
-                conn.execute("ROLLBACK", [])?;
-                error!("Transaction rolled back due to error: {}", e);
-                Err(e)
-            }
-        }
+        })
     }
 
     /// Get pool statistics
@@ -231,6 +272,20 @@ impl Database {
     }
 }
 
+impl Shutdown for Database {
+    /// Let SQLite refresh its query planner statistics before exit. The
+    /// pooled connections close when the last handle to the pool is dropped.
+    fn shutdown(&self) -> AppResult<()> {
+        let stats = self.pool_stats();
+        self.get_conn()?.execute_batch("PRAGMA optimize")?;
+        info!(
+            "Database closed: connections={}, idle={}",
+            stats.connections, stats.idle_connections
+        );
+        Ok(())
+    }
+}
+
 /// Pool statistics for monitoring
 #[derive(Debug, Clone)]
 pub struct PoolStats {
@@ -261,8 +316,8 @@ mod tests {
         let conn2 = db.get_conn().expect("Failed to get second connection");
         
         // Both connections should be usable
-        assert!(conn1.is_valid().is_ok());
-        assert!(conn2.is_valid().is_ok());
+        assert!(conn1.execute_batch("SELECT 1").is_ok());
+        assert!(conn2.execute_batch("SELECT 1").is_ok());
         
         // Check pool stats
         let stats = db.pool_stats();
@@ -291,7 +346,7 @@ mod tests {
         db.init().expect("Failed to init");
 
         // This should rollback due to error
-        let result = db.transaction(|conn| {
+        let result: AppResult<()> = db.transaction(|conn| {
             conn.execute(
                 "INSERT INTO users (name, email, role, status) VALUES (?, ?, ?, ?)",
                 ["Test User", "test@example.com", "Admin", "Active"],
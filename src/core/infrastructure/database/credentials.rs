@@ -0,0 +1,141 @@
+// src/core/infrastructure/database/credentials.rs
+// Password hashes and failed sign-in counters per user. A user without a
+// row can't sign in with a password.
+
+use rusqlite::{params, OptionalExtension};
+
+use super::connection::Database;
+use crate::core::error::AppError;
+
+type DbResult<T> = Result<T, AppError>;
+
+/// A user's stored password and lockout state
+#[derive(Debug, Clone)]
+pub struct CredentialRecord {
+    /// PHC string from `PasswordUtils::hash_password`
+    pub password_hash: String,
+    /// Failed sign-ins since the last success or lockout
+    #[allow(dead_code)]
+    pub failed_attempts: u32,
+    /// Unix seconds until which sign-ins are refused
+    pub locked_until: Option<i64>,
+}
+
+impl Database {
+    /// Create the user_credentials table
+    #[tracing::instrument(skip_all)]
+    pub fn init_credentials(&self) -> DbResult<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS user_credentials (
+                user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+                password_hash TEXT NOT NULL,
+                failed_attempts INTEGER NOT NULL DEFAULT 0,
+                locked_until INTEGER,
+                changed_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// `user_id`'s password hash and lockout state
+    pub fn credentials(&self, user_id: i64) -> DbResult<Option<CredentialRecord>> {
+        let conn = self.get_conn()?;
+        let record = conn
+            .query_row(
+                "SELECT password_hash, failed_attempts, locked_until
+                 FROM user_credentials WHERE user_id = ?",
+                [user_id],
+                |row| {
+                    Ok(CredentialRecord {
+                        password_hash: row.get(0)?,
+                        failed_attempts: row.get(1)?,
+                        locked_until: row.get(2)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(record)
+    }
+
+    /// Store a new password hash for `user_id`, clearing any lockout
+    pub fn set_password_hash(&self, user_id: i64, password_hash: &str) -> DbResult<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "INSERT INTO user_credentials (user_id, password_hash) VALUES (?1, ?2)
+             ON CONFLICT(user_id) DO UPDATE SET
+                 password_hash = excluded.password_hash,
+                 failed_attempts = 0,
+                 locked_until = NULL,
+                 changed_at = datetime('now')",
+            params![user_id, password_hash],
+        )?;
+        Ok(())
+    }
+
+    /// Count a failed sign-in; returns the failures in a row so far
+    pub fn record_failed_login(&self, user_id: i64) -> DbResult<u32> {
+        let conn = self.get_conn()?;
+        let attempts = conn
+            .query_row(
+                "UPDATE user_credentials SET failed_attempts = failed_attempts + 1
+                 WHERE user_id = ? RETURNING failed_attempts",
+                [user_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(attempts.unwrap_or(0))
+    }
+
+    /// Refuse sign-ins for `user_id` until `until` (Unix seconds)
+    pub fn lock_account(&self, user_id: i64, until: i64) -> DbResult<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE user_credentials SET failed_attempts = 0, locked_until = ? WHERE user_id = ?",
+            params![until, user_id],
+        )?;
+        Ok(())
+    }
+
+    /// Forget failed sign-ins and any lockout after a successful one
+    pub fn clear_failed_logins(&self, user_id: i64) -> DbResult<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE user_credentials SET failed_attempts = 0, locked_until = NULL
+             WHERE user_id = ?",
+            [user_id],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_logins_and_lockout() {
+        let db = Database::new(":memory:").expect("Failed to create database");
+        db.init().expect("Failed to init schema");
+        db.init_credentials().expect("Failed to init credentials");
+        let user = db
+            .insert_user("Ada", "ada@example.com", "Admin", "Active")
+            .expect("Failed to insert user");
+
+        assert!(db.credentials(user).unwrap().is_none());
+        assert_eq!(db.record_failed_login(user).unwrap(), 0);
+
+        db.set_password_hash(user, "hash-1").unwrap();
+        assert_eq!(db.record_failed_login(user).unwrap(), 1);
+        assert_eq!(db.record_failed_login(user).unwrap(), 2);
+        db.lock_account(user, 1_000).unwrap();
+        let locked = db.credentials(user).unwrap().unwrap();
+        assert_eq!((locked.failed_attempts, locked.locked_until), (0, Some(1_000)));
+
+        db.set_password_hash(user, "hash-2").unwrap();
+        let changed = db.credentials(user).unwrap().unwrap();
+        assert_eq!(changed.password_hash, "hash-2");
+        assert_eq!(changed.locked_until, None);
+    }
+}
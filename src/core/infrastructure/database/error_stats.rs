@@ -0,0 +1,105 @@
+// src/core/infrastructure/database/error_stats.rs
+// Persisted error telemetry: per-minute counts of each error code per
+// handler, kept when `[database] persist_error_stats` is on
+
+use rusqlite::params;
+
+use super::connection::Database;
+use crate::core::error::{AppError, ErrorCode, ErrorValue};
+use crate::core::infrastructure::error_stats::{current_minute, minute_to_rfc3339, ErrorStat};
+
+type DbResult<T> = Result<T, AppError>;
+
+/// Days of counts kept in the table
+const RETENTION_DAYS: i64 = 30;
+
+impl Database {
+    /// Create the error_stats table and drop counts older than 30 days
+    #[tracing::instrument(skip_all)]
+    pub fn init_error_stats(&self) -> DbResult<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS error_stats (
+                minute INTEGER NOT NULL,
+                handler TEXT NOT NULL,
+                code TEXT NOT NULL,
+                count INTEGER NOT NULL,
+                PRIMARY KEY (minute, handler, code)
+            )",
+            [],
+        )?;
+        let cutoff = current_minute() - RETENTION_DAYS * 24 * 60;
+        conn.execute("DELETE FROM error_stats WHERE minute < ?", [cutoff])?;
+        Ok(())
+    }
+
+    /// Count one `code` answer from `handler` in `minute`
+    pub fn record_error_stat(&self, minute: i64, handler: &str, code: &str) -> DbResult<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "INSERT INTO error_stats (minute, handler, code, count) VALUES (?, ?, ?, 1)
+             ON CONFLICT (minute, handler, code) DO UPDATE SET count = count + 1",
+            params![minute, handler, code],
+        )?;
+        Ok(())
+    }
+
+    /// Totals per handler and code from `since` (minutes since the epoch)
+    /// on; only `handler`'s when given
+    #[tracing::instrument(skip(self))]
+    pub fn error_stats_since(&self, since: i64, handler: Option<&str>) -> DbResult<Vec<ErrorStat>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT handler, code, SUM(count), MAX(minute)
+             FROM error_stats
+             WHERE minute >= ?1 AND (?2 IS NULL OR handler = ?2)
+             GROUP BY handler, code",
+        )?;
+        let rows = stmt.query_map(params![since, handler], |row| {
+            Ok(ErrorStat {
+                handler: row.get(0)?,
+                code: row.get(1)?,
+                count: row.get::<_, i64>(2)?.max(0) as u64,
+                last_seen: minute_to_rfc3339(row.get(3)?),
+            })
+        })?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(|e| {
+            AppError::Database(
+                ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to read error stats")
+                    .with_cause(e.to_string()),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_are_summed_per_handler_and_code() {
+        let db = Database::new(":memory:").expect("Failed to create database");
+        db.init_error_stats().expect("Failed to init error stats");
+
+        db.record_error_stat(100, "get_users", "DB_QUERY_FAILED")
+            .unwrap();
+        db.record_error_stat(100, "get_users", "DB_QUERY_FAILED")
+            .unwrap();
+        db.record_error_stat(101, "get_users", "DB_QUERY_FAILED")
+            .unwrap();
+        db.record_error_stat(101, "create_user", "VALIDATION_FAILED")
+            .unwrap();
+        db.record_error_stat(50, "create_user", "TIMEOUT").unwrap();
+
+        let mut stats = db.error_stats_since(100, None).unwrap();
+        stats.sort_by_key(|stat| std::cmp::Reverse(stat.count));
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].handler, "get_users");
+        assert_eq!(stats[0].count, 3);
+        assert_eq!(stats[0].last_seen, minute_to_rfc3339(101));
+
+        let stats = db.error_stats_since(0, Some("create_user")).unwrap();
+        assert_eq!(stats.len(), 2);
+    }
+}
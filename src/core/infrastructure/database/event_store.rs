@@ -0,0 +1,350 @@
+// src/core/infrastructure/database/event_store.rs
+// Event-sourcing store: domain events per aggregate, folded back into state,
+// with periodic snapshots so rebuilding doesn't replay the whole history
+
+use log::{error, info};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::connection::Database;
+use crate::core::domain::traits::Aggregate;
+use crate::core::error::{AppError, ErrorCode, ErrorValue};
+
+type DbResult<T> = Result<T, AppError>;
+
+/// Event store settings, fixed once the store is enabled
+#[derive(Debug, Clone)]
+pub struct EventStoreOptions {
+    /// Snapshot an aggregate every this many events (0 disables snapshots)
+    pub snapshot_interval: i64,
+}
+
+impl Default for EventStoreOptions {
+    fn default() -> Self {
+        Self {
+            snapshot_interval: 50,
+        }
+    }
+}
+
+/// A domain event as recorded, for audit views
+#[allow(dead_code)]
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredEvent {
+    pub seq: i64,
+    pub aggregate_type: String,
+    pub aggregate_id: i64,
+    pub version: i64,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub recorded_at: String,
+}
+
+fn serialization_error(message: &str, cause: serde_json::Error) -> AppError {
+    AppError::Serialization(
+        ErrorValue::new(ErrorCode::SerializationFailed, message).with_cause(cause.to_string()),
+    )
+}
+
+fn deserialization_error(aggregate_type: &str, id: i64, cause: serde_json::Error) -> AppError {
+    AppError::Serialization(
+        ErrorValue::new(ErrorCode::DeserializationFailed, "Failed to read stored domain event")
+            .with_cause(cause.to_string())
+            .with_context("aggregate", format!("{}:{}", aggregate_type, id)),
+    )
+}
+
+/// Latest snapshot at or before `max_version`, as (version, state)
+fn load_snapshot<A>(conn: &Connection, id: i64, max_version: i64) -> DbResult<Option<(i64, A)>>
+where
+    A: Aggregate + DeserializeOwned,
+{
+    let row: Option<(i64, String)> = conn
+        .query_row(
+            "SELECT version, state FROM snapshots
+             WHERE aggregate_type = ? AND aggregate_id = ? AND version <= ?",
+            params![A::TYPE, id, max_version],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+
+    match row {
+        Some((version, state)) => {
+            let state = serde_json::from_str(&state)
+                .map_err(|e| deserialization_error(A::TYPE, id, e))?;
+            Ok(Some((version, state)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Fold the events of aggregate `id` up to `max_version`, returning the
+/// state and the version reached (0 when nothing is recorded)
+fn fold<A>(conn: &Connection, id: i64, max_version: i64) -> DbResult<(A, i64)>
+where
+    A: Aggregate + DeserializeOwned,
+    A::Event: DeserializeOwned,
+{
+    let (mut version, mut state) = load_snapshot::<A>(conn, id, max_version)?.unwrap_or_default();
+
+    let mut stmt = conn.prepare(
+        "SELECT version, payload FROM domain_events
+         WHERE aggregate_type = ? AND aggregate_id = ? AND version > ? AND version <= ?
+         ORDER BY version",
+    )?;
+    let rows = stmt.query_map(params![A::TYPE, id, version, max_version], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+    })?;
+    for row in rows {
+        let (event_version, payload) = row?;
+        let event: A::Event =
+            serde_json::from_str(&payload).map_err(|e| deserialization_error(A::TYPE, id, e))?;
+        state.apply(&event);
+        version = event_version;
+    }
+    Ok((state, version))
+}
+
+impl Database {
+    /// Create the event store tables and start recording entity mutations
+    #[tracing::instrument(skip_all)]
+    pub fn init_event_store(&self, options: EventStoreOptions) -> DbResult<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS domain_events (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                aggregate_type TEXT NOT NULL,
+                aggregate_id INTEGER NOT NULL,
+                version INTEGER NOT NULL,
+                event_type TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                recorded_at TEXT NOT NULL DEFAULT (datetime('now')),
+                UNIQUE (aggregate_type, aggregate_id, version)
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS snapshots (
+                aggregate_type TEXT NOT NULL,
+                aggregate_id INTEGER NOT NULL,
+                version INTEGER NOT NULL,
+                state TEXT NOT NULL,
+                taken_at TEXT NOT NULL DEFAULT (datetime('now')),
+                PRIMARY KEY (aggregate_type, aggregate_id)
+            )",
+            [],
+        )?;
+
+        info!(
+            "Event store initialized (snapshot every {} events)",
+            options.snapshot_interval
+        );
+        let _ = self.event_store.set(options);
+        Ok(())
+    }
+
+    pub fn event_store_enabled(&self) -> bool {
+        self.event_store.get().is_some()
+    }
+
+    /// Append `event` to aggregate `id`, returning its version. Every
+    /// `snapshot_interval` events the folded state is snapshotted.
+    #[tracing::instrument(skip(self, event))]
+    pub fn append_event<A>(&self, id: i64, event: &A::Event) -> DbResult<i64>
+    where
+        A: Aggregate + Serialize + DeserializeOwned,
+        A::Event: Serialize + DeserializeOwned,
+    {
+        let payload = serde_json::to_value(event)
+            .map_err(|e| serialization_error("Failed to serialize domain event", e))?;
+        let event_type = payload
+            .get("type")
+            .and_then(|t| t.as_str())
+            .unwrap_or("event")
+            .to_string();
+        let snapshot_interval = self
+            .event_store
+            .get()
+            .map(|options| options.snapshot_interval)
+            .unwrap_or_default();
+
+        self.transaction(|conn| {
+            let version: i64 = conn.query_row(
+                "SELECT COALESCE(MAX(version), 0) + 1 FROM domain_events
+                 WHERE aggregate_type = ? AND aggregate_id = ?",
+                params![A::TYPE, id],
+                |row| row.get(0),
+            )?;
+            conn.execute(
+                "INSERT INTO domain_events (aggregate_type, aggregate_id, version, event_type, payload)
+                 VALUES (?, ?, ?, ?, ?)",
+                params![A::TYPE, id, version, event_type, payload.to_string()],
+            )?;
+
+            if snapshot_interval > 0 && version % snapshot_interval == 0 {
+                let (state, _) = fold::<A>(conn, id, version)?;
+                let state = serde_json::to_string(&state)
+                    .map_err(|e| serialization_error("Failed to serialize snapshot", e))?;
+                conn.execute(
+                    "INSERT OR REPLACE INTO snapshots (aggregate_type, aggregate_id, version, state)
+                     VALUES (?, ?, ?, ?)",
+                    params![A::TYPE, id, version, state],
+                )?;
+            }
+            Ok(version)
+        })
+    }
+
+    /// Append `event` if the event store is enabled. The entity row has
+    /// already changed by then, so a failure is logged rather than returned.
+    pub fn record_event<A>(&self, id: i64, event: A::Event)
+    where
+        A: Aggregate + Serialize + DeserializeOwned,
+        A::Event: Serialize + DeserializeOwned,
+    {
+        if !self.event_store_enabled() {
+            return;
+        }
+        if let Err(e) = self.append_event::<A>(id, &event) {
+            error!("Failed to record event for {}:{}: {}", A::TYPE, id, e);
+        }
+    }
+
+    /// Current state of aggregate `id` and its version, or `None` if no
+    /// events are recorded for it
+    #[allow(dead_code)]
+    #[tracing::instrument(skip(self))]
+    pub fn load_aggregate<A>(&self, id: i64) -> DbResult<Option<(A, i64)>>
+    where
+        A: Aggregate + DeserializeOwned,
+        A::Event: DeserializeOwned,
+    {
+        let conn = self.get_conn()?;
+        let (state, version) = fold::<A>(&conn, id, i64::MAX)?;
+        Ok((version > 0).then_some((state, version)))
+    }
+
+    /// State of aggregate `id` as of `version`, e.g. to undo later changes
+    #[allow(dead_code)]
+    #[tracing::instrument(skip(self))]
+    pub fn load_aggregate_at<A>(&self, id: i64, version: i64) -> DbResult<Option<A>>
+    where
+        A: Aggregate + DeserializeOwned,
+        A::Event: DeserializeOwned,
+    {
+        let conn = self.get_conn()?;
+        let (state, reached) = fold::<A>(&conn, id, version)?;
+        Ok((reached > 0).then_some(state))
+    }
+
+    /// Every event recorded for an aggregate, oldest first
+    #[allow(dead_code)]
+    #[tracing::instrument(skip(self))]
+    pub fn event_history(&self, aggregate_type: &str, id: i64) -> DbResult<Vec<StoredEvent>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT seq, aggregate_type, aggregate_id, version, event_type, payload, recorded_at
+             FROM domain_events
+             WHERE aggregate_type = ? AND aggregate_id = ?
+             ORDER BY version",
+        )?;
+        let rows = stmt.query_map(params![aggregate_type, id], |row| {
+            Ok(StoredEvent {
+                seq: row.get(0)?,
+                aggregate_type: row.get(1)?,
+                aggregate_id: row.get(2)?,
+                version: row.get(3)?,
+                event_type: row.get(4)?,
+                payload: serde_json::from_str(&row.get::<_, String>(5)?)
+                    .unwrap_or(serde_json::Value::Null),
+                recorded_at: row.get(6)?,
+            })
+        })?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(|e| {
+            AppError::Database(
+                ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to read event history")
+                    .with_cause(e.to_string())
+                    .with_context("aggregate", format!("{}:{}", aggregate_type, id)),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::domain::events::{UserAggregate, UserEvent};
+
+    fn create_test_db(snapshot_interval: i64) -> Database {
+        let db = Database::new(":memory:").expect("Failed to create database");
+        db.init().expect("Failed to init database");
+        db.init_event_store(EventStoreOptions { snapshot_interval })
+            .expect("Failed to init event store");
+        db
+    }
+
+    fn rename(name: &str) -> UserEvent {
+        UserEvent::Updated {
+            name: Some(name.to_string()),
+            email: None,
+            role: None,
+            status: None,
+        }
+    }
+
+    #[test]
+    fn test_user_mutations_fold_into_state() {
+        let db = create_test_db(0);
+
+        let id = db.insert_user("Ada", "ada@example.com", "User", "Active")
+            .expect("Failed to insert user");
+        db.update_user(id, None, None, Some("Admin".to_string()), None)
+            .expect("Failed to update user");
+        db.delete_user(id).expect("Failed to delete user");
+
+        let (state, version) = db.load_aggregate::<UserAggregate>(id)
+            .expect("Failed to load aggregate")
+            .expect("No events recorded");
+        assert_eq!(version, 3);
+        assert_eq!(state.role, "Admin");
+        assert!(state.deleted);
+
+        // Undo the delete by rebuilding the previous version
+        let before = db.load_aggregate_at::<UserAggregate>(id, 2)
+            .expect("Failed to load aggregate")
+            .expect("No events recorded");
+        assert!(!before.deleted);
+        assert_eq!(before.email, "ada@example.com");
+
+        let history = db.event_history("user", id).expect("Failed to read history");
+        let types: Vec<&str> = history.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(types, ["created", "updated", "deleted"]);
+
+        assert!(db.load_aggregate::<UserAggregate>(id + 1).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_snapshots_match_full_replay() {
+        let db = create_test_db(3);
+        for n in 1..=7 {
+            db.append_event::<UserAggregate>(1, &rename(&format!("name {}", n)))
+                .expect("Failed to append event");
+        }
+
+        let snapshot_version: i64 = db.get_conn().unwrap()
+            .query_row("SELECT version FROM snapshots WHERE aggregate_id = 1", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(snapshot_version, 6);
+
+        let (state, version) = db.load_aggregate::<UserAggregate>(1).unwrap().unwrap();
+        assert_eq!((state.name.as_str(), version), ("name 7", 7));
+
+        // Versions before the snapshot replay from the start
+        let state = db.load_aggregate_at::<UserAggregate>(1, 4).unwrap().unwrap();
+        assert_eq!(state.name, "name 4");
+    }
+}
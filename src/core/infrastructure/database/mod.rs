@@ -1,8 +1,20 @@
 // src/core/infrastructure/database/mod.rs
 // Database module - SQLite with connection pooling
 
+pub mod api_keys;
+pub mod audit_log;
+pub mod config_audit;
 pub mod connection;
+pub mod credentials;
+pub mod error_stats;
+pub mod event_store;
 pub mod models;
+pub mod two_factor;
 pub mod users;
+pub mod vault;
 
+pub use api_keys::ApiKeyRecord;
+pub use audit_log::{AuditEntry, AuditFilter};
+pub use config_audit::ConfigAuditEntry;
 pub use connection::Database;
+pub use event_store::EventStoreOptions;
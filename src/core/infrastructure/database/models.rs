@@ -2,6 +2,7 @@
 // src/core/infrastructure/database/models.rs
 // Database data structures and models
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Represents a database row as a dynamic JSON-like object
@@ -33,7 +34,7 @@ impl QueryResult {
 }
 
 /// User record structure
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct User {
     pub id: i64,
     pub name: String,
@@ -0,0 +1,189 @@
+// src/core/infrastructure/database/two_factor.rs
+// Per-user TOTP secrets and hashed recovery codes. A secret is pending until
+// the user confirms it with a first code; only then does login ask for one.
+
+use rusqlite::{params, OptionalExtension};
+
+use super::connection::Database;
+use crate::core::error::AppError;
+
+type DbResult<T> = Result<T, AppError>;
+
+/// A user's stored TOTP secret
+#[derive(Debug, Clone)]
+pub struct TwoFactorRecord {
+    /// Base32 shared secret
+    pub secret: String,
+    /// Confirmed with a first code; login only asks for codes when set
+    pub enabled: bool,
+    /// Last time step a code was accepted for, so it can't be used again
+    #[allow(dead_code)]
+    pub last_step: Option<i64>,
+}
+
+impl Database {
+    /// Create the user_totp and user_recovery_codes tables
+    #[tracing::instrument(skip_all)]
+    pub fn init_two_factor(&self) -> DbResult<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS user_totp (
+                user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+                secret TEXT NOT NULL,
+                enabled INTEGER NOT NULL DEFAULT 0,
+                last_step INTEGER,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS user_recovery_codes (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                code_hash TEXT NOT NULL,
+                used_at TEXT
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_recovery_codes_user ON user_recovery_codes(user_id)",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// `user_id`'s TOTP secret, pending or enabled
+    pub fn two_factor(&self, user_id: i64) -> DbResult<Option<TwoFactorRecord>> {
+        let conn = self.get_conn()?;
+        let record = conn
+            .query_row(
+                "SELECT secret, enabled, last_step FROM user_totp WHERE user_id = ?",
+                [user_id],
+                |row| {
+                    Ok(TwoFactorRecord {
+                        secret: row.get(0)?,
+                        enabled: row.get(1)?,
+                        last_step: row.get(2)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(record)
+    }
+
+    /// Store a new pending secret and recovery codes for `user_id`,
+    /// replacing any earlier ones
+    pub fn begin_two_factor(
+        &self,
+        user_id: i64,
+        secret: &str,
+        recovery_code_hashes: &[String],
+    ) -> DbResult<()> {
+        let mut conn = self.get_conn()?;
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO user_totp (user_id, secret, enabled) VALUES (?, ?, 0)",
+            params![user_id, secret],
+        )?;
+        tx.execute("DELETE FROM user_recovery_codes WHERE user_id = ?", [user_id])?;
+        {
+            let mut stmt =
+                tx.prepare("INSERT INTO user_recovery_codes (user_id, code_hash) VALUES (?, ?)")?;
+            for hash in recovery_code_hashes {
+                stmt.execute(params![user_id, hash])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Mark `user_id`'s secret confirmed
+    pub fn enable_two_factor(&self, user_id: i64) -> DbResult<()> {
+        let conn = self.get_conn()?;
+        conn.execute("UPDATE user_totp SET enabled = 1 WHERE user_id = ?", [user_id])?;
+        Ok(())
+    }
+
+    /// Remove `user_id`'s secret and recovery codes
+    pub fn disable_two_factor(&self, user_id: i64) -> DbResult<()> {
+        let mut conn = self.get_conn()?;
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM user_totp WHERE user_id = ?", [user_id])?;
+        tx.execute("DELETE FROM user_recovery_codes WHERE user_id = ?", [user_id])?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Accept a code for `step`; false when a code for this or a later step
+    /// was already accepted
+    pub fn record_totp_step(&self, user_id: i64, step: i64) -> DbResult<bool> {
+        let conn = self.get_conn()?;
+        let updated = conn.execute(
+            "UPDATE user_totp SET last_step = ?2
+             WHERE user_id = ?1 AND (last_step IS NULL OR last_step < ?2)",
+            params![user_id, step],
+        )?;
+        Ok(updated == 1)
+    }
+
+    /// Use up the unused recovery code with `code_hash`; false when there
+    /// is none
+    pub fn use_recovery_code(&self, user_id: i64, code_hash: &str) -> DbResult<bool> {
+        let conn = self.get_conn()?;
+        let updated = conn.execute(
+            "UPDATE user_recovery_codes SET used_at = datetime('now')
+             WHERE id = (
+                 SELECT id FROM user_recovery_codes
+                 WHERE user_id = ? AND code_hash = ? AND used_at IS NULL
+                 LIMIT 1
+             )",
+            params![user_id, code_hash],
+        )?;
+        Ok(updated == 1)
+    }
+
+    /// Recovery codes `user_id` has left
+    pub fn recovery_codes_left(&self, user_id: i64) -> DbResult<i64> {
+        let conn = self.get_conn()?;
+        let count = conn.query_row(
+            "SELECT COUNT(*) FROM user_recovery_codes WHERE user_id = ? AND used_at IS NULL",
+            [user_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_factor_lifecycle() {
+        let db = Database::new(":memory:").expect("Failed to create database");
+        db.init().expect("Failed to init schema");
+        db.init_two_factor().expect("Failed to init two factor");
+        let user = db
+            .insert_user("Ada", "ada@example.com", "Admin", "Active")
+            .expect("Failed to insert user");
+
+        assert!(db.two_factor(user).unwrap().is_none());
+        let hashes = ["h1".to_string(), "h2".to_string()];
+        db.begin_two_factor(user, "SECRET", &hashes).unwrap();
+        assert!(!db.two_factor(user).unwrap().unwrap().enabled);
+        db.enable_two_factor(user).unwrap();
+        assert!(db.two_factor(user).unwrap().unwrap().enabled);
+
+        assert!(db.record_totp_step(user, 10).unwrap());
+        assert!(!db.record_totp_step(user, 10).unwrap());
+        assert!(db.record_totp_step(user, 11).unwrap());
+
+        assert!(db.use_recovery_code(user, "h1").unwrap());
+        assert!(!db.use_recovery_code(user, "h1").unwrap());
+        assert_eq!(db.recovery_codes_left(user).unwrap(), 1);
+
+        db.disable_two_factor(user).unwrap();
+        assert!(db.two_factor(user).unwrap().is_none());
+        assert_eq!(db.recovery_codes_left(user).unwrap(), 0);
+    }
+}
@@ -6,25 +6,22 @@ use rusqlite::{params, OptionalExtension};
 
 use super::connection::Database;
 use super::models::User;
-use crate::core::error::{ErrorCode, ErrorValue, AppError};
+use crate::core::domain::events::{UserAggregate, UserEvent};
+use crate::core::error::{ErrorCode, ErrorExt, ErrorValue, AppError};
 
 /// Database operation result type alias
 type DbResult<T> = Result<T, AppError>;
 
 impl Database {
     /// Get all users
+    #[tracing::instrument(skip_all)]
     pub fn get_all_users(&self) -> DbResult<Vec<User>> {
         let conn = self.get_conn()?;
 
         let mut stmt = conn
             .prepare("SELECT id, name, email, role, status, created_at FROM users ORDER BY id")
-            .map_err(|e| {
-                AppError::Database(
-                    ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to prepare users query")
-                        .with_cause(e.to_string())
-                        .with_context("table", "users")
-                )
-            })?;
+            .map_db_err("prepare users query")
+            .with_context("table", "users")?;
 
         let users = stmt.query_map([], |row| {
             Ok(User {
@@ -35,22 +32,13 @@ impl Database {
                 status: row.get(4)?,
                 created_at: row.get(5)?,
             })
-        }).map_err(|e| {
-            AppError::Database(
-                ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to query users")
-                    .with_cause(e.to_string())
-            )
-        })?;
+        }).map_db_err("query users")?;
 
-        users.collect::<rusqlite::Result<Vec<_>>>().map_err(|e| {
-            AppError::Database(
-                ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to collect users")
-                    .with_cause(e.to_string())
-            )
-        })
+        users.collect::<rusqlite::Result<Vec<_>>>().map_db_err("collect users")
     }
 
     /// Insert a new user
+    #[tracing::instrument(skip_all)]
     pub fn insert_user(
         &self,
         name: &str,
@@ -95,10 +83,21 @@ impl Database {
             }
         })?;
 
-        Ok(conn.last_insert_rowid())
+        let id = conn.last_insert_rowid();
+        self.record_event::<UserAggregate>(
+            id,
+            UserEvent::Created {
+                name: name.to_string(),
+                email: email.to_string(),
+                role: role.to_string(),
+                status: status.to_string(),
+            },
+        );
+        Ok(id)
     }
 
     /// Update an existing user
+    #[tracing::instrument(skip(self, name, email, role, status))]
     pub fn update_user(
         &self,
         id: i64,
@@ -141,36 +140,36 @@ impl Database {
             updates.join(", ")
         );
 
-        let rows_affected = conn.execute(&query, params.as_slice()).map_err(|e| {
-            AppError::Database(
-                ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to update user")
-                    .with_cause(e.to_string())
-                    .with_context("user_id", id.to_string())
-            )
-        })?;
+        let rows_affected = conn
+            .execute(&query, params.as_slice())
+            .map_db_err("update user")
+            .with_context("user_id", id.to_string())?;
 
+        if rows_affected > 0 {
+            self.record_event::<UserAggregate>(id, UserEvent::Updated { name, email, role, status });
+        }
         Ok(rows_affected)
     }
 
     /// Delete a user by ID
+    #[tracing::instrument(skip(self))]
     pub fn delete_user(&self, id: i64) -> DbResult<usize> {
         let conn = self.get_conn()?;
 
         let rows_affected = conn
             .execute("DELETE FROM users WHERE id = ?", [id])
-            .map_err(|e| {
-                AppError::Database(
-                    ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to delete user")
-                        .with_cause(e.to_string())
-                        .with_context("user_id", id.to_string())
-                )
-            })?;
+            .map_db_err("delete user")
+            .with_context("user_id", id.to_string())?;
 
+        if rows_affected > 0 {
+            self.record_event::<UserAggregate>(id, UserEvent::Deleted);
+        }
         Ok(rows_affected)
     }
 
     /// Get user by ID
     #[allow(dead_code)]
+    #[tracing::instrument(skip(self))]
     pub fn get_user_by_id(&self, id: i64) -> DbResult<Option<User>> {
         let conn = self.get_conn()?;
 
@@ -178,12 +177,7 @@ impl Database {
             .prepare(
                 "SELECT id, name, email, role, status, created_at FROM users WHERE id = ?",
             )
-            .map_err(|e| {
-                AppError::Database(
-                    ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to prepare user query")
-                        .with_cause(e.to_string())
-                )
-            })?;
+            .map_db_err("prepare user query")?;
 
         let user = stmt
             .query_row([id], |row| {
@@ -202,6 +196,7 @@ impl Database {
     }
 
     /// Get user by email
+    #[tracing::instrument(skip_all)]
     pub fn get_user_by_email(&self, email: &str) -> DbResult<Option<User>> {
         let conn = self.get_conn()?;
 
@@ -209,12 +204,7 @@ impl Database {
             .prepare(
                 "SELECT id, name, email, role, status, created_at FROM users WHERE email = ?",
             )
-            .map_err(|e| {
-                AppError::Database(
-                    ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to prepare user query")
-                        .with_cause(e.to_string())
-                )
-            })?;
+            .map_db_err("prepare user query")?;
 
         let user = stmt
             .query_row([email], |row| {
@@ -233,6 +223,7 @@ impl Database {
     }
 
     /// Insert sample data if not exists
+    #[tracing::instrument(skip_all)]
     pub fn insert_sample_data(&self) -> DbResult<()> {
         let sample_users = [
             ("Alice Johnson", "alice@example.com", "Admin", "Active"),
@@ -252,23 +243,20 @@ impl Database {
 
     /// Get user count
     #[allow(dead_code)]
+    #[tracing::instrument(skip_all)]
     pub fn get_user_count(&self) -> DbResult<i64> {
         let conn = self.get_conn()?;
 
         let count: i64 = conn
             .query_row("SELECT COUNT(*) FROM users", [], |row| row.get(0))
-            .map_err(|e| {
-                AppError::Database(
-                    ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to count users")
-                        .with_cause(e.to_string())
-                )
-            })?;
+            .map_db_err("count users")?;
 
         Ok(count)
     }
 
     /// Search users by name or email
     #[allow(dead_code)]
+    #[tracing::instrument(skip_all)]
     pub fn search_users(&self, query: &str) -> DbResult<Vec<User>> {
         let conn = self.get_conn()?;
 
@@ -281,12 +269,7 @@ impl Database {
                  WHERE name LIKE ? OR email LIKE ? 
                  ORDER BY id",
             )
-            .map_err(|e| {
-                AppError::Database(
-                    ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to prepare search query")
-                        .with_cause(e.to_string())
-                )
-            })?;
+            .map_db_err("prepare search query")?;
 
         let users = stmt.query_map(params![search_pattern, search_pattern], |row| {
             Ok(User {
@@ -299,12 +282,7 @@ impl Database {
             })
         })?;
 
-        users.collect::<rusqlite::Result<Vec<_>>>().map_err(|e| {
-            AppError::Database(
-                ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to search users")
-                    .with_cause(e.to_string())
-            )
-        })
+        users.collect::<rusqlite::Result<Vec<_>>>().map_db_err("search users")
     }
 }
 
@@ -0,0 +1,88 @@
+// src/core/infrastructure/database/vault.rs
+// Storage behind the frontend vault: one encrypted value per session and key.
+// Rows only ever hold ciphertext; `infrastructure::vault` seals and opens it.
+
+use rusqlite::{params, OptionalExtension};
+
+use super::connection::Database;
+use crate::core::error::AppError;
+
+type DbResult<T> = Result<T, AppError>;
+
+impl Database {
+    /// Create the vault table
+    #[tracing::instrument(skip_all)]
+    pub fn init_vault(&self) -> DbResult<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS vault (
+                session_id TEXT NOT NULL,
+                user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                key TEXT NOT NULL,
+                value BLOB NOT NULL,
+                updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+                PRIMARY KEY (session_id, key)
+            )",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// The sealed value stored under `key` in `session_id`
+    pub fn vault_value(&self, session_id: &str, key: &str) -> DbResult<Option<Vec<u8>>> {
+        let conn = self.get_conn()?;
+        let value = conn
+            .query_row(
+                "SELECT value FROM vault WHERE session_id = ? AND key = ?",
+                params![session_id, key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(value)
+    }
+
+    /// Store a sealed value under `key` in `user_id`'s session `session_id`,
+    /// replacing any old one
+    pub fn set_vault_value(
+        &self,
+        session_id: &str,
+        user_id: i64,
+        key: &str,
+        value: &[u8],
+    ) -> DbResult<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "INSERT INTO vault (session_id, user_id, key, value) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(session_id, key) DO UPDATE SET
+                 value = excluded.value,
+                 updated_at = datetime('now')",
+            params![session_id, user_id, key, value],
+        )?;
+        Ok(())
+    }
+
+    /// Remove the value under `key` in `session_id`; false when there was none
+    pub fn delete_vault_value(&self, session_id: &str, key: &str) -> DbResult<bool> {
+        let conn = self.get_conn()?;
+        let rows = conn.execute(
+            "DELETE FROM vault WHERE session_id = ? AND key = ?",
+            params![session_id, key],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Remove every value of `session_id`, once it has ended
+    pub fn clear_vault(&self, session_id: &str) -> DbResult<usize> {
+        let conn = self.get_conn()?;
+        let rows = conn.execute("DELETE FROM vault WHERE session_id = ?", params![session_id])?;
+        Ok(rows)
+    }
+
+    /// Remove every value of every session, e.g. at startup, when no
+    /// session of the last run is live any more
+    pub fn clear_all_vaults(&self) -> DbResult<usize> {
+        let conn = self.get_conn()?;
+        let rows = conn.execute("DELETE FROM vault", [])?;
+        Ok(rows)
+    }
+}
@@ -1,37 +1,471 @@
 #![allow(dead_code)]
 use std::any::{Any, TypeId};
+use std::cell::RefCell;
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::thread::{self, ThreadId};
 
-use crate::core::error::{AppError, AppResult, ErrorValue, ErrorCode, ToAppResult};
+use log::{error, info};
+use schemars::JsonSchema;
+use serde::Serialize;
 
+use crate::core::error::{AppError, AppResult, ErrorValue, ErrorCode};
+
+type Instance = Arc<dyn Any + Send + Sync>;
+type Factory = Arc<dyn Fn(&Container) -> AppResult<Instance> + Send + Sync>;
+type ScopedFactory = Arc<dyn Fn(&Scope) -> AppResult<Instance> + Send + Sync>;
+type ShutdownHook = Box<dyn Fn(&Container) -> AppResult<()> + Send + Sync>;
+
+/// A service that releases resources (flushes buffers, closes connections,
+/// stops threads) when the application exits; see [`Container::shutdown_all`]
+pub trait Shutdown: Send + Sync {
+    fn shutdown(&self) -> AppResult<()>;
+}
+
+impl<T: Shutdown + ?Sized> Shutdown for Arc<T> {
+    fn shutdown(&self) -> AppResult<()> {
+        (**self).shutdown()
+    }
+}
+
+/// A type built by resolving its fields from the container; usually
+/// implemented with `#[derive(Injectable)]` from `rustwebui_macros`
+pub trait Injectable: Sized {
+    fn inject(container: &Container) -> AppResult<Self>;
+}
+
+/// How long a resolved service instance lives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lifetime {
+    /// One instance shared by every caller
+    Singleton,
+    /// A new instance on every resolve
+    Transient,
+    /// One instance per [`Scope`], dropped with it
+    Scoped,
+}
+
+impl std::fmt::Display for Lifetime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Lifetime::Singleton => write!(f, "singleton"),
+            Lifetime::Transient => write!(f, "transient"),
+            Lifetime::Scoped => write!(f, "scoped"),
+        }
+    }
+}
+
+/// A singleton built by its factory on first resolve
+/// Threads waiting for a lazy service, each with the thread building it. A
+/// wait that would close a loop is a circular dependency across threads,
+/// which would otherwise never end.
+static WAITING_ON: Mutex<Vec<(ThreadId, ThreadId)>> = Mutex::new(Vec::new());
+
+enum LazyState {
+    Empty,
+    /// The factory is running on this thread, without the lock held
+    Building(ThreadId),
+    Built(Instance),
+}
+
+struct LazyService {
+    /// Short type name, for errors
+    type_name: String,
+    factory: Factory,
+    state: Mutex<LazyState>,
+    built: Condvar,
+}
+
+/// Marks a lazy service as no longer being built if its factory fails or
+/// panics, waking the threads waiting for it so one of them can retry
+struct PendingBuild<'a> {
+    lazy: &'a LazyService,
+    done: bool,
+}
+
+impl Drop for PendingBuild<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            *self.lazy.state.lock().unwrap_or_else(|e| e.into_inner()) = LazyState::Empty;
+            self.lazy.built.notify_all();
+        }
+    }
+}
+
+impl LazyService {
+    fn lock(&self) -> AppResult<MutexGuard<'_, LazyState>> {
+        self.state.lock().map_err(|e| {
+            AppError::LockPoisoned(
+                ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire lazy service lock")
+                    .with_cause(e.to_string())
+            )
+        })
+    }
+
+    fn get(&self, container: &Container) -> AppResult<Instance> {
+        let current = thread::current().id();
+        let mut state = self.lock()?;
+        loop {
+            match &*state {
+                LazyState::Built(instance) => return Ok(instance.clone()),
+                LazyState::Empty => break,
+                LazyState::Building(builder) => {
+                    let builder = *builder;
+                    self.start_waiting(current, builder)?;
+                    let woken = self.built.wait(state);
+                    stop_waiting(current);
+                    state = woken.map_err(|e| {
+                        AppError::LockPoisoned(
+                            ErrorValue::new(
+                                ErrorCode::LockPoisoned,
+                                "Failed to acquire lazy service lock",
+                            )
+                            .with_cause(e.to_string()),
+                        )
+                    })?;
+                }
+            }
+        }
+        *state = LazyState::Building(current);
+        drop(state);
+
+        // A failed build is retried on the next resolve
+        let mut pending = PendingBuild { lazy: self, done: false };
+        let built = (self.factory)(container)?;
+        *self.lock()? = LazyState::Built(built.clone());
+        pending.done = true;
+        self.built.notify_all();
+        Ok(built)
+    }
+
+    /// Record that `current` waits for `builder` to finish this service,
+    /// unless `builder` is, through other waits, waiting for `current`
+    fn start_waiting(&self, current: ThreadId, builder: ThreadId) -> AppResult<()> {
+        let mut waiting = WAITING_ON.lock().unwrap_or_else(|e| e.into_inner());
+        let mut next = Some(builder);
+        while let Some(thread) = next {
+            if thread == current {
+                return Err(AppError::DependencyInjection(
+                    ErrorValue::new(
+                        ErrorCode::InternalError,
+                        format!(
+                            "Circular dependency: {} is being built by a thread that waits for this one",
+                            self.type_name
+                        ),
+                    )
+                    .with_context("service", self.type_name.clone()),
+                ));
+            }
+            next = waiting
+                .iter()
+                .find(|(waiter, _)| *waiter == thread)
+                .map(|(_, builder)| *builder);
+        }
+        waiting.push((current, builder));
+        Ok(())
+    }
+
+    fn is_created(&self) -> bool {
+        self.state
+            .lock()
+            .map(|state| matches!(*state, LazyState::Built(_)))
+            .unwrap_or(false)
+    }
+
+    fn instance(&self) -> Option<Instance> {
+        match &*self.state.lock().ok()? {
+            LazyState::Built(instance) => Some(instance.clone()),
+            _ => None,
+        }
+    }
+}
+
+fn stop_waiting(current: ThreadId) {
+    let mut waiting = WAITING_ON.lock().unwrap_or_else(|e| e.into_inner());
+    waiting.retain(|(waiter, _)| *waiter != current);
+}
+
+enum Registration {
+    Singleton(Instance),
+    Lazy(Arc<LazyService>),
+    Transient(Factory),
+    Scoped(ScopedFactory),
+}
+
+impl Registration {
+    fn lazy<T, F>(factory: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&Container) -> AppResult<T> + Send + Sync + 'static,
+    {
+        let factory: Factory = Arc::new(move |container| {
+            factory(container).map(|service| Arc::new(service) as Instance)
+        });
+        Registration::Lazy(Arc::new(LazyService {
+            type_name: short_type_name(std::any::type_name::<T>()),
+            factory,
+            state: Mutex::new(LazyState::Empty),
+            built: Condvar::new(),
+        }))
+    }
+
+    fn lifetime(&self) -> Lifetime {
+        match self {
+            Registration::Singleton(_) | Registration::Lazy(_) => Lifetime::Singleton,
+            Registration::Transient(_) => Lifetime::Transient,
+            Registration::Scoped(_) => Lifetime::Scoped,
+        }
+    }
+}
+
+/// A registration plus what the diagnostics report about it
+struct ServiceEntry {
+    type_name: &'static str,
+    registration: Registration,
+    /// Lookups by resolve, including failed and scope-cached ones
+    resolves: AtomicU64,
+}
+
+impl ServiceEntry {
+    fn new<T: ?Sized + 'static>(registration: Registration) -> Self {
+        Self {
+            type_name: std::any::type_name::<T>(),
+            registration,
+            resolves: AtomicU64::new(0),
+        }
+    }
+
+    fn is_created(&self) -> bool {
+        match &self.registration {
+            Registration::Singleton(_) => true,
+            Registration::Lazy(lazy) => lazy.is_created(),
+            Registration::Transient(_) | Registration::Scoped(_) => false,
+        }
+    }
+}
+
+/// A registration as reported by the `di_services` diagnostics handler
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct ServiceInfo {
+    /// Short type name, e.g. `Arc<Database>`
+    pub type_name: String,
+    /// Set for named registrations
+    pub name: Option<String>,
+    /// "singleton", "transient" or "scoped"
+    pub lifetime: String,
+    /// Whether a singleton instance exists; transient and scoped services
+    /// are built per resolve or scope and are never reported as created
+    pub created: bool,
+    pub resolve_count: u64,
+}
+
+/// What resolving a type needs, taken out of the lock so factories can
+/// resolve their own dependencies
+enum Resolution {
+    Instance(Instance),
+    Lazy(Arc<LazyService>),
+    Transient(Factory),
+    Scoped(ScopedFactory),
+}
+
+/// A registration slot: the service type plus an optional name, so several
+/// instances of one type (e.g. an "app" and a "cache" `Database`) can coexist
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ServiceKey {
+    type_id: TypeId,
+    name: Option<String>,
+}
+
+impl ServiceKey {
+    fn of<T: ?Sized + 'static>(name: Option<&str>) -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            name: name.map(str::to_string),
+        }
+    }
+
+    /// `Database` or `Database("cache")`, for error messages
+    fn describe<T: ?Sized + 'static>(name: Option<&str>) -> String {
+        let type_name = short_type_name(std::any::type_name::<T>());
+        match name {
+            Some(name) => format!("{}(\"{}\")", type_name, name),
+            None => type_name,
+        }
+    }
+}
+
+thread_local! {
+    /// Services whose factories are running on this thread, outermost first
+    static RESOLVING: RefCell<Vec<(ServiceKey, String)>> = const { RefCell::new(Vec::new()) };
+
+    /// Scopes of the `scoped` calls running on this thread, innermost last
+    static CURRENT_SCOPES: RefCell<Vec<Rc<Scope<'static>>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// `Arc<dyn my_app::repo::UserRepository>` -> `Arc<dyn UserRepository>`
+fn short_type_name(name: &str) -> String {
+    let mut short = String::new();
+    let mut path = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            path.push(c);
+        } else {
+            short.push_str(path.rsplit("::").next().unwrap_or_default());
+            path.clear();
+            short.push(c);
+        }
+    }
+    short.push_str(path.rsplit("::").next().unwrap_or_default());
+    short
+}
+
+/// Marks a service as being built on this thread until dropped
+struct Building;
+
+impl Building {
+    /// Fails with the dependency chain if `T` (under `name`) is already being
+    /// built, which would otherwise recurse forever or deadlock on a lazy
+    /// service
+    fn enter<T: ?Sized + 'static>(name: Option<&str>) -> AppResult<Self> {
+        RESOLVING.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            let key = ServiceKey::of::<T>(name);
+            let described = ServiceKey::describe::<T>(name);
+            if let Some(start) = stack.iter().position(|(building, _)| *building == key) {
+                let chain = stack[start..]
+                    .iter()
+                    .map(|(_, described)| described.as_str())
+                    .chain(std::iter::once(described.as_str()))
+                    .collect::<Vec<_>>()
+                    .join(" → ");
+                return Err(AppError::DependencyInjection(
+                    ErrorValue::new(
+                        ErrorCode::InternalError,
+                        format!("Circular dependency: {}", chain),
+                    )
+                    .with_context("chain", chain),
+                ));
+            }
+            stack.push((key, described));
+            Ok(Building)
+        })
+    }
+}
+
+impl Drop for Building {
+    fn drop(&mut self) {
+        RESOLVING.with(|stack| {
+            stack.borrow_mut().pop();
+        });
+    }
+}
+
+fn not_found<T: ?Sized + 'static>() -> AppError {
+    AppError::NotFound(ErrorValue::new(
+        ErrorCode::ResourceNotFound,
+        format!("Service {} not found in container", std::any::type_name::<T>()),
+    ))
+}
+
+// Errors are built lazily: resolving is on every handler's hot path
+fn downcast<T: Send + Sync + 'static>(instance: Instance) -> AppResult<Arc<T>> {
+    instance.downcast::<T>().map_err(|_| not_found::<T>())
+}
+
+type ServiceMap = HashMap<ServiceKey, ServiceEntry>;
+
+fn lock_error(operation: &str, cause: impl std::fmt::Display) -> AppError {
+    AppError::LockPoisoned(
+        ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire DI container lock")
+            .with_cause(cause.to_string())
+            .with_context("operation", operation)
+    )
+}
+
+/// Resolves take a read lock, so concurrent handlers resolving services
+/// don't contend with each other; only registration takes the write lock
 pub struct Container {
-    services: Mutex<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
+    services: Arc<RwLock<ServiceMap>>,
+    /// Teardown of disposable services, in registration order
+    shutdown_hooks: Arc<Mutex<Vec<(String, ShutdownHook)>>>,
+    /// Set for containers made with [`Container::create_child`]
+    parent: Option<Arc<Container>>,
 }
 
 impl Container {
     pub fn new() -> Self {
         Self {
-            services: Mutex::new(HashMap::new()),
+            services: Arc::default(),
+            shutdown_hooks: Arc::default(),
+            parent: None,
         }
     }
 
-    pub fn register<T: 'static + Send + Sync>(&self, instance: T) -> AppResult<()> {
-        let type_id = TypeId::of::<T>();
-        let mut services = self
-            .services
-            .lock()
-            .map_err(|e| {
-                AppError::LockPoisoned(
-                    ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire DI container lock")
-                        .with_cause(e.to_string())
-                        .with_context("operation", "register")
-                )
-            })?;
-        services.insert(type_id, Arc::new(instance));
+    /// A container whose registrations shadow this one's, e.g. to swap in an
+    /// in-memory `Database` for one test or plugin without touching the
+    /// global container. Anything the child doesn't register resolves from
+    /// the parent: the parent's singletons are shared, while its transient
+    /// factories run against the child so they see the overrides.
+    pub fn create_child(&self) -> Container {
+        let parent = Container {
+            services: self.services.clone(),
+            shutdown_hooks: self.shutdown_hooks.clone(),
+            parent: self.parent.clone(),
+        };
+        Container {
+            services: Arc::default(),
+            shutdown_hooks: Arc::default(),
+            parent: Some(Arc::new(parent)),
+        }
+    }
+
+    /// This container, then its parents
+    fn chain(&self) -> impl Iterator<Item = &Container> {
+        std::iter::successors(Some(self), |container| container.parent.as_deref())
+    }
+
+    /// Apply `f` to the entry for `key` in the nearest container that
+    /// registers it, so a child's registration shadows its parent's
+    fn find<'a, R>(
+        &'a self,
+        key: &ServiceKey,
+        operation: &str,
+        f: impl Fn(&'a Container, &ServiceEntry) -> R,
+    ) -> AppResult<Option<R>> {
+        for container in self.chain() {
+            let services = container.services(operation)?;
+            if let Some(entry) = services.get(key) {
+                return Ok(Some(f(container, entry)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn services(&self, operation: &str) -> AppResult<RwLockReadGuard<'_, ServiceMap>> {
+        self.services.read().map_err(|e| lock_error(operation, e))
+    }
+
+    fn services_mut(&self, operation: &str) -> AppResult<RwLockWriteGuard<'_, ServiceMap>> {
+        self.services.write().map_err(|e| lock_error(operation, e))
+    }
+
+    fn insert<T: ?Sized + 'static>(
+        &self,
+        name: Option<&str>,
+        registration: Registration,
+        operation: &str,
+    ) -> AppResult<()> {
+        let mut services = self.services_mut(operation)?;
+        services.insert(ServiceKey::of::<T>(name), ServiceEntry::new::<T>(registration));
         Ok(())
     }
 
+    pub fn register<T: 'static + Send + Sync>(&self, instance: T) -> AppResult<()> {
+        self.insert::<T>(None, Registration::Singleton(Arc::new(instance)), "register")
+    }
+
     pub fn register_singleton<T>(&self, service: T) -> AppResult<()>
     where
         T: Send + Sync + 'static,
@@ -39,63 +473,436 @@ impl Container {
         self.register(service)
     }
 
-    pub fn resolve<T: 'static + Clone>(&self) -> AppResult<T> {
+    /// Register a service under a trait (`dyn Trait`) rather than its
+    /// concrete type, so callers can depend on the abstraction and tests can
+    /// swap in a mock. Stored as `Arc<dyn Trait>`, which the factory
+    /// registrations accept too.
+    pub fn register_trait<T>(&self, service: Arc<T>) -> AppResult<()>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.register::<Arc<T>>(service)
+    }
+
+    /// Resolve a service registered under a trait
+    pub fn resolve_trait<T>(&self) -> AppResult<Arc<T>>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.resolve::<Arc<T>>()
+    }
+
+    /// Resolve a shared `T` whether it was registered as `T` or as `Arc<T>`
+    /// (e.g. `register_singleton(Arc::clone(&db))`)
+    pub fn resolve_shared<T: 'static + Send + Sync>(&self) -> AppResult<Arc<T>> {
+        if self.has::<Arc<T>>()? {
+            return self.resolve::<Arc<T>>();
+        }
+        self.resolve_arc::<T>()
+    }
+
+    /// Register `T` as a lazy singleton built by [`Injectable::inject`]
+    pub fn register_injectable<T: Injectable + Send + Sync + 'static>(&self) -> AppResult<()> {
+        self.register_factory(T::inject)
+    }
+
+    /// Register a singleton that is only built, by `factory`, when first
+    /// resolved; for services that are expensive to create and may go unused
+    pub fn register_factory<T, F>(&self, factory: F) -> AppResult<()>
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&Container) -> AppResult<T> + Send + Sync + 'static,
+    {
+        self.insert::<T>(None, Registration::lazy(factory), "register_factory")
+    }
+
+    /// Register `instance` under `name`, alongside any other instances of
+    /// `T`; e.g. an "app" and a "cache" `Database`
+    pub fn register_named<T>(&self, name: &str, instance: T) -> AppResult<()>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.insert::<T>(Some(name), Registration::Singleton(Arc::new(instance)), "register_named")
+    }
+
+    /// Register a lazily built singleton under `name`
+    pub fn register_named_factory<T, F>(&self, name: &str, factory: F) -> AppResult<()>
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&Container) -> AppResult<T> + Send + Sync + 'static,
+    {
+        self.insert::<T>(Some(name), Registration::lazy(factory), "register_named_factory")
+    }
+
+    /// Resolve the `T` registered under `name`
+    pub fn get_named<T: 'static + Send + Sync>(&self, name: &str) -> AppResult<Arc<T>> {
+        self.resolve_key::<T>(Some(name), "get_named")
+    }
+
+    pub fn has_named<T: 'static>(&self, name: &str) -> AppResult<bool> {
+        let found = self.find(&ServiceKey::of::<T>(Some(name)), "has_named", |_, _| ())?;
+        Ok(found.is_some())
+    }
+
+    /// Names `T` is registered under, sorted
+    pub fn names_of<T: 'static>(&self) -> AppResult<Vec<String>> {
         let type_id = TypeId::of::<T>();
-        let services = self
-            .services
-            .lock()
-            .map_err(|e| {
-                AppError::LockPoisoned(
-                    ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire DI container lock")
+        let mut names = Vec::new();
+        for container in self.chain() {
+            let services = container.services("names_of")?;
+            names.extend(
+                services
+                    .keys()
+                    .filter(|key| key.type_id == type_id)
+                    .filter_map(|key| key.name.clone()),
+            );
+        }
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    /// Resolve `T`, or build it with `create` and register it as a
+    /// singleton if it isn't registered yet
+    pub fn get_or_create<T, F>(&self, create: F) -> AppResult<Arc<T>>
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce() -> AppResult<T>,
+    {
+        if self.has::<T>()? {
+            return self.resolve_arc::<T>();
+        }
+        let created: Instance = Arc::new(create()?);
+        let mut services = self.services_mut("get_or_create")?;
+        // Another caller may have registered it while `create` ran
+        let registration = services
+            .entry(ServiceKey::of::<T>(None))
+            .or_insert(ServiceEntry::new::<T>(Registration::Singleton(created)));
+        if let Registration::Singleton(instance) = &registration.registration {
+            let instance = instance.clone();
+            drop(services);
+            return downcast(instance);
+        }
+        drop(services);
+        self.resolve_arc::<T>()
+    }
+
+    /// Whether `T` is registered and has an instance; false for lazy
+    /// services not resolved yet and for transient or scoped services
+    pub fn is_created<T: 'static>(&self) -> AppResult<bool> {
+        let created = self.find(&ServiceKey::of::<T>(None), "is_created", |_, entry| {
+            entry.is_created()
+        })?;
+        Ok(created.unwrap_or(false))
+    }
+
+    /// Register a singleton that [`Container::shutdown_all`] shuts down
+    pub fn register_disposable<T: Shutdown + 'static>(&self, instance: T) -> AppResult<()> {
+        self.register(instance)?;
+        self.on_shutdown::<T>()
+    }
+
+    /// Shut down the singleton or lazy `T` in [`Container::shutdown_all`];
+    /// a lazy service that was never built is skipped
+    pub fn on_shutdown<T: Shutdown + 'static>(&self) -> AppResult<()> {
+        let hook: ShutdownHook = Box::new(|container| {
+            match container.created_instance(&ServiceKey::of::<T>(None), "shutdown_all")? {
+                Some(instance) => downcast::<T>(instance)?.shutdown(),
+                None => Ok(()),
+            }
+        });
+        let mut hooks = self.shutdown_hooks.lock().map_err(|e| {
+            AppError::LockPoisoned(
+                ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire DI shutdown lock")
+                    .with_cause(e.to_string())
+                    .with_context("operation", "on_shutdown")
+            )
+        })?;
+        hooks.push((short_type_name(std::any::type_name::<T>()), hook));
+        Ok(())
+    }
+
+    /// Shut down disposable services in reverse registration order, so a
+    /// service is shut down before the ones registered ahead of it that it
+    /// may depend on. Every service is attempted; the first failure is
+    /// returned. Hooks run once, so calling this again is a no-op.
+    pub fn shutdown_all(&self) -> AppResult<()> {
+        let hooks = match self.shutdown_hooks.lock() {
+            Ok(mut hooks) => std::mem::take(&mut *hooks),
+            Err(e) => {
+                return Err(AppError::LockPoisoned(
+                    ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire DI shutdown lock")
                         .with_cause(e.to_string())
-                        .with_context("operation", "resolve")
+                        .with_context("operation", "shutdown_all"),
+                ))
+            }
+        };
+
+        let mut first_error = None;
+        for (name, hook) in hooks.into_iter().rev() {
+            match hook(self) {
+                Ok(()) => info!("Shut down {}", name),
+                Err(e) => {
+                    error!("Failed to shut down {}: {}", name, e);
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// The instance registered under `key` in this container (not its
+    /// parents), if one has been built
+    fn created_instance(&self, key: &ServiceKey, operation: &str) -> AppResult<Option<Instance>> {
+        let services = self.services(operation)?;
+        Ok(match services.get(key).map(|entry| &entry.registration) {
+            Some(Registration::Singleton(instance)) => Some(instance.clone()),
+            Some(Registration::Lazy(lazy)) => lazy.instance(),
+            _ => None,
+        })
+    }
+
+    /// Register a factory that builds a new `T` on every resolve
+    pub fn register_transient<T, F>(&self, factory: F) -> AppResult<()>
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&Container) -> AppResult<T> + Send + Sync + 'static,
+    {
+        let factory: Factory = Arc::new(move |container| {
+            factory(container).map(|service| Arc::new(service) as Instance)
+        });
+        self.insert::<T>(None, Registration::Transient(factory), "register_transient")
+    }
+
+    /// Register a factory that builds one `T` per [`Scope`]; scoped services
+    /// can only be resolved from a scope
+    pub fn register_scoped<T, F>(&self, factory: F) -> AppResult<()>
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&Scope) -> AppResult<T> + Send + Sync + 'static,
+    {
+        let factory: ScopedFactory = Arc::new(move |scope| {
+            factory(scope).map(|service| Arc::new(service) as Instance)
+        });
+        self.insert::<T>(None, Registration::Scoped(factory), "register_scoped")
+    }
+
+    /// How to resolve `T` and the container that registers it
+    fn resolution<T: 'static>(
+        &self,
+        name: Option<&str>,
+        operation: &str,
+    ) -> AppResult<(Resolution, &Container)> {
+        let resolution = self.find(&ServiceKey::of::<T>(name), operation, |owner, entry| {
+            entry.resolves.fetch_add(1, Ordering::Relaxed);
+            let resolution = match &entry.registration {
+                Registration::Singleton(instance) => Resolution::Instance(instance.clone()),
+                Registration::Lazy(lazy) => Resolution::Lazy(lazy.clone()),
+                Registration::Transient(factory) => Resolution::Transient(factory.clone()),
+                Registration::Scoped(factory) => Resolution::Scoped(factory.clone()),
+            };
+            (resolution, owner)
+        })?;
+        match name {
+            None => resolution.ok_or_else(not_found::<T>),
+            Some(name) => resolution.ok_or_else(|| {
+                AppError::NotFound(
+                    ErrorValue::new(
+                        ErrorCode::ResourceNotFound,
+                        format!(
+                            "Service {} named '{}' not found in container",
+                            std::any::type_name::<T>(),
+                            name
+                        ),
+                    )
+                    .with_context("name", name)
+                    .with_context("operation", operation),
                 )
-            })?;
+            }),
+        }
+    }
 
-        services
-            .get(&type_id)
-            .and_then(|service| service.downcast_ref::<T>().cloned())
-            .to_app_error(&format!(
-                "Service {} not found in container",
-                std::any::type_name::<T>()
-            ))
+    pub fn resolve<T: 'static + Clone + Send + Sync>(&self) -> AppResult<T> {
+        self.resolve_arc::<T>().map(|service| (*service).clone())
     }
 
     pub fn resolve_arc<T: 'static + Send + Sync>(&self) -> AppResult<Arc<T>> {
-        let type_id = TypeId::of::<T>();
-        let services = self
-            .services
-            .lock()
-            .map_err(|e| {
-                AppError::LockPoisoned(
-                    ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire DI container lock")
-                        .with_cause(e.to_string())
-                        .with_context("operation", "resolve_arc")
-                )
-            })?;
+        self.resolve_key::<T>(None, "resolve_arc")
+    }
 
-        services
-            .get(&type_id)
-            .and_then(|service| service.clone().downcast::<T>().ok())
-            .to_app_error(&format!(
-                "Service {} not found in container",
-                std::any::type_name::<T>()
-            ))
+    fn resolve_key<T: 'static + Send + Sync>(
+        &self,
+        name: Option<&str>,
+        operation: &str,
+    ) -> AppResult<Arc<T>> {
+        let (resolution, owner) = self.resolution::<T>(name, operation)?;
+        match resolution {
+            Resolution::Instance(instance) => downcast(instance),
+            Resolution::Lazy(lazy) => {
+                // Built in the registering container so a parent's singleton
+                // doesn't capture a child's overrides
+                let _building = Building::enter::<T>(name)?;
+                downcast(lazy.get(owner)?)
+            }
+            Resolution::Transient(factory) => {
+                let _building = Building::enter::<T>(name)?;
+                downcast(factory(self)?)
+            }
+            Resolution::Scoped(_) => Err(AppError::DependencyInjection(
+                ErrorValue::new(
+                    ErrorCode::InternalError,
+                    format!(
+                        "Service {} is scoped and must be resolved from a Scope",
+                        std::any::type_name::<T>()
+                    ),
+                )
+                .with_context("operation", operation),
+            )),
+        }
     }
 
     pub fn has<T: 'static>(&self) -> AppResult<bool> {
+        let found = self.find(&ServiceKey::of::<T>(None), "has", |_, _| ())?;
+        Ok(found.is_some())
+    }
+
+    pub fn lifetime_of<T: 'static>(&self) -> AppResult<Option<Lifetime>> {
+        self.find(&ServiceKey::of::<T>(None), "lifetime_of", |_, entry| {
+            entry.registration.lifetime()
+        })
+    }
+
+    /// Every registration, sorted by type and name, for diagnosing
+    /// "service not found" errors; a child lists what it inherits too
+    pub fn services_info(&self) -> AppResult<Vec<ServiceInfo>> {
+        let mut seen = std::collections::HashSet::new();
+        let mut info = Vec::new();
+        for container in self.chain() {
+            let services = container.services("services_info")?;
+            for (key, entry) in services.iter() {
+                // Shadowed by a nearer container
+                if !seen.insert(key.clone()) {
+                    continue;
+                }
+                info.push(ServiceInfo {
+                    type_name: short_type_name(entry.type_name),
+                    name: key.name.clone(),
+                    lifetime: entry.registration.lifetime().to_string(),
+                    created: entry.is_created(),
+                    resolve_count: entry.resolves.load(Ordering::Relaxed),
+                });
+            }
+        }
+        info.sort_by(|a, b| (&a.type_name, &a.name).cmp(&(&b.type_name, &b.name)));
+        Ok(info)
+    }
+
+    /// Start a scope, e.g. for one handler invocation
+    pub fn create_scope(&self) -> Scope<'_> {
+        Scope {
+            container: self,
+            instances: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Run `f` in a new scope, which [`resolve_scoped`] resolves from on
+    /// this thread until `f` returns
+    pub fn scoped<R>(&'static self, f: impl FnOnce(&Scope) -> R) -> R {
+        struct Exit;
+
+        impl Drop for Exit {
+            fn drop(&mut self) {
+                CURRENT_SCOPES.with(|scopes| scopes.borrow_mut().pop());
+            }
+        }
+
+        let scope = Rc::new(self.create_scope());
+        CURRENT_SCOPES.with(|scopes| scopes.borrow_mut().push(Rc::clone(&scope)));
+        // Leaves the scope even if `f` panics
+        let _exit = Exit;
+        f(&scope)
+    }
+}
+
+/// Services resolved for one unit of work. Scoped services are built once
+/// per scope and dropped with it, newest first; singletons and transients
+/// resolve as they do on the container.
+pub struct Scope<'a> {
+    container: &'a Container,
+    instances: Mutex<Vec<(TypeId, Instance)>>,
+}
+
+impl Scope<'_> {
+    pub fn container(&self) -> &Container {
+        self.container
+    }
+
+    fn scoped_instance(&self, type_id: TypeId) -> Option<Instance> {
+        self.instances.lock().ok().and_then(|instances| {
+            instances
+                .iter()
+                .find(|(id, _)| *id == type_id)
+                .map(|(_, instance)| instance.clone())
+        })
+    }
+
+    pub fn resolve<T: 'static + Clone + Send + Sync>(&self) -> AppResult<T> {
+        self.resolve_arc::<T>().map(|service| (*service).clone())
+    }
+
+    pub fn resolve_trait<T>(&self) -> AppResult<Arc<T>>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.resolve::<Arc<T>>()
+    }
+
+    /// Named services are singletons, so this is the container's instance
+    pub fn get_named<T: 'static + Send + Sync>(&self, name: &str) -> AppResult<Arc<T>> {
+        self.container.get_named::<T>(name)
+    }
+
+    pub fn resolve_arc<T: 'static + Send + Sync>(&self) -> AppResult<Arc<T>> {
+        let (resolution, _) = self.container.resolution::<T>(None, "resolve_arc")?;
+        let factory = match resolution {
+            Resolution::Instance(instance) => return downcast(instance),
+            Resolution::Lazy(_) | Resolution::Transient(_) => return self.container.resolve_arc::<T>(),
+            Resolution::Scoped(factory) => factory,
+        };
+
         let type_id = TypeId::of::<T>();
-        let services = self
-            .services
-            .lock()
-            .map_err(|e| {
-                AppError::LockPoisoned(
-                    ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire DI container lock")
-                        .with_cause(e.to_string())
-                        .with_context("operation", "has")
-                )
-            })?;
-        Ok(services.contains_key(&type_id))
+        if let Some(instance) = self.scoped_instance(type_id) {
+            return downcast(instance);
+        }
+        // Built without the lock held so the factory can resolve its own
+        // scoped dependencies
+        let building = Building::enter::<T>(None)?;
+        let instance = factory(self)?;
+        drop(building);
+        let mut instances = self.instances.lock().map_err(|e| {
+            AppError::LockPoisoned(
+                ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire DI scope lock")
+                    .with_cause(e.to_string())
+                    .with_context("operation", "resolve_arc")
+            )
+        })?;
+        let instance = match instances.iter().find(|(id, _)| *id == type_id) {
+            Some((_, existing)) => existing.clone(),
+            None => {
+                instances.push((type_id, instance.clone()));
+                instance
+            }
+        };
+        drop(instances);
+        downcast(instance)
+    }
+}
+
+impl Drop for Scope<'_> {
+    fn drop(&mut self) {
+        if let Ok(instances) = self.instances.get_mut() {
+            while instances.pop().is_some() {}
+        }
     }
 }
 
@@ -110,12 +917,75 @@ use std::sync::OnceLock;
 static GLOBAL_CONTAINER: OnceLock<Container> = OnceLock::new();
 
 pub fn get_container() -> &'static Container {
-    GLOBAL_CONTAINER.get_or_init(|| Container::new())
+    GLOBAL_CONTAINER.get_or_init(Container::new)
+}
+
+/// Run `f` in a new scope of the global container, e.g. once per handler
+/// invocation so stateful services aren't shared across concurrent calls
+pub fn scoped<R>(f: impl FnOnce(&Scope) -> R) -> R {
+    get_container().scoped(f)
+}
+
+/// Resolve `T` from the innermost scope entered with [`scoped`] on this
+/// thread, e.g. the scope of the handler call being served
+pub fn resolve_scoped<T: 'static + Send + Sync>() -> AppResult<Arc<T>> {
+    let scope = CURRENT_SCOPES.with(|scopes| scopes.borrow().last().cloned());
+    match scope {
+        Some(scope) => scope.resolve_arc::<T>(),
+        None => Err(AppError::DependencyInjection(
+            ErrorValue::new(
+                ErrorCode::InternalError,
+                format!(
+                    "No scope is active to resolve {} from",
+                    std::any::type_name::<T>()
+                ),
+            )
+            .with_context("operation", "resolve_scoped"),
+        )),
+    }
 }
 
-pub fn init_container() -> AppResult<()> {
-    use crate::core::infrastructure::logging;
-    get_container().register(logging::Logger::new())
+/// One subsystem's service registrations, installed together by a
+/// [`ContainerBuilder`]
+pub trait ServiceModule {
+    /// Used in the startup log and registration errors
+    fn name(&self) -> &'static str;
+
+    fn register(&self, container: &Container) -> AppResult<()>;
+}
+
+/// Installs [`ServiceModule`]s in the order they are added, so a module can
+/// resolve what earlier modules registered; e.g. every module after the
+/// config module can resolve `AppConfig`
+pub struct ContainerBuilder<'a> {
+    container: &'a Container,
+    modules: Vec<Box<dyn ServiceModule + 'a>>,
+}
+
+impl<'a> ContainerBuilder<'a> {
+    pub fn new(container: &'a Container) -> Self {
+        Self {
+            container,
+            modules: Vec::new(),
+        }
+    }
+
+    pub fn module(mut self, module: impl ServiceModule + 'a) -> Self {
+        self.modules.push(Box::new(module));
+        self
+    }
+
+    /// Register every module, stopping at the first that fails
+    pub fn build(self) -> AppResult<&'a Container> {
+        for module in &self.modules {
+            if let Err(e) = module.register(self.container) {
+                error!("Failed to register {} services: {}", module.name(), e);
+                return Err(e);
+            }
+            info!("Registered {} services", module.name());
+        }
+        Ok(self.container)
+    }
 }
 
 #[cfg(test)]
@@ -143,4 +1013,561 @@ mod tests {
         let resolved: Arc<String> = container.resolve_arc().expect("Failed to resolve");
         assert_eq!(*resolved, "test");
     }
+
+    trait Greeter: Send + Sync {
+        fn greet(&self) -> String;
+    }
+
+    struct English;
+
+    impl Greeter for English {
+        fn greet(&self) -> String {
+            "hello".to_string()
+        }
+    }
+
+    struct MockGreeter(&'static str);
+
+    impl Greeter for MockGreeter {
+        fn greet(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    #[test]
+    fn test_trait_object_registration() {
+        let container = Container::new();
+        assert!(container.resolve_trait::<dyn Greeter>().is_err());
+
+        container
+            .register_trait::<dyn Greeter>(Arc::new(English))
+            .expect("Failed to register");
+        assert_eq!(container.resolve_trait::<dyn Greeter>().unwrap().greet(), "hello");
+
+        // A test swaps in a mock under the same trait
+        container
+            .register_trait::<dyn Greeter>(Arc::new(MockGreeter("mocked")))
+            .expect("Failed to register");
+        assert_eq!(container.resolve_trait::<dyn Greeter>().unwrap().greet(), "mocked");
+
+        // Factories register under the trait by returning Arc<dyn Trait>
+        let scoped = Container::new();
+        scoped
+            .register_scoped(|_| Ok(Arc::new(English) as Arc<dyn Greeter>))
+            .expect("Failed to register");
+        let scope = scoped.create_scope();
+        assert_eq!(scope.resolve_trait::<dyn Greeter>().unwrap().greet(), "hello");
+    }
+
+    #[test]
+    fn test_lazy_factory_builds_once_on_first_resolve() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let container = Container::new();
+        container.register(String::from("app.db")).expect("Failed to register");
+        let built = Arc::new(AtomicUsize::new(0));
+        let counter = built.clone();
+        container
+            .register_factory(move |c| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(vec![c.resolve::<String>()?])
+            })
+            .expect("Failed to register");
+
+        assert!(!container.is_created::<Vec<String>>().unwrap());
+        assert_eq!(built.load(Ordering::SeqCst), 0);
+
+        let first = container.resolve_arc::<Vec<String>>().unwrap();
+        let second = container.create_scope().resolve_arc::<Vec<String>>().unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(built.load(Ordering::SeqCst), 1);
+        assert!(container.is_created::<Vec<String>>().unwrap());
+        assert_eq!(container.lifetime_of::<Vec<String>>().unwrap(), Some(Lifetime::Singleton));
+    }
+
+    #[test]
+    fn test_named_registrations() {
+        let container = Container::new();
+        container.register(String::from("default")).expect("Failed to register");
+        container
+            .register_named("app", String::from("app.db"))
+            .expect("Failed to register");
+        container
+            .register_named_factory("cache", |c| {
+                Ok(format!("{}-cache", c.get_named::<String>("app")?))
+            })
+            .expect("Failed to register");
+
+        assert_eq!(*container.get_named::<String>("app").unwrap(), "app.db");
+        assert_eq!(*container.get_named::<String>("cache").unwrap(), "app.db-cache");
+        assert_eq!(container.resolve::<String>().unwrap(), "default");
+        assert_eq!(container.names_of::<String>().unwrap(), ["app", "cache"]);
+        assert!(container.has_named::<String>("app").unwrap());
+
+        let scope = container.create_scope();
+        assert!(Arc::ptr_eq(
+            &scope.get_named::<String>("cache").unwrap(),
+            &container.get_named::<String>("cache").unwrap()
+        ));
+
+        let error = container.get_named::<String>("logs").unwrap_err();
+        assert!(matches!(error, AppError::NotFound(_)));
+        assert!(error.to_value().message.contains("named 'logs'"));
+
+        // A named service may depend on another of the same type
+        container
+            .register_named_factory("loop", |c| c.get_named::<u8>("loop").map(|n| *n))
+            .expect("Failed to register");
+        assert_eq!(
+            container.get_named::<u8>("loop").unwrap_err().to_value().message,
+            "Circular dependency: u8(\"loop\") → u8(\"loop\")"
+        );
+    }
+
+    type ShutdownLog = Arc<Mutex<Vec<&'static str>>>;
+
+    struct Service {
+        name: &'static str,
+        fails: bool,
+        log: ShutdownLog,
+    }
+
+    impl Shutdown for Service {
+        fn shutdown(&self) -> AppResult<()> {
+            self.log.lock().unwrap().push(self.name);
+            if self.fails {
+                return Err(AppError::DependencyInjection(ErrorValue::new(
+                    ErrorCode::InternalError,
+                    format!("{} failed to shut down", self.name),
+                )));
+            }
+            Ok(())
+        }
+    }
+
+    struct LazyServer(Service);
+    struct UnusedServer(Service);
+
+    impl Shutdown for LazyServer {
+        fn shutdown(&self) -> AppResult<()> {
+            self.0.shutdown()
+        }
+    }
+
+    impl Shutdown for UnusedServer {
+        fn shutdown(&self) -> AppResult<()> {
+            self.0.shutdown()
+        }
+    }
+
+    #[test]
+    fn test_shutdown_all_in_reverse_registration_order() {
+        let log = ShutdownLog::default();
+        let service = |name, fails, log: &ShutdownLog| Service { name, fails, log: log.clone() };
+
+        let container = Container::new();
+        container.register_disposable(service("logger", false, &log)).unwrap();
+        container.register_disposable(Arc::new(service("database", true, &log))).unwrap();
+        let lazy_log = log.clone();
+        container
+            .register_factory(move |_| Ok(LazyServer(service("server", false, &lazy_log))))
+            .unwrap();
+        container.on_shutdown::<LazyServer>().unwrap();
+        let unused_log = log.clone();
+        container
+            .register_factory(move |_| Ok(UnusedServer(service("unused", false, &unused_log))))
+            .unwrap();
+        container.on_shutdown::<UnusedServer>().unwrap();
+        container.resolve_arc::<LazyServer>().unwrap();
+
+        // A failure doesn't stop the teardown; an unbuilt lazy service is skipped
+        let error = container.shutdown_all().unwrap_err();
+        assert_eq!(error.to_value().message, "database failed to shut down");
+        assert_eq!(*log.lock().unwrap(), ["server", "database", "logger"]);
+
+        assert!(container.shutdown_all().is_ok());
+        assert_eq!(log.lock().unwrap().len(), 3);
+    }
+
+    #[derive(rustwebui_macros::Injectable)]
+    struct UserHandler {
+        db: Arc<String>,
+        greeter: Arc<dyn Greeter>,
+        #[inject(name = "cache")]
+        cache: Arc<Vec<u8>>,
+        retries: u8,
+        #[inject(default)]
+        calls: Mutex<usize>,
+    }
+
+    #[test]
+    fn test_injectable_resolves_fields() {
+        let container = Container::new();
+        container.register(Arc::new(String::from("app.db"))).unwrap();
+        container.register_trait::<dyn Greeter>(Arc::new(English)).unwrap();
+        container.register_named("cache", vec![1u8, 2]).unwrap();
+        container.register(3u8).unwrap();
+        container.register_injectable::<UserHandler>().unwrap();
+
+        let handler = container.resolve_arc::<UserHandler>().unwrap();
+        assert_eq!(*handler.db, "app.db");
+        assert_eq!(handler.greeter.greet(), "hello");
+        assert_eq!(*handler.cache, [1, 2]);
+        assert_eq!(handler.retries, 3);
+        assert_eq!(*handler.calls.lock().unwrap(), 0);
+
+        let error = UserHandler::inject(&Container::new()).err().unwrap();
+        assert!(matches!(error, AppError::NotFound(_)));
+    }
+
+    struct ConfigModule;
+    struct CacheModule;
+
+    impl ServiceModule for ConfigModule {
+        fn name(&self) -> &'static str {
+            "config"
+        }
+
+        fn register(&self, container: &Container) -> AppResult<()> {
+            container.register(String::from("cache.db"))
+        }
+    }
+
+    impl ServiceModule for CacheModule {
+        fn name(&self) -> &'static str {
+            "cache"
+        }
+
+        fn register(&self, container: &Container) -> AppResult<()> {
+            let path = container.resolve::<String>()?;
+            container.register_named("cache", vec![path])
+        }
+    }
+
+    #[test]
+    fn test_container_builder_installs_modules_in_order() {
+        let container = Container::new();
+        ContainerBuilder::new(&container)
+            .module(ConfigModule)
+            .module(CacheModule)
+            .build()
+            .expect("Failed to build container");
+        assert_eq!(*container.get_named::<Vec<String>>("cache").unwrap(), ["cache.db"]);
+
+        // A module that depends on a later one fails the build
+        let container = Container::new();
+        let result = ContainerBuilder::new(&container)
+            .module(CacheModule)
+            .module(ConfigModule)
+            .build();
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+        assert!(!container.has::<String>().unwrap());
+    }
+
+    #[test]
+    fn test_services_info() {
+        let container = Container::new();
+        container.register(Arc::new(String::from("app.db"))).unwrap();
+        container.register_named("cache", String::from("cache.db")).unwrap();
+        container.register_factory(|_| Ok(1u16)).unwrap();
+        container.register_transient(|_| Ok(2u32)).unwrap();
+        container.resolve::<u32>().unwrap();
+        container.resolve::<u32>().unwrap();
+
+        let summary: Vec<_> = container
+            .services_info()
+            .unwrap()
+            .into_iter()
+            .map(|s| (s.type_name, s.name, s.lifetime, s.created, s.resolve_count))
+            .collect();
+        let expected = [
+            ("Arc<String>", None, "singleton", true, 0),
+            ("String", Some("cache"), "singleton", true, 0),
+            ("u16", None, "singleton", false, 0),
+            ("u32", None, "transient", false, 2),
+        ];
+        assert_eq!(summary.len(), expected.len());
+        for (actual, expected) in summary.iter().zip(expected) {
+            assert_eq!(actual.0, expected.0);
+            assert_eq!(actual.1.as_deref(), expected.1);
+            assert_eq!(actual.2, expected.2);
+            assert_eq!((actual.3, actual.4), (expected.3, expected.4));
+        }
+    }
+
+    /// Concurrent resolves against a `Mutex<HashMap>` lookup, the container's
+    /// previous design. Run with
+    /// `cargo test --release bench_concurrent_resolve -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_concurrent_resolve() {
+        use std::time::{Duration, Instant};
+
+        const THREADS: usize = 8;
+        const RESOLVES: usize = 200_000;
+
+        let container = Container::new();
+        container.register(String::from("app.db")).unwrap();
+        let mut map: HashMap<TypeId, Instance> = HashMap::new();
+        map.insert(TypeId::of::<String>(), Arc::new(String::from("app.db")));
+        let baseline = Mutex::new(map);
+
+        let time = |resolve: &(dyn Fn() + Sync)| -> Duration {
+            let started = Instant::now();
+            std::thread::scope(|scope| {
+                for _ in 0..THREADS {
+                    scope.spawn(|| (0..RESOLVES).for_each(|_| resolve()));
+                }
+            });
+            started.elapsed()
+        };
+        let mutex = time(&|| {
+            let instance = baseline.lock().unwrap()[&TypeId::of::<String>()].clone();
+            std::hint::black_box(downcast::<String>(instance).unwrap());
+        });
+        let rwlock = time(&|| {
+            std::hint::black_box(container.resolve_arc::<String>().unwrap());
+        });
+
+        let per_resolve = |elapsed: Duration| elapsed.as_nanos() / (THREADS * RESOLVES) as u128;
+        println!(
+            "{} threads x {} resolves: Mutex {:?} ({} ns/resolve), RwLock {:?} ({} ns/resolve), {:.1}x",
+            THREADS,
+            RESOLVES,
+            mutex,
+            per_resolve(mutex),
+            rwlock,
+            per_resolve(rwlock),
+            mutex.as_secs_f64() / rwlock.as_secs_f64()
+        );
+    }
+
+    #[test]
+    fn test_child_container_overrides_parent() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let parent = Container::new();
+        parent.register(String::from("app.db")).unwrap();
+        parent.register_named("cache", 1u8).unwrap();
+        parent
+            .register_factory(|c| Ok(vec![c.resolve::<String>()?]))
+            .unwrap();
+        let built = Arc::new(AtomicUsize::new(0));
+        let counter = built.clone();
+        parent
+            .register_transient(move |c| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(format!("report from {}", c.resolve::<String>()?).into_bytes())
+            })
+            .unwrap();
+
+        let child = parent.create_child();
+        child.register(String::from(":memory:")).unwrap();
+        child.register_named("logs", 2u8).unwrap();
+
+        // The override only applies to the child
+        assert_eq!(child.resolve::<String>().unwrap(), ":memory:");
+        assert_eq!(parent.resolve::<String>().unwrap(), "app.db");
+        assert!(parent.has_named::<u8>("cache").unwrap() && !parent.has_named::<u8>("logs").unwrap());
+        assert_eq!(child.names_of::<u8>().unwrap(), ["cache", "logs"]);
+
+        // Parent transients see the child's overrides; parent singletons
+        // are built in, and shared with, the parent
+        assert_eq!(child.resolve::<Vec<u8>>().unwrap(), b"report from :memory:");
+        assert_eq!(built.load(Ordering::SeqCst), 1);
+        let shared = child.resolve_arc::<Vec<String>>().unwrap();
+        assert_eq!(*shared, ["app.db"]);
+        assert!(Arc::ptr_eq(&shared, &parent.resolve_arc::<Vec<String>>().unwrap()));
+
+        let grandchild = child.create_child();
+        assert_eq!(grandchild.resolve::<String>().unwrap(), ":memory:");
+        let strings: Vec<_> = grandchild
+            .services_info()
+            .unwrap()
+            .into_iter()
+            .filter(|s| s.type_name == "String")
+            .collect();
+        assert_eq!(strings.len(), 1);
+    }
+
+    #[test]
+    fn test_get_or_create() {
+        let container = Container::new();
+        let created = container.get_or_create(|| Ok(7u8)).unwrap();
+        let existing = container
+            .get_or_create::<u8, _>(|| panic!("already registered"))
+            .unwrap();
+        assert!(Arc::ptr_eq(&created, &existing));
+    }
+
+    #[derive(Debug)]
+    struct ServiceA;
+    #[derive(Debug)]
+    struct ServiceB;
+    #[derive(Debug)]
+    struct ServiceC;
+
+    #[test]
+    fn test_circular_dependency_is_reported() {
+        let container = Container::new();
+        container
+            .register_transient(|c| c.resolve_arc::<ServiceB>().map(|_| ServiceA))
+            .expect("Failed to register");
+        container
+            .register_factory(|c| c.resolve_arc::<ServiceC>().map(|_| ServiceB))
+            .expect("Failed to register");
+        container
+            .register_transient(|c| c.resolve_arc::<ServiceA>().map(|_| ServiceC))
+            .expect("Failed to register");
+
+        let error = container.resolve_arc::<ServiceA>().unwrap_err();
+        assert!(matches!(error, AppError::DependencyInjection(_)));
+        assert_eq!(
+            error.to_value().message,
+            "Circular dependency: ServiceA → ServiceB → ServiceC → ServiceA"
+        );
+
+        // The lazy service in the middle is neither deadlocked nor half-built
+        let error = container.resolve_arc::<ServiceB>().unwrap_err();
+        assert!(error.to_value().message.ends_with("ServiceB → ServiceC → ServiceA → ServiceB"));
+        assert!(!container.is_created::<ServiceB>().unwrap());
+
+        let scoped = Container::new();
+        scoped
+            .register_scoped(|scope| scope.resolve_arc::<ServiceA>().map(|_| ServiceA))
+            .expect("Failed to register");
+        let error = scoped.create_scope().resolve_arc::<ServiceA>().unwrap_err();
+        assert_eq!(error.to_value().message, "Circular dependency: ServiceA → ServiceA");
+    }
+
+    #[test]
+    fn test_circular_dependency_across_threads_is_reported() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Barrier;
+
+        // Each factory waits, on its first run only, until both are running,
+        // so each thread needs the service the other is building
+        let both_building = Arc::new(Barrier::new(2));
+        let first_run = |barrier: Arc<Barrier>| {
+            let started = AtomicBool::new(false);
+            move || {
+                if !started.swap(true, Ordering::SeqCst) {
+                    barrier.wait();
+                }
+            }
+        };
+        let (sync_a, sync_b) = (first_run(both_building.clone()), first_run(both_building));
+        let container = Container::new();
+        container
+            .register_factory(move |c| {
+                sync_a();
+                c.resolve_arc::<ServiceB>().map(|_| ServiceA)
+            })
+            .expect("Failed to register");
+        container
+            .register_factory(move |c| {
+                sync_b();
+                c.resolve_arc::<ServiceA>().map(|_| ServiceB)
+            })
+            .expect("Failed to register");
+
+        let (a, b) = thread::scope(|s| {
+            let a = s.spawn(|| container.resolve_arc::<ServiceA>());
+            let b = s.spawn(|| container.resolve_arc::<ServiceB>());
+            (a.join().unwrap(), b.join().unwrap())
+        });
+        for error in [a.unwrap_err(), b.unwrap_err()] {
+            assert!(matches!(error, AppError::DependencyInjection(_)));
+            assert!(error.to_value().message.starts_with("Circular dependency: "));
+        }
+        assert!(!container.is_created::<ServiceA>().unwrap());
+        assert!(!container.is_created::<ServiceB>().unwrap());
+    }
+
+    #[test]
+    fn test_short_type_name() {
+        assert_eq!(
+            short_type_name("alloc::sync::Arc<dyn my_app::repo::Users + core::marker::Send>"),
+            "Arc<dyn Users + Send>"
+        );
+    }
+
+    #[test]
+    fn test_transient_builds_per_resolve() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let container = Container::new();
+        let built = Arc::new(AtomicUsize::new(0));
+        let counter = built.clone();
+        container
+            .register_transient(move |_| Ok(counter.fetch_add(1, Ordering::SeqCst)))
+            .expect("Failed to register");
+
+        assert_eq!(container.resolve::<usize>().unwrap(), 0);
+        assert_eq!(container.resolve::<usize>().unwrap(), 1);
+        assert_eq!(container.create_scope().resolve::<usize>().unwrap(), 2);
+        assert_eq!(container.lifetime_of::<usize>().unwrap(), Some(Lifetime::Transient));
+    }
+
+    #[test]
+    fn test_scoped_per_scope_and_dropped_with_it() {
+        struct RequestState(Mutex<Vec<String>>);
+        struct Handle(Arc<RequestState>);
+
+        let container = Container::new();
+        container.register(String::from("db")).expect("Failed to register");
+        container
+            .register_scoped(|scope| {
+                let db = scope.resolve::<String>()?;
+                Ok(RequestState(Mutex::new(vec![db])))
+            })
+            .expect("Failed to register");
+        // Scoped services can depend on each other
+        container
+            .register_scoped(|scope| Ok(Handle(scope.resolve_arc::<RequestState>()?)))
+            .expect("Failed to register");
+
+        assert!(container.resolve_arc::<RequestState>().is_err());
+
+        let weak = {
+            let scope = container.create_scope();
+            let first = scope.resolve_arc::<RequestState>().unwrap();
+            first.0.lock().unwrap().push("call".to_string());
+            let handle = scope.resolve_arc::<Handle>().unwrap();
+            assert!(Arc::ptr_eq(&first, &handle.0));
+            assert_eq!(*handle.0 .0.lock().unwrap(), ["db", "call"]);
+
+            let other = container.create_scope().resolve_arc::<RequestState>().unwrap();
+            assert!(!Arc::ptr_eq(&first, &other));
+            Arc::downgrade(&first)
+        };
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_resolve_scoped_uses_the_current_scope() {
+        struct CallState(Mutex<u32>);
+
+        let container: &'static Container = Box::leak(Box::new(Container::new()));
+        container
+            .register_scoped(|_| Ok(CallState(Mutex::new(0))))
+            .expect("Failed to register");
+        let count = || -> u32 {
+            let state = resolve_scoped::<CallState>().unwrap();
+            let mut calls = state.0.lock().unwrap();
+            *calls += 1;
+            *calls
+        };
+
+        assert!(resolve_scoped::<CallState>().is_err());
+        container.scoped(|_| {
+            assert_eq!(count(), 1);
+            assert_eq!(count(), 2);
+            // A nested call gets a scope of its own
+            container.scoped(|_| assert_eq!(count(), 1));
+            assert_eq!(count(), 3);
+        });
+        assert_eq!(container.scoped(|_| count()), 1);
+        assert!(resolve_scoped::<CallState>().is_err());
+    }
 }
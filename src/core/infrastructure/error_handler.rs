@@ -2,14 +2,20 @@
 // Enhanced error handling with panic hooks, error tracking, and terminal output
 
 use log::{error, info, warn};
+use schemars::JsonSchema;
+use serde::Serialize;
 use std::collections::VecDeque;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 use crate::core::error::ErrorCode;
+use super::crash_reporter;
 
 /// Maximum errors to keep in memory
 const MAX_ERROR_HISTORY: usize = 100;
 
+/// CustomEvent the frontend receives a `PanicReport` in
+pub const PANIC_EVENT: &str = "backend_panic";
+
 /// Error severity levels for terminal output
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(dead_code)]
@@ -251,6 +257,32 @@ pub fn get_error_tracker() -> Arc<ErrorTracker> {
     Arc::clone(&GLOBAL_ERROR_TRACKER)
 }
 
+/// A panic as pushed to the frontend; the crash reporter saves it with
+/// more context
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PanicReport {
+    pub message: String,
+    /// file:line:column of the panic
+    pub location: String,
+    pub thread: String,
+    pub timestamp: String,
+    pub backtrace: String,
+    /// Where the report was saved; `None` if it couldn't be written
+    pub crash_file: Option<String>,
+}
+
+type PanicNotifier = Box<dyn Fn(&PanicReport) + Send + Sync>;
+
+static PANIC_NOTIFIER: OnceLock<PanicNotifier> = OnceLock::new();
+
+/// Call `notify` with every panic after it is logged and saved, e.g. to
+/// tell the frontend; only the first notifier registered is kept
+pub fn on_panic(notify: impl Fn(&PanicReport) + Send + Sync + 'static) {
+    if PANIC_NOTIFIER.set(Box::new(notify)).is_err() {
+        warn!("A panic notifier is already registered");
+    }
+}
+
 /// Initialize enhanced error handling with panic hook
 pub fn init_error_handling() {
     // Set up custom panic hook
@@ -276,6 +308,18 @@ pub fn init_error_handling() {
         // Create stack trace
         let stack_trace = format!("{:?}", backtrace::Backtrace::new());
 
+        let mut report = PanicReport {
+            message: message.clone(),
+            location: location.clone(),
+            thread: std::thread::current()
+                .name()
+                .unwrap_or("unnamed")
+                .to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            backtrace: stack_trace.clone(),
+            crash_file: None,
+        };
+
         // Record as critical error
         let entry = ErrorEntry::new(
             ErrorSeverity::Critical,
@@ -284,6 +328,7 @@ pub fn init_error_handling() {
             message,
         )
         .with_details(format!("Location: {}", location))
+        .with_context("thread".to_string(), report.thread.clone())
         .with_stack_trace(stack_trace);
 
         get_error_tracker().record(entry);
@@ -291,6 +336,14 @@ pub fn init_error_handling() {
         // Log error summary
         let summary = get_error_tracker().get_summary();
         error!("{}", summary.format_terminal());
+
+        report.crash_file =
+            crash_reporter::record_panic(&report).map(|path| path.display().to_string());
+        log::logger().flush();
+
+        if let Some(notify) = PANIC_NOTIFIER.get() {
+            notify(&report);
+        }
     }));
 
     info!("Enhanced error handling initialized with panic hook");
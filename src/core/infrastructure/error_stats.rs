@@ -0,0 +1,225 @@
+// src/core/infrastructure/error_stats.rs
+// Error telemetry: how often each handler answered with each ErrorCode,
+// counted per minute over the last day, so a diagnostics page can show the
+// most frequent errors of the last hour. With `[database]
+// persist_error_stats` the counts also go to the error_stats table and
+// survive restarts.
+
+use chrono::{TimeZone, Utc};
+use log::debug;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, RwLock};
+
+use super::database::Database;
+use crate::core::error::{AppResult, ErrorCode};
+
+/// Minutes of counts kept in memory
+pub const RETENTION_MINUTES: i64 = 24 * 60;
+
+/// How many times one handler answered with one code
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct ErrorStat {
+    pub handler: String,
+    /// Wire name, e.g. "DB_QUERY_FAILED"
+    pub code: String,
+    pub count: u64,
+    /// Start of the latest minute the error was seen in
+    pub last_seen: String,
+}
+
+/// Which counts `error_stats` returns
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ErrorStatsQuery {
+    /// Minutes to look back (default 60)
+    pub window_minutes: Option<i64>,
+    /// Most frequent errors to return (default 10)
+    pub limit: Option<usize>,
+    /// Only errors answered by this handler
+    pub handler: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ErrorStatsReport {
+    pub window_minutes: i64,
+    /// Errors in the window across every handler and code
+    pub total: u64,
+    /// Most frequent first
+    pub top: Vec<ErrorStat>,
+}
+
+pub struct ErrorStats {
+    /// (minute, handler, code) -> count, oldest minute first
+    counts: Mutex<BTreeMap<(i64, String, String), u64>>,
+    store: RwLock<Option<Arc<Database>>>,
+}
+
+impl Default for ErrorStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ErrorStats {
+    pub fn new() -> Self {
+        Self {
+            counts: Mutex::new(BTreeMap::new()),
+            store: RwLock::new(None),
+        }
+    }
+
+    /// Also write counts to `db`, and answer queries from it
+    pub fn set_store(&self, db: Arc<Database>) {
+        if let Ok(mut store) = self.store.write() {
+            *store = Some(db);
+        }
+    }
+
+    /// Count one `code` answer from `handler`
+    pub fn record(&self, handler: &str, code: &ErrorCode) {
+        self.record_at(current_minute(), handler, code);
+    }
+
+    fn record_at(&self, minute: i64, handler: &str, code: &ErrorCode) {
+        let code = code.to_string();
+        if let Ok(mut counts) = self.counts.lock() {
+            *counts
+                .entry((minute, handler.to_string(), code.clone()))
+                .or_default() += 1;
+            // Drop whole minutes that fell out of the retention window
+            let kept =
+                counts.split_off(&(minute - RETENTION_MINUTES, String::new(), String::new()));
+            *counts = kept;
+        }
+        let store = self.store.read().ok().and_then(|store| store.clone());
+        if let Some(db) = store {
+            if let Err(e) = db.record_error_stat(minute, handler, &code) {
+                debug!("Failed to persist error stats: {}", e);
+            }
+        }
+    }
+
+    /// The most frequent errors in the query's window
+    pub fn report(&self, query: &ErrorStatsQuery) -> AppResult<ErrorStatsReport> {
+        self.report_at(current_minute(), query)
+    }
+
+    fn report_at(&self, minute: i64, query: &ErrorStatsQuery) -> AppResult<ErrorStatsReport> {
+        let window_minutes = query.window_minutes.unwrap_or(60).max(1);
+        let since = minute - window_minutes + 1;
+        let store = self.store.read().ok().and_then(|store| store.clone());
+        let mut stats = match store {
+            Some(db) => db.error_stats_since(since, query.handler.as_deref())?,
+            None => self.in_memory_since(since, query.handler.as_deref()),
+        };
+        stats.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| b.last_seen.cmp(&a.last_seen))
+        });
+        let total = stats.iter().map(|stat| stat.count).sum();
+        stats.truncate(query.limit.unwrap_or(10));
+        Ok(ErrorStatsReport {
+            window_minutes,
+            total,
+            top: stats,
+        })
+    }
+
+    fn in_memory_since(&self, since: i64, handler: Option<&str>) -> Vec<ErrorStat> {
+        let Ok(counts) = self.counts.lock() else {
+            return Vec::new();
+        };
+        let mut totals: HashMap<(&str, &str), (u64, i64)> = HashMap::new();
+        for ((minute, entry_handler, code), count) in
+            counts.range((since, String::new(), String::new())..)
+        {
+            if handler.is_some_and(|handler| handler != entry_handler.as_str()) {
+                continue;
+            }
+            let total = totals
+                .entry((entry_handler.as_str(), code.as_str()))
+                .or_default();
+            total.0 += count;
+            total.1 = total.1.max(*minute);
+        }
+        totals
+            .into_iter()
+            .map(|((handler, code), (count, minute))| ErrorStat {
+                handler: handler.to_string(),
+                code: code.to_string(),
+                count,
+                last_seen: minute_to_rfc3339(minute),
+            })
+            .collect()
+    }
+}
+
+/// Minutes since the Unix epoch
+pub fn current_minute() -> i64 {
+    Utc::now().timestamp().div_euclid(60)
+}
+
+/// RFC 3339 start of `minute`
+pub fn minute_to_rfc3339(minute: i64) -> String {
+    Utc.timestamp_opt(minute * 60, 0)
+        .single()
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_default()
+}
+
+lazy_static::lazy_static! {
+    pub static ref GLOBAL_ERROR_STATS: ErrorStats = ErrorStats::new();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_errors_in_window() {
+        let stats = ErrorStats::new();
+        let now = 1_000_000;
+        for _ in 0..3 {
+            stats.record_at(now, "get_users", &ErrorCode::DbQueryFailed);
+        }
+        stats.record_at(now - 1, "get_users", &ErrorCode::DbQueryFailed);
+        stats.record_at(now - 5, "create_user", &ErrorCode::ValidationFailed);
+        stats.record_at(now - 90, "create_user", &ErrorCode::DbBusy);
+
+        let report = stats.report_at(now, &ErrorStatsQuery::default()).unwrap();
+        assert_eq!(report.window_minutes, 60);
+        assert_eq!(report.total, 5);
+        assert_eq!(report.top.len(), 2);
+        assert_eq!(report.top[0].handler, "get_users");
+        assert_eq!(report.top[0].code, "DB_QUERY_FAILED");
+        assert_eq!(report.top[0].count, 4);
+        assert_eq!(report.top[0].last_seen, minute_to_rfc3339(now));
+
+        let query = ErrorStatsQuery {
+            window_minutes: Some(120),
+            limit: Some(1),
+            handler: Some(String::from("create_user")),
+        };
+        let report = stats.report_at(now, &query).unwrap();
+        assert_eq!(report.total, 2);
+        assert_eq!(report.top.len(), 1);
+        assert_eq!(report.top[0].handler, "create_user");
+    }
+
+    #[test]
+    fn test_old_minutes_are_dropped() {
+        let stats = ErrorStats::new();
+        stats.record_at(0, "get_users", &ErrorCode::DbQueryFailed);
+        stats.record_at(RETENTION_MINUTES + 1, "get_users", &ErrorCode::Timeout);
+
+        let query = ErrorStatsQuery {
+            window_minutes: Some(RETENTION_MINUTES * 2),
+            ..ErrorStatsQuery::default()
+        };
+        let report = stats.report_at(RETENTION_MINUTES + 1, &query).unwrap();
+        assert_eq!(report.total, 1);
+        assert_eq!(report.top[0].code, "TIMEOUT");
+    }
+}
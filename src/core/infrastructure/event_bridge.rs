@@ -0,0 +1,254 @@
+// src/core/infrastructure/event_bridge.rs
+// Forwards backend EventBus topics to the frontend as CustomEvents
+
+#![allow(dead_code)]
+
+use log::{debug, info};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use super::codec;
+use super::event_bus::{EventBus, EventData, EventPriority, SubscriptionId, GLOBAL_EVENT_BUS};
+use super::outbound_queue::{self, PushPriority};
+use crate::core::rpc::RpcResponse;
+
+/// Prefix of the CustomEvent a bridged topic is dispatched as
+pub const EVENT_PREFIX: &str = "bus:";
+
+/// CustomEvent name for a bridged topic, e.g. `bus:user.created`
+pub fn frontend_event_name(topic: &str) -> String {
+    format!("{}{}", EVENT_PREFIX, topic)
+}
+
+/// Envelope the frontend receives: the whole event as `data`
+pub fn bridge_detail(event: &EventData) -> serde_json::Value {
+    RpcResponse::ok(event).to_json()
+}
+
+/// Frontend publications are already fanned out as `bus_event`
+fn is_bridged(event: &EventData) -> bool {
+    event.source.as_deref() != Some("frontend")
+}
+
+fn forward(event: &EventData) {
+    if !is_bridged(event) {
+        return;
+    }
+    let event_name = frontend_event_name(&event.event_type);
+    outbound_queue::broadcast_event(
+        &event_name,
+        &bridge_detail(event),
+        PushPriority::Normal,
+    );
+}
+
+fn forward_to(window_id: usize, event: &EventData) {
+    if !is_bridged(event) {
+        return;
+    }
+    let event_name = frontend_event_name(&event.event_type);
+    outbound_queue::push_event(
+        window_id,
+        &event_name,
+        codec::shared().encode(window_id, &event_name, &bridge_detail(event)),
+        PushPriority::Normal,
+    );
+}
+
+/// A topic a frontend client asked to receive
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BusSubscription {
+    pub id: u64,
+    pub topic: String,
+}
+
+/// Bus subscription shared by every subscription of one window to a topic,
+/// so each event reaches the window once
+struct WindowTopic {
+    bus_id: Option<SubscriptionId>,
+    ids: HashSet<u64>,
+}
+
+/// Topics frontend clients subscribed to at runtime, tracked per window so
+/// events only go to the windows that asked for them
+pub struct ClientSubscriptions {
+    topics: Mutex<HashMap<(usize, String), WindowTopic>>,
+    /// Topics already broadcast to every window by `bridge_topics`
+    broadcast: Mutex<HashSet<String>>,
+    next_id: AtomicU64,
+}
+
+impl ClientSubscriptions {
+    pub fn new() -> Self {
+        Self {
+            topics: Mutex::new(HashMap::new()),
+            broadcast: Mutex::new(HashSet::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    fn is_broadcast(&self, topic: &str) -> bool {
+        self.broadcast
+            .lock()
+            .map(|broadcast| broadcast.contains(topic))
+            .unwrap_or(false)
+    }
+
+    /// Start forwarding `topic` to `window_id`
+    pub fn subscribe(&self, bus: &EventBus, window_id: usize, topic: &str) -> BusSubscription {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let broadcast = self.is_broadcast(topic);
+        if let Ok(mut topics) = self.topics.lock() {
+            let entry = topics
+                .entry((window_id, topic.to_string()))
+                .or_insert_with(|| WindowTopic {
+                    bus_id: (!broadcast).then(|| {
+                        bus.subscribe_with_priority(topic, EventPriority::Low, move |event| {
+                            forward_to(window_id, event)
+                        })
+                        .detach()
+                    }),
+                    ids: HashSet::new(),
+                });
+            entry.ids.insert(id);
+        }
+        debug!("Window {} subscribed to '{}' ({})", window_id, topic, id);
+        BusSubscription {
+            id,
+            topic: topic.to_string(),
+        }
+    }
+
+    /// Drop subscription `id` of `window_id`; the topic stops being forwarded
+    /// once the window has no subscriptions to it left
+    pub fn unsubscribe(&self, bus: &EventBus, window_id: usize, id: u64) -> Option<BusSubscription> {
+        let mut topics = self.topics.lock().ok()?;
+        let key = topics
+            .iter()
+            .find(|((window, _), entry)| *window == window_id && entry.ids.contains(&id))
+            .map(|(key, _)| key.clone())?;
+
+        let entry = topics.get_mut(&key)?;
+        entry.ids.remove(&id);
+        if entry.ids.is_empty() {
+            if let Some(bus_id) = topics.remove(&key).and_then(|entry| entry.bus_id) {
+                bus.unsubscribe(bus_id);
+            }
+        }
+        Some(BusSubscription { id, topic: key.1 })
+    }
+
+    /// Drop every subscription of a window, e.g. once it disconnects
+    pub fn remove_window(&self, bus: &EventBus, window_id: usize) -> usize {
+        let Ok(mut topics) = self.topics.lock() else {
+            return 0;
+        };
+        let keys: Vec<(usize, String)> = topics
+            .keys()
+            .filter(|(window, _)| *window == window_id)
+            .cloned()
+            .collect();
+        let mut removed = 0;
+        for key in keys {
+            if let Some(entry) = topics.remove(&key) {
+                removed += entry.ids.len();
+                if let Some(bus_id) = entry.bus_id {
+                    bus.unsubscribe(bus_id);
+                }
+            }
+        }
+        removed
+    }
+
+    /// Subscriptions of a window, by id
+    pub fn for_window(&self, window_id: usize) -> Vec<BusSubscription> {
+        let mut subscriptions: Vec<BusSubscription> = self
+            .topics
+            .lock()
+            .map(|topics| {
+                topics
+                    .iter()
+                    .filter(|((window, _), _)| *window == window_id)
+                    .flat_map(|((_, topic), entry)| {
+                        entry.ids.iter().map(|&id| BusSubscription {
+                            id,
+                            topic: topic.clone(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        subscriptions.sort_by_key(|s| s.id);
+        subscriptions
+    }
+}
+
+impl Default for ClientSubscriptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+lazy_static::lazy_static! {
+    pub static ref GLOBAL_CLIENT_SUBSCRIPTIONS: ClientSubscriptions = ClientSubscriptions::new();
+}
+
+/// Subscribe the bridge to `topics` for the lifetime of the process
+pub fn start(topics: &[String]) {
+    if let Ok(mut broadcast) = GLOBAL_CLIENT_SUBSCRIPTIONS.broadcast.lock() {
+        broadcast.extend(topics.iter().cloned());
+    }
+    for topic in topics {
+        GLOBAL_EVENT_BUS
+            .subscribe_with_priority(topic, EventPriority::Low, forward)
+            .detach();
+    }
+    if !topics.is_empty() {
+        info!("Event bridge forwarding {} topic(s): {}", topics.len(), topics.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bridge_detail() {
+        let event = EventData::new("user.created", serde_json::json!({ "id": 7 }))
+            .with_source("db");
+        assert_eq!(frontend_event_name(&event.event_type), "bus:user.created");
+
+        let detail = bridge_detail(&event);
+        assert_eq!(detail["success"], true);
+        assert_eq!(detail["data"]["event_type"], "user.created");
+        assert_eq!(detail["data"]["payload"]["id"], 7);
+        assert_eq!(detail["data"]["source"], "db");
+    }
+
+    #[test]
+    fn test_client_subscriptions_per_window() {
+        let bus = EventBus::new(10);
+        let subscriptions = ClientSubscriptions::new();
+
+        let first = subscriptions.subscribe(&bus, 1, "user.created");
+        let second = subscriptions.subscribe(&bus, 1, "user.created");
+        subscriptions.subscribe(&bus, 2, "user.created");
+        // One bus subscription per window and topic
+        assert_eq!(bus.listener_count("user.created"), 2);
+        assert_eq!(subscriptions.for_window(1), vec![first.clone(), second.clone()]);
+
+        // Windows can only cancel their own subscriptions
+        assert!(subscriptions.unsubscribe(&bus, 2, first.id).is_none());
+        assert_eq!(subscriptions.unsubscribe(&bus, 1, first.id), Some(first));
+        assert_eq!(bus.listener_count("user.created"), 2);
+        subscriptions.unsubscribe(&bus, 1, second.id);
+        assert_eq!(bus.listener_count("user.created"), 1);
+
+        assert_eq!(subscriptions.remove_window(&bus, 2), 1);
+        assert_eq!(bus.listener_count("user.created"), 0);
+        assert!(subscriptions.for_window(2).is_empty());
+    }
+}
@@ -1,9 +1,25 @@
 #![allow(dead_code)]
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use log::{error, warn};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::sync::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant};
 
+use super::config::AppConfig;
+use super::event_middleware::{CorrelationMiddleware, EventMiddleware};
+use super::event_scheduler::{ScheduledId, Scheduler};
+use super::logging::context as log_context;
+use crate::core::domain::traits::EventTopic;
 use crate::core::error::{AppError, AppResult, ErrorValue, ErrorCode};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -13,6 +29,9 @@ pub struct EventData {
     pub timestamp: i64,
     pub source: Option<String>,
     pub target: Option<String>,
+    /// Ties together events and logs caused by the same action
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
 }
 
 impl EventData {
@@ -23,6 +42,7 @@ impl EventData {
             timestamp: Utc::now().timestamp_millis(),
             source: None,
             target: None,
+            correlation_id: None,
         }
     }
 
@@ -35,41 +55,537 @@ impl EventData {
         self.target = Some(target.into());
         self
     }
+
+    pub fn with_correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct EventBusStats {
     pub total_listeners: usize,
+    pub total_published: u64,
+    pub total_dropped: u64,
+    /// Events removed by middleware before reaching subscribers
+    pub total_filtered: u64,
+    /// Average publish rate since the bus was created
+    pub events_per_second: f64,
     pub event_types: Vec<EventTypeInfo>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct EventTypeInfo {
     pub event_type: String,
     pub listener_count: usize,
+    /// Events queued for this type's subscribers but not yet handled
+    pub pending: usize,
+    /// Events discarded by subscriber overflow strategies
+    pub dropped: u64,
+    pub published: u64,
+    /// Handler invocations, and how many of them panicked
+    pub handled: u64,
+    pub failed: u64,
+    pub avg_handler_ms: f64,
+    pub max_handler_ms: f64,
 }
 
-pub struct EventBus {
+/// Counters kept per event type, including types nobody subscribes to
+#[derive(Clone, Debug, Default)]
+struct TopicMetrics {
+    published: u64,
+    dropped: u64,
+    handled: u64,
+    failed: u64,
+    handler_micros: u64,
+    max_handler_micros: u64,
+}
+
+pub type EventHandler = Arc<dyn Fn(&EventData) + Send + Sync>;
+
+/// Identifies a handler registered with [`EventBus::subscribe`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+/// Keeps a handler subscribed; dropping it unsubscribes the handler
+#[must_use = "dropping a Subscription immediately unsubscribes its handler"]
+pub struct Subscription<'a> {
+    bus: &'a EventBus,
+    id: SubscriptionId,
+}
+
+impl Subscription<'_> {
+    pub fn id(&self) -> SubscriptionId {
+        self.id
+    }
+
+    /// Keep the handler subscribed for the life of the bus, returning the id
+    /// for a later explicit [`EventBus::unsubscribe`]
+    pub fn detach(self) -> SubscriptionId {
+        let id = self.id;
+        std::mem::forget(self);
+        id
+    }
+}
+
+impl Drop for Subscription<'_> {
+    fn drop(&mut self) {
+        self.bus.unsubscribe(self.id);
+    }
+}
+
+/// How long [`EventBus::request`] waits for a responder
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub type Responder = Arc<dyn Fn(&EventData) -> AppResult<serde_json::Value> + Send + Sync>;
+
+#[derive(Default)]
+struct ReplyState {
+    result: Option<AppResult<serde_json::Value>>,
+    waker: Option<Waker>,
+}
+
+/// A request awaiting its reply; the first result set wins
+struct ReplySlot {
+    id: String,
+    topic: String,
+    timeout: Duration,
+    state: Mutex<ReplyState>,
+    ready: Condvar,
+}
+
+impl ReplySlot {
+    fn complete(&self, result: AppResult<serde_json::Value>) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if state.result.is_some() {
+            return;
+        }
+        state.result = Some(result);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+        drop(state);
+        self.ready.notify_all();
+    }
+
+    fn timed_out(&self) -> AppError {
+        AppError::EventBus(
+            ErrorValue::new(
+                ErrorCode::Timeout,
+                format!("No reply to '{}' within {}ms", self.topic, self.timeout.as_millis()),
+            )
+            .with_context("topic", self.topic.as_str())
+            .with_context("request_id", self.id.as_str()),
+        )
+    }
+}
+
+/// Requests awaiting a reply, by correlation id
+type ReplyTable = Mutex<HashMap<String, Arc<ReplySlot>>>;
+
+/// The pending reply to an [`EventBus::request`]. Await it, or block on
+/// [`wait`](Self::wait) outside of event handlers.
+#[must_use = "a request's reply is only observed through its Reply"]
+pub struct Reply {
+    slot: Arc<ReplySlot>,
+}
+
+impl Reply {
+    fn ready(topic: &str, result: AppResult<serde_json::Value>) -> Self {
+        let slot = Arc::new(ReplySlot {
+            id: String::new(),
+            topic: topic.to_string(),
+            timeout: Duration::ZERO,
+            state: Mutex::new(ReplyState::default()),
+            ready: Condvar::new(),
+        });
+        slot.complete(result);
+        Self { slot }
+    }
+
+    /// Correlation id the request was published with
+    pub fn id(&self) -> &str {
+        &self.slot.id
+    }
+
+    /// Block until the responder answers or the request times out
+    pub fn wait(self) -> AppResult<serde_json::Value> {
+        let lock_error = || {
+            AppError::LockPoisoned(
+                ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire reply lock")
+                    .with_context("topic", self.slot.topic.as_str()),
+            )
+        };
+        let state = self.slot.state.lock().map_err(|_| lock_error())?;
+        let mut state = self
+            .slot
+            .ready
+            .wait_while(state, |state| state.result.is_none())
+            .map_err(|_| lock_error())?;
+        state.result.take().unwrap_or_else(|| Err(lock_error()))
+    }
+}
+
+impl Future for Reply {
+    type Output = AppResult<serde_json::Value>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Ok(mut state) = self.slot.state.lock() else {
+            return Poll::Ready(Err(self.slot.timed_out()));
+        };
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Order in which an event's handlers run; lower runs first
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EventPriority {
+    /// Error reporting, state persistence
+    Critical = 0,
+    High = 1,
+    #[default]
+    Normal = 2,
+    /// Best-effort work such as UI notifications
+    Low = 3,
+}
+
+/// What happens to a new event when a subscriber's queue is full
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowStrategy {
+    /// Wait for the subscriber to catch up (never on the dispatcher thread,
+    /// where it falls back to dropping the oldest event)
+    Block,
+    /// Discard the oldest queued event
+    #[default]
+    DropOldest,
+    /// Replace the newest queued event, so the subscriber still sees the
+    /// latest state (e.g. sysinfo snapshots)
+    Coalesce,
+}
+
+impl OverflowStrategy {
+    /// Parse "block", "drop_oldest" or "coalesce"
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "block" => Some(Self::Block),
+            "drop_oldest" | "drop-oldest" => Some(Self::DropOldest),
+            "coalesce" => Some(Self::Coalesce),
+            _ => None,
+        }
+    }
+}
+
+/// Queue limits applied to subscriptions that don't set their own
+#[derive(Debug, Clone)]
+pub struct EventBusConfig {
+    pub queue_capacity: usize,
+    pub default_overflow: OverflowStrategy,
+    /// Event type -> overflow strategy
+    pub overflow: HashMap<String, OverflowStrategy>,
+}
+
+impl Default for EventBusConfig {
+    fn default() -> Self {
+        Self {
+            queue_capacity: 1024,
+            default_overflow: OverflowStrategy::DropOldest,
+            overflow: HashMap::new(),
+        }
+    }
+}
+
+impl EventBusConfig {
+    pub fn from_app_config(config: &AppConfig) -> Self {
+        let parse = |event_type: &str, name: &str| {
+            OverflowStrategy::from_name(name).or_else(|| {
+                warn!(
+                    "Unknown overflow strategy '{}' for '{}', using drop_oldest",
+                    name, event_type
+                );
+                None
+            })
+        };
+
+        let mut overflow = HashMap::new();
+        for (event_type, name) in config.get_event_overflow_strategies() {
+            if let Some(strategy) = parse(&event_type, &name) {
+                overflow.insert(event_type, strategy);
+            }
+        }
+
+        Self {
+            queue_capacity: config.get_event_queue_capacity(),
+            default_overflow: parse("*", &config.get_event_overflow())
+                .unwrap_or(OverflowStrategy::DropOldest),
+            overflow,
+        }
+    }
+}
+
+/// Per-subscription settings; unset queue limits come from the bus config
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SubscriptionOptions {
+    pub priority: EventPriority,
+    pub capacity: Option<usize>,
+    pub overflow: Option<OverflowStrategy>,
+}
+
+impl SubscriptionOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn priority(mut self, priority: EventPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity.max(1));
+        self
+    }
+
+    pub fn overflow(mut self, overflow: OverflowStrategy) -> Self {
+        self.overflow = Some(overflow);
+        self
+    }
+}
+
+struct Listener {
+    id: SubscriptionId,
+    priority: EventPriority,
+    handler: EventHandler,
+    /// Pending events tagged with their publish sequence number
+    queue: VecDeque<(u64, EventData)>,
+    capacity: usize,
+    overflow: OverflowStrategy,
+    dropped: u64,
+}
+
+impl Listener {
+    /// Dispatch order: publish order, then priority, then subscription order
+    fn next_key(&self) -> Option<(u64, EventPriority, u64)> {
+        self.queue
+            .front()
+            .map(|(sequence, _)| (*sequence, self.priority, self.id.0))
+    }
+}
+
+struct DispatchState {
+    /// Per event type, sorted by priority and then subscription order
+    listeners: HashMap<String, Vec<Listener>>,
+    config: EventBusConfig,
+    next_sequence: u64,
+    metrics: HashMap<String, TopicMetrics>,
+    /// A handler is running on the dispatcher thread
+    busy: bool,
+    closed: bool,
+}
+
+impl DispatchState {
+    fn listener_mut(&mut self, event_type: &str, id: SubscriptionId) -> Option<&mut Listener> {
+        self.listeners
+            .get_mut(event_type)?
+            .iter_mut()
+            .find(|l| l.id == id)
+    }
+
+    fn is_idle(&self) -> bool {
+        !self.busy
+            && self
+                .listeners
+                .values()
+                .flatten()
+                .all(|l| l.queue.is_empty())
+    }
+
+    /// Pop the next event to handle, with the handler to run it
+    fn take_next(&mut self) -> Option<(EventHandler, EventData)> {
+        let listener = self
+            .listeners
+            .values_mut()
+            .flatten()
+            .filter(|l| !l.queue.is_empty())
+            .min_by_key(|l| l.next_key())?;
+        let (_, event) = listener.queue.pop_front()?;
+        Some((listener.handler.clone(), event))
+    }
+}
+
+struct Dispatcher {
+    state: Mutex<DispatchState>,
+    /// Signalled on new events, freed queue space and idleness
+    changed: Condvar,
+    thread: Mutex<Option<ThreadId>>,
+}
+
+impl Dispatcher {
+    fn lock(&self) -> Option<MutexGuard<'_, DispatchState>> {
+        self.state.lock().ok()
+    }
+
+    fn on_dispatcher_thread(&self) -> bool {
+        self.thread
+            .lock()
+            .map(|id| *id == Some(thread::current().id()))
+            .unwrap_or(false)
+    }
+
+    /// Runs until the bus is dropped
+    fn run(&self) {
+        if let Ok(mut id) = self.thread.lock() {
+            *id = Some(thread::current().id());
+        }
+
+        let Some(mut state) = self.lock() else {
+            return;
+        };
+        loop {
+            let Some((handler, event)) = state.take_next() else {
+                state.busy = false;
+                self.changed.notify_all();
+                if state.closed {
+                    return;
+                }
+                state = match self.changed.wait(state) {
+                    Ok(state) => state,
+                    Err(_) => return,
+                };
+                continue;
+            };
+
+            // Handlers run outside the lock so they may publish, subscribe
+            // or unsubscribe
+            state.busy = true;
+            drop(state);
+            self.changed.notify_all();
+            let started = Instant::now();
+            let correlation = log_context::scope(event.correlation_id.clone());
+            let panicked = panic::catch_unwind(AssertUnwindSafe(|| handler(&event))).is_err();
+            drop(correlation);
+            let micros = started.elapsed().as_micros() as u64;
+            if panicked {
+                warn!("Event handler for '{}' panicked", event.event_type);
+            }
+
+            state = match self.lock() {
+                Some(state) => state,
+                None => return,
+            };
+            let metrics = state.metrics.entry(event.event_type).or_default();
+            metrics.handled += 1;
+            metrics.failed += panicked as u64;
+            metrics.handler_micros += micros;
+            metrics.max_handler_micros = metrics.max_handler_micros.max(micros);
+        }
+    }
+}
+
+/// Publishing state shared by the bus and its scheduler thread
+struct BusCore {
     history: Mutex<Vec<EventData>>,
     max_history: usize,
+    dispatcher: Arc<Dispatcher>,
+    middleware: Mutex<Vec<Arc<dyn EventMiddleware>>>,
+    filtered: AtomicU64,
+    started: Instant,
 }
 
-impl EventBus {
-    pub fn new(max_history: usize) -> Self {
-        Self {
-            history: Mutex::new(Vec::new()),
-            max_history,
+impl BusCore {
+    fn apply_middleware(&self, mut event: EventData) -> Option<EventData> {
+        let chain: Vec<Arc<dyn EventMiddleware>> = self
+            .middleware
+            .lock()
+            .map(|chain| chain.clone())
+            .unwrap_or_default();
+        for middleware in chain {
+            event = middleware.process(event)?;
         }
+        Some(event)
     }
 
-    pub fn emit(&self, event_type: &str, payload: serde_json::Value) {
-        let event = EventData::new(event_type, payload);
+    fn publish(&self, event: EventData) {
+        let Some(event) = self.apply_middleware(event) else {
+            self.filtered.fetch_add(1, Ordering::Relaxed);
+            return;
+        };
+        self.enqueue(&event);
         let _ = self.store_event(event);
     }
 
-    pub fn emit_with_source(&self, event_type: &str, payload: serde_json::Value, source: &str) {
-        let event = EventData::new(event_type, payload).with_source(source);
-        let _ = self.store_event(event);
+    /// Queue `event` for each of its subscribers, applying their overflow
+    /// strategies
+    fn enqueue(&self, event: &EventData) {
+        let dispatcher = &self.dispatcher;
+        let Some(mut state) = dispatcher.lock() else {
+            return;
+        };
+        let sequence = state.next_sequence;
+        state.next_sequence += 1;
+
+        let ids: Vec<SubscriptionId> = state
+            .listeners
+            .get(&event.event_type)
+            .map(|handlers| handlers.iter().map(|l| l.id).collect())
+            .unwrap_or_default();
+        let may_block = !dispatcher.on_dispatcher_thread();
+        let mut dropped = 0;
+
+        for id in ids {
+            loop {
+                let closed = state.closed;
+                let Some(listener) = state.listener_mut(&event.event_type, id) else {
+                    break;
+                };
+                if listener.queue.len() < listener.capacity {
+                    listener.queue.push_back((sequence, event.clone()));
+                    break;
+                }
+                match listener.overflow {
+                    OverflowStrategy::Block if may_block && !closed => {
+                        state = match dispatcher.changed.wait(state) {
+                            Ok(state) => state,
+                            Err(_) => return,
+                        };
+                    }
+                    OverflowStrategy::Coalesce => {
+                        if let Some(newest) = listener.queue.back_mut() {
+                            *newest = (sequence, event.clone());
+                        }
+                        listener.dropped += 1;
+                        dropped += 1;
+                        break;
+                    }
+                    OverflowStrategy::Block | OverflowStrategy::DropOldest => {
+                        listener.queue.pop_front();
+                        listener.queue.push_back((sequence, event.clone()));
+                        listener.dropped += 1;
+                        dropped += 1;
+                        if listener.dropped.is_power_of_two() {
+                            warn!(
+                                "Subscriber {:?} of '{}' is falling behind, {} events dropped",
+                                id, event.event_type, listener.dropped
+                            );
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+
+        let metrics = state.metrics.entry(event.event_type.clone()).or_default();
+        metrics.published += 1;
+        metrics.dropped += dropped;
+        drop(state);
+        dispatcher.changed.notify_all();
     }
 
     fn store_event(&self, event: EventData) -> AppResult<()> {
@@ -89,6 +605,339 @@ impl EventBus {
         }
         Ok(())
     }
+}
+
+/// Publishes events to subscribers on a dedicated dispatcher thread, so a
+/// slow handler never blocks the publisher (usually the WebUI thread).
+///
+/// Each subscriber has a bounded queue whose overflow strategy decides what
+/// happens when it falls behind. Events are handled in publish order, and an
+/// event's handlers in priority order.
+///
+/// The queues share one lock and condvar instead of being channels: dropping
+/// or coalescing queued events, keeping publish order across subscribers and
+/// [`flush`](Self::flush) all need to see and edit every queue, which a
+/// channel's sender can't.
+pub struct EventBus {
+    core: Arc<BusCore>,
+    next_subscription: AtomicU64,
+    scheduler: Arc<Scheduler<EventData>>,
+    replies: Arc<ReplyTable>,
+    reply_timeouts: Arc<Scheduler<Arc<ReplySlot>>>,
+}
+
+impl EventBus {
+    pub fn new(max_history: usize) -> Self {
+        let dispatcher = Arc::new(Dispatcher {
+            state: Mutex::new(DispatchState {
+                listeners: HashMap::new(),
+                config: EventBusConfig::default(),
+                next_sequence: 0,
+                metrics: HashMap::new(),
+                busy: false,
+                closed: false,
+            }),
+            changed: Condvar::new(),
+            thread: Mutex::new(None),
+        });
+
+        let worker = dispatcher.clone();
+        if let Err(e) = thread::Builder::new()
+            .name("event-bus".to_string())
+            .spawn(move || worker.run())
+        {
+            error!("Failed to start event bus dispatcher: {}", e);
+        }
+
+        let core = Arc::new(BusCore {
+            history: Mutex::new(Vec::new()),
+            max_history,
+            dispatcher,
+            middleware: Mutex::new(Vec::new()),
+            filtered: AtomicU64::new(0),
+            started: Instant::now(),
+        });
+
+        let scheduled = core.clone();
+        let scheduler = Scheduler::new(move |mut event: EventData| {
+            event.timestamp = Utc::now().timestamp_millis();
+            scheduled.publish(event);
+        });
+
+        let replies: Arc<ReplyTable> = Arc::new(Mutex::new(HashMap::new()));
+        let pending = replies.clone();
+        let reply_timeouts = Scheduler::new(move |slot: Arc<ReplySlot>| {
+            if let Ok(mut pending) = pending.lock() {
+                pending.remove(&slot.id);
+            }
+            slot.complete(Err(slot.timed_out()));
+        });
+
+        Self {
+            core,
+            next_subscription: AtomicU64::new(1),
+            scheduler,
+            replies,
+            reply_timeouts,
+        }
+    }
+
+    /// Set the queue limits used by subscriptions made from now on
+    pub fn configure(&self, config: EventBusConfig) {
+        if let Some(mut state) = self.core.dispatcher.lock() {
+            state.config = config;
+        }
+    }
+
+    pub fn emit(&self, event_type: &str, payload: serde_json::Value) {
+        self.core.publish(EventData::new(event_type, payload));
+    }
+
+    pub fn emit_with_source(&self, event_type: &str, payload: serde_json::Value, source: &str) {
+        self.core.publish(EventData::new(event_type, payload).with_source(source));
+    }
+
+    /// Publish `event` once `delay` has passed. Cancel it with
+    /// [`cancel_scheduled`](Self::cancel_scheduled), e.g. to debounce.
+    pub fn publish_after(&self, delay: Duration, event: EventData) -> ScheduledId {
+        self.scheduler.schedule(delay, event)
+    }
+
+    /// Publish `event` at `at`; times in the past publish on the next tick
+    pub fn publish_at(&self, at: DateTime<Utc>, event: EventData) -> ScheduledId {
+        let delay = (at - Utc::now()).to_std().unwrap_or_default();
+        self.scheduler.schedule(delay, event)
+    }
+
+    /// Cancel a scheduled event; returns false if it was already published
+    pub fn cancel_scheduled(&self, id: ScheduledId) -> bool {
+        self.scheduler.cancel(id)
+    }
+
+    /// Events scheduled but not yet published
+    pub fn scheduled_count(&self) -> usize {
+        self.scheduler.pending()
+    }
+
+    /// Ask the responder of `topic` for a reply, waiting at most
+    /// [`DEFAULT_REQUEST_TIMEOUT`]
+    pub fn request(&self, topic: &str, payload: serde_json::Value) -> Reply {
+        self.request_with_timeout(topic, payload, DEFAULT_REQUEST_TIMEOUT)
+    }
+
+    /// Publish `payload` on `topic` as a request and return its pending
+    /// reply. Fails immediately when nothing responds to `topic`.
+    pub fn request_with_timeout(
+        &self,
+        topic: &str,
+        payload: serde_json::Value,
+        timeout: Duration,
+    ) -> Reply {
+        if self.listener_count(topic) == 0 {
+            return Reply::ready(
+                topic,
+                Err(AppError::NotFound(
+                    ErrorValue::new(ErrorCode::ResourceNotFound, format!("No responder for '{}'", topic))
+                        .with_context("topic", topic),
+                )),
+            );
+        }
+
+        let slot = Arc::new(ReplySlot {
+            id: CorrelationMiddleware::generate_id(),
+            topic: topic.to_string(),
+            timeout,
+            state: Mutex::new(ReplyState::default()),
+            ready: Condvar::new(),
+        });
+        if let Ok(mut pending) = self.replies.lock() {
+            pending.insert(slot.id.clone(), slot.clone());
+        }
+        self.reply_timeouts.schedule(timeout, slot.clone());
+        self.core
+            .publish(EventData::new(topic, payload).with_correlation_id(slot.id.as_str()));
+        Reply { slot }
+    }
+
+    /// Answer requests made on `topic`. The first responder to a request
+    /// replies; events published on `topic` without a pending request are
+    /// ignored.
+    pub fn respond_to<F>(&self, topic: &str, handler: F) -> Subscription<'_>
+    where
+        F: Fn(&EventData) -> AppResult<serde_json::Value> + Send + Sync + 'static,
+    {
+        let replies = self.replies.clone();
+        let handler: Responder = Arc::new(handler);
+        self.subscribe(topic, move |event| {
+            let Some(id) = event.correlation_id.as_deref() else {
+                return;
+            };
+            let slot = replies.lock().ok().and_then(|mut pending| pending.remove(id));
+            if let Some(slot) = slot {
+                // A panicking responder leaves the request to time out
+                slot.complete(handler(event));
+            }
+        })
+    }
+
+    /// Publish a typed event under its topic
+    pub fn publish_typed<T: EventTopic + Serialize>(&self, event: &T) -> AppResult<()> {
+        self.core.publish(EventData::new(T::TOPIC, Self::typed_payload(event)?));
+        Ok(())
+    }
+
+    pub fn publish_typed_with_source<T: EventTopic + Serialize>(
+        &self,
+        event: &T,
+        source: &str,
+    ) -> AppResult<()> {
+        self.core.publish(EventData::new(T::TOPIC, Self::typed_payload(event)?).with_source(source));
+        Ok(())
+    }
+
+    fn typed_payload<T: EventTopic + Serialize>(event: &T) -> AppResult<serde_json::Value> {
+        serde_json::to_value(event).map_err(|e| {
+            AppError::Serialization(
+                ErrorValue::new(ErrorCode::SerializationFailed, "Failed to serialize event payload")
+                    .with_cause(e.to_string())
+                    .with_context("topic", T::TOPIC),
+            )
+        })
+    }
+
+    /// Append `middleware` to the chain every published event goes through
+    pub fn use_middleware(&self, middleware: impl EventMiddleware + 'static) {
+        if let Ok(mut chain) = self.core.middleware.lock() {
+            chain.push(Arc::new(middleware));
+        }
+    }
+
+    /// Names of the registered middleware, in the order they run
+    pub fn middleware_names(&self) -> Vec<String> {
+        self.core.middleware
+            .lock()
+            .map(|chain| chain.iter().map(|m| m.name().to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Block until every event published so far has been handled. Returns
+    /// immediately when called from a handler.
+    pub fn flush(&self) {
+        if self.core.dispatcher.on_dispatcher_thread() {
+            return;
+        }
+        let Some(state) = self.core.dispatcher.lock() else {
+            return;
+        };
+        let idle = self
+            .core
+            .dispatcher
+            .changed
+            .wait_while(state, |state| !state.closed && !state.is_idle());
+        drop(idle);
+    }
+
+    /// Call `handler` on the dispatcher thread for every `event_type` event
+    /// until the returned subscription is dropped
+    pub fn subscribe<F>(&self, event_type: &str, handler: F) -> Subscription<'_>
+    where
+        F: Fn(&EventData) + Send + Sync + 'static,
+    {
+        self.subscribe_with(event_type, SubscriptionOptions::default(), handler)
+    }
+
+    /// Like [`subscribe`](Self::subscribe), running `handler` before every
+    /// lower-priority handler; equal priorities run in subscription order
+    pub fn subscribe_with_priority<F>(
+        &self,
+        event_type: &str,
+        priority: EventPriority,
+        handler: F,
+    ) -> Subscription<'_>
+    where
+        F: Fn(&EventData) + Send + Sync + 'static,
+    {
+        self.subscribe_with(event_type, SubscriptionOptions::new().priority(priority), handler)
+    }
+
+    /// Call `handler` with the deserialized payload of every `T::TOPIC`
+    /// event. Payloads that don't match `T` are logged and skipped.
+    pub fn subscribe_typed<T, F>(&self, handler: F) -> Subscription<'_>
+    where
+        T: EventTopic + DeserializeOwned + 'static,
+        F: Fn(T) + Send + Sync + 'static,
+    {
+        self.subscribe(T::TOPIC, move |event| {
+            match T::deserialize(&event.payload) {
+                Ok(payload) => handler(payload),
+                Err(e) => warn!(
+                    "Ignoring '{}' event with mismatched payload for {}: {}",
+                    T::TOPIC,
+                    std::any::type_name::<T>(),
+                    e
+                ),
+            }
+        })
+    }
+
+    /// Subscribe with an explicit priority and queue limits
+    pub fn subscribe_with<F>(
+        &self,
+        event_type: &str,
+        options: SubscriptionOptions,
+        handler: F,
+    ) -> Subscription<'_>
+    where
+        F: Fn(&EventData) + Send + Sync + 'static,
+    {
+        let id = SubscriptionId(self.next_subscription.fetch_add(1, Ordering::Relaxed));
+        if let Some(mut state) = self.core.dispatcher.lock() {
+            let capacity = options.capacity.unwrap_or(state.config.queue_capacity).max(1);
+            let overflow = options.overflow.unwrap_or_else(|| {
+                state
+                    .config
+                    .overflow
+                    .get(event_type)
+                    .copied()
+                    .unwrap_or(state.config.default_overflow)
+            });
+
+            let handlers = state.listeners.entry(event_type.to_string()).or_default();
+            let position = handlers.partition_point(|l| l.priority <= options.priority);
+            handlers.insert(
+                position,
+                Listener {
+                    id,
+                    priority: options.priority,
+                    handler: Arc::new(handler),
+                    queue: VecDeque::new(),
+                    capacity,
+                    overflow,
+                    dropped: 0,
+                },
+            );
+        }
+        Subscription { bus: self, id }
+    }
+
+    /// Remove a handler and its pending events; returns false if it was
+    /// already removed
+    pub fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        let Some(mut state) = self.core.dispatcher.lock() else {
+            return false;
+        };
+        let mut removed = false;
+        state.listeners.retain(|_, handlers| {
+            let before = handlers.len();
+            handlers.retain(|l| l.id != id);
+            removed |= handlers.len() != before;
+            !handlers.is_empty()
+        });
+        drop(state);
+        // Wake publishers blocked on the removed queue
+        self.core.dispatcher.changed.notify_all();
+        removed
+    }
 
     pub fn get_history(
         &self,
@@ -96,6 +945,7 @@ impl EventBus {
         limit: Option<usize>,
     ) -> AppResult<Vec<EventData>> {
         let history = self
+            .core
             .history
             .lock()
             .map_err(|e| {
@@ -123,6 +973,7 @@ impl EventBus {
 
     pub fn clear_history(&self) -> AppResult<()> {
         let mut history = self
+            .core
             .history
             .lock()
             .map_err(|e| {
@@ -136,22 +987,96 @@ impl EventBus {
         Ok(())
     }
 
-    pub fn listener_count(&self, _event_type: &str) -> usize {
-        0
+    pub fn listener_count(&self, event_type: &str) -> usize {
+        self.core.dispatcher
+            .lock()
+            .map(|state| state.listeners.get(event_type).map_or(0, Vec::len))
+            .unwrap_or(0)
     }
 
     pub fn total_listeners(&self) -> usize {
-        0
+        self.core.dispatcher
+            .lock()
+            .map(|state| state.listeners.values().map(Vec::len).sum())
+            .unwrap_or(0)
     }
 
     pub fn get_stats(&self) -> EventBusStats {
+        let mut event_types: Vec<EventTypeInfo> = self
+            .core
+            .dispatcher
+            .lock()
+            .map(|state| {
+                let mut topics: Vec<&String> =
+                    state.listeners.keys().chain(state.metrics.keys()).collect();
+                topics.sort();
+                topics.dedup();
+                topics
+                    .into_iter()
+                    .map(|event_type| {
+                        let handlers = state.listeners.get(event_type);
+                        let metrics = state.metrics.get(event_type).cloned().unwrap_or_default();
+                        let avg_micros = if metrics.handled > 0 {
+                            metrics.handler_micros as f64 / metrics.handled as f64
+                        } else {
+                            0.0
+                        };
+                        EventTypeInfo {
+                            event_type: event_type.clone(),
+                            listener_count: handlers.map_or(0, Vec::len),
+                            pending: handlers.map_or(0, |h| h.iter().map(|l| l.queue.len()).sum()),
+                            dropped: metrics.dropped,
+                            published: metrics.published,
+                            handled: metrics.handled,
+                            failed: metrics.failed,
+                            avg_handler_ms: avg_micros / 1000.0,
+                            max_handler_ms: metrics.max_handler_micros as f64 / 1000.0,
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        event_types.sort_by(|a, b| a.event_type.cmp(&b.event_type));
+
+        let total_published: u64 = event_types.iter().map(|info| info.published).sum();
+        let elapsed = self.core.started.elapsed().as_secs_f64();
         EventBusStats {
-            total_listeners: 0,
-            event_types: vec![],
+            total_listeners: event_types.iter().map(|info| info.listener_count).sum(),
+            total_published,
+            total_dropped: event_types.iter().map(|info| info.dropped).sum(),
+            total_filtered: self.core.filtered.load(Ordering::Relaxed),
+            events_per_second: if elapsed > 0.0 {
+                total_published as f64 / elapsed
+            } else {
+                0.0
+            },
+            event_types,
         }
     }
 }
 
+impl Drop for EventBus {
+    fn drop(&mut self) {
+        self.scheduler.close();
+        self.reply_timeouts.close();
+        let pending: Vec<Arc<ReplySlot>> = self
+            .replies
+            .lock()
+            .map(|mut pending| pending.drain().map(|(_, slot)| slot).collect())
+            .unwrap_or_default();
+        for slot in pending {
+            slot.complete(Err(AppError::EventBus(
+                ErrorValue::new(ErrorCode::InternalError, "Event bus shut down before the reply")
+                    .with_context("topic", slot.topic.as_str()),
+            )));
+        }
+        if let Some(mut state) = self.core.dispatcher.lock() {
+            state.closed = true;
+        }
+        self.core.dispatcher.changed.notify_all();
+    }
+}
+
 impl Default for EventBus {
     fn default() -> Self {
         Self::new(100)
@@ -168,3 +1093,381 @@ macro_rules! event_publish {
         $crate::core::infrastructure::event_bus::GLOBAL_EVENT_BUS.emit($event_type, $payload)
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    /// Subscribe a handler that waits for a release per event and records
+    /// the `n` payload field
+    fn gated<'a>(
+        bus: &'a EventBus,
+        options: SubscriptionOptions,
+    ) -> (Subscription<'a>, mpsc::Sender<()>, Arc<Mutex<Vec<i64>>>) {
+        let (release, gate) = mpsc::channel::<()>();
+        let gate = Mutex::new(gate);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let order = seen.clone();
+        let subscription = bus.subscribe_with("job", options, move |event| {
+            let _ = gate.lock().unwrap().recv();
+            order.lock().unwrap().push(event.payload["n"].as_i64().unwrap());
+        });
+        (subscription, release, seen)
+    }
+
+    fn release_all(bus: &EventBus, release: &mpsc::Sender<()>) {
+        for _ in 0..16 {
+            let _ = release.send(());
+        }
+        bus.flush();
+    }
+
+    #[test]
+    fn test_subscription_drop_unsubscribes() {
+        let bus = EventBus::new(10);
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let counter = calls.clone();
+        let subscription = bus.subscribe("user.created", move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        assert_eq!(bus.listener_count("user.created"), 1);
+
+        bus.emit("user.created", serde_json::json!({ "id": 1 }));
+        bus.emit("user.deleted", serde_json::json!({ "id": 1 }));
+        bus.flush();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        drop(subscription);
+        bus.emit("user.created", serde_json::json!({ "id": 2 }));
+        bus.flush();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(bus.total_listeners(), 0);
+        let stats = bus.get_stats();
+        assert!(stats.event_types.iter().all(|info| info.listener_count == 0));
+        assert_eq!(stats.total_published, 3);
+    }
+
+    #[test]
+    fn test_detached_subscription_and_unsubscribe() {
+        let bus = EventBus::new(10);
+        let id = bus.subscribe("tick", |_| {}).detach();
+        let _other = bus.subscribe("tick", |_| {});
+
+        let stats = bus.get_stats();
+        assert_eq!(stats.total_listeners, 2);
+        assert_eq!(stats.event_types[0].listener_count, 2);
+
+        assert!(bus.unsubscribe(id));
+        assert!(!bus.unsubscribe(id));
+        assert_eq!(bus.listener_count("tick"), 1);
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Saved {
+        id: i64,
+    }
+
+    impl EventTopic for Saved {
+        const TOPIC: &'static str = "saved";
+    }
+
+    #[test]
+    fn test_typed_events() {
+        let bus = EventBus::new(10);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let received = seen.clone();
+        let _subscription = bus.subscribe_typed(move |event: Saved| {
+            received.lock().unwrap().push(event);
+        });
+
+        bus.publish_typed_with_source(&Saved { id: 3 }, "db").unwrap();
+        // Mismatched payloads are skipped rather than delivered half-parsed
+        bus.emit("saved", serde_json::json!({ "id": "three" }));
+        bus.flush();
+
+        assert_eq!(*seen.lock().unwrap(), vec![Saved { id: 3 }]);
+        let history = bus.get_history(Some("saved"), None).unwrap();
+        assert_eq!(history[0].source.as_deref(), Some("db"));
+    }
+
+    #[test]
+    fn test_middleware_chain() {
+        use crate::core::infrastructure::event_middleware::{
+            CorrelationMiddleware, FilterMiddleware, RedactionMiddleware, REDACTED,
+        };
+
+        let bus = EventBus::new(10);
+        bus.use_middleware(FilterMiddleware::new("no-heartbeats", |e: &EventData| {
+            e.event_type != "heartbeat"
+        }));
+        bus.use_middleware(RedactionMiddleware::default());
+        bus.use_middleware(CorrelationMiddleware);
+        assert_eq!(bus.middleware_names(), vec!["no-heartbeats", "redaction", "correlation"]);
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let received = seen.clone();
+        let _login = bus.subscribe("login", move |event| {
+            received.lock().unwrap().push(event.clone());
+        });
+        let beats = Arc::new(AtomicUsize::new(0));
+        let counter = beats.clone();
+        let _heartbeat = bus.subscribe("heartbeat", move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        bus.emit("login", serde_json::json!({ "user": "ada", "token": "abc" }));
+        bus.emit("heartbeat", serde_json::Value::Null);
+        bus.flush();
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen[0].payload["token"], REDACTED);
+        assert!(seen[0].correlation_id.is_some());
+        assert_eq!(beats.load(Ordering::SeqCst), 0);
+        assert!(bus.get_history(Some("heartbeat"), None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_publish_after_debounce() {
+        let bus = EventBus::new(10);
+        let saves = Arc::new(AtomicUsize::new(0));
+        let counter = saves.clone();
+        let _subscription = bus.subscribe("autosave", move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        // Each keystroke pushes the auto-save back
+        let mut pending = None;
+        for _ in 0..3 {
+            if let Some(id) = pending.take() {
+                assert!(bus.cancel_scheduled(id));
+            }
+            pending = Some(bus.publish_after(
+                Duration::from_millis(40),
+                EventData::new("autosave", serde_json::Value::Null),
+            ));
+        }
+        bus.publish_at(
+            Utc::now() - chrono::Duration::seconds(1),
+            EventData::new("overdue", serde_json::Value::Null),
+        );
+        assert_eq!(bus.scheduled_count(), 2);
+        assert!(bus.get_history(None, None).unwrap().is_empty());
+
+        let started = Instant::now();
+        while bus.scheduled_count() > 0 && started.elapsed() < Duration::from_secs(2) {
+            thread::sleep(Duration::from_millis(10));
+        }
+        bus.flush();
+        assert_eq!(saves.load(Ordering::SeqCst), 1);
+        assert_eq!(bus.get_history(None, None).unwrap()[0].event_type, "overdue");
+    }
+
+    #[test]
+    fn test_request_reply() {
+        let bus = EventBus::new(10);
+        let _responder = bus.respond_to("user.count", |event| {
+            Ok(serde_json::json!({ "count": event.payload["n"].as_i64().unwrap_or(0) * 2 }))
+        });
+        // Plain events on the topic reach the responder without a request
+        bus.emit("user.count", serde_json::json!({ "n": 1 }));
+
+        let reply = bus.request("user.count", serde_json::json!({ "n": 21 }));
+        assert_eq!(reply.id().len(), 16);
+        assert_eq!(reply.wait().unwrap()["count"], 42);
+
+        let error = bus.request("nobody.home", serde_json::Value::Null).wait().unwrap_err();
+        assert_eq!(error.to_value().code, ErrorCode::ResourceNotFound);
+    }
+
+    #[test]
+    fn test_request_timeout() {
+        let bus = EventBus::new(10);
+        let (release, gate) = mpsc::channel::<()>();
+        let gate = Mutex::new(gate);
+        let _responder = bus.respond_to("slow", move |_| {
+            let _ = gate.lock().unwrap().recv();
+            Ok(serde_json::Value::Null)
+        });
+
+        let started = Instant::now();
+        let error = bus
+            .request_with_timeout("slow", serde_json::Value::Null, Duration::from_millis(30))
+            .wait()
+            .unwrap_err();
+        assert!(started.elapsed() >= Duration::from_millis(30));
+        assert_eq!(error.to_value().code, ErrorCode::Timeout);
+        assert_eq!(error.to_value().context.as_ref().unwrap()["topic"], "slow");
+        release.send(()).unwrap();
+    }
+
+    #[test]
+    fn test_priority_order() {
+        let bus = EventBus::new(10);
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let record = |name: &'static str| {
+            let order = order.clone();
+            move |_: &EventData| order.lock().unwrap().push(name)
+        };
+
+        let _toast = bus.subscribe_with_priority("saved", EventPriority::Low, record("toast"));
+        let _audit = bus.subscribe("saved", record("audit"));
+        let _persist = bus.subscribe_with_priority("saved", EventPriority::Critical, record("persist"));
+        let _report = bus.subscribe_with_priority("saved", EventPriority::Critical, record("report"));
+        let _badge = bus.subscribe_with_priority("saved", EventPriority::Low, record("badge"));
+
+        bus.emit("saved", serde_json::Value::Null);
+        bus.flush();
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec!["persist", "report", "audit", "toast", "badge"]
+        );
+    }
+
+    #[test]
+    fn test_slow_handler_does_not_block_publisher() {
+        let bus = EventBus::new(10);
+        let (_subscription, release, seen) = gated(&bus, SubscriptionOptions::new());
+
+        // Returns while the handler is still blocked on the gate
+        for n in 0..3 {
+            bus.emit("job", serde_json::json!({ "n": n }));
+        }
+        assert_eq!(bus.get_history(Some("job"), None).unwrap().len(), 3);
+        assert!(seen.lock().unwrap().is_empty());
+
+        release_all(&bus, &release);
+        assert_eq!(*seen.lock().unwrap(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_overflow_drop_oldest() {
+        let bus = EventBus::new(10);
+        let options = SubscriptionOptions::new()
+            .capacity(2)
+            .overflow(OverflowStrategy::DropOldest);
+        let (_subscription, release, seen) = gated(&bus, options);
+
+        // 0 is taken by the blocked handler; 1..=4 compete for two slots
+        bus.emit("job", serde_json::json!({ "n": 0 }));
+        thread::sleep(Duration::from_millis(50));
+        for n in 1..=4 {
+            bus.emit("job", serde_json::json!({ "n": n }));
+        }
+        let stats = bus.get_stats();
+        assert_eq!(stats.event_types[0].pending, 2);
+        assert_eq!(stats.event_types[0].dropped, 2);
+
+        release_all(&bus, &release);
+        assert_eq!(*seen.lock().unwrap(), vec![0, 3, 4]);
+    }
+
+    #[test]
+    fn test_handler_latency_metrics() {
+        let bus = EventBus::new(10);
+        let _slow = bus.subscribe("report", |_| thread::sleep(Duration::from_millis(20)));
+
+        bus.emit("report", serde_json::Value::Null);
+        bus.emit("unheard", serde_json::Value::Null);
+        bus.flush();
+
+        let stats = bus.get_stats();
+        assert_eq!(stats.total_published, 2);
+        let report = &stats.event_types[0];
+        assert_eq!(report.event_type, "report");
+        assert!(report.avg_handler_ms >= 20.0);
+        assert!(report.max_handler_ms >= report.avg_handler_ms);
+        // Topics without subscribers still count publishes
+        assert_eq!(stats.event_types[1].published, 1);
+        assert_eq!(stats.event_types[1].listener_count, 0);
+    }
+
+    #[test]
+    fn test_overflow_coalesce() {
+        let bus = EventBus::new(10);
+        let options = SubscriptionOptions::new()
+            .capacity(1)
+            .overflow(OverflowStrategy::Coalesce);
+        let (_subscription, release, seen) = gated(&bus, options);
+
+        bus.emit("job", serde_json::json!({ "n": 0 }));
+        thread::sleep(Duration::from_millis(50));
+        for n in 1..=5 {
+            bus.emit("job", serde_json::json!({ "n": n }));
+        }
+
+        release_all(&bus, &release);
+        assert_eq!(*seen.lock().unwrap(), vec![0, 5]);
+    }
+
+    #[test]
+    fn test_overflow_block_waits_for_room() {
+        let bus = Arc::new(EventBus::new(10));
+        let options = SubscriptionOptions::new()
+            .capacity(1)
+            .overflow(OverflowStrategy::Block);
+        let (subscription, release, seen) = gated(&bus, options);
+        let id = subscription.detach();
+
+        bus.emit("job", serde_json::json!({ "n": 0 }));
+        thread::sleep(Duration::from_millis(50));
+        bus.emit("job", serde_json::json!({ "n": 1 }));
+
+        let publisher = {
+            let bus = bus.clone();
+            thread::spawn(move || bus.emit("job", serde_json::json!({ "n": 2 })))
+        };
+        thread::sleep(Duration::from_millis(50));
+        assert!(!publisher.is_finished());
+
+        release_all(&bus, &release);
+        publisher.join().unwrap();
+        release_all(&bus, &release);
+        assert_eq!(*seen.lock().unwrap(), vec![0, 1, 2]);
+        bus.unsubscribe(id);
+    }
+
+    #[test]
+    fn test_overflow_from_config() {
+        let bus = EventBus::new(10);
+        let mut config = EventBusConfig {
+            queue_capacity: 8,
+            ..EventBusConfig::default()
+        };
+        config
+            .overflow
+            .insert("sysinfo.updated".to_string(), OverflowStrategy::Coalesce);
+        bus.configure(config);
+
+        let _subscription = bus.subscribe("sysinfo.updated", |_| {});
+        let state = bus.core.dispatcher.lock().unwrap();
+        let listener = &state.listeners["sysinfo.updated"][0];
+        assert_eq!(listener.capacity, 8);
+        assert_eq!(listener.overflow, OverflowStrategy::Coalesce);
+        assert_eq!(OverflowStrategy::from_name("drop-oldest"), Some(OverflowStrategy::DropOldest));
+    }
+
+    #[test]
+    fn test_panicking_handler_keeps_dispatcher_alive() {
+        let bus = EventBus::new(10);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let _bad = bus.subscribe("boom", |_| panic!("handler failure"));
+        let counter = calls.clone();
+        let _good = bus.subscribe("boom", move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        bus.emit("boom", serde_json::Value::Null);
+        bus.emit("boom", serde_json::Value::Null);
+        bus.flush();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let stats = bus.get_stats();
+        assert_eq!(stats.event_types[0].published, 2);
+        assert_eq!(stats.event_types[0].handled, 4);
+        assert_eq!(stats.event_types[0].failed, 2);
+    }
+}
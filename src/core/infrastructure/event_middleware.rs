@@ -0,0 +1,199 @@
+// src/core/infrastructure/event_middleware.rs
+// Middleware run on every published event before it reaches subscribers
+
+#![allow(dead_code)]
+
+use log::debug;
+use std::collections::HashSet;
+
+use super::event_bus::EventData;
+use super::logging::context as log_context;
+use crate::utils::crypto::CryptoUtils;
+
+/// Placeholder written over redacted payload values
+pub const REDACTED: &str = "[REDACTED]";
+
+/// A step of the EventBus middleware chain.
+///
+/// Middleware runs on the publisher's thread in registration order. Returning
+/// `None` drops the event: later middleware, subscribers and the history
+/// never see it.
+pub trait EventMiddleware: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn process(&self, event: EventData) -> Option<EventData>;
+}
+
+/// Logs every event at debug level
+pub struct LoggingMiddleware;
+
+impl EventMiddleware for LoggingMiddleware {
+    fn name(&self) -> &str {
+        "logging"
+    }
+
+    fn process(&self, event: EventData) -> Option<EventData> {
+        debug!(
+            "[EventBus] {} from {} ({})",
+            event.event_type,
+            event.source.as_deref().unwrap_or("backend"),
+            event.correlation_id.as_deref().unwrap_or("-")
+        );
+        Some(event)
+    }
+}
+
+/// Replaces the values of sensitive keys anywhere in the payload
+pub struct RedactionMiddleware {
+    keys: HashSet<String>,
+}
+
+impl RedactionMiddleware {
+    pub fn new<I, S>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            keys: keys.into_iter().map(|k| k.as_ref().to_lowercase()).collect(),
+        }
+    }
+
+    pub fn redact(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if self.keys.contains(&key.to_lowercase()) {
+                        *value = serde_json::Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact(value);
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(|v| self.redact(v)),
+            _ => {}
+        }
+    }
+}
+
+impl Default for RedactionMiddleware {
+    fn default() -> Self {
+        Self::new(["password", "token", "secret", "authorization"])
+    }
+}
+
+impl EventMiddleware for RedactionMiddleware {
+    fn name(&self) -> &str {
+        "redaction"
+    }
+
+    fn process(&self, mut event: EventData) -> Option<EventData> {
+        self.redact(&mut event.payload);
+        Some(event)
+    }
+}
+
+/// Gives every event a correlation id so handlers and logs can follow it.
+/// Events published while servicing a frontend call take that call's id.
+pub struct CorrelationMiddleware;
+
+impl CorrelationMiddleware {
+    pub fn generate_id() -> String {
+        hex::encode(CryptoUtils::random_bytes(8))
+    }
+}
+
+impl EventMiddleware for CorrelationMiddleware {
+    fn name(&self) -> &str {
+        "correlation"
+    }
+
+    fn process(&self, event: EventData) -> Option<EventData> {
+        if event.correlation_id.is_some() {
+            return Some(event);
+        }
+        let id = log_context::current().unwrap_or_else(Self::generate_id);
+        Some(event.with_correlation_id(id))
+    }
+}
+
+/// Drops events the predicate rejects
+pub struct FilterMiddleware<F> {
+    name: String,
+    predicate: F,
+}
+
+impl<F> FilterMiddleware<F>
+where
+    F: Fn(&EventData) -> bool + Send + Sync,
+{
+    pub fn new(name: impl Into<String>, predicate: F) -> Self {
+        Self {
+            name: name.into(),
+            predicate,
+        }
+    }
+}
+
+impl<F> EventMiddleware for FilterMiddleware<F>
+where
+    F: Fn(&EventData) -> bool + Send + Sync,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(&self, event: EventData) -> Option<EventData> {
+        if (self.predicate)(&event) {
+            Some(event)
+        } else {
+            debug!("[EventBus] '{}' filtered out {}", self.name, event.event_type);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redaction_is_recursive() {
+        let event = EventData::new(
+            "auth.login",
+            serde_json::json!({
+                "user": "ada",
+                "Password": "hunter2",
+                "sessions": [{ "token": "abc", "ip": "10.0.0.1" }]
+            }),
+        );
+        let event = RedactionMiddleware::default().process(event).unwrap();
+        assert_eq!(event.payload["user"], "ada");
+        assert_eq!(event.payload["Password"], REDACTED);
+        assert_eq!(event.payload["sessions"][0]["token"], REDACTED);
+        assert_eq!(event.payload["sessions"][0]["ip"], "10.0.0.1");
+    }
+
+    #[test]
+    fn test_correlation_and_filter() {
+        let event = CorrelationMiddleware
+            .process(EventData::new("tick", serde_json::Value::Null))
+            .unwrap();
+        assert_eq!(event.correlation_id.as_ref().map(String::len), Some(16));
+
+        let kept = event.clone().with_correlation_id("fixed");
+        assert_eq!(
+            CorrelationMiddleware.process(kept).unwrap().correlation_id.as_deref(),
+            Some("fixed")
+        );
+
+        let _call = log_context::enter("c0ffee");
+        let inherited = CorrelationMiddleware
+            .process(EventData::new("tick", serde_json::Value::Null))
+            .unwrap();
+        assert_eq!(inherited.correlation_id.as_deref(), Some("c0ffee"));
+
+        let filter = FilterMiddleware::new("no-ticks", |e: &EventData| e.event_type != "tick");
+        assert!(filter.process(event).is_none());
+    }
+}
@@ -0,0 +1,273 @@
+// src/core/infrastructure/event_scheduler.rs
+// Timer wheel and scheduler thread for delayed event publishing
+
+#![allow(dead_code)]
+
+use log::error;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Scheduling resolution
+pub const TICK: Duration = Duration::from_millis(10);
+
+/// Slots per wheel rotation (2.56s at the default tick)
+const WHEEL_SLOTS: usize = 256;
+
+/// Identifies a scheduled item so it can be cancelled, e.g. to debounce
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ScheduledId(u64);
+
+struct Entry<T> {
+    id: ScheduledId,
+    deadline: u64,
+    item: T,
+}
+
+/// Hashed timer wheel: an item lands in slot `deadline % slots` and is
+/// released once the wheel has advanced to its deadline tick, however many
+/// rotations away that is.
+pub struct TimerWheel<T> {
+    slots: Vec<Vec<Entry<T>>>,
+    tick: u64,
+    len: usize,
+}
+
+impl<T> TimerWheel<T> {
+    pub fn new(slots: usize) -> Self {
+        Self {
+            slots: (0..slots.max(1)).map(|_| Vec::new()).collect(),
+            tick: 0,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Add an item due at `deadline`; past deadlines fire on the next tick
+    pub fn insert(&mut self, id: ScheduledId, deadline: u64, item: T) {
+        let deadline = deadline.max(self.tick + 1);
+        let slot = (deadline % self.slots.len() as u64) as usize;
+        self.slots[slot].push(Entry { id, deadline, item });
+        self.len += 1;
+    }
+
+    pub fn cancel(&mut self, id: ScheduledId) -> Option<T> {
+        for slot in self.slots.iter_mut() {
+            if let Some(position) = slot.iter().position(|entry| entry.id == id) {
+                self.len -= 1;
+                return Some(slot.swap_remove(position).item);
+            }
+        }
+        None
+    }
+
+    /// Move to tick `to`, returning the items that came due in deadline
+    /// order (scheduling order for equal deadlines)
+    pub fn advance(&mut self, to: u64) -> Vec<T> {
+        if to <= self.tick {
+            return Vec::new();
+        }
+        // One full rotation visits every slot
+        let slot_count = self.slots.len() as u64;
+        let last = to.min(self.tick + slot_count);
+        let mut due = Vec::new();
+        for tick in (self.tick + 1)..=last {
+            let slot = &mut self.slots[(tick % slot_count) as usize];
+            let mut i = 0;
+            while i < slot.len() {
+                if slot[i].deadline <= to {
+                    due.push(slot.swap_remove(i));
+                } else {
+                    i += 1;
+                }
+            }
+        }
+        self.tick = to;
+        self.len -= due.len();
+        due.sort_by_key(|entry| (entry.deadline, entry.id.0));
+        due.into_iter().map(|entry| entry.item).collect()
+    }
+}
+
+struct SchedulerState<T> {
+    wheel: TimerWheel<T>,
+    next_id: u64,
+    running: bool,
+    closed: bool,
+}
+
+/// Hands items to `deliver` on a background thread once their delay has
+/// passed. The thread starts with the first scheduled item.
+pub struct Scheduler<T> {
+    state: Mutex<SchedulerState<T>>,
+    changed: Condvar,
+    epoch: Instant,
+    deliver: Box<dyn Fn(T) + Send + Sync>,
+}
+
+impl<T: Send + 'static> Scheduler<T> {
+    pub fn new(deliver: impl Fn(T) + Send + Sync + 'static) -> Arc<Self> {
+        Arc::new(Self {
+            state: Mutex::new(SchedulerState {
+                wheel: TimerWheel::new(WHEEL_SLOTS),
+                next_id: 1,
+                running: false,
+                closed: false,
+            }),
+            changed: Condvar::new(),
+            epoch: Instant::now(),
+            deliver: Box::new(deliver),
+        })
+    }
+
+    fn current_tick(&self) -> u64 {
+        (self.epoch.elapsed().as_millis() / TICK.as_millis()) as u64
+    }
+
+    pub fn schedule(self: &Arc<Self>, delay: Duration, item: T) -> ScheduledId {
+        let ticks = delay.as_millis().div_ceil(TICK.as_millis()) as u64;
+        let deadline = self.current_tick() + ticks;
+
+        let Ok(mut state) = self.state.lock() else {
+            return ScheduledId(0);
+        };
+        let id = ScheduledId(state.next_id);
+        state.next_id += 1;
+        if state.wheel.is_empty() {
+            // The wheel only advances while it has work
+            let now = self.current_tick();
+            state.wheel.advance(now);
+        }
+        state.wheel.insert(id, deadline, item);
+
+        if !state.running {
+            state.running = true;
+            let scheduler = self.clone();
+            if let Err(e) = thread::Builder::new()
+                .name("event-scheduler".to_string())
+                .spawn(move || scheduler.run())
+            {
+                error!("Failed to start event scheduler: {}", e);
+                state.running = false;
+            }
+        }
+        drop(state);
+        self.changed.notify_all();
+        id
+    }
+
+    pub fn cancel(&self, id: ScheduledId) -> bool {
+        self.state
+            .lock()
+            .map(|mut state| state.wheel.cancel(id).is_some())
+            .unwrap_or(false)
+    }
+
+    pub fn pending(&self) -> usize {
+        self.state.lock().map(|state| state.wheel.len()).unwrap_or(0)
+    }
+
+    /// Stop the thread; items still pending are discarded
+    pub fn close(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+        }
+        self.changed.notify_all();
+    }
+
+    fn run(&self) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        loop {
+            if state.closed {
+                return;
+            }
+            state = if state.wheel.is_empty() {
+                match self.changed.wait(state) {
+                    Ok(state) => state,
+                    Err(_) => return,
+                }
+            } else {
+                match self.changed.wait_timeout(state, TICK) {
+                    Ok((state, _)) => state,
+                    Err(_) => return,
+                }
+            };
+
+            let now = self.current_tick();
+            let due = state.wheel.advance(now);
+            if due.is_empty() {
+                continue;
+            }
+            drop(state);
+            for item in due {
+                (self.deliver)(item);
+            }
+            state = match self.state.lock() {
+                Ok(state) => state,
+                Err(_) => return,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wheel_releases_in_deadline_order() {
+        let mut wheel = TimerWheel::new(8);
+        wheel.insert(ScheduledId(1), 5, "b");
+        wheel.insert(ScheduledId(2), 3, "a");
+        // Several rotations away, in the same slot as deadline 5
+        wheel.insert(ScheduledId(3), 21, "c");
+        wheel.insert(ScheduledId(4), 5, "b2");
+
+        assert!(wheel.advance(2).is_empty());
+        assert_eq!(wheel.advance(6), vec!["a", "b", "b2"]);
+        assert_eq!(wheel.len(), 1);
+        assert!(wheel.advance(20).is_empty());
+        assert_eq!(wheel.advance(100), vec!["c"]);
+        assert!(wheel.is_empty());
+    }
+
+    #[test]
+    fn test_wheel_cancel() {
+        let mut wheel = TimerWheel::new(8);
+        wheel.insert(ScheduledId(1), 4, 1);
+        wheel.insert(ScheduledId(2), 4, 2);
+        assert_eq!(wheel.cancel(ScheduledId(1)), Some(1));
+        assert_eq!(wheel.cancel(ScheduledId(1)), None);
+        assert_eq!(wheel.advance(4), vec![2]);
+    }
+
+    #[test]
+    fn test_scheduler_delivers_after_delay() {
+        let delivered = Arc::new(Mutex::new(Vec::new()));
+        let sink = delivered.clone();
+        let scheduler = Scheduler::new(move |item: u32| sink.lock().unwrap().push(item));
+
+        let started = Instant::now();
+        scheduler.schedule(Duration::from_millis(60), 2);
+        scheduler.schedule(Duration::from_millis(20), 1);
+        let cancelled = scheduler.schedule(Duration::from_millis(40), 3);
+        assert!(scheduler.cancel(cancelled));
+
+        while delivered.lock().unwrap().len() < 2 && started.elapsed() < Duration::from_secs(2) {
+            thread::sleep(TICK);
+        }
+        assert!(started.elapsed() >= Duration::from_millis(60));
+        assert_eq!(*delivered.lock().unwrap(), vec![1, 2]);
+        assert_eq!(scheduler.pending(), 0);
+        scheduler.close();
+    }
+}
@@ -0,0 +1,156 @@
+// src/core/infrastructure/http_client.rs
+// The builder every outgoing HTTP client starts from, so crash reports, log
+// shipping, remote config and plugins all go through the proxy `[network]`
+// asks for: the system proxy by default, a configured one, or none. One
+// shared client, registered by `NetworkModule`, is built on first use.
+
+use log::info;
+use reqwest::blocking::{Client, ClientBuilder};
+use reqwest::{NoProxy, Proxy};
+use std::sync::{Arc, OnceLock};
+
+use super::config::AppConfig;
+use super::di::{self, Container};
+use super::secrets::{self, KeyringStore};
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::utils::network::{NetworkUtils, SystemProxy};
+
+/// Detected on first use only, as that asks the OS and may download a PAC
+/// script
+static SYSTEM_PROXY: OnceLock<Option<SystemProxy>> = OnceLock::new();
+
+/// The shared client, built from the registered config when first resolved;
+/// set per-request timeouts on the request. Built here and registered as a
+/// singleton if `NetworkModule` isn't installed.
+pub fn shared() -> AppResult<Arc<Client>> {
+    let container = di::get_container();
+    container.get_or_create(|| build(container))
+}
+
+/// A client with the proxy of the config registered in `container`
+pub fn build(container: &Container) -> AppResult<Client> {
+    let builder = match container.resolve_arc::<AppConfig>() {
+        Ok(config) => builder_for(&config)?,
+        Err(_) => builder_for(&AppConfig::default())?,
+    };
+    builder.build().map_err(|e| {
+        AppError::Configuration(
+            ErrorValue::new(ErrorCode::InternalError, "Failed to build the HTTP client")
+                .with_cause(e.to_string()),
+        )
+    })
+}
+
+/// A client builder set up with the proxy of `config`, for code that runs
+/// before the config is registered
+pub fn builder_for(config: &AppConfig) -> AppResult<ClientBuilder> {
+    let builder = reqwest::blocking::Client::builder();
+    let mut bypass = config.get_no_proxy();
+    let (http, https) = match config.get_proxy() {
+        "none" => return Ok(builder.no_proxy()),
+        "system" => match system_proxy() {
+            Some(system) => {
+                bypass.extend(system.no_proxy.iter().cloned());
+                (system.http.clone(), system.https.clone())
+            }
+            None => (None, None),
+        },
+        url => (Some(url.to_string()), Some(url.to_string())),
+    };
+    let bypass = NoProxy::from_string(&bypass.join(","));
+    let credentials = credentials(config)?;
+    let configure = |proxy: reqwest::Result<Proxy>| -> AppResult<Proxy> {
+        let mut proxy = proxy.map_err(proxy_error)?.no_proxy(bypass.clone());
+        if let Some((username, password)) = &credentials {
+            proxy = proxy.basic_auth(username, password);
+        }
+        Ok(proxy)
+    };
+
+    Ok(match (http, https) {
+        (None, None) => builder.no_proxy(),
+        (Some(http), Some(https)) if http == https => builder.proxy(configure(Proxy::all(http))?),
+        (http, https) => {
+            let mut builder = builder;
+            if let Some(http) = http {
+                builder = builder.proxy(configure(Proxy::http(http))?);
+            }
+            if let Some(https) = https {
+                builder = builder.proxy(configure(Proxy::https(https))?);
+            }
+            builder
+        }
+    })
+}
+
+fn system_proxy() -> Option<&'static SystemProxy> {
+    SYSTEM_PROXY
+        .get_or_init(|| {
+            let proxy = NetworkUtils::detect_proxy();
+            if let Some(proxy) = &proxy {
+                info!("Using the proxy from the {:?} settings", proxy.source);
+            }
+            proxy
+        })
+        .as_ref()
+}
+
+fn credentials(config: &AppConfig) -> AppResult<Option<(String, String)>> {
+    let Some((username, password)) = config.get_proxy_credentials() else {
+        return Ok(None);
+    };
+    let store = KeyringStore::new(config.get_secrets_service());
+    Ok(Some((username.to_string(), secrets::resolve(&store, password)?)))
+}
+
+/// The proxy URL itself isn't included, as it may carry a password
+fn proxy_error(cause: reqwest::Error) -> AppError {
+    AppError::Configuration(
+        ErrorValue::new(ErrorCode::ConfigInvalid, "Invalid proxy")
+            .with_cause(cause.to_string())
+            .with_field("network.proxy"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::infrastructure::config::NetworkSettings;
+    use std::io::Read;
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    fn with_proxy(proxy: &str) -> AppConfig {
+        AppConfig {
+            network: Some(NetworkSettings {
+                proxy: Some(proxy.to_string()),
+                no_proxy: Some(vec![String::from("localhost")]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_requests_go_through_configured_proxy() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let config = with_proxy(&format!("http://{}", listener.local_addr().unwrap()));
+        assert!(config.validate().is_ok());
+        let client = builder_for(&config)
+            .unwrap()
+            .timeout(Duration::from_secs(2))
+            .build()
+            .unwrap();
+        let request = std::thread::spawn(move || client.get("http://backend.invalid/").send());
+
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut head = [0; 64];
+        let read = stream.read(&mut head).unwrap();
+        assert!(String::from_utf8_lossy(&head[..read]).starts_with("GET http://backend.invalid/"));
+        drop(stream);
+        assert!(request.join().unwrap().is_err());
+
+        assert!(builder_for(&with_proxy("none")).unwrap().build().is_ok());
+        assert!(with_proxy("proxy.corp:8080").validate().is_err());
+    }
+}
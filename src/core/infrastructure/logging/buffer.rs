@@ -0,0 +1,265 @@
+// src/core/infrastructure/logging/buffer.rs
+// In-memory ring buffer of the latest log records, for the log viewer
+
+use chrono::Utc;
+use log::Level;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use super::formatter::OwnedRecord;
+
+/// Records kept when `[logging] buffer_size` is not set
+pub const DEFAULT_CAPACITY: usize = 1000;
+
+/// A log record as kept in the buffer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LogEntry {
+    /// Increases by one per record; pass the last one seen as `after`
+    pub seq: u64,
+    pub timestamp: String,
+    pub level: String,
+    /// Module path the record was logged from
+    pub module: String,
+    pub message: String,
+    /// Set on records logged while servicing a frontend call or bus event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+}
+
+/// Which records to return; every filter is optional
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct LogQuery {
+    /// Least severe level to include, e.g. "warn" for warnings and errors
+    pub level: Option<String>,
+    /// Module path prefix, e.g. "rustwebui_app::core::infrastructure"
+    pub module: Option<String>,
+    /// Case-insensitive text the message must contain
+    pub search: Option<String>,
+    /// Only records logged under this correlation id
+    pub correlation_id: Option<String>,
+    /// Only records with a larger `seq`
+    pub after: Option<u64>,
+    /// Most recent matching records to return; all when omitted
+    pub limit: Option<usize>,
+}
+
+impl LogQuery {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        let level_ok = match (self.level.as_deref(), Level::from_str(&entry.level)) {
+            (Some(min), Ok(level)) => Level::from_str(min).map_or(true, |min| level <= min),
+            _ => true,
+        };
+        level_ok
+            && self.after.is_none_or(|after| entry.seq > after)
+            && self
+                .correlation_id
+                .as_deref()
+                .is_none_or(|id| entry.correlation_id.as_deref() == Some(id))
+            && self
+                .module
+                .as_deref()
+                .is_none_or(|module| entry.module.starts_with(module))
+            && self.search.as_deref().is_none_or(|search| {
+                entry
+                    .message
+                    .to_lowercase()
+                    .contains(&search.to_lowercase())
+            })
+    }
+}
+
+pub struct LogBuffer {
+    entries: Mutex<VecDeque<LogEntry>>,
+    capacity: AtomicUsize,
+    next_seq: AtomicU64,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity: AtomicUsize::new(capacity),
+            next_seq: AtomicU64::new(1),
+        }
+    }
+
+    /// Keep at most `capacity` records, dropping the oldest beyond that
+    pub fn set_capacity(&self, capacity: usize) {
+        self.capacity.store(capacity, Ordering::Relaxed);
+        if let Ok(mut entries) = self.entries.lock() {
+            while entries.len() > capacity {
+                entries.pop_front();
+            }
+        }
+    }
+
+    pub fn push(&self, record: &OwnedRecord) {
+        self.push_entry(
+            record.level,
+            &record.module,
+            record.message.clone(),
+            record.correlation_id.clone(),
+        );
+    }
+
+    fn push_entry(
+        &self,
+        level: Level,
+        module: &str,
+        message: String,
+        correlation_id: Option<String>,
+    ) {
+        let capacity = self.capacity.load(Ordering::Relaxed);
+        if capacity == 0 {
+            return;
+        }
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        // Numbered under the lock so the buffer stays in `seq` order
+        let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        while entries.len() >= capacity {
+            entries.pop_front();
+        }
+        entries.push_back(LogEntry {
+            seq,
+            timestamp: Utc::now().to_rfc3339(),
+            level: level.as_str().to_lowercase(),
+            module: module.to_string(),
+            message,
+            correlation_id,
+        });
+    }
+
+    /// The latest records matching `query`, oldest first
+    pub fn tail(&self, query: &LogQuery) -> Vec<LogEntry> {
+        let Ok(entries) = self.entries.lock() else {
+            return Vec::new();
+        };
+        let limit = query.limit.unwrap_or(usize::MAX);
+        let mut matching: Vec<LogEntry> = entries
+            .iter()
+            .rev()
+            .filter(|entry| query.matches(entry))
+            .take(limit)
+            .cloned()
+            .collect();
+        matching.reverse();
+        matching
+    }
+
+    /// `seq` of the newest record, 0 when nothing was logged yet
+    pub fn last_seq(&self) -> u64 {
+        self.next_seq.load(Ordering::Relaxed) - 1
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Fed by the installed `Logger`
+    pub static ref GLOBAL_LOG_BUFFER: LogBuffer = LogBuffer::new(DEFAULT_CAPACITY);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled(capacity: usize) -> LogBuffer {
+        let buffer = LogBuffer::new(capacity);
+        let call = || Some(String::from("5eed"));
+        buffer.push_entry(
+            Level::Info,
+            "app::db",
+            String::from("Database opened"),
+            None,
+        );
+        buffer.push_entry(
+            Level::Debug,
+            "app::db",
+            String::from("Pool has 4 connections"),
+            call(),
+        );
+        buffer.push_entry(
+            Level::Warn,
+            "app::ui",
+            String::from("Slow handler: get_users"),
+            call(),
+        );
+        buffer.push_entry(Level::Error, "app::db", String::from("Query FAILED"), None);
+        buffer
+    }
+
+    fn messages(entries: &[LogEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_ring_buffer_keeps_the_latest_records() {
+        let buffer = filled(3);
+        let all = buffer.tail(&LogQuery::default());
+        assert_eq!(
+            messages(&all),
+            [
+                "Pool has 4 connections",
+                "Slow handler: get_users",
+                "Query FAILED"
+            ]
+        );
+        assert_eq!(all[0].seq, 2);
+        assert_eq!(buffer.last_seq(), 4);
+
+        buffer.set_capacity(1);
+        assert_eq!(
+            messages(&buffer.tail(&LogQuery::default())),
+            ["Query FAILED"]
+        );
+    }
+
+    #[test]
+    fn test_tail_filters() {
+        let buffer = filled(10);
+        let query = |query: LogQuery| messages(&buffer.tail(&query)).join(" | ");
+
+        assert_eq!(
+            query(LogQuery {
+                level: Some(String::from("warn")),
+                ..Default::default()
+            }),
+            "Slow handler: get_users | Query FAILED"
+        );
+        assert_eq!(
+            query(LogQuery {
+                module: Some(String::from("app::db")),
+                search: Some(String::from("failed")),
+                ..Default::default()
+            }),
+            "Query FAILED"
+        );
+        assert_eq!(
+            query(LogQuery {
+                after: Some(2),
+                limit: Some(1),
+                ..Default::default()
+            }),
+            "Query FAILED"
+        );
+        assert_eq!(
+            query(LogQuery {
+                limit: Some(2),
+                module: Some(String::from("app::db")),
+                ..Default::default()
+            }),
+            "Pool has 4 connections | Query FAILED"
+        );
+        assert_eq!(
+            query(LogQuery {
+                correlation_id: Some(String::from("5eed")),
+                ..Default::default()
+            }),
+            "Pool has 4 connections | Slow handler: get_users"
+        );
+    }
+}
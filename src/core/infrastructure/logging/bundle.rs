@@ -0,0 +1,185 @@
+// src/core/infrastructure/logging/bundle.rs
+// Support bundle: the current and rotated log files, a redacted config
+// snapshot and system info in one zip that users can attach to bug reports
+
+use chrono::Utc;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::core::infrastructure::config::AppConfig;
+use crate::core::infrastructure::event_middleware::RedactionMiddleware;
+
+/// Config keys whose values are left out of the bundle; `headers` covers
+/// log sink credentials such as X-Api-Key
+const REDACTED_CONFIG_KEYS: [&str; 5] = ["password", "token", "secret", "authorization", "headers"];
+
+/// What was written to the bundle
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct BundleSummary {
+    pub path: String,
+    /// Entries in the zip, e.g. "logs/application.log.1"
+    pub files: Vec<String>,
+    /// Size of the zip
+    pub bytes: u64,
+}
+
+/// File name offered in the save dialog
+pub fn default_file_name() -> String {
+    format!("support-bundle-{}.zip", Utc::now().format("%Y%m%d-%H%M%S"))
+}
+
+/// `log_file` and its rotated copies (`.1`, `.2`, ..) that exist, newest
+/// first
+pub fn log_files(log_file: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<(u32, PathBuf)> = Vec::new();
+    if log_file.is_file() {
+        files.push((0, log_file.to_path_buf()));
+    }
+    let Some(name) = log_file.file_name().and_then(|n| n.to_str()) else {
+        return Vec::new();
+    };
+    let dir = log_file
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let entry_name = entry.file_name();
+            let rotation = entry_name
+                .to_str()
+                .and_then(|n| n.strip_prefix(name))
+                .and_then(|suffix| suffix.strip_prefix('.'))
+                .and_then(|n| n.parse::<u32>().ok());
+            if let Some(rotation) = rotation {
+                files.push((rotation, entry.path()));
+            }
+        }
+    }
+    files.sort();
+    files.into_iter().map(|(_, path)| path).collect()
+}
+
+/// `config` as JSON with credentials replaced by "[REDACTED]"
+pub fn redacted_config(config: &AppConfig) -> serde_json::Value {
+    let mut value = serde_json::to_value(config).unwrap_or_default();
+    RedactionMiddleware::new(REDACTED_CONFIG_KEYS).redact(&mut value);
+    value
+}
+
+/// Write the bundle to `dest`. The installed logger is flushed first so the
+/// log files hold everything logged until now.
+pub fn write_bundle(
+    dest: &Path,
+    config: &AppConfig,
+    system_info: serde_json::Value,
+) -> AppResult<BundleSummary> {
+    log::logger().flush();
+
+    let mut zip = ZipWriter::new(File::create(dest)?);
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut files = Vec::new();
+
+    for path in log_files(Path::new(config.get_log_file())) {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let entry = format!("logs/{}", name);
+        zip.start_file(entry.as_str(), options).map_err(zip_error)?;
+        io::copy(&mut File::open(&path)?, &mut zip)?;
+        files.push(entry);
+    }
+
+    let system = serde_json::json!({
+        "app": config.get_app_name(),
+        "version": config.get_version(),
+        "generated_at": Utc::now().to_rfc3339(),
+        "system": system_info,
+    });
+    for (entry, value) in [
+        ("config.json", redacted_config(config)),
+        ("system.json", system),
+    ] {
+        zip.start_file(entry, options).map_err(zip_error)?;
+        zip.write_all(&serde_json::to_vec_pretty(&value)?)?;
+        files.push(entry.to_string());
+    }
+
+    zip.finish().map_err(zip_error)?;
+    Ok(BundleSummary {
+        path: dest.display().to_string(),
+        files,
+        bytes: fs::metadata(dest)?.len(),
+    })
+}
+
+fn zip_error(e: zip::result::ZipError) -> AppError {
+    AppError::Logging(
+        ErrorValue::new(
+            ErrorCode::InternalError,
+            "Failed to write the support bundle",
+        )
+        .with_cause(e.to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::infrastructure::config::LogSinkSettings;
+    use std::io::Read;
+    use zip::ZipArchive;
+
+    #[test]
+    fn test_bundle_has_logs_redacted_config_and_system_info() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_file = dir.path().join("app.log");
+        fs::write(&log_file, "current\n").unwrap();
+        fs::write(dir.path().join("app.log.2"), "older\n").unwrap();
+        fs::write(dir.path().join("app.log.1"), "old\n").unwrap();
+        fs::write(dir.path().join("app.log.bak"), "not a rotation\n").unwrap();
+
+        let mut config = AppConfig::default();
+        config.logging.file = log_file.display().to_string();
+        config.logging.level = String::from("debug");
+        config.logging.sinks = Some(vec![LogSinkSettings {
+            kind: String::from("http"),
+            headers: Some([(String::from("X-Api-Key"), String::from("k3y"))].into()),
+            ..Default::default()
+        }]);
+
+        let dest = dir.path().join("bundle.zip");
+        let summary = write_bundle(&dest, &config, serde_json::json!({ "os": "test" })).unwrap();
+        assert_eq!(
+            summary.files,
+            [
+                "logs/app.log",
+                "logs/app.log.1",
+                "logs/app.log.2",
+                "config.json",
+                "system.json"
+            ]
+        );
+
+        let mut zip = ZipArchive::new(File::open(&dest).unwrap()).unwrap();
+        let mut read = |name: &str| {
+            let mut text = String::new();
+            zip.by_name(name)
+                .unwrap()
+                .read_to_string(&mut text)
+                .unwrap();
+            text
+        };
+        assert_eq!(read("logs/app.log.1"), "old\n");
+        let config: serde_json::Value = serde_json::from_str(&read("config.json")).unwrap();
+        assert_eq!(config["logging"]["level"], "debug");
+        assert_eq!(config["logging"]["sinks"][0]["headers"], "[REDACTED]");
+        let system: serde_json::Value = serde_json::from_str(&read("system.json")).unwrap();
+        assert_eq!(system["system"]["os"], "test");
+    }
+}
@@ -0,0 +1,62 @@
+// src/core/infrastructure/logging/context.rs
+// Correlation id of the frontend call (or bus event) the current thread is
+// servicing. Every log record emitted meanwhile carries it, so the log
+// viewer can group the lines of one call together.
+
+use std::cell::RefCell;
+
+thread_local! {
+    static CURRENT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous correlation id when dropped
+#[must_use = "the correlation id is cleared as soon as the guard is dropped"]
+pub struct CorrelationGuard {
+    previous: Option<String>,
+}
+
+impl Drop for CorrelationGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Tag what this thread logs with `id` until the guard is dropped
+pub fn enter(id: impl Into<String>) -> CorrelationGuard {
+    scope(Some(id.into()))
+}
+
+/// Like `enter`, but `None` clears the id for the guard's lifetime
+pub fn scope(id: Option<String>) -> CorrelationGuard {
+    let previous = CURRENT.with(|current| current.replace(id));
+    CorrelationGuard { previous }
+}
+
+/// Correlation id of the call being serviced on this thread, if any
+pub fn current() -> Option<String> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guards_nest_and_restore() {
+        assert_eq!(current(), None);
+        {
+            let _outer = enter("a1");
+            assert_eq!(current().as_deref(), Some("a1"));
+            {
+                let _inner = scope(None);
+                assert_eq!(current(), None);
+            }
+            assert_eq!(current().as_deref(), Some("a1"));
+            std::thread::spawn(|| assert_eq!(current(), None))
+                .join()
+                .unwrap();
+        }
+        assert_eq!(current(), None);
+    }
+}
@@ -0,0 +1,117 @@
+// src/core/infrastructure/logging/filters.rs
+// Level filters the installed Logger applies: one default level plus
+// overrides per module path, changeable while the app runs. `log`'s max
+// level is kept at the most verbose of them so macros elsewhere aren't
+// skipped before the Logger sees them.
+
+use log::{LevelFilter, Metadata};
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelFilters {
+    pub default: LevelFilter,
+    /// Module path prefix -> level, e.g. "rustwebui_app::core::infrastructure::database"
+    pub targets: BTreeMap<String, LevelFilter>,
+}
+
+impl LevelFilters {
+    /// Level of the longest configured prefix of `target`, or the default
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        self.targets
+            .iter()
+            .filter(|(prefix, _)| {
+                target == prefix.as_str()
+                    || target
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.default, |(_, level)| *level)
+    }
+
+    /// Most verbose level any target may log at
+    pub fn max(&self) -> LevelFilter {
+        self.targets
+            .values()
+            .copied()
+            .fold(self.default, Ord::max)
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref FILTERS: RwLock<LevelFilters> = RwLock::new(LevelFilters {
+        default: LevelFilter::Info,
+        targets: BTreeMap::new(),
+    });
+}
+
+fn update(change: impl FnOnce(&mut LevelFilters)) {
+    if let Ok(mut filters) = FILTERS.write() {
+        change(&mut filters);
+        log::set_max_level(filters.max());
+    }
+}
+
+/// Level for modules without an override
+pub fn set_default(level: LevelFilter) {
+    update(|filters| filters.default = level);
+}
+
+/// Override the level of `target` and the modules below it; `None` removes
+/// the override so the default applies again
+pub fn set_target(target: &str, level: Option<LevelFilter>) {
+    update(|filters| match level {
+        Some(level) => {
+            filters.targets.insert(target.to_string(), level);
+        }
+        None => {
+            filters.targets.remove(target);
+        }
+    });
+}
+
+/// Drop every per-module override
+pub fn clear_targets() {
+    update(|filters| filters.targets.clear());
+}
+
+pub fn current() -> LevelFilters {
+    FILTERS
+        .read()
+        .map(|filters| filters.clone())
+        .unwrap_or_else(|_| LevelFilters {
+            default: log::max_level(),
+            targets: BTreeMap::new(),
+        })
+}
+
+pub fn enabled(metadata: &Metadata) -> bool {
+    let level = FILTERS
+        .read()
+        .map(|filters| filters.level_for(metadata.target()))
+        .unwrap_or_else(|_| log::max_level());
+    metadata.level() <= level
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_longest_prefix_wins() {
+        let filters = LevelFilters {
+            default: LevelFilter::Info,
+            targets: BTreeMap::from([
+                (String::from("app::db"), LevelFilter::Debug),
+                (String::from("app::db::pool"), LevelFilter::Warn),
+            ]),
+        };
+        assert_eq!(filters.level_for("app::db"), LevelFilter::Debug);
+        assert_eq!(filters.level_for("app::db::users"), LevelFilter::Debug);
+        assert_eq!(filters.level_for("app::db::pool::r2d2"), LevelFilter::Warn);
+        assert_eq!(filters.level_for("app::dbx"), LevelFilter::Info);
+        assert_eq!(filters.level_for("reqwest"), LevelFilter::Info);
+        assert_eq!(filters.max(), LevelFilter::Debug);
+    }
+}
@@ -1,7 +1,104 @@
 // src/core/infrastructure/logging/formatter.rs
 // Log message formatting
 
-use log::Record;
+use chrono::{DateTime, Local};
+use log::{Level, Record};
+use std::io::IsTerminal;
+use std::sync::RwLock;
+
+use super::{context, redaction};
+use crate::core::infrastructure::config::AppConfig;
+
+/// How much of a record a console line shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleStyle {
+    /// Level, last module segment and message
+    Compact,
+    /// Level, full module path, file:line, thread and message
+    Verbose,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsoleFormat {
+    pub colors: bool,
+    pub timestamps: bool,
+    pub style: ConsoleStyle,
+}
+
+impl ConsoleFormat {
+    /// From `[logging.console]`; colors default to whether stdout is a
+    /// terminal, and NO_COLOR turns them off
+    pub fn from_config(config: &AppConfig) -> Self {
+        let colors = config.get_console_colors().unwrap_or_else(|| {
+            std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+        });
+        Self {
+            colors,
+            timestamps: config.get_console_timestamps(),
+            style: match config.get_console_format() {
+                "verbose" => ConsoleStyle::Verbose,
+                _ => ConsoleStyle::Compact,
+            },
+        }
+    }
+}
+
+impl Default for ConsoleFormat {
+    fn default() -> Self {
+        Self {
+            colors: true,
+            timestamps: false,
+            style: ConsoleStyle::Compact,
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref CONSOLE_FORMAT: RwLock<ConsoleFormat> = RwLock::new(ConsoleFormat::default());
+}
+
+/// Change how console lines look, e.g. after a config reload
+pub fn set_console_format(format: ConsoleFormat) {
+    if let Ok(mut current) = CONSOLE_FORMAT.write() {
+        *current = format;
+    }
+}
+
+/// A `Record` as the buffer, the sinks and the writer thread get it, taken
+/// on the logging thread since `Record` borrows its arguments and the
+/// correlation id is thread-local. The message is already redacted.
+#[derive(Debug, Clone)]
+pub struct OwnedRecord {
+    pub time: DateTime<Local>,
+    pub level: Level,
+    pub target: String,
+    /// Module path, or the target when the record has none
+    pub module: String,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub message: String,
+    pub correlation_id: Option<String>,
+    pub thread: Option<String>,
+}
+
+impl OwnedRecord {
+    pub fn capture(record: &Record) -> Self {
+        Self {
+            time: Local::now(),
+            level: record.level(),
+            target: record.target().to_string(),
+            module: record
+                .module_path()
+                .unwrap_or_else(|| record.target())
+                .to_string(),
+            file: record.file().map(str::to_string),
+            line: record.line(),
+            message: redaction::redact(record.args().to_string()),
+            correlation_id: context::current(),
+            thread: std::thread::current().name().map(str::to_string),
+        }
+    }
+}
 
 pub struct LogFormatter;
 
@@ -10,35 +107,129 @@ impl LogFormatter {
         Self
     }
 
-    pub fn format_json(&self, record: &Record) -> String {
-        let level = record.level();
-        let target = record.target();
-        let message = record.args().to_string();
-        let line = record.line().unwrap_or(0);
-        let file = record.file().unwrap_or("unknown");
+    pub fn format_json(&self, record: &OwnedRecord) -> String {
+        let level = record.level;
+        let target = &record.target;
+        let line = record.line.unwrap_or(0);
+        let file = record.file.as_deref().unwrap_or("unknown");
 
-        let escaped_msg = message.replace('\\', "\\\\").replace('"', "\\\"");
+        let escape = |text: &str| text.replace('\\', "\\\\").replace('"', "\\\"");
+        let escaped_msg = escape(&record.message);
+        let correlation = record
+            .correlation_id
+            .as_deref()
+            .map(|id| format!(r#","correlation_id":"{}""#, escape(id)))
+            .unwrap_or_default();
 
         format!(
-            r#"{{"level":"{}","target":"{}","file":"{}","line":{},"message":"{}"}}"#,
-            level, target, file, line, escaped_msg
+            r#"{{"level":"{}","target":"{}","file":"{}","line":{}{},"message":"{}"}}"#,
+            level, target, file, line, correlation, escaped_msg
         )
     }
 
-    pub fn format_console(&self, record: &Record) -> String {
-        let level = record.level();
-        let target = record.target();
-        let message = record.args().to_string();
+    pub fn format_console(&self, record: &OwnedRecord) -> String {
+        let format = CONSOLE_FORMAT
+            .read()
+            .map(|format| *format)
+            .unwrap_or_default();
+        self.format_console_with(record, &format)
+    }
+
+    pub fn format_console_with(&self, record: &OwnedRecord, format: &ConsoleFormat) -> String {
+        let (color, dim, reset) = if format.colors {
+            let color = match record.level {
+                log::Level::Error => "\x1b[31m",
+                log::Level::Warn => "\x1b[33m",
+                log::Level::Info => "\x1b[32m",
+                log::Level::Debug => "\x1b[36m",
+                log::Level::Trace => "\x1b[90m",
+            };
+            (color, "\x1b[2m", "\x1b[0m")
+        } else {
+            ("", "", "")
+        };
+
+        let mut line = String::new();
+        if format.timestamps {
+            let time = match format.style {
+                ConsoleStyle::Compact => record.time.format("%H:%M:%S%.3f"),
+                ConsoleStyle::Verbose => record.time.format("%Y-%m-%d %H:%M:%S%.3f"),
+            };
+            line.push_str(&format!("{}{}{} ", dim, time, reset));
+        }
+        line.push_str(&format!("{}{:<5}{} ", color, record.level, reset));
+
+        match format.style {
+            ConsoleStyle::Compact => {
+                let module = record.target.rsplit("::").next().unwrap_or_default();
+                line.push_str(&format!("{}{}{} ", dim, module, reset));
+            }
+            ConsoleStyle::Verbose => {
+                line.push_str(&format!("{}{}", dim, record.target));
+                if let (Some(file), Some(number)) = (&record.file, record.line) {
+                    line.push_str(&format!(" {}:{}", file, number));
+                }
+                if let Some(thread) = &record.thread {
+                    line.push_str(&format!(" ({})", thread));
+                }
+                line.push_str(&format!("{} ", reset));
+            }
+        }
+        if let Some(id) = &record.correlation_id {
+            let id = match format.style {
+                ConsoleStyle::Compact => id.get(..8).unwrap_or(id.as_str()),
+                ConsoleStyle::Verbose => id.as_str(),
+            };
+            line.push_str(&format!("[{}] ", id));
+        }
+        line.push_str(&record.message);
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn record() -> OwnedRecord {
+        OwnedRecord {
+            time: Local.with_ymd_and_hms(2026, 3, 4, 5, 6, 7).unwrap(),
+            level: Level::Warn,
+            target: String::from("rustwebui_app::core::infrastructure::database"),
+            module: String::from("rustwebui_app::core::infrastructure::database"),
+            file: Some(String::from("src/core/infrastructure/database/mod.rs")),
+            line: Some(42),
+            message: String::from("Pool exhausted"),
+            correlation_id: Some(String::from("5f0c9a1e2b3d4c6f")),
+            thread: Some(String::from("main")),
+        }
+    }
+
+    #[test]
+    fn test_compact_and_verbose_console_lines() {
+        let formatter = LogFormatter::new();
+        let plain = ConsoleFormat {
+            colors: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            formatter.format_console_with(&record(), &plain),
+            "WARN  database [5f0c9a1e] Pool exhausted"
+        );
 
-        let color = match level {
-            log::Level::Error => "\x1b[31m",
-            log::Level::Warn => "\x1b[33m",
-            log::Level::Info => "\x1b[32m",
-            log::Level::Debug => "\x1b[36m",
-            log::Level::Trace => "\x1b[90m",
+        let verbose = ConsoleFormat {
+            colors: false,
+            timestamps: true,
+            style: ConsoleStyle::Verbose,
         };
-        let reset = "\x1b[0m";
+        assert_eq!(
+            formatter.format_console_with(&record(), &verbose),
+            "2026-03-04 05:06:07.000 WARN  rustwebui_app::core::infrastructure::database \
+             src/core/infrastructure/database/mod.rs:42 (main) [5f0c9a1e2b3d4c6f] Pool exhausted"
+        );
 
-        format!("{}[{}]{} [{}] {}", color, level, reset, target, message)
+        let colored = formatter.format_console_with(&record(), &ConsoleFormat::default());
+        assert!(colored.starts_with("\x1b[33mWARN \x1b[0m \x1b[2mdatabase\x1b[0m "));
     }
 }
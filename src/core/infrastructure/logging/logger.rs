@@ -1,30 +1,51 @@
 // src/core/infrastructure/logging/logger.rs
-// Logger implementation
+// Logger implementation. Formatting, console output and file IO happen on a
+// "log-writer" thread fed by a bounded queue, so logging from a handler
+// never waits on the disk.
 
-use log::{Metadata, Record};
-use std::fs::{self, OpenOptions};
-use std::io::Write;
+use log::{Level, Metadata, Record};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, OnceLock};
+use std::thread::{self, ThreadId};
+use std::time::Duration;
 
-use super::formatter::LogFormatter;
+use super::buffer::GLOBAL_LOG_BUFFER;
+use super::filters;
+use super::formatter::{LogFormatter, OwnedRecord};
+use super::shipping::GLOBAL_LOG_SHIPPER;
+use crate::core::error::AppResult;
+use crate::core::infrastructure::di::Shutdown;
+
+/// Records queued for the writer when `[logging] queue_size` is not set
+pub const DEFAULT_QUEUE_SIZE: usize = 8192;
+
+/// Longest `flush` waits for the writer to catch up
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
 
 pub struct Logger {
-    file_path: Mutex<PathBuf>,
+    file_path: PathBuf,
     max_file_size: u64,
     max_backup_files: usize,
     log_to_console: bool,
+    queue_size: usize,
     formatter: LogFormatter,
+    writer: OnceLock<Option<WriterHandle>>,
 }
 
 impl Logger {
     pub fn new() -> Self {
         Self {
-            file_path: Mutex::new(Self::resolve_log_path("application.log")),
+            file_path: Self::resolve_log_path("application.log"),
             max_file_size: 10 * 1024 * 1024,
             max_backup_files: 5,
             log_to_console: true,
+            queue_size: DEFAULT_QUEUE_SIZE,
             formatter: LogFormatter::new(),
+            writer: OnceLock::new(),
         }
     }
 
@@ -60,10 +81,8 @@ impl Logger {
             .to_string()
     }
 
-    pub fn with_file(self, path: &str) -> Self {
-        if let Ok(mut guard) = self.file_path.lock() {
-            *guard = PathBuf::from(path);
-        }
+    pub fn with_file(mut self, path: &str) -> Self {
+        self.file_path = PathBuf::from(path);
         self
     }
 
@@ -82,69 +101,317 @@ impl Logger {
         self
     }
 
-    fn rotate_if_needed(&self) {
-        let Ok(path) = self.file_path.lock() else {
-            return;
-        };
-        if let Ok(metadata) = fs::metadata(&*path) {
-            if metadata.len() > self.max_file_size {
-                drop(metadata);
-                let path_str = path.to_string_lossy().to_string();
-                drop(path);
-
-                for i in (1..self.max_backup_files).rev() {
-                    let old_path = format!("{}.{}", path_str, i);
-                    let new_path = format!("{}.{}", path_str, i + 1);
-                    let _ = fs::remove_file(&new_path);
-                    if PathBuf::from(&old_path).exists() {
-                        let _ = fs::rename(&old_path, &new_path);
-                    }
-                }
+    /// Records queued for the writer thread before debug and info records
+    /// are dropped; warnings and errors wait for room instead
+    pub fn with_queue_size(mut self, size: usize) -> Self {
+        self.queue_size = size.max(1);
+        self
+    }
+
+    /// Started on the first record, after the builder calls
+    fn writer(&self) -> Option<&WriterHandle> {
+        self.writer
+            .get_or_init(|| {
+                let file = LogFile::new(
+                    self.file_path.clone(),
+                    self.max_file_size,
+                    self.max_backup_files,
+                );
+                WriterHandle::spawn(file, self.log_to_console, self.queue_size)
+                    .map_err(|e| eprintln!("Failed to start the log writer thread: {}", e))
+                    .ok()
+            })
+            .as_ref()
+    }
+}
 
-                let backup_path = format!("{}.1", path_str);
-                if let Ok(p) = self.file_path.lock() {
-                    let _ = fs::rename(&*p, &backup_path);
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::STATIC_MAX_LEVEL && filters::enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let owned = OwnedRecord::capture(record);
+            GLOBAL_LOG_BUFFER.push(&owned);
+            GLOBAL_LOG_SHIPPER.push(&owned);
+            match self.writer() {
+                Some(writer) => writer.send(owned),
+                None if self.log_to_console => {
+                    eprintln!("{}", self.formatter.format_console(&owned))
                 }
+                None => {}
             }
         }
     }
 
-    fn write_to_file(&self, message: &str) {
-        self.rotate_if_needed();
+    /// Wait until the writer thread has written and flushed what was
+    /// logged so far
+    fn flush(&self) {
+        if let Some(writer) = self.writer.get().and_then(Option::as_ref) {
+            writer.flush(FLUSH_TIMEOUT);
+        }
+    }
+}
+
+impl Shutdown for Logger {
+    /// Flush the installed logger, console output and log sinks so the last
+    /// records aren't lost at exit
+    fn shutdown(&self) -> AppResult<()> {
+        log::logger().flush();
+        GLOBAL_LOG_SHIPPER.shutdown(std::time::Duration::from_secs(2));
+        std::io::stdout().flush()?;
+        Ok(())
+    }
+}
 
-        let path = match self.file_path.lock() {
-            Ok(p) => p,
-            Err(_) => return,
+enum WriterMessage {
+    Record(OwnedRecord),
+    Flush(SyncSender<()>),
+}
+
+struct WriterHandle {
+    sender: SyncSender<WriterMessage>,
+    /// Records dropped because the queue was full, not yet reported
+    dropped: Arc<AtomicU64>,
+    thread: ThreadId,
+}
+
+impl WriterHandle {
+    fn spawn(file: LogFile, log_to_console: bool, queue_size: usize) -> std::io::Result<Self> {
+        let (sender, receiver) = mpsc::sync_channel(queue_size);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let writer = LogWriter {
+            file,
+            formatter: LogFormatter::new(),
+            log_to_console,
         };
+        let thread_dropped = dropped.clone();
+        let handle = thread::Builder::new()
+            .name(String::from("log-writer"))
+            .spawn(move || writer.run(receiver, thread_dropped))?;
+        Ok(Self {
+            sender,
+            dropped,
+            thread: handle.thread().id(),
+        })
+    }
 
-        if let Ok(mut file) = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(path.as_path())
-        {
-            let _ = writeln!(file, "{}", message);
-            let _ = file.flush();
+    fn send(&self, record: OwnedRecord) {
+        let blocking = record.level <= Level::Warn;
+        match self.sender.try_send(WriterMessage::Record(record)) {
+            Err(TrySendError::Full(message)) if blocking => {
+                let _ = self.sender.send(message);
+            }
+            Err(TrySendError::Full(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            _ => {}
+        }
+    }
+
+    fn flush(&self, timeout: Duration) {
+        // The writer waiting for itself would only time out
+        if thread::current().id() == self.thread {
+            return;
+        }
+        let (done, flushed) = mpsc::sync_channel(1);
+        if self.sender.send(WriterMessage::Flush(done)).is_ok() {
+            let _ = flushed.recv_timeout(timeout);
         }
     }
 }
 
-impl log::Log for Logger {
-    fn enabled(&self, metadata: &Metadata) -> bool {
-        metadata.level() <= log::max_level() && metadata.level() <= log::STATIC_MAX_LEVEL
+/// Runs on the writer thread
+struct LogWriter {
+    file: LogFile,
+    formatter: LogFormatter,
+    log_to_console: bool,
+}
+
+impl LogWriter {
+    fn run(mut self, receiver: Receiver<WriterMessage>, dropped: Arc<AtomicU64>) {
+        while let Ok(message) = receiver.recv() {
+            // Drain whatever else is queued before flushing once
+            for message in std::iter::once(message).chain(receiver.try_iter()) {
+                match message {
+                    WriterMessage::Record(record) => self.write(&record),
+                    WriterMessage::Flush(done) => {
+                        self.flush();
+                        let _ = done.send(());
+                    }
+                }
+            }
+            let count = dropped.swap(0, Ordering::Relaxed);
+            if count > 0 {
+                self.write(&OwnedRecord {
+                    time: chrono::Local::now(),
+                    level: Level::Warn,
+                    target: String::from(module_path!()),
+                    module: String::from(module_path!()),
+                    file: None,
+                    line: None,
+                    message: format!("Dropped {} log records: writer queue full", count),
+                    correlation_id: None,
+                    thread: None,
+                });
+            }
+            self.flush();
+        }
     }
 
-    fn log(&self, record: &Record) {
-        if self.enabled(record.metadata()) {
-            let json_msg = self.formatter.format_json(record);
+    fn write(&mut self, record: &OwnedRecord) {
+        if self.log_to_console {
+            println!("{}", self.formatter.format_console(record));
+        }
+        self.file.write_line(&self.formatter.format_json(record));
+    }
+
+    fn flush(&mut self) {
+        self.file.flush();
+        let _ = std::io::stdout().flush();
+    }
+}
+
+/// The log file as the writer thread keeps it open, rotated by size
+struct LogFile {
+    path: PathBuf,
+    max_file_size: u64,
+    max_backup_files: usize,
+    writer: Option<BufWriter<File>>,
+    size: u64,
+}
+
+impl LogFile {
+    fn new(path: PathBuf, max_file_size: u64, max_backup_files: usize) -> Self {
+        Self {
+            path,
+            max_file_size,
+            max_backup_files,
+            writer: None,
+            size: 0,
+        }
+    }
 
-            if self.log_to_console {
-                let console_msg = self.formatter.format_console(record);
-                println!("{}", console_msg);
+    fn write_line(&mut self, line: &str) {
+        if self.writer.is_none() {
+            self.open();
+        }
+        if self.size > self.max_file_size {
+            self.rotate();
+            self.open();
+        }
+        if let Some(writer) = self.writer.as_mut() {
+            if writeln!(writer, "{}", line).is_ok() {
+                self.size += line.len() as u64 + 1;
             }
+        }
+    }
 
-            self.write_to_file(&json_msg);
+    fn flush(&mut self) {
+        if let Some(writer) = self.writer.as_mut() {
+            let _ = writer.flush();
         }
     }
 
-    fn flush(&self) {}
+    fn open(&mut self) {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            let _ = fs::create_dir_all(parent);
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path);
+        if let Ok(file) = file {
+            self.size = file.metadata().map(|m| m.len()).unwrap_or(0);
+            self.writer = Some(BufWriter::new(file));
+        }
+    }
+
+    fn rotate(&mut self) {
+        self.flush();
+        self.writer = None;
+        let path_str = self.path.to_string_lossy().to_string();
+
+        for i in (1..self.max_backup_files).rev() {
+            let old_path = format!("{}.{}", path_str, i);
+            let new_path = format!("{}.{}", path_str, i + 1);
+            let _ = fs::remove_file(&new_path);
+            if PathBuf::from(&old_path).exists() {
+                let _ = fs::rename(&old_path, &new_path);
+            }
+        }
+
+        let backup_path = format!("{}.1", path_str);
+        let _ = fs::rename(&self.path, &backup_path);
+        self.size = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::Log;
+
+    fn log_line(logger: &Logger, level: Level, message: &str) {
+        logger.log(
+            &Record::builder()
+                .level(level)
+                .target("app::test")
+                .args(format_args!("{}", message))
+                .build(),
+        );
+    }
+
+    #[test]
+    fn test_records_are_written_by_the_writer_thread() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join("app.log");
+        let logger = Logger::new()
+            .with_file(path.to_str().unwrap())
+            .with_max_size(200)
+            .with_max_backups(2)
+            .with_console_output(false);
+        log::set_max_level(log::LevelFilter::Info);
+
+        for i in 0..6 {
+            log_line(&logger, Level::Error, &format!("record {}", i));
+        }
+        logger.flush();
+
+        let current = fs::read_to_string(&path).unwrap();
+        let rotated = fs::read_to_string(dir.path().join("logs").join("app.log.1")).unwrap();
+        assert!(rotated.starts_with(r#"{"level":"ERROR","target":"app::test""#));
+        assert!(current.trim_end().ends_with(r#""message":"record 5"}"#));
+        assert_eq!(current.lines().count() + rotated.lines().count(), 6);
+    }
+
+    #[test]
+    fn test_full_queue_drops_only_low_levels() {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let writer = WriterHandle {
+            sender,
+            dropped: Arc::new(AtomicU64::new(0)),
+            thread: thread::current().id(),
+        };
+        let record = |level| OwnedRecord {
+            time: chrono::Local::now(),
+            level,
+            target: String::from("app::test"),
+            module: String::from("app::test"),
+            file: None,
+            line: None,
+            message: String::new(),
+            correlation_id: None,
+            thread: None,
+        };
+        writer.send(record(Level::Info));
+        writer.send(record(Level::Debug));
+        assert_eq!(writer.dropped.load(Ordering::Relaxed), 1);
+
+        let reader = thread::spawn(move || receiver.iter().count());
+        writer.send(record(Level::Error));
+        drop(writer);
+        assert_eq!(reader.join().unwrap(), 2);
+    }
 }
@@ -2,16 +2,27 @@
 // src/core/infrastructure/logging/mod.rs
 // Logging module - Application logging system
 
+pub mod buffer;
+pub mod bundle;
 pub mod config;
+pub mod context;
+pub mod filters;
 pub mod formatter;
 pub mod logger;
+pub mod redaction;
+pub mod shipping;
+pub mod spans;
+pub mod stream;
 
+pub use buffer::{LogEntry, LogQuery, GLOBAL_LOG_BUFFER};
 pub use config::LoggingConfig;
-pub use logger::Logger;
+pub use formatter::{set_console_format, ConsoleFormat};
+pub use logger::{Logger, DEFAULT_QUEUE_SIZE};
+pub use shipping::GLOBAL_LOG_SHIPPER;
 
 /// Initialize logging with default configuration
 pub fn init_logging() -> Result<(), Box<dyn std::error::Error>> {
-    init_logging_with_config(None, "info", false)
+    init_logging_with_config(None, "info", false, DEFAULT_QUEUE_SIZE)
 }
 
 /// Initialize logging with custom configuration
@@ -19,6 +30,7 @@ pub fn init_logging_with_config(
     log_file: Option<&str>,
     log_level: &str,
     _append: bool,
+    queue_size: usize,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let file_path = log_file.unwrap_or("logs/application.log");
 
@@ -26,12 +38,12 @@ pub fn init_logging_with_config(
         .with_file(file_path)
         .with_max_size(10 * 1024 * 1024)
         .with_max_backups(5)
-        .with_console_output(true);
+        .with_console_output(true)
+        .with_queue_size(queue_size);
 
     log::set_boxed_logger(Box::new(logger))?;
 
-    let level = LoggingConfig::level_from_str(log_level);
-    log::set_max_level(level);
+    filters::set_default(LoggingConfig::level_from_str(log_level));
 
     log::info!(
         "Logging initialized: level={}, file={}",
@@ -42,6 +54,13 @@ pub fn init_logging_with_config(
     Ok(())
 }
 
+/// Change the default level of the installed logger, e.g. after a config
+/// reload; per-module overrides are kept
+pub fn set_level(log_level: &str) {
+    filters::set_default(LoggingConfig::level_from_str(log_level));
+    log::info!("Log level set to {}", log_level);
+}
+
 /// Get default log file path (resolved relative to executable)
 pub fn get_log_file_path() -> String {
     Logger::default_log_path()
@@ -0,0 +1,165 @@
+// src/core/infrastructure/logging/redaction.rs
+// Masks emails, credentials and `[logging.redaction] patterns` in log
+// messages before they reach the log file, the console, the log viewer or a
+// sink, so user data doesn't end up in support bundles
+
+use regex::Regex;
+use std::borrow::Cow;
+use std::sync::RwLock;
+
+use crate::core::infrastructure::config::AppConfig;
+use crate::core::infrastructure::event_middleware::REDACTED;
+
+const EMAIL_PATTERN: &str = r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}";
+
+/// Credentials logged as `key=value`, `key: value` or `"key":"value"`; the
+/// key is kept so the line still says what was there
+const CREDENTIAL_PATTERN: &str =
+    r#"(?i)\b([\w-]*(?:password|passwd|secret|token|api[_-]?key)"?\s*[:=]\s*"?)[^\s"',;&]+"#;
+
+/// `Authorization: Bearer ..` headers and bare JSON Web Tokens
+const BEARER_PATTERN: &str = r"(?i)\b(bearer\s+)[A-Za-z0-9._~+/=-]+";
+const JWT_PATTERN: &str = r"\beyJ[A-Za-z0-9_-]*\.[A-Za-z0-9_-]+\.[A-Za-z0-9_-]*";
+
+struct Rule {
+    regex: Regex,
+    /// "[REDACTED]", after capture group 1 for rules that keep the key
+    replacement: String,
+}
+
+pub struct Redactor {
+    rules: Vec<Rule>,
+}
+
+impl Redactor {
+    /// Patterns that fail to compile are skipped; `AppConfig::validate`
+    /// reports them
+    pub fn new(emails: bool, tokens: bool, patterns: &[String]) -> Self {
+        let mut rules = Vec::new();
+        let mut add = |pattern: &str, keeps_prefix: bool| {
+            if let Ok(regex) = Regex::new(pattern) {
+                let replacement = if keeps_prefix {
+                    format!("${{1}}{}", REDACTED)
+                } else {
+                    REDACTED.to_string()
+                };
+                rules.push(Rule { regex, replacement });
+            }
+        };
+        if tokens {
+            add(CREDENTIAL_PATTERN, true);
+            add(BEARER_PATTERN, true);
+            add(JWT_PATTERN, false);
+        }
+        if emails {
+            add(EMAIL_PATTERN, false);
+        }
+        for pattern in patterns {
+            add(pattern, false);
+        }
+        Self { rules }
+    }
+
+    pub fn from_config(config: &AppConfig) -> Self {
+        if !config.is_log_redaction_enabled() {
+            return Self::disabled();
+        }
+        Self::new(
+            config.should_redact_emails(),
+            config.should_redact_tokens(),
+            config.get_redact_patterns(),
+        )
+    }
+
+    pub fn disabled() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    pub fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut text = Cow::Borrowed(text);
+        for rule in &self.rules {
+            let replaced = match rule.regex.replace_all(&text, rule.replacement.as_str()) {
+                Cow::Owned(replaced) => Some(replaced),
+                Cow::Borrowed(_) => None,
+            };
+            if let Some(replaced) = replaced {
+                text = Cow::Owned(replaced);
+            }
+        }
+        text
+    }
+}
+
+impl Default for Redactor {
+    /// Emails and credentials, as when `[logging.redaction]` is not set
+    fn default() -> Self {
+        Self::new(true, true, &[])
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Applied by the installed `Logger` to every message
+    pub static ref GLOBAL_LOG_REDACTOR: RwLock<Redactor> = RwLock::new(Redactor::default());
+}
+
+/// Replace the rules from `[logging.redaction]`, e.g. after a config reload
+pub fn configure(config: &AppConfig) {
+    if let Ok(mut redactor) = GLOBAL_LOG_REDACTOR.write() {
+        *redactor = Redactor::from_config(config);
+    }
+}
+
+/// `message` with what the global rules match masked
+pub fn redact(message: String) -> String {
+    let Ok(redactor) = GLOBAL_LOG_REDACTOR.read() else {
+        return message;
+    };
+    let redacted = match redactor.redact(&message) {
+        Cow::Owned(redacted) => Some(redacted),
+        Cow::Borrowed(_) => None,
+    };
+    redacted.unwrap_or(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_rules_mask_emails_and_credentials() {
+        let redactor = Redactor::default();
+        assert_eq!(
+            redactor.redact("Created user ada@example.com (id 7)"),
+            "Created user [REDACTED] (id 7)"
+        );
+        assert_eq!(
+            redactor.redact(r#"login password=hunter2 api_key: "k-123" access_token=t0k"#),
+            r#"login password=[REDACTED] api_key: "[REDACTED]" access_token=[REDACTED]"#
+        );
+        assert_eq!(
+            redactor.redact("Authorization: Bearer abc.def-ghi"),
+            "Authorization: Bearer [REDACTED]"
+        );
+        assert_eq!(
+            redactor.redact("jwt eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiI3In0.c2ln"),
+            "jwt [REDACTED]"
+        );
+        assert!(matches!(
+            redactor.redact("Pool has 4 connections"),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_configured_patterns_and_switches() {
+        let redactor = Redactor::new(false, false, &[String::from(r"\b\d{3}-\d{2}-\d{4}\b")]);
+        assert_eq!(
+            redactor.redact("ssn 123-45-6789 for ada@example.com"),
+            "ssn [REDACTED] for ada@example.com"
+        );
+        assert_eq!(
+            Redactor::disabled().redact("password=hunter2"),
+            "password=hunter2"
+        );
+    }
+}
@@ -0,0 +1,633 @@
+// src/core/infrastructure/logging/shipping.rs
+// Log shipping: forwards records to the `[[logging.sinks]]` — syslog, a TCP
+// or UDP collector, or an HTTP ingest URL. Each sink has its own thread that
+// sends records in batches and retries a failed batch with backoff before
+// dropping it, so a slow or unreachable collector never blocks logging.
+
+use chrono::Utc;
+use log::{Level, LevelFilter};
+use serde::Serialize;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::io::Write;
+use std::net::{TcpStream, ToSocketAddrs, UdpSocket};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::core::error::{
+    retry_with_backoff, AppError, AppResult, ErrorCode, ErrorValue, RetryPolicy,
+};
+use crate::core::infrastructure::config::{AppConfig, LogSinkSettings};
+use crate::core::infrastructure::http_client;
+use crate::core::infrastructure::secrets::{self, KeyringStore};
+
+use super::formatter::OwnedRecord;
+
+/// Records queued per sink before new ones are dropped
+const QUEUE_CAPACITY: usize = 10_000;
+
+const DEFAULT_BATCH_SIZE: usize = 100;
+const DEFAULT_FLUSH_INTERVAL_MS: u64 = 1000;
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const DEFAULT_SYSLOG_ADDRESS: &str = "127.0.0.1:514";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const RETRY_BACKOFF: Duration = Duration::from_millis(250);
+
+thread_local! {
+    /// Set on sink threads: what they log (including from reqwest) is not
+    /// shipped again, or a failing sink would feed itself
+    static ON_SINK_THREAD: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A log record as sent to a collector
+#[derive(Debug, Clone, Serialize)]
+pub struct ShippedRecord {
+    pub timestamp: String,
+    pub level: String,
+    pub module: String,
+    pub message: String,
+    pub host: String,
+    pub app: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+}
+
+struct SinkHandle {
+    name: String,
+    level: LevelFilter,
+    sender: SyncSender<ShippedRecord>,
+    thread: JoinHandle<()>,
+}
+
+pub struct LogShipper {
+    sinks: Mutex<Vec<SinkHandle>>,
+    host: String,
+    app: Mutex<String>,
+    dropped: AtomicU64,
+}
+
+impl LogShipper {
+    fn new() -> Self {
+        Self {
+            sinks: Mutex::new(Vec::new()),
+            host: hostname::get()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            app: Mutex::new(String::new()),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Start a thread per configured sink, replacing the running ones; the
+    /// old threads send what they have queued and exit
+    pub fn configure(&self, config: &AppConfig) {
+        if let Ok(mut app) = self.app.lock() {
+            *app = config.get_app_name().to_string();
+        }
+        let mut started = Vec::new();
+        for settings in config.get_log_sinks() {
+            match start_sink(config, settings) {
+                Ok(handle) => started.push(handle),
+                Err(e) => eprintln!("Warning: log sink '{}' not started: {}", settings.kind, e),
+            }
+        }
+        if let Ok(mut sinks) = self.sinks.lock() {
+            *sinks = started;
+        }
+    }
+
+    /// Queue `record` for every sink whose level includes it
+    pub fn push(&self, record: &OwnedRecord) {
+        if ON_SINK_THREAD.with(Cell::get) {
+            return;
+        }
+        let Ok(sinks) = self.sinks.lock() else {
+            return;
+        };
+        let mut shipped = None;
+        for sink in sinks.iter().filter(|sink| record.level <= sink.level) {
+            let shipped = shipped.get_or_insert_with(|| self.to_shipped(record));
+            if let Err(TrySendError::Full(_)) = sink.sender.try_send(shipped.clone()) {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Names of the running sinks, e.g. "http https://logs.example.com/ingest"
+    pub fn sink_names(&self) -> Vec<String> {
+        self.sinks
+            .lock()
+            .map(|sinks| sinks.iter().map(|sink| sink.name.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Stop every sink after it has sent what is queued, waiting at most
+    /// `timeout` for collectors that are slow to answer
+    pub fn shutdown(&self, timeout: Duration) {
+        let Ok(mut sinks) = self.sinks.lock() else {
+            return;
+        };
+        // Dropping the senders ends each thread after its last batch
+        let threads: Vec<JoinHandle<()>> = sinks.drain(..).map(|sink| sink.thread).collect();
+        let deadline = Instant::now() + timeout;
+        while threads.iter().any(|thread| !thread.is_finished()) && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Records dropped because a sink's queue was full
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    fn to_shipped(&self, record: &OwnedRecord) -> ShippedRecord {
+        ShippedRecord {
+            timestamp: record.time.with_timezone(&Utc).to_rfc3339(),
+            level: record.level.as_str().to_lowercase(),
+            module: record.module.clone(),
+            message: record.message.clone(),
+            host: self.host.clone(),
+            app: self.app.lock().map(|app| app.clone()).unwrap_or_default(),
+            correlation_id: record.correlation_id.clone(),
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Fed by the installed `Logger`
+    pub static ref GLOBAL_LOG_SHIPPER: LogShipper = LogShipper::new();
+}
+
+/// Sends one batch; an error leaves the sink ready to try again
+trait Sink: Send {
+    fn send(&mut self, batch: &[ShippedRecord]) -> AppResult<()>;
+}
+
+struct BatchOptions {
+    batch_size: usize,
+    flush_interval: Duration,
+    max_retries: u32,
+}
+
+impl BatchOptions {
+    fn from_settings(settings: &LogSinkSettings) -> Self {
+        Self {
+            batch_size: settings.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1),
+            flush_interval: Duration::from_millis(
+                settings
+                    .flush_interval_ms
+                    .unwrap_or(DEFAULT_FLUSH_INTERVAL_MS),
+            ),
+            max_retries: settings.max_retries.unwrap_or(DEFAULT_MAX_RETRIES),
+        }
+    }
+}
+
+fn start_sink(config: &AppConfig, settings: &LogSinkSettings) -> AppResult<SinkHandle> {
+    let (name, sink): (String, Box<dyn Sink>) = match settings.kind.as_str() {
+        "syslog" => {
+            let address = settings
+                .address
+                .as_deref()
+                .unwrap_or(DEFAULT_SYSLOG_ADDRESS);
+            (
+                format!("syslog {}", address),
+                Box::new(SyslogSink::new(address)?),
+            )
+        }
+        "tcp" => {
+            let address = required(settings, "address", settings.address.as_deref())?;
+            (
+                format!("tcp {}", address),
+                Box::new(TcpSink {
+                    address: address.to_string(),
+                    stream: None,
+                }),
+            )
+        }
+        "udp" => {
+            let address = required(settings, "address", settings.address.as_deref())?;
+            (format!("udp {}", address), Box::new(UdpSink::new(address)?))
+        }
+        "http" => {
+            let url = required(settings, "url", settings.url.as_deref())?;
+            (
+                format!("http {}", url),
+                Box::new(HttpSink::new(config, url, settings.headers.as_ref())?),
+            )
+        }
+        other => return Err(sink_error(other, "unknown sink kind")),
+    };
+    let level = settings
+        .level
+        .as_deref()
+        .and_then(|level| LevelFilter::from_str(level).ok())
+        .unwrap_or_else(|| {
+            LevelFilter::from_str(config.get_log_level()).unwrap_or(LevelFilter::Info)
+        });
+    let options = BatchOptions::from_settings(settings);
+    let (sender, receiver) = mpsc::sync_channel(QUEUE_CAPACITY);
+    let thread_name = name.clone();
+    let thread = std::thread::Builder::new()
+        .name(format!("log-sink-{}", settings.kind))
+        .spawn(move || run(&thread_name, sink, options, receiver))
+        .map_err(|e| sink_error(&name, e))?;
+    Ok(SinkHandle {
+        name,
+        level,
+        sender,
+        thread,
+    })
+}
+
+fn required<'a>(
+    settings: &LogSinkSettings,
+    field: &str,
+    value: Option<&'a str>,
+) -> AppResult<&'a str> {
+    value.ok_or_else(|| {
+        AppError::Validation(
+            ErrorValue::new(
+                ErrorCode::MissingRequiredField,
+                format!("A {} logging sink needs {}", settings.kind, field),
+            )
+            .with_field(field),
+        )
+    })
+}
+
+/// Collect records until the batch is full or the flush interval has passed
+/// since its first record, then send it
+fn run(
+    name: &str,
+    mut sink: Box<dyn Sink>,
+    options: BatchOptions,
+    receiver: Receiver<ShippedRecord>,
+) {
+    ON_SINK_THREAD.with(|flag| flag.set(true));
+    let mut batch = Vec::with_capacity(options.batch_size);
+    let mut deadline: Option<Instant> = None;
+    loop {
+        let received = match deadline {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        let disconnected = match received {
+            Ok(record) => {
+                batch.push(record);
+                deadline.get_or_insert_with(|| Instant::now() + options.flush_interval);
+                if batch.len() < options.batch_size {
+                    continue;
+                }
+                false
+            }
+            Err(RecvTimeoutError::Timeout) => false,
+            Err(RecvTimeoutError::Disconnected) => true,
+        };
+        if !batch.is_empty() {
+            send_with_retry(name, sink.as_mut(), &batch, options.max_retries);
+            batch.clear();
+        }
+        deadline = None;
+        if disconnected {
+            return;
+        }
+    }
+}
+
+fn send_with_retry(name: &str, sink: &mut dyn Sink, batch: &[ShippedRecord], max_retries: u32) {
+    // Any failure is worth another try: the collector may come back
+    let policy =
+        RetryPolicy::new(max_retries + 1, RETRY_BACKOFF, RETRY_BACKOFF * 32).retry_on(|_| true);
+    if let Err(e) = retry_with_backoff(&policy, |_| sink.send(batch)) {
+        eprintln!(
+            "Warning: dropped {} log records for sink {}: {}",
+            batch.len(),
+            name,
+            e
+        );
+    }
+}
+
+/// One JSON record per line over a TCP connection, reconnecting after errors
+struct TcpSink {
+    address: String,
+    stream: Option<TcpStream>,
+}
+
+impl Sink for TcpSink {
+    fn send(&mut self, batch: &[ShippedRecord]) -> AppResult<()> {
+        let mut payload = Vec::new();
+        for record in batch {
+            serde_json::to_writer(&mut payload, record)
+                .map_err(|e| sink_error(&self.address, e))?;
+            payload.push(b'\n');
+        }
+        let mut stream = match self.stream.take() {
+            Some(stream) => stream,
+            None => connect(&self.address)?,
+        };
+        stream
+            .write_all(&payload)
+            .and_then(|()| stream.flush())
+            .map_err(|e| sink_error(&self.address, e))?;
+        // Kept only while it works; the next batch reconnects otherwise
+        self.stream = Some(stream);
+        Ok(())
+    }
+}
+
+fn connect(address: &str) -> AppResult<TcpStream> {
+    let addr = resolve(address)?;
+    let stream =
+        TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).map_err(|e| sink_error(address, e))?;
+    let _ = stream.set_write_timeout(Some(CONNECT_TIMEOUT));
+    Ok(stream)
+}
+
+fn resolve(address: &str) -> AppResult<std::net::SocketAddr> {
+    address
+        .to_socket_addrs()
+        .map_err(|e| sink_error(address, e))?
+        .next()
+        .ok_or_else(|| sink_error(address, "address did not resolve"))
+}
+
+/// Datagrams to a host:port
+struct UdpTarget {
+    address: String,
+    socket: UdpSocket,
+}
+
+impl UdpTarget {
+    fn new(address: &str) -> AppResult<Self> {
+        let local = if resolve(address)?.is_ipv6() {
+            "[::]:0"
+        } else {
+            "0.0.0.0:0"
+        };
+        let socket = UdpSocket::bind(local).map_err(|e| sink_error(address, e))?;
+        Ok(Self {
+            address: address.to_string(),
+            socket,
+        })
+    }
+
+    fn send(&self, datagram: &[u8]) -> AppResult<()> {
+        self.socket
+            .send_to(datagram, self.address.as_str())
+            .map(|_| ())
+            .map_err(|e| sink_error(&self.address, e))
+    }
+}
+
+/// One JSON record per datagram
+struct UdpSink(UdpTarget);
+
+impl UdpSink {
+    fn new(address: &str) -> AppResult<Self> {
+        UdpTarget::new(address).map(Self)
+    }
+}
+
+impl Sink for UdpSink {
+    fn send(&mut self, batch: &[ShippedRecord]) -> AppResult<()> {
+        for record in batch {
+            let datagram =
+                serde_json::to_vec(record).map_err(|e| sink_error(&self.0.address, e))?;
+            self.0.send(&datagram)?;
+        }
+        Ok(())
+    }
+}
+
+/// RFC 5424 messages over UDP, or to a local socket such as /dev/log
+enum SyslogSink {
+    Udp(UdpTarget),
+    #[cfg(unix)]
+    Local(String, std::os::unix::net::UnixDatagram),
+}
+
+impl SyslogSink {
+    fn new(address: &str) -> AppResult<Self> {
+        #[cfg(unix)]
+        if address.starts_with('/') {
+            let socket =
+                std::os::unix::net::UnixDatagram::unbound().map_err(|e| sink_error(address, e))?;
+            return Ok(Self::Local(address.to_string(), socket));
+        }
+        UdpTarget::new(address).map(Self::Udp)
+    }
+}
+
+impl Sink for SyslogSink {
+    fn send(&mut self, batch: &[ShippedRecord]) -> AppResult<()> {
+        for record in batch {
+            let message = syslog_message(record);
+            match self {
+                Self::Udp(target) => target.send(message.as_bytes())?,
+                #[cfg(unix)]
+                Self::Local(path, socket) => {
+                    socket
+                        .send_to(message.as_bytes(), path.as_str())
+                        .map_err(|e| sink_error(path, e))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `<PRI>1 TIMESTAMP HOST APP PROCID MSGID - MSG`, facility "user"
+fn syslog_message(record: &ShippedRecord) -> String {
+    let severity = match Level::from_str(&record.level) {
+        Ok(Level::Error) => 3,
+        Ok(Level::Warn) => 4,
+        Ok(Level::Info) => 6,
+        _ => 7,
+    };
+    let field = |value: &str| {
+        if value.is_empty() {
+            String::from("-")
+        } else {
+            value.replace(' ', "_")
+        }
+    };
+    // 32473 is the private enterprise number reserved for examples
+    let structured_data = match &record.correlation_id {
+        Some(id) => format!(
+            r#"[request@32473 correlation_id="{}"]"#,
+            id.replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace(']', "\\]")
+        ),
+        None => String::from("-"),
+    };
+    format!(
+        "<{}>1 {} {} {} {} {} {} {}",
+        8 + severity,
+        record.timestamp,
+        field(&record.host),
+        field(&record.app),
+        std::process::id(),
+        field(&record.module),
+        structured_data,
+        record.message
+    )
+}
+
+/// Each batch POSTed as a JSON array
+struct HttpSink {
+    url: String,
+    client: reqwest::blocking::Client,
+    headers: reqwest::header::HeaderMap,
+}
+
+impl HttpSink {
+    fn new(
+        config: &AppConfig,
+        url: &str,
+        headers: Option<&HashMap<String, String>>,
+    ) -> AppResult<Self> {
+        let store = KeyringStore::new(config.get_secrets_service());
+        let mut header_map = reqwest::header::HeaderMap::new();
+        for (name, value) in headers.into_iter().flatten() {
+            let value = secrets::resolve(&store, value)?;
+            let name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| sink_error(url, e))?;
+            let value =
+                reqwest::header::HeaderValue::from_str(&value).map_err(|e| sink_error(url, e))?;
+            header_map.insert(name, value);
+        }
+        let client = http_client::builder_for(config)?
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|e| sink_error(url, e))?;
+        Ok(Self {
+            url: url.to_string(),
+            client,
+            headers: header_map,
+        })
+    }
+}
+
+impl Sink for HttpSink {
+    fn send(&mut self, batch: &[ShippedRecord]) -> AppResult<()> {
+        self.client
+            .post(&self.url)
+            .headers(self.headers.clone())
+            .json(batch)
+            .send()
+            .and_then(reqwest::blocking::Response::error_for_status)
+            .map(|_| ())
+            .map_err(|e| sink_error(&self.url, e))
+    }
+}
+
+fn sink_error(sink: &str, cause: impl ToString) -> AppError {
+    AppError::Logging(
+        ErrorValue::new(ErrorCode::InternalError, "Failed to ship log records")
+            .with_cause(cause.to_string())
+            .with_context("sink", sink),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+
+    fn record(level: &str, message: &str) -> ShippedRecord {
+        ShippedRecord {
+            timestamp: String::from("2026-01-02T03:04:05+00:00"),
+            level: level.to_string(),
+            module: String::from("app::db"),
+            message: message.to_string(),
+            host: String::from("kiosk-7"),
+            app: String::from("Rust WebUI Application"),
+            correlation_id: None,
+        }
+    }
+
+    #[test]
+    fn test_syslog_message_format() {
+        let message = syslog_message(&record("warn", "Pool exhausted"));
+        let expected = format!(
+            "<12>1 2026-01-02T03:04:05+00:00 kiosk-7 Rust_WebUI_Application {} app::db - Pool exhausted",
+            std::process::id()
+        );
+        assert_eq!(message, expected);
+        assert!(syslog_message(&record("trace", "x")).starts_with("<15>1 "));
+
+        let tagged = ShippedRecord {
+            correlation_id: Some(String::from("9f2c")),
+            ..record("info", "Saved")
+        };
+        assert!(syslog_message(&tagged)
+            .ends_with(r#" app::db [request@32473 correlation_id="9f2c"] Saved"#));
+    }
+
+    #[test]
+    fn test_tcp_sink_sends_json_lines() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let mut sink = TcpSink {
+            address,
+            stream: None,
+        };
+        sink.send(&[record("info", "one"), record("error", "two")])
+            .unwrap();
+
+        let (stream, _) = listener.accept().unwrap();
+        let lines: Vec<serde_json::Value> = BufReader::new(stream)
+            .lines()
+            .take(2)
+            .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+            .collect();
+        assert_eq!(lines[0]["message"], "one");
+        assert_eq!(lines[1]["level"], "error");
+    }
+
+    /// Fails the first `failures` sends
+    struct Flaky {
+        failures: u32,
+        sent: Vec<usize>,
+    }
+
+    impl Sink for Flaky {
+        fn send(&mut self, batch: &[ShippedRecord]) -> AppResult<()> {
+            if self.failures > 0 {
+                self.failures -= 1;
+                return Err(sink_error("flaky", "unreachable"));
+            }
+            self.sent.push(batch.len());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_failed_batches_are_retried_then_dropped() {
+        let batch = [record("info", "one")];
+        let mut sink = Flaky {
+            failures: 1,
+            sent: Vec::new(),
+        };
+        send_with_retry("flaky", &mut sink, &batch, 1);
+        assert_eq!(sink.sent, [1]);
+
+        let mut sink = Flaky {
+            failures: 2,
+            sent: Vec::new(),
+        };
+        send_with_retry("flaky", &mut sink, &batch, 1);
+        assert!(sink.sent.is_empty());
+    }
+}
@@ -0,0 +1,206 @@
+// src/core/infrastructure/logging/spans.rs
+// Bridge from `tracing` to the `log`-based Logger: handler calls and
+// database operations run in spans, and each span is logged with its fields
+// and elapsed time when it closes. Spans slower than the threshold are
+// logged as warnings so they stand out in the log viewer.
+
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Registry;
+
+/// Spans at least this long are logged at warn level when
+/// `[logging] slow_span_ms` is not set
+pub const DEFAULT_SLOW_SPAN_MS: u64 = 100;
+
+static SLOW_SPAN_MS: AtomicU64 = AtomicU64::new(DEFAULT_SLOW_SPAN_MS);
+
+/// Install the span layer as the global `tracing` subscriber
+pub fn install(slow_span_ms: u64) -> Result<(), tracing::subscriber::SetGlobalDefaultError> {
+    set_slow_threshold(slow_span_ms);
+    tracing::subscriber::set_global_default(Registry::default().with(SpanLogLayer))
+}
+
+/// Change the threshold, e.g. after a config reload
+pub fn set_slow_threshold(slow_span_ms: u64) {
+    SLOW_SPAN_MS.store(slow_span_ms, Ordering::Relaxed);
+}
+
+/// Fields of a span or event as `key=value` pairs
+#[derive(Default)]
+struct Fields {
+    message: Option<String>,
+    pairs: String,
+}
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "message" => self.message = Some(value.to_string()),
+            _ => self.record_debug(field, &value),
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{:?}", value));
+            return;
+        }
+        if !self.pairs.is_empty() {
+            self.pairs.push(' ');
+        }
+        let _ = write!(self.pairs, "{}={:?}", field.name(), value);
+    }
+}
+
+/// Kept in the span's extensions from creation to close
+struct Timing {
+    started: Instant,
+    fields: Fields,
+}
+
+struct SpanLogLayer;
+
+impl<S> Layer<S> for SpanLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        span.extensions_mut().insert(Timing {
+            started: Instant::now(),
+            fields,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        if let Some(timing) = extensions.get_mut::<Timing>() {
+            values.record(&mut timing.fields);
+        }
+    }
+
+    /// `tracing` events are passed on to the Logger, prefixed with the
+    /// span they happened in
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let level = to_log_level(metadata.level());
+        if level > log::max_level() {
+            return;
+        }
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let scope = ctx
+            .event_span(event)
+            .map(|span| format!("{}: ", span.name()))
+            .unwrap_or_default();
+        log::logger().log(
+            &log::Record::builder()
+                .level(level)
+                .target(metadata.target())
+                .module_path(metadata.module_path())
+                .file(metadata.file())
+                .line(metadata.line())
+                .args(format_args!(
+                    "{}{} {}",
+                    scope,
+                    fields.message.unwrap_or_default(),
+                    fields.pairs
+                ))
+                .build(),
+        );
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let extensions = span.extensions();
+        let Some(timing) = extensions.get::<Timing>() else {
+            return;
+        };
+        let elapsed = timing.started.elapsed();
+        let level = if elapsed.as_millis() as u64 >= SLOW_SPAN_MS.load(Ordering::Relaxed) {
+            log::Level::Warn
+        } else {
+            log::Level::Debug
+        };
+        if level > log::max_level() {
+            return;
+        }
+        let metadata = span.metadata();
+        log::logger().log(
+            &log::Record::builder()
+                .level(level)
+                .target(metadata.target())
+                .module_path(metadata.module_path())
+                .args(format_args!(
+                    "{}{{{}}} elapsed_ms={:.2}",
+                    metadata.name(),
+                    timing.fields.pairs,
+                    elapsed.as_secs_f64() * 1000.0
+                ))
+                .build(),
+        );
+    }
+}
+
+fn to_log_level(level: &tracing::Level) -> log::Level {
+    match *level {
+        tracing::Level::ERROR => log::Level::Error,
+        tracing::Level::WARN => log::Level::Warn,
+        tracing::Level::INFO => log::Level::Info,
+        tracing::Level::DEBUG => log::Level::Debug,
+        tracing::Level::TRACE => log::Level::Trace,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// Keeps what `Fields` collected from each new span
+    struct FieldsProbe(Arc<Mutex<Vec<String>>>);
+
+    impl<S: Subscriber> Layer<S> for FieldsProbe {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+            let mut fields = Fields::default();
+            attrs.record(&mut fields);
+            self.0.lock().unwrap().push(fields.pairs);
+        }
+    }
+
+    #[test]
+    fn test_span_fields_are_formatted_as_pairs() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let subscriber = Registry::default().with(FieldsProbe(seen.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info_span!("webui_call", handler = "get_users", window = 3_usize)
+                .in_scope(|| {});
+            tracing::debug_span!("db", op = "config_audit").in_scope(|| {});
+        });
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [r#"handler="get_users" window=3"#, r#"op="config_audit""#]
+        );
+    }
+
+    #[test]
+    fn test_log_levels_map_one_to_one() {
+        assert_eq!(to_log_level(&tracing::Level::WARN), log::Level::Warn);
+        assert_eq!(to_log_level(&tracing::Level::TRACE), log::Level::Trace);
+    }
+}
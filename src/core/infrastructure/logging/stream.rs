@@ -0,0 +1,94 @@
+// src/core/infrastructure/logging/stream.rs
+// Live follow for the log viewer: each following window gets the new records
+// matching its filters pushed as a `logs_stream_event`
+
+use log::error;
+use std::collections::HashMap;
+use std::sync::{Mutex, Once};
+use std::time::Duration;
+
+use super::buffer::{LogEntry, LogQuery, GLOBAL_LOG_BUFFER};
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::outbound_queue::{self, PushPriority};
+use crate::core::rpc::RpcResponse;
+
+/// CustomEvent whose `data` is `{ entries: LogEntry[] }`, sent to following windows
+pub const STREAM_EVENT: &str = "logs_stream_event";
+
+/// How often new records are collected and pushed
+const STREAM_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Most records pushed to a window per interval
+const MAX_BATCH: usize = 500;
+
+lazy_static::lazy_static! {
+    /// Window id -> filters, with `after` at the last record pushed
+    static ref FOLLOWERS: Mutex<HashMap<usize, LogQuery>> = Mutex::new(HashMap::new());
+}
+
+static STREAM_THREAD: Once = Once::new();
+
+/// Push records matching `query` to `window_id` from now on, or from
+/// `query.after` when given; returns the `seq` streaming starts after
+pub fn follow(window_id: usize, mut query: LogQuery) -> u64 {
+    let after = query.after.unwrap_or_else(|| GLOBAL_LOG_BUFFER.last_seq());
+    query.after = Some(after);
+    query.limit = Some(MAX_BATCH);
+    if let Ok(mut followers) = FOLLOWERS.lock() {
+        followers.insert(window_id, query);
+    }
+    STREAM_THREAD.call_once(start);
+    after
+}
+
+/// Stop pushing to `window_id`; false when it wasn't following
+pub fn unfollow(window_id: usize) -> bool {
+    FOLLOWERS
+        .lock()
+        .map(|mut followers| followers.remove(&window_id).is_some())
+        .unwrap_or(false)
+}
+
+/// The dispatcher logs each delivery at debug level; streaming those records
+/// would make every batch trigger the next
+fn is_own_delivery(entry: &LogEntry) -> bool {
+    entry.module.ends_with("outbound_queue") && entry.message.contains(STREAM_EVENT)
+}
+
+fn push_new_records() {
+    let Ok(mut followers) = FOLLOWERS.lock() else {
+        return;
+    };
+    for (window_id, query) in followers.iter_mut() {
+        let entries = GLOBAL_LOG_BUFFER.tail(query);
+        let Some(last) = entries.last() else {
+            continue;
+        };
+        query.after = Some(last.seq);
+        let entries: Vec<LogEntry> = entries
+            .into_iter()
+            .filter(|entry| !is_own_delivery(entry))
+            .collect();
+        if !entries.is_empty() {
+            let detail = RpcResponse::ok(serde_json::json!({ "entries": entries })).to_json();
+            outbound_queue::push_event(
+                *window_id,
+                STREAM_EVENT,
+                codec::shared().encode(*window_id, STREAM_EVENT, &detail),
+                PushPriority::Telemetry,
+            );
+        }
+    }
+}
+
+fn start() {
+    let spawned = std::thread::Builder::new()
+        .name("log-stream".to_string())
+        .spawn(|| loop {
+            std::thread::sleep(STREAM_INTERVAL);
+            push_new_records();
+        });
+    if let Err(e) = spawned {
+        error!("Failed to start log stream: {}", e);
+    }
+}
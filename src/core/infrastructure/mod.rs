@@ -1,9 +1,37 @@
 // src/core/infrastructure/mod.rs
 // Infrastructure services - database, config, logging, DI, event bus, error handling
 
+pub mod api_keys;
+pub mod auth;
+pub mod bridge_signing;
+pub mod client_registry;
+pub mod codec;
 pub mod config;
+pub mod config_template;
+pub mod config_watcher;
+pub mod crash_reporter;
 pub mod database;
 pub mod di;
 pub mod error_handler;
+pub mod error_stats;
+pub mod event_bridge;
 pub mod event_bus;
+pub mod event_middleware;
+pub mod event_scheduler;
+pub mod http_client;
 pub mod logging;
+pub mod openapi;
+pub mod outbound_queue;
+pub mod payload_limits;
+pub mod rbac;
+pub mod remote_access;
+pub mod remote_config;
+pub mod sanitize;
+pub mod schema_registry;
+pub mod secrets;
+pub mod security_audit;
+pub mod service_modules;
+pub mod sessions;
+pub mod settings;
+pub mod two_factor;
+pub mod vault;
\ No newline at end of file
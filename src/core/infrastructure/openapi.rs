@@ -0,0 +1,176 @@
+// src/core/infrastructure/openapi.rs
+// OpenAPI document generation from registered WebUI handlers
+
+#![allow(dead_code)]
+
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+
+type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+fn schema_of<T: JsonSchema>(gen: &mut SchemaGenerator) -> Schema {
+    gen.subschema_for::<T>()
+}
+
+/// Description of a handler bound on the WebUI window
+#[derive(Clone)]
+pub struct HandlerSpec {
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub tag: &'static str,
+    pub response_event: Option<&'static str>,
+    request: Option<SchemaFn>,
+    response: Option<SchemaFn>,
+}
+
+impl HandlerSpec {
+    pub fn new(name: &'static str, summary: &'static str) -> Self {
+        Self {
+            name,
+            summary,
+            tag: "default",
+            response_event: None,
+            request: None,
+            response: None,
+        }
+    }
+
+    pub fn tag(mut self, tag: &'static str) -> Self {
+        self.tag = tag;
+        self
+    }
+
+    /// CustomEvent name the response is dispatched as
+    pub fn event(mut self, event: &'static str) -> Self {
+        self.response_event = Some(event);
+        self
+    }
+
+    pub fn request<T: JsonSchema>(mut self) -> Self {
+        self.request = Some(schema_of::<T>);
+        self
+    }
+
+    pub fn response<T: JsonSchema>(mut self) -> Self {
+        self.response = Some(schema_of::<T>);
+        self
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref HANDLER_SPECS: Mutex<BTreeMap<&'static str, HandlerSpec>> = Mutex::new(BTreeMap::new());
+}
+
+/// Register a handler so it appears in the generated document
+pub fn register(spec: HandlerSpec) {
+    if let Ok(mut specs) = HANDLER_SPECS.lock() {
+        specs.insert(spec.name, spec);
+    }
+}
+
+/// Names of all registered handlers
+pub fn registered_handlers() -> Vec<&'static str> {
+    HANDLER_SPECS
+        .lock()
+        .map(|specs| specs.keys().copied().collect())
+        .unwrap_or_default()
+}
+
+/// Build an OpenAPI 3.0 document describing every registered handler
+pub fn generate_spec(title: &str, version: &str) -> serde_json::Value {
+    let specs: Vec<HandlerSpec> = HANDLER_SPECS
+        .lock()
+        .map(|specs| specs.values().cloned().collect())
+        .unwrap_or_default();
+
+    let mut gen = SchemaSettings::openapi3().into_generator();
+    let mut paths = serde_json::Map::new();
+
+    for spec in specs {
+        let description = match spec.response_event {
+            Some(event) => format!("Response dispatched as the `{}` CustomEvent", event),
+            None => "Handler does not send a response".to_string(),
+        };
+
+        let mut response = serde_json::json!({ "description": description });
+        if let Some(schema) = spec.response {
+            response["content"] = serde_json::json!({
+                "application/json": { "schema": schema(&mut gen) }
+            });
+        }
+
+        let mut operation = serde_json::json!({
+            "operationId": spec.name,
+            "summary": spec.summary,
+            "tags": [spec.tag],
+            "responses": { "200": response },
+        });
+        if let Some(schema) = spec.request {
+            operation["requestBody"] = serde_json::json!({
+                "required": true,
+                "content": { "application/json": { "schema": schema(&mut gen) } }
+            });
+        }
+
+        paths.insert(
+            format!("/api/{}", spec.name),
+            serde_json::json!({ "post": operation }),
+        );
+    }
+
+    serde_json::json!({
+        "openapi": "3.0.3",
+        "info": { "title": title, "version": version },
+        "paths": paths,
+        "components": { "schemas": gen.definitions() },
+    })
+}
+
+/// Write the generated document to `<root>/api/openapi.json`
+pub fn write_spec(root: &Path, title: &str, version: &str) -> AppResult<()> {
+    let api_dir = root.join("api");
+    std::fs::create_dir_all(&api_dir)?;
+
+    let spec = serde_json::to_string_pretty(&generate_spec(title, version))?;
+    std::fs::write(api_dir.join("openapi.json"), spec).map_err(|e| {
+        AppError::Serialization(
+            ErrorValue::new(ErrorCode::InternalError, "Failed to write OpenAPI document")
+                .with_cause(e.to_string())
+                .with_context("path", api_dir.display().to_string()),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct PingRequest {
+        message: String,
+    }
+
+    #[test]
+    fn test_generate_spec_contains_handler() {
+        register(
+            HandlerSpec::new("test_ping", "Ping the backend")
+                .tag("test")
+                .event("test_ping_response")
+                .request::<PingRequest>()
+                .response::<String>(),
+        );
+
+        let spec = generate_spec("Test", "1.0.0");
+        let operation = &spec["paths"]["/api/test_ping"]["post"];
+        assert_eq!(operation["operationId"], "test_ping");
+        assert!(operation.get("requestBody").is_some());
+        assert!(spec["components"]["schemas"].get("PingRequest").is_some());
+    }
+}
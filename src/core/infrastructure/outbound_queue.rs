@@ -0,0 +1,609 @@
+// src/core/infrastructure/outbound_queue.rs
+// Prioritized backend → frontend message queue with coalescing and backpressure
+
+#![allow(dead_code)]
+
+use log::{debug, error, info, warn};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use super::client_registry::GLOBAL_CLIENT_REGISTRY;
+use super::codec;
+use super::config::AppConfig;
+use super::payload_limits;
+
+/// Maximum messages delivered per dispatcher wake-up
+const MAX_BATCH_SIZE: usize = 64;
+
+/// Buffered events may grow past the congestion depth by this factor before
+/// the oldest ones are dropped anyway
+const BUFFER_HARD_LIMIT_FACTOR: usize = 4;
+
+/// In-flight credit is reclaimed if the frontend stops acknowledging, e.g.
+/// after a page reload dropped the pending acks
+const ACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Delivery priority for pushed messages, highest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PushPriority {
+    /// UI-critical updates (handler responses, errors)
+    Critical = 0,
+    /// Regular event traffic
+    Normal = 1,
+    /// Telemetry and stats that can lag behind
+    Telemetry = 2,
+}
+
+impl PushPriority {
+    const COUNT: usize = 3;
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// What to do with an event type while the frontend is congested
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackpressurePolicy {
+    /// Keep everything, up to a hard memory limit
+    #[default]
+    Buffer,
+    /// Discard the oldest pending message of the same event type
+    DropOldest,
+    /// Keep only every n-th message of the event type
+    Sample(u32),
+}
+
+impl BackpressurePolicy {
+    /// Parse "buffer", "drop_oldest" or "sample:<n>"
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "buffer" => Some(Self::Buffer),
+            "drop_oldest" | "drop-oldest" => Some(Self::DropOldest),
+            other => other
+                .strip_prefix("sample:")
+                .and_then(|n| n.parse::<u32>().ok())
+                .filter(|n| *n > 0)
+                .map(Self::Sample),
+        }
+    }
+}
+
+/// A CustomEvent waiting to be dispatched into a window
+#[derive(Debug, Clone)]
+pub struct OutgoingMessage {
+    pub window_id: usize,
+    pub event_name: String,
+    pub detail: serde_json::Value,
+    pub priority: PushPriority,
+    /// Messages sharing a key replace each other while still pending
+    pub coalesce_key: Option<String>,
+}
+
+impl OutgoingMessage {
+    pub fn new(
+        window_id: usize,
+        event_name: impl Into<String>,
+        detail: serde_json::Value,
+        priority: PushPriority,
+    ) -> Self {
+        Self {
+            window_id,
+            event_name: event_name.into(),
+            detail,
+            priority,
+            coalesce_key: None,
+        }
+    }
+
+    pub fn coalesce(mut self, key: impl Into<String>) -> Self {
+        self.coalesce_key = Some(key.into());
+        self
+    }
+
+    /// JavaScript that dispatches this message as a CustomEvent and
+    /// acknowledges it so the dispatcher can release flow-control credit.
+    /// The event name is a JSON string literal, so quotes in it stay inside.
+    pub fn to_js(&self) -> String {
+        let event_name = serde_json::to_string(&self.event_name)
+            .unwrap_or_else(|_| "\"\"".to_string());
+        format!(
+            "window.dispatchEvent(new CustomEvent({}, {{ detail: {} }})); \
+             if (typeof window.outbound_ack === 'function') {{ window.outbound_ack('1'); }}",
+            event_name, self.detail
+        )
+    }
+}
+
+/// Snapshot of queue activity
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct OutboundStats {
+    pub pending: usize,
+    pub enqueued: u64,
+    pub coalesced: u64,
+    pub delivered: u64,
+    pub dropped: u64,
+    /// Delivered but not yet acknowledged by the frontend
+    pub in_flight: usize,
+    pub congested: bool,
+}
+
+/// Flow-control limits applied to the queue
+#[derive(Debug, Clone)]
+pub struct BackpressureConfig {
+    pub max_queue_depth: usize,
+    pub max_in_flight: usize,
+    pub default_policy: BackpressurePolicy,
+    pub policies: HashMap<String, BackpressurePolicy>,
+}
+
+impl Default for BackpressureConfig {
+    fn default() -> Self {
+        Self {
+            max_queue_depth: 256,
+            max_in_flight: 32,
+            default_policy: BackpressurePolicy::Buffer,
+            policies: HashMap::new(),
+        }
+    }
+}
+
+impl BackpressureConfig {
+    pub fn from_app_config(config: &AppConfig) -> Self {
+        let mut policies = HashMap::new();
+        for (event_name, name) in config.get_backpressure_policies() {
+            match BackpressurePolicy::from_name(&name) {
+                Some(policy) => {
+                    policies.insert(event_name, policy);
+                }
+                None => warn!(
+                    "Unknown backpressure policy '{}' for '{}', using buffer",
+                    name, event_name
+                ),
+            }
+        }
+
+        Self {
+            max_queue_depth: config.get_max_queue_depth(),
+            max_in_flight: config.get_max_in_flight(),
+            default_policy: BackpressurePolicy::Buffer,
+            policies,
+        }
+    }
+
+    fn policy_for(&self, event_name: &str) -> BackpressurePolicy {
+        self.policies
+            .get(event_name)
+            .copied()
+            .unwrap_or(self.default_policy)
+    }
+}
+
+struct QueueState {
+    queues: [VecDeque<OutgoingMessage>; PushPriority::COUNT],
+    stats: OutboundStats,
+    config: BackpressureConfig,
+    sample_counters: HashMap<String, u32>,
+    was_congested: bool,
+    last_ack: Instant,
+}
+
+impl QueueState {
+    fn pending(&self) -> usize {
+        self.queues.iter().map(|q| q.len()).sum()
+    }
+
+    fn is_congested(&self) -> bool {
+        self.pending() >= self.config.max_queue_depth
+            || self.stats.in_flight >= self.config.max_in_flight
+    }
+
+    /// Remove the oldest pending message, preferring the given event type.
+    /// Critical messages are handler responses and are never dropped;
+    /// returns false when nothing else is pending.
+    fn drop_oldest(&mut self, event_name: &str) -> bool {
+        let droppable = &mut self.queues[PushPriority::Critical.index() + 1..];
+        for queue in droppable.iter_mut().rev() {
+            if let Some(pos) = queue.iter().position(|m| m.event_name == event_name) {
+                queue.remove(pos);
+                return true;
+            }
+        }
+        // Fall back to the lowest-priority message of any type
+        droppable.iter_mut().rev().any(|q| q.pop_front().is_some())
+    }
+}
+
+pub struct OutboundQueue {
+    state: Mutex<QueueState>,
+    ready: Condvar,
+}
+
+impl OutboundQueue {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                queues: Default::default(),
+                stats: OutboundStats::default(),
+                config: BackpressureConfig::default(),
+                sample_counters: HashMap::new(),
+                was_congested: false,
+                last_ack: Instant::now(),
+            }),
+            ready: Condvar::new(),
+        }
+    }
+
+    pub fn configure(&self, config: BackpressureConfig) {
+        if let Ok(mut state) = self.state.lock() {
+            state.config = config;
+        }
+    }
+
+    pub fn set_policy(&self, event_name: &str, policy: BackpressurePolicy) {
+        if let Ok(mut state) = self.state.lock() {
+            state.config.policies.insert(event_name.to_string(), policy);
+        }
+    }
+
+    /// Enqueue a message, replacing a pending one with the same coalesce key
+    /// and applying the event's backpressure policy while congested
+    pub fn push(&self, message: OutgoingMessage) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.stats.enqueued += 1;
+
+        let congested = state.is_congested();
+        if congested != state.was_congested {
+            state.was_congested = congested;
+            if congested {
+                warn!(
+                    "Frontend congested: {} pending, {} in flight",
+                    state.pending(),
+                    state.stats.in_flight
+                );
+            } else {
+                info!("Frontend caught up, backpressure released");
+                state.sample_counters.clear();
+            }
+        }
+
+        // Critical messages bypass policies; they are handler responses
+        if congested && message.priority != PushPriority::Critical {
+            match state.config.policy_for(&message.event_name) {
+                BackpressurePolicy::Buffer => {
+                    let hard_limit = state.config.max_queue_depth * BUFFER_HARD_LIMIT_FACTOR;
+                    if state.pending() >= hard_limit {
+                        // Only Critical messages left: the new one goes instead
+                        state.stats.dropped += 1;
+                        if !state.drop_oldest(&message.event_name) {
+                            return;
+                        }
+                    }
+                }
+                BackpressurePolicy::DropOldest => {
+                    state.stats.dropped += 1;
+                    if !state.drop_oldest(&message.event_name) {
+                        return;
+                    }
+                }
+                BackpressurePolicy::Sample(every) => {
+                    let counter = state
+                        .sample_counters
+                        .entry(message.event_name.clone())
+                        .or_insert(0);
+                    *counter += 1;
+                    if *counter % every != 0 {
+                        state.stats.dropped += 1;
+                        return;
+                    }
+                }
+            }
+        }
+
+        let queue = &mut state.queues[message.priority.index()];
+        if let Some(ref key) = message.coalesce_key {
+            if let Some(pending) = queue.iter_mut().find(|m| {
+                m.window_id == message.window_id && m.coalesce_key.as_deref() == Some(key.as_str())
+            }) {
+                *pending = message;
+                state.stats.coalesced += 1;
+                return;
+            }
+        }
+
+        queue.push_back(message);
+        drop(state);
+        self.ready.notify_one();
+    }
+
+    /// Take up to `max` messages, highest priority first
+    pub fn drain(&self, max: usize) -> Vec<OutgoingMessage> {
+        let Ok(mut state) = self.state.lock() else {
+            return Vec::new();
+        };
+        Self::take_batch(&mut state, max)
+    }
+
+    /// Block until messages are available or the timeout elapses, then drain
+    pub fn wait_and_drain(&self, timeout: Duration, max: usize) -> Vec<OutgoingMessage> {
+        let Ok(state) = self.state.lock() else {
+            return Vec::new();
+        };
+        let Ok((mut state, _)) = self.ready.wait_timeout_while(state, timeout, |s| {
+            s.pending() == 0 || s.stats.in_flight >= s.config.max_in_flight
+        }) else {
+            return Vec::new();
+        };
+        Self::take_batch(&mut state, max)
+    }
+
+    /// Release flow-control credit for messages the frontend has handled
+    pub fn acknowledge(&self, count: usize) {
+        if let Ok(mut state) = self.state.lock() {
+            state.stats.in_flight = state.stats.in_flight.saturating_sub(count);
+            state.last_ack = Instant::now();
+        }
+        self.ready.notify_one();
+    }
+
+    fn take_batch(state: &mut QueueState, max: usize) -> Vec<OutgoingMessage> {
+        if state.stats.in_flight >= state.config.max_in_flight
+            && state.last_ack.elapsed() >= ACK_TIMEOUT
+        {
+            warn!(
+                "No acknowledgements for {:?}, reclaiming {} in-flight messages",
+                ACK_TIMEOUT, state.stats.in_flight
+            );
+            state.stats.in_flight = 0;
+            state.last_ack = Instant::now();
+        }
+
+        let credit = state.config.max_in_flight.saturating_sub(state.stats.in_flight);
+        let max = max.min(credit);
+        let mut batch = Vec::new();
+        for queue in state.queues.iter_mut() {
+            while batch.len() < max {
+                match queue.pop_front() {
+                    Some(message) => batch.push(message),
+                    None => break,
+                }
+            }
+        }
+        state.stats.delivered += batch.len() as u64;
+        state.stats.in_flight += batch.len();
+        batch
+    }
+
+    /// Drop everything still pending for a window whose clients are gone
+    pub fn purge_window(&self, window_id: usize) -> usize {
+        let Ok(mut state) = self.state.lock() else {
+            return 0;
+        };
+        let before = state.pending();
+        for queue in state.queues.iter_mut() {
+            queue.retain(|m| m.window_id != window_id);
+        }
+        before - state.pending()
+    }
+
+    pub fn pending(&self) -> usize {
+        self.state.lock().map(|s| s.pending()).unwrap_or(0)
+    }
+
+    pub fn stats(&self) -> OutboundStats {
+        self.state
+            .lock()
+            .map(|s| OutboundStats {
+                pending: s.pending(),
+                congested: s.is_congested(),
+                ..s.stats.clone()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Default for OutboundQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+lazy_static::lazy_static! {
+    pub static ref GLOBAL_OUTBOUND_QUEUE: Arc<OutboundQueue> = Arc::new(OutboundQueue::new());
+}
+
+/// Queue a CustomEvent for a window on the global queue; oversized details
+/// are replaced by a PAYLOAD_TOO_LARGE error so the WebView is not stalled
+pub fn push_event(
+    window_id: usize,
+    event_name: &str,
+    detail: serde_json::Value,
+    priority: PushPriority,
+) {
+    let detail = payload_limits::limit_response(event_name, detail);
+    GLOBAL_OUTBOUND_QUEUE.push(OutgoingMessage::new(window_id, event_name, detail, priority));
+}
+
+/// Queue a CustomEvent for every window with a connected client, encoding
+/// `detail` in the format each window negotiated
+pub fn broadcast_event(event_name: &str, detail: &serde_json::Value, priority: PushPriority) {
+    let codec = codec::shared();
+    for window_id in GLOBAL_CLIENT_REGISTRY.connected_windows() {
+        push_event(window_id, event_name, codec.encode(window_id, event_name, detail), priority);
+    }
+}
+
+/// Acknowledge delivered messages on the global queue
+pub fn acknowledge(count: usize) {
+    GLOBAL_OUTBOUND_QUEUE.acknowledge(count);
+}
+
+/// Start the background thread that delivers queued messages via `deliver`
+pub fn start_dispatcher<F>(deliver: F)
+where
+    F: Fn(&OutgoingMessage) + Send + 'static,
+{
+    let queue = Arc::clone(&GLOBAL_OUTBOUND_QUEUE);
+    let spawned = std::thread::Builder::new()
+        .name("outbound-dispatcher".to_string())
+        .spawn(move || loop {
+            let batch = queue.wait_and_drain(Duration::from_millis(50), MAX_BATCH_SIZE);
+            for message in &batch {
+                debug!(
+                    "Dispatching '{}' to window {} ({:?})",
+                    message.event_name, message.window_id, message.priority
+                );
+                deliver(message);
+            }
+        });
+
+    match spawned {
+        Ok(_) => info!("Outbound message dispatcher started"),
+        Err(e) => error!("Failed to start outbound dispatcher: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_order() {
+        let queue = OutboundQueue::new();
+        queue.push(OutgoingMessage::new(1, "stats", serde_json::json!(1), PushPriority::Telemetry));
+        queue.push(OutgoingMessage::new(1, "event", serde_json::json!(2), PushPriority::Normal));
+        queue.push(OutgoingMessage::new(1, "reply", serde_json::json!(3), PushPriority::Critical));
+
+        let names: Vec<String> = queue.drain(10).into_iter().map(|m| m.event_name).collect();
+        assert_eq!(names, vec!["reply", "event", "stats"]);
+    }
+
+    #[test]
+    fn test_to_js_quotes_event_name() {
+        let message = OutgoingMessage::new(
+            1,
+            "bus:x'));alert(1);//",
+            serde_json::json!({ "ok": true }),
+            PushPriority::Normal,
+        );
+        let js = message.to_js();
+        assert!(js.starts_with(r#"window.dispatchEvent(new CustomEvent("bus:x'));alert(1);//", "#));
+        assert!(js.contains(r#"{ detail: {"ok":true} }"#));
+    }
+
+    #[test]
+    fn test_coalescing_keeps_latest() {
+        let queue = OutboundQueue::new();
+        for i in 0..5 {
+            queue.push(
+                OutgoingMessage::new(1, "sysinfo", serde_json::json!(i), PushPriority::Telemetry)
+                    .coalesce("sysinfo"),
+            );
+        }
+
+        assert_eq!(queue.pending(), 1);
+        let batch = queue.drain(10);
+        assert_eq!(batch[0].detail, serde_json::json!(4));
+        assert_eq!(queue.stats().coalesced, 4);
+    }
+
+    #[test]
+    fn test_backpressure_policies() {
+        let queue = OutboundQueue::new();
+        let mut config = BackpressureConfig {
+            max_queue_depth: 2,
+            max_in_flight: 100,
+            ..Default::default()
+        };
+        config.policies.insert("ticks".into(), BackpressurePolicy::DropOldest);
+        config.policies.insert("stats".into(), BackpressurePolicy::Sample(2));
+        queue.configure(config);
+
+        for i in 0..5 {
+            queue.push(OutgoingMessage::new(1, "ticks", serde_json::json!(i), PushPriority::Normal));
+        }
+        // Depth stays at the congestion limit and the newest tick survives
+        assert_eq!(queue.pending(), 2);
+        let ticks: Vec<_> = queue.drain(10).into_iter().map(|m| m.detail).collect();
+        assert_eq!(ticks, vec![serde_json::json!(3), serde_json::json!(4)]);
+
+        queue.push(OutgoingMessage::new(1, "fill", serde_json::json!(0), PushPriority::Normal));
+        queue.push(OutgoingMessage::new(1, "fill", serde_json::json!(1), PushPriority::Normal));
+        for i in 0..4 {
+            queue.push(OutgoingMessage::new(1, "stats", serde_json::json!(i), PushPriority::Telemetry));
+        }
+        assert_eq!(queue.pending(), 4);
+        assert_eq!(queue.stats().dropped, 5);
+    }
+
+    #[test]
+    fn test_backpressure_never_drops_critical() {
+        let queue = OutboundQueue::new();
+        let mut config = BackpressureConfig {
+            max_queue_depth: 2,
+            max_in_flight: 100,
+            ..Default::default()
+        };
+        config.policies.insert("ticks".into(), BackpressurePolicy::DropOldest);
+        queue.configure(config);
+
+        for i in 0..3 {
+            queue.push(OutgoingMessage::new(1, "reply", serde_json::json!(i), PushPriority::Critical));
+        }
+        queue.push(OutgoingMessage::new(1, "ticks", serde_json::json!(0), PushPriority::Normal));
+        for i in 0..8 {
+            queue.push(OutgoingMessage::new(1, "event", serde_json::json!(i), PushPriority::Normal));
+        }
+
+        // Every reply survives; the tick and the buffered events over the
+        // hard limit are what gets dropped
+        let batch = queue.drain(100);
+        let replies = batch.iter().filter(|m| m.event_name == "reply").count();
+        assert_eq!(replies, 3);
+        assert!(batch.iter().all(|m| m.event_name != "ticks"));
+        assert_eq!(batch.len(), 2 * BUFFER_HARD_LIMIT_FACTOR);
+        assert_eq!(queue.stats().dropped, 4);
+    }
+
+    #[test]
+    fn test_in_flight_limit_and_ack() {
+        let queue = OutboundQueue::new();
+        queue.configure(BackpressureConfig {
+            max_in_flight: 2,
+            ..Default::default()
+        });
+        for i in 0..3 {
+            queue.push(OutgoingMessage::new(1, "event", serde_json::json!(i), PushPriority::Normal));
+        }
+
+        assert_eq!(queue.drain(10).len(), 2);
+        assert!(queue.drain(10).is_empty());
+        queue.acknowledge(2);
+        assert_eq!(queue.drain(10).len(), 1);
+        assert_eq!(queue.stats().in_flight, 1);
+    }
+
+    #[test]
+    fn test_purge_window() {
+        let queue = OutboundQueue::new();
+        queue.push(OutgoingMessage::new(1, "event", serde_json::json!(1), PushPriority::Normal));
+        queue.push(OutgoingMessage::new(2, "event", serde_json::json!(2), PushPriority::Normal));
+        queue.push(OutgoingMessage::new(2, "reply", serde_json::json!(3), PushPriority::Critical));
+
+        assert_eq!(queue.purge_window(2), 2);
+        assert_eq!(queue.drain(10)[0].window_id, 1);
+    }
+
+    #[test]
+    fn test_policy_from_name() {
+        assert_eq!(BackpressurePolicy::from_name("buffer"), Some(BackpressurePolicy::Buffer));
+        assert_eq!(BackpressurePolicy::from_name("drop_oldest"), Some(BackpressurePolicy::DropOldest));
+        assert_eq!(BackpressurePolicy::from_name("sample:3"), Some(BackpressurePolicy::Sample(3)));
+        assert_eq!(BackpressurePolicy::from_name("sample:0"), None);
+    }
+}
@@ -0,0 +1,139 @@
+// src/core/infrastructure/payload_limits.rs
+// Size limits for frontend requests and backend responses
+
+#![allow(dead_code)]
+
+use log::warn;
+use std::sync::Mutex;
+
+use super::config::AppConfig;
+use crate::core::error::{ErrorCode, ErrorValue};
+use crate::core::rpc::RpcResponse;
+
+/// Largest payloads accepted from and pushed to the frontend, in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadLimits {
+    pub max_request_bytes: usize,
+    pub max_response_bytes: usize,
+}
+
+impl Default for PayloadLimits {
+    fn default() -> Self {
+        Self {
+            max_request_bytes: 1024 * 1024,
+            max_response_bytes: 4 * 1024 * 1024,
+        }
+    }
+}
+
+impl PayloadLimits {
+    pub fn from_app_config(config: &AppConfig) -> Self {
+        Self {
+            max_request_bytes: config.get_max_request_bytes(),
+            max_response_bytes: config.get_max_response_bytes(),
+        }
+    }
+
+    /// Reject a request payload of `size` bytes sent to `handler` if it is too large
+    pub fn check_request(&self, handler: &str, size: usize) -> Result<(), ErrorValue> {
+        if size <= self.max_request_bytes {
+            return Ok(());
+        }
+        warn!(
+            "Rejected {} byte payload for '{}' (limit {})",
+            size, handler, self.max_request_bytes
+        );
+        Err(ErrorValue::new(ErrorCode::PayloadTooLarge, "Request payload is too large")
+            .with_details(format!(
+                "{} bytes exceeds the {} byte request limit",
+                size, self.max_request_bytes
+            ))
+            .with_context("handler", handler)
+            .with_context("size", size.to_string())
+            .with_context("limit", self.max_request_bytes.to_string()))
+    }
+
+    /// Return `detail` unchanged, or an error envelope with a pagination hint
+    /// when pushing it would hand the WebView an oversized script
+    pub fn limit_response(&self, event_name: &str, detail: serde_json::Value) -> serde_json::Value {
+        let size = detail.to_string().len();
+        if size <= self.max_response_bytes {
+            return detail;
+        }
+        warn!(
+            "Response '{}' is {} bytes (limit {}), sending a pagination hint instead",
+            event_name, size, self.max_response_bytes
+        );
+        let error = ErrorValue::new(ErrorCode::PayloadTooLarge, "Response is too large to deliver")
+            .with_details(format!(
+                "{} bytes exceeds the {} byte response limit; request smaller pages or stream the data",
+                size, self.max_response_bytes
+            ))
+            .with_context("event", event_name)
+            .with_context("size", size.to_string())
+            .with_context("limit", self.max_response_bytes.to_string())
+            .with_context("hint", "paginate");
+        RpcResponse::err(error).to_json()
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref GLOBAL_PAYLOAD_LIMITS: Mutex<PayloadLimits> = Mutex::new(PayloadLimits::default());
+}
+
+pub fn configure(limits: PayloadLimits) {
+    if let Ok(mut current) = GLOBAL_PAYLOAD_LIMITS.lock() {
+        *current = limits;
+    }
+}
+
+pub fn limits() -> PayloadLimits {
+    GLOBAL_PAYLOAD_LIMITS
+        .lock()
+        .map(|limits| *limits)
+        .unwrap_or_default()
+}
+
+/// Check an inbound payload against the global limits
+pub fn check_request(handler: &str, payload: &str) -> Result<(), ErrorValue> {
+    limits().check_request(handler, payload.len())
+}
+
+/// Apply the global response limit to an outbound detail
+pub fn limit_response(event_name: &str, detail: serde_json::Value) -> serde_json::Value {
+    limits().limit_response(event_name, detail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_limit() {
+        let limits = PayloadLimits {
+            max_request_bytes: 8,
+            max_response_bytes: 64,
+        };
+        assert!(limits.check_request("log_message", 8).is_ok());
+
+        let error = limits.check_request("log_message", 9).unwrap_err();
+        assert_eq!(error.code, ErrorCode::PayloadTooLarge);
+        assert_eq!(error.context.unwrap()["handler"], "log_message");
+    }
+
+    #[test]
+    fn test_oversized_response_becomes_hint() {
+        let limits = PayloadLimits {
+            max_request_bytes: 8,
+            max_response_bytes: 64,
+        };
+        let small = serde_json::json!({ "success": true, "data": [1, 2, 3] });
+        assert_eq!(limits.limit_response("db_response", small.clone()), small);
+
+        let large = serde_json::json!({ "success": true, "data": "x".repeat(100) });
+        let limited = limits.limit_response("db_response", large);
+        assert_eq!(limited["success"], false);
+        assert_eq!(limited["error"]["code"], "PAYLOAD_TOO_LARGE");
+        assert_eq!(limited["error"]["context"]["hint"], "paginate");
+    }
+}
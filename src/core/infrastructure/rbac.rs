@@ -0,0 +1,95 @@
+// src/core/infrastructure/rbac.rs
+// Role-based access control. Handlers name the permission they need, e.g.
+// "users:delete"; the caller's role (from its session, or `[security]
+// anonymous_role` for calls without one) must grant it in `[security.roles]`.
+// Calls made with an API key are checked against the key's scopes instead.
+
+use super::config::AppConfig;
+use super::database::ApiKeyRecord;
+use super::sessions::SessionInfo;
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+
+/// Who a handler call is made by
+#[derive(Debug, Clone)]
+pub struct Caller {
+    /// `None` for calls without a session
+    pub session: Option<SessionInfo>,
+    /// The key a call was made with, for calls from automation clients
+    pub api_key: Option<ApiKeyRecord>,
+    pub role: String,
+}
+
+impl Caller {
+    pub fn user_id(&self) -> Option<i64> {
+        self.session.as_ref().map(|s| s.user_id)
+    }
+
+    /// The permissions the caller has under `config`
+    pub fn permissions(&self, config: &AppConfig) -> Vec<String> {
+        match &self.api_key {
+            Some(key) => key.scopes.clone(),
+            None => config.get_role_permissions(&self.role),
+        }
+    }
+}
+
+/// Whether one of `granted` covers `permission`: an exact match, "*", or a
+/// "resource:*" wildcard
+pub fn grants(granted: &[String], permission: &str) -> bool {
+    granted.iter().any(|g| {
+        g == "*"
+            || g == permission
+            || g.strip_suffix(":*").is_some_and(|resource| {
+                permission
+                    .strip_prefix(resource)
+                    .is_some_and(|rest| rest.starts_with(':'))
+            })
+    })
+}
+
+/// Ok when `role` has `permission` under `config`
+pub fn check(config: &AppConfig, role: &str, permission: &str) -> AppResult<()> {
+    if grants(&config.get_role_permissions(role), permission) {
+        return Ok(());
+    }
+    Err(AppError::Validation(
+        ErrorValue::new(
+            ErrorCode::Forbidden,
+            format!("Your role doesn't allow {}", permission),
+        )
+        .with_context("role", role)
+        .with_context("permission", permission),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::infrastructure::config::SecuritySettings;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_permissions_and_wildcards() {
+        let granted = vec![String::from("users:*"), String::from("settings:read")];
+        assert!(grants(&granted, "users:delete"));
+        assert!(grants(&granted, "settings:read"));
+        assert!(!grants(&granted, "settings:write"));
+        assert!(!grants(&granted, "usersx:read"));
+        assert!(grants(&[String::from("*")], "anything"));
+
+        let mut config = AppConfig::default();
+        assert!(check(&config, "Admin", "users:delete").is_ok());
+        let denied = check(&config, "User", "users:delete").unwrap_err();
+        assert_eq!(denied.to_value().code, ErrorCode::Forbidden);
+
+        config.security = Some(SecuritySettings {
+            roles: Some(HashMap::from([(
+                String::from("Editor"),
+                vec![String::from("users:*")],
+            )])),
+            ..Default::default()
+        });
+        assert!(check(&config, "Editor", "users:delete").is_ok());
+        assert!(check(&config, "Admin", "users:delete").is_err());
+    }
+}
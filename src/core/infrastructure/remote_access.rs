@@ -0,0 +1,506 @@
+// src/core/infrastructure/remote_access.rs
+// LAN access gateway: token auth and client IP allowlist in front of WebUI
+
+use log::{debug, info, warn};
+use std::io::{Read, Write};
+use std::net::{IpAddr, Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::config::AppConfig;
+use super::di;
+use super::secrets::{self, SecretStore};
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::utils::crypto::CryptoUtils;
+use crate::utils::security::SecurityUtils;
+
+/// Cookie carrying the access token after the first authenticated request
+const TOKEN_COOKIE: &str = "webui_remote_token";
+/// Query parameter accepted on the first request, e.g. `/?token=...`
+const TOKEN_QUERY: &str = "token";
+/// Largest request head accepted before the token is checked
+const MAX_HEAD_SIZE: usize = 16 * 1024;
+const HEAD_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A single allowlist entry: an address or a CIDR range
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IpRule {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl IpRule {
+    /// Parse "192.168.1.20", "192.168.1.0/24" or "fd00::/8"
+    pub fn parse(rule: &str) -> Option<Self> {
+        let (addr, prefix) = match rule.trim().split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix.parse::<u8>().ok()?)),
+            None => (rule.trim(), None),
+        };
+        let network: IpAddr = addr.parse().ok()?;
+        let max = if network.is_ipv4() { 32 } else { 128 };
+        let prefix = prefix.unwrap_or(max);
+        if prefix > max {
+            return None;
+        }
+        Some(Self { network, prefix })
+    }
+
+    pub fn matches(&self, ip: IpAddr) -> bool {
+        match (self.network, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix as u32).unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Resolved remote access settings
+#[derive(Debug, Clone)]
+pub struct RemoteAccessConfig {
+    pub bind_address: String,
+    pub port: u16,
+    pub token: String,
+    pub allowed: Vec<IpRule>,
+    pub max_connections: usize,
+}
+
+impl RemoteAccessConfig {
+    /// Build from the app config, or `None` when remote access is disabled.
+    /// A token is mandatory; one is generated when none is configured or a
+    /// `secret:` token can't be read from `secrets`.
+    pub fn from_app_config(config: &AppConfig, secrets: &dyn SecretStore) -> Option<Self> {
+        if !config.is_remote_access_enabled() {
+            return None;
+        }
+
+        let configured = config.get_remote_token().and_then(|token| {
+            secrets::resolve(secrets, &token)
+                .map_err(|e| warn!("Remote access token unavailable: {}", e))
+                .ok()
+        });
+        let token = configured.unwrap_or_else(|| {
+            let token = generate_token();
+            warn!("No remote access token configured, generated one for this session");
+            token
+        });
+
+        let mut allowed = Vec::new();
+        for rule in config.get_remote_allowed_ips() {
+            match IpRule::parse(&rule) {
+                Some(parsed) => allowed.push(parsed),
+                None => warn!("Ignoring invalid remote allowlist entry '{}'", rule),
+            }
+        }
+        if allowed.is_empty() {
+            warn!("Remote access allowlist is empty, only loopback clients can connect");
+        }
+
+        Some(Self {
+            bind_address: config.get_remote_bind_address().to_string(),
+            port: config.get_remote_port(),
+            token,
+            allowed,
+            max_connections: config.get_remote_max_connections(),
+        })
+    }
+
+    pub fn is_ip_allowed(&self, ip: IpAddr) -> bool {
+        ip.is_loopback() || self.allowed.iter().any(|rule| rule.matches(ip))
+    }
+}
+
+/// Random 32-byte hex token
+pub fn generate_token() -> String {
+    hex::encode(CryptoUtils::random_bytes(32))
+}
+
+/// Tell the operator the URL that signs a browser in. Written to `out`
+/// (stdout) rather than logged, as log redaction masks the token.
+pub fn announce_token(out: &mut impl Write, addr: SocketAddr, token: &str) -> std::io::Result<()> {
+    writeln!(out, "Remote access: http://{}/?{}={}", addr, TOKEN_QUERY, token)
+}
+
+/// How a request presented the token
+#[derive(Debug, PartialEq, Eq)]
+enum TokenSource {
+    Header,
+    Cookie,
+    Query,
+}
+
+fn find_token(head: &str, token: &str) -> Option<TokenSource> {
+    let is_token = |value: &str| SecurityUtils::ct_eq(value.as_bytes(), token.as_bytes());
+    let mut lines = head.lines();
+    let request_line = lines.next()?;
+
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("authorization")
+            && value.strip_prefix("Bearer ").is_some_and(is_token)
+        {
+            return Some(TokenSource::Header);
+        }
+        if name.eq_ignore_ascii_case("cookie")
+            && value
+                .split(';')
+                .filter_map(|c| c.trim().split_once('='))
+                .any(|(k, v)| k == TOKEN_COOKIE && is_token(v))
+        {
+            return Some(TokenSource::Cookie);
+        }
+    }
+
+    let target = request_line.split_whitespace().nth(1)?;
+    let (_, query) = target.split_once('?')?;
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .any(|(k, v)| k == TOKEN_QUERY && is_token(v))
+        .then_some(TokenSource::Query)
+}
+
+/// Request target with the token query parameter removed
+fn strip_token_query(target: &str) -> String {
+    let Some((path, query)) = target.split_once('?') else {
+        return target.to_string();
+    };
+    let rest: Vec<&str> = query
+        .split('&')
+        .filter(|pair| !pair.starts_with(&format!("{}=", TOKEN_QUERY)))
+        .collect();
+    if rest.is_empty() {
+        path.to_string()
+    } else {
+        format!("{}?{}", path, rest.join("&"))
+    }
+}
+
+fn read_request_head(stream: &mut TcpStream) -> Option<Vec<u8>> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        if head.len() > MAX_HEAD_SIZE {
+            return None;
+        }
+        let n = stream.read(&mut buf).ok()?;
+        if n == 0 {
+            return None;
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+    Some(head)
+}
+
+/// Request header `name`, matched case-insensitively
+fn header_value<'a>(line: &'a [u8], name: &str) -> Option<&'a [u8]> {
+    let colon = line.iter().position(|&b| b == b':')?;
+    line[..colon]
+        .eq_ignore_ascii_case(name.as_bytes())
+        .then(|| line[colon + 1..].trim_ascii())
+}
+
+/// `head` rewritten to ask the WebUI server to close the connection after
+/// its response. The bytes that follow are piped unread, so a keep-alive
+/// connection would carry later requests past the token check; with the
+/// connection closed the browser sends them on a new one, which is checked.
+/// Websocket upgrades are left alone, as no HTTP request follows them.
+fn close_after_response(head: &[u8]) -> Vec<u8> {
+    let Some(end) = head.windows(4).position(|w| w == b"\r\n\r\n") else {
+        return head.to_vec();
+    };
+    let lines: Vec<&[u8]> = head[..end]
+        .split(|&b| b == b'\n')
+        .map(|l| l.trim_ascii_end())
+        .collect();
+    let is_websocket = lines
+        .iter()
+        .any(|l| header_value(l, "upgrade").is_some_and(|v| v.eq_ignore_ascii_case(b"websocket")));
+    if is_websocket {
+        return head.to_vec();
+    }
+
+    let mut rewritten = Vec::with_capacity(head.len() + 32);
+    for line in lines {
+        let hop_by_hop = ["connection", "keep-alive", "proxy-connection"]
+            .iter()
+            .any(|name| header_value(line, name).is_some());
+        if !hop_by_hop {
+            rewritten.extend_from_slice(line);
+            rewritten.extend_from_slice(b"\r\n");
+        }
+    }
+    rewritten.extend_from_slice(b"Connection: close\r\n\r\n");
+    rewritten.extend_from_slice(&head[end + 4..]);
+    rewritten
+}
+
+/// One of the gateway's `max_connections` slots, freed on drop
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl ConnectionSlot {
+    fn acquire(active: &Arc<AtomicUsize>, max: usize) -> Option<Self> {
+        active
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| (n < max).then_some(n + 1))
+            .ok()
+            .map(|_| Self(active.clone()))
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn reject(mut stream: TcpStream, status: &str) {
+    let body = format!("{}\n", status);
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let _ = stream.shutdown(Shutdown::Both);
+}
+
+fn pipe(mut from: TcpStream, mut to: TcpStream) {
+    let _ = std::io::copy(&mut from, &mut to);
+    let _ = to.shutdown(Shutdown::Write);
+}
+
+fn handle_client(
+    mut client: TcpStream,
+    peer: SocketAddr,
+    config: &RemoteAccessConfig,
+    upstream: SocketAddr,
+) {
+    if !config.is_ip_allowed(peer.ip()) {
+        warn!("Remote access denied for {}: not in allowlist", peer.ip());
+        reject(client, "403 Forbidden");
+        return;
+    }
+
+    let _ = client.set_read_timeout(Some(HEAD_READ_TIMEOUT));
+    let Some(head) = read_request_head(&mut client) else {
+        reject(client, "400 Bad Request");
+        return;
+    };
+    let head_text = String::from_utf8_lossy(&head).into_owned();
+
+    match find_token(&head_text, &config.token) {
+        None => {
+            warn!("Remote access denied for {}: missing or invalid token", peer.ip());
+            reject(client, "401 Unauthorized");
+            return;
+        }
+        Some(TokenSource::Query) => {
+            // Trade the query token for a cookie so the page and its websocket
+            // keep authenticating without the token in the URL
+            let target = head_text
+                .lines()
+                .next()
+                .and_then(|line| line.split_whitespace().nth(1))
+                .map(strip_token_query)
+                .unwrap_or_else(|| "/".to_string());
+            let _ = write!(
+                client,
+                "HTTP/1.1 302 Found\r\nLocation: {}\r\nSet-Cookie: {}={}; Path=/; HttpOnly; SameSite=Strict\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                target, TOKEN_COOKIE, config.token
+            );
+            return;
+        }
+        Some(_) => {}
+    }
+
+    let _ = client.set_read_timeout(None);
+    let mut server = match TcpStream::connect(upstream) {
+        Ok(server) => server,
+        Err(e) => {
+            warn!("Remote gateway could not reach WebUI at {}: {}", upstream, e);
+            reject(client, "502 Bad Gateway");
+            return;
+        }
+    };
+    if server.write_all(&close_after_response(&head)).is_err() {
+        return;
+    }
+
+    debug!("Remote client {} authenticated", peer);
+    let (Ok(client_read), Ok(client_end), Ok(server_read)) =
+        (client.try_clone(), client.try_clone(), server.try_clone())
+    else {
+        return;
+    };
+    let upload = std::thread::spawn(move || pipe(client_read, server));
+    pipe(server_read, client);
+    // The response is complete; anything more the client sends would be
+    // another request, so end the upload rather than wait for the client
+    let _ = client_end.shutdown(Shutdown::Both);
+    let _ = upload.join();
+}
+
+/// A running gateway; shutting it down stops accepting new clients while
+/// connections already forwarded finish on their own
+pub struct RemoteGateway {
+    addr: SocketAddr,
+    stopped: Arc<AtomicBool>,
+}
+
+impl RemoteGateway {
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl di::Shutdown for RemoteGateway {
+    fn shutdown(&self) -> AppResult<()> {
+        if self.stopped.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+        // Wake the accept loop so it sees the flag
+        let mut wake = self.addr;
+        if wake.ip().is_unspecified() {
+            wake.set_ip(IpAddr::from([127, 0, 0, 1]));
+        }
+        let _ = TcpStream::connect_timeout(&wake, Duration::from_secs(1));
+        info!("Remote access gateway on {} stopped", self.addr);
+        Ok(())
+    }
+}
+
+/// Start the gateway on the configured LAN address, forwarding authenticated
+/// clients to the WebUI server on `127.0.0.1:<upstream_port>`
+pub fn start_gateway(config: RemoteAccessConfig, upstream_port: u16) -> AppResult<RemoteGateway> {
+    let address = format!("{}:{}", config.bind_address, config.port);
+    let listener = TcpListener::bind(&address).map_err(|e| {
+        AppError::Configuration(
+            ErrorValue::new(ErrorCode::ConfigInvalid, "Failed to bind remote access address")
+                .with_cause(e.to_string())
+                .with_context("address", address.clone()),
+        )
+    })?;
+    let local = listener.local_addr()?;
+    let upstream = SocketAddr::from(([127, 0, 0, 1], upstream_port));
+    let stopped = Arc::new(AtomicBool::new(false));
+    let stop = stopped.clone();
+    let active = Arc::new(AtomicUsize::new(0));
+
+    std::thread::Builder::new()
+        .name("remote-gateway".to_string())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(client) = stream else {
+                    continue;
+                };
+                let Ok(peer) = client.peer_addr() else {
+                    continue;
+                };
+                let Some(slot) = ConnectionSlot::acquire(&active, config.max_connections) else {
+                    warn!("Remote access busy, turned away {}", peer);
+                    reject(client, "503 Service Unavailable");
+                    continue;
+                };
+                let config = config.clone();
+                std::thread::spawn(move || {
+                    let _slot = slot;
+                    handle_client(client, peer, &config, upstream)
+                });
+            }
+        })?;
+
+    info!("Remote access gateway listening on {} -> {}", local, upstream);
+    Ok(RemoteGateway {
+        addr: local,
+        stopped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ip_rules() {
+        let lan = IpRule::parse("192.168.1.0/24").unwrap();
+        assert!(lan.matches("192.168.1.42".parse().unwrap()));
+        assert!(!lan.matches("192.168.2.1".parse().unwrap()));
+
+        let host = IpRule::parse("10.0.0.5").unwrap();
+        assert!(host.matches("10.0.0.5".parse().unwrap()));
+        assert!(!host.matches("10.0.0.6".parse().unwrap()));
+
+        assert!(IpRule::parse("10.0.0.0/33").is_none());
+        assert!(IpRule::parse("not-an-ip").is_none());
+    }
+
+    #[test]
+    fn test_find_token() {
+        let header = "GET / HTTP/1.1\r\nAuthorization: Bearer secret\r\n\r\n";
+        assert_eq!(find_token(header, "secret"), Some(TokenSource::Header));
+
+        let cookie = "GET /app.js HTTP/1.1\r\nCookie: a=b; webui_remote_token=secret\r\n\r\n";
+        assert_eq!(find_token(cookie, "secret"), Some(TokenSource::Cookie));
+
+        let query = "GET /?lang=en&token=secret HTTP/1.1\r\nHost: x\r\n\r\n";
+        assert_eq!(find_token(query, "secret"), Some(TokenSource::Query));
+        assert_eq!(strip_token_query("/?lang=en&token=secret"), "/?lang=en");
+
+        assert_eq!(find_token("GET /?token=wrong HTTP/1.1\r\n\r\n", "secret"), None);
+    }
+
+    #[test]
+    fn test_forwarded_requests_close_the_connection() {
+        let head = b"GET /app.js HTTP/1.1\r\nHost: x\r\nConnection: keep-alive\r\nKeep-Alive: timeout=5\r\n\r\nGET /other HTTP/1.1\r\n";
+        let forwarded = String::from_utf8(close_after_response(head)).unwrap();
+        assert_eq!(
+            forwarded,
+            "GET /app.js HTTP/1.1\r\nHost: x\r\nConnection: close\r\n\r\nGET /other HTTP/1.1\r\n"
+        );
+
+        let upgrade = b"GET /_webui_ws_connect HTTP/1.1\r\nConnection: Upgrade\r\nUpgrade: websocket\r\n\r\n";
+        assert_eq!(close_after_response(upgrade), upgrade.to_vec());
+    }
+
+    #[test]
+    fn test_connection_slots_are_capped() {
+        let active = Arc::new(AtomicUsize::new(0));
+        let first = ConnectionSlot::acquire(&active, 2).unwrap();
+        let _second = ConnectionSlot::acquire(&active, 2).unwrap();
+        assert!(ConnectionSlot::acquire(&active, 2).is_none());
+        drop(first);
+        assert!(ConnectionSlot::acquire(&active, 2).is_some());
+        assert_eq!(active.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_announced_token_is_not_redacted() {
+        use crate::core::infrastructure::logging::redaction::Redactor;
+
+        let token = generate_token();
+        let addr = SocketAddr::from(([192, 168, 1, 5], 9000));
+        let mut out = Vec::new();
+        announce_token(&mut out, addr, &token).unwrap();
+        let line = String::from_utf8(out).unwrap();
+        let target = line.trim().strip_prefix("Remote access: http://192.168.1.5:9000").unwrap();
+        let head = format!("GET {} HTTP/1.1\r\n\r\n", target);
+        assert_eq!(find_token(&head, &token), Some(TokenSource::Query));
+
+        // The same token in a log record would be masked
+        let redactor = Redactor::new(false, true, &[]);
+        assert!(!redactor.redact(&format!("Remote access token: {}", token)).contains(&token));
+    }
+}
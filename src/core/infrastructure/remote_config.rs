@@ -0,0 +1,283 @@
+// src/core/infrastructure/remote_config.rs
+// Fleet-managed config: fetch a TOML or JSON document from an HTTPS URL,
+// check its HMAC signature and hand it back to be merged over the local
+// config file. The last verified copy is cached with its ETag, so unchanged
+// documents aren't downloaded again and offline starts still get them.
+
+#![allow(dead_code)]
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fs;
+use std::path::Path;
+
+use super::config::AppConfig;
+use super::http_client;
+use super::secrets::{self, KeyringStore};
+use crate::core::error::{
+    retry_with_backoff, AppError, AppResult, ErrorCode, ErrorValue, RetryPolicy,
+};
+use crate::utils::file_ops::FileUtils;
+
+/// Response header carrying the hex HMAC-SHA256 of the body, optionally
+/// prefixed with "sha256="
+pub const SIGNATURE_HEADER: &str = "X-Config-Signature";
+
+/// A downloaded document as kept in the cache file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Document {
+    url: String,
+    etag: Option<String>,
+    signature: Option<String>,
+    body: String,
+}
+
+/// The document `[remote_config] url` points at, as a TOML value to merge
+/// over the local config; `None` when no URL is configured. When the server
+/// can't be reached the cached copy is used.
+pub fn fetch(config: &AppConfig) -> AppResult<Option<toml::Value>> {
+    let Some(url) = config.get_remote_config_url() else {
+        return Ok(None);
+    };
+    let key = signing_key(config)?;
+    let cache_file = config.get_remote_config_cache_file();
+    let cached = cache_file
+        .as_deref()
+        .and_then(read_cache)
+        .filter(|cached| cached.url == url);
+
+    let downloaded = retry_with_backoff(&RetryPolicy::network(), |_| {
+        download(config, url, cached.as_ref())
+    });
+    let document = match (downloaded, cached) {
+        (Ok(Some(fresh)), _) => {
+            verify(key.as_deref(), &fresh)?;
+            if let Some(cache_file) = &cache_file {
+                if let Err(e) = write_cache(cache_file, &fresh) {
+                    eprintln!("Warning: {}", e);
+                }
+            }
+            fresh
+        }
+        (Ok(None), Some(cached)) => cached,
+        (Err(e), Some(cached)) => {
+            eprintln!("Warning: {}; using the cached copy", e);
+            cached
+        }
+        (Ok(None), None) => {
+            return Err(fetch_error(url, "server answered 304 with nothing cached"))
+        }
+        (Err(e), None) => return Err(e),
+    };
+    // The cache file is as writable as the config file; check it again
+    verify(key.as_deref(), &document)?;
+    parse(&document).map(Some)
+}
+
+fn signing_key(config: &AppConfig) -> AppResult<Option<Vec<u8>>> {
+    let Some(value) = config.get_remote_config_signing_key() else {
+        return Ok(None);
+    };
+    let store = KeyringStore::new(config.get_secrets_service());
+    Ok(Some(secrets::resolve(&store, value)?.into_bytes()))
+}
+
+/// `None` when the server says the cached copy is current
+fn download(
+    config: &AppConfig,
+    url: &str,
+    cached: Option<&Document>,
+) -> AppResult<Option<Document>> {
+    let client = http_client::builder_for(config)?
+        .https_only(true)
+        .timeout(config.get_remote_config_timeout())
+        .build()
+        .map_err(|e| fetch_error(url, e))?;
+    let mut request = client.get(url);
+    if let Some(etag) = cached.and_then(|cached| cached.etag.as_deref()) {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+    let response = request.send().map_err(|e| http_error(url, e))?;
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(None);
+    }
+    let response = response
+        .error_for_status()
+        .map_err(|e| http_error(url, e))?;
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = header(reqwest::header::ETAG.as_str());
+    let signature = header(SIGNATURE_HEADER);
+    let body = response.text().map_err(|e| http_error(url, e))?;
+    Ok(Some(Document {
+        url: url.to_string(),
+        etag,
+        signature,
+        body,
+    }))
+}
+
+/// Without a signing key any document is accepted; with one, only a body
+/// whose signature matches
+fn verify(key: Option<&[u8]>, document: &Document) -> AppResult<()> {
+    let Some(key) = key else {
+        return Ok(());
+    };
+    let signature = document
+        .signature
+        .as_deref()
+        .map(|signature| signature.trim().trim_start_matches("sha256="))
+        .ok_or_else(|| signature_error(&document.url, "the response has no signature"))?;
+    let signature = hex::decode(signature)
+        .map_err(|_| signature_error(&document.url, "the signature is not hex"))?;
+    let mut mac = Hmac::<Sha256>::new_from_slice(key)
+        .map_err(|_| signature_error(&document.url, "the signing key is unusable"))?;
+    mac.update(document.body.as_bytes());
+    mac.verify_slice(&signature)
+        .map_err(|_| signature_error(&document.url, "the signature does not match"))
+}
+
+/// JSON when the body is an object, TOML otherwise
+fn parse(document: &Document) -> AppResult<toml::Value> {
+    let parsed = if document.body.trim_start().starts_with('{') {
+        serde_json::from_str::<serde_json::Value>(&document.body)
+            .map_err(|e| e.to_string())
+            .and_then(|json| toml::Value::try_from(json).map_err(|e| e.to_string()))
+    } else {
+        toml::from_str::<toml::Value>(&document.body).map_err(|e| e.to_string())
+    };
+    parsed.map_err(|e| {
+        AppError::Configuration(
+            ErrorValue::new(ErrorCode::ConfigInvalid, "Failed to parse remote config")
+                .with_cause(e)
+                .with_context("url", document.url.as_str()),
+        )
+    })
+}
+
+fn read_cache(path: &Path) -> Option<Document> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn write_cache(path: &Path, document: &Document) -> AppResult<()> {
+    let content = serde_json::to_string(document).map_err(|e| {
+        AppError::Serialization(
+            ErrorValue::new(
+                ErrorCode::SerializationFailed,
+                "Failed to serialize remote config cache",
+            )
+            .with_cause(e.to_string()),
+        )
+    })?;
+    path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .map_err(|e| e.to_string())
+        .and_then(|()| FileUtils::write_atomic(path, content.as_bytes()))
+        .map_err(|e| {
+            AppError::Configuration(
+                ErrorValue::new(ErrorCode::ConfigInvalid, "Failed to cache remote config")
+                    .with_cause(e.to_string())
+                    .with_context("path", path.display().to_string()),
+            )
+        })
+}
+
+fn fetch_error(url: &str, cause: impl ToString) -> AppError {
+    AppError::Configuration(
+        ErrorValue::new(ErrorCode::ConfigNotFound, "Failed to fetch remote config")
+            .with_cause(cause.to_string())
+            .with_context("url", url),
+    )
+}
+
+/// Like `fetch_error`, but coded `Timeout` or `ServiceUnavailable` when the
+/// request is worth retrying
+fn http_error(url: &str, err: reqwest::Error) -> AppError {
+    let code = ErrorCode::for_http(&err, ErrorCode::ConfigNotFound);
+    AppError::Configuration(
+        ErrorValue::new(code, "Failed to fetch remote config")
+            .with_cause(err.to_string())
+            .with_context("url", url),
+    )
+}
+
+fn signature_error(url: &str, reason: &str) -> AppError {
+    AppError::Validation(
+        ErrorValue::new(
+            ErrorCode::ValidationFailed,
+            format!("Remote config rejected: {}", reason),
+        )
+        .with_context("url", url),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign(key: &[u8], body: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+        mac.update(body.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn document(body: &str, signature: Option<String>) -> Document {
+        Document {
+            url: String::from("https://config.example.com/kiosk.toml"),
+            etag: Some(String::from("\"v1\"")),
+            signature,
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_signature_verification() {
+        let key = b"fleet-key".as_slice();
+        let body = "[logging]\nlevel = \"warn\"\n";
+
+        assert!(verify(None, &document(body, None)).is_ok());
+        assert!(verify(Some(key), &document(body, Some(sign(key, body)))).is_ok());
+        assert!(verify(
+            Some(key),
+            &document(body, Some(format!("sha256={}", sign(key, body))))
+        )
+        .is_ok());
+
+        for signature in [None, Some(String::from("zz")), Some(sign(b"other", body))] {
+            assert!(matches!(
+                verify(Some(key), &document(body, signature)),
+                Err(AppError::Validation(_))
+            ));
+        }
+        let tampered = document("[logging]\nlevel = \"trace\"\n", Some(sign(key, body)));
+        assert!(verify(Some(key), &tampered).is_err());
+    }
+
+    #[test]
+    fn test_parse_toml_and_json_documents() {
+        let toml_doc = parse(&document("[logging]\nlevel = \"warn\"\n", None)).unwrap();
+        let json_doc = parse(&document(r#"{"logging": {"level": "warn"}}"#, None)).unwrap();
+        assert_eq!(toml_doc, json_doc);
+        assert!(parse(&document("[logging", None)).is_err());
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache").join("remote-config.json");
+        assert!(read_cache(&path).is_none());
+
+        let cached = document("[window]\ntitle = \"Kiosk\"\n", Some(String::from("ab")));
+        write_cache(&path, &cached).unwrap();
+        let read = read_cache(&path).unwrap();
+        assert_eq!(read.etag.as_deref(), Some("\"v1\""));
+        assert_eq!(read.body, cached.body);
+    }
+}
@@ -0,0 +1,226 @@
+// src/core/infrastructure/sanitize.rs
+// Sanitization stage for handler parameters, run on every request payload
+// after its schema upgrade and before the handler parses it. Strings are
+// normalized to Unicode NFC and stripped of control characters other than
+// tab and newline; a string longer than its field's limit rejects the call.
+// Secrets are only length checked, so a password is compared as typed, and
+// opaque values such as vault contents are left as sent. Field limits are
+// per handler. Element-string handlers (`create_user:...`) call `clean` per
+// field.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use unicode_normalization::UnicodeNormalization;
+
+use super::config::AppConfig;
+use crate::core::error::{ErrorCode, ErrorValue};
+
+/// Longest string accepted in a field without a limit of its own
+pub const DEFAULT_MAX_CHARS: usize = 64 * 1024;
+
+/// Limits of the user fields, applied unless configured otherwise
+const DEFAULT_FIELD_MAX_CHARS: [(&str, usize); 4] = [
+    ("create_user.name", 100),
+    ("create_user.email", 254),
+    ("update_user.name", 100),
+    ("update_user.email", 254),
+];
+
+/// Fields holding secrets, in any handler; length checked but not altered
+const SECRET_FIELDS: [&str; 3] = ["password", "current_password", "new_password"];
+
+/// `(handler, field)` whose value is opaque data the backend only stores
+const OPAQUE_FIELDS: [(&str, &str); 1] = [("vault_set", "value")];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sanitizer {
+    pub max_chars: usize,
+    /// Limits keyed `handler.field`, or by a bare field name for that field
+    /// in every handler
+    pub field_max_chars: HashMap<String, usize>,
+}
+
+impl Default for Sanitizer {
+    fn default() -> Self {
+        Self {
+            max_chars: DEFAULT_MAX_CHARS,
+            field_max_chars: DEFAULT_FIELD_MAX_CHARS
+                .iter()
+                .map(|&(field, max)| (field.to_string(), max))
+                .collect(),
+        }
+    }
+}
+
+impl Sanitizer {
+    pub fn from_app_config(config: &AppConfig) -> Self {
+        let mut sanitizer = Self {
+            max_chars: config.get_max_string_chars(),
+            ..Self::default()
+        };
+        sanitizer.field_max_chars.extend(config.get_field_max_chars());
+        sanitizer
+    }
+
+    fn limit(&self, handler: &str, field: &str) -> usize {
+        self.field_max_chars
+            .get(&format!("{}.{}", handler, field))
+            .or_else(|| self.field_max_chars.get(field))
+            .copied()
+            .unwrap_or(self.max_chars)
+    }
+
+    /// `value` normalized and stripped, or an error when it is too long for
+    /// `field` of `handler`. Secret fields are returned as they are.
+    pub fn clean(&self, handler: &str, field: &str, value: &str) -> Result<String, ErrorValue> {
+        let cleaned: String = if SECRET_FIELDS.contains(&field) {
+            value.to_string()
+        } else {
+            value
+                .nfc()
+                .filter(|&c| !c.is_control() || c == '\t' || c == '\n')
+                .collect()
+        };
+        let max = self.limit(handler, field);
+        let chars = cleaned.chars().count();
+        if chars > max {
+            let name = if field.is_empty() { "Value" } else { field };
+            return Err(ErrorValue::new(
+                ErrorCode::InvalidFieldValue,
+                format!("{} is longer than {} characters", name, max),
+            )
+            .with_field(field)
+            .with_context("length", chars.to_string())
+            .with_context("limit", max.to_string()));
+        }
+        Ok(cleaned)
+    }
+
+    /// Clean every string in the `handler` request `payload`. Strings in
+    /// arrays count against the limit of the field holding the array; a
+    /// top-level string against `max_chars`.
+    pub fn sanitize(
+        &self,
+        handler: &str,
+        payload: serde_json::Value,
+    ) -> Result<serde_json::Value, ErrorValue> {
+        self.sanitize_field(handler, "", payload)
+    }
+
+    fn sanitize_field(
+        &self,
+        handler: &str,
+        field: &str,
+        value: serde_json::Value,
+    ) -> Result<serde_json::Value, ErrorValue> {
+        use serde_json::Value;
+        if OPAQUE_FIELDS.contains(&(handler, field)) {
+            return Ok(value);
+        }
+        Ok(match value {
+            Value::String(text) => Value::String(self.clean(handler, field, &text)?),
+            Value::Array(items) => Value::Array(
+                items
+                    .into_iter()
+                    .map(|item| self.sanitize_field(handler, field, item))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Value::Object(object) => Value::Object(
+                object
+                    .into_iter()
+                    .map(|(key, item)| {
+                        let item = self.sanitize_field(handler, &key, item)?;
+                        Ok((key, item))
+                    })
+                    .collect::<Result<_, ErrorValue>>()?,
+            ),
+            other => other,
+        })
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref GLOBAL_SANITIZER: RwLock<Arc<Sanitizer>> =
+        RwLock::new(Arc::new(Sanitizer::default()));
+}
+
+pub fn configure(sanitizer: Sanitizer) {
+    if let Ok(mut current) = GLOBAL_SANITIZER.write() {
+        *current = Arc::new(sanitizer);
+    }
+}
+
+pub fn sanitizer() -> Arc<Sanitizer> {
+    GLOBAL_SANITIZER
+        .read()
+        .map(|sanitizer| Arc::clone(&sanitizer))
+        .unwrap_or_default()
+}
+
+/// Clean one field of an element-string call with the global limits
+pub fn clean(handler: &str, field: &str, value: &str) -> Result<String, ErrorValue> {
+    sanitizer().clean(handler, field, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_clean_strings() {
+        let sanitizer = Sanitizer::default();
+        // "e" + combining acute becomes the single character "é"
+        assert_eq!(sanitizer.clean("create_user", "name", "Jose\u{301}").unwrap(), "Jos\u{e9}");
+        assert_eq!(
+            sanitizer.clean("create_user", "notes", "a\u{0}b\u{1b}[31mc\r\n\td").unwrap(),
+            "ab[31mc\n\td"
+        );
+
+        let error = sanitizer.clean("create_user", "name", &"x".repeat(101)).unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidFieldValue);
+        assert_eq!(error.field.as_deref(), Some("name"));
+        assert!(sanitizer.clean("create_user", "notes", &"x".repeat(101)).is_ok());
+        // The user name limit is not every handler's `name` limit
+        assert!(sanitizer.clean("api_key_create", "name", &"x".repeat(101)).is_ok());
+    }
+
+    #[test]
+    fn test_secrets_are_only_length_checked() {
+        let sanitizer = Sanitizer {
+            field_max_chars: HashMap::from([("password".to_string(), 16)]),
+            ..Sanitizer::default()
+        };
+        let password = "Jose\u{301}\u{7}pass";
+        for field in SECRET_FIELDS {
+            assert_eq!(sanitizer.clean("auth_login", field, password).unwrap(), password);
+        }
+        assert!(sanitizer.clean("auth_login", "password", &"x".repeat(17)).is_err());
+    }
+
+    #[test]
+    fn test_sanitize_payload() {
+        let sanitizer = Sanitizer::default();
+        let payload = json!({
+            "key": "theme\u{7}",
+            "value": { "name": "Ada\u{0}", "tags": ["a\u{8}", "b"], "count": 3 },
+        });
+        assert_eq!(
+            sanitizer.sanitize("settings_set", payload.clone()).unwrap(),
+            json!({
+                "key": "theme",
+                "value": { "name": "Ada", "tags": ["a", "b"], "count": 3 },
+            })
+        );
+        // Vault values are stored as sent
+        assert_eq!(
+            sanitizer.sanitize("vault_set", payload.clone()).unwrap()["value"],
+            payload["value"]
+        );
+
+        let nested = json!({ "users": [{ "email": "x".repeat(255) }] });
+        let error = sanitizer.sanitize("create_user", nested.clone()).unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("email"));
+        assert!(sanitizer.sanitize("import_contacts", nested).is_ok());
+    }
+}
@@ -0,0 +1,146 @@
+// src/core/infrastructure/schema_registry.rs
+// Message schema versions and migrations for older request payloads
+//
+// The frontend bundle and the backend can be built from different versions.
+// Requests carry the `schema_version` they were built against (absent means 1);
+// registered migrations upgrade those payloads one version at a time before the
+// handler parses them.
+
+#![allow(dead_code)]
+
+use log::debug;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::core::error::{ErrorCode, ErrorValue};
+
+/// Schema version of the envelope and entities produced by this backend
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// Upgrades a payload from version `n` to `n + 1`
+pub type Migration = fn(serde_json::Value) -> serde_json::Value;
+
+pub struct SchemaRegistry {
+    /// (handler, from_version) -> migration
+    migrations: Mutex<HashMap<(String, u32), Migration>>,
+}
+
+impl SchemaRegistry {
+    pub fn new() -> Self {
+        Self {
+            migrations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register the upgrade of `handler` payloads from `from_version` to the next version
+    pub fn register(&self, handler: &str, from_version: u32, migration: Migration) {
+        if let Ok(mut migrations) = self.migrations.lock() {
+            migrations.insert((handler.to_string(), from_version), migration);
+        }
+    }
+
+    /// Upgrade a `version` payload for `handler` to `target`. Steps without a
+    /// registered migration leave the payload unchanged.
+    pub fn upgrade_to(
+        &self,
+        handler: &str,
+        version: u32,
+        target: u32,
+        mut payload: serde_json::Value,
+    ) -> Result<serde_json::Value, ErrorValue> {
+        if version > target {
+            return Err(ErrorValue::new(
+                ErrorCode::InvalidFormat,
+                "Request uses a newer schema than the backend supports",
+            )
+            .with_details(format!(
+                "schema_version {} is newer than {}; update the application",
+                version, target
+            ))
+            .with_field("schema_version")
+            .with_context("handler", handler));
+        }
+
+        let migrations = match self.migrations.lock() {
+            Ok(migrations) => migrations,
+            Err(_) => return Ok(payload),
+        };
+        for from in version..target {
+            if let Some(migration) = migrations.get(&(handler.to_string(), from)) {
+                debug!("Migrating '{}' payload from schema v{} to v{}", handler, from, from + 1);
+                payload = migration(payload);
+            }
+        }
+        Ok(payload)
+    }
+
+    /// Upgrade a payload to [`CURRENT_SCHEMA_VERSION`]
+    pub fn upgrade(
+        &self,
+        handler: &str,
+        version: u32,
+        payload: serde_json::Value,
+    ) -> Result<serde_json::Value, ErrorValue> {
+        self.upgrade_to(handler, version, CURRENT_SCHEMA_VERSION, payload)
+    }
+}
+
+impl Default for SchemaRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+lazy_static::lazy_static! {
+    pub static ref GLOBAL_SCHEMA_REGISTRY: SchemaRegistry = SchemaRegistry::new();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // v1 sent a single "name"; v2 split it, v3 renamed "first" to "given"
+    fn split_name(mut payload: serde_json::Value) -> serde_json::Value {
+        let name = payload["name"].as_str().unwrap_or_default().to_string();
+        let (first, last) = name.split_once(' ').unwrap_or((&name, ""));
+        payload["first"] = first.into();
+        payload["last"] = last.into();
+        payload.as_object_mut().unwrap().remove("name");
+        payload
+    }
+
+    fn rename_first(mut payload: serde_json::Value) -> serde_json::Value {
+        let first = payload.as_object_mut().unwrap().remove("first").unwrap();
+        payload["given"] = first;
+        payload
+    }
+
+    #[test]
+    fn test_migrations_chain() {
+        let registry = SchemaRegistry::new();
+        registry.register("create_user", 1, split_name);
+        registry.register("create_user", 2, rename_first);
+
+        let v1 = serde_json::json!({ "name": "Ada Lovelace" });
+        let upgraded = registry.upgrade_to("create_user", 1, 3, v1).unwrap();
+        assert_eq!(upgraded, serde_json::json!({ "given": "Ada", "last": "Lovelace" }));
+
+        let v2 = serde_json::json!({ "first": "Ada", "last": "L" });
+        let upgraded = registry.upgrade_to("create_user", 2, 3, v2).unwrap();
+        assert_eq!(upgraded["given"], "Ada");
+
+        // Other handlers are untouched
+        let other = serde_json::json!({ "name": "x" });
+        assert_eq!(registry.upgrade_to("get_users", 1, 3, other.clone()).unwrap(), other);
+    }
+
+    #[test]
+    fn test_newer_schema_rejected() {
+        let registry = SchemaRegistry::new();
+        let error = registry
+            .upgrade("negotiate", CURRENT_SCHEMA_VERSION + 1, serde_json::Value::Null)
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidFormat);
+        assert_eq!(error.field.as_deref(), Some("schema_version"));
+    }
+}
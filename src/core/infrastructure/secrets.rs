@@ -0,0 +1,238 @@
+// src/core/infrastructure/secrets.rs
+// Secrets kept in the OS keyring (Keychain, Credential Manager, Secret
+// Service), or an encrypted file when there is none, and referenced from
+// config as "secret:<name>"
+
+#![allow(dead_code)]
+
+use log::warn;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+
+/// Prefix marking a config value as a reference to a stored secret
+pub const SECRET_PREFIX: &str = "secret:";
+
+/// Where named secrets are stored; resolved from the DI container as
+/// `Arc<dyn SecretStore>`
+pub trait SecretStore: Send + Sync {
+    fn get(&self, name: &str) -> AppResult<Option<String>>;
+    fn set(&self, name: &str, value: &str) -> AppResult<()>;
+    fn delete(&self, name: &str) -> AppResult<()>;
+}
+
+/// The secret name a config value refers to, e.g. "remote_token" for
+/// `token = "secret:remote_token"`
+pub fn secret_name(value: &str) -> Option<&str> {
+    value.strip_prefix(SECRET_PREFIX).map(str::trim)
+}
+
+/// `value` itself, or the secret it refers to
+pub fn resolve(store: &dyn SecretStore, value: &str) -> AppResult<String> {
+    let Some(name) = secret_name(value) else {
+        return Ok(value.to_string());
+    };
+    if name.is_empty() {
+        return Err(AppError::Validation(
+            ErrorValue::new(ErrorCode::InvalidFieldValue, "Secret reference has no name")
+                .with_context("value", value),
+        ));
+    }
+    store.get(name)?.ok_or_else(|| {
+        AppError::NotFound(
+            ErrorValue::new(ErrorCode::ResourceNotFound, format!("Secret '{}' is not stored", name))
+                .with_context("secret", name),
+        )
+    })
+}
+
+fn keyring_error(operation: &str, name: &str, cause: keyring::Error) -> AppError {
+    AppError::Configuration(
+        ErrorValue::new(ErrorCode::ConfigInvalid, format!("Failed to {} secret", operation))
+            .with_cause(cause.to_string())
+            .with_context("secret", name),
+    )
+}
+
+/// Secrets in the OS keyring, one entry per name under `service`
+pub struct KeyringStore {
+    service: String,
+}
+
+impl KeyringStore {
+    pub fn new(service: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+        }
+    }
+
+    fn entry(&self, operation: &str, name: &str) -> AppResult<keyring::Entry> {
+        keyring::Entry::new(&self.service, name).map_err(|e| keyring_error(operation, name, e))
+    }
+}
+
+impl SecretStore for KeyringStore {
+    fn get(&self, name: &str) -> AppResult<Option<String>> {
+        match self.entry("read", name)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(keyring_error("read", name, e)),
+        }
+    }
+
+    fn set(&self, name: &str, value: &str) -> AppResult<()> {
+        self.entry("store", name)?
+            .set_password(value)
+            .map_err(|e| keyring_error("store", name, e))
+    }
+
+    fn delete(&self, name: &str) -> AppResult<()> {
+        match self.entry("delete", name)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(keyring_error("delete", name, e)),
+        }
+    }
+}
+
+/// The OS keyring, with `fallback` (an encrypted file) used when the keyring
+/// can't be reached; secrets stored while it was down stay readable
+pub struct FallbackSecretStore {
+    primary: Box<dyn SecretStore>,
+    fallback: Box<dyn SecretStore>,
+}
+
+impl FallbackSecretStore {
+    pub fn new(primary: impl SecretStore + 'static, fallback: impl SecretStore + 'static) -> Self {
+        Self {
+            primary: Box::new(primary),
+            fallback: Box::new(fallback),
+        }
+    }
+}
+
+impl SecretStore for FallbackSecretStore {
+    fn get(&self, name: &str) -> AppResult<Option<String>> {
+        match self.primary.get(name) {
+            Ok(Some(value)) => Ok(Some(value)),
+            Ok(None) => self.fallback.get(name),
+            Err(e) => {
+                warn!("Keyring unavailable, reading secret '{}' from file: {}", name, e);
+                self.fallback.get(name)
+            }
+        }
+    }
+
+    fn set(&self, name: &str, value: &str) -> AppResult<()> {
+        match self.primary.set(name, value) {
+            // Drop any copy stored while the keyring was down
+            Ok(()) => self.fallback.delete(name),
+            Err(e) => {
+                warn!("Keyring unavailable, storing secret '{}' in file: {}", name, e);
+                self.fallback.set(name, value)
+            }
+        }
+    }
+
+    fn delete(&self, name: &str) -> AppResult<()> {
+        let primary = self.primary.delete(name);
+        self.fallback.delete(name)?;
+        primary.or_else(|e| {
+            warn!("Keyring unavailable, deleted secret '{}' from file only: {}", name, e);
+            Ok(())
+        })
+    }
+}
+
+/// Secrets held in memory, for tests and machines without a keyring
+#[derive(Default)]
+pub struct MemorySecretStore {
+    secrets: Mutex<HashMap<String, String>>,
+}
+
+impl MemorySecretStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn secrets(&self) -> AppResult<std::sync::MutexGuard<'_, HashMap<String, String>>> {
+        self.secrets.lock().map_err(|e| {
+            AppError::LockPoisoned(
+                ErrorValue::new(ErrorCode::LockPoisoned, "Secret store lock poisoned")
+                    .with_cause(e.to_string()),
+            )
+        })
+    }
+}
+
+impl SecretStore for MemorySecretStore {
+    fn get(&self, name: &str) -> AppResult<Option<String>> {
+        Ok(self.secrets()?.get(name).cloned())
+    }
+
+    fn set(&self, name: &str, value: &str) -> AppResult<()> {
+        self.secrets()?.insert(name.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn delete(&self, name: &str) -> AppResult<()> {
+        self.secrets()?.remove(name);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_secret_references() {
+        let store = MemorySecretStore::new();
+        store.set("remote_token", "s3cr3t").unwrap();
+
+        assert_eq!(resolve(&store, "plain-token").unwrap(), "plain-token");
+        assert_eq!(resolve(&store, "secret:remote_token").unwrap(), "s3cr3t");
+        assert!(matches!(
+            resolve(&store, "secret:api_key"),
+            Err(AppError::NotFound(_))
+        ));
+        assert!(matches!(
+            resolve(&store, "secret:"),
+            Err(AppError::Validation(_))
+        ));
+
+        store.delete("remote_token").unwrap();
+        assert!(resolve(&store, "secret:remote_token").is_err());
+    }
+
+    struct Unavailable;
+
+    impl SecretStore for Unavailable {
+        fn get(&self, _: &str) -> AppResult<Option<String>> {
+            Err(AppError::Configuration(ErrorValue::new(ErrorCode::ConfigInvalid, "no keyring")))
+        }
+        fn set(&self, name: &str, _: &str) -> AppResult<()> {
+            self.get(name).map(|_| ())
+        }
+        fn delete(&self, name: &str) -> AppResult<()> {
+            self.get(name).map(|_| ())
+        }
+    }
+
+    #[test]
+    fn test_fallback_when_keyring_is_unavailable() {
+        let store = FallbackSecretStore::new(Unavailable, MemorySecretStore::new());
+        store.set("db_key", "k").unwrap();
+        assert_eq!(store.get("db_key").unwrap().as_deref(), Some("k"));
+        store.delete("db_key").unwrap();
+        assert_eq!(store.get("db_key").unwrap(), None);
+
+        let fallback = MemorySecretStore::new();
+        fallback.set("api_token", "old").unwrap();
+        let store = FallbackSecretStore::new(MemorySecretStore::new(), fallback);
+        assert_eq!(store.get("api_token").unwrap().as_deref(), Some("old"));
+        store.set("api_token", "new").unwrap();
+        assert_eq!(store.fallback.get("api_token").unwrap(), None);
+        assert_eq!(store.get("api_token").unwrap().as_deref(), Some("new"));
+    }
+}
@@ -0,0 +1,82 @@
+// src/core/infrastructure/security_audit.rs
+// Security audit trail. Code that signs users in, refuses a permission,
+// changes settings or runs raw SQL calls `emit`, which publishes a
+// `SecurityEvent` on the event bus; `persist` subscribes and writes every
+// one to the audit_log table, where the `security_audit` handler reads them.
+// Other subscribers, e.g. the event bridge, see the same events.
+
+use log::warn;
+use std::sync::Arc;
+
+use super::database::Database;
+use super::error_handler;
+use super::event_bus::{EventBus, Subscription, GLOBAL_EVENT_BUS};
+use crate::core::domain::events::SecurityEvent;
+
+/// Longest subject kept, e.g. for raw SQL statements
+const MAX_SUBJECT_CHARS: usize = 500;
+
+/// Publish a security event. Publishing can't fail the action being
+/// audited; a payload that doesn't serialize is only logged.
+pub fn emit(kind: &str, user_id: Option<i64>, subject: &str, detail: Option<&str>) {
+    let event = SecurityEvent {
+        kind: kind.to_string(),
+        user_id,
+        subject: subject.chars().take(MAX_SUBJECT_CHARS).collect(),
+        detail: detail.map(str::to_string),
+    };
+    if let Err(e) = GLOBAL_EVENT_BUS.publish_typed(&event) {
+        warn!("Failed to publish security event {}: {}", kind, e);
+    }
+}
+
+/// Write every security event published on `bus` to `db`'s audit log until
+/// the subscription is dropped
+pub fn persist(bus: &EventBus, db: Arc<Database>) -> Subscription<'_> {
+    bus.subscribe_typed(move |event: SecurityEvent| {
+        let recorded = db.record_audit(
+            &event.kind,
+            event.user_id,
+            Some(&event.subject),
+            event.detail.as_deref(),
+        );
+        if let Err(e) = recorded {
+            error_handler::record_app_error("AUDIT", &e);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::infrastructure::database::AuditFilter;
+
+    #[test]
+    fn test_events_reach_audit_log() {
+        let db = Arc::new(Database::new(":memory:").expect("Failed to create database"));
+        db.init_audit_log().expect("Failed to init audit log");
+        let _persisted = persist(&GLOBAL_EVENT_BUS, Arc::clone(&db));
+
+        emit("permission_denied", Some(7), "audit-test:delete", Some("role User"));
+        emit("sql_executed", None, &"x".repeat(MAX_SUBJECT_CHARS + 10), None);
+        GLOBAL_EVENT_BUS.flush();
+
+        let denied = db
+            .audit_entries(&AuditFilter {
+                event: Some("permission_denied".to_string()),
+                user_id: Some(7),
+                ..Default::default()
+            })
+            .unwrap();
+        let denied = denied.iter().find(|e| e.subject.as_deref() == Some("audit-test:delete"));
+        assert_eq!(denied.unwrap().detail.as_deref(), Some("role User"));
+
+        let sql = db
+            .audit_entries(&AuditFilter {
+                event: Some("sql_executed".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(sql.iter().all(|e| e.subject.as_deref().unwrap().len() <= MAX_SUBJECT_CHARS));
+    }
+}
@@ -0,0 +1,227 @@
+// src/core/infrastructure/service_modules.rs
+// Service modules: each subsystem's DI registrations in one place, installed
+// by main.rs through a ContainerBuilder
+
+use log::info;
+use std::sync::Arc;
+
+use super::codec::ResponseCodec;
+use super::config::{AppConfig, CallConfig};
+use super::database::{Database, EventStoreOptions};
+use super::di::{Container, ServiceModule};
+use super::error_stats::GLOBAL_ERROR_STATS;
+use super::http_client;
+use super::logging;
+use super::secrets::{FallbackSecretStore, KeyringStore, SecretStore};
+use super::sessions::SessionManager;
+use super::vault::Vault;
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::utils::security::EncryptedFileStore;
+use crate::utils::serialization::SerializationFormat;
+
+/// Registers the loaded `AppConfig`, and the `CallConfig` handler calls
+/// read it through; install it first, the other modules resolve their
+/// settings from it
+pub struct ConfigModule {
+    config: AppConfig,
+}
+
+impl ConfigModule {
+    pub fn new(config: AppConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl ServiceModule for ConfigModule {
+    fn name(&self) -> &'static str {
+        "config"
+    }
+
+    fn register(&self, container: &Container) -> AppResult<()> {
+        container.register_singleton(self.config.clone())?;
+        container.register_scoped(|scope| Ok(CallConfig::new(scope.resolve_arc::<AppConfig>()?)))
+    }
+}
+
+/// Installs the global logger and the `tracing` span bridge, and registers
+/// a `Logger` that flushes output on shutdown
+pub struct LoggingModule;
+
+impl ServiceModule for LoggingModule {
+    fn name(&self) -> &'static str {
+        "logging"
+    }
+
+    fn register(&self, container: &Container) -> AppResult<()> {
+        let config = container.resolve_arc::<AppConfig>()?;
+        logging::set_console_format(logging::ConsoleFormat::from_config(&config));
+        logging::redaction::configure(&config);
+        logging::init_logging_with_config(
+            Some(config.get_log_file()),
+            config.get_log_level(),
+            config.is_append_log(),
+            config.get_log_queue_size(),
+        )
+        .map_err(|e| {
+            AppError::Logging(
+                ErrorValue::new(ErrorCode::InternalError, "Failed to initialize logger")
+                    .with_cause(e.to_string()),
+            )
+        })?;
+        logging::GLOBAL_LOG_BUFFER.set_capacity(config.get_log_buffer_size());
+        logging::GLOBAL_LOG_SHIPPER.configure(&config);
+        logging::spans::install(config.get_slow_span_ms()).map_err(|e| {
+            AppError::Logging(
+                ErrorValue::new(
+                    ErrorCode::InternalError,
+                    "Failed to install tracing subscriber",
+                )
+                .with_cause(e.to_string()),
+            )
+        })?;
+        container.register_disposable(logging::Logger::new())
+    }
+}
+
+/// Registers the OS keyring, backed by the encrypted fallback file, as the
+/// `dyn SecretStore` that `secret:` config values are read from
+pub struct SecretsModule;
+
+impl ServiceModule for SecretsModule {
+    fn name(&self) -> &'static str {
+        "secrets"
+    }
+
+    fn register(&self, container: &Container) -> AppResult<()> {
+        let config = container.resolve_arc::<AppConfig>()?;
+        let keyring = KeyringStore::new(config.get_secrets_service());
+        let store: Arc<dyn SecretStore> = match config.get_secrets_fallback_file() {
+            Some(path) => {
+                info!("Secrets fallback file: {}", path.display());
+                Arc::new(FallbackSecretStore::new(keyring, EncryptedFileStore::new(path)))
+            }
+            None => Arc::new(keyring),
+        };
+        container.register_trait(store)
+    }
+}
+
+/// Registers the `SessionManager` that issues and checks handler session
+/// tokens; install after `SecretsModule`, the signing key may be a secret
+pub struct SessionModule;
+
+impl ServiceModule for SessionModule {
+    fn name(&self) -> &'static str {
+        "sessions"
+    }
+
+    fn register(&self, container: &Container) -> AppResult<()> {
+        let config = container.resolve_arc::<AppConfig>()?;
+        let secrets = container.resolve_trait::<dyn SecretStore>()?;
+        container.register_singleton(SessionManager::from_config(&config, secrets.as_ref()))
+    }
+}
+
+/// Registers the `Vault` behind `vault_set` / `vault_get`; install after
+/// `SecretsModule`, its key is a secret
+pub struct VaultModule;
+
+impl ServiceModule for VaultModule {
+    fn name(&self) -> &'static str {
+        "vault"
+    }
+
+    fn register(&self, container: &Container) -> AppResult<()> {
+        let secrets = container.resolve_trait::<dyn SecretStore>()?;
+        container.register_singleton(Vault::new(secrets))
+    }
+}
+
+/// Registers the shared response codec handlers encode replies with
+pub struct TransportModule;
+
+impl ServiceModule for TransportModule {
+    fn name(&self) -> &'static str {
+        "transport"
+    }
+
+    fn register(&self, container: &Container) -> AppResult<()> {
+        let config = container.resolve_arc::<AppConfig>()?;
+        let format = SerializationFormat::from_name(config.get_serialization())
+            .filter(|format| format.is_self_describing())
+            .unwrap_or(SerializationFormat::Json);
+        container.register_singleton(
+            ResponseCodec::new(format)
+                .with_compression_threshold(config.get_compression_threshold()),
+        )
+    }
+}
+
+/// Registers the shared HTTP client, built on first use with the proxy
+/// `[network]` asks for
+pub struct NetworkModule;
+
+impl ServiceModule for NetworkModule {
+    fn name(&self) -> &'static str {
+        "network"
+    }
+
+    fn register(&self, container: &Container) -> AppResult<()> {
+        container.register_factory(http_client::build)
+    }
+}
+
+/// Registers `Arc<Database>`, built on first resolve: opens the pooled
+/// database and prepares the schema, config audit, two-factor, credential,
+/// audit log, vault and API key tables, event store, error stats and sample
+/// data
+pub struct DatabaseModule;
+
+impl ServiceModule for DatabaseModule {
+    fn name(&self) -> &'static str {
+        "database"
+    }
+
+    fn register(&self, container: &Container) -> AppResult<()> {
+        container.register_factory(open_database)?;
+        container.on_shutdown::<Arc<Database>>()
+    }
+}
+
+fn open_database(container: &Container) -> AppResult<Arc<Database>> {
+    let config = container.resolve_arc::<AppConfig>()?;
+    let db_path = config.get_db_path();
+    info!("Database path: {}", db_path);
+
+    let db = Database::new(db_path)?;
+    info!("Database connection pool initialized successfully");
+    db.init()?;
+    db.init_config_audit()?;
+    db.init_two_factor()?;
+    db.init_credentials()?;
+    db.init_audit_log()?;
+    db.init_vault()?;
+    db.init_api_keys()?;
+
+    if config.is_event_sourcing_enabled() {
+        db.init_event_store(EventStoreOptions {
+            snapshot_interval: config.get_snapshot_interval(),
+        })?;
+    }
+    if config.should_create_sample_data() {
+        db.insert_sample_data()?;
+        info!("Sample data created (if not exists)");
+    }
+    let db = Arc::new(db);
+    if config.should_persist_error_stats() {
+        db.init_error_stats()?;
+        GLOBAL_ERROR_STATS.set_store(Arc::clone(&db));
+    }
+
+    let stats = db.pool_stats();
+    info!(
+        "Database pool stats: connections={}, idle={}",
+        stats.connections, stats.idle_connections
+    );
+    Ok(db)
+}
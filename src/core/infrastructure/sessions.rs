@@ -0,0 +1,366 @@
+// src/core/infrastructure/sessions.rs
+// Handler sessions: a signed token (HS256 JWT) issued on login, sent back by
+// the frontend as `session_token` in handler payloads and checked by
+// `envelope::respond_with_session`. Sessions are kept in memory, so a token
+// can be refreshed or revoked before it expires; a restart signs everyone
+// out. Hooks added with `on_end` hear of every session that ends, however it
+// ends, to drop what was kept for it.
+
+use chrono::Utc;
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use log::{info, warn};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use super::config::AppConfig;
+use super::secrets::{self, SecretStore};
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::utils::crypto::CryptoUtils;
+use crate::utils::security::SecretString;
+
+/// A signed-in user as handlers and the frontend see it
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct SessionInfo {
+    pub session_id: String,
+    pub user_id: i64,
+    pub role: String,
+    /// Unix seconds of the login
+    pub created_at: i64,
+    /// Unix seconds the current token stops being accepted
+    pub expires_at: i64,
+    /// Unix seconds after which the session can't be refreshed
+    pub refreshable_until: i64,
+}
+
+/// A token handed to the frontend, with the session it opens
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SessionToken {
+    pub token: String,
+    pub session: SessionInfo,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    sid: String,
+    sub: String,
+    role: String,
+    /// Bumped on every refresh, so the token it replaced stops working
+    generation: u32,
+    iat: i64,
+    exp: i64,
+}
+
+struct Entry {
+    info: SessionInfo,
+    generation: u32,
+}
+
+/// Called with the ids of sessions that just ended
+pub type EndHook = Box<dyn Fn(&[String]) + Send + Sync>;
+
+pub struct SessionManager {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    ttl: Duration,
+    max_age: Duration,
+    sessions: RwLock<HashMap<String, Entry>>,
+    end_hooks: RwLock<Vec<EndHook>>,
+}
+
+impl SessionManager {
+    pub fn new(key: &[u8], ttl: Duration, max_age: Duration) -> Self {
+        Self {
+            encoding: EncodingKey::from_secret(key),
+            decoding: DecodingKey::from_secret(key),
+            ttl,
+            max_age,
+            sessions: RwLock::new(HashMap::new()),
+            end_hooks: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Call `hook` whenever sessions end: signed out, revoked, expired or
+    /// too old to refresh
+    pub fn on_end(&self, hook: impl Fn(&[String]) + Send + Sync + 'static) -> AppResult<()> {
+        self.end_hooks.write()?.push(Box::new(hook));
+        Ok(())
+    }
+
+    /// Run the end hooks, with the session lock released
+    fn ended(&self, session_ids: Vec<String>) {
+        if session_ids.is_empty() {
+            return;
+        }
+        match self.end_hooks.read() {
+            Ok(hooks) => hooks.iter().for_each(|hook| hook(&session_ids)),
+            Err(e) => warn!("Session end hooks unavailable: {}", e),
+        }
+    }
+
+    /// Remove the sessions `ends` picks, returning their ids
+    fn remove_where(&self, ends: impl Fn(&SessionInfo) -> bool) -> AppResult<Vec<String>> {
+        let mut sessions = self.write()?;
+        let ended: Vec<String> = sessions
+            .values()
+            .filter(|entry| ends(&entry.info))
+            .map(|entry| entry.info.session_id.clone())
+            .collect();
+        for session_id in &ended {
+            sessions.remove(session_id);
+        }
+        Ok(ended)
+    }
+
+    /// Signing key from `[security] session_signing_key`, read from
+    /// `secrets` when it is a `secret:` reference; a random one when none
+    /// is configured or it can't be read. The text of the key is wiped once
+    /// the signing keys are built.
+    pub fn from_config(config: &AppConfig, secrets: &dyn SecretStore) -> Self {
+        let configured = config.get_session_signing_key().and_then(|key| {
+            secrets::resolve(secrets, key)
+                .map_err(|e| warn!("Session signing key unavailable: {}", e))
+                .ok()
+        });
+        let key = SecretString::new(configured.unwrap_or_else(|| {
+            info!("No session signing key configured, using a random one for this run");
+            hex::encode(CryptoUtils::random_bytes(32))
+        }));
+        Self::new(
+            key.expose().as_bytes(),
+            config.get_session_ttl(),
+            config.get_session_max_age(),
+        )
+    }
+
+    /// Open a session for `user_id` with `role`
+    pub fn issue(&self, user_id: i64, role: &str) -> AppResult<SessionToken> {
+        self.prune()?;
+        let now = Utc::now().timestamp();
+        let info = SessionInfo {
+            session_id: CryptoUtils::uuid_v4(),
+            user_id,
+            role: role.to_string(),
+            created_at: now,
+            expires_at: now + self.ttl.as_secs() as i64,
+            refreshable_until: now + self.max_age.as_secs() as i64,
+        };
+        let token = self.sign(&info, 0, now)?;
+        self.write()?.insert(
+            info.session_id.clone(),
+            Entry {
+                info: info.clone(),
+                generation: 0,
+            },
+        );
+        info!("Session {} opened for user {}", info.session_id, user_id);
+        Ok(SessionToken {
+            token,
+            session: info,
+        })
+    }
+
+    /// The session `token` belongs to, if it is signed by us, unexpired,
+    /// the latest token of its session and not revoked
+    pub fn validate(&self, token: &str) -> AppResult<SessionInfo> {
+        let claims = self.decode(token)?;
+        let sessions = self.read()?;
+        match sessions.get(&claims.sid) {
+            Some(entry) if entry.generation == claims.generation => Ok(entry.info.clone()),
+            _ => Err(expired("Session was signed out or replaced")),
+        }
+    }
+
+    /// Swap `token` for a new one with a fresh expiry. The old token stops
+    /// working. Refused once the session is older than the max age.
+    pub fn refresh(&self, token: &str) -> AppResult<SessionToken> {
+        let claims = self.decode(token)?;
+        let now = Utc::now().timestamp();
+        let mut sessions = self.write()?;
+        let entry = match sessions.get_mut(&claims.sid) {
+            Some(entry) if entry.generation == claims.generation => entry,
+            _ => return Err(expired("Session was signed out or replaced")),
+        };
+        if now >= entry.info.refreshable_until {
+            sessions.remove(&claims.sid);
+            drop(sessions);
+            self.ended(vec![claims.sid]);
+            return Err(expired("Session is too old to refresh"));
+        }
+        entry.generation += 1;
+        entry.info.expires_at = (now + self.ttl.as_secs() as i64).min(entry.info.refreshable_until);
+        let info = entry.info.clone();
+        let token = self.sign(&info, entry.generation, now)?;
+        Ok(SessionToken {
+            token,
+            session: info,
+        })
+    }
+
+    /// End a session; false when there was none with that id
+    pub fn revoke(&self, session_id: &str) -> AppResult<bool> {
+        let removed = self.write()?.remove(session_id).is_some();
+        if removed {
+            info!("Session {} closed", session_id);
+            self.ended(vec![session_id.to_string()]);
+        }
+        Ok(removed)
+    }
+
+    /// End every session of `user_id`, e.g. after a password change;
+    /// returns how many were open
+    pub fn revoke_user(&self, user_id: i64) -> AppResult<usize> {
+        let ended = self.remove_where(|info| info.user_id == user_id)?;
+        let count = ended.len();
+        self.ended(ended);
+        Ok(count)
+    }
+
+    /// Drop sessions whose token expired; they can no longer be refreshed
+    pub fn prune(&self) -> AppResult<usize> {
+        let now = Utc::now().timestamp();
+        let ended = self.remove_where(|info| info.expires_at <= now)?;
+        let count = ended.len();
+        self.ended(ended);
+        Ok(count)
+    }
+
+    fn sign(&self, info: &SessionInfo, generation: u32, now: i64) -> AppResult<String> {
+        let claims = Claims {
+            sid: info.session_id.clone(),
+            sub: info.user_id.to_string(),
+            role: info.role.clone(),
+            generation,
+            iat: now,
+            exp: info.expires_at,
+        };
+        jsonwebtoken::encode(&Header::new(Algorithm::HS256), &claims, &self.encoding).map_err(
+            |e| {
+                AppError::Serialization(
+                    ErrorValue::new(ErrorCode::SerializationFailed, "Failed to sign session token")
+                        .with_cause(e.to_string()),
+                )
+            },
+        )
+    }
+
+    fn decode(&self, token: &str) -> AppResult<Claims> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.leeway = 0;
+        jsonwebtoken::decode::<Claims>(token, &self.decoding, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| match e.kind() {
+                ErrorKind::ExpiredSignature => expired("Session token has expired"),
+                _ => AppError::Validation(
+                    ErrorValue::new(ErrorCode::Unauthorized, "Session token is not valid")
+                        .with_cause(e.to_string()),
+                ),
+            })
+    }
+
+    fn read(&self) -> AppResult<std::sync::RwLockReadGuard<'_, HashMap<String, Entry>>> {
+        Ok(self.sessions.read()?)
+    }
+
+    fn write(&self) -> AppResult<std::sync::RwLockWriteGuard<'_, HashMap<String, Entry>>> {
+        Ok(self.sessions.write()?)
+    }
+}
+
+fn expired(message: &str) -> AppError {
+    AppError::Validation(ErrorValue::new(ErrorCode::SessionExpired, message))
+}
+
+/// The error for a call that needs a session but sent no token
+pub fn unauthorized(handler: &str) -> AppError {
+    AppError::Validation(
+        ErrorValue::new(ErrorCode::Unauthorized, "Sign in to continue")
+            .with_context("handler", handler),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager(ttl_secs: u64) -> SessionManager {
+        SessionManager::new(
+            b"test-key",
+            Duration::from_secs(ttl_secs),
+            Duration::from_secs(3600),
+        )
+    }
+
+    fn other_key() -> SessionManager {
+        SessionManager::new(b"other-key", Duration::from_secs(60), Duration::from_secs(60))
+    }
+
+    fn code(err: AppError) -> ErrorCode {
+        err.to_value().code.clone()
+    }
+
+    #[test]
+    fn test_issue_validate_and_revoke() {
+        let sessions = manager(60);
+        let issued = sessions.issue(7, "Admin").unwrap();
+        let info = sessions.validate(&issued.token).unwrap();
+        assert_eq!(info, issued.session);
+        assert_eq!(info.user_id, 7);
+        assert_eq!(info.role, "Admin");
+
+        // Signed with our key but opened by another manager, e.g. before a restart
+        let unknown = manager(60).issue(7, "Admin").unwrap();
+        assert_eq!(
+            code(sessions.validate(&unknown.token).unwrap_err()),
+            ErrorCode::SessionExpired
+        );
+        let forged = other_key().issue(7, "Admin").unwrap();
+        assert_eq!(
+            code(sessions.validate(&forged.token).unwrap_err()),
+            ErrorCode::Unauthorized
+        );
+        assert_eq!(
+            code(sessions.validate("not-a-token").unwrap_err()),
+            ErrorCode::Unauthorized
+        );
+
+        assert!(sessions.revoke(&info.session_id).unwrap());
+        assert_eq!(
+            code(sessions.validate(&issued.token).unwrap_err()),
+            ErrorCode::SessionExpired
+        );
+    }
+
+    #[test]
+    fn test_refresh_replaces_the_token() {
+        let sessions = manager(60);
+        let issued = sessions.issue(1, "User").unwrap();
+        let refreshed = sessions.refresh(&issued.token).unwrap();
+        assert_eq!(refreshed.session.session_id, issued.session.session_id);
+        assert!(sessions.validate(&refreshed.token).is_ok());
+        assert_eq!(
+            code(sessions.validate(&issued.token).unwrap_err()),
+            ErrorCode::SessionExpired
+        );
+        assert!(sessions.refresh(&issued.token).is_err());
+
+        sessions.issue(1, "User").unwrap();
+        sessions.issue(2, "User").unwrap();
+        assert_eq!(sessions.revoke_user(1).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_expired_tokens_are_rejected() {
+        let sessions = manager(0);
+        let issued = sessions.issue(1, "User").unwrap();
+        std::thread::sleep(Duration::from_millis(1100));
+        assert_eq!(
+            code(sessions.validate(&issued.token).unwrap_err()),
+            ErrorCode::SessionExpired
+        );
+        assert_eq!(sessions.prune().unwrap(), 1);
+    }
+}
@@ -0,0 +1,321 @@
+// src/core/infrastructure/settings.rs
+// Runtime settings: change AppConfig values by dotted key ("logging.level"),
+// persist them to the config file and apply them like a hot reload. The
+// file is edited in place, so its comments and layout survive.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, InlineTable, Item, Table, TableLike};
+
+use super::config::AppConfig;
+use super::config_template;
+use super::config_watcher;
+use super::di::Container;
+use crate::core::domain::events::ConfigChanged;
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::utils::file_ops::FileUtils;
+
+/// The file settings are written to: the loaded config file, or
+/// `app.config.toml` when the app runs on defaults
+pub fn settings_path() -> PathBuf {
+    AppConfig::config_path().unwrap_or_else(|| PathBuf::from("app.config.toml"))
+}
+
+/// Set `key` to `value` in the config file at `path`; `null` removes the
+/// key so its default applies. Returns the change, if anything differs.
+pub fn set(
+    container: &Container,
+    path: &Path,
+    key: &str,
+    value: serde_json::Value,
+) -> Result<Option<ConfigChanged>, Vec<AppError>> {
+    let keys = split_key(key).map_err(|e| vec![e])?;
+    let value = match value {
+        serde_json::Value::Null => None,
+        value => Some(toml::Value::try_from(&value).map_err(|e| {
+            vec![AppError::Validation(
+                invalid_key(key, "Value can't be stored in the config file")
+                    .with_cause(e.to_string()),
+            )]
+        })?),
+    };
+    update(container, path, |file| put(file, &keys, value.map(to_item)))
+}
+
+/// Put `key`, or every setting when `None`, back to its default; resetting
+/// everything writes the documented first-run file again
+pub fn reset(
+    container: &Container,
+    path: &Path,
+    key: Option<&str>,
+) -> Result<Option<ConfigChanged>, Vec<AppError>> {
+    match key {
+        None => {
+            let defaults = default_document().map_err(|e| vec![e])?;
+            update(container, path, |file| {
+                *file = defaults;
+                Ok(())
+            })
+        }
+        Some(key) => {
+            let keys = split_key(key).map_err(|e| vec![e])?;
+            let defaults = default_toml().map_err(|e| vec![e])?;
+            let default = keys
+                .iter()
+                .try_fold(&defaults, |value, key| value.get(*key))
+                .cloned();
+            update(container, path, |file| put(file, &keys, default.map(to_item)))
+        }
+    }
+}
+
+/// Apply `edit` to the config file, refusing results that don't load, then
+/// swap the new config into `container`
+fn update(
+    container: &Container,
+    path: &Path,
+    edit: impl FnOnce(&mut DocumentMut) -> AppResult<()>,
+) -> Result<Option<ConfigChanged>, Vec<AppError>> {
+    let mut file = read_file(path).map_err(|e| vec![e])?;
+    edit(&mut file).map_err(|e| vec![e])?;
+    let content = file.to_string();
+    AppConfig::from_toml(&content, path)?;
+    write_atomically(path, &content).map_err(|e| vec![e])?;
+    config_watcher::reload(container, path)
+}
+
+/// The file as written, without environment overrides; the documented
+/// defaults when there is no file yet
+fn read_file(path: &Path) -> AppResult<DocumentMut> {
+    if !path.exists() {
+        return default_document();
+    }
+    let content =
+        fs::read_to_string(path).map_err(|e| file_error("Failed to read config file", e, path))?;
+    content
+        .parse()
+        .map_err(|e| file_error("Failed to parse config file", e, path))
+}
+
+/// Write through a temporary file so the watcher never reads half a config
+/// and a crash can't leave a torn one
+fn write_atomically(path: &Path, content: &str) -> AppResult<()> {
+    FileUtils::write_atomic(path, content.as_bytes())
+        .map_err(|e| file_error("Failed to write config file", e, path))
+}
+
+fn default_toml() -> AppResult<toml::Value> {
+    toml::Value::try_from(AppConfig::default()).map_err(|e| {
+        AppError::Serialization(
+            ErrorValue::new(
+                ErrorCode::SerializationFailed,
+                "Failed to serialize default config",
+            )
+            .with_cause(e.to_string()),
+        )
+    })
+}
+
+/// The first-run file `config_template` writes
+fn default_document() -> AppResult<DocumentMut> {
+    config_template::render(&AppConfig::default())?
+        .parse()
+        .map_err(|e: toml_edit::TomlError| {
+            AppError::Serialization(
+                ErrorValue::new(ErrorCode::SerializationFailed, "Failed to parse default config")
+                    .with_cause(e.to_string()),
+            )
+        })
+}
+
+/// Set or remove `keys` in `file`, creating missing sections. A replaced
+/// value keeps the comments around it.
+fn put(file: &mut DocumentMut, keys: &[&str], value: Option<Item>) -> AppResult<()> {
+    let Some((leaf, parents)) = keys.split_last() else {
+        return Ok(());
+    };
+    let mut table: &mut dyn TableLike = file.as_table_mut();
+    for key in parents {
+        if table.get(key).is_none() {
+            let mut section = Table::new();
+            section.set_implicit(true);
+            table.insert(key, Item::Table(section));
+        }
+        table = table
+            .get_mut(key)
+            .and_then(Item::as_table_like_mut)
+            .ok_or_else(|| {
+                AppError::Validation(invalid_key(
+                    &keys.join("."),
+                    &format!("'{}' is a value, not a section", key),
+                ))
+            })?;
+    }
+    let Some(mut value) = value else {
+        table.remove(leaf);
+        return Ok(());
+    };
+    match table.get_mut(leaf) {
+        Some(existing) => {
+            match (&*existing, &mut value) {
+                (Item::Value(old), Item::Value(new)) => *new.decor_mut() = old.decor().clone(),
+                (Item::Table(old), Item::Table(new)) => *new.decor_mut() = old.decor().clone(),
+                _ => {}
+            }
+            *existing = value;
+        }
+        None => {
+            table.insert(leaf, value);
+        }
+    }
+    Ok(())
+}
+
+/// Sections become `[section]` tables, anything nested in a value inline
+fn to_item(value: toml::Value) -> Item {
+    match value {
+        toml::Value::Table(entries) => {
+            let mut table = Table::new();
+            for (key, value) in entries {
+                table.insert(&key, to_item(value));
+            }
+            Item::Table(table)
+        }
+        value => Item::Value(to_value(value)),
+    }
+}
+
+fn to_value(value: toml::Value) -> toml_edit::Value {
+    match value {
+        toml::Value::String(s) => s.into(),
+        toml::Value::Integer(i) => i.into(),
+        toml::Value::Float(f) => f.into(),
+        toml::Value::Boolean(b) => b.into(),
+        toml::Value::Datetime(d) => d.into(),
+        toml::Value::Array(items) => {
+            items.into_iter().map(to_value).collect::<toml_edit::Array>().into()
+        }
+        toml::Value::Table(entries) => entries
+            .into_iter()
+            .map(|(key, value)| (key, to_value(value)))
+            .collect::<InlineTable>()
+            .into(),
+    }
+}
+
+fn split_key(key: &str) -> AppResult<Vec<&str>> {
+    let keys: Vec<&str> = key.split('.').map(str::trim).collect();
+    if keys.iter().any(|k| k.is_empty()) {
+        return Err(AppError::Validation(invalid_key(
+            key,
+            "Setting key must look like 'section.key'",
+        )));
+    }
+    Ok(keys)
+}
+
+fn invalid_key(key: &str, message: &str) -> ErrorValue {
+    ErrorValue::new(ErrorCode::InvalidFieldValue, message)
+        .with_field("key")
+        .with_context("key", key)
+}
+
+fn file_error(message: &str, cause: impl ToString, path: &Path) -> AppError {
+    AppError::Configuration(
+        ErrorValue::new(ErrorCode::ConfigInvalid, message)
+            .with_cause(cause.to_string())
+            .with_context("path", path.display().to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_set_and_reset_persist_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.config.toml");
+        let container = Container::new();
+        container.register_singleton(AppConfig::default()).unwrap();
+        let level = || {
+            container
+                .resolve_arc::<AppConfig>()
+                .unwrap()
+                .get_log_level()
+                .to_string()
+        };
+
+        let changed = set(&container, &path, "logging.level", json!("debug")).unwrap();
+        assert_eq!(changed.unwrap().sections, ["logging"]);
+        assert_eq!(level(), "debug");
+        assert_eq!(
+            AppConfig::load_from(&path).unwrap().get_log_level(),
+            "debug"
+        );
+
+        // Already set: nothing to broadcast
+        assert!(set(&container, &path, "logging.level", json!("debug"))
+            .unwrap()
+            .is_none());
+
+        // Invalid values and keys leave the file and container alone
+        assert!(set(&container, &path, "logging.level", json!("verbose")).is_err());
+        assert!(set(&container, &path, "logging.level", json!(null)).is_err());
+        assert!(set(&container, &path, "logging..level", json!("info")).is_err());
+        assert!(set(&container, &path, "logging.level.name", json!("info")).is_err());
+        assert_eq!(level(), "debug");
+        assert_eq!(
+            AppConfig::load_from(&path).unwrap().get_log_level(),
+            "debug"
+        );
+
+        set(&container, &path, "remote.port", json!(9000)).unwrap();
+        assert_eq!(
+            container
+                .resolve_arc::<AppConfig>()
+                .unwrap()
+                .get_remote_port(),
+            9000
+        );
+
+        let changed = reset(&container, &path, Some("logging.level")).unwrap();
+        assert_eq!(changed.unwrap().sections, ["logging"]);
+        assert_eq!(level(), "info");
+
+        reset(&container, &path, None).unwrap();
+        assert_eq!(
+            *container.resolve_arc::<AppConfig>().unwrap(),
+            AppConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_edits_keep_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.config.toml");
+        config_template::write_default(&path).unwrap();
+        let container = Container::new();
+        container.register_singleton(AppConfig::default()).unwrap();
+
+        let before = fs::read_to_string(&path).unwrap();
+        let level_doc = before
+            .lines()
+            .find(|line| line.starts_with('#') && line.contains("level"))
+            .unwrap()
+            .to_string();
+        set(&container, &path, "logging.level", json!("warn")).unwrap();
+        set(&container, &path, "filesystem.allowed_roots", json!(["/srv"])).unwrap();
+
+        let after = fs::read_to_string(&path).unwrap();
+        assert!(after.starts_with("# Application configuration"));
+        assert!(after.contains(&level_doc));
+        assert!(after.contains("level = \"warn\""));
+        let config = AppConfig::load_from(&path).unwrap();
+        assert_eq!(config.get_log_level(), "warn");
+        assert_eq!(config.get_allowed_roots(), [PathBuf::from("/srv")]);
+    }
+}
@@ -0,0 +1,200 @@
+// src/core/infrastructure/two_factor.rs
+// Optional second sign-in factor per user. Setup stores a pending TOTP
+// secret with ten recovery codes; the first valid code turns it on. From
+// then on login must pass `verify` with a TOTP code or an unused recovery
+// code.
+
+use chrono::Utc;
+use log::warn;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::config::AppConfig;
+use super::database::Database;
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue, OptionExt};
+use crate::utils::crypto::CryptoUtils;
+use crate::utils::security::Totp;
+
+const RECOVERY_CODES: usize = 10;
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct TwoFactorStatus {
+    /// Login asks for a code
+    pub enabled: bool,
+    /// Set up but not yet confirmed with a first code
+    pub pending: bool,
+    pub recovery_codes_left: i64,
+}
+
+/// What the user needs to add the account to an authenticator app; the
+/// secret and recovery codes are only ever shown here
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct TwoFactorSetup {
+    /// Base32 secret for manual entry
+    pub secret: String,
+    /// `otpauth://` URI to render as a QR code
+    pub otpauth_uri: String,
+    pub recovery_codes: Vec<String>,
+}
+
+fn invalid_code() -> AppError {
+    AppError::Validation(
+        ErrorValue::new(
+            ErrorCode::InvalidTwoFactorCode,
+            "The authentication code is wrong or was already used",
+        )
+        .with_field("code"),
+    )
+}
+
+fn recovery_hash(code: &str) -> String {
+    CryptoUtils::sha256(&Totp::normalize_recovery_code(code))
+}
+
+pub fn status(db: &Database, user_id: i64) -> AppResult<TwoFactorStatus> {
+    let record = db.two_factor(user_id)?;
+    Ok(TwoFactorStatus {
+        enabled: record.as_ref().is_some_and(|r| r.enabled),
+        pending: record.as_ref().is_some_and(|r| !r.enabled),
+        recovery_codes_left: db.recovery_codes_left(user_id)?,
+    })
+}
+
+/// Start setup for `user_id`, replacing an unconfirmed secret. Refused while
+/// two-factor sign-in is on; turn it off first.
+pub fn begin(db: &Database, config: &AppConfig, user_id: i64) -> AppResult<TwoFactorSetup> {
+    if db.two_factor(user_id)?.is_some_and(|r| r.enabled) {
+        return Err(AppError::Validation(ErrorValue::new(
+            ErrorCode::ValidationFailed,
+            "Two-factor sign-in is already on; turn it off first",
+        )));
+    }
+    let user = db.get_user_by_id(user_id)?.or_not_found("User", user_id)?;
+    let secret = Totp::generate_secret();
+    let recovery_codes = Totp::generate_recovery_codes(RECOVERY_CODES);
+    let hashes: Vec<String> = recovery_codes.iter().map(|c| recovery_hash(c)).collect();
+    db.begin_two_factor(user_id, &secret, &hashes)?;
+    Ok(TwoFactorSetup {
+        otpauth_uri: Totp::provisioning_uri(&secret, &user.email, config.get_totp_issuer()),
+        secret,
+        recovery_codes,
+    })
+}
+
+/// Turn two-factor sign-in on with the first code from the app
+pub fn confirm(db: &Database, config: &AppConfig, user_id: i64, code: &str) -> AppResult<()> {
+    let record = db.two_factor(user_id)?.ok_or_else(|| {
+        AppError::Validation(ErrorValue::new(
+            ErrorCode::ValidationFailed,
+            "Set up two-factor sign-in first",
+        ))
+    })?;
+    if record.enabled {
+        return Ok(());
+    }
+    check_totp(db, config, user_id, &record.secret, code)?;
+    db.enable_two_factor(user_id)
+}
+
+/// Turn two-factor sign-in off; needs a current code or a recovery code
+pub fn disable(db: &Database, config: &AppConfig, user_id: i64, code: &str) -> AppResult<()> {
+    verify(db, config, user_id, Some(code))?;
+    db.disable_two_factor(user_id)
+}
+
+/// The login check: passes when `user_id` has no second factor, else needs
+/// a TOTP code or an unused recovery code. Each code works once.
+pub fn verify(
+    db: &Database,
+    config: &AppConfig,
+    user_id: i64,
+    code: Option<&str>,
+) -> AppResult<()> {
+    let Some(record) = db.two_factor(user_id)?.filter(|r| r.enabled) else {
+        return Ok(());
+    };
+    let code = code.map(str::trim).filter(|c| !c.is_empty()).ok_or_else(|| {
+        AppError::Validation(
+            ErrorValue::new(
+                ErrorCode::TwoFactorRequired,
+                "Enter the code from your authenticator app",
+            )
+            .with_field("code"),
+        )
+    })?;
+    // Recovery codes have 8 characters, TOTP codes 6 digits
+    if Totp::normalize_recovery_code(code).len() != 8 {
+        return check_totp(db, config, user_id, &record.secret, code);
+    }
+    if db.use_recovery_code(user_id, &recovery_hash(code))? {
+        warn!("User {} signed in with a recovery code", user_id);
+        return Ok(());
+    }
+    Err(invalid_code())
+}
+
+fn check_totp(
+    db: &Database,
+    config: &AppConfig,
+    user_id: i64,
+    secret: &str,
+    code: &str,
+) -> AppResult<()> {
+    let now = Utc::now().timestamp();
+    match Totp::verify(secret, code, now, config.get_totp_drift()) {
+        Some(step) if db.record_totp_step(user_id, step)? => Ok(()),
+        _ => Err(invalid_code()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code_for(err: AppError) -> ErrorCode {
+        err.to_value().code.clone()
+    }
+
+    #[test]
+    fn test_setup_confirm_and_verify() {
+        let db = Database::new(":memory:").expect("Failed to create database");
+        db.init().expect("Failed to init schema");
+        db.init_two_factor().expect("Failed to init two factor");
+        let config = AppConfig::default();
+        let user = db
+            .insert_user("Ada", "ada@example.com", "Admin", "Active")
+            .expect("Failed to insert user");
+
+        assert!(verify(&db, &config, user, None).is_ok());
+
+        let setup = begin(&db, &config, user).unwrap();
+        assert!(setup.otpauth_uri.contains("ada%40example.com"));
+        assert!(status(&db, user).unwrap().pending);
+        // Pending secrets don't gate login yet
+        assert!(verify(&db, &config, user, None).is_ok());
+
+        let now = Totp::step(Utc::now().timestamp());
+        let code = Totp::code(&setup.secret, now).unwrap();
+        confirm(&db, &config, user, &code).unwrap();
+        assert!(status(&db, user).unwrap().enabled);
+        assert!(begin(&db, &config, user).is_err());
+
+        assert_eq!(
+            code_for(verify(&db, &config, user, None).unwrap_err()),
+            ErrorCode::TwoFactorRequired
+        );
+        // The confirming code can't be replayed
+        assert_eq!(
+            code_for(verify(&db, &config, user, Some(&code)).unwrap_err()),
+            ErrorCode::InvalidTwoFactorCode
+        );
+
+        let recovery = setup.recovery_codes[0].to_uppercase();
+        assert!(verify(&db, &config, user, Some(&recovery)).is_ok());
+        assert!(verify(&db, &config, user, Some(&recovery)).is_err());
+        assert_eq!(status(&db, user).unwrap().recovery_codes_left, 9);
+
+        disable(&db, &config, user, &setup.recovery_codes[1]).unwrap();
+        assert!(!status(&db, user).unwrap().enabled);
+    }
+}
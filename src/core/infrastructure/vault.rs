@@ -0,0 +1,246 @@
+// src/core/infrastructure/vault.rs
+// Encrypted key-value storage for the frontend, in place of localStorage for
+// tokens and preferences. Values belong to the session that stored them and
+// are sealed with AES-256-GCM under a key kept in the secret store as
+// "vault_key". The session and key are bound in as associated data, so a
+// sealed value copied to another row doesn't open. `clear_with_sessions`
+// removes a session's values when it ends.
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use log::{info, warn};
+use std::sync::{Arc, Mutex};
+
+use super::database::Database;
+use super::secrets::SecretStore;
+use super::sessions::{SessionInfo, SessionManager};
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::utils::crypto::CryptoUtils;
+
+/// Secret store entry holding the hex encoded vault key
+pub const KEY_SECRET: &str = "vault_key";
+/// Longest key the frontend may use
+pub const MAX_KEY_LEN: usize = 128;
+
+const NONCE_LEN: usize = 12;
+
+fn vault_error(message: &str, key: &str) -> AppError {
+    AppError::Serialization(
+        ErrorValue::new(ErrorCode::InternalError, message).with_context("key", key),
+    )
+}
+
+/// Remove the values of sessions as they end, however they end. Values
+/// left by the last run go now, as sessions don't outlive a restart.
+pub fn clear_with_sessions(db: Arc<Database>, sessions: &SessionManager) -> AppResult<()> {
+    let orphaned = db.clear_all_vaults()?;
+    if orphaned > 0 {
+        info!("Removed {} vault values of sessions from the last run", orphaned);
+    }
+    sessions.on_end(move |session_ids| {
+        for session_id in session_ids {
+            if let Err(e) = db.clear_vault(session_id) {
+                warn!("Failed to clear the vault of session {}: {}", session_id, e);
+            }
+        }
+    })
+}
+
+pub struct Vault {
+    secrets: Arc<dyn SecretStore>,
+    cipher: Mutex<Option<Aes256Gcm>>,
+}
+
+impl Vault {
+    /// A vault whose key is read from, or on first use created in, `secrets`
+    pub fn new(secrets: Arc<dyn SecretStore>) -> Self {
+        Self {
+            secrets,
+            cipher: Mutex::new(None),
+        }
+    }
+
+    fn cipher(&self) -> AppResult<Aes256Gcm> {
+        let mut cipher = self.cipher.lock()?;
+        if let Some(cipher) = cipher.as_ref() {
+            return Ok(cipher.clone());
+        }
+        let key = match self.secrets.get(KEY_SECRET)? {
+            Some(stored) => hex::decode(stored.trim())
+                .ok()
+                .filter(|key| key.len() == 32)
+                .ok_or_else(|| {
+                    AppError::Configuration(
+                        ErrorValue::new(ErrorCode::ConfigInvalid, "Vault key is damaged")
+                            .with_context("secret", KEY_SECRET),
+                    )
+                })?,
+            None => {
+                let key = CryptoUtils::random_bytes(32);
+                self.secrets.set(KEY_SECRET, &hex::encode(&key))?;
+                info!("Created a new vault key");
+                key
+            }
+        };
+        Ok(cipher
+            .insert(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)))
+            .clone())
+    }
+
+    fn check_key(key: &str) -> AppResult<()> {
+        if key.is_empty() || key.chars().count() > MAX_KEY_LEN {
+            return Err(AppError::Validation(
+                ErrorValue::new(
+                    ErrorCode::InvalidFieldValue,
+                    format!("Vault keys are 1 to {} characters", MAX_KEY_LEN),
+                )
+                .with_field("key"),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Store `value` under `key` in `session`; null removes it
+    pub fn set(
+        &self,
+        db: &Database,
+        session: &SessionInfo,
+        key: &str,
+        value: &serde_json::Value,
+    ) -> AppResult<()> {
+        Self::check_key(key)?;
+        if value.is_null() {
+            db.delete_vault_value(&session.session_id, key)?;
+            return Ok(());
+        }
+        let plaintext = serde_json::to_vec(value)?;
+        let nonce = CryptoUtils::random_bytes(NONCE_LEN);
+        let aad = format!("{}\n{}", session.session_id, key);
+        let ciphertext = self
+            .cipher()?
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &plaintext,
+                    aad: aad.as_bytes(),
+                },
+            )
+            .map_err(|_| vault_error("Failed to encrypt vault value", key))?;
+        let mut sealed = nonce;
+        sealed.extend_from_slice(&ciphertext);
+        db.set_vault_value(&session.session_id, session.user_id, key, &sealed)
+    }
+
+    /// The value `session` stored under `key`, null when there is none
+    pub fn get(
+        &self,
+        db: &Database,
+        session: &SessionInfo,
+        key: &str,
+    ) -> AppResult<serde_json::Value> {
+        Self::check_key(key)?;
+        let Some(sealed) = db.vault_value(&session.session_id, key)? else {
+            return Ok(serde_json::Value::Null);
+        };
+        if sealed.len() < NONCE_LEN {
+            return Err(vault_error("Vault value is damaged", key));
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let aad = format!("{}\n{}", session.session_id, key);
+        let plaintext = self
+            .cipher()?
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: aad.as_bytes(),
+                },
+            )
+            .map_err(|_| vault_error("Vault value can't be decrypted", key))?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::infrastructure::secrets::MemorySecretStore;
+    use crate::core::infrastructure::sessions::SessionManager;
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[test]
+    fn test_vault_round_trip_per_session() {
+        let db = Database::new(":memory:").expect("Failed to create database");
+        db.init().expect("Failed to init schema");
+        db.init_vault().expect("Failed to init vault");
+        let ada = db.insert_user("Ada", "ada@example.com", "Admin", "Active").unwrap();
+        let sessions = SessionManager::new(
+            b"test-key",
+            Duration::from_secs(60),
+            Duration::from_secs(3600),
+        );
+        let first = sessions.issue(ada, "Admin").unwrap().session;
+        let second = sessions.issue(ada, "Admin").unwrap().session;
+        let secrets: Arc<dyn SecretStore> = Arc::new(MemorySecretStore::new());
+        let vault = Vault::new(Arc::clone(&secrets));
+
+        let prefs = json!({ "theme": "dark", "token": "t0ken" });
+        vault.set(&db, &first, "prefs", &prefs).unwrap();
+        assert_eq!(vault.get(&db, &first, "prefs").unwrap(), prefs);
+        assert_eq!(vault.get(&db, &second, "prefs").unwrap(), json!(null));
+        let sealed = db.vault_value(&first.session_id, "prefs").unwrap().unwrap();
+        assert!(!String::from_utf8_lossy(&sealed).contains("t0ken"));
+
+        // The key persists in the secret store, so another vault reads it
+        let reopened = Vault::new(secrets);
+        assert_eq!(reopened.get(&db, &first, "prefs").unwrap(), prefs);
+
+        // A value moved to another session's row doesn't open
+        db.set_vault_value(&second.session_id, ada, "prefs", &sealed).unwrap();
+        assert!(vault.get(&db, &second, "prefs").is_err());
+
+        vault.set(&db, &first, "prefs", &json!(null)).unwrap();
+        assert_eq!(vault.get(&db, &first, "prefs").unwrap(), json!(null));
+        assert!(vault.set(&db, &first, "", &json!(1)).is_err());
+        assert_eq!(db.clear_vault(&second.session_id).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_values_end_with_their_session() {
+        let db = Arc::new(Database::new(":memory:").expect("Failed to create database"));
+        db.init().expect("Failed to init schema");
+        db.init_vault().expect("Failed to init vault");
+        let ada = db.insert_user("Ada", "ada@example.com", "Admin", "Active").unwrap();
+        let bob = db.insert_user("Bob", "bob@example.com", "User", "Active").unwrap();
+        db.set_vault_value("from-last-run", ada, "prefs", b"sealed").unwrap();
+
+        let sessions = SessionManager::new(
+            b"test-key",
+            Duration::from_secs(60),
+            Duration::from_secs(3600),
+        );
+        clear_with_sessions(Arc::clone(&db), &sessions).unwrap();
+        assert_eq!(db.vault_value("from-last-run", "prefs").unwrap(), None);
+
+        let vault = Vault::new(Arc::new(MemorySecretStore::new()));
+        let ada_sessions = [
+            sessions.issue(ada, "Admin").unwrap().session,
+            sessions.issue(ada, "Admin").unwrap().session,
+        ];
+        let bob_session = sessions.issue(bob, "User").unwrap().session;
+        for session in ada_sessions.iter().chain([&bob_session]) {
+            vault.set(&db, session, "prefs", &json!("dark")).unwrap();
+        }
+
+        // A password change signs every session of the user out
+        assert_eq!(sessions.revoke_user(ada).unwrap(), 2);
+        for session in &ada_sessions {
+            assert_eq!(db.vault_value(&session.session_id, "prefs").unwrap(), None);
+        }
+        assert_eq!(vault.get(&db, &bob_session, "prefs").unwrap(), json!("dark"));
+
+        sessions.revoke(&bob_session.session_id).unwrap();
+        assert_eq!(db.vault_value(&bob_session.session_id, "prefs").unwrap(), None);
+    }
+}
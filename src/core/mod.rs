@@ -6,3 +6,4 @@ pub mod application;
 pub mod infrastructure;
 pub mod presentation;
 pub mod error;
+pub mod rpc;
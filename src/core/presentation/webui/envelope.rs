@@ -0,0 +1,167 @@
+// src/core/presentation/webui/envelope.rs
+// Handler bodies written as `AppResult`-returning closures. `respond` turns
+// the result into the `{ success, data, error }` envelope: failures are
+// recorded with the error tracker and reach the frontend as an
+// `ErrorResponse` carrying the call's correlation id, never as a string the
+// handler formatted itself. The log line gets the error's full internal
+// details; the response only its public message. Handlers that need a
+// signed-in user use `respond_with_session`, those that need a permission
+// `respond_permitted`; both refuse calls without a valid bridge signature
+// when `[security] sign_bridge_messages` is on. Permission checked handlers
+// also take an `api_key` from automation clients on the http_rest and
+// websocket transports, checked against the key's scopes. A refused
+// permission is published as a `permission_denied` security event.
+
+use log::error;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::core::error::{AppError, AppResult};
+use crate::core::infrastructure::api_keys;
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::config::CallConfig;
+use crate::core::infrastructure::database::Database;
+use crate::core::infrastructure::di;
+use crate::core::infrastructure::error_handler;
+use crate::core::infrastructure::rbac::{self, Caller};
+use crate::core::infrastructure::security_audit;
+use crate::core::infrastructure::sessions::{self, SessionInfo, SessionManager};
+use crate::core::rpc::{RpcRequest, RpcResponse};
+
+/// Run `body` and answer `request` with its result; errors are logged and
+/// recorded under `source`, e.g. "SETTINGS"
+pub fn respond<T: Serialize>(
+    request: &RpcRequest,
+    source: &'static str,
+    body: impl FnOnce() -> AppResult<T>,
+) -> RpcResponse {
+    match body() {
+        Ok(data) => request.ok(data),
+        Err(e) => {
+            error!("{} failed: {}", request.handler, e.internal_details());
+            error_handler::record_app_error(source, &e);
+            request.err(e.to_value().clone())
+        }
+    }
+}
+
+/// `respond` with the payload parsed into `R` first. A payload that doesn't
+/// parse is answered with the parse error and `body` isn't called.
+pub fn respond_with<R: DeserializeOwned, T: Serialize>(
+    request: &RpcRequest,
+    source: &'static str,
+    body: impl FnOnce(R) -> AppResult<T>,
+) -> RpcResponse {
+    match request.parse::<R>() {
+        Ok(req) => respond(request, source, || body(req)),
+        Err(e) => request.err(e),
+    }
+}
+
+/// The session the request's `session_token` belongs to
+pub fn session(request: &RpcRequest) -> AppResult<SessionInfo> {
+    bridge_signing::check_current(&request.handler).map_err(AppError::Validation)?;
+    let token = request
+        .session_token
+        .as_deref()
+        .ok_or_else(|| sessions::unauthorized(&request.handler))?;
+    di::get_container()
+        .resolve_arc::<SessionManager>()?
+        .validate(token)
+}
+
+/// `respond_with` for handlers that need a signed-in user. A call without a
+/// valid session is answered with UNAUTHORIZED or SESSION_EXPIRED and `body`
+/// isn't called; those aren't recorded as handler failures.
+pub fn respond_with_session<R: DeserializeOwned, T: Serialize>(
+    request: &RpcRequest,
+    source: &'static str,
+    body: impl FnOnce(SessionInfo, R) -> AppResult<T>,
+) -> RpcResponse {
+    match session(request) {
+        Ok(info) => respond_with(request, source, |req| body(info, req)),
+        Err(e) => request.err(e.to_value().clone()),
+    }
+}
+
+/// Audit a call refused `permission`; `by` names the role or API key
+fn denied(request: &RpcRequest, user_id: Option<i64>, permission: &str, by: &str) {
+    let detail = format!("{} called by {}", request.handler, by);
+    security_audit::emit("permission_denied", user_id, permission, Some(&detail));
+}
+
+/// The caller of `request`, if its role has `permission`. Calls without a
+/// session act as `[security] anonymous_role`, or are refused when it's unset.
+/// A call with an `api_key` needs the permission among the key's scopes; the
+/// key stands in for the bridge signature.
+pub fn authorize(request: &RpcRequest, permission: &str) -> AppResult<Caller> {
+    let config = di::resolve_scoped::<CallConfig>()?;
+    if let Some(key) = request.api_key.as_deref() {
+        if !api_keys::accepted(&config) {
+            return Err(sessions::unauthorized(&request.handler));
+        }
+        let db = di::get_container().resolve::<Arc<Database>>()?;
+        let record = api_keys::verify(&db, key)?;
+        if let Err(e) = api_keys::check_scope(&record, permission) {
+            denied(request, None, permission, &format!("API key {}", record.prefix));
+            return Err(e);
+        }
+        return Ok(Caller {
+            role: api_keys::ROLE.to_string(),
+            session: None,
+            api_key: Some(record),
+        });
+    }
+    bridge_signing::check_current(&request.handler).map_err(AppError::Validation)?;
+    let caller = match request.session_token {
+        Some(_) => {
+            let info = session(request)?;
+            Caller {
+                role: info.role.clone(),
+                session: Some(info),
+                api_key: None,
+            }
+        }
+        None => Caller {
+            role: config
+                .get_anonymous_role()
+                .ok_or_else(|| sessions::unauthorized(&request.handler))?
+                .to_string(),
+            session: None,
+            api_key: None,
+        },
+    };
+    if let Err(e) = rbac::check(&config, &caller.role, permission) {
+        denied(request, caller.user_id(), permission, &format!("role {}", caller.role));
+        return Err(e);
+    }
+    Ok(caller)
+}
+
+/// `respond` for handlers that need `permission`. A caller without it is
+/// answered with FORBIDDEN (or a sign-in error) and `body` isn't called.
+pub fn respond_permitted<T: Serialize>(
+    request: &RpcRequest,
+    source: &'static str,
+    permission: &str,
+    body: impl FnOnce(Caller) -> AppResult<T>,
+) -> RpcResponse {
+    match authorize(request, permission) {
+        Ok(caller) => respond(request, source, || body(caller)),
+        Err(e) => request.err(e.to_value().clone()),
+    }
+}
+
+/// `respond_with` for handlers that need `permission`
+pub fn respond_permitted_with<R: DeserializeOwned, T: Serialize>(
+    request: &RpcRequest,
+    source: &'static str,
+    permission: &str,
+    body: impl FnOnce(Caller, R) -> AppResult<T>,
+) -> RpcResponse {
+    match authorize(request, permission) {
+        Ok(caller) => respond_with(request, source, |req| body(caller, req)),
+        Err(e) => request.err(e.to_value().clone()),
+    }
+}
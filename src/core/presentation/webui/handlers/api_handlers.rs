@@ -0,0 +1,41 @@
+// src/core/presentation/webui/handlers/api_handlers.rs
+// API description handlers - expose the generated OpenAPI document
+
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::outbound_queue::{self, PushPriority};
+use crate::core::infrastructure::{config::AppConfig, di};
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use crate::core::rpc::RpcRequest;
+use log::info;
+use webui_rs::webui;
+
+pub fn setup_api_handlers(window: &mut webui::Window) {
+    window.bind_traced("get_openapi_spec", |event| {
+        info!("get_openapi_spec called from frontend");
+        let request = RpcRequest::new(event.event_number, "get_openapi_spec", None);
+
+        let config = di::get_container()
+            .resolve::<AppConfig>()
+            .unwrap_or_default();
+        let spec = openapi::generate_spec(config.get_app_name(), config.get_version());
+
+        let response = request.ok(spec).to_json();
+
+        outbound_queue::push_event(
+            event.window,
+            "get_openapi_spec_response",
+            codec::shared().encode(event.window, "get_openapi_spec_response", &response),
+            PushPriority::Critical,
+        );
+    });
+
+    openapi::register(
+        HandlerSpec::new("get_openapi_spec", "Get the OpenAPI document for all handlers")
+            .tag("api")
+            .response::<serde_json::Value>()
+            .event("get_openapi_spec_response"),
+    );
+
+    info!("API handlers set up successfully");
+}
@@ -0,0 +1,126 @@
+// src/core/presentation/webui/handlers/api_key_handlers.rs
+// API key handlers: issue, list and revoke the keys automation clients use
+// on the http_rest and websocket transports
+
+use crate::core::infrastructure::api_keys::{self, IssuedApiKey};
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::config::CallConfig;
+use crate::core::infrastructure::database::{ApiKeyRecord, Database};
+use crate::core::infrastructure::di;
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::outbound_queue::{self, PushPriority};
+use crate::core::infrastructure::security_audit;
+use crate::core::presentation::webui::envelope;
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use crate::core::rpc::{RpcRequest, RpcResponse};
+use log::info;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::ffi::CStr;
+use std::sync::Arc;
+use webui_rs::webui;
+use webui_rs::webui::bindgen::webui_interface_get_string_at;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ApiKeyCreateRequest {
+    pub name: String,
+    /// Permissions the key grants, e.g. ["users:read"]; each must be one the
+    /// caller has
+    pub scopes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ApiKeyRevokeRequest {
+    pub id: i64,
+}
+
+fn read_event_payload(event: &webui::Event) -> Option<String> {
+    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
+    if ptr.is_null() {
+        return None;
+    }
+    bridge_signing::unwrap_payload(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+}
+
+fn send_response(window: usize, event_name: &str, response: &RpcResponse) {
+    let detail = codec::shared().encode(window, event_name, &response.to_json());
+    outbound_queue::push_event(window, event_name, detail, PushPriority::Critical);
+}
+
+pub fn setup_api_key_handlers(window: &mut webui::Window) {
+    window.bind_traced("api_key_create", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "api_key_create", data.as_deref());
+        let response = envelope::respond_permitted_with(
+            &request,
+            "API_KEYS",
+            "api_keys:create",
+            |caller, req: ApiKeyCreateRequest| {
+                info!("api_key_create {} called from frontend", req.name);
+                let config = di::resolve_scoped::<CallConfig>()?;
+                let db = di::get_container().resolve::<Arc<Database>>()?;
+                let granted = caller.permissions(&config);
+                let issued =
+                    api_keys::issue(&db, &req.name, &req.scopes, &granted, caller.user_id())?;
+                let (prefix, scopes) = (&issued.record.prefix, issued.record.scopes.join(" "));
+                security_audit::emit("api_key_created", caller.user_id(), prefix, Some(&scopes));
+                Ok(issued)
+            },
+        );
+        send_response(event.window, "api_key_create_response", &response);
+    });
+
+    window.bind_traced("api_key_list", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "api_key_list", data.as_deref());
+        let response = envelope::respond_permitted(&request, "API_KEYS", "api_keys:read", |_| {
+            di::get_container().resolve::<Arc<Database>>()?.api_keys()
+        });
+        send_response(event.window, "api_key_list_response", &response);
+    });
+
+    window.bind_traced("api_key_revoke", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "api_key_revoke", data.as_deref());
+        let response = envelope::respond_permitted_with(
+            &request,
+            "API_KEYS",
+            "api_keys:revoke",
+            |caller, req: ApiKeyRevokeRequest| {
+                info!("api_key_revoke {} called from frontend", req.id);
+                let db = di::get_container().resolve::<Arc<Database>>()?;
+                let revoked = api_keys::revoke(&db, req.id)?;
+                security_audit::emit("api_key_revoked", caller.user_id(), &revoked.prefix, None);
+                Ok(())
+            },
+        );
+        send_response(event.window, "api_key_revoke_response", &response);
+    });
+
+    openapi::register(
+        HandlerSpec::new(
+            "api_key_create",
+            "Issue an API key with the given scopes; the key is only returned this once",
+        )
+        .tag("api_keys")
+        .request::<ApiKeyCreateRequest>()
+        .response::<IssuedApiKey>()
+        .event("api_key_create_response"),
+    );
+    openapi::register(
+        HandlerSpec::new("api_key_list", "Every issued API key, newest first, without the keys")
+            .tag("api_keys")
+            .response::<Vec<ApiKeyRecord>>()
+            .event("api_key_list_response"),
+    );
+    openapi::register(
+        HandlerSpec::new("api_key_revoke", "Revoke an API key; it is refused from then on")
+            .tag("api_keys")
+            .request::<ApiKeyRevokeRequest>()
+            .response::<()>()
+            .event("api_key_revoke_response"),
+    );
+
+    info!("API key handlers set up");
+}
@@ -0,0 +1,61 @@
+// src/core/presentation/webui/handlers/audit_handlers.rs
+// Security audit handler: the sign-ins, refused permissions, settings
+// changes and other security events `security_audit` recorded, filtered
+
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::database::{AuditEntry, AuditFilter, Database};
+use crate::core::infrastructure::di;
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::outbound_queue::{self, PushPriority};
+use crate::core::presentation::webui::envelope;
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use crate::core::rpc::{RpcRequest, RpcResponse};
+use log::info;
+use std::ffi::CStr;
+use std::sync::Arc;
+use webui_rs::webui;
+use webui_rs::webui::bindgen::webui_interface_get_string_at;
+
+fn read_event_payload(event: &webui::Event) -> Option<String> {
+    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
+    if ptr.is_null() {
+        return None;
+    }
+    bridge_signing::unwrap_payload(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+}
+
+fn send_response(window: usize, event_name: &str, response: &RpcResponse) {
+    let detail = codec::shared().encode(window, event_name, &response.to_json());
+    outbound_queue::push_event(window, event_name, detail, PushPriority::Critical);
+}
+
+pub fn setup_audit_handlers(window: &mut webui::Window) {
+    window.bind_traced("security_audit", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "security_audit", data.as_deref());
+        let response = envelope::respond_permitted_with(
+            &request,
+            "AUDIT",
+            "audit:read",
+            |_, filter: AuditFilter| {
+                info!("security_audit called from frontend");
+                di::get_container().resolve::<Arc<Database>>()?.audit_entries(&filter)
+            },
+        );
+        send_response(event.window, "security_audit_response", &response);
+    });
+
+    openapi::register(
+        HandlerSpec::new(
+            "security_audit",
+            "Recorded security events, newest first, filtered by kind, user, subject and time",
+        )
+        .tag("security")
+        .request::<AuditFilter>()
+        .response::<Vec<AuditEntry>>()
+        .event("security_audit_response"),
+    );
+
+    info!("Audit handlers set up");
+}
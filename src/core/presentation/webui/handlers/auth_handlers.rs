@@ -0,0 +1,262 @@
+// src/core/presentation/webui/handlers/auth_handlers.rs
+// Sign-in handlers: password login and logout, password changes, and
+// setting up, confirming and turning off two-factor sign-in with an
+// authenticator app
+
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::core::infrastructure::auth;
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::config::CallConfig;
+use crate::core::infrastructure::database::Database;
+use crate::core::infrastructure::di;
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::outbound_queue::{self, PushPriority};
+use crate::core::infrastructure::sessions::{SessionManager, SessionToken};
+use crate::core::infrastructure::two_factor::{self, TwoFactorSetup, TwoFactorStatus};
+use crate::core::presentation::webui::envelope;
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use crate::core::rpc::{RpcRequest, RpcResponse};
+use log::info;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::ffi::CStr;
+use std::sync::Arc;
+use webui_rs::webui;
+use webui_rs::webui::bindgen::webui_interface_get_string_at;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TwoFactorCodeRequest {
+    /// 6-digit code from the authenticator app, or a recovery code
+    pub code: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct LoginRequest {
+    pub email: String,
+    pub password: String,
+    /// Authenticator or recovery code, for users with two-factor sign-in on
+    pub code: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ChangePasswordRequest {
+    /// Another user's id, for an administrator's reset (needs
+    /// `users:update`); the caller's own password when omitted
+    pub user_id: Option<i64>,
+    /// Required when changing one's own password
+    pub current_password: Option<String>,
+    pub new_password: String,
+}
+
+fn read_event_payload(event: &webui::Event) -> Option<String> {
+    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
+    if ptr.is_null() {
+        return None;
+    }
+    bridge_signing::unwrap_payload(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+}
+
+fn send_response(window: usize, event_name: &str, response: &RpcResponse) {
+    let detail = codec::shared().encode(window, event_name, &response.to_json());
+    outbound_queue::push_event(window, event_name, detail, PushPriority::Critical);
+}
+
+fn database() -> AppResult<Arc<Database>> {
+    di::get_container().resolve::<Arc<Database>>()
+}
+
+fn session_manager() -> AppResult<Arc<SessionManager>> {
+    di::get_container().resolve_arc::<SessionManager>()
+}
+
+/// Change the caller's own password, or reset another user's when
+/// `req.user_id` names someone else and the caller has `users:update`
+fn change_password(request: &RpcRequest, req: ChangePasswordRequest) -> AppResult<usize> {
+    let (db, sessions) = (database()?, session_manager()?);
+    let config = di::resolve_scoped::<CallConfig>()?;
+    let own = envelope::session(request).ok().map(|s| s.user_id);
+    if let Some(user_id) = req.user_id.filter(|&id| Some(id) != own) {
+        envelope::authorize(request, "users:update")?;
+        return auth::change_password(&db, &config, &sessions, user_id, None, &req.new_password);
+    }
+    let session = envelope::session(request)?;
+    let current = req.current_password.as_deref().ok_or_else(|| {
+        AppError::Validation(
+            ErrorValue::new(ErrorCode::MissingRequiredField, "Current password is required")
+                .with_field("current_password"),
+        )
+    })?;
+    let user_id = session.user_id;
+    auth::change_password(&db, &config, &sessions, user_id, Some(current), &req.new_password)
+}
+
+pub fn setup_auth_handlers(window: &mut webui::Window) {
+    window.bind_traced("auth_login", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "auth_login", data.as_deref());
+        let response = envelope::respond_with(&request, "AUTH", |req: LoginRequest| {
+            let config = di::resolve_scoped::<CallConfig>()?;
+            let (db, sessions) = (database()?, session_manager()?);
+            auth::login(
+                &db,
+                &config,
+                &sessions,
+                &req.email,
+                &req.password,
+                req.code.as_deref(),
+            )
+        });
+        send_response(event.window, "auth_login_response", &response);
+    });
+
+    window.bind_traced("auth_logout", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "auth_logout", data.as_deref());
+        let response = envelope::respond_with_session(
+            &request,
+            "AUTH",
+            |session, _: serde_json::Value| {
+                info!("auth_logout called for user {}", session.user_id);
+                let sessions = session_manager()?;
+                auth::logout(&sessions, &session)
+            },
+        );
+        send_response(event.window, "auth_logout_response", &response);
+    });
+
+    window.bind_traced("auth_change_password", |event| {
+        let data = read_event_payload(&event);
+        let request =
+            RpcRequest::new(event.event_number, "auth_change_password", data.as_deref());
+        let response = envelope::respond_with(&request, "AUTH", |req: ChangePasswordRequest| {
+            change_password(&request, req)
+        });
+        send_response(event.window, "auth_change_password_response", &response);
+    });
+
+    window.bind_traced("two_factor_status", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "two_factor_status", data.as_deref());
+        let response = envelope::respond_with_session(
+            &request,
+            "AUTH",
+            |session, _: serde_json::Value| {
+                let db = database()?;
+                two_factor::status(&db, session.user_id)
+            },
+        );
+        send_response(event.window, "two_factor_status_response", &response);
+    });
+
+    window.bind_traced("two_factor_setup", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "two_factor_setup", data.as_deref());
+        let response = envelope::respond_with_session(
+            &request,
+            "AUTH",
+            |session, _: serde_json::Value| {
+                info!("two_factor_setup called for user {}", session.user_id);
+                let config = di::resolve_scoped::<CallConfig>()?;
+                let db = database()?;
+                two_factor::begin(&db, &config, session.user_id)
+            },
+        );
+        send_response(event.window, "two_factor_setup_response", &response);
+    });
+
+    window.bind_traced("two_factor_enable", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "two_factor_enable", data.as_deref());
+        let response = envelope::respond_with_session(
+            &request,
+            "AUTH",
+            |session, req: TwoFactorCodeRequest| {
+                let config = di::resolve_scoped::<CallConfig>()?;
+                let db = database()?;
+                two_factor::confirm(&db, &config, session.user_id, &req.code)?;
+                info!("Two-factor sign-in turned on for user {}", session.user_id);
+                two_factor::status(&db, session.user_id)
+            },
+        );
+        send_response(event.window, "two_factor_enable_response", &response);
+    });
+
+    window.bind_traced("two_factor_disable", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "two_factor_disable", data.as_deref());
+        let response = envelope::respond_with_session(
+            &request,
+            "AUTH",
+            |session, req: TwoFactorCodeRequest| {
+                let config = di::resolve_scoped::<CallConfig>()?;
+                let db = database()?;
+                two_factor::disable(&db, &config, session.user_id, &req.code)?;
+                info!("Two-factor sign-in turned off for user {}", session.user_id);
+                two_factor::status(&db, session.user_id)
+            },
+        );
+        send_response(event.window, "two_factor_disable_response", &response);
+    });
+
+    openapi::register(
+        HandlerSpec::new("auth_login", "Sign in with email and password; returns a session token")
+            .tag("auth")
+            .request::<LoginRequest>()
+            .response::<SessionToken>()
+            .event("auth_login_response"),
+    );
+    openapi::register(
+        HandlerSpec::new("auth_logout", "End the calling session")
+            .tag("auth")
+            .response::<bool>()
+            .event("auth_logout_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "auth_change_password",
+            "Change the caller's password, or reset another user's; signs the user out everywhere",
+        )
+        .tag("auth")
+        .request::<ChangePasswordRequest>()
+        .response::<usize>()
+        .event("auth_change_password_response"),
+    );
+    openapi::register(
+        HandlerSpec::new("two_factor_status", "Whether the signed-in user has two-factor sign-in on")
+            .tag("auth")
+            .response::<TwoFactorStatus>()
+            .event("two_factor_status_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "two_factor_setup",
+            "Start two-factor setup: a new TOTP secret, its otpauth:// URI and recovery codes",
+        )
+        .tag("auth")
+        .response::<TwoFactorSetup>()
+        .event("two_factor_setup_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "two_factor_enable",
+            "Turn two-factor sign-in on with the first code from the authenticator app",
+        )
+        .tag("auth")
+        .request::<TwoFactorCodeRequest>()
+        .response::<TwoFactorStatus>()
+        .event("two_factor_enable_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "two_factor_disable",
+            "Turn two-factor sign-in off with a current code or a recovery code",
+        )
+        .tag("auth")
+        .request::<TwoFactorCodeRequest>()
+        .response::<TwoFactorStatus>()
+        .event("two_factor_disable_response"),
+    );
+
+    info!("Auth handlers set up");
+}
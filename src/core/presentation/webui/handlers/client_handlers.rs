@@ -0,0 +1,94 @@
+// src/core/presentation/webui/handlers/client_handlers.rs
+// Client connection tracking and the connected-clients query
+
+use super::logging_handlers;
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::client_registry::{ClientInfo, GLOBAL_CLIENT_REGISTRY};
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::event_bridge::GLOBAL_CLIENT_SUBSCRIPTIONS;
+use crate::core::infrastructure::event_bus::GLOBAL_EVENT_BUS;
+use crate::core::infrastructure::logging::stream as log_stream;
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::outbound_queue::{self, PushPriority, GLOBAL_OUTBOUND_QUEUE};
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use crate::core::rpc::RpcRequest;
+use log::{debug, info};
+use std::ffi::c_char;
+use webui_rs::webui;
+use webui_rs::webui::bindgen::webui_interface_bind;
+
+// Event types from webui.h (enum webui_event)
+const WEBUI_EVENT_DISCONNECTED: usize = 0;
+const WEBUI_EVENT_CONNECTED: usize = 1;
+
+extern "C" fn on_window_event(
+    window: usize,
+    event_type: usize,
+    _element: *mut c_char,
+    _event_number: usize,
+    _bind_id: usize,
+) {
+    match event_type {
+        WEBUI_EVENT_CONNECTED => {
+            GLOBAL_CLIENT_REGISTRY.on_connected(window);
+            logging_handlers::install_error_hook(window);
+            inject_bridge_key(window);
+        }
+        WEBUI_EVENT_DISCONNECTED if GLOBAL_CLIENT_REGISTRY.on_disconnected(window) => {
+            let dropped = GLOBAL_OUTBOUND_QUEUE.purge_window(window);
+            debug!("Dropped {} pending pushes for window {}", dropped, window);
+            let subscriptions = GLOBAL_CLIENT_SUBSCRIPTIONS.remove_window(&GLOBAL_EVENT_BUS, window);
+            debug!("Dropped {} event subscriptions of window {}", subscriptions, window);
+            if log_stream::unfollow(window) {
+                debug!("Window {} stopped following the log", window);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Hand the page its bridge signing key; a reload gets it again on connect
+fn inject_bridge_key(window: usize) {
+    if let Some(signer) = bridge_signing::active() {
+        let js = format!("window.__BRIDGE_KEY = '{}';", signer.key_hex());
+        webui::Window::from_id(window).run_js(&js);
+    }
+}
+
+/// Track connects/disconnects for a window; call once per window before showing it
+pub fn track_window(window: &webui::Window, label: &str) {
+    GLOBAL_CLIENT_REGISTRY.register_window(window.id, label);
+    // An empty element binds the window-level connection events
+    let all_events = c"".as_ptr();
+    unsafe { webui_interface_bind(window.id, all_events, Some(on_window_event)) };
+}
+
+pub fn setup_client_handlers(window: &mut webui::Window) {
+    window.bind_traced("get_clients", |event| {
+        info!("get_clients called from frontend");
+        let request = RpcRequest::new(event.event_number, "get_clients", None);
+
+        let clients: Vec<ClientInfo> = GLOBAL_CLIENT_REGISTRY.clients();
+        let detail = request
+            .ok(serde_json::json!({
+                "clients": clients,
+                "current_window": event.window,
+            }))
+            .to_json();
+        outbound_queue::push_event(
+            event.window,
+            "get_clients_response",
+            codec::shared().encode(event.window, "get_clients_response", &detail),
+            PushPriority::Critical,
+        );
+    });
+
+    openapi::register(
+        HandlerSpec::new("get_clients", "List connected frontend clients")
+            .tag("clients")
+            .response::<Vec<ClientInfo>>()
+            .event("get_clients_response"),
+    );
+
+    info!("Client handlers set up successfully");
+}
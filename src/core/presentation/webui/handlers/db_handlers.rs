@@ -1,9 +1,26 @@
-use crate::core::error::{AppError, ErrorValue, ErrorCode};
+use crate::core::domain::events::{UserCreated, UserDeleted, UserUpdated};
+use crate::core::domain::traits::EventTopic;
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::core::infrastructure::auth;
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::config::CallConfig;
 use crate::core::infrastructure::database::Database;
-use crate::core::infrastructure::error_handler;
+use crate::core::infrastructure::database::models::User;
+use crate::core::infrastructure::di;
+use crate::core::infrastructure::event_bus::GLOBAL_EVENT_BUS;
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::outbound_queue::{self, PushPriority};
+use crate::core::infrastructure::sanitize;
+use crate::core::infrastructure::sessions::SessionManager;
+use crate::core::presentation::webui::envelope;
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use crate::core::rpc::{RpcRequest, RpcResponse};
 use log::{error, info};
+use std::ffi::CStr;
 use std::sync::{Arc, Mutex};
 use webui_rs::webui;
+use webui_rs::webui::bindgen::webui_interface_get_string_at;
 
 lazy_static::lazy_static! {
     static ref DB_INSTANCE: Mutex<Option<Arc<Database>>> = Mutex::new(None);
@@ -15,92 +32,93 @@ pub fn init_database(db: Arc<Database>) {
     info!("Database handlers initialized");
 }
 
-fn get_db() -> Option<Arc<Database>> {
+fn get_db() -> AppResult<Arc<Database>> {
     let instance = DB_INSTANCE.lock().unwrap();
-    instance.clone()
+    instance.clone().ok_or_else(|| {
+        AppError::DependencyInjection(
+            ErrorValue::new(ErrorCode::InternalError, "Database not initialized")
+                .with_cause("DI container missing database instance"),
+        )
+    })
 }
 
-/// Send a success response to the frontend
-fn send_success_response(window: webui::Window, event_name: &str, data: &serde_json::Value) {
-    let response = serde_json::json!({
-        "success": true,
-        "data": data,
-        "error": null
-    });
-    dispatch_event(window, event_name, &response);
+fn read_arg(event: &webui::Event, index: usize) -> Option<String> {
+    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, index) };
+    if ptr.is_null() {
+        return None;
+    }
+    let arg = unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() };
+    match index {
+        0 => bridge_signing::unwrap_payload(arg),
+        _ => Some(arg),
+    }
 }
 
-/// Send an error response to the frontend using structured error values
-fn send_error_response(window: webui::Window, event_name: &str, err: &AppError) {
-    let error_value = err.to_value();
-    let response = serde_json::json!({
-        "success": false,
-        "data": null,
-        "error": error_value.to_response()
-    });
-    dispatch_event(window, event_name, &response);
+/// Send a response to the frontend
+fn send_response(window: webui::Window, event_name: &str, response: &RpcResponse) {
+    let detail = codec::shared().encode(window.id, event_name, &response.to_json());
+    outbound_queue::push_event(window.id, event_name, detail, PushPriority::Critical);
 }
 
-/// Helper to dispatch a custom event to the frontend
-fn dispatch_event(window: webui::Window, event_name: &str, detail: &serde_json::Value) {
-    let js = format!(
-        "window.dispatchEvent(new CustomEvent('{}', {{ detail: {} }}))",
-        event_name, detail
-    );
-    webui::Window::from_id(window.id).run_js(&js);
+/// Success data with a message for the UI: `{ message, payload }`
+fn with_message<T: serde::Serialize>(message: String, payload: T) -> serde_json::Value {
+    serde_json::json!({
+        "message": message,
+        "payload": payload
+    })
+}
+
+/// The optional password after the fixed segments of the element string;
+/// it may itself contain ':'
+fn password_segment(parts: &[&str], from: usize) -> Option<String> {
+    parts.get(from..).map(|rest| rest.join(":")).filter(|p| !p.is_empty())
 }
 
-/// Handle a database operation result and send appropriate response
-fn handle_db_result<T: serde::Serialize>(
-    window: webui::Window,
-    event_name: &str,
-    result: Result<T, AppError>,
-    success_message: Option<&str>,
-) {
-    match result {
-        Ok(data) => {
-            let response_data = if let Some(msg) = success_message {
-                serde_json::json!({
-                    "message": msg,
-                    "payload": data
-                })
-            } else {
-                serde_json::to_value(data).unwrap_or(serde_json::Value::Null)
-            };
-            send_success_response(window, event_name, &response_data);
-        }
-        Err(e) => {
-            error!("Database operation failed: {}", e);
-            error_handler::record_app_error("DB_HANDLER", &e);
-            send_error_response(window, event_name, &e);
-        }
+/// A field of `request`'s element string, sanitized like JSON payloads are
+fn clean(request: &RpcRequest, field: &str, value: &str) -> AppResult<String> {
+    sanitize::clean(&request.handler, field, value).map_err(AppError::Validation)
+}
+
+/// Refuse `password` when it breaks `[security.password_policy]`
+fn check_password(password: &str) -> AppResult<()> {
+    let config = di::resolve_scoped::<CallConfig>()?;
+    config.get_password_policy().validate(password, "password")
+}
+
+/// Set `user_id`'s password as an administrator's reset
+fn set_password(user_id: i64, password: &str) -> AppResult<()> {
+    let config = di::resolve_scoped::<CallConfig>()?;
+    let sessions = di::get_container().resolve_arc::<SessionManager>()?;
+    let db = get_db()?;
+    auth::change_password(&db, &config, &sessions, user_id, None, password)?;
+    Ok(())
+}
+
+/// Announce a user change on the event bus (forwarded to the frontend when
+/// the topic is bridged)
+fn publish_user_event<T: EventTopic + serde::Serialize>(event: T) {
+    if let Err(e) = GLOBAL_EVENT_BUS.publish_typed_with_source(&event, "db") {
+        error!("Failed to publish '{}': {}", T::TOPIC, e);
     }
 }
 
 pub fn setup_db_handlers(window: &mut webui::Window) {
-    window.bind("get_users", |event| {
+    window.bind_traced("get_users", |event| {
         info!("get_users called from frontend");
         info!("[Communication] Frontend → Backend (get_users): JSON/FFI call received");
-        let window = event.get_window();
-
-        let Some(db) = get_db() else {
-            let err = AppError::DependencyInjection(
-                ErrorValue::new(ErrorCode::InternalError, "Database not initialized")
-                    .with_cause("DI container missing database instance")
-            );
-            send_error_response(window, "db_response", &err);
-            return;
-        };
-
-        handle_db_result(
-            window,
-            "db_response",
-            db.get_all_users(),
-            Some("Users retrieved successfully"),
-        );
+        let data = read_arg(&event, 0);
+        let request = RpcRequest::new(event.event_number, "get_users", data.as_deref());
+        let response = envelope::respond_permitted(&request, "DB_HANDLER", "users:read", |_| {
+            let users = get_db()?.get_all_users()?;
+            Ok(with_message(
+                "Users retrieved successfully".to_string(),
+                users,
+            ))
+        });
+        send_response(event.get_window(), "db_response", &response);
     });
 
-    window.bind("create_user", |event| {
+    window.bind_traced("create_user", |event| {
         info!("create_user called from frontend");
 
         let element_name = unsafe {
@@ -116,25 +134,41 @@ pub fn setup_db_handlers(window: &mut webui::Window) {
         let email = if parts.len() > 2 { parts[2] } else { "" };
         let role = if parts.len() > 3 { parts[3] } else { "User" };
         let status = if parts.len() > 4 { parts[4] } else { "Active" };
+        let password = password_segment(&parts, 5);
 
-        let Some(db) = get_db() else {
-            let err = AppError::DependencyInjection(
-                ErrorValue::new(ErrorCode::InternalError, "Database not initialized")
-                    .with_cause("DI container missing database instance")
-            );
-            send_error_response(window, "user_create_response", &err);
-            return;
-        };
-
-        handle_db_result(
-            window,
-            "user_create_response",
-            db.insert_user(name, email, role, status),
-            Some(&format!("User '{}' created successfully", name)),
-        );
+        // The session token follows the element string
+        let request = RpcRequest::new(event.event_number, "create_user", None)
+            .with_session_token(read_arg(&event, 1));
+        let response = envelope::respond_permitted(&request, "DB_HANDLER", "users:create", |_| {
+            let (name, email) =
+                (clean(&request, "name", name)?, clean(&request, "email", email)?);
+            let (role, status) =
+                (clean(&request, "role", role)?, clean(&request, "status", status)?);
+            let password = password.map(|p| clean(&request, "password", &p)).transpose()?;
+            // Check the password first so a rejected one creates no user
+            if let Some(password) = &password {
+                check_password(password)?;
+            }
+            let id = get_db()?.insert_user(&name, &email, &role, &status)?;
+            if let Some(password) = &password {
+                set_password(id, password)?;
+            }
+            publish_user_event(UserCreated {
+                id,
+                name: name.clone(),
+                email,
+                role,
+                status,
+            });
+            Ok(with_message(
+                format!("User '{}' created successfully", name),
+                id,
+            ))
+        });
+        send_response(window, "user_create_response", &response);
     });
 
-    window.bind("update_user", |event| {
+    window.bind_traced("update_user", |event| {
         info!("update_user called from frontend");
 
         let element_name = unsafe {
@@ -171,25 +205,36 @@ pub fn setup_db_handlers(window: &mut webui::Window) {
         } else {
             None
         };
+        let password = password_segment(&parts, 6);
 
-        let Some(db) = get_db() else {
-            let err = AppError::DependencyInjection(
-                ErrorValue::new(ErrorCode::InternalError, "Database not initialized")
-                    .with_cause("DI container missing database instance")
-            );
-            send_error_response(window, "user_update_response", &err);
-            return;
-        };
-
-        handle_db_result(
-            window,
-            "user_update_response",
-            db.update_user(id, name, email, role, status),
-            Some(&format!("User ID {} updated successfully", id)),
-        );
+        // The session token follows the element string
+        let request = RpcRequest::new(event.event_number, "update_user", None)
+            .with_session_token(read_arg(&event, 1));
+        let response = envelope::respond_permitted(&request, "DB_HANDLER", "users:update", |_| {
+            let name = name.map(|v| clean(&request, "name", &v)).transpose()?;
+            let email = email.map(|v| clean(&request, "email", &v)).transpose()?;
+            let role = role.map(|v| clean(&request, "role", &v)).transpose()?;
+            let status = status.map(|v| clean(&request, "status", &v)).transpose()?;
+            let password = password.map(|p| clean(&request, "password", &p)).transpose()?;
+            if let Some(password) = &password {
+                check_password(password)?;
+            }
+            let rows = get_db()?.update_user(id, name, email, role, status)?;
+            if let Some(password) = password.as_deref().filter(|_| rows > 0) {
+                set_password(id, password)?;
+            }
+            if rows > 0 {
+                publish_user_event(UserUpdated { id });
+            }
+            Ok(with_message(
+                format!("User ID {} updated successfully", id),
+                rows,
+            ))
+        });
+        send_response(window, "user_update_response", &response);
     });
 
-    window.bind("delete_user", |event| {
+    window.bind_traced("delete_user", |event| {
         info!("delete_user called from frontend");
 
         let element_name = unsafe {
@@ -207,22 +252,55 @@ pub fn setup_db_handlers(window: &mut webui::Window) {
             0
         };
 
-        let Some(db) = get_db() else {
-            let err = AppError::DependencyInjection(
-                ErrorValue::new(ErrorCode::InternalError, "Database not initialized")
-                    .with_cause("DI container missing database instance")
-            );
-            send_error_response(window, "user_delete_response", &err);
-            return;
-        };
-
-        handle_db_result(
-            window,
-            "user_delete_response",
-            db.delete_user(id),
-            Some(&format!("User ID {} deleted successfully", id)),
-        );
+        // The session token follows the element string
+        let request = RpcRequest::new(event.event_number, "delete_user", None)
+            .with_session_token(read_arg(&event, 1));
+        let response = envelope::respond_permitted(&request, "DB_HANDLER", "users:delete", |_| {
+            let rows = get_db()?.delete_user(id)?;
+            if rows > 0 {
+                publish_user_event(UserDeleted { id });
+            }
+            Ok(with_message(
+                format!("User ID {} deleted successfully", id),
+                rows,
+            ))
+        });
+        send_response(window, "user_delete_response", &response);
     });
 
+    openapi::register(
+        HandlerSpec::new("get_users", "List all users")
+            .tag("database")
+            .response::<Vec<User>>()
+            .event("db_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "create_user",
+            "Create a user from `create_user:name:email:role:status[:password]`",
+        )
+            .tag("database")
+            .request::<String>()
+            .response::<i64>()
+            .event("user_create_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "update_user",
+            "Update a user from `update_user:id:name:email:role:status[:password]`",
+        )
+            .tag("database")
+            .request::<String>()
+            .response::<usize>()
+            .event("user_update_response"),
+    );
+    openapi::register(
+        HandlerSpec::new("delete_user", "Delete a user from `delete_user:id`")
+            .tag("database")
+            .request::<String>()
+            .response::<usize>()
+            .event("user_delete_response"),
+    );
+
     info!("Database handlers set up successfully");
 }
@@ -1,11 +1,40 @@
 // src/core/presentation/webui/handlers/error_handlers.rs
 // Error handling WebUI handlers - expose error stats to frontend
 
-use crate::core::error::ErrorCode;
-use crate::core::infrastructure::{error_handler, database::Database};
+use crate::core::error::{self, AppResult, ErrorCode, ErrorCodeInfo, ErrorValue};
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::crash_reporter::{self, CrashReportSummary};
+use crate::core::infrastructure::error_stats::{
+    ErrorStatsQuery, ErrorStatsReport, GLOBAL_ERROR_STATS,
+};
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::outbound_queue::{self, PushPriority};
+use crate::core::infrastructure::{di, error_handler, database::Database};
+use crate::core::presentation::webui::envelope;
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use crate::core::rpc::RpcRequest;
 use log::info;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::ffi::CStr;
 use std::sync::Arc;
 use webui_rs::webui;
+use webui_rs::webui::bindgen::webui_interface_get_string_at;
+
+/// The user's answer to the crash report prompt
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CrashReportSubmitRequest {
+    pub id: String,
+    /// true to send the report, false to discard it without sending
+    pub consent: bool,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CrashReportSubmitResult {
+    pub id: String,
+    pub submitted: bool,
+}
 
 lazy_static::lazy_static! {
     static ref DB_INSTANCE: std::sync::Mutex<Option<Arc<Database>>> = std::sync::Mutex::new(None);
@@ -22,30 +51,58 @@ fn get_db() -> Option<Arc<Database>> {
     instance.clone()
 }
 
+fn read_event_payload(event: &webui::Event) -> Option<String> {
+    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
+    if ptr.is_null() {
+        return None;
+    }
+    bridge_signing::unwrap_payload(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+}
+
+/// Submit the report when the user agreed, discard it otherwise
+fn answer_crash_report(req: CrashReportSubmitRequest) -> AppResult<CrashReportSubmitResult> {
+    let dir = crash_reporter::crash_dir();
+    if req.consent {
+        crash_reporter::submit(&dir, &req.id)?;
+    } else {
+        crash_reporter::discard(&dir, &req.id)?;
+    }
+    Ok(CrashReportSubmitResult {
+        id: req.id,
+        submitted: req.consent,
+    })
+}
+
+/// Helper to dispatch a custom event to the frontend
+fn dispatch_event(window: webui::Window, event_name: &str, detail: &serde_json::Value) {
+    let detail = codec::shared().encode(window.id, event_name, detail);
+    outbound_queue::push_event(window.id, event_name, detail, PushPriority::Critical);
+}
+
 pub fn setup_error_handlers(window: &mut webui::Window) {
     // Get error statistics
-    window.bind("get_error_stats", |_event| {
+    window.bind_traced("get_error_stats", |event| {
         info!("get_error_stats called from frontend");
+        let request = RpcRequest::new(event.event_number, "get_error_stats", None);
         let tracker = error_handler::get_error_tracker();
         let summary = tracker.get_summary();
         
-        let response = serde_json::json!({
-            "total": summary.total,
-            "errors": summary.errors,
-            "warnings": summary.warnings,
-            "critical": summary.critical,
-        });
-        
-        let js = format!(
-            "window.dispatchEvent(new CustomEvent('error_stats_response', {{ detail: {} }}))",
-            response
-        );
-        webui::Window::from_id(_event.get_window().id).run_js(&js);
+        let response = request
+            .ok(serde_json::json!({
+                "total": summary.total,
+                "errors": summary.errors,
+                "warnings": summary.warnings,
+                "critical": summary.critical,
+            }))
+            .to_json();
+        
+        dispatch_event(event.get_window(), "error_stats_response", &response);
     });
 
     // Get recent errors
-    window.bind("get_recent_errors", |event| {
+    window.bind_traced("get_recent_errors", |event| {
         info!("get_recent_errors called from frontend");
+        let request = RpcRequest::new(event.event_number, "get_recent_errors", None);
         
         let element_name = unsafe {
             std::ffi::CStr::from_ptr(event.element)
@@ -71,95 +128,185 @@ pub fn setup_error_handlers(window: &mut webui::Window) {
             })
         }).collect();
         
-        let response = serde_json::json!({
-            "errors": errors_json,
-            "count": errors.len(),
-        });
+        let response = request
+            .ok(serde_json::json!({
+                "errors": errors_json,
+                "count": errors.len(),
+            }))
+            .to_json();
         
-        let js = format!(
-            "window.dispatchEvent(new CustomEvent('recent_errors_response', {{ detail: {} }}))",
-            response
-        );
-        webui::Window::from_id(event.get_window().id).run_js(&js);
+        dispatch_event(event.get_window(), "recent_errors_response", &response);
     });
 
     // Clear error history
-    window.bind("clear_error_history", |_event| {
+    window.bind_traced("clear_error_history", |event| {
         info!("clear_error_history called from frontend");
+        let request = RpcRequest::new(event.event_number, "clear_error_history", None);
         let tracker = error_handler::get_error_tracker();
         tracker.clear();
         
-        let response = serde_json::json!({
-            "success": true,
-            "message": "Error history cleared",
-        });
+        let response = request
+            .ok(serde_json::json!({ "message": "Error history cleared" }))
+            .to_json();
         
-        let js = format!(
-            "window.dispatchEvent(new CustomEvent('error_history_cleared', {{ detail: {} }}))",
-            response
-        );
-        webui::Window::from_id(_event.get_window().id).run_js(&js);
+        dispatch_event(event.get_window(), "error_history_cleared", &response);
+    });
+
+    // Error code catalog, so the frontend can check it knows every code
+    window.bind_traced("error_codes", |event| {
+        info!("error_codes called from frontend");
+        let request = RpcRequest::new(event.event_number, "error_codes", None);
+        let response = request.ok(error::error_catalog()).to_json();
+
+        dispatch_event(event.get_window(), "error_codes_response", &response);
     });
 
+    // Most frequent error codes per handler, e.g. the top 10 of the last hour
+    window.bind_traced("error_stats", |event| {
+        info!("error_stats called from frontend");
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "error_stats", data.as_deref());
+        let response = envelope::respond_with(&request, "ERROR_STATS", |query: ErrorStatsQuery| {
+            GLOBAL_ERROR_STATS.report(&query)
+        })
+        .to_json();
+
+        dispatch_event(event.get_window(), "error_stats_report", &response);
+    });
+
+    // Crash reports saved by earlier runs, for the frontend to offer
+    window.bind_traced("crash_report_list", |event| {
+        info!("crash_report_list called from frontend");
+        let request = RpcRequest::new(event.event_number, "crash_report_list", None);
+        let response = request
+            .ok(crash_reporter::pending(&crash_reporter::crash_dir()))
+            .to_json();
+
+        dispatch_event(event.get_window(), "crash_report_list_response", &response);
+    });
+
+    // Nothing is sent unless the user consented; declining discards it
+    window.bind_traced("crash_report_submit", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "crash_report_submit", data.as_deref());
+        let response =
+            envelope::respond_with(&request, "CRASH_REPORT", answer_crash_report).to_json();
+
+        dispatch_event(event.get_window(), "crash_report_submit_response", &response);
+    });
+
+    openapi::register(
+        HandlerSpec::new("get_error_stats", "Get error counts by severity")
+            .tag("errors")
+            .event("error_stats_response"),
+    );
+    openapi::register(
+        HandlerSpec::new("get_recent_errors", "Get recent errors from `get_recent_errors:limit`")
+            .tag("errors")
+            .request::<String>()
+            .event("recent_errors_response"),
+    );
+    openapi::register(
+        HandlerSpec::new("clear_error_history", "Clear tracked error history")
+            .tag("errors")
+            .event("error_history_cleared"),
+    );
+    openapi::register(
+        HandlerSpec::new("error_codes", "List every error code with its category and description")
+            .tag("errors")
+            .response::<Vec<ErrorCodeInfo>>()
+            .event("error_codes_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "error_stats",
+            "Most frequent error codes per handler over the last `window_minutes`",
+        )
+        .tag("errors")
+        .request::<ErrorStatsQuery>()
+        .response::<ErrorStatsReport>()
+        .event("error_stats_report"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "crash_report_list",
+            "List crash reports from earlier runs that await the user's decision",
+        )
+        .tag("errors")
+        .response::<Vec<CrashReportSummary>>()
+        .event("crash_report_list_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "crash_report_submit",
+            "Send a crash report to `[logging] crash_report_url` with the user's consent, or discard it",
+        )
+        .tag("errors")
+        .request::<CrashReportSubmitRequest>()
+        .response::<CrashReportSubmitResult>()
+        .event("crash_report_submit_response"),
+    );
+
     info!("Error handlers set up successfully");
 }
 
 /// Setup database pool monitoring handlers
 pub fn setup_db_monitoring_handlers(window: &mut webui::Window) {
-    window.bind("get_db_pool_stats", |_event| {
+    window.bind_traced("get_db_pool_stats", |event| {
         info!("get_db_pool_stats called from frontend");
+        let request = RpcRequest::new(event.event_number, "get_db_pool_stats", None);
         
         let Some(db) = get_db() else {
-            let response = serde_json::json!({
-                "error": "Database not initialized"
-            });
-            let js = format!(
-                "window.dispatchEvent(new CustomEvent('db_pool_stats_response', {{ detail: {} }}))",
-                response
-            );
-            webui::Window::from_id(_event.get_window().id).run_js(&js);
+            let response = request
+                .err(ErrorValue::new(ErrorCode::InternalError, "Database not initialized"))
+                .to_json();
+            dispatch_event(event.get_window(), "db_pool_stats_response", &response);
             return;
         };
         
         let stats = db.pool_stats();
-        let response = serde_json::json!({
-            "connections": stats.connections,
-            "idle_connections": stats.idle_connections,
-            "utilization": stats.utilization(),
-        });
-        
-        let js = format!(
-            "window.dispatchEvent(new CustomEvent('db_pool_stats_response', {{ detail: {} }}))",
-            response
-        );
-        webui::Window::from_id(_event.get_window().id).run_js(&js);
+        let response = request
+            .ok(serde_json::json!({
+                "connections": stats.connections,
+                "idle_connections": stats.idle_connections,
+                "utilization": stats.utilization(),
+            }))
+            .to_json();
+        
+        dispatch_event(event.get_window(), "db_pool_stats_response", &response);
     });
     
+    openapi::register(
+        HandlerSpec::new("get_db_pool_stats", "Get database connection pool statistics")
+            .tag("database")
+            .event("db_pool_stats_response"),
+    );
+
     info!("Database monitoring handlers set up");
 }
 
 /// Setup devtools backend handlers
 pub fn setup_devtools_handlers(window: &mut webui::Window) {
     // Get backend statistics
-    window.bind("get_backend_stats", |_event| {
+    window.bind_traced("get_backend_stats", |event| {
         info!("get_backend_stats called from frontend");
+        let request = RpcRequest::new(event.event_number, "get_backend_stats", None);
         
         // Calculate uptime from application start
         // Note: This is a simplified version - in production you'd track start time
-        let response = serde_json::json!({
-            "uptime": 0, // Would need a global start time tracker
-        });
-        
-        let js = format!(
-            "window.dispatchEvent(new CustomEvent('backend_stats_response', {{ detail: {} }}))",
-            response
-        );
-        webui::Window::from_id(_event.get_window().id).run_js(&js);
+        let response = request
+            .ok(serde_json::json!({
+                "uptime": 0, // Would need a global start time tracker
+            }))
+            .to_json();
+        
+        dispatch_event(event.get_window(), "backend_stats_response", &response);
     });
 
     // Get backend logs
-    window.bind("get_backend_logs", |event| {
+    window.bind_traced("get_backend_logs", |event| {
         info!("get_backend_logs called from frontend");
+        let request = RpcRequest::new(event.event_number, "get_backend_logs", None);
         
         let element_name = unsafe {
             std::ffi::CStr::from_ptr(event.element)
@@ -187,21 +334,20 @@ pub fn setup_devtools_handlers(window: &mut webui::Window) {
             })
         }).collect();
         
-        let response = serde_json::json!({
-            "logs": logs,
-            "count": logs.len(),
-        });
+        let response = request
+            .ok(serde_json::json!({
+                "logs": logs,
+                "count": logs.len(),
+            }))
+            .to_json();
         
-        let js = format!(
-            "window.dispatchEvent(new CustomEvent('backend_logs_response', {{ detail: {} }}))",
-            response
-        );
-        webui::Window::from_id(event.get_window().id).run_js(&js);
+        dispatch_event(event.get_window(), "backend_logs_response", &response);
     });
 
     // Create test backend error
-    window.bind("create_backend_error", |_event| {
+    window.bind_traced("create_backend_error", |event| {
         info!("create_backend_error called from frontend - generating test error");
+        let request = RpcRequest::new(event.event_number, "create_backend_error", None);
         
         let test_error = error_handler::ErrorEntry::new(
             error_handler::ErrorSeverity::Warning,
@@ -213,17 +359,48 @@ pub fn setup_devtools_handlers(window: &mut webui::Window) {
         
         error_handler::get_error_tracker().record(test_error);
         
-        let response = serde_json::json!({
-            "success": true,
-            "message": "Test error created",
-        });
+        let response = request
+            .ok(serde_json::json!({ "message": "Test error created" }))
+            .to_json();
         
-        let js = format!(
-            "window.dispatchEvent(new CustomEvent('backend_test_error', {{ detail: {} }}))",
-            response
-        );
-        webui::Window::from_id(_event.get_window().id).run_js(&js);
+        dispatch_event(event.get_window(), "backend_test_error", &response);
     });
     
+    // List DI registrations to debug "service not found" errors
+    window.bind_traced("di_services", |event| {
+        info!("di_services called from frontend");
+        let request = RpcRequest::new(event.event_number, "di_services", None);
+        let response = envelope::respond(&request, "DI", || di::get_container().services_info())
+            .to_json();
+
+        dispatch_event(event.get_window(), "di_services_response", &response);
+    });
+
+    openapi::register(
+        HandlerSpec::new("get_backend_stats", "Get backend runtime statistics")
+            .tag("devtools")
+            .event("backend_stats_response"),
+    );
+    openapi::register(
+        HandlerSpec::new("get_backend_logs", "Get recent backend log entries from `get_backend_logs:limit`")
+            .tag("devtools")
+            .request::<String>()
+            .event("backend_logs_response"),
+    );
+    openapi::register(
+        HandlerSpec::new("create_backend_error", "Record a test error in the error tracker")
+            .tag("devtools")
+            .event("backend_test_error"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "di_services",
+            "List DI container registrations with their lifetime and whether they were resolved",
+        )
+        .tag("devtools")
+        .response::<Vec<di::ServiceInfo>>()
+        .event("di_services_response"),
+    );
+
     info!("DevTools backend handlers set up");
 }
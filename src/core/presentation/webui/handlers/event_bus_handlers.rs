@@ -1,46 +1,63 @@
-use crate::core::infrastructure::event_bus::{EventData, GLOBAL_EVENT_BUS};
+use crate::core::error::{ErrorCode, ErrorValue};
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::event_bridge::{BusSubscription, GLOBAL_CLIENT_SUBSCRIPTIONS};
+use crate::core::infrastructure::event_bus::{EventBusStats, EventData, GLOBAL_EVENT_BUS};
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::outbound_queue::{self, PushPriority};
+use crate::core::infrastructure::payload_limits;
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use crate::core::rpc::{RpcRequest, RpcResponse};
 use log::info;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::ffi::CStr;
 use webui_rs::webui::bindgen::webui_interface_get_string_at;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct EventPublishRequest {
     pub event_type: String,
     pub data: serde_json::Value,
     pub source: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct EventHistoryRequest {
     pub event_type: Option<String>,
     pub limit: Option<usize>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct EventHistoryResponse {
     pub events: Vec<serde_json::Value>,
     pub count: usize,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct EventSubscribeRequest {
+    pub topic: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct EventUnsubscribeRequest {
+    pub id: u64,
+}
+
 fn read_event_payload(event: &webui_rs::webui::Event) -> Option<String> {
     let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
     if ptr.is_null() {
         return None;
     }
-    Some(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+    bridge_signing::unwrap_payload(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
 }
 
-fn send_response(window: webui_rs::webui::Window, response: &str) {
-    let js = format!(
-        "window.dispatchEvent(new CustomEvent('event_response', {{ detail: {} }}))",
-        response
-    );
-    let _ = window.run_js(&js);
+fn send_response(window: webui_rs::webui::Window, response: &RpcResponse) {
+    let detail = codec::shared().encode(window.id, "event_response", &response.to_json());
+    outbound_queue::push_event(window.id, "event_response", detail, PushPriority::Normal);
 }
 
 pub fn setup_event_bus_handlers(window: &mut webui_rs::webui::Window) {
-    window.bind("event:publish", move |event| {
+    window.bind_traced("event:publish", move |event| {
         let data = match read_event_payload(&event) {
             Some(payload) => payload,
             None => {
@@ -48,37 +65,53 @@ pub fn setup_event_bus_handlers(window: &mut webui_rs::webui::Window) {
                 return;
             }
         };
+        let window = webui_rs::webui::Window::from_id(event.window);
+        let request = RpcRequest::new(event.event_number, "event:publish", Some(&data));
+        if let Err(error) = payload_limits::check_request("event:publish", &data) {
+            send_response(window, &request.err(error));
+            return;
+        }
 
         log::info!("[Communication] Frontend → Backend (event:publish): JSON payload received");
 
-        match serde_json::from_str::<EventPublishRequest>(&data) {
+        match request.parse::<EventPublishRequest>() {
             Ok(req) => {
                 let frontend_event = EventData::new(req.event_type.clone(), req.data)
                     .with_source(req.source.unwrap_or_else(|| "frontend".to_string()));
 
                 GLOBAL_EVENT_BUS.emit_with_source(
                     &frontend_event.event_type,
-                    frontend_event.payload,
+                    frontend_event.payload.clone(),
                     frontend_event.source.as_deref().unwrap_or("frontend"),
                 );
 
-                let response = serde_json::json!({
-                    "success": true,
-                    "event_type": req.event_type,
-                });
+                // Fan the event out to every connected client, including other windows
+                outbound_queue::broadcast_event(
+                    "bus_event",
+                    &serde_json::json!({
+                        "event_type": req.event_type,
+                        "data": frontend_event.payload,
+                        "source": frontend_event.source,
+                        "origin_window": event.window,
+                        "timestamp": frontend_event.timestamp,
+                    }),
+                    PushPriority::Normal,
+                );
 
-                if let Ok(json) = serde_json::to_string(&response) {
-                    log::info!("[Communication] Backend → Frontend: JSON response sent");
-                    send_response(webui_rs::webui::Window::from_id(event.window), &json);
-                }
+                log::info!("[Communication] Backend → Frontend: JSON response sent");
+                send_response(
+                    window,
+                    &request.ok(serde_json::json!({ "event_type": req.event_type })),
+                );
             }
             Err(e) => {
                 log::error!("Failed to parse event publish request: {}", e);
+                send_response(window, &request.err(e));
             }
         }
     });
 
-    window.bind("event:history", move |event| {
+    window.bind_traced("event:history", move |event| {
         let data = match read_event_payload(&event) {
             Some(payload) => payload,
             None => {
@@ -87,7 +120,9 @@ pub fn setup_event_bus_handlers(window: &mut webui_rs::webui::Window) {
             }
         };
 
-        let req: EventHistoryRequest = serde_json::from_str(&data).unwrap_or(EventHistoryRequest {
+        let window = webui_rs::webui::Window::from_id(event.window);
+        let request = RpcRequest::new(event.event_number, "event:history", Some(&data));
+        let req: EventHistoryRequest = request.parse().unwrap_or(EventHistoryRequest {
             event_type: None,
             limit: Some(50),
         });
@@ -96,6 +131,7 @@ pub fn setup_event_bus_handlers(window: &mut webui_rs::webui::Window) {
             Ok(h) => h,
             Err(e) => {
                 log::error!("Failed to get event history: {}", e);
+                send_response(window, &request.err(e.to_value().clone()));
                 return;
             }
         };
@@ -117,24 +153,135 @@ pub fn setup_event_bus_handlers(window: &mut webui_rs::webui::Window) {
             count: history.len(),
         };
 
-        if let Ok(json) = serde_json::to_string(&response) {
-            send_response(webui_rs::webui::Window::from_id(event.window), &json);
-        }
+        send_response(window, &request.ok(response));
     });
 
-    window.bind("event:stats", move |event| {
+    window.bind_traced("event:stats", move |event| {
+        let request = RpcRequest::new(event.event_number, "event:stats", None);
         let stats = GLOBAL_EVENT_BUS.get_stats();
 
-        if let Ok(json) = serde_json::to_string(&stats) {
-            send_response(webui_rs::webui::Window::from_id(event.window), &json);
-        }
+        send_response(
+            webui_rs::webui::Window::from_id(event.window),
+            &request.ok(stats),
+        );
+    });
+
+    window.bind_traced("event_bus_stats", move |event| {
+        let request = RpcRequest::new(event.event_number, "event_bus_stats", None);
+        let detail = codec::shared().encode(
+            event.window,
+            "event_bus_stats_response",
+            &request.ok(GLOBAL_EVENT_BUS.get_stats()).to_json(),
+        );
+        outbound_queue::push_event(
+            event.window,
+            "event_bus_stats_response",
+            detail,
+            PushPriority::Telemetry,
+        );
+    });
+
+    window.bind_traced("event_subscribe", move |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "event_subscribe", data.as_deref());
+        let response = match request.parse::<EventSubscribeRequest>() {
+            Ok(req) if req.topic.is_empty() => request.err(
+                ErrorValue::new(ErrorCode::MissingRequiredField, "Topic is required")
+                    .with_field("topic"),
+            ),
+            Ok(req) => request.ok(GLOBAL_CLIENT_SUBSCRIPTIONS.subscribe(
+                &GLOBAL_EVENT_BUS,
+                event.window,
+                &req.topic,
+            )),
+            Err(e) => request.err(e),
+        };
+        outbound_queue::push_event(
+            event.window,
+            "event_subscribe_response",
+            codec::shared().encode(event.window, "event_subscribe_response", &response.to_json()),
+            PushPriority::Normal,
+        );
+    });
+
+    window.bind_traced("event_unsubscribe", move |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "event_unsubscribe", data.as_deref());
+        let response = match request.parse::<EventUnsubscribeRequest>() {
+            Ok(req) => {
+                match GLOBAL_CLIENT_SUBSCRIPTIONS.unsubscribe(&GLOBAL_EVENT_BUS, event.window, req.id) {
+                    Some(subscription) => request.ok(subscription),
+                    None => request.err(
+                        ErrorValue::new(ErrorCode::ResourceNotFound, "No such event subscription")
+                            .with_field("id")
+                            .with_context("id", req.id.to_string()),
+                    ),
+                }
+            }
+            Err(e) => request.err(e),
+        };
+        outbound_queue::push_event(
+            event.window,
+            "event_unsubscribe_response",
+            codec::shared().encode(event.window, "event_unsubscribe_response", &response.to_json()),
+            PushPriority::Normal,
+        );
     });
 
-    window.bind("event:clear_history", move |_event| {
+    window.bind_traced("event:clear_history", move |_event| {
         if let Err(e) = GLOBAL_EVENT_BUS.clear_history() {
             log::error!("Failed to clear event history: {}", e);
         }
     });
 
+    openapi::register(
+        HandlerSpec::new("event:publish", "Publish an event on the backend event bus")
+            .tag("event_bus")
+            .request::<EventPublishRequest>()
+            .event("event_response"),
+    );
+    openapi::register(
+        HandlerSpec::new("event:history", "Get recent event history")
+            .tag("event_bus")
+            .request::<EventHistoryRequest>()
+            .response::<EventHistoryResponse>()
+            .event("event_response"),
+    );
+    openapi::register(
+        HandlerSpec::new("event:stats", "Get event bus statistics")
+            .tag("event_bus")
+            .response::<EventBusStats>()
+            .event("event_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "event_bus_stats",
+            "Per-topic publish counts, subscribers, handler latencies and dropped events",
+        )
+        .tag("event_bus")
+        .response::<EventBusStats>()
+        .event("event_bus_stats_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "event_subscribe",
+            "Receive a backend EventBus topic in this window as bus:<topic> CustomEvents",
+        )
+        .tag("event_bus")
+        .request::<EventSubscribeRequest>()
+        .response::<BusSubscription>()
+        .event("event_subscribe_response"),
+    );
+    openapi::register(
+        HandlerSpec::new("event_unsubscribe", "Cancel a subscription made with event_subscribe")
+            .tag("event_bus")
+            .request::<EventUnsubscribeRequest>()
+            .response::<BusSubscription>()
+            .event("event_unsubscribe_response"),
+    );
+    openapi::register(
+        HandlerSpec::new("event:clear_history", "Clear event history").tag("event_bus"),
+    );
+
     info!("Event bus handlers initialized");
 }
@@ -0,0 +1,113 @@
+// src/core/presentation/webui/handlers/fs_handlers.rs
+// File system handlers for the frontend's file browser, confined to the
+// folders in `[filesystem] allowed_roots`
+
+use crate::core::error::{AppError, ErrorCode, ErrorValue};
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::config::CallConfig;
+use crate::core::infrastructure::di;
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::outbound_queue::{self, PushPriority};
+use crate::core::presentation::webui::envelope;
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use crate::core::rpc::{RpcRequest, RpcResponse};
+use crate::utils::file_ops::{DirEntryInfo, FileUtils, PathSandbox};
+use log::info;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::ffi::CStr;
+use webui_rs::webui;
+use webui_rs::webui::bindgen::webui_interface_get_string_at;
+
+/// Most entries one listing returns; a recursive listing of a large folder
+/// stops here and is marked truncated
+const MAX_ENTRIES: usize = 5000;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FsListRequest {
+    /// Folder to list; a relative path starts at the first allowed folder,
+    /// an empty one is that folder itself
+    #[serde(default)]
+    pub path: String,
+    /// List every folder below it as well
+    #[serde(default)]
+    pub recursive: bool,
+    /// Keep only entries matching, e.g. "*.pdf" or "reports/**/*.csv"
+    pub glob: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FsListing {
+    /// The folder listed, resolved
+    pub path: String,
+    pub entries: Vec<DirEntryInfo>,
+    /// Whether entries past `MAX_ENTRIES` were left out
+    pub truncated: bool,
+    /// Every allowed folder, for the browser's starting points
+    pub roots: Vec<String>,
+}
+
+fn read_event_payload(event: &webui::Event) -> Option<String> {
+    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
+    if ptr.is_null() {
+        return None;
+    }
+    bridge_signing::unwrap_payload(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+}
+
+fn send_response(window: usize, event_name: &str, response: &RpcResponse) {
+    let detail = codec::shared().encode(window, event_name, &response.to_json());
+    outbound_queue::push_event(window, event_name, detail, PushPriority::Critical);
+}
+
+pub fn setup_fs_handlers(window: &mut webui::Window) {
+    window.bind_traced("fs_list", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "fs_list", data.as_deref());
+        let response = envelope::respond_permitted_with(
+            &request,
+            "FS",
+            "files:read",
+            |_, req: FsListRequest| {
+                info!("fs_list {} called from frontend", req.path);
+                let config = di::resolve_scoped::<CallConfig>()?;
+                let sandbox = PathSandbox::new(config.get_allowed_roots());
+                let dir = sandbox.resolve(&req.path)?;
+                // One past the limit tells whether anything was left out
+                let glob = req.glob.as_deref();
+                let mut entries = FileUtils::list_dir(&dir, req.recursive, glob, MAX_ENTRIES + 1)
+                    .map_err(|e| {
+                        AppError::Validation(
+                            ErrorValue::new(ErrorCode::InvalidFieldValue, "Can't list that folder")
+                                .with_cause(e)
+                                .with_field("path")
+                                .with_context("path", dir.display().to_string()),
+                        )
+                    })?;
+                let truncated = entries.len() > MAX_ENTRIES;
+                entries.truncate(MAX_ENTRIES);
+                Ok(FsListing {
+                    path: dir.display().to_string(),
+                    entries,
+                    truncated,
+                    roots: sandbox.roots().iter().map(|r| r.display().to_string()).collect(),
+                })
+            },
+        );
+        send_response(event.window, "fs_list_response", &response);
+    });
+
+    openapi::register(
+        HandlerSpec::new(
+            "fs_list",
+            "Files and folders in an allowed folder, with type, size and modification time",
+        )
+        .tag("fs")
+        .request::<FsListRequest>()
+        .response::<FsListing>()
+        .event("fs_list_response"),
+    );
+
+    info!("File system handlers set up");
+}
@@ -1,9 +1,26 @@
-use log::{debug, error, info, warn};
+use super::sysinfo_handlers::get_system_info;
+use crate::core::error::{AppResult, ErrorCode, ErrorValue};
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::config::{AppConfig, CallConfig};
+use crate::core::infrastructure::di;
+use crate::core::infrastructure::logging::bundle::{self, BundleSummary};
+use crate::core::infrastructure::logging::{self, filters, stream, LogEntry, LogQuery};
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::outbound_queue::{self, PushPriority};
+use crate::core::infrastructure::payload_limits;
+use crate::core::presentation::webui::envelope;
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use crate::core::rpc::{RpcRequest, RpcResponse};
+use log::{debug, error, info, warn, LevelFilter};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::ffi::CStr;
+use std::path::PathBuf;
+use std::str::FromStr;
 use webui_rs::webui::bindgen::webui_interface_get_string_at;
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct FrontendLogEntry {
     pub message: String,
     pub level: String,
@@ -17,16 +34,241 @@ pub struct FrontendLogEntry {
     pub frontend_timestamp: String,
 }
 
+/// An uncaught error or unhandled rejection in the frontend, sent by the
+/// hook `install_error_hook` injects
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct FrontendErrorReport {
+    /// "error" when left out
+    #[serde(default)]
+    pub level: Option<String>,
+    pub message: String,
+    #[serde(default)]
+    pub stack: Option<String>,
+    /// Where it was caught: "window", "promise", "angular", or file:line:col
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct LogStreamRequest {
+    /// Start (true) or stop (false) pushing new records to this window
+    pub follow: bool,
+    /// Filters for the pushed records; `limit` is ignored
+    #[serde(flatten)]
+    pub filter: LogQuery,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct LogStreamStatus {
+    pub following: bool,
+    /// Records after this `seq` are pushed as `logs_stream_event`
+    pub after: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
+pub struct LogExportRequest {
+    /// Write the bundle here instead of asking with a save dialog
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct LogExportResult {
+    /// The save dialog was closed without choosing a file
+    pub cancelled: bool,
+    pub bundle: Option<BundleSummary>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
+pub struct LogLevelRequest {
+    /// "off", "error", "warn", "info", "debug" or "trace"; may be left out
+    /// together with `target` to remove that module's override
+    pub level: Option<String>,
+    /// Module path the level applies to, e.g. "rustwebui_app::core::infrastructure::database";
+    /// without it the default level changes
+    pub target: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TargetLevel {
+    pub target: String,
+    pub level: String,
+}
+
+/// Levels the logger applies right now, which may differ from `[logging]`
+/// after `logging_set_level`
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct LoggingRuntimeConfig {
+    pub level: String,
+    pub targets: Vec<TargetLevel>,
+    pub file: String,
+    /// Kinds of the configured log sinks
+    pub sinks: Vec<String>,
+}
+
+/// Target of the records logged for `frontend_log`, so `logging_set_level`
+/// can quiet them separately
+const FRONTEND_LOG_TARGET: &str = "frontend";
+
+/// Reports `window.onerror` and unhandled rejections to `frontend_log`, and
+/// exposes `window.__frontendLog(level, message, stack, source)` for errors
+/// Angular's ErrorHandler catches. The same message is sent at most once a
+/// second so an error thrown on every change detection doesn't flood the log.
+const FRONTEND_ERROR_HOOK: &str = r#"(function () {
+  if (window.__frontendLog) return;
+  var last = { key: '', at: 0 };
+  function send(level, message, stack, source) {
+    try {
+      if (typeof window.frontend_log !== 'function') return;
+      var text = String(message || 'Unknown error');
+      var now = Date.now();
+      if (last.key === text && now - last.at < 1000) return;
+      last = { key: text, at: now };
+      window.frontend_log(JSON.stringify({
+        level: level || 'error',
+        message: text,
+        stack: stack ? String(stack) : null,
+        source: source || null,
+        url: location.href
+      }));
+    } catch (e) {}
+  }
+  window.__frontendLog = send;
+  window.addEventListener('error', function (event) {
+    var error = event.error;
+    var source = event.filename ? event.filename + ':' + event.lineno + ':' + event.colno : 'window';
+    send('error', (error && error.message) || event.message, error && error.stack, source);
+  });
+  window.addEventListener('unhandledrejection', function (event) {
+    var reason = event.reason;
+    send('error', 'Unhandled promise rejection: ' + ((reason && reason.message) || reason),
+      reason && reason.stack, 'promise');
+  });
+})();"#;
+
+/// Inject the frontend error hook; call on every connect since a reload
+/// drops it
+pub fn install_error_hook(window: usize) {
+    webui_rs::webui::Window::from_id(window).run_js(FRONTEND_ERROR_HOOK);
+}
+
+fn log_frontend_error(report: &FrontendErrorReport) {
+    let level = report
+        .level
+        .as_deref()
+        .and_then(|level| log::Level::from_str(level).ok())
+        .unwrap_or(log::Level::Error);
+    let mut message = format!(
+        "{} ({})",
+        report.message,
+        report.source.as_deref().unwrap_or("window")
+    );
+    if let Some(url) = &report.url {
+        message.push_str(&format!(" at {}", url));
+    }
+    if let Some(stack) = &report.stack {
+        message.push('\n');
+        message.push_str(stack);
+    }
+    log::log!(target: FRONTEND_LOG_TARGET, level, "{}", message);
+}
+
+fn send_response(window: usize, event_name: &str, response: &RpcResponse) {
+    let detail = codec::shared().encode(window, event_name, &response.to_json());
+    outbound_queue::push_event(window, event_name, detail, PushPriority::Critical);
+}
+
 fn read_event_payload(event: &webui_rs::webui::Event) -> Option<String> {
     let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
     if ptr.is_null() {
         return None;
     }
-    Some(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+    bridge_signing::unwrap_payload(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+}
+
+fn runtime_config() -> LoggingRuntimeConfig {
+    let current = filters::current();
+    let file = di::get_container()
+        .resolve_arc::<AppConfig>()
+        .map(|config| config.get_log_file().to_string())
+        .unwrap_or_default();
+    LoggingRuntimeConfig {
+        level: current.default.to_string().to_lowercase(),
+        targets: current
+            .targets
+            .into_iter()
+            .map(|(target, level)| TargetLevel {
+                target,
+                level: level.to_string().to_lowercase(),
+            })
+            .collect(),
+        file,
+        sinks: logging::GLOBAL_LOG_SHIPPER.sink_names(),
+    }
+}
+
+fn set_level(request: &RpcRequest, req: LogLevelRequest) -> RpcResponse {
+    let level = match req.level.as_deref().map(LevelFilter::from_str).transpose() {
+        Ok(level) => level,
+        Err(_) => {
+            return request.err(
+                ErrorValue::new(ErrorCode::InvalidFieldValue, "Unknown log level")
+                    .with_field("level"),
+            )
+        }
+    };
+    match (req.target.as_deref().filter(|t| !t.is_empty()), level) {
+        (Some(target), level) => {
+            filters::set_target(target, level);
+            match level {
+                Some(level) => info!("Log level of {} set to {}", target, level),
+                None => info!("Log level override of {} removed", target),
+            }
+        }
+        (None, Some(level)) => {
+            filters::set_default(level);
+            info!("Default log level set to {}", level);
+        }
+        (None, None) => {
+            return request.err(
+                ErrorValue::new(ErrorCode::MissingRequiredField, "Level is required")
+                    .with_field("level"),
+            )
+        }
+    }
+    request.ok(runtime_config())
+}
+
+/// Ask where to save unless the request says, then write the bundle there
+fn export_logs(req: LogExportRequest) -> AppResult<LogExportResult> {
+    let dest = req.path.map(PathBuf::from).or_else(|| {
+        rfd::FileDialog::new()
+            .set_title("Save support bundle")
+            .set_file_name(bundle::default_file_name())
+            .add_filter("Zip archive", &["zip"])
+            .save_file()
+    });
+    let Some(dest) = dest else {
+        return Ok(LogExportResult {
+            cancelled: true,
+            bundle: None,
+        });
+    };
+    let config = di::resolve_scoped::<CallConfig>()?;
+    let summary = bundle::write_bundle(&dest, &config, get_system_info())?;
+    info!(
+        "Support bundle written to {} ({} bytes)",
+        summary.path, summary.bytes
+    );
+    Ok(LogExportResult {
+        cancelled: false,
+        bundle: Some(summary),
+    })
 }
 
 pub fn setup_logging_handlers(window: &mut webui_rs::webui::Window) {
-    window.bind("log_message", |event| {
+    window.bind_traced("log_message", |event| {
         let data = match read_event_payload(&event) {
             Some(payload) => payload,
             None => {
@@ -34,6 +276,9 @@ pub fn setup_logging_handlers(window: &mut webui_rs::webui::Window) {
                 return;
             }
         };
+        if payload_limits::check_request("log_message", &data).is_err() {
+            return;
+        }
 
         match serde_json::from_str::<FrontendLogEntry>(&data) {
             Ok(entry) => {
@@ -65,9 +310,147 @@ pub fn setup_logging_handlers(window: &mut webui_rs::webui::Window) {
         }
     });
 
-    window.bind("get_backend_logs", |_event| {
+    window.bind_traced("frontend_log", |event| {
+        let Some(data) = read_event_payload(&event) else {
+            error!("frontend_log missing payload");
+            return;
+        };
+        if payload_limits::check_request("frontend_log", &data).is_err() {
+            return;
+        }
+        match serde_json::from_str::<FrontendErrorReport>(&data) {
+            Ok(report) => log_frontend_error(&report),
+            Err(e) => warn!("Failed to parse frontend error report: {}", e),
+        }
+    });
+
+    window.bind_traced("get_backend_logs", |_event| {
         info!("Frontend requested backend logs");
     });
 
+    window.bind_traced("logs_tail", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "logs_tail", data.as_deref());
+        let response = match request.parse::<LogQuery>() {
+            Ok(query) => request.ok(logging::GLOBAL_LOG_BUFFER.tail(&query)),
+            Err(e) => request.err(e),
+        };
+        send_response(event.window, "logs_tail_response", &response);
+    });
+
+    window.bind_traced("logs_stream", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "logs_stream", data.as_deref());
+        let response = match request.parse::<LogStreamRequest>() {
+            Ok(req) if req.follow => {
+                let after = stream::follow(event.window, req.filter);
+                info!("Window {} is following the log", event.window);
+                request.ok(LogStreamStatus {
+                    following: true,
+                    after: Some(after),
+                })
+            }
+            Ok(_) => {
+                if stream::unfollow(event.window) {
+                    info!("Window {} stopped following the log", event.window);
+                }
+                request.ok(LogStreamStatus {
+                    following: false,
+                    after: None,
+                })
+            }
+            Err(e) => request.err(e),
+        };
+        send_response(event.window, "logs_stream_response", &response);
+    });
+
+    window.bind_traced("logs_export", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "logs_export", data.as_deref());
+        let response = envelope::respond_with(&request, "LOGS_EXPORT", export_logs);
+        send_response(event.window, "logs_export_response", &response);
+    });
+
+    window.bind_traced("logging_set_level", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "logging_set_level", data.as_deref());
+        let response = match request.parse::<LogLevelRequest>() {
+            Ok(req) => set_level(&request, req),
+            Err(e) => request.err(e),
+        };
+        send_response(event.window, "logging_set_level_response", &response);
+    });
+
+    window.bind_traced("logging_get_config", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "logging_get_config", data.as_deref());
+        let response = request.ok(runtime_config());
+        send_response(event.window, "logging_get_config_response", &response);
+    });
+
+    openapi::register(
+        HandlerSpec::new("log_message", "Forward a frontend log entry to the backend logger")
+            .tag("logging")
+            .request::<FrontendLogEntry>(),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "frontend_log",
+            "Log an uncaught frontend error with its stack under the `frontend` target",
+        )
+        .tag("logging")
+        .request::<FrontendErrorReport>(),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "logs_tail",
+            "Latest backend log records, filtered by level, module and text",
+        )
+        .tag("logging")
+        .request::<LogQuery>()
+        .response::<Vec<LogEntry>>()
+        .event("logs_tail_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "logs_stream",
+            "Start or stop pushing new log records to this window as logs_stream_event",
+        )
+        .tag("logging")
+        .request::<LogStreamRequest>()
+        .response::<LogStreamStatus>()
+        .event("logs_stream_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "logs_export",
+            "Zip the log files, a redacted config and system info into a support bundle",
+        )
+        .tag("logging")
+        .request::<LogExportRequest>()
+        .response::<LogExportResult>()
+        .event("logs_export_response"),
+    );
+
+    openapi::register(
+        HandlerSpec::new(
+            "logging_set_level",
+            "Change the default log level or one module's level until the next restart",
+        )
+        .tag("logging")
+        .request::<LogLevelRequest>()
+        .response::<LoggingRuntimeConfig>()
+        .event("logging_set_level_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "logging_get_config",
+            "Log levels in effect, the log file and the configured sinks",
+        )
+        .tag("logging")
+        .response::<LoggingRuntimeConfig>()
+        .event("logging_get_config_response"),
+    );
+
     info!("Logging handlers initialized");
 }
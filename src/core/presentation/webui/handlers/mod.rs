@@ -4,4 +4,16 @@ pub mod sysinfo_handlers;
 pub mod logging_handlers;
 pub mod event_bus_handlers;
 pub mod window_state_handler;
-pub mod error_handlers;
\ No newline at end of file
+pub mod error_handlers;
+pub mod serialization_handlers;
+pub mod api_handlers;
+pub mod outbound_handlers;
+pub mod client_handlers;
+pub mod settings_handlers;
+pub mod session_handlers;
+pub mod auth_handlers;
+pub mod vault_handlers;
+pub mod api_key_handlers;
+pub mod audit_handlers;
+pub mod fs_handlers;
+pub mod network_handlers;
@@ -0,0 +1,182 @@
+// src/core/presentation/webui/handlers/network_handlers.rs
+// Connectivity checks for a status view: this machine's addresses and
+// whether the services the app depends on resolve and accept connections
+
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::config::CallConfig;
+use crate::core::infrastructure::di;
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::outbound_queue::{self, PushPriority};
+use crate::core::presentation::webui::envelope;
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use crate::core::rpc::{RpcRequest, RpcResponse};
+use crate::utils::network::NetworkUtils;
+use log::info;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::ffi::CStr;
+use std::time::Duration;
+use webui_rs::webui;
+use webui_rs::webui::bindgen::webui_interface_get_string_at;
+
+/// Most services one call checks; they are checked in parallel
+const MAX_TARGETS: usize = 32;
+const DEFAULT_TIMEOUT_MS: u64 = 3000;
+const MAX_TIMEOUT_MS: u64 = 30_000;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NetworkDiagnosticsRequest {
+    /// "host:port" services to check; `[network] diagnostics_targets` when
+    /// empty
+    #[serde(default)]
+    pub targets: Vec<String>,
+    /// Connect timeout per address, 3000 by default and at most 30000
+    pub timeout_ms: Option<u64>,
+    /// Also ask a public echo service for this machine's internet address
+    #[serde(default)]
+    pub public_ip: bool,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct NetworkDiagnostics {
+    /// Address of the interface outgoing traffic uses
+    pub local_ip: Option<String>,
+    /// Only looked up when asked for; None when that failed
+    pub public_ip: Option<String>,
+    pub targets: Vec<TargetStatus>,
+}
+
+/// Whether one service resolved and accepted a TCP connection
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TargetStatus {
+    /// The "host:port" checked
+    pub target: String,
+    pub addresses: Vec<String>,
+    pub reachable: bool,
+    /// Time to connect, when reachable
+    pub latency_ms: Option<u64>,
+    /// Why resolving or connecting failed
+    pub error: Option<String>,
+}
+
+fn read_event_payload(event: &webui::Event) -> Option<String> {
+    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
+    if ptr.is_null() {
+        return None;
+    }
+    bridge_signing::unwrap_payload(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+}
+
+fn send_response(window: usize, event_name: &str, response: &RpcResponse) {
+    let detail = codec::shared().encode(window, event_name, &response.to_json());
+    outbound_queue::push_event(window, event_name, detail, PushPriority::Critical);
+}
+
+fn check(target: String, host: &str, port: u16, timeout: Duration) -> TargetStatus {
+    let mut status = TargetStatus {
+        target,
+        addresses: Vec::new(),
+        reachable: false,
+        latency_ms: None,
+        error: None,
+    };
+    match NetworkUtils::resolve(host) {
+        Ok(ips) => status.addresses = ips.iter().map(ToString::to_string).collect(),
+        Err(e) => {
+            status.error = Some(e);
+            return status;
+        }
+    }
+    match NetworkUtils::connect_time(host, port, timeout) {
+        Ok(latency) => {
+            status.reachable = true;
+            status.latency_ms = Some(latency.as_millis() as u64);
+        }
+        Err(e) => status.error = Some(e),
+    }
+    status
+}
+
+fn diagnose(req: NetworkDiagnosticsRequest) -> AppResult<NetworkDiagnostics> {
+    let targets = if req.targets.is_empty() {
+        di::resolve_scoped::<CallConfig>()?.get_diagnostics_targets()
+    } else {
+        req.targets
+    };
+    if targets.len() > MAX_TARGETS {
+        return Err(AppError::Validation(
+            ErrorValue::new(ErrorCode::InvalidFieldValue, "Too many targets")
+                .with_field("targets")
+                .with_context("max", MAX_TARGETS.to_string()),
+        ));
+    }
+    let parsed = targets
+        .into_iter()
+        .map(|target| match NetworkUtils::split_host_port(&target) {
+            Ok((host, port)) => Ok((target, host, port)),
+            Err(e) => Err(AppError::Validation(
+                ErrorValue::new(ErrorCode::InvalidFieldValue, "Targets must be host:port")
+                    .with_cause(e)
+                    .with_field("targets")
+                    .with_context("target", target),
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let timeout = Duration::from_millis(
+        req.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS).clamp(1, MAX_TIMEOUT_MS),
+    );
+
+    let targets = std::thread::scope(|scope| {
+        let checks: Vec<_> = parsed
+            .into_iter()
+            .map(|(target, host, port)| scope.spawn(move || check(target, &host, port, timeout)))
+            .collect();
+        checks
+            .into_iter()
+            .map(|check| check.join().expect("diagnostics check panicked"))
+            .collect()
+    });
+    let public_ip = if req.public_ip {
+        NetworkUtils::public_ip().map(|ip| ip.to_string()).ok()
+    } else {
+        None
+    };
+
+    Ok(NetworkDiagnostics {
+        local_ip: NetworkUtils::get_local_ip(),
+        public_ip,
+        targets,
+    })
+}
+
+pub fn setup_network_handlers(window: &mut webui::Window) {
+    window.bind_traced("network_diagnostics", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "network_diagnostics", data.as_deref());
+        let response = envelope::respond_permitted_with(
+            &request,
+            "NETWORK",
+            "network:read",
+            |_, req: NetworkDiagnosticsRequest| {
+                info!("network_diagnostics called from frontend");
+                diagnose(req)
+            },
+        );
+        send_response(event.window, "network_diagnostics_response", &response);
+    });
+
+    openapi::register(
+        HandlerSpec::new(
+            "network_diagnostics",
+            "Local and public address, and whether each backing service accepts connections",
+        )
+        .tag("network")
+        .request::<NetworkDiagnosticsRequest>()
+        .response::<NetworkDiagnostics>()
+        .event("network_diagnostics_response"),
+    );
+
+    info!("Network handlers set up");
+}
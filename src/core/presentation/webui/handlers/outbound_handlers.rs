@@ -0,0 +1,59 @@
+// src/core/presentation/webui/handlers/outbound_handlers.rs
+// Flow control for backend → frontend pushes
+
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::outbound_queue::{self, OutboundStats, PushPriority};
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use crate::core::rpc::RpcRequest;
+use log::info;
+use std::ffi::CStr;
+use webui_rs::webui;
+use webui_rs::webui::bindgen::webui_interface_get_string_at;
+
+fn read_event_payload(event: &webui::Event) -> Option<String> {
+    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
+    if ptr.is_null() {
+        return None;
+    }
+    bridge_signing::unwrap_payload(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+}
+
+pub fn setup_outbound_handlers(window: &mut webui::Window) {
+    // Called by the JS emitted with each pushed event; not logged, it is hot
+    window.bind_traced("outbound_ack", |event| {
+        let count = read_event_payload(&event)
+            .and_then(|payload| payload.trim().parse::<usize>().ok())
+            .unwrap_or(1);
+        outbound_queue::acknowledge(count);
+    });
+
+    window.bind_traced("get_outbound_stats", |event| {
+        info!("get_outbound_stats called from frontend");
+        let request = RpcRequest::new(event.event_number, "get_outbound_stats", None);
+
+        let stats = outbound_queue::GLOBAL_OUTBOUND_QUEUE.stats();
+        let detail = request.ok(stats).to_json();
+        outbound_queue::push_event(
+            event.window,
+            "get_outbound_stats_response",
+            codec::shared().encode(event.window, "get_outbound_stats_response", &detail),
+            PushPriority::Critical,
+        );
+    });
+
+    openapi::register(
+        HandlerSpec::new("outbound_ack", "Acknowledge pushed events to release flow-control credit")
+            .tag("outbound")
+            .request::<String>(),
+    );
+    openapi::register(
+        HandlerSpec::new("get_outbound_stats", "Get outbound queue depth and backpressure counters")
+            .tag("outbound")
+            .response::<OutboundStats>()
+            .event("get_outbound_stats_response"),
+    );
+
+    info!("Outbound handlers set up successfully");
+}
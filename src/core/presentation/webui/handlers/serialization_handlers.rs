@@ -0,0 +1,229 @@
+// src/core/presentation/webui/handlers/serialization_handlers.rs
+// Serialization format negotiation between backend and frontend
+
+use crate::core::error::{ErrorCode, ErrorValue};
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::outbound_queue::{self, PushPriority};
+use crate::core::infrastructure::payload_limits;
+use crate::core::infrastructure::{config::AppConfig, di};
+use crate::core::rpc::RpcRequest;
+use crate::utils::serialization::{
+    self, negotiate_browser_format, CompressionStats, FormatComparison, SerializationFormat,
+    SerializationStats,
+};
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use log::{info, warn};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::ffi::CStr;
+use webui_rs::webui;
+use webui_rs::webui::bindgen::webui_interface_get_string_at;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct NegotiateRequest {
+    /// Formats the frontend bridge is able to decode
+    #[serde(default)]
+    pub formats: Vec<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct NegotiateResponse {
+    pub format: String,
+    pub preferred: String,
+    pub supported: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetFormatRequest {
+    pub format: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SetFormatResponse {
+    pub format: String,
+    pub previous: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SerializationStatsResponse {
+    pub active: String,
+    pub stats: Vec<SerializationStats>,
+    pub comparison: Vec<FormatComparison>,
+    /// Bytes saved by gzip, per response event
+    pub compression: Vec<CompressionStats>,
+}
+
+fn read_event_payload(event: &webui::Event) -> Option<String> {
+    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
+    if ptr.is_null() {
+        return None;
+    }
+    bridge_signing::unwrap_payload(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+}
+
+fn dispatch_event(window: webui::Window, event_name: &str, detail: &serde_json::Value) {
+    let detail = codec::shared().encode(window.id, event_name, detail);
+    outbound_queue::push_event(window.id, event_name, detail, PushPriority::Critical);
+}
+
+fn configured_format() -> SerializationFormat {
+    let config = di::get_container()
+        .resolve::<AppConfig>()
+        .unwrap_or_default();
+
+    SerializationFormat::from_name(config.get_serialization()).unwrap_or_else(|| {
+        warn!(
+            "Unknown serialization format '{}' in config, using JSON",
+            config.get_serialization()
+        );
+        SerializationFormat::Json
+    })
+}
+
+pub fn setup_serialization_handlers(window: &mut webui::Window) {
+    window.bind_traced("negotiate", |event| {
+        info!("negotiate called from frontend");
+
+        let payload = read_event_payload(&event).unwrap_or_default();
+        let request = RpcRequest::new(event.event_number, "negotiate", Some(&payload));
+        if let Err(error) = payload_limits::check_request("negotiate", &payload) {
+            let detail = request.err(error).to_json();
+            dispatch_event(event.get_window(), "negotiate_response", &detail);
+            return;
+        }
+        let negotiation = request
+            .parse::<NegotiateRequest>()
+            .unwrap_or(NegotiateRequest { formats: vec![] });
+
+        let client_formats: Vec<SerializationFormat> = negotiation
+            .formats
+            .iter()
+            .filter_map(|name| SerializationFormat::from_name(name))
+            .collect();
+
+        if client_formats.is_empty() {
+            warn!("negotiate: frontend advertised no known formats, assuming JSON");
+        }
+
+        let preferred = configured_format();
+        // Bincode and protobuf need the Rust type to decode, which the
+        // browser doesn't have
+        let selected = negotiate_browser_format(preferred, &client_formats);
+        // Only this window switches; other windows keep what they negotiated
+        codec::shared().set_window_format(event.window, selected);
+
+        info!(
+            "[Communication] Serialization negotiated: {} (configured: {}, frontend: {:?})",
+            selected, preferred, negotiation.formats
+        );
+
+        let response = NegotiateResponse {
+            format: selected.name().to_string(),
+            preferred: preferred.name().to_string(),
+            supported: SerializationFormat::available_formats()
+                .iter()
+                .filter(|f| f.is_self_describing())
+                .map(|f| f.name().to_string())
+                .collect(),
+        };
+
+        let detail = request.ok(response).to_json();
+        dispatch_event(event.get_window(), "negotiate_response", &detail);
+    });
+
+    window.bind_traced("set_serialization_format", |event| {
+        info!("set_serialization_format called from frontend");
+
+        let payload = read_event_payload(&event).unwrap_or_default();
+        let request = RpcRequest::new(event.event_number, "set_serialization_format", Some(&payload));
+        if let Err(error) = payload_limits::check_request("set_serialization_format", &payload) {
+            outbound_queue::push_event(
+                event.get_window().id,
+                "set_serialization_format_response",
+                request.err(error).to_json(),
+                PushPriority::Critical,
+            );
+            return;
+        }
+        let requested = request
+            .parse::<SetFormatRequest>()
+            .ok()
+            .and_then(|change| SerializationFormat::from_name(&change.format))
+            // Schema-bound formats cannot be decoded generically by the bridge
+            .filter(|format| format.is_self_describing());
+
+        // The confirmation is always JSON so the frontend can read it before
+        // switching its own decoder
+        let window_id = event.get_window().id;
+        let detail = match requested {
+            Some(format) => {
+                let codec = codec::shared();
+                let previous = codec.format_for(window_id);
+                codec.set_window_format(window_id, format);
+                info!(
+                    "[Communication] Serialization switched at runtime: {} -> {}",
+                    previous, format
+                );
+                request
+                    .ok(SetFormatResponse {
+                        format: format.name().to_string(),
+                        previous: previous.name().to_string(),
+                    })
+                    .to_json()
+            }
+            None => {
+                warn!("set_serialization_format: unknown, missing or non-browser format");
+                let error = ErrorValue::new(ErrorCode::InvalidFormat, "Unknown serialization format")
+                    .with_details("Expected one of: json, messagepack, cbor")
+                    .with_field("format");
+                request.err(error).to_json()
+            }
+        };
+        outbound_queue::push_event(
+            window_id,
+            "set_serialization_format_response",
+            detail,
+            PushPriority::Critical,
+        );
+    });
+
+    window.bind_traced("serialization_stats", |event| {
+        info!("serialization_stats called from frontend");
+        let request = RpcRequest::new(event.event_number, "serialization_stats", None);
+
+        let response = SerializationStatsResponse {
+            active: codec::shared().format_for(event.window).name().to_string(),
+            stats: serialization::all_stats(),
+            comparison: serialization::get_format_comparison(),
+            compression: serialization::compression_stats(),
+        };
+
+        let detail = request.ok(response).to_json();
+        dispatch_event(event.get_window(), "serialization_stats_response", &detail);
+    });
+
+    openapi::register(
+        HandlerSpec::new("negotiate", "Negotiate the backend-frontend serialization format")
+            .tag("serialization")
+            .request::<NegotiateRequest>()
+            .response::<NegotiateResponse>()
+            .event("negotiate_response"),
+    );
+    openapi::register(
+        HandlerSpec::new("set_serialization_format", "Switch the response serialization format at runtime")
+            .tag("serialization")
+            .request::<SetFormatRequest>()
+            .response::<SetFormatResponse>()
+            .event("set_serialization_format_response"),
+    );
+    openapi::register(
+        HandlerSpec::new("serialization_stats", "Get measured serialization statistics per format")
+            .tag("serialization")
+            .response::<SerializationStatsResponse>()
+            .event("serialization_stats_response"),
+    );
+
+    info!("Serialization handlers set up successfully");
+}
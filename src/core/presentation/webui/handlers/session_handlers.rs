@@ -0,0 +1,75 @@
+// src/core/presentation/webui/handlers/session_handlers.rs
+// Session handlers: look up and refresh the session whose token the
+// frontend sends as `session_token`. Sessions are opened and ended by the
+// auth handlers.
+
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::di;
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::outbound_queue::{self, PushPriority};
+use crate::core::infrastructure::sessions::{self, SessionInfo, SessionManager, SessionToken};
+use crate::core::presentation::webui::envelope;
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use crate::core::rpc::{RpcRequest, RpcResponse};
+use log::info;
+use std::ffi::CStr;
+use webui_rs::webui;
+use webui_rs::webui::bindgen::webui_interface_get_string_at;
+
+fn read_event_payload(event: &webui::Event) -> Option<String> {
+    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
+    if ptr.is_null() {
+        return None;
+    }
+    bridge_signing::unwrap_payload(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+}
+
+fn send_response(window: usize, event_name: &str, response: &RpcResponse) {
+    let detail = codec::shared().encode(window, event_name, &response.to_json());
+    outbound_queue::push_event(window, event_name, detail, PushPriority::Critical);
+}
+
+pub fn setup_session_handlers(window: &mut webui::Window) {
+    window.bind_traced("session_info", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "session_info", data.as_deref());
+        let response = match envelope::session(&request) {
+            Ok(info) => request.ok(info),
+            Err(e) => request.err(e.to_value().clone()),
+        };
+        send_response(event.window, "session_info_response", &response);
+    });
+
+    window.bind_traced("session_refresh", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "session_refresh", data.as_deref());
+        let response = match request.session_token.as_deref() {
+            Some(token) => envelope::respond(&request, "SESSION", || {
+                di::get_container()
+                    .resolve_arc::<SessionManager>()?
+                    .refresh(token)
+            }),
+            None => request.err(sessions::unauthorized(&request.handler).to_value().clone()),
+        };
+        send_response(event.window, "session_refresh_response", &response);
+    });
+
+    openapi::register(
+        HandlerSpec::new("session_info", "The signed-in user of the calling session")
+            .tag("session")
+            .response::<SessionInfo>()
+            .event("session_info_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "session_refresh",
+            "Swap the session token for one with a fresh expiry; the old token stops working",
+        )
+        .tag("session")
+        .response::<SessionToken>()
+        .event("session_refresh_response"),
+    );
+
+    info!("Session handlers set up");
+}
@@ -0,0 +1,269 @@
+// src/core/presentation/webui/handlers/settings_handlers.rs
+// Settings page backend: read, change and reset AppConfig values at runtime
+
+use crate::core::domain::events::ConfigChanged;
+use crate::core::error::{AppError, ErrorCode, ErrorValue};
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::config::{AppConfig, CallConfig};
+use crate::core::infrastructure::database::{ConfigAuditEntry, Database};
+use crate::core::infrastructure::di;
+use crate::core::infrastructure::error_handler;
+use crate::core::infrastructure::event_bus::GLOBAL_EVENT_BUS;
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::outbound_queue::{self, PushPriority};
+use crate::core::infrastructure::payload_limits;
+use crate::core::infrastructure::secrets;
+use crate::core::infrastructure::settings;
+use crate::core::presentation::webui::envelope;
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use crate::core::rpc::{RpcRequest, RpcResponse};
+use log::info;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::ffi::CStr;
+use std::sync::Arc;
+use webui_rs::webui;
+use webui_rs::webui::bindgen::webui_interface_get_string_at;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SettingsSetRequest {
+    /// Dotted config path, e.g. "logging.level"
+    pub key: String,
+    /// New value; null removes the key so its default applies
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SettingsResetRequest {
+    /// Setting to reset; every setting when omitted
+    pub key: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SettingsHistoryRequest {
+    /// Only changes to this key, e.g. "logging.level"
+    pub key: Option<String>,
+    /// Most recent entries to return; 100 when omitted
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SettingsUpdate {
+    pub config: AppConfig,
+    /// Sections that changed; empty when the value was already set
+    pub changed: Vec<String>,
+    /// Config file the change was written to
+    pub path: String,
+}
+
+fn read_event_payload(event: &webui::Event) -> Option<String> {
+    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
+    if ptr.is_null() {
+        return None;
+    }
+    bridge_signing::unwrap_payload(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+}
+
+/// `config` with the remote access token, session signing key and proxy
+/// password masked; LAN clients can call these handlers too
+fn visible(config: &AppConfig) -> AppConfig {
+    let mut config = config.clone();
+    if let Some(token) = config.remote.as_mut().and_then(|r| r.token.as_mut()) {
+        *token = String::from("********");
+    }
+    let plain =
+        |value: &&mut String| !value.is_empty() && !value.starts_with(secrets::SECRET_PREFIX);
+    if let Some(key) = config
+        .security
+        .as_mut()
+        .and_then(|s| s.session_signing_key.as_mut())
+        .filter(plain)
+    {
+        *key = String::from("********");
+    }
+    if let Some(password) = config
+        .network
+        .as_mut()
+        .and_then(|n| n.proxy_password.as_mut())
+        .filter(plain)
+    {
+        *password = String::from("********");
+    }
+    config
+}
+
+fn send_response(window: usize, event_name: &str, response: &RpcResponse) {
+    let detail = codec::shared().encode(window, event_name, &response.to_json());
+    outbound_queue::push_event(window, event_name, detail, PushPriority::Critical);
+}
+
+/// One error value for the envelope; several violations are listed in `details`
+fn combined_error(errors: &[AppError]) -> ErrorValue {
+    for e in errors {
+        error_handler::record_app_error("SETTINGS", e);
+    }
+    match errors {
+        [error] => error.to_value().clone(),
+        errors => ErrorValue::new(
+            ErrorCode::ValidationFailed,
+            format!("{} invalid settings", errors.len()),
+        )
+        .with_details(
+            errors
+                .iter()
+                .map(|e| e.to_value().message.clone())
+                .collect::<Vec<_>>()
+                .join("; "),
+        ),
+    }
+}
+
+/// Reply with the config now in effect and tell everyone else what changed
+fn settings_response(
+    request: &RpcRequest,
+    result: Result<Option<ConfigChanged>, Vec<AppError>>,
+) -> RpcResponse {
+    let changed = match result {
+        Ok(changed) => changed,
+        Err(errors) => return request.err(combined_error(&errors)),
+    };
+    let config = match di::get_container().resolve_arc::<AppConfig>() {
+        Ok(config) => config,
+        Err(e) => return request.err(e.to_value().clone()),
+    };
+    if let Some(changed) = &changed {
+        info!("Settings changed: {}", changed.sections.join(", "));
+        if let Err(e) = GLOBAL_EVENT_BUS.publish_typed_with_source(changed, "ui") {
+            error_handler::record_app_error("SETTINGS", &e);
+        }
+    }
+    request.ok(SettingsUpdate {
+        config: visible(&config),
+        changed: changed.map(|c| c.sections).unwrap_or_default(),
+        path: settings::settings_path().display().to_string(),
+    })
+}
+
+pub fn setup_settings_handlers(window: &mut webui::Window) {
+    window.bind_traced("settings_get", |event| {
+        info!("settings_get called from frontend");
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "settings_get", data.as_deref());
+        let response = envelope::respond_permitted(&request, "SETTINGS", "settings:read", |_| {
+            let config = di::resolve_scoped::<CallConfig>()?;
+            Ok(visible(&config))
+        });
+        send_response(event.window, "settings_get_response", &response);
+    });
+
+    window.bind_traced("settings_set", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "settings_set", data.as_deref());
+        let payload = data.as_deref().unwrap_or("");
+        if let Err(error) = payload_limits::check_request("settings_set", payload) {
+            send_response(event.window, "settings_set_response", &request.err(error));
+            return;
+        }
+        if let Err(e) = envelope::authorize(&request, "settings:write") {
+            let response = request.err(e.to_value().clone());
+            send_response(event.window, "settings_set_response", &response);
+            return;
+        }
+        let response = match request.parse::<SettingsSetRequest>() {
+            Ok(req) => {
+                info!("settings_set {} called from frontend", req.key);
+                let result = settings::set(
+                    di::get_container(),
+                    &settings::settings_path(),
+                    &req.key,
+                    req.value,
+                );
+                settings_response(&request, result)
+            }
+            Err(e) => request.err(e),
+        };
+        send_response(event.window, "settings_set_response", &response);
+    });
+
+    window.bind_traced("settings_reset", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "settings_reset", data.as_deref());
+        if let Err(e) = envelope::authorize(&request, "settings:write") {
+            let response = request.err(e.to_value().clone());
+            send_response(event.window, "settings_reset_response", &response);
+            return;
+        }
+        let response = match request.parse::<SettingsResetRequest>() {
+            Ok(req) => {
+                info!(
+                    "settings_reset {} called from frontend",
+                    req.key.as_deref().unwrap_or("(all)")
+                );
+                let result = settings::reset(
+                    di::get_container(),
+                    &settings::settings_path(),
+                    req.key.as_deref(),
+                );
+                settings_response(&request, result)
+            }
+            Err(e) => request.err(e),
+        };
+        send_response(event.window, "settings_reset_response", &response);
+    });
+
+    window.bind_traced("settings_history", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "settings_history", data.as_deref());
+        let response = envelope::respond_permitted_with(
+            &request,
+            "SETTINGS",
+            "settings:read",
+            |_, req: SettingsHistoryRequest| {
+                info!("settings_history called from frontend");
+                let db = di::get_container().resolve::<Arc<Database>>()?;
+                db.config_audit(req.key.as_deref(), i64::from(req.limit.unwrap_or(100)))
+            },
+        );
+        send_response(event.window, "settings_history_response", &response);
+    });
+
+    openapi::register(
+        HandlerSpec::new("settings_get", "Get the configuration in effect")
+            .tag("settings")
+            .response::<AppConfig>()
+            .event("settings_get_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "settings_set",
+            "Change a setting, save it to the config file and publish config.changed",
+        )
+        .tag("settings")
+        .request::<SettingsSetRequest>()
+        .response::<SettingsUpdate>()
+        .event("settings_set_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "settings_reset",
+            "Reset one setting, or all of them, to the default and save the config file",
+        )
+        .tag("settings")
+        .request::<SettingsResetRequest>()
+        .response::<SettingsUpdate>()
+        .event("settings_reset_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "settings_history",
+            "Recorded settings changes, newest first, with old and new values and their source",
+        )
+        .tag("settings")
+        .request::<SettingsHistoryRequest>()
+        .response::<Vec<ConfigAuditEntry>>()
+        .event("settings_history_response"),
+    );
+
+    info!("Settings handlers set up");
+}
@@ -1,3 +1,9 @@
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::outbound_queue::{self, PushPriority};
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use crate::core::rpc::RpcRequest;
+use crate::utils::security::SecurityUtils;
 use log::info;
 use std::process::Command;
 use webui_rs::webui;
@@ -165,23 +171,48 @@ fn get_uptime() -> String {
 }
 
 pub fn setup_sysinfo_handlers(window: &mut webui::Window) {
-    window.bind("get_system_info", |event| {
+    window.bind_traced("get_system_info", |event| {
         info!("get_system_info called from frontend");
+        let request = RpcRequest::new(event.event_number, "get_system_info", None);
 
         let sysinfo = get_system_info();
+        let response = request.ok(sysinfo).to_json();
 
-        let response = serde_json::json!({
-            "success": true,
-            "data": sysinfo
-        });
-
-        let js = format!(
-            "window.dispatchEvent(new CustomEvent('sysinfo_response', {{ detail: {} }}))",
-            response
+        outbound_queue::push_event(
+            event.window,
+            "sysinfo_response",
+            codec::shared().encode(event.window, "sysinfo_response", &response),
+            PushPriority::Critical,
         );
+    });
 
-        webui::Window::from_id(event.window).run_js(&js);
+    window.bind_traced("is_elevated", |event| {
+        let request = RpcRequest::new(event.event_number, "is_elevated", None);
+        let response = request.ok(SecurityUtils::check_admin()).to_json();
+
+        outbound_queue::push_event(
+            event.window,
+            "is_elevated_response",
+            codec::shared().encode(event.window, "is_elevated_response", &response),
+            PushPriority::Critical,
+        );
     });
 
+    openapi::register(
+        HandlerSpec::new("get_system_info", "Get OS, memory, CPU, disk and uptime information")
+            .tag("system")
+            .response::<serde_json::Value>()
+            .event("sysinfo_response"),
+    );
+    openapi::register(
+        HandlerSpec::new(
+            "is_elevated",
+            "Whether the app runs as root or with an elevated Windows token",
+        )
+        .tag("system")
+        .response::<bool>()
+        .event("is_elevated_response"),
+    );
+
     info!("System info handlers set up successfully");
 }
\ No newline at end of file
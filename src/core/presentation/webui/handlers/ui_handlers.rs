@@ -1,18 +1,23 @@
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::presentation::webui::traced_bind::TracedBind;
 use log::{debug, info};
 use webui_rs::webui;
 
 pub fn setup_ui_handlers(window: &mut webui::Window) {
-    window.bind("open_folder", |_event| {
+    window.bind_traced("open_folder", |_event| {
         info!("Open folder button clicked!");
     });
 
-    window.bind("organize_images", |_event| {
+    window.bind_traced("organize_images", |_event| {
         info!("Organize images button clicked!");
     });
+
+    openapi::register(HandlerSpec::new("open_folder", "Open folder action").tag("ui"));
+    openapi::register(HandlerSpec::new("organize_images", "Organize images action").tag("ui"));
 }
 
 pub fn setup_counter_handlers(window: &mut webui::Window) {
-    window.bind("increment_counter", |event| {
+    window.bind_traced("increment_counter", |event| {
         let element_name = unsafe {
             std::ffi::CStr::from_ptr(event.element)
                 .to_string_lossy()
@@ -29,7 +34,7 @@ pub fn setup_counter_handlers(window: &mut webui::Window) {
         );
     });
 
-    window.bind("reset_counter", |event| {
+    window.bind_traced("reset_counter", |event| {
         let element_name = unsafe {
             std::ffi::CStr::from_ptr(event.element)
                 .to_string_lossy()
@@ -42,4 +47,7 @@ pub fn setup_counter_handlers(window: &mut webui::Window) {
             element_name, event.window
         );
     });
+
+    openapi::register(HandlerSpec::new("increment_counter", "Increment the demo counter").tag("ui"));
+    openapi::register(HandlerSpec::new("reset_counter", "Reset the demo counter").tag("ui"));
 }
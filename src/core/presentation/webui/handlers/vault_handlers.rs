@@ -0,0 +1,103 @@
+// src/core/presentation/webui/handlers/vault_handlers.rs
+// Encrypted storage for the frontend: values the current session keeps in
+// SQLite under a key instead of in localStorage
+
+use crate::core::error::AppResult;
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::codec;
+use crate::core::infrastructure::database::Database;
+use crate::core::infrastructure::di;
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::outbound_queue::{self, PushPriority};
+use crate::core::infrastructure::payload_limits;
+use crate::core::infrastructure::vault::Vault;
+use crate::core::presentation::webui::envelope;
+use crate::core::presentation::webui::traced_bind::TracedBind;
+use crate::core::rpc::{RpcRequest, RpcResponse};
+use log::info;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::ffi::CStr;
+use std::sync::Arc;
+use webui_rs::webui;
+use webui_rs::webui::bindgen::webui_interface_get_string_at;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VaultSetRequest {
+    pub key: String,
+    /// Any JSON value; null removes the key
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VaultGetRequest {
+    pub key: String,
+}
+
+fn read_event_payload(event: &webui::Event) -> Option<String> {
+    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
+    if ptr.is_null() {
+        return None;
+    }
+    bridge_signing::unwrap_payload(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+}
+
+fn send_response(window: usize, event_name: &str, response: &RpcResponse) {
+    let detail = codec::shared().encode(window, event_name, &response.to_json());
+    outbound_queue::push_event(window, event_name, detail, PushPriority::Critical);
+}
+
+fn services() -> AppResult<(Arc<Vault>, Arc<Database>)> {
+    let container = di::get_container();
+    Ok((container.resolve_arc::<Vault>()?, container.resolve::<Arc<Database>>()?))
+}
+
+pub fn setup_vault_handlers(window: &mut webui::Window) {
+    window.bind_traced("vault_set", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "vault_set", data.as_deref());
+        let payload = data.as_deref().unwrap_or("");
+        if let Err(error) = payload_limits::check_request("vault_set", payload) {
+            send_response(event.window, "vault_set_response", &request.err(error));
+            return;
+        }
+        let response =
+            envelope::respond_with_session(&request, "VAULT", |session, req: VaultSetRequest| {
+                info!("vault_set {} called for user {}", req.key, session.user_id);
+                let (vault, db) = services()?;
+                vault.set(&db, &session, &req.key, &req.value)
+            });
+        send_response(event.window, "vault_set_response", &response);
+    });
+
+    window.bind_traced("vault_get", |event| {
+        let data = read_event_payload(&event);
+        let request = RpcRequest::new(event.event_number, "vault_get", data.as_deref());
+        let response =
+            envelope::respond_with_session(&request, "VAULT", |session, req: VaultGetRequest| {
+                let (vault, db) = services()?;
+                vault.get(&db, &session, &req.key)
+            });
+        send_response(event.window, "vault_get_response", &response);
+    });
+
+    openapi::register(
+        HandlerSpec::new(
+            "vault_set",
+            "Store an encrypted value for the current session under a key; null removes it",
+        )
+        .tag("vault")
+        .request::<VaultSetRequest>()
+        .response::<()>()
+        .event("vault_set_response"),
+    );
+    openapi::register(
+        HandlerSpec::new("vault_get", "The current session's value under a key, null when unset")
+            .tag("vault")
+            .request::<VaultGetRequest>()
+            .response::<serde_json::Value>()
+            .event("vault_get_response"),
+    );
+
+    info!("Vault handlers set up");
+}
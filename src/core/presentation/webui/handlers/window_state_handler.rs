@@ -1,9 +1,14 @@
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::openapi::{self, HandlerSpec};
+use crate::core::infrastructure::payload_limits;
+use crate::core::presentation::webui::traced_bind::TracedBind;
 use log::{debug, error, info};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::ffi::CStr;
 use webui_rs::webui::bindgen::webui_interface_get_string_at;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum WindowState {
     Focused,
@@ -14,7 +19,7 @@ pub enum WindowState {
     Closed,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct WindowStateEvent {
     pub window_id: String,
     pub state: WindowState,
@@ -27,11 +32,11 @@ fn read_event_payload(event: &webui_rs::webui::Event) -> Option<String> {
     if ptr.is_null() {
         return None;
     }
-    Some(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+    bridge_signing::unwrap_payload(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
 }
 
 pub fn setup_window_state_handlers(window: &mut webui_rs::webui::Window) {
-    window.bind("window_state_change", |event| {
+    window.bind_traced("window_state_change", |event| {
         let data = match read_event_payload(&event) {
             Some(payload) => payload,
             None => {
@@ -39,6 +44,9 @@ pub fn setup_window_state_handlers(window: &mut webui_rs::webui::Window) {
                 return;
             }
         };
+        if payload_limits::check_request("window_state_change", &data).is_err() {
+            return;
+        }
 
         match serde_json::from_str::<WindowStateEvent>(&data) {
             Ok(event_data) => {
@@ -64,5 +72,11 @@ pub fn setup_window_state_handlers(window: &mut webui_rs::webui::Window) {
         }
     });
 
+    openapi::register(
+        HandlerSpec::new("window_state_change", "Report a frontend window state change")
+            .tag("window")
+            .request::<WindowStateEvent>(),
+    );
+
     info!("Window state handlers initialized");
 }
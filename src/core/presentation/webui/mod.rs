@@ -1,3 +1,5 @@
+pub mod envelope;
 pub mod handlers;
+pub mod traced_bind;
 
-pub use handlers::*;
\ No newline at end of file
+pub use handlers::*;
@@ -0,0 +1,54 @@
+// src/core/presentation/webui/traced_bind.rs
+// `window.bind_traced(..)`: bind a handler that runs inside a `webui_call`
+// span, so each invocation is logged with its elapsed time, and under a
+// fresh correlation id that tags every log line the call emits. The call's
+// bridge signature is checked here too, for `envelope` to enforce. Each call
+// runs in a DI scope of its own, which `di::resolve_scoped` resolves from.
+
+use std::ffi::CStr;
+use webui_rs::webui;
+use webui_rs::webui::bindgen::webui_interface_get_string_at;
+
+use crate::core::infrastructure::bridge_signing;
+use crate::core::infrastructure::di;
+use crate::core::infrastructure::event_middleware::CorrelationMiddleware;
+use crate::core::infrastructure::logging::context as log_context;
+
+pub trait TracedBind {
+    /// Like `bind`, with the call wrapped in a span carrying the handler
+    /// name, window, event number and correlation id
+    fn bind_traced<F>(&mut self, handler: &'static str, func: F)
+    where
+        F: FnMut(webui::Event) + Send + 'static;
+}
+
+impl TracedBind for webui::Window {
+    fn bind_traced<F>(&mut self, handler: &'static str, mut func: F)
+    where
+        F: FnMut(webui::Event) + Send + 'static,
+    {
+        self.bind(handler, move |event: webui::Event| {
+            // Entered before the span so its closing line is tagged too
+            let correlation_id = CorrelationMiddleware::generate_id();
+            let _correlation = log_context::enter(correlation_id.as_str());
+            let span = tracing::info_span!(
+                "webui_call",
+                handler,
+                window = event.window,
+                event_number = event.event_number,
+                correlation_id = correlation_id.as_str()
+            );
+            let _entered = span.enter();
+            let _signature = bridge_signing::enter(handler, first_arg(&event).as_deref());
+            di::scoped(|_| func(event))
+        });
+    }
+}
+
+fn first_arg(event: &webui::Event) -> Option<String> {
+    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
+    if ptr.is_null() {
+        return None;
+    }
+    Some(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+}
@@ -0,0 +1,297 @@
+// src/core/rpc.rs
+// Canonical request/response envelope shared by all handlers
+//
+// Every bound handler answers with an RpcResponse, serialized on the wire as
+// `{ id?, schema_version, success, data, error }` - the shape the frontend
+// `ApiResponse` type and the generated client expect.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::{AppResult, ErrorCode, ErrorValue};
+use crate::core::infrastructure::error_stats::GLOBAL_ERROR_STATS;
+use crate::core::infrastructure::logging::context as log_context;
+use crate::core::infrastructure::sanitize;
+use crate::core::infrastructure::schema_registry::{
+    CURRENT_SCHEMA_VERSION, GLOBAL_SCHEMA_REGISTRY,
+};
+
+/// A frontend call to a bound handler
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcRequest {
+    /// Per-call id; WebUI's event number for bound calls
+    pub id: String,
+    pub handler: String,
+    /// Schema the sender was built against; 1 when the payload does not say
+    pub schema_version: u32,
+    /// Decoded argument, upgraded to the current schema and sanitized; text
+    /// that is not JSON is kept as a JSON string
+    pub payload: serde_json::Value,
+    /// `session_token` taken out of the payload; checked by
+    /// `envelope::respond_with_session`
+    #[serde(skip)]
+    pub session_token: Option<String>,
+    /// `api_key` taken out of the payload; sent by automation clients in
+    /// place of a session and checked by `envelope::authorize`
+    #[serde(skip)]
+    pub api_key: Option<String>,
+    /// Set when the payload could not be upgraded or sanitized; returned by
+    /// `parse`
+    #[serde(skip)]
+    rejected: Option<ErrorValue>,
+}
+
+impl RpcRequest {
+    pub fn new(id: impl ToString, handler: impl Into<String>, raw: Option<&str>) -> Self {
+        let handler = handler.into();
+        let mut payload = match raw {
+            None => serde_json::Value::Null,
+            Some(raw) => serde_json::from_str(raw)
+                .unwrap_or_else(|_| serde_json::Value::String(raw.to_string())),
+        };
+
+        let schema_version = payload
+            .as_object_mut()
+            .and_then(|object| object.remove("schema_version"))
+            .and_then(|version| version.as_u64())
+            .map_or(1, |version| version as u32);
+        let session_token = payload
+            .as_object_mut()
+            .and_then(|object| object.remove("session_token"))
+            .and_then(|token| token.as_str().map(str::to_string));
+        let api_key = payload
+            .as_object_mut()
+            .and_then(|object| object.remove("api_key"))
+            .and_then(|key| key.as_str().map(str::to_string));
+        let (payload, rejected) = match GLOBAL_SCHEMA_REGISTRY
+            .upgrade(&handler, schema_version, payload.clone())
+            .and_then(|upgraded| sanitize::sanitizer().sanitize(&handler, upgraded))
+        {
+            Ok(clean) => (clean, None),
+            Err(error) => (payload, Some(error)),
+        };
+
+        Self {
+            id: id.to_string(),
+            handler,
+            schema_version,
+            payload,
+            session_token,
+            api_key,
+            rejected,
+        }
+    }
+
+    /// Set the session token of a call whose payload can't carry one, e.g.
+    /// the `create_user:...` element strings
+    pub fn with_session_token(mut self, token: Option<String>) -> Self {
+        if token.is_some() {
+            self.session_token = token;
+        }
+        self
+    }
+
+    /// Deserialize the payload into the handler's request type
+    pub fn parse<T: DeserializeOwned>(&self) -> Result<T, ErrorValue> {
+        if let Some(ref error) = self.rejected {
+            return Err(error.clone());
+        }
+        T::deserialize(&self.payload).map_err(|e| {
+            ErrorValue::new(ErrorCode::DeserializationFailed, "Invalid request payload")
+                .with_cause(e.to_string())
+                .with_context("handler", self.handler.clone())
+        })
+    }
+
+    /// Success response correlated with this request
+    pub fn ok<T: Serialize>(&self, data: T) -> RpcResponse {
+        RpcResponse::ok(data).with_id(self.id.clone())
+    }
+
+    /// Error response correlated with this request, counted in the error
+    /// stats under this handler
+    pub fn err(&self, error: ErrorValue) -> RpcResponse {
+        GLOBAL_ERROR_STATS.record(&self.handler, &error.code);
+        RpcResponse::err(error).with_id(self.id.clone())
+    }
+}
+
+/// Response envelope sent back as the handler's CustomEvent detail
+#[derive(Debug, Clone)]
+pub struct RpcResponse {
+    /// Id of the request this answers; `None` for unsolicited pushes
+    pub id: Option<String>,
+    pub ok: bool,
+    pub data: serde_json::Value,
+    pub error: Option<ErrorValue>,
+}
+
+impl RpcResponse {
+    pub fn ok<T: Serialize>(data: T) -> Self {
+        match serde_json::to_value(data) {
+            Ok(data) => Self {
+                id: None,
+                ok: true,
+                data,
+                error: None,
+            },
+            Err(e) => Self::err(
+                ErrorValue::new(ErrorCode::SerializationFailed, "Failed to serialize response")
+                    .with_cause(e.to_string()),
+            ),
+        }
+    }
+
+    /// Failure response; the error is tagged with the correlation id of the
+    /// call being serviced unless it already has one
+    pub fn err(mut error: ErrorValue) -> Self {
+        if error.correlation_id.is_none() {
+            error.correlation_id = log_context::current();
+        }
+        Self {
+            id: None,
+            ok: false,
+            data: serde_json::Value::Null,
+            error: Some(error),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn from_result<T: Serialize>(result: AppResult<T>) -> Self {
+        match result {
+            Ok(data) => Self::ok(data),
+            Err(e) => Self::err(e.to_value().clone()),
+        }
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Wire representation: `{ id?, schema_version, success, data, error }`
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut map = serde_json::Map::new();
+        if let Some(ref id) = self.id {
+            map.insert("id".to_string(), serde_json::json!(id));
+        }
+        map.insert(
+            "schema_version".to_string(),
+            serde_json::json!(CURRENT_SCHEMA_VERSION),
+        );
+        map.insert("success".to_string(), serde_json::json!(self.ok));
+        map.insert("data".to_string(), self.data.clone());
+        map.insert(
+            "error".to_string(),
+            self.error
+                .as_ref()
+                .map(ErrorValue::to_response)
+                .unwrap_or(serde_json::Value::Null),
+        );
+        serde_json::Value::Object(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::error::AppError;
+
+    #[derive(Debug, Deserialize)]
+    struct Ping {
+        count: u32,
+    }
+
+    #[test]
+    fn test_request_payload() {
+        let request = RpcRequest::new(7, "ping", Some(r#"{"count": 3}"#));
+        assert_eq!(request.parse::<Ping>().unwrap().count, 3);
+
+        let text = RpcRequest::new(8, "log", Some("plain text"));
+        assert_eq!(text.payload, "plain text");
+
+        let error = text.parse::<Ping>().unwrap_err();
+        assert_eq!(error.code, ErrorCode::DeserializationFailed);
+
+        let signed_in = RpcRequest::new(9, "ping", Some(r#"{"count": 4, "session_token": "t"}"#));
+        assert_eq!(signed_in.session_token.as_deref(), Some("t"));
+        assert!(signed_in.payload.get("session_token").is_none());
+        assert_eq!(signed_in.parse::<Ping>().unwrap().count, 4);
+
+        let keyed = RpcRequest::new(10, "ping", Some(r#"{"count": 5, "api_key": "rwk_k"}"#));
+        assert_eq!(keyed.api_key.as_deref(), Some("rwk_k"));
+        assert!(keyed.payload.get("api_key").is_none());
+    }
+
+    #[test]
+    fn test_request_payload_is_sanitized() {
+        let request = RpcRequest::new(10, "vault_set", Some(r#"{"key": "a\u0000b"}"#));
+        assert_eq!(request.payload["key"], "ab");
+
+        let long_name = format!(r#"{{"name": "{}"}}"#, "x".repeat(101));
+        let error = RpcRequest::new(11, "create_user", Some(&long_name))
+            .parse::<Ping>()
+            .unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("name"));
+    }
+
+    #[test]
+    fn test_request_schema_version() {
+        let current = RpcRequest::new(1, "ping", Some(r#"{"count": 1}"#));
+        assert_eq!(current.schema_version, 1);
+
+        let versioned = RpcRequest::new(2, "ping", Some(r#"{"count": 2, "schema_version": 1}"#));
+        assert_eq!(versioned.schema_version, 1);
+        assert!(versioned.payload.get("schema_version").is_none());
+        assert_eq!(versioned.parse::<Ping>().unwrap().count, 2);
+
+        let newer = format!(r#"{{"count": 3, "schema_version": {}}}"#, CURRENT_SCHEMA_VERSION + 1);
+        let error = RpcRequest::new(3, "ping", Some(&newer)).parse::<Ping>().unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("schema_version"));
+    }
+
+    #[test]
+    fn test_response_envelope() {
+        let request = RpcRequest::new(1, "get_users", None);
+        let ok = request.ok(vec![1, 2]).to_json();
+        assert_eq!(
+            ok,
+            serde_json::json!({
+                "id": "1",
+                "schema_version": CURRENT_SCHEMA_VERSION,
+                "success": true,
+                "data": [1, 2],
+                "error": null
+            })
+        );
+
+        let failed = RpcResponse::from_result::<()>(Err(AppError::Validation(
+            ErrorValue::new(ErrorCode::MissingRequiredField, "name is required").with_field("name"),
+        )))
+        .to_json();
+        assert_eq!(failed["success"], false);
+        assert!(failed["data"].is_null());
+        assert!(failed.get("id").is_none());
+        assert_eq!(failed["error"]["code"], "MISSING_REQUIRED_FIELD");
+        assert_eq!(failed["error"]["field"], "name");
+        assert!(failed["error"].get("correlation_id").is_none());
+    }
+
+    #[test]
+    fn test_error_carries_correlation_id() {
+        let request = RpcRequest::new(4, "settings_get", None);
+        let failed = {
+            let _call = log_context::enter("5f0c9a1e2b3d4c6f");
+            request.err(ErrorValue::new(ErrorCode::InternalError, "boom"))
+        };
+        assert_eq!(
+            failed.to_json()["error"]["correlation_id"],
+            "5f0c9a1e2b3d4c6f"
+        );
+
+        let kept = RpcResponse::err(
+            ErrorValue::new(ErrorCode::InternalError, "boom").with_correlation_id("a1"),
+        );
+        assert_eq!(kept.to_json()["error"]["correlation_id"], "a1");
+    }
+}
@@ -1,49 +1,99 @@
+use clap::Parser;
 use log::{error, info, warn};
 use std::sync::Arc;
-use std::net::TcpListener;
 use std::fs;
 use std::path::PathBuf;
 use webui_rs::webui;
-use webui_rs::webui::bindgen::webui_set_port;
+use webui_rs::webui::bindgen::{webui_config_multi_client, webui_maximize, webui_set_center, webui_set_config, webui_set_hide, webui_set_minimum_size, webui_set_port, webui_set_position, webui_set_resizable, webui_set_size};
 
 // MVVM: Core - Domain, Application, Infrastructure, Presentation
 mod core;
 use core::{
-    infrastructure::{config::AppConfig, database::Database, logging, di, error_handler},
-    error::ErrorCode,
+    domain::{events::ConfigChanged, traits::EventTopic},
+    infrastructure::{bridge_signing, client_registry, config::{self, AppConfig}, config_template, config_watcher, crash_reporter, database::Database, di, error_handler, event_bridge, event_bus, event_middleware, http_client, logging, openapi, outbound_queue, payload_limits, remote_access, sanitize, secrets, security_audit, service_modules, sessions::SessionManager, vault},
     presentation,
+    rpc::RpcResponse,
 };
 
 // Shared utilities
 mod utils;
+use utils::file_ops::{FileUtils, TempFileGuard};
+use utils::network::NetworkUtils;
+use utils::security::DevCertificates;
 
 include!(concat!(env!("OUT_DIR"), "/embedded_frontend.rs"));
 
 mod utils_demo;
 use utils_demo::run_utilities_demo;
 
+/// Command-line options; they override both the config file and the
+/// `RUSTWEBUI__` environment variables
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Cli {
+    /// SQLite database file
+    #[arg(long, value_name = "PATH")]
+    db_path: Option<String>,
+
+    /// Log level
+    #[arg(long, value_parser = config::LOG_LEVELS)]
+    log_level: Option<String>,
+
+    /// Backend-frontend transport
+    #[arg(long, value_parser = config::TRANSPORTS)]
+    transport: Option<String>,
+
+    /// Keep the window hidden, e.g. to serve LAN clients through remote access
+    #[arg(long)]
+    headless: bool,
+
+    /// WebUI server port instead of a random free one
+    #[arg(long)]
+    port: Option<u16>,
+
+    /// Make the development TLS certificates if missing, print how to trust
+    /// them, and exit
+    #[arg(long)]
+    dev_certs: bool,
+}
+
+impl Cli {
+    /// Config keys the options override
+    fn config_overrides(&self) -> Vec<(String, toml::Value)> {
+        [
+            ("database.path", &self.db_path),
+            ("logging.level", &self.log_level),
+            ("communication.transport", &self.transport),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| Some((key.to_string(), toml::Value::from(value.clone()?))))
+        .collect()
+    }
+}
+
 #[allow(unused_variables)]
 fn main() {
+    let cli = Cli::parse();
+
     // Initialize enhanced error handling with panic hook
     error_handler::init_error_handling();
 
-    // Initialize dependency injection container
-    if let Err(e) = di::init_container() {
-        eprintln!("Failed to initialize DI container: {}", e);
-        error_handler::record_error(
-            error_handler::ErrorSeverity::Critical,
-            "MAIN",
-            ErrorCode::InternalError,
-            format!("Failed to initialize DI container: {}", e),
-            None,
-        );
-        return;
+    // Load application configuration, command-line options taking precedence
+    config::set_arg_overrides(cli.config_overrides());
+
+    // First run: leave a documented config file where the next run finds it
+    if AppConfig::config_path().is_none() {
+        if let Some(template_path) = AppConfig::executable_config_path() {
+            match config_template::write_default(&template_path) {
+                Ok(()) => println!("No config file found; wrote the defaults to {}", template_path.display()),
+                Err(e) => {
+                    eprintln!("No config file found; running on defaults: {}", e);
+                    error_handler::record_app_error("CONFIG", &e);
+                }
+            }
+        }
     }
-    info!("Dependency injection container initialized");
-
-    let container = di::get_container();
 
-    // Load application configuration
     let config = match AppConfig::load() {
         Ok(config) => {
             println!("Configuration loaded successfully!");
@@ -54,27 +104,67 @@ fn main() {
             );
             config
         }
-        Err(e) => {
-            eprintln!("Failed to load configuration: {}", e);
-            eprintln!("Using default configuration");
-            AppConfig::default()
+        Err(errors) => {
+            eprintln!("Invalid configuration:");
+            for e in &errors {
+                eprintln!("  {}", e);
+                error_handler::record_app_error("CONFIG", e);
+            }
+            error_handler::print_error_summary();
+            return;
         }
     };
-
-    // Register configuration in the container
-    if let Err(e) = container.register_singleton(config.clone()) {
-        eprintln!("Failed to register config in DI container: {}", e);
-        return;
+    crash_reporter::configure(&config);
+
+    if cli.dev_certs {
+        let Some(dir) = config.get_dev_cert_dir() else {
+            eprintln!("No data directory for the development certificates; set [dev] cert_dir");
+            std::process::exit(1);
+        };
+        match DevCertificates::ensure(&dir, &config.get_dev_cert_hosts()) {
+            Ok(certs) => {
+                let state = if certs.created { "Created" } else { "Using existing" };
+                println!("{} development certificates in {}", state, dir.display());
+                println!("{}", certs.trust_instructions());
+                return;
+            }
+            Err(e) => {
+                eprintln!("Failed to make development certificates: {}", e);
+                std::process::exit(1);
+            }
+        }
     }
 
-    // Initialize logging system with config settings
-    if let Err(e) = logging::init_logging_with_config(
-        Some(config.get_log_file()),
-        config.get_log_level(),
-        config.is_append_log(),
-    ) {
-        eprintln!("Failed to initialize logger: {}", e);
-        return;
+    // Register each subsystem's services; later modules resolve the config
+    let container = match di::ContainerBuilder::new(di::get_container())
+        .module(service_modules::ConfigModule::new(config.clone()))
+        .module(service_modules::LoggingModule)
+        .module(service_modules::SecretsModule)
+        .module(service_modules::SessionModule)
+        .module(service_modules::VaultModule)
+        .module(service_modules::TransportModule)
+        .module(service_modules::NetworkModule)
+        .module(service_modules::DatabaseModule)
+        .build()
+    {
+        Ok(container) => container,
+        Err(e) => {
+            error_handler::record_app_error("MAIN", &e);
+            eprintln!("Failed to initialize services: {}", e);
+            crash_reporter::record_fatal("MAIN", &e);
+            return;
+        }
+    };
+    info!("Dependency injection container initialized");
+
+    // Reports from earlier runs wait for the user to submit or discard them
+    let pending_crashes = crash_reporter::pending(&crash_reporter::crash_dir());
+    if !pending_crashes.is_empty() {
+        warn!(
+            "{} crash report(s) from earlier runs in {}",
+            pending_crashes.len(),
+            crash_reporter::crash_dir().display()
+        );
     }
 
     info!("=============================================");
@@ -155,50 +245,15 @@ fn main() {
 
     info!("Application starting...");
 
-    // Get database path from config
-    let db_path = config.get_db_path();
-    info!("Database path: {}", db_path);
-
-    // Initialize SQLite database with connection pooling
-    let db = match Database::new(db_path) {
-        Ok(db) => {
-            info!("Database connection pool initialized successfully");
-            if let Err(e) = db.init() {
-                error_handler::record_error(
-                    error_handler::ErrorSeverity::Critical,
-                    "MAIN",
-                    ErrorCode::DbQueryFailed,
-                    format!("Failed to initialize database schema: {}", e),
-                    None,
-                );
-                return;
-            }
-            if config.should_create_sample_data() {
-                if let Err(e) = db.insert_sample_data() {
-                    error_handler::record_app_error("MAIN", &e);
-                    return;
-                }
-                info!("Sample data created (if not exists)");
-            }
-            // Log pool stats
-            let stats = db.pool_stats();
-            info!("Database pool stats: connections={}, idle={}", 
-                  stats.connections, stats.idle_connections);
-            Arc::new(db)
-        }
+    let db = match container.resolve::<Arc<Database>>() {
+        Ok(db) => db,
         Err(e) => {
             error_handler::record_app_error("MAIN", &e);
-            eprintln!("Failed to initialize database: {}", e);
+            crash_reporter::record_fatal("MAIN", &e);
             return;
         }
     };
 
-    // Register database in the container
-    if let Err(e) = container.register_singleton(Arc::clone(&db)) {
-        eprintln!("Failed to register database in DI container: {}", e);
-        return;
-    }
-
     // Initialize database handlers with the database instance
     presentation::db_handlers::init_database(Arc::clone(&db));
     presentation::error_handlers::init_database_monitoring(Arc::clone(&db));
@@ -209,15 +264,11 @@ fn main() {
     // Create a new window
     let mut my_window = webui::Window::new();
 
-    // Randomize WebUI server port
-    let port = TcpListener::bind("127.0.0.1:0")
-        .ok()
-        .and_then(|listener| listener.local_addr().ok())
-        .map(|addr| addr.port());
-
-    let port_ok = port
-        .map(|p| unsafe { webui_set_port(my_window.id, p as usize) })
-        .unwrap_or(false);
+    // WebUI server port: --port, else a random free one
+    let (port, port_ok) = match cli.port {
+        Some(port) => (Some(port), unsafe { webui_set_port(my_window.id, port as usize) }),
+        None => set_free_port(my_window.id),
+    };
 
     if port_ok {
         info!("WebUI port set to {}", port.unwrap_or(0));
@@ -225,6 +276,113 @@ fn main() {
         info!("WebUI port not set, using default");
     }
 
+    // Deliver queued backend → frontend pushes in priority order
+    outbound_queue::GLOBAL_OUTBOUND_QUEUE
+        .configure(outbound_queue::BackpressureConfig::from_app_config(&config));
+    payload_limits::configure(payload_limits::PayloadLimits::from_app_config(&config));
+    sanitize::configure(sanitize::Sanitizer::from_app_config(&config));
+    bridge_signing::configure(config.is_bridge_signing_enabled());
+    if config.is_bridge_signing_enabled() {
+        info!("Bridge messages to session and permission checked handlers must be signed");
+    }
+    event_bus::GLOBAL_EVENT_BUS.configure(event_bus::EventBusConfig::from_app_config(&config));
+    event_bus::GLOBAL_EVENT_BUS.use_middleware(event_middleware::RedactionMiddleware::default());
+    event_bus::GLOBAL_EVENT_BUS.use_middleware(event_middleware::CorrelationMiddleware);
+    event_bus::GLOBAL_EVENT_BUS.use_middleware(event_middleware::LoggingMiddleware);
+    outbound_queue::start_dispatcher(|message| {
+        webui::Window::from_id(message.window_id).run_js(&message.to_js());
+    });
+    // A panic opens an error dialog in every connected window. It is sent
+    // directly: the dispatcher may be the thread that panicked.
+    error_handler::on_panic(|report| {
+        let message = outbound_queue::OutgoingMessage::new(
+            0,
+            error_handler::PANIC_EVENT,
+            RpcResponse::ok(report).to_json(),
+            outbound_queue::PushPriority::Critical,
+        );
+        for window_id in client_registry::GLOBAL_CLIENT_REGISTRY.connected_windows() {
+            webui::Window::from_id(window_id).run_js(&message.to_js());
+        }
+    });
+    event_bridge::start(&config.get_event_bridge_topics());
+
+    // Audit trail: what env/CLI overrode at startup, then every change made
+    // through the settings UI ("ui") or by editing the file ("file")
+    for (source, changes) in AppConfig::override_changes() {
+        if !changes.is_empty() {
+            if let Err(e) = db.record_config_changes(source, &changes) {
+                error_handler::record_app_error("CONFIG", &e);
+            }
+        }
+    }
+    let audit_db = Arc::clone(&db);
+    event_bus::GLOBAL_EVENT_BUS
+        .subscribe(ConfigChanged::TOPIC, move |event| {
+            let Ok(changed) = serde_json::from_value::<ConfigChanged>(event.payload.clone()) else {
+                return;
+            };
+            let source = event.source.as_deref().unwrap_or("file");
+            if let Err(e) = audit_db.record_config_changes(source, &changed.changes) {
+                error_handler::record_app_error("CONFIG", &e);
+            }
+            let keys: Vec<&str> = changed.changes.iter().map(|c| c.key.as_str()).collect();
+            security_audit::emit("settings_changed", None, &keys.join(", "), Some(source));
+        })
+        .detach();
+    // Security events (sign-ins, refused permissions, raw SQL, ...) go to the
+    // audit log, where `security_audit` reads them
+    security_audit::persist(&event_bus::GLOBAL_EVENT_BUS, Arc::clone(&db)).detach();
+    // Vault values go with the session that stored them, however it ends
+    let vault_cleanup = container
+        .resolve_arc::<SessionManager>()
+        .and_then(|sessions| vault::clear_with_sessions(Arc::clone(&db), &sessions));
+    if let Err(e) = vault_cleanup {
+        error_handler::record_app_error("VAULT", &e);
+    }
+
+    // Hot reload: the watcher swaps the AppConfig in the container, settings
+    // read after startup are re-applied here
+    let reload_db = Arc::clone(&db);
+    event_bus::GLOBAL_EVENT_BUS
+        .subscribe_typed(move |changed: ConfigChanged| {
+            let config = match container.resolve_arc::<AppConfig>() {
+                Ok(config) => config,
+                Err(e) => return error_handler::record_app_error("CONFIG", &e),
+            };
+            crash_reporter::configure(&config);
+            if changed.touches("logging") {
+                logging::set_level(config.get_log_level());
+                logging::GLOBAL_LOG_BUFFER.set_capacity(config.get_log_buffer_size());
+                logging::spans::set_slow_threshold(config.get_slow_span_ms());
+                logging::set_console_format(logging::ConsoleFormat::from_config(&config));
+                logging::redaction::configure(&config);
+                logging::GLOBAL_LOG_SHIPPER.configure(&config);
+            }
+            if changed.touches("network") {
+                // The shared client is built again, with the new proxy, on next use
+                if let Err(e) = container.register_factory(http_client::build) {
+                    error_handler::record_app_error("CONFIG", &e);
+                }
+            }
+            if changed.touches("database") && config.should_create_sample_data() {
+                if let Err(e) = reload_db.insert_sample_data() {
+                    error_handler::record_app_error("CONFIG", &e);
+                }
+            }
+        })
+        .detach();
+    if let Some(config_path) = AppConfig::config_path() {
+        match config_watcher::start(container, &config_path) {
+            Ok(watcher) => {
+                if let Err(e) = container.register_disposable(watcher) {
+                    error_handler::record_app_error("CONFIG", &e);
+                }
+            }
+            Err(e) => error_handler::record_app_error("CONFIG", &e),
+        }
+    }
+
     // Set up UI event handlers from views layer
     presentation::ui_handlers::setup_ui_handlers(&mut my_window);
     presentation::ui_handlers::setup_counter_handlers(&mut my_window);
@@ -236,32 +394,83 @@ fn main() {
     presentation::error_handlers::setup_error_handlers(&mut my_window);
     presentation::error_handlers::setup_db_monitoring_handlers(&mut my_window);
     presentation::error_handlers::setup_devtools_handlers(&mut my_window);
+    presentation::serialization_handlers::setup_serialization_handlers(&mut my_window);
+    presentation::api_handlers::setup_api_handlers(&mut my_window);
+    presentation::outbound_handlers::setup_outbound_handlers(&mut my_window);
+    presentation::client_handlers::setup_client_handlers(&mut my_window);
+    presentation::settings_handlers::setup_settings_handlers(&mut my_window);
+    presentation::session_handlers::setup_session_handlers(&mut my_window);
+    presentation::auth_handlers::setup_auth_handlers(&mut my_window);
+    presentation::vault_handlers::setup_vault_handlers(&mut my_window);
+    presentation::api_key_handlers::setup_api_key_handlers(&mut my_window);
+    presentation::audit_handlers::setup_audit_handlers(&mut my_window);
+    presentation::fs_handlers::setup_fs_handlers(&mut my_window);
+    presentation::network_handlers::setup_network_handlers(&mut my_window);
+    presentation::client_handlers::track_window(&my_window, "main");
+
+    if cli.headless {
+        unsafe { webui_set_hide(my_window.id, true) };
+        info!("Headless mode: the window stays hidden");
+    }
 
     // Get window settings from config
     let window_title = config.get_window_title();
     info!("Window title: {}", window_title);
+    apply_window_settings(my_window.id, &config);
+
+    // Embedded frontend files extracted to a temp folder, removed on exit
+    let mut _embedded_dist = None;
+    if let Some(dev_server_url) = config.get_dev_server_url() {
+        // Dev mode: the dev server serves the UI with HMR, handlers stay bound here
+        match port.filter(|_| port_ok) {
+            Some(port) => {
+                let url = dev_server_entry_url(&dev_server_url, port);
+                info!("Loading application UI from dev server {}", url);
+                my_window.show(&url);
+            }
+            None => {
+                error!("Dev server mode requires a known WebUI port");
+                return;
+            }
+        }
+    } else {
+        // Show the built application - resolve dist/ robustly for both `cargo run` and packaged binaries
+        let (dist_dir, index_path, temp) = match resolve_frontend_dist() {
+            Some(paths) => paths,
+            None => {
+                error!("Could not locate frontend dist/index.html");
+                error!("Run `./run.sh --build-frontend` and ensure dist/index.html exists.");
+                return;
+            }
+        };
 
-    // Show the built application - resolve dist/ robustly for both `cargo run` and packaged binaries
-    let (dist_dir, index_path) = match resolve_frontend_dist() {
-        Some(paths) => paths,
-        None => {
-            error!("Could not locate frontend dist/index.html");
-            error!("Run `./run.sh --build-frontend` and ensure dist/index.html exists.");
-            return;
+        _embedded_dist = temp;
+
+        // Publish the handler API description at /api/openapi.json in http mode
+        if transport == "http_rest" {
+            match openapi::write_spec(&dist_dir, config.get_app_name(), config.get_version()) {
+                Ok(()) => info!("OpenAPI document written to {}/api/openapi.json", dist_dir.display()),
+                Err(e) => error_handler::record_app_error("MAIN", &e),
+            }
         }
-    };
-    
-    // Set root folder for WebUI to serve static files
-    let root_folder = dist_dir.to_str().unwrap_or("dist");
-    info!("Setting WebUI root folder to: {}", root_folder);
-    let c_string = std::ffi::CString::new(root_folder).unwrap();
-    unsafe {
-        webui_rs::webui::bindgen::webui_set_root_folder(my_window.id, c_string.as_ptr());
+
+        // Set root folder for WebUI to serve static files
+        let root_folder = dist_dir.to_str().unwrap_or("dist");
+        info!("Setting WebUI root folder to: {}", root_folder);
+        let c_string = std::ffi::CString::new(root_folder).unwrap();
+        unsafe {
+            webui_rs::webui::bindgen::webui_set_root_folder(my_window.id, c_string.as_ptr());
+        }
+
+        info!("Loading application UI from {}", index_path.display());
+        // When root folder is set, WebUI should load by route, not absolute file path.
+        my_window.show("index.html");
+    }
+
+    // Maximizing needs the native window, which exists once show() returns
+    if config.is_maximized() && !cli.headless {
+        unsafe { webui_maximize(my_window.id) };
     }
-    
-    info!("Loading application UI from {}", index_path.display());
-    // When root folder is set, WebUI should load by route, not absolute file path.
-    my_window.show("index.html");
 
     // Sync WebUI port to frontend
     if port_ok {
@@ -274,20 +483,121 @@ fn main() {
         }
     }
 
+    // Expose the app on the LAN through the token + allowlist gateway
+    let remote = match container.resolve_trait::<dyn secrets::SecretStore>() {
+        Ok(store) => remote_access::RemoteAccessConfig::from_app_config(&config, store.as_ref()),
+        Err(e) => {
+            error_handler::record_app_error("REMOTE", &e);
+            None
+        }
+    };
+    if let Some(remote) = remote {
+        match port {
+            Some(upstream_port) if port_ok => {
+                unsafe { webui_set_config(webui_config_multi_client, true) };
+                let token = remote.token.clone();
+                match remote_access::start_gateway(remote, upstream_port) {
+                    Ok(gateway) => {
+                        info!("Remote access enabled at http://{}/?token=<token>", gateway.addr());
+                        // A token read from the keyring isn't shown; a
+                        // generated one goes to stdout, as the log masks it
+                        let from_keyring = config
+                            .get_remote_token()
+                            .is_some_and(|t| secrets::secret_name(&t).is_some());
+                        if !from_keyring {
+                            let mut stdout = std::io::stdout().lock();
+                            if let Err(e) = remote_access::announce_token(&mut stdout, gateway.addr(), &token) {
+                                warn!("Failed to print the remote access token: {}", e);
+                            }
+                        }
+                        if let Err(e) = container.register_disposable(gateway) {
+                            error_handler::record_app_error("REMOTE", &e);
+                        }
+                    }
+                    Err(e) => error_handler::record_app_error("REMOTE", &e),
+                }
+            }
+            _ => warn!("Remote access requires a known WebUI port, gateway not started"),
+        }
+    }
+
     info!("Application started successfully, waiting for events...");
     info!("=============================================");
 
     // Wait until all windows are closed
     webui::wait();
 
-    // Print error summary before shutdown
+    info!("Application shutting down...");
+
+    // Stop servers, close the database and flush logs, newest service first
+    if let Err(e) = container.shutdown_all() {
+        error_handler::record_app_error("MAIN", &e);
+    }
+
+    // Print error summary before exit
     error_handler::print_error_summary();
 
-    info!("Application shutting down...");
     info!("=============================================");
 }
 
-fn resolve_frontend_dist() -> Option<(PathBuf, PathBuf)> {
+/// Size, position and resizability from `[window]`; must run before show()
+fn apply_window_settings(window_id: usize, config: &AppConfig) {
+    let (width, height) = config.get_window_size();
+    let (min_width, min_height) = config.get_min_window_size();
+    unsafe {
+        webui_set_size(window_id, width, height);
+        webui_set_minimum_size(window_id, min_width, min_height);
+        webui_set_resizable(window_id, config.is_resizable());
+        match config.get_window_position() {
+            Some((x, y)) => webui_set_position(window_id, x, y),
+            None => webui_set_center(window_id),
+        }
+    }
+    info!(
+        "Window size {}x{} (min {}x{}), {}",
+        width,
+        height,
+        min_width,
+        min_height,
+        config
+            .get_window_position()
+            .map(|(x, y)| format!("at {},{}", x, y))
+            .unwrap_or_else(|| String::from("centered"))
+    );
+    if config.is_always_on_top() {
+        // WebUI has no always-on-top call; keep the setting for when it does
+        warn!("window.always_on_top is not supported by WebUI and is ignored");
+    }
+}
+
+/// Give WebUI a free port from the dynamic range. Another process can take
+/// the port between the check and `webui_set_port`, which then refuses it,
+/// so a few others are tried before giving up.
+fn set_free_port(window_id: usize) -> (Option<u16>, bool) {
+    const ATTEMPTS: usize = 5;
+    let mut port = None;
+    for _ in 0..ATTEMPTS {
+        port = NetworkUtils::find_free_port(49152..=65535);
+        let Some(candidate) = port else {
+            break;
+        };
+        if unsafe { webui_set_port(window_id, candidate as usize) } {
+            return (port, true);
+        }
+        warn!("WebUI couldn't use port {}, trying another", candidate);
+    }
+    (port, false)
+}
+
+/// Dev server URL carrying the WebUI port so the page can load the bridge script
+fn dev_server_entry_url(base: &str, port: u16) -> String {
+    let separator = if base.contains('?') { '&' } else { '?' };
+    format!("{}{}webui_port={}", base, separator, port)
+}
+
+/// The dist folder and its index.html; the guard is set when they were
+/// extracted from the embedded assets and removes them when dropped
+fn resolve_frontend_dist() -> Option<(PathBuf, PathBuf, Option<TempFileGuard>)> {
     let mut candidates: Vec<PathBuf> = Vec::new();
 
     if let Ok(custom_dist) = std::env::var("RUSTWEBUI_DIST_DIR") {
@@ -317,29 +627,31 @@ fn resolve_frontend_dist() -> Option<(PathBuf, PathBuf)> {
         let index_path = dist_dir.join("index.html");
         if index_path.exists() {
             info!("Resolved frontend dist directory: {}", dist_dir.display());
-            return Some((dist_dir, index_path));
+            return Some((dist_dir, index_path, None));
         }
         warn!("Frontend dist candidate missing index.html: {}", dist_dir.display());
     }
 
-    if let Some((dist_dir, index_path)) = materialize_embedded_frontend_dist() {
+    if let Some((dist_dir, index_path, temp)) = materialize_embedded_frontend_dist() {
         info!(
             "Resolved frontend dist from embedded assets: {}",
             dist_dir.display()
         );
-        return Some((dist_dir, index_path));
+        return Some((dist_dir, index_path, Some(temp)));
     }
 
     None
 }
 
-fn materialize_embedded_frontend_dist() -> Option<(PathBuf, PathBuf)> {
+fn materialize_embedded_frontend_dist() -> Option<(PathBuf, PathBuf, TempFileGuard)> {
     if !EMBEDDED_FRONTEND_AVAILABLE {
         warn!("Embedded frontend assets unavailable");
         return None;
     }
 
     let base = std::env::temp_dir().join(format!("rustwebui-embedded-{}", std::process::id()));
+    // Removes the extracted files once the app exits, or if extraction fails
+    let temp = TempFileGuard::new(&base);
     let dist_dir = base.join("dist");
     let js_dir = dist_dir.join("static").join("js");
 
@@ -356,11 +668,12 @@ fn materialize_embedded_frontend_dist() -> Option<(PathBuf, PathBuf)> {
     ];
 
     for (path, contents) in writes {
-        if let Err(e) = fs::write(&path, contents) {
+        if let Err(e) = FileUtils::write_atomic(&path, contents.as_bytes()) {
             warn!("Failed to write embedded frontend file {}: {}", path.display(), e);
             return None;
         }
     }
 
-    Some((dist_dir.clone(), dist_dir.join("index.html")))
+    let index_path = dist_dir.join("index.html");
+    Some((dist_dir, index_path, temp))
 }
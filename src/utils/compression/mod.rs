@@ -1,5 +1,108 @@
 #![allow(dead_code)]
-use std::io::{BufReader, Read, Write};
+// src/utils/compression/mod.rs
+// Compression utilities: gzip, zstd and brotli, picked by `CompressionFormat`
+// Gzip is what browsers decompress natively; zstd is the fastest for logs
+// and backend-to-backend data; brotli gives the smallest static payloads.
+// `compress_file` and the `stream` adapters work on inputs of any size.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+pub mod stream;
+
+pub use stream::{CompressWriter, DecompressReader};
+
+/// Buffer size brotli works with
+pub(crate) const BROTLI_BUFFER: usize = 4096;
+/// Brotli window size as log2 bytes; 22 is the format's default
+pub(crate) const BROTLI_LG_WINDOW: u32 = 22;
+
+/// Supported compression formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompressionFormat {
+    Gzip,
+    Zstd,
+    Brotli,
+}
+
+impl fmt::Display for CompressionFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompressionFormat::Gzip => write!(f, "Gzip"),
+            CompressionFormat::Zstd => write!(f, "Zstandard"),
+            CompressionFormat::Brotli => write!(f, "Brotli"),
+        }
+    }
+}
+
+impl CompressionFormat {
+    /// Get all available formats
+    pub fn available_formats() -> &'static [CompressionFormat] {
+        &[
+            CompressionFormat::Gzip,
+            CompressionFormat::Zstd,
+            CompressionFormat::Brotli,
+        ]
+    }
+
+    /// Parse a format from its config/wire name
+    pub fn from_name(name: &str) -> Option<CompressionFormat> {
+        match name.trim().to_lowercase().as_str() {
+            "gzip" | "gz" => Some(CompressionFormat::Gzip),
+            "zstd" | "zstandard" | "zst" => Some(CompressionFormat::Zstd),
+            "brotli" | "br" => Some(CompressionFormat::Brotli),
+            _ => None,
+        }
+    }
+
+    /// Get the config/wire name of the format
+    pub fn name(&self) -> &'static str {
+        match self {
+            CompressionFormat::Gzip => "gzip",
+            CompressionFormat::Zstd => "zstd",
+            CompressionFormat::Brotli => "brotli",
+        }
+    }
+
+    /// File extension for files in this format, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CompressionFormat::Gzip => "gz",
+            CompressionFormat::Zstd => "zst",
+            CompressionFormat::Brotli => "br",
+        }
+    }
+
+    /// Get format description
+    pub fn description(&self) -> &'static str {
+        match self {
+            CompressionFormat::Gzip => "Gzip - universal, browsers decompress it natively",
+            CompressionFormat::Zstd => "Zstandard - fast at every level, for logs and backends",
+            CompressionFormat::Brotli => "Brotli - smallest output, slow at high quality",
+        }
+    }
+
+    /// Levels the format accepts; higher compresses better and slower
+    pub fn levels(&self) -> RangeInclusive<i32> {
+        match self {
+            CompressionFormat::Gzip => 0..=9,
+            CompressionFormat::Zstd => zstd::compression_level_range(),
+            CompressionFormat::Brotli => 0..=11,
+        }
+    }
+
+    /// Level used when none is given
+    pub fn default_level(&self) -> i32 {
+        match self {
+            CompressionFormat::Gzip => 6,
+            CompressionFormat::Zstd => zstd::DEFAULT_COMPRESSION_LEVEL,
+            CompressionFormat::Brotli => 6,
+        }
+    }
+}
 
 pub struct CompressionUtils;
 
@@ -22,4 +125,158 @@ impl CompressionUtils {
 
         Ok(result)
     }
+
+    /// Zstandard at `level`, see `CompressionFormat::Zstd.levels()`
+    pub fn compress_zstd(input: &[u8], level: i32) -> Result<Vec<u8>, String> {
+        zstd::encode_all(input, level).map_err(|e| e.to_string())
+    }
+
+    pub fn decompress_zstd(input: &[u8]) -> Result<Vec<u8>, String> {
+        zstd::decode_all(input).map_err(|e| e.to_string())
+    }
+
+    /// Brotli at `quality` 0-11
+    pub fn compress_brotli(input: &[u8], quality: u32) -> Result<Vec<u8>, String> {
+        let mut encoder =
+            brotli::CompressorWriter::new(Vec::new(), BROTLI_BUFFER, quality, BROTLI_LG_WINDOW);
+        encoder.write_all(input).map_err(|e| e.to_string())?;
+
+        // Finishes the stream
+        Ok(encoder.into_inner())
+    }
+
+    pub fn decompress_brotli(input: &[u8]) -> Result<Vec<u8>, String> {
+        let mut decoder = brotli::Decompressor::new(input, BROTLI_BUFFER);
+        let mut result = Vec::new();
+
+        decoder
+            .read_to_end(&mut result)
+            .map_err(|e| e.to_string())?;
+
+        Ok(result)
+    }
+
+    /// Compress `input` in `format` at its default level
+    pub fn compress(input: &[u8], format: CompressionFormat) -> Result<Vec<u8>, String> {
+        Self::compress_with_level(input, format, format.default_level())
+    }
+
+    /// Compress `input` in `format` at `level`; Err when the format doesn't
+    /// have that level
+    pub fn compress_with_level(
+        input: &[u8],
+        format: CompressionFormat,
+        level: i32,
+    ) -> Result<Vec<u8>, String> {
+        let mut encoder =
+            CompressWriter::new(Vec::new(), format, level).map_err(|e| e.to_string())?;
+        encoder.write_all(input).map_err(|e| e.to_string())?;
+
+        encoder.finish().map_err(|e| e.to_string())
+    }
+
+    pub fn decompress(input: &[u8], format: CompressionFormat) -> Result<Vec<u8>, String> {
+        match format {
+            CompressionFormat::Gzip => Self::decompress_gzip(input),
+            CompressionFormat::Zstd => Self::decompress_zstd(input),
+            CompressionFormat::Brotli => Self::decompress_brotli(input),
+        }
+    }
+
+    /// Compress file `src` into `dst` in `format` at its default level,
+    /// streaming so memory use doesn't grow with the file; returns the size
+    /// of `dst`. A partly written `dst` is removed on failure.
+    pub fn compress_file(src: &Path, dst: &Path, format: CompressionFormat) -> Result<u64, String> {
+        Self::stream_file(src, dst, |input, output| {
+            let mut encoder = CompressWriter::new(output, format, format.default_level())?;
+            io::copy(input, &mut encoder)?;
+            encoder.finish()?.flush()
+        })
+    }
+
+    /// Decompress `format` file `src` into `dst`, streaming; returns the
+    /// size of `dst`. A partly written `dst` is removed on failure.
+    pub fn decompress_file(
+        src: &Path,
+        dst: &Path,
+        format: CompressionFormat,
+    ) -> Result<u64, String> {
+        Self::stream_file(src, dst, |input, output| {
+            io::copy(&mut DecompressReader::new(input, format)?, output)?;
+            output.flush()
+        })
+    }
+
+    fn stream_file(
+        src: &Path,
+        dst: &Path,
+        copy: impl FnOnce(&mut BufReader<File>, &mut BufWriter<File>) -> io::Result<()>,
+    ) -> Result<u64, String> {
+        let describe = |path: &Path, e: io::Error| format!("{}: {}", path.display(), e);
+        let mut input = BufReader::new(File::open(src).map_err(|e| describe(src, e))?);
+        let mut output = BufWriter::new(File::create(dst).map_err(|e| describe(dst, e))?);
+        if let Err(e) = copy(&mut input, &mut output) {
+            drop(output);
+            let _ = fs::remove_file(dst);
+            return Err(format!("{} to {}: {}", src.display(), dst.display(), e));
+        }
+        drop(output);
+        fs::metadata(dst).map(|meta| meta.len()).map_err(|e| describe(dst, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats_round_trip() {
+        let input = "Compressible text, repeated. ".repeat(200);
+        for &format in CompressionFormat::available_formats() {
+            assert_eq!(CompressionFormat::from_name(format.name()), Some(format));
+            for level in [1, format.default_level(), 9] {
+                let compressed =
+                    CompressionUtils::compress_with_level(input.as_bytes(), format, level).unwrap();
+                assert!(compressed.len() < input.len() / 10, "{} level {}", format, level);
+                let restored = CompressionUtils::decompress(&compressed, format).unwrap();
+                assert_eq!(restored, input.as_bytes());
+            }
+            assert!(CompressionUtils::compress_with_level(b"x", format, 99).is_err());
+        }
+        assert!(CompressionUtils::decompress_gzip(b"not compressed").is_err());
+        assert!(CompressionUtils::decompress_zstd(b"not compressed").is_err());
+        assert_eq!(CompressionFormat::from_name(" BR "), Some(CompressionFormat::Brotli));
+        assert_eq!(CompressionFormat::from_name("lz4"), None);
+    }
+
+    #[test]
+    fn test_file_streaming() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("export.csv");
+        let mut file = BufWriter::new(File::create(&src).unwrap());
+        for i in 0..50_000 {
+            writeln!(file, "{},user{}@example.com,active", i, i % 97).unwrap();
+        }
+        drop(file);
+        let original = fs::read(&src).unwrap();
+
+        for &format in CompressionFormat::available_formats() {
+            let packed = dir.path().join(format!("export.csv.{}", format.extension()));
+            let restored = dir.path().join("restored.csv");
+            let size = CompressionUtils::compress_file(&src, &packed, format).unwrap();
+            assert!(size < original.len() as u64 / 4, "{}", format);
+
+            let in_memory = CompressionUtils::decompress(&fs::read(&packed).unwrap(), format);
+            assert_eq!(in_memory.unwrap(), original);
+            let size = CompressionUtils::decompress_file(&packed, &restored, format).unwrap();
+            assert_eq!(size, original.len() as u64);
+            assert_eq!(fs::read(&restored).unwrap(), original);
+        }
+
+        let broken = dir.path().join("broken.zst");
+        fs::write(&broken, b"not zstd").unwrap();
+        let out = dir.path().join("out.csv");
+        assert!(CompressionUtils::decompress_file(&broken, &out, CompressionFormat::Zstd).is_err());
+        assert!(!out.exists());
+    }
 }
@@ -0,0 +1,108 @@
+// src/utils/compression/stream.rs
+// Streaming compression: a writer that compresses what is written to it and
+// a reader that decompresses what is read from it, in any
+// `CompressionFormat`, holding only a small buffer instead of the whole input
+
+use std::io::{self, BufReader, Read, Write};
+
+use super::{CompressionFormat, BROTLI_BUFFER, BROTLI_LG_WINDOW};
+
+/// Compresses everything written to it into `W`. Call `finish` when done;
+/// dropping it without leaves the output truncated.
+pub enum CompressWriter<W: Write> {
+    Gzip(flate2::write::GzEncoder<W>),
+    Zstd(zstd::Encoder<'static, W>),
+    // Boxed, as its buffers are kilobytes inline
+    Brotli(Box<brotli::CompressorWriter<W>>),
+}
+
+impl<W: Write> CompressWriter<W> {
+    /// Compress into `writer` in `format` at `level`, see
+    /// `CompressionFormat::levels`
+    pub fn new(writer: W, format: CompressionFormat, level: i32) -> io::Result<Self> {
+        let levels = format.levels();
+        if !levels.contains(&level) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "{} level must be between {} and {}",
+                    format,
+                    levels.start(),
+                    levels.end()
+                ),
+            ));
+        }
+        Ok(match format {
+            CompressionFormat::Gzip => CompressWriter::Gzip(flate2::write::GzEncoder::new(
+                writer,
+                flate2::Compression::new(level as u32),
+            )),
+            CompressionFormat::Zstd => CompressWriter::Zstd(zstd::Encoder::new(writer, level)?),
+            CompressionFormat::Brotli => CompressWriter::Brotli(Box::new(
+                brotli::CompressorWriter::new(writer, BROTLI_BUFFER, level as u32, BROTLI_LG_WINDOW),
+            )),
+        })
+    }
+
+    /// Write the end of the compressed stream and return the writer
+    pub fn finish(self) -> io::Result<W> {
+        match self {
+            CompressWriter::Gzip(encoder) => encoder.finish(),
+            CompressWriter::Zstd(encoder) => encoder.finish(),
+            CompressWriter::Brotli(mut encoder) => {
+                encoder.flush()?;
+                Ok((*encoder).into_inner())
+            }
+        }
+    }
+}
+
+impl<W: Write> Write for CompressWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            CompressWriter::Gzip(encoder) => encoder.write(buf),
+            CompressWriter::Zstd(encoder) => encoder.write(buf),
+            CompressWriter::Brotli(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            CompressWriter::Gzip(encoder) => encoder.flush(),
+            CompressWriter::Zstd(encoder) => encoder.flush(),
+            CompressWriter::Brotli(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// Decompresses `R` as it is read
+pub enum DecompressReader<R: Read> {
+    Gzip(flate2::read::MultiGzDecoder<R>),
+    Zstd(zstd::Decoder<'static, BufReader<R>>),
+    Brotli(Box<brotli::Decompressor<R>>),
+}
+
+impl<R: Read> DecompressReader<R> {
+    /// Read `format` compressed data from `reader`
+    pub fn new(reader: R, format: CompressionFormat) -> io::Result<Self> {
+        Ok(match format {
+            CompressionFormat::Gzip => {
+                DecompressReader::Gzip(flate2::read::MultiGzDecoder::new(reader))
+            }
+            CompressionFormat::Zstd => DecompressReader::Zstd(zstd::Decoder::new(reader)?),
+            CompressionFormat::Brotli => {
+                DecompressReader::Brotli(Box::new(brotli::Decompressor::new(reader, BROTLI_BUFFER)))
+            }
+        })
+    }
+}
+
+impl<R: Read> Read for DecompressReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            DecompressReader::Gzip(decoder) => decoder.read(buf),
+            DecompressReader::Zstd(decoder) => decoder.read(buf),
+            DecompressReader::Brotli(decoder) => decoder.read(buf),
+        }
+    }
+}
@@ -0,0 +1,260 @@
+// src/utils/crypto/hashing.rs
+// Password hashing algorithms behind one trait, so a deployment can pick
+// Argon2id, bcrypt or scrypt (`[security.password_hashing]`). Every hash
+// names its algorithm and parameters, so `verify_any` checks a password
+// against a hash from any of them and stored hashes keep working after the
+// configured algorithm changes.
+
+use argon2::password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier as _, SaltString};
+
+use super::CryptoUtils;
+
+/// Names accepted by `[security.password_hashing] algorithm`
+pub const HASH_ALGORITHMS: [&str; 3] = ["argon2", "bcrypt", "scrypt"];
+
+pub trait PasswordHasher: Send + Sync {
+    /// Name of the algorithm, one of `HASH_ALGORITHMS`
+    fn algorithm(&self) -> &'static str;
+
+    /// Err with the reason when the parameters can't be used
+    fn check(&self) -> Result<(), String>;
+
+    /// Hash `password` with a random salt
+    fn hash(&self, password: &str) -> Result<String, String>;
+
+    /// Whether `password` matches `hash`, a hash of this algorithm with any
+    /// parameters
+    fn verify(&self, password: &str, hash: &str) -> bool;
+
+    /// Whether `hash` was made by this algorithm with these parameters; when
+    /// not, it should be replaced after the next successful sign-in
+    fn is_current(&self, hash: &str) -> bool;
+}
+
+fn salt() -> Result<SaltString, String> {
+    SaltString::encode_b64(&CryptoUtils::random_bytes(16)).map_err(|e| e.to_string())
+}
+
+/// Argon2id, the default; memory in KiB
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2Hasher {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for Argon2Hasher {
+    fn default() -> Self {
+        Self {
+            memory_kib: argon2::Params::DEFAULT_M_COST,
+            iterations: argon2::Params::DEFAULT_T_COST,
+            parallelism: argon2::Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl Argon2Hasher {
+    fn params(&self) -> Result<argon2::Params, String> {
+        argon2::Params::new(self.memory_kib, self.iterations, self.parallelism, None)
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl PasswordHasher for Argon2Hasher {
+    fn algorithm(&self) -> &'static str {
+        "argon2"
+    }
+
+    fn check(&self) -> Result<(), String> {
+        self.params().map(|_| ())
+    }
+
+    fn hash(&self, password: &str) -> Result<String, String> {
+        argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, self.params()?)
+            .hash_password(password.as_bytes(), &salt()?)
+            .map(|hash| hash.to_string())
+            .map_err(|e| e.to_string())
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> bool {
+        PasswordHash::new(hash).is_ok_and(|parsed| {
+            argon2::Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+    }
+
+    fn is_current(&self, hash: &str) -> bool {
+        let Ok(parsed) = PasswordHash::new(hash) else {
+            return false;
+        };
+        parsed.algorithm == argon2::ARGON2ID_IDENT
+            && argon2::Params::try_from(&parsed).is_ok_and(|params| {
+                params.m_cost() == self.memory_kib
+                    && params.t_cost() == self.iterations
+                    && params.p_cost() == self.parallelism
+            })
+    }
+}
+
+/// bcrypt ("$2b$"); only the first 72 bytes of a password count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BcryptHasher {
+    pub cost: u32,
+}
+
+impl Default for BcryptHasher {
+    fn default() -> Self {
+        Self {
+            cost: bcrypt::DEFAULT_COST,
+        }
+    }
+}
+
+impl PasswordHasher for BcryptHasher {
+    fn algorithm(&self) -> &'static str {
+        "bcrypt"
+    }
+
+    fn check(&self) -> Result<(), String> {
+        if (4..=31).contains(&self.cost) {
+            return Ok(());
+        }
+        Err(String::from("bcrypt cost must be between 4 and 31"))
+    }
+
+    fn hash(&self, password: &str) -> Result<String, String> {
+        bcrypt::hash(password, self.cost).map_err(|e| e.to_string())
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> bool {
+        bcrypt::verify(password, hash).unwrap_or(false)
+    }
+
+    fn is_current(&self, hash: &str) -> bool {
+        hash.strip_prefix("$2b$")
+            .and_then(|rest| rest.split('$').next())
+            .and_then(|cost| cost.parse::<u32>().ok())
+            == Some(self.cost)
+    }
+}
+
+/// scrypt as a PHC string ("$scrypt$"); the cost is 2^log_n
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScryptHasher {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl Default for ScryptHasher {
+    fn default() -> Self {
+        Self {
+            log_n: scrypt::Params::RECOMMENDED_LOG_N,
+            r: scrypt::Params::RECOMMENDED_R,
+            p: scrypt::Params::RECOMMENDED_P,
+        }
+    }
+}
+
+impl ScryptHasher {
+    fn params(&self) -> Result<scrypt::Params, String> {
+        let len = scrypt::Params::RECOMMENDED_LEN;
+        scrypt::Params::new(self.log_n, self.r, self.p, len).map_err(|e| e.to_string())
+    }
+}
+
+impl PasswordHasher for ScryptHasher {
+    fn algorithm(&self) -> &'static str {
+        "scrypt"
+    }
+
+    fn check(&self) -> Result<(), String> {
+        self.params().map(|_| ())
+    }
+
+    fn hash(&self, password: &str) -> Result<String, String> {
+        scrypt::Scrypt
+            .hash_password_customized(password.as_bytes(), None, None, self.params()?, &salt()?)
+            .map(|hash| hash.to_string())
+            .map_err(|e| e.to_string())
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> bool {
+        PasswordHash::new(hash).is_ok_and(|parsed| {
+            scrypt::Scrypt
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+    }
+
+    fn is_current(&self, hash: &str) -> bool {
+        let Ok(parsed) = PasswordHash::new(hash) else {
+            return false;
+        };
+        parsed.algorithm == scrypt::ALG_ID
+            && scrypt::Params::try_from(&parsed).is_ok_and(|params| {
+                params.log_n() == self.log_n && params.r() == self.r && params.p() == self.p
+            })
+    }
+}
+
+/// The algorithm `hash` was made with, from its prefix
+pub fn detect(hash: &str) -> Option<&'static str> {
+    if hash.starts_with("$argon2") {
+        Some("argon2")
+    } else if ["$2a$", "$2b$", "$2x$", "$2y$"].iter().any(|p| hash.starts_with(p)) {
+        Some("bcrypt")
+    } else if hash.starts_with("$scrypt$") {
+        Some("scrypt")
+    } else {
+        None
+    }
+}
+
+/// Whether `password` matches `hash`, whichever algorithm made it
+pub fn verify_any(password: &str, hash: &str) -> bool {
+    match detect(hash) {
+        Some("argon2") => Argon2Hasher::default().verify(password, hash),
+        Some("bcrypt") => BcryptHasher::default().verify(password, hash),
+        Some("scrypt") => ScryptHasher::default().verify(password, hash),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hashers_round_trip_and_detect() {
+        // Cheap parameters; the defaults are slow on purpose
+        let hashers: [Box<dyn PasswordHasher>; 3] = [
+            Box::new(Argon2Hasher {
+                memory_kib: 64,
+                iterations: 1,
+                parallelism: 1,
+            }),
+            Box::new(BcryptHasher { cost: 4 }),
+            Box::new(ScryptHasher { log_n: 4, r: 8, p: 1 }),
+        ];
+        for hasher in &hashers {
+            assert!(hasher.check().is_ok());
+            let hash = hasher.hash("correct horse").unwrap();
+            assert_eq!(detect(&hash), Some(hasher.algorithm()));
+            assert!(hasher.verify("correct horse", &hash));
+            assert!(verify_any("correct horse", &hash));
+            assert!(!verify_any("wrong horse", &hash));
+            assert!(hasher.is_current(&hash));
+            for other in hashers.iter().filter(|h| h.algorithm() != hasher.algorithm()) {
+                assert!(!other.is_current(&hash));
+            }
+        }
+
+        let old = BcryptHasher { cost: 4 }.hash("pw").unwrap();
+        assert!(!BcryptHasher { cost: 5 }.is_current(&old));
+        assert!(!verify_any("pw", "plain text"));
+        assert!(BcryptHasher { cost: 40 }.check().is_err());
+        assert!(ScryptHasher { log_n: 20, r: 1, p: 1 }.check().is_err());
+    }
+}
@@ -1,7 +1,71 @@
 #![allow(dead_code)]
+use rand::rngs::OsRng;
+use rand::RngCore;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+pub mod hashing;
+
+pub use hashing::{Argon2Hasher, BcryptHasher, PasswordHasher, ScryptHasher};
+
+/// Bytes read at a time by the streaming digests
+const DIGEST_CHUNK: usize = 64 * 1024;
+
+/// Characters of `random_token`: URL-safe, 6 bits each
+const TOKEN_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
 pub struct CryptoUtils;
 
 impl CryptoUtils {
+    /// `n` bytes from the operating system's CSPRNG
+    pub fn random_bytes(n: usize) -> Vec<u8> {
+        let mut bytes = vec![0u8; n];
+        OsRng.fill_bytes(&mut bytes);
+        bytes
+    }
+
+    /// A URL-safe token of `len` characters, 6 random bits each
+    pub fn random_token(len: usize) -> String {
+        Self::random_string(len, TOKEN_ALPHABET)
+    }
+
+    /// `len` characters drawn uniformly from `alphabet` (at most 256 ASCII
+    /// characters); bytes that would bias the draw are skipped
+    pub fn random_string(len: usize, alphabet: &[u8]) -> String {
+        assert!(!alphabet.is_empty() && alphabet.len() <= 256);
+        let limit = 256 - 256 % alphabet.len();
+        let mut out = String::with_capacity(len);
+        while out.len() < len {
+            for byte in Self::random_bytes(len - out.len()) {
+                if (byte as usize) < limit && out.len() < len {
+                    out.push(alphabet[byte as usize % alphabet.len()] as char);
+                }
+            }
+        }
+        out
+    }
+
+    /// A random (version 4) UUID, e.g. "3f2b8c1e-9d4a-4f6b-8e2a-1c5d7e9f0a3b"
+    pub fn uuid_v4() -> String {
+        let mut bytes = [0u8; 16];
+        OsRng.fill_bytes(&mut bytes);
+        format_uuid(bytes, 4)
+    }
+
+    /// A time-ordered (version 7) UUID: the Unix time in milliseconds
+    /// followed by random bits, so ids sort by creation time
+    pub fn uuid_v7() -> String {
+        Self::uuid_v7_at(chrono::Utc::now().timestamp_millis() as u64)
+    }
+
+    fn uuid_v7_at(unix_millis: u64) -> String {
+        let mut bytes = [0u8; 16];
+        OsRng.fill_bytes(&mut bytes[6..]);
+        bytes[..6].copy_from_slice(&unix_millis.to_be_bytes()[2..]);
+        format_uuid(bytes, 7)
+    }
+
     pub fn sha256(data: &str) -> String {
         use sha2::{Digest, Sha256};
         let mut hasher = Sha256::new();
@@ -9,6 +73,30 @@ impl CryptoUtils {
         format!("{:x}", hasher.finalize())
     }
 
+    /// Hex SHA-256 of everything `reader` yields, read in 64 KiB chunks so
+    /// memory use doesn't grow with the input; matches `sha256` of the same
+    /// bytes
+    pub fn sha256_reader(mut reader: impl Read) -> io::Result<String> {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; DIGEST_CHUNK];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => hasher.update(&buffer[..n]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// Hex SHA-256 of the file at `path`, e.g. to check a download or a
+    /// backup against a published checksum
+    pub fn sha256_file(path: impl AsRef<Path>) -> io::Result<String> {
+        Self::sha256_reader(File::open(path)?)
+    }
+
     pub fn md5(data: &str) -> String {
         use md5::compute;
         format!("{:x}", compute(data.as_bytes()))
@@ -18,11 +106,83 @@ impl CryptoUtils {
 pub struct PasswordUtils;
 
 impl PasswordUtils {
+    /// Argon2id hash of `password` with a random salt, as a PHC string
+    /// ("$argon2id$v=19$...") that carries its own parameters
     pub fn hash_password(password: &str) -> Result<String, String> {
-        use sha2::{Digest, Sha256};
-        let salt = "fixed_salt_for_demo"; // In production, use random salt
-        let mut hasher = Sha256::new();
-        hasher.update(format!("{}{}", password, salt).as_bytes());
-        Ok(format!("{:x}", hasher.finalize()))
+        Argon2Hasher::default().hash(password)
+    }
+
+    /// Whether `password` matches `hash`, from `hash_password` or any
+    /// other `PasswordHasher`
+    pub fn verify_password(password: &str, hash: &str) -> bool {
+        hashing::verify_any(password, hash)
+    }
+}
+
+/// Set the version and RFC 4122 variant bits and format as 8-4-4-4-12 hex
+fn format_uuid(mut bytes: [u8; 16], version: u8) -> String {
+    bytes[6] = (bytes[6] & 0x0f) | (version << 4);
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = hex::encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streaming_sha256() {
+        // FIPS 180-2 test vector
+        let abc = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        assert_eq!(CryptoUtils::sha256_reader("abc".as_bytes()).unwrap(), abc);
+        assert_eq!(CryptoUtils::sha256("abc"), abc);
+
+        // Larger than one chunk, so the digest spans several reads
+        let data = "0123456789abcdef".repeat(DIGEST_CHUNK / 8);
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), &data).unwrap();
+        assert_eq!(CryptoUtils::sha256_file(file.path()).unwrap(), CryptoUtils::sha256(&data));
+        assert!(CryptoUtils::sha256_file(file.path().with_extension("missing")).is_err());
+    }
+
+    #[test]
+    fn test_random_tokens() {
+        assert_eq!(CryptoUtils::random_bytes(24).len(), 24);
+        let token = CryptoUtils::random_token(43);
+        assert_eq!(token.len(), 43);
+        assert!(token.bytes().all(|b| TOKEN_ALPHABET.contains(&b)));
+        assert_ne!(token, CryptoUtils::random_token(43));
+        assert!(CryptoUtils::random_string(100, b"abc").bytes().all(|b| b"abc".contains(&b)));
+    }
+
+    #[test]
+    fn test_password_hashes() {
+        let hash = PasswordUtils::hash_password("correct horse").unwrap();
+        assert!(hash.starts_with("$argon2id$"));
+        assert_ne!(hash, PasswordUtils::hash_password("correct horse").unwrap());
+        assert!(PasswordUtils::verify_password("correct horse", &hash));
+        assert!(!PasswordUtils::verify_password("wrong horse", &hash));
+        assert!(!PasswordUtils::verify_password("correct horse", "not a hash"));
+    }
+
+    #[test]
+    fn test_uuids() {
+        let v4 = CryptoUtils::uuid_v4();
+        assert_eq!(v4.len(), 36);
+        assert_eq!(&v4[14..15], "4");
+        assert!(matches!(&v4[19..20], "8" | "9" | "a" | "b"));
+
+        let v7 = CryptoUtils::uuid_v7_at(0x0189_0abc_def0);
+        assert!(v7.starts_with("01890abc-def0-7"));
+        assert!(matches!(&v7[19..20], "8" | "9" | "a" | "b"));
+        assert!(CryptoUtils::uuid_v7_at(1) < CryptoUtils::uuid_v7_at(2));
     }
 }
@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 use base64::Engine;
+use std::fmt::Write as _;
 
 #[derive(Debug, Clone)]
 pub enum EncodingError {
@@ -8,6 +9,21 @@ pub enum EncodingError {
     EncodingError(String),
 }
 
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+/// RFC 3986 gen-delims and sub-delims, kept by `UrlEncodeMode::FullUrl`
+const URL_RESERVED: &[u8] = b":/?#[]@!$&'()*+,;=";
+
+/// What `EncodingUtils::encode_url` leaves unescaped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UrlEncodeMode {
+    /// One component, e.g. a query value: only unreserved characters are
+    /// kept, so `/`, `?`, `&` and `=` are escaped
+    Component,
+    /// A whole URL: the reserved delimiters are kept as well, so the URL's
+    /// structure survives, like JavaScript's `encodeURI`
+    FullUrl,
+}
+
 pub struct EncodingUtils;
 
 impl EncodingUtils {
@@ -33,37 +49,137 @@ impl EncodingUtils {
         hex::encode(input).to_uppercase()
     }
 
+    /// RFC 4648 base32 without padding, as authenticator apps expect
+    pub fn encode_base32(input: &[u8]) -> String {
+        let mut output = String::with_capacity(input.len().div_ceil(5) * 8);
+        for chunk in input.chunks(5) {
+            let mut block = [0u8; 5];
+            block[..chunk.len()].copy_from_slice(chunk);
+            let bits = block.iter().fold(0u64, |acc, &b| (acc << 8) | u64::from(b));
+            let chars = (chunk.len() * 8).div_ceil(5);
+            for i in 0..chars {
+                let index = (bits >> (35 - i * 5)) & 0x1f;
+                output.push(BASE32_ALPHABET[index as usize] as char);
+            }
+        }
+        output
+    }
+
+    /// Base32 in either case, with or without padding; spaces are ignored
+    pub fn decode_base32(input: &str) -> Result<Vec<u8>, EncodingError> {
+        let mut output = Vec::with_capacity(input.len() * 5 / 8);
+        let mut buffer = 0u32;
+        let mut bits = 0;
+        for c in input.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+            let value = BASE32_ALPHABET
+                .iter()
+                .position(|&a| a as char == c.to_ascii_uppercase())
+                .ok_or_else(|| {
+                    EncodingError::DecodingError(format!("invalid base32 character '{}'", c))
+                })?;
+            buffer = (buffer << 5) | value as u32;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                output.push((buffer >> bits) as u8);
+                buffer &= (1 << bits) - 1;
+            }
+        }
+        Ok(output)
+    }
+
+    /// Percent-encode `input` as one URL component, e.g. a query value or
+    /// path segment: every byte but the RFC 3986 unreserved characters
+    /// (`A-Z a-z 0-9 - . _ ~`) is escaped, so multi-byte UTF-8 becomes one
+    /// `%XX` per byte
     pub fn encode_url_safe(input: &str) -> String {
-        input
-            .chars()
-            .map(|c| match c {
-                'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' | '.' | '~' => c.to_string(),
-                _ => format!("%{:02X}", c as u8),
-            })
-            .collect()
+        Self::encode_url(input, UrlEncodeMode::Component)
     }
 
-    pub fn decode_url_safe(input: &str) -> String {
-        let mut result = String::new();
-        let mut chars = input.chars().peekable();
-
-        while let Some(c) = chars.next() {
-            if c == '%' {
-                let hex1 = chars.next().unwrap_or('0');
-                let hex2 = chars.next().unwrap_or('0');
-                let hex_str = format!("{}{}", hex1, hex2);
-                if let Ok(byte) = u8::from_str_radix(&hex_str, 16) {
-                    result.push(byte as char);
-                } else {
-                    result.push('%');
-                    result.push(hex1);
-                    result.push(hex2);
-                }
+    /// Percent-encode `input` in the given mode; `%` is always escaped, so
+    /// `decode_url_safe` gives back the input
+    pub fn encode_url(input: &str, mode: UrlEncodeMode) -> String {
+        let mut output = String::with_capacity(input.len());
+        for &byte in input.as_bytes() {
+            let reserved_kept = mode == UrlEncodeMode::FullUrl && URL_RESERVED.contains(&byte);
+            if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) || reserved_kept {
+                output.push(byte as char);
             } else {
-                result.push(c);
+                let _ = write!(output, "%{:02X}", byte);
+            }
+        }
+        output
+    }
+
+    /// Undo percent-encoding from either mode. A `%` not followed by two hex
+    /// digits is kept as it is, and `+` stays a plus; Err when the decoded
+    /// bytes aren't UTF-8.
+    pub fn decode_url_safe(input: &str) -> Result<String, EncodingError> {
+        let bytes = input.as_bytes();
+        let mut output = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            let escaped = match bytes.get(i..i + 3) {
+                Some([b'%', high, low]) => hex_digit(*high).zip(hex_digit(*low)),
+                _ => None,
+            };
+            match escaped {
+                Some((high, low)) => {
+                    output.push((high << 4) | low);
+                    i += 3;
+                }
+                None => {
+                    output.push(bytes[i]);
+                    i += 1;
+                }
+            }
+        }
+        String::from_utf8(output).map_err(|e| EncodingError::DecodingError(e.to_string()))
+    }
+}
+
+fn hex_digit(byte: u8) -> Option<u8> {
+    (byte as char).to_digit(16).map(|d| d as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base32_round_trip() {
+        // RFC 4648 test vectors
+        assert_eq!(EncodingUtils::encode_base32(b"f"), "MY");
+        assert_eq!(EncodingUtils::encode_base32(b"foobar"), "MZXW6YTBOI");
+        assert_eq!(EncodingUtils::decode_base32("mzxw 6ytb oi======").unwrap(), b"foobar");
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        let encoded = EncodingUtils::encode_base32(&bytes);
+        assert_eq!(EncodingUtils::decode_base32(&encoded).unwrap(), bytes);
+        assert!(EncodingUtils::decode_base32("MZ1").is_err());
+    }
+
+    #[test]
+    fn test_url_encoding_round_trip() {
+        assert_eq!(EncodingUtils::encode_url_safe("café"), "caf%C3%A9");
+        assert_eq!(EncodingUtils::encode_url_safe("a b&c=d/e"), "a%20b%26c%3Dd%2Fe");
+        let url = "https://example.com/über?q=a b&x=ü#top";
+        assert_eq!(
+            EncodingUtils::encode_url(url, UrlEncodeMode::FullUrl),
+            "https://example.com/%C3%BCber?q=a%20b&x=%C3%BC#top"
+        );
+
+        for input in ["café", "日本語 / テスト", "emoji 🚀 & more", "100%", "-._~", ""] {
+            for mode in [UrlEncodeMode::Component, UrlEncodeMode::FullUrl] {
+                let encoded = EncodingUtils::encode_url(input, mode);
+                assert!(encoded.is_ascii());
+                assert_eq!(EncodingUtils::decode_url_safe(&encoded).unwrap(), input);
             }
         }
 
-        result
+        assert_eq!(EncodingUtils::decode_url_safe("%e6%97%a5").unwrap(), "日");
+        assert_eq!(EncodingUtils::decode_url_safe("50% off %G1 %4").unwrap(), "50% off %G1 %4");
+        assert_eq!(EncodingUtils::decode_url_safe("a+b").unwrap(), "a+b");
+        assert!(EncodingUtils::decode_url_safe("%C3%28").is_err());
     }
 }
@@ -0,0 +1,150 @@
+// src/utils/file_ops/atomic.rs
+// Crash-safe file writes and temporary files that remove themselves. An
+// atomic write goes to a temporary file next to the target, is flushed to
+// disk and then renamed over the target, so a crash or a reader at the
+// wrong moment sees the old file or the new one, never half of it.
+
+use log::warn;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A temporary file or folder, removed with everything in it when the guard
+/// is dropped unless `keep` was called
+#[derive(Debug)]
+pub struct TempFileGuard {
+    path: PathBuf,
+    armed: bool,
+}
+
+impl TempFileGuard {
+    /// Guard `path`, which need not exist yet
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            armed: true,
+        }
+    }
+
+    /// Guard a new, unused path in `dir`, e.g. ".config.toml.4711-3.tmp"
+    /// for `name` "config.toml"
+    pub fn unique_in(dir: &Path, name: &str) -> Self {
+        let count = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+        Self::new(dir.join(format!(".{}.{}-{}.tmp", name, std::process::id(), count)))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Leave the file in place, e.g. once it has been renamed to its final
+    /// name; returns its path
+    pub fn keep(mut self) -> PathBuf {
+        self.armed = false;
+        std::mem::take(&mut self.path)
+    }
+}
+
+impl Drop for TempFileGuard {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        let removed = match fs::symlink_metadata(&self.path) {
+            Ok(meta) if meta.is_dir() => fs::remove_dir_all(&self.path),
+            Ok(_) => fs::remove_file(&self.path),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = removed {
+            warn!("Failed to remove temporary {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Replace `path` with `bytes` atomically. An existing file keeps its
+/// permissions; the temporary file is removed if anything fails.
+pub fn write(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let name = path.file_name().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, "path has no file name")
+    })?;
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let temp = TempFileGuard::unique_in(dir, &name.to_string_lossy());
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(temp.path())?;
+    file.write_all(bytes)?;
+    if let Ok(existing) = fs::metadata(path) {
+        file.set_permissions(existing.permissions())?;
+    }
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(temp.path(), path)?;
+    temp.keep();
+    sync_dir(dir);
+    Ok(())
+}
+
+/// Flush the rename itself to disk; folders can't be opened for that on
+/// Windows, where the rename is durable once it returns
+fn sync_dir(dir: &Path) {
+    #[cfg(unix)]
+    if let Err(e) = fs::File::open(dir).and_then(|d| d.sync_all()) {
+        warn!("Failed to sync {}: {}", dir.display(), e);
+    }
+    #[cfg(not(unix))]
+    let _ = dir;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_replaces_and_leaves_no_temp_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.toml");
+        write(&path, b"first").unwrap();
+        write(&path, b"second").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"second");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        // The target is a folder: the rename fails and the temp file goes
+        fs::create_dir(dir.path().join("busy")).unwrap();
+        fs::write(dir.path().join("busy").join("x"), "x").unwrap();
+        assert!(write(&dir.path().join("busy"), b"data").is_err());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_guard_removes_unless_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = TempFileGuard::unique_in(dir.path(), "export.csv");
+        fs::write(file.path(), "rows").unwrap();
+        let removed = file.path().to_path_buf();
+        drop(file);
+        assert!(!removed.exists());
+
+        let folder = TempFileGuard::new(dir.path().join("unpacked"));
+        fs::create_dir_all(folder.path().join("nested")).unwrap();
+        fs::write(folder.path().join("nested").join("a.js"), "a").unwrap();
+        drop(folder);
+        assert!(!dir.path().join("unpacked").exists());
+
+        let kept = TempFileGuard::unique_in(dir.path(), "report.pdf");
+        fs::write(kept.path(), "pdf").unwrap();
+        assert!(kept.keep().exists());
+
+        // Never created: dropping is a no-op
+        drop(TempFileGuard::new(dir.path().join("missing")));
+    }
+}
@@ -0,0 +1,207 @@
+// src/utils/file_ops/listing.rs
+// Directory listings for file browsers: entries with their type, size and
+// modification time, optionally recursive and filtered by a glob. Symlinks
+// are listed but not followed, so a recursive listing stays inside the
+// folder it started in. Entries that can't be read are left out.
+
+use log::warn;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    File,
+    Dir,
+    Symlink,
+}
+
+/// One file, folder or symlink in a listing
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DirEntryInfo {
+    pub name: String,
+    /// Full path, to pass back to a later listing
+    pub path: String,
+    /// Path from the listed folder with `/` separators, e.g. "src/main.rs"
+    pub relative_path: String,
+    // ts: 'file' | 'dir' | 'symlink'
+    pub kind: EntryKind,
+    /// Bytes; 0 for folders, see `FileUtils::dir_size`
+    pub size: u64,
+    /// Last modification as Unix seconds, when the platform reports it
+    pub modified: Option<i64>,
+}
+
+/// A glob such as "*.rs", "**/*.json" or "docs/?.md". `*` and `?` stay
+/// within one path segment and `**` spans any number of them. A pattern
+/// without `/` is matched against the entry name, one with `/` against its
+/// path from the listed folder.
+#[derive(Debug, Clone)]
+pub struct Glob {
+    regex: Regex,
+    match_path: bool,
+}
+
+impl Glob {
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        let mut regex = String::from("^");
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        regex.push_str("(?:.*/)?");
+                    } else {
+                        regex.push_str(".*");
+                    }
+                }
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        regex.push('$');
+        Ok(Self {
+            regex: Regex::new(&regex).map_err(|e| e.to_string())?,
+            match_path: pattern.contains('/'),
+        })
+    }
+
+    /// Whether the entry `name` at `relative_path` matches
+    pub fn matches(&self, name: &str, relative_path: &str) -> bool {
+        self.regex.is_match(if self.match_path { relative_path } else { name })
+    }
+}
+
+/// Entries of `dir`, of every folder below it when `recursive`, sorted by
+/// path. The walk stops once `limit` entries are found.
+pub fn list(
+    dir: &Path,
+    recursive: bool,
+    glob: Option<&Glob>,
+    limit: usize,
+) -> Result<Vec<DirEntryInfo>, String> {
+    if !dir.is_dir() {
+        return Err(format!("{} is not a folder", dir.display()));
+    }
+    let walker = WalkDir::new(dir)
+        .min_depth(1)
+        .max_depth(if recursive { usize::MAX } else { 1 })
+        .sort_by_file_name();
+
+    let mut entries = Vec::new();
+    for entry in walker {
+        if entries.len() >= limit {
+            break;
+        }
+        // A folder that can't be read is skipped along with its contents
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                warn!("Skipping unreadable entry in {}: {}", dir.display(), e);
+                continue;
+            }
+        };
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let relative = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+        let relative_path = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if glob.is_some_and(|glob| !glob.matches(&name, &relative_path)) {
+            continue;
+        }
+
+        // symlink_metadata, as walkdir doesn't follow links
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                warn!("Skipping {}: {}", entry.path().display(), e);
+                continue;
+            }
+        };
+        let kind = if metadata.file_type().is_symlink() {
+            EntryKind::Symlink
+        } else if metadata.is_dir() {
+            EntryKind::Dir
+        } else {
+            EntryKind::File
+        };
+        entries.push(DirEntryInfo {
+            name,
+            path: entry.path().display().to_string(),
+            relative_path,
+            kind,
+            size: if kind == EntryKind::File { metadata.len() } else { 0 },
+            modified: metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|age| age.as_secs() as i64),
+        });
+    }
+    Ok(entries)
+}
+
+/// Total bytes of the files in `dir` and every folder below it; symlinks
+/// aren't followed or counted
+pub fn size(dir: &Path) -> Result<u64, String> {
+    let mut total = 0;
+    for entry in WalkDir::new(dir) {
+        let entry = entry.map_err(|e| e.to_string())?;
+        if entry.file_type().is_file() {
+            total += entry.metadata().map_err(|e| e.to_string())?.len();
+        }
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_list_with_globs_and_size() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/nested")).unwrap();
+        fs::write(dir.path().join("README.md"), "readme").unwrap();
+        fs::write(dir.path().join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(dir.path().join("src/nested/lib.rs"), "pub fn x() {}").unwrap();
+        fs::write(dir.path().join("src/nested/data.json"), "{}").unwrap();
+
+        let top = list(dir.path(), false, None, usize::MAX).unwrap();
+        let names: Vec<_> = top.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["README.md", "src"]);
+        assert_eq!(top[0].kind, EntryKind::File);
+        assert_eq!(top[0].size, 6);
+        assert!(top[0].modified.is_some());
+        assert_eq!((top[1].kind, top[1].size), (EntryKind::Dir, 0));
+
+        let rust = Glob::new("*.rs").unwrap();
+        let found: Vec<_> = list(dir.path(), true, Some(&rust), usize::MAX)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.relative_path)
+            .collect();
+        assert_eq!(found, ["src/main.rs", "src/nested/lib.rs"]);
+
+        let nested = Glob::new("src/*/*.json").unwrap();
+        assert_eq!(list(dir.path(), true, Some(&nested), usize::MAX).unwrap().len(), 1);
+        assert_eq!(list(dir.path(), true, None, 3).unwrap().len(), 3);
+        let anywhere = Glob::new("**/lib.?s").unwrap();
+        assert!(anywhere.matches("lib.rs", "lib.rs"));
+        assert!(anywhere.matches("lib.rs", "src/nested/lib.rs"));
+        assert!(!Glob::new("src/*.rs").unwrap().matches("lib.rs", "src/nested/lib.rs"));
+
+        assert_eq!(size(dir.path()).unwrap(), 6 + 12 + 13 + 2);
+        assert!(list(&dir.path().join("README.md"), false, None, usize::MAX).is_err());
+    }
+}
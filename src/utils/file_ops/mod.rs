@@ -1,5 +1,14 @@
 #![allow(dead_code)]
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+pub mod atomic;
+pub mod listing;
+pub mod sandbox;
+pub mod watcher;
+
+pub use atomic::TempFileGuard;
+pub use listing::{DirEntryInfo, Glob};
+pub use sandbox::PathSandbox;
 
 pub struct FileUtils;
 
@@ -12,6 +21,13 @@ impl FileUtils {
         std::fs::write(path, content).map_err(|e| e.to_string())
     }
 
+    /// Replace `path` with `bytes` so that a crash leaves the old file or
+    /// the new one, never a torn mix: write a temporary file beside it,
+    /// fsync, then rename it over `path`
+    pub fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), String> {
+        atomic::write(path, bytes).map_err(|e| e.to_string())
+    }
+
     pub fn file_exists(path: &PathBuf) -> bool {
         path.exists()
     }
@@ -29,4 +45,23 @@ impl FileUtils {
     pub fn delete_file(path: &PathBuf) -> Result<(), String> {
         std::fs::remove_file(path).map_err(|e| e.to_string())
     }
+
+    /// Entries of the folder `path` with their type, size and modification
+    /// time, sorted by path; of every folder below it too when `recursive`.
+    /// `glob` keeps only matching entries, see `Glob`. At most `limit`
+    /// entries are returned; unreadable ones are skipped.
+    pub fn list_dir(
+        path: &Path,
+        recursive: bool,
+        glob: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<DirEntryInfo>, String> {
+        let glob = glob.map(Glob::new).transpose()?;
+        listing::list(path, recursive, glob.as_ref(), limit)
+    }
+
+    /// Total size in bytes of the files below `path`
+    pub fn dir_size(path: &Path) -> Result<u64, String> {
+        listing::size(path)
+    }
 }
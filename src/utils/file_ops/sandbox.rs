@@ -0,0 +1,130 @@
+// src/utils/file_ops/sandbox.rs
+// Confines paths the frontend sends to file handlers to the folders in
+// `[filesystem] allowed_roots`. A path is resolved through its symlinks
+// before the check, so a link inside a root that points outside it is
+// refused, as is any path with a `..` component.
+
+use log::warn;
+use std::path::{Component, Path, PathBuf};
+
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+
+fn not_allowed(path: &Path, reason: &str) -> AppError {
+    AppError::Validation(
+        ErrorValue::new(
+            ErrorCode::PathNotAllowed,
+            "That path is outside the folders the app may use",
+        )
+        .with_details(reason)
+        .with_field("path")
+        .with_context("path", path.display().to_string()),
+    )
+}
+
+/// The folders file handlers may touch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathSandbox {
+    roots: Vec<PathBuf>,
+}
+
+impl PathSandbox {
+    /// A sandbox of `roots`; roots that don't exist are left out
+    pub fn new(roots: impl IntoIterator<Item = PathBuf>) -> Self {
+        let roots = roots
+            .into_iter()
+            .filter_map(|root| match root.canonicalize() {
+                Ok(root) => Some(root),
+                Err(e) => {
+                    warn!("Ignoring allowed root {}: {}", root.display(), e);
+                    None
+                }
+            })
+            .collect();
+        Self { roots }
+    }
+
+    /// The allowed roots, resolved
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// `path` resolved to where it really is, if that is inside an allowed
+    /// root. Relative paths are taken from the first root. The path need
+    /// not exist yet, so it can name a file about to be written.
+    pub fn resolve(&self, path: impl AsRef<Path>) -> AppResult<PathBuf> {
+        let path = path.as_ref();
+        if path.components().any(|c| c == Component::ParentDir) {
+            return Err(not_allowed(path, "'..' isn't allowed in paths"));
+        }
+        let joined = match (path.is_absolute(), self.roots.first()) {
+            (true, _) => path.to_path_buf(),
+            (false, Some(root)) => root.join(path),
+            (false, None) => return Err(not_allowed(path, "no folders are allowed")),
+        };
+        let resolved = resolve_existing(&joined).map_err(|e| not_allowed(path, &e))?;
+        if self.roots.iter().any(|root| resolved.starts_with(root)) {
+            Ok(resolved)
+        } else {
+            Err(not_allowed(path, "the path is outside every allowed folder"))
+        }
+    }
+}
+
+/// Canonicalize the longest part of `path` that exists and append the rest,
+/// which can't contain symlinks because it doesn't exist
+fn resolve_existing(path: &Path) -> Result<PathBuf, String> {
+    let mut existing = path;
+    let mut missing = Vec::new();
+    loop {
+        match existing.canonicalize() {
+            Ok(resolved) => {
+                return Ok(missing.into_iter().rev().fold(resolved, |acc, part| acc.join(part)));
+            }
+            Err(e) => match (existing.parent(), existing.file_name()) {
+                (Some(parent), Some(name)) => {
+                    missing.push(name.to_owned());
+                    existing = parent;
+                }
+                _ => return Err(e.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn code(result: AppResult<PathBuf>) -> ErrorCode {
+        result.unwrap_err().to_value().code.clone()
+    }
+
+    #[test]
+    fn test_resolve_inside_roots_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("docs");
+        let outside = dir.path().join("private");
+        fs::create_dir_all(root.join("notes")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(outside.join("secret.txt"), "s").unwrap();
+        let sandbox = PathSandbox::new([root.clone(), dir.path().join("missing")]);
+        let root = root.canonicalize().unwrap();
+        assert_eq!(sandbox.roots(), std::slice::from_ref(&root));
+
+        assert_eq!(sandbox.resolve("notes").unwrap(), root.join("notes"));
+        assert_eq!(sandbox.resolve("notes/new.txt").unwrap(), root.join("notes/new.txt"));
+        assert_eq!(sandbox.resolve(root.join("a/b.txt")).unwrap(), root.join("a/b.txt"));
+
+        assert_eq!(code(sandbox.resolve("notes/../../private")), ErrorCode::PathNotAllowed);
+        assert_eq!(code(sandbox.resolve(outside.join("secret.txt"))), ErrorCode::PathNotAllowed);
+        assert_eq!(code(PathSandbox::default().resolve("notes")), ErrorCode::PathNotAllowed);
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&outside, root.join("escape")).unwrap();
+            let escape = sandbox.resolve("escape/secret.txt");
+            assert_eq!(code(escape), ErrorCode::PathNotAllowed);
+        }
+    }
+}
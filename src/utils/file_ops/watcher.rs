@@ -0,0 +1,224 @@
+// src/utils/file_ops/watcher.rs
+// Watch a file or directory and publish what changed as `FileChanged` on the
+// event bus. Bursts of events (an editor's truncate, write and rename, a
+// build writing many files) are collected for a debounce window and
+// published together, one change per path.
+
+use log::{info, warn};
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use crate::core::domain::events::{FileChange, FileChangeKind, FileChanged};
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::core::infrastructure::error_handler;
+use crate::core::infrastructure::event_bus::GLOBAL_EVENT_BUS;
+
+/// Quiet time after the last file event before a batch is published
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// A running watch; dropping it stops watching, after publishing what is
+/// still pending
+pub struct FileWatcher {
+    path: PathBuf,
+    recursive: bool,
+    /// Dropping it closes the event channel, which ends the debounce thread
+    _watcher: RecommendedWatcher,
+}
+
+impl FileWatcher {
+    /// Watch `path`, and everything below it when `recursive`, publishing
+    /// `FileChanged` events on `GLOBAL_EVENT_BUS`
+    pub fn watch(path: impl AsRef<Path>, recursive: bool) -> AppResult<Self> {
+        Self::watch_debounced(path, recursive, DEFAULT_DEBOUNCE)
+    }
+
+    /// `watch` with a debounce window other than `DEFAULT_DEBOUNCE`
+    pub fn watch_debounced(
+        path: impl AsRef<Path>,
+        recursive: bool,
+        debounce: Duration,
+    ) -> AppResult<Self> {
+        let path = path.as_ref();
+        let path = path.canonicalize().map_err(|e| {
+            AppError::NotFound(
+                ErrorValue::new(ErrorCode::ResourceNotFound, "Path to watch doesn't exist")
+                    .with_cause(e.to_string())
+                    .with_context("path", path.display().to_string()),
+            )
+        })?;
+
+        let (changes, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+            Ok(event) => {
+                for change in classify(&event) {
+                    let _ = changes.send(change);
+                }
+            }
+            Err(e) => warn!("File watcher error: {}", e),
+        })
+        .map_err(|e| watch_error(&path, e))?;
+        let mode = if recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        watcher.watch(&path, mode).map_err(|e| watch_error(&path, e))?;
+
+        let root = path.display().to_string();
+        std::thread::Builder::new()
+            .name("file-watcher".to_string())
+            .spawn(move || {
+                while let Ok((changed, kind)) = rx.recv() {
+                    let mut pending = BTreeMap::new();
+                    merge(&mut pending, changed, kind);
+                    while let Ok((changed, kind)) = rx.recv_timeout(debounce) {
+                        merge(&mut pending, changed, kind);
+                    }
+                    publish(&root, pending);
+                }
+            })?;
+
+        info!("Watching {} for changes", path.display());
+        Ok(Self {
+            path,
+            recursive,
+            _watcher: watcher,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn is_recursive(&self) -> bool {
+        self.recursive
+    }
+}
+
+fn watch_error(path: &Path, cause: impl ToString) -> AppError {
+    AppError::EventBus(
+        ErrorValue::new(ErrorCode::ServiceUnavailable, "Failed to watch path")
+            .with_cause(cause.to_string())
+            .with_context("path", path.display().to_string()),
+    )
+}
+
+/// The changes a notify event stands for; access events are ignored
+fn classify(event: &Event) -> Vec<(PathBuf, FileChangeKind)> {
+    let all = |kind: FileChangeKind| -> Vec<(PathBuf, FileChangeKind)> {
+        event.paths.iter().map(|p| (p.clone(), kind)).collect()
+    };
+    match event.kind {
+        EventKind::Access(_) => Vec::new(),
+        EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+            all(FileChangeKind::Created)
+        }
+        EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+            all(FileChangeKind::Removed)
+        }
+        // paths are [from, to]
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => match event.paths.as_slice() {
+            [from, to] => vec![
+                (from.clone(), FileChangeKind::Removed),
+                (to.clone(), FileChangeKind::Created),
+            ],
+            _ => all(FileChangeKind::Modified),
+        },
+        _ => all(FileChangeKind::Modified),
+    }
+}
+
+/// Fold `kind` into what is already pending for `path`: a file created and
+/// then written is still new, one created and removed again within the
+/// window never existed, and one removed and created again was replaced
+fn merge(pending: &mut BTreeMap<PathBuf, FileChangeKind>, path: PathBuf, kind: FileChangeKind) {
+    use FileChangeKind::{Created, Modified, Removed};
+
+    let merged = match (pending.get(&path).copied(), kind) {
+        (Some(Created), Modified) => Created,
+        (Some(Created), Removed) => {
+            pending.remove(&path);
+            return;
+        }
+        (Some(Removed), Created) => Modified,
+        (_, kind) => kind,
+    };
+    pending.insert(path, merged);
+}
+
+fn publish(root: &str, pending: BTreeMap<PathBuf, FileChangeKind>) {
+    if pending.is_empty() {
+        return;
+    }
+    let event = FileChanged {
+        path: root.to_string(),
+        changes: pending
+            .into_iter()
+            .map(|(path, kind)| FileChange {
+                path: path.display().to_string(),
+                kind,
+            })
+            .collect(),
+    };
+    if let Err(e) = GLOBAL_EVENT_BUS.publish_typed(&event) {
+        error_handler::record_app_error("FILE_WATCHER", &e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    #[test]
+    fn test_merge_collapses_bursts() {
+        use FileChangeKind::{Created, Modified, Removed};
+
+        let mut pending = BTreeMap::new();
+        let (a, b, c) = (PathBuf::from("a"), PathBuf::from("b"), PathBuf::from("c"));
+        merge(&mut pending, a.clone(), Created);
+        merge(&mut pending, a.clone(), Modified);
+        merge(&mut pending, b.clone(), Created);
+        merge(&mut pending, b.clone(), Removed);
+        merge(&mut pending, c.clone(), Removed);
+        merge(&mut pending, c.clone(), Created);
+        assert_eq!(pending.get(&a), Some(&Created));
+        assert_eq!(pending.get(&b), None);
+        assert_eq!(pending.get(&c), Some(&Modified));
+    }
+
+    #[test]
+    fn test_changes_reach_event_bus() {
+        let dir = tempfile::tempdir().unwrap();
+        let watcher = FileWatcher::watch_debounced(dir.path(), true, Duration::from_millis(50))
+            .expect("Failed to watch temp dir");
+        let root = watcher.path().display().to_string();
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let _subscription = GLOBAL_EVENT_BUS.subscribe_typed(move |event: FileChanged| {
+            if event.path == root {
+                sink.lock().unwrap().extend(event.changes);
+            }
+        });
+
+        fs::write(dir.path().join("asset.js"), "export {}").unwrap();
+        let expected = watcher.path().join("asset.js").display().to_string();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            GLOBAL_EVENT_BUS.flush();
+            if seen.lock().unwrap().iter().any(|change| change.path == expected) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        panic!("No change published for {}", expected);
+    }
+}
@@ -1,4 +1,25 @@
 #![allow(dead_code)]
+pub mod proxy;
+
+pub use proxy::SystemProxy;
+
+use rand::Rng;
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::core::infrastructure::http_client;
+
+/// Most connections `scan_ports` has open at once
+const MAX_SCAN_THREADS: usize = 32;
+
+/// Services that answer with the caller's address as plain text, tried in
+/// order by `public_ip`
+const PUBLIC_IP_SERVICES: [&str; 2] = ["https://api.ipify.org", "https://icanhazip.com"];
+const PUBLIC_IP_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub struct NetworkUtils;
 
 impl NetworkUtils {
@@ -11,7 +32,182 @@ impl NetworkUtils {
     }
 
     pub fn is_port_available(port: u16) -> bool {
-        use std::net::TcpListener;
         TcpListener::bind(("127.0.0.1", port)).is_ok()
     }
+
+    /// A port in `range` nothing listens on locally. The search starts at a
+    /// random port and wraps around, so two instances starting together
+    /// rarely pick the same one. The port can still be taken before the
+    /// caller binds it; callers retry with another.
+    pub fn find_free_port(range: RangeInclusive<u16>) -> Option<u16> {
+        let (start, end) = (*range.start(), *range.end());
+        if start > end {
+            return None;
+        }
+        let len = u32::from(end - start) + 1;
+        let offset = rand::thread_rng().gen_range(0..len);
+        (0..len)
+            .map(|i| start + ((offset + i) % len) as u16)
+            .find(|&port| port != 0 && Self::is_port_available(port))
+    }
+
+    /// Ports in `range` on `host` that accept a TCP connection within
+    /// `timeout`, in order. At most 32 connections are tried at once.
+    pub fn scan_ports(
+        host: &str,
+        range: RangeInclusive<u16>,
+        timeout: Duration,
+    ) -> Result<Vec<u16>, String> {
+        let ip = (host, 0)
+            .to_socket_addrs()
+            .map_err(|e| e.to_string())?
+            .next()
+            .map(|addr| addr.ip())
+            .ok_or_else(|| format!("{} has no address", host))?;
+        let ports: Vec<u16> = range.collect();
+        let next = AtomicUsize::new(0);
+        let open = Mutex::new(Vec::new());
+
+        std::thread::scope(|scope| {
+            for _ in 0..ports.len().min(MAX_SCAN_THREADS) {
+                scope.spawn(|| {
+                    while let Some(&port) = ports.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        if Self::accepts(ip, port, timeout) {
+                            open.lock().unwrap_or_else(|e| e.into_inner()).push(port);
+                        }
+                    }
+                });
+            }
+        });
+
+        let mut open = open.into_inner().unwrap_or_else(|e| e.into_inner());
+        open.sort_unstable();
+        Ok(open)
+    }
+
+    /// Split "host:port", "[::1]:port" or "ip:port" into host and port
+    pub fn split_host_port(target: &str) -> Result<(String, u16), String> {
+        let (host, port) = target
+            .rsplit_once(':')
+            .ok_or_else(|| format!("'{}' has no port", target))?;
+        let port = port
+            .parse::<u16>()
+            .map_err(|_| format!("'{}' has an invalid port", target))?;
+        let host = host.strip_prefix('[').and_then(|h| h.strip_suffix(']')).unwrap_or(host);
+        if host.is_empty() || port == 0 {
+            return Err(format!("'{}' is not a host:port pair", target));
+        }
+        Ok((host.to_string(), port))
+    }
+
+    /// Addresses `host` resolves to, without duplicates, in the resolver's
+    /// order; an IP address resolves to itself
+    pub fn resolve(host: &str) -> Result<Vec<IpAddr>, String> {
+        let mut ips: Vec<IpAddr> = Vec::new();
+        for addr in (host, 0).to_socket_addrs().map_err(|e| e.to_string())? {
+            if !ips.contains(&addr.ip()) {
+                ips.push(addr.ip());
+            }
+        }
+        if ips.is_empty() {
+            return Err(format!("{} has no address", host));
+        }
+        Ok(ips)
+    }
+
+    /// Whether any address of `host` accepts a TCP connection on `port`
+    /// within `timeout`
+    pub fn is_reachable(host: &str, port: u16, timeout: Duration) -> bool {
+        Self::connect_time(host, port, timeout).is_ok()
+    }
+
+    /// How long the first address of `host` that accepts a TCP connection
+    /// on `port` took to connect; each address gets `timeout`
+    pub fn connect_time(host: &str, port: u16, timeout: Duration) -> Result<Duration, String> {
+        let mut last_error = String::new();
+        for ip in Self::resolve(host)? {
+            let started = Instant::now();
+            match TcpStream::connect_timeout(&SocketAddr::new(ip, port), timeout) {
+                Ok(_) => return Ok(started.elapsed()),
+                Err(e) => last_error = format!("{}:{}: {}", ip, port, e),
+            }
+        }
+        Err(last_error)
+    }
+
+    /// This machine's address as seen from the internet, asked of a public
+    /// echo service; behind NAT it differs from `get_local_ip`
+    pub fn public_ip() -> Result<IpAddr, String> {
+        let client = http_client::shared().map_err(|e| e.to_string())?;
+        let mut last_error = String::new();
+        for url in PUBLIC_IP_SERVICES {
+            let reply = client
+                .get(url)
+                .timeout(PUBLIC_IP_TIMEOUT)
+                .send()
+                .and_then(reqwest::blocking::Response::error_for_status)
+                .and_then(reqwest::blocking::Response::text);
+            match reply {
+                Ok(body) => match body.trim().parse() {
+                    Ok(ip) => return Ok(ip),
+                    Err(_) => last_error = format!("{} answered {:?}", url, body.trim()),
+                },
+                Err(e) => last_error = format!("{}: {}", url, e),
+            }
+        }
+        Err(last_error)
+    }
+
+    /// The proxy the environment or OS settings name, see `proxy::detect`
+    pub fn detect_proxy() -> Option<SystemProxy> {
+        proxy::detect()
+    }
+
+    fn accepts(ip: IpAddr, port: u16, timeout: Duration) -> bool {
+        TcpStream::connect_timeout(&SocketAddr::new(ip, port), timeout).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_free_port_and_scan() {
+        let port = NetworkUtils::find_free_port(20000..=60000).unwrap();
+        assert!((20000..=60000).contains(&port));
+        let listener = TcpListener::bind(("127.0.0.1", port)).unwrap();
+        assert_eq!(NetworkUtils::find_free_port(port..=port), None);
+
+        let timeout = Duration::from_millis(200);
+        let open = NetworkUtils::scan_ports("127.0.0.1", port..=port, timeout).unwrap();
+        assert_eq!(open, [port]);
+        drop(listener);
+        let range = port.saturating_sub(20)..=port;
+        assert!(!NetworkUtils::scan_ports("localhost", range, timeout).unwrap().contains(&port));
+        assert!(NetworkUtils::scan_ports("no-such-host.invalid", 80..=80, timeout).is_err());
+    }
+
+    #[test]
+    fn test_resolve_and_reachability() {
+        let loopback: IpAddr = "127.0.0.1".parse().unwrap();
+        assert_eq!(
+            NetworkUtils::split_host_port("[::1]:8080").unwrap(),
+            ("::1".to_string(), 8080)
+        );
+        assert!(NetworkUtils::split_host_port("example.com").is_err());
+        assert!(NetworkUtils::split_host_port(":443").is_err());
+        assert_eq!(NetworkUtils::resolve("127.0.0.1").unwrap(), [loopback]);
+        assert!(NetworkUtils::resolve("localhost").unwrap().iter().all(IpAddr::is_loopback));
+        assert!(NetworkUtils::resolve("no-such-host.invalid").is_err());
+
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let timeout = Duration::from_millis(500);
+        assert!(NetworkUtils::is_reachable("127.0.0.1", port, timeout));
+        assert!(NetworkUtils::connect_time("127.0.0.1", port, timeout).unwrap() < timeout);
+        drop(listener);
+        assert!(!NetworkUtils::is_reachable("127.0.0.1", port, timeout));
+        assert!(!NetworkUtils::is_reachable("no-such-host.invalid", port, timeout));
+    }
 }
@@ -0,0 +1,300 @@
+// src/utils/network/proxy.rs
+// The proxy this machine is set up to use: HTTP(S)_PROXY and NO_PROXY from
+// the environment, else the Windows Internet Settings or the macOS network
+// preferences. A proxy auto-config (PAC) script isn't evaluated; the first
+// proxy it names is used for every host outside the bypass list.
+
+use log::warn;
+use regex::Regex;
+use std::collections::HashMap;
+use std::time::Duration;
+
+const PAC_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProxySource {
+    Environment,
+    Windows,
+    MacOs,
+}
+
+/// Proxies found in the environment or OS settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemProxy {
+    /// For http:// URLs, e.g. "http://proxy.corp:8080"
+    pub http: Option<String>,
+    /// For https:// URLs
+    pub https: Option<String>,
+    /// Hosts reached directly, e.g. "localhost", ".corp.example.com"
+    pub no_proxy: Vec<String>,
+    /// Auto-config script the OS points at
+    pub pac_url: Option<String>,
+    pub source: ProxySource,
+}
+
+impl SystemProxy {
+    fn new(source: ProxySource) -> Self {
+        Self {
+            http: None,
+            https: None,
+            no_proxy: Vec::new(),
+            pac_url: None,
+            source,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.http.is_none() && self.https.is_none() && self.pac_url.is_none()
+    }
+}
+
+/// The proxy set in the environment, else in the OS; `None` when connections
+/// go direct. A PAC script is downloaded to find its proxy.
+pub fn detect() -> Option<SystemProxy> {
+    let mut proxy = from_env(|name| std::env::var(name).ok()).or_else(from_os)?;
+    if proxy.http.is_none() && proxy.https.is_none() {
+        if let Some(found) = proxy.pac_url.as_deref().and_then(fetch_pac_proxy) {
+            proxy.http = Some(found.clone());
+            proxy.https = Some(found);
+        }
+    }
+    Some(proxy)
+}
+
+/// Proxies from `https_proxy`, `http_proxy`, `all_proxy` and `no_proxy`,
+/// lowercase or uppercase, as curl reads them
+fn from_env(var: impl Fn(&str) -> Option<String>) -> Option<SystemProxy> {
+    let get = |name: &str| {
+        var(name)
+            .or_else(|| var(&name.to_uppercase()))
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    let all = get("all_proxy");
+    let mut proxy = SystemProxy::new(ProxySource::Environment);
+    proxy.http = get("http_proxy").or_else(|| all.clone());
+    proxy.https = get("https_proxy").or(all);
+    proxy.no_proxy = get("no_proxy").map(|list| split_list(&list, ',')).unwrap_or_default();
+    (!proxy.is_empty()).then_some(proxy)
+}
+
+#[cfg(windows)]
+fn from_os() -> Option<SystemProxy> {
+    let output = std::process::Command::new("reg")
+        .args([
+            "query",
+            r"HKCU\Software\Microsoft\Windows\CurrentVersion\Internet Settings",
+        ])
+        .output()
+        .ok()?;
+    from_windows_settings(&parse_reg_query(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(target_os = "macos")]
+fn from_os() -> Option<SystemProxy> {
+    let output = std::process::Command::new("scutil").arg("--proxy").output().ok()?;
+    from_scutil(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Linux desktops export their proxy settings to the environment
+#[cfg(not(any(windows, target_os = "macos")))]
+fn from_os() -> Option<SystemProxy> {
+    None
+}
+
+/// Values of `reg query` output lines such as
+/// "    ProxyServer    REG_SZ    proxy.corp:8080"
+fn parse_reg_query(output: &str) -> HashMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?;
+            parts.next().filter(|kind| kind.starts_with("REG_"))?;
+            Some((name.to_string(), parts.collect::<Vec<_>>().join(" ")))
+        })
+        .collect()
+}
+
+/// ProxyServer is "host:port" for every scheme or
+/// "http=host:port;https=host:port"; ProxyOverride is a ';' list where
+/// "<local>" means hosts without a dot
+fn from_windows_settings(values: &HashMap<String, String>) -> Option<SystemProxy> {
+    let mut proxy = SystemProxy::new(ProxySource::Windows);
+    proxy.pac_url = values.get("AutoConfigURL").cloned();
+    if values.get("ProxyEnable").is_some_and(|v| v != "0x0") {
+        if let Some(server) = values.get("ProxyServer") {
+            if server.contains('=') {
+                for entry in split_list(server, ';') {
+                    match entry.split_once('=') {
+                        Some(("http", addr)) => proxy.http = Some(with_scheme(addr)),
+                        Some(("https", addr)) => proxy.https = Some(with_scheme(addr)),
+                        _ => {}
+                    }
+                }
+            } else {
+                proxy.http = Some(with_scheme(server));
+                proxy.https = proxy.http.clone();
+            }
+        }
+        proxy.no_proxy = values
+            .get("ProxyOverride")
+            .map(|list| {
+                split_list(list, ';')
+                    .into_iter()
+                    .map(|host| match host.as_str() {
+                        "<local>" => String::from("localhost"),
+                        host => host.trim_start_matches('*').to_string(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+    (!proxy.is_empty()).then_some(proxy)
+}
+
+/// `scutil --proxy` prints "Key : value" lines, with ExceptionsList as an
+/// array of "index : host" lines
+fn from_scutil(output: &str) -> Option<SystemProxy> {
+    let mut values = HashMap::new();
+    let mut exceptions = Vec::new();
+    let mut in_exceptions = false;
+    for line in output.lines().map(str::trim) {
+        if line == "}" {
+            in_exceptions = false;
+            continue;
+        }
+        let Some((key, value)) = line.split_once(" : ") else {
+            continue;
+        };
+        if in_exceptions {
+            exceptions.push(value.trim_start_matches('*').to_string());
+        } else if key == "ExceptionsList" {
+            in_exceptions = true;
+        } else {
+            values.insert(key, value);
+        }
+    }
+    let enabled = |key: &str| values.get(key) == Some(&"1");
+    let server = |host: &str, port: &str| {
+        let host = values.get(host)?;
+        Some(match values.get(port) {
+            Some(port) => format!("http://{}:{}", host, port),
+            None => with_scheme(host),
+        })
+    };
+
+    let mut proxy = SystemProxy::new(ProxySource::MacOs);
+    if enabled("HTTPEnable") {
+        proxy.http = server("HTTPProxy", "HTTPPort");
+    }
+    if enabled("HTTPSEnable") {
+        proxy.https = server("HTTPSProxy", "HTTPSPort");
+    }
+    if enabled("ProxyAutoConfigEnable") {
+        proxy.pac_url = values.get("ProxyAutoConfigURLString").map(|url| url.to_string());
+    }
+    proxy.no_proxy = exceptions;
+    (!proxy.is_empty()).then_some(proxy)
+}
+
+fn fetch_pac_proxy(url: &str) -> Option<String> {
+    let script = reqwest::blocking::Client::builder()
+        .no_proxy()
+        .timeout(PAC_TIMEOUT)
+        .build()
+        .ok()?
+        .get(url)
+        .send()
+        .and_then(reqwest::blocking::Response::error_for_status)
+        .and_then(reqwest::blocking::Response::text);
+    match script {
+        Ok(script) => pac_proxy(&script),
+        Err(e) => {
+            warn!("Failed to download proxy auto-config {}: {}", url, e);
+            None
+        }
+    }
+}
+
+/// The first proxy a PAC script returns, e.g. "http://proxy.corp:8080" for
+/// `return "PROXY proxy.corp:8080; DIRECT";`
+fn pac_proxy(script: &str) -> Option<String> {
+    let directive = Regex::new(r"\b(PROXY|HTTPS)\s+([A-Za-z0-9.\-\[\]:]+)").ok()?;
+    let found = directive.captures(script)?;
+    let scheme = if &found[1] == "HTTPS" { "https" } else { "http" };
+    Some(format!("{}://{}", scheme, &found[2]))
+}
+
+fn with_scheme(addr: &str) -> String {
+    if addr.contains("://") {
+        addr.to_string()
+    } else {
+        format!("http://{}", addr)
+    }
+}
+
+fn split_list(list: &str, separator: char) -> Vec<String> {
+    list.split(separator)
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_environment_proxy() {
+        let env = HashMap::from([
+            ("HTTPS_PROXY", "http://proxy.corp:3128"),
+            ("http_proxy", "http://plain.corp:80"),
+            ("NO_PROXY", "localhost, .corp.example.com,"),
+        ]);
+        let proxy = from_env(|name| env.get(name).map(|v| v.to_string())).unwrap();
+        assert_eq!(proxy.https.as_deref(), Some("http://proxy.corp:3128"));
+        assert_eq!(proxy.http.as_deref(), Some("http://plain.corp:80"));
+        assert_eq!(proxy.no_proxy, ["localhost", ".corp.example.com"]);
+
+        let all = HashMap::from([("ALL_PROXY", "socks.corp:1080")]);
+        let proxy = from_env(|name| all.get(name).map(|v| v.to_string())).unwrap();
+        assert_eq!(proxy.http, proxy.https);
+        assert!(from_env(|_| Some(String::new())).is_none());
+    }
+
+    #[test]
+    fn test_os_settings() {
+        let reg = "\r\nHKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\CurrentVersion\\Internet \
+                   Settings\r\n    ProxyEnable    REG_DWORD    0x1\r\n    ProxyServer    REG_SZ    \
+                   http=proxy.corp:80;https=secure.corp:443\r\n    ProxyOverride    REG_SZ    \
+                   *.corp.example.com;<local>\r\n";
+        let proxy = from_windows_settings(&parse_reg_query(reg)).unwrap();
+        assert_eq!(proxy.http.as_deref(), Some("http://proxy.corp:80"));
+        assert_eq!(proxy.https.as_deref(), Some("http://secure.corp:443"));
+        assert_eq!(proxy.no_proxy, [".corp.example.com", "localhost"]);
+        let disabled = parse_reg_query("    ProxyEnable    REG_DWORD    0x0\n");
+        assert!(from_windows_settings(&disabled).is_none());
+
+        let scutil = "<dictionary> {\n  ExceptionsList : <array> {\n    0 : *.local\n    \
+                      1 : 169.254/16\n  }\n  HTTPEnable : 1\n  HTTPPort : 8080\n  \
+                      HTTPProxy : proxy.corp\n  HTTPSEnable : 0\n  ProxyAutoConfigEnable : 1\n  \
+                      ProxyAutoConfigURLString : http://wpad/wpad.dat\n}\n";
+        let proxy = from_scutil(scutil).unwrap();
+        assert_eq!(proxy.http.as_deref(), Some("http://proxy.corp:8080"));
+        assert_eq!(proxy.https, None);
+        assert_eq!(proxy.pac_url.as_deref(), Some("http://wpad/wpad.dat"));
+        assert_eq!(proxy.no_proxy, [".local", "169.254/16"]);
+    }
+
+    #[test]
+    fn test_pac_proxy() {
+        let script = r#"function FindProxyForURL(url, host) {
+            if (isPlainHostName(host)) return "DIRECT";
+            return "PROXY proxy.corp:8080; PROXY backup.corp:8080; DIRECT";
+        }"#;
+        assert_eq!(pac_proxy(script).as_deref(), Some("http://proxy.corp:8080"));
+        assert_eq!(pac_proxy(r#"return "DIRECT";"#), None);
+    }
+}
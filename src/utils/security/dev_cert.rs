@@ -0,0 +1,177 @@
+// src/utils/security/dev_cert.rs
+// Certificates for serving the HTTP and WebSocket transports over TLS during
+// development: a local CA, made once and trusted by the developer, and a
+// leaf certificate it signs for localhost. Replaces the usual openssl steps.
+
+use chrono::{Datelike, Duration, Utc};
+use rcgen::{
+    BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa, KeyPair,
+    KeyUsagePurpose,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::secret_file::write_private;
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+
+/// Names the leaf certificate is valid for unless configured otherwise
+pub const DEFAULT_HOSTS: [&str; 3] = ["localhost", "127.0.0.1", "::1"];
+/// Leaf lifetime; browsers refuse longer ones even from a trusted local CA
+const LEAF_DAYS: i64 = 825;
+const CA_DAYS: i64 = 3650;
+
+fn cert_error(message: &str, path: &Path, cause: impl ToString) -> AppError {
+    AppError::Configuration(
+        ErrorValue::new(ErrorCode::ConfigInvalid, message)
+            .with_cause(cause.to_string())
+            .with_context("path", path.display().to_string()),
+    )
+}
+
+/// Year, month and day `days` from today, for `rcgen::date_time_ymd`
+fn date_after(days: i64) -> (i32, u8, u8) {
+    let date = Utc::now() + Duration::days(days);
+    (date.year(), date.month() as u8, date.day() as u8)
+}
+
+/// Where the development CA and leaf certificate are kept, as PEM files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DevCertificates {
+    pub ca_cert: PathBuf,
+    pub ca_key: PathBuf,
+    pub cert: PathBuf,
+    pub key: PathBuf,
+    /// Made by this call rather than found on disk
+    pub created: bool,
+}
+
+impl DevCertificates {
+    fn paths(dir: &Path) -> Self {
+        Self {
+            ca_cert: dir.join("dev-ca.pem"),
+            ca_key: dir.join("dev-ca-key.pem"),
+            cert: dir.join("localhost.pem"),
+            key: dir.join("localhost-key.pem"),
+            created: false,
+        }
+    }
+
+    /// The certificates in `dir`, made for `hosts` when any file is missing.
+    /// A new CA has to be trusted again, see `trust_instructions`.
+    pub fn ensure(dir: &Path, hosts: &[String]) -> AppResult<Self> {
+        let mut certs = Self::paths(dir);
+        let files = [&certs.ca_cert, &certs.ca_key, &certs.cert, &certs.key];
+        if files.iter().all(|path| path.is_file()) {
+            return Ok(certs);
+        }
+        certs.generate(dir, hosts)?;
+        certs.created = true;
+        Ok(certs)
+    }
+
+    fn generate(&self, dir: &Path, hosts: &[String]) -> AppResult<()> {
+        let rcgen_error = |message: &str| {
+            let message = message.to_string();
+            move |e: rcgen::Error| cert_error(&message, dir, e)
+        };
+        let hosts = if hosts.is_empty() {
+            DEFAULT_HOSTS.iter().map(|host| host.to_string()).collect()
+        } else {
+            hosts.to_vec()
+        };
+
+        let mut ca = CertificateParams::default();
+        ca.distinguished_name.push(DnType::CommonName, "Development CA (do not share)");
+        ca.is_ca = IsCa::Ca(BasicConstraints::Constrained(0));
+        ca.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+        let (year, month, day) = date_after(CA_DAYS);
+        ca.not_after = rcgen::date_time_ymd(year, month, day);
+        let ca_key = KeyPair::generate().map_err(rcgen_error("Failed to make CA key"))?;
+        let ca_cert = ca
+            .self_signed(&ca_key)
+            .map_err(rcgen_error("Failed to make CA certificate"))?;
+
+        let mut leaf = CertificateParams::new(hosts.clone())
+            .map_err(rcgen_error("Invalid certificate host name"))?;
+        leaf.distinguished_name.push(DnType::CommonName, hosts[0].as_str());
+        leaf.key_usages = vec![
+            KeyUsagePurpose::DigitalSignature,
+            KeyUsagePurpose::KeyEncipherment,
+        ];
+        leaf.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+        leaf.use_authority_key_identifier_extension = true;
+        let (year, month, day) = date_after(LEAF_DAYS);
+        leaf.not_after = rcgen::date_time_ymd(year, month, day);
+        let leaf_key = KeyPair::generate().map_err(rcgen_error("Failed to make key"))?;
+        let leaf_cert = leaf
+            .signed_by(&leaf_key, &ca_cert, &ca_key)
+            .map_err(rcgen_error("Failed to sign certificate"))?;
+
+        fs::create_dir_all(dir)
+            .map_err(|e| cert_error("Failed to create certificate directory", dir, e))?;
+        write_private(&self.ca_key, ca_key.serialize_pem().as_bytes())?;
+        write_private(&self.key, leaf_key.serialize_pem().as_bytes())?;
+        fs::write(&self.ca_cert, ca_cert.pem())
+            .map_err(|e| cert_error("Failed to write certificate", &self.ca_cert, e))?;
+        fs::write(&self.cert, leaf_cert.pem())
+            .map_err(|e| cert_error("Failed to write certificate", &self.cert, e))?;
+        Ok(())
+    }
+
+    /// How to make this machine trust the CA, for the current platform
+    pub fn trust_instructions(&self) -> String {
+        let ca = self.ca_cert.display();
+        let steps = if cfg!(target_os = "macos") {
+            format!(
+                "  sudo security add-trusted-cert -d -r trustRoot \\\n    \
+                 -k /Library/Keychains/System.keychain \"{}\"",
+                ca
+            )
+        } else if cfg!(windows) {
+            format!("  certutil -user -addstore Root \"{}\"", ca)
+        } else {
+            [
+                String::from("  Debian/Ubuntu:"),
+                format!("    sudo cp \"{}\" /usr/local/share/ca-certificates/dev-ca.crt", ca),
+                String::from("    sudo update-ca-certificates"),
+                String::from("  Fedora/Arch:"),
+                format!("    sudo trust anchor --store \"{}\"", ca),
+            ]
+            .join("\n")
+        };
+        format!(
+            "Trust the development CA once so browsers accept https://localhost:\n{}\n\
+             Firefox keeps its own list: Settings > Privacy & Security > Certificates > \
+             View Certificates > Authorities > Import \"{}\".\n\
+             Certificate: {}\nKey: {}",
+            steps,
+            ca,
+            self.cert.display(),
+            self.key.display()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ensure_creates_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let certs = DevCertificates::ensure(dir.path(), &[]).unwrap();
+        assert!(certs.created);
+        let pem = fs::read_to_string(&certs.cert).unwrap();
+        assert!(pem.starts_with("-----BEGIN CERTIFICATE-----"));
+        let key = fs::read_to_string(&certs.key).unwrap();
+        assert!(key.contains("PRIVATE KEY"));
+
+        let again = DevCertificates::ensure(dir.path(), &[]).unwrap();
+        assert!(!again.created);
+        assert_eq!(fs::read_to_string(&again.cert).unwrap(), pem);
+        assert!(again.trust_instructions().contains("dev-ca.pem"));
+
+        fs::remove_file(&certs.cert).unwrap();
+        assert!(DevCertificates::ensure(dir.path(), &[]).unwrap().created);
+    }
+}
@@ -1,16 +1,48 @@
 #![allow(unexpected_cfgs, dead_code)]
 
+pub mod dev_cert;
+pub mod secret_file;
+pub mod secret_string;
+pub mod totp;
+
+pub use dev_cert::DevCertificates;
+pub use secret_file::EncryptedFileStore;
+pub use secret_string::SecretString;
+pub use totp::Totp;
+
 pub struct SecurityUtils;
 
 impl SecurityUtils {
+    /// Whether the process runs with administrator rights: effective uid 0
+    /// on Unix, an elevated token on Windows. An administrator account
+    /// running without UAC elevation is not.
     pub fn check_admin() -> bool {
-        // Simplified check - in real apps this would be platform-specific
-        match whoami::username() {
-            Ok(username) => username == "root" || username == "Administrator",
-            Err(_) => false,
+        #[cfg(unix)]
+        {
+            // SAFETY: geteuid has no preconditions and can't fail
+            unsafe { libc::geteuid() == 0 }
+        }
+        #[cfg(windows)]
+        {
+            windows_token_elevated()
+        }
+        #[cfg(not(any(unix, windows)))]
+        {
+            false
         }
     }
 
+    /// Whether `a` and `b` are equal, taking the same time wherever they
+    /// differ; use it for tokens, MACs and key hashes. Only the length can
+    /// leak, and it returns early when the lengths differ.
+    pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+        if a.len() != b.len() {
+            return false;
+        }
+        let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+        std::hint::black_box(diff) == 0
+    }
+
     pub fn encrypt_bytes(data: &[u8], key: &str) -> Result<Vec<u8>, String> {
         // Simple XOR encryption for demo purposes
         let mut result = Vec::with_capacity(data.len());
@@ -27,6 +59,34 @@ impl SecurityUtils {
     }
 }
 
+#[cfg(windows)]
+fn windows_token_elevated() -> bool {
+    use winapi::um::handleapi::CloseHandle;
+    use winapi::um::processthreadsapi::{GetCurrentProcess, OpenProcessToken};
+    use winapi::um::securitybaseapi::GetTokenInformation;
+    use winapi::um::winnt::{TokenElevation, HANDLE, TOKEN_ELEVATION, TOKEN_QUERY};
+
+    // SAFETY: the token handle is only used while open and closed once;
+    // GetTokenInformation writes at most `size_of::<TOKEN_ELEVATION>()` bytes
+    unsafe {
+        let mut token: HANDLE = std::ptr::null_mut();
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0 {
+            return false;
+        }
+        let mut elevation = TOKEN_ELEVATION { TokenIsElevated: 0 };
+        let mut size = 0u32;
+        let ok = GetTokenInformation(
+            token,
+            TokenElevation,
+            &mut elevation as *mut TOKEN_ELEVATION as *mut _,
+            std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+            &mut size,
+        );
+        CloseHandle(token);
+        ok != 0 && elevation.TokenIsElevated != 0
+    }
+}
+
 pub struct ClipboardUtils;
 
 impl ClipboardUtils {
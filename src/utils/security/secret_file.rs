@@ -0,0 +1,200 @@
+// src/utils/security/secret_file.rs
+// Secrets in an AES-256-GCM encrypted file, for machines where the OS
+// keyring is unavailable (headless Linux without a Secret Service, CI). The
+// key is kept next to the file in `<file>.key`, readable only by the user,
+// so the file is safe in backups and bug reports but not from someone who
+// can read the user's files.
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::core::infrastructure::secrets::SecretStore;
+
+const NONCE_LEN: usize = 12;
+
+fn file_error(message: &str, path: &Path, cause: impl ToString) -> AppError {
+    AppError::Configuration(
+        ErrorValue::new(ErrorCode::ConfigInvalid, message)
+            .with_cause(cause.to_string())
+            .with_context("path", path.display().to_string()),
+    )
+}
+
+/// Named secrets in one encrypted file; every change rewrites the file
+pub struct EncryptedFileStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl EncryptedFileStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn key_path(&self) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(".key");
+        PathBuf::from(name)
+    }
+
+    /// The file's key; created on first write. A secrets file without its
+    /// key is an error rather than a reason to start over.
+    fn key(&self, create: bool) -> AppResult<Option<Aes256Gcm>> {
+        let key_path = self.key_path();
+        let bytes = match fs::read(&key_path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                if self.path.exists() {
+                    return Err(file_error("Secrets file has no key file", &key_path, e));
+                }
+                if !create {
+                    return Ok(None);
+                }
+                let bytes = rand::random::<[u8; 32]>().to_vec();
+                write_private(&key_path, &bytes)?;
+                bytes
+            }
+            Err(e) => return Err(file_error("Failed to read secrets key", &key_path, e)),
+        };
+        if bytes.len() != 32 {
+            return Err(file_error(
+                "Secrets key file is damaged",
+                &key_path,
+                format!("expected 32 bytes, found {}", bytes.len()),
+            ));
+        }
+        Ok(Some(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&bytes))))
+    }
+
+    fn load(&self, cipher: &Aes256Gcm) -> AppResult<BTreeMap<String, String>> {
+        let data = match fs::read(&self.path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(e) => return Err(file_error("Failed to read secrets file", &self.path, e)),
+        };
+        if data.len() < NONCE_LEN {
+            return Err(file_error(
+                "Secrets file is damaged",
+                &self.path,
+                "file is shorter than its nonce",
+            ));
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                file_error(
+                    "Secrets file can't be decrypted",
+                    &self.path,
+                    "wrong key or modified file",
+                )
+            })?;
+        serde_json::from_slice(&plaintext)
+            .map_err(|e| file_error("Secrets file is damaged", &self.path, e))
+    }
+
+    fn save(&self, cipher: &Aes256Gcm, secrets: &BTreeMap<String, String>) -> AppResult<()> {
+        let plaintext = serde_json::to_vec(secrets)
+            .map_err(|e| file_error("Failed to encode secrets", &self.path, e))?;
+        let nonce = rand::random::<[u8; NONCE_LEN]>();
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_ref())
+            .map_err(|e| file_error("Failed to encrypt secrets", &self.path, e))?;
+        let mut data = nonce.to_vec();
+        data.extend_from_slice(&ciphertext);
+        write_private(&self.path, &data)
+    }
+
+    fn update(&self, change: impl FnOnce(&mut BTreeMap<String, String>)) -> AppResult<()> {
+        let _guard = self.lock.lock()?;
+        let cipher = self.key(true)?.expect("key is created on write");
+        let mut secrets = self.load(&cipher)?;
+        change(&mut secrets);
+        self.save(&cipher, &secrets)
+    }
+}
+
+/// Replace `path` with `data` in one step, readable only by the user
+pub(crate) fn write_private(path: &Path, data: &[u8]) -> AppResult<()> {
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+    if let Some(dir) = dir {
+        fs::create_dir_all(dir).map_err(|e| file_error("Failed to create directory", dir, e))?;
+    }
+    // Temp files are created with mode 0600 on Unix
+    let mut file = tempfile::NamedTempFile::new_in(dir.unwrap_or_else(|| Path::new(".")))
+        .map_err(|e| file_error("Failed to write secrets", path, e))?;
+    file.write_all(data).map_err(|e| file_error("Failed to write secrets", path, e))?;
+    file.persist(path).map_err(|e| file_error("Failed to write secrets", path, e.error))?;
+    Ok(())
+}
+
+impl SecretStore for EncryptedFileStore {
+    fn get(&self, name: &str) -> AppResult<Option<String>> {
+        let _guard = self.lock.lock()?;
+        match self.key(false)? {
+            Some(cipher) => Ok(self.load(&cipher)?.remove(name)),
+            None => Ok(None),
+        }
+    }
+
+    fn set(&self, name: &str, value: &str) -> AppResult<()> {
+        self.update(|secrets| {
+            secrets.insert(name.to_string(), value.to_string());
+        })
+    }
+
+    fn delete(&self, name: &str) -> AppResult<()> {
+        if !self.path.exists() {
+            return Ok(());
+        }
+        self.update(|secrets| {
+            secrets.remove(name);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secrets_round_trip_encrypted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.enc");
+        let store = EncryptedFileStore::new(&path);
+        assert_eq!(store.get("db_key").unwrap(), None);
+
+        store.set("db_key", "hunter2").unwrap();
+        store.set("api_token", "t0ken").unwrap();
+        store.delete("api_token").unwrap();
+
+        let reopened = EncryptedFileStore::new(&path);
+        assert_eq!(reopened.get("db_key").unwrap().as_deref(), Some("hunter2"));
+        assert_eq!(reopened.get("api_token").unwrap(), None);
+        let raw = fs::read(&path).unwrap();
+        assert!(!String::from_utf8_lossy(&raw).contains("hunter2"));
+
+        let mut tampered = raw.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        fs::write(&path, tampered).unwrap();
+        assert!(reopened.get("db_key").is_err());
+
+        fs::write(&path, raw).unwrap();
+        fs::remove_file(store.key_path()).unwrap();
+        assert!(reopened.get("db_key").is_err());
+        assert!(reopened.set("db_key", "other").is_err());
+    }
+}
@@ -0,0 +1,76 @@
+// src/utils/security/secret_string.rs
+// A string holding a key or token. Its bytes are wiped when it is dropped,
+// and Debug and Display print a placeholder, so a secret doesn't outlive its
+// use in freed memory or end up in a log line by accident.
+
+use std::fmt;
+use zeroize::Zeroize;
+
+use super::SecurityUtils;
+
+#[derive(Clone)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self(secret.into())
+    }
+
+    /// The secret itself; keep what you do with it short-lived
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether `other` is this secret, compared in constant time
+    pub fn matches(&self, other: &str) -> bool {
+        SecurityUtils::ct_eq(self.0.as_bytes(), other.as_bytes())
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self(secret)
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretString(***)")
+    }
+}
+
+impl fmt::Display for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_string_hides_and_compares() {
+        let secret = SecretString::new("s3cr3t-token");
+        assert_eq!(format!("{:?} {}", secret, secret), "SecretString(***) ***");
+        assert_eq!(secret.expose(), "s3cr3t-token");
+        assert!(secret.matches("s3cr3t-token"));
+        assert!(!secret.matches("s3cr3t-tokeN"));
+        assert!(!secret.matches("s3cr3t"));
+        assert!(secret.clone().matches("s3cr3t-token"));
+
+        assert!(SecurityUtils::ct_eq(b"", b""));
+        assert!(!SecurityUtils::ct_eq(b"abc", b"abd"));
+        assert!(!SecurityUtils::ct_eq(b"abc", b"abcd"));
+    }
+}
@@ -4,15 +4,28 @@
 // Supports multiple formats: JSON, MessagePack, CBOR
 
 use base64::{engine::general_purpose::STANDARD, Engine};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::utils::compression::CompressionUtils;
+
+pub mod protobuf;
 
 /// Supported serialization formats
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SerializationFormat {
     Json,
     MessagePack,
     Cbor,
+    /// Compact non-self-describing format for backend-to-backend and
+    /// persistence use; decoding needs the exact Rust type
+    Bincode,
+    /// Protocol Buffers via prost, for the wire types in proto/app.proto
+    Protobuf,
 }
 
 impl fmt::Display for SerializationFormat {
@@ -21,6 +34,8 @@ impl fmt::Display for SerializationFormat {
             SerializationFormat::Json => write!(f, "JSON"),
             SerializationFormat::MessagePack => write!(f, "MessagePack"),
             SerializationFormat::Cbor => write!(f, "CBOR"),
+            SerializationFormat::Bincode => write!(f, "Bincode"),
+            SerializationFormat::Protobuf => write!(f, "Protobuf"),
         }
     }
 }
@@ -32,13 +47,57 @@ impl SerializationFormat {
             SerializationFormat::Json,
             SerializationFormat::MessagePack,
             SerializationFormat::Cbor,
+            SerializationFormat::Bincode,
+            SerializationFormat::Protobuf,
         ]
     }
 
+    /// Whether the payload can be decoded without knowing the Rust type
+    /// (required for generic decoding in the browser)
+    pub fn is_self_describing(&self) -> bool {
+        !matches!(
+            self,
+            SerializationFormat::Bincode | SerializationFormat::Protobuf
+        )
+    }
+
     /// Get the currently selected format
     pub fn selected() -> SerializationFormat {
-        // Default to JSON for web compatibility
-        SerializationFormat::Json
+        // Default to JSON for web compatibility until a handshake completes
+        ACTIVE_FORMAT
+            .lock()
+            .map(|format| *format)
+            .unwrap_or(SerializationFormat::Json)
+    }
+
+    /// Set the format used for backend-frontend communication
+    pub fn set_selected(format: SerializationFormat) {
+        if let Ok(mut active) = ACTIVE_FORMAT.lock() {
+            *active = format;
+        }
+    }
+
+    /// Parse a format from its config/wire name
+    pub fn from_name(name: &str) -> Option<SerializationFormat> {
+        match name.trim().to_lowercase().as_str() {
+            "json" => Some(SerializationFormat::Json),
+            "messagepack" | "msgpack" => Some(SerializationFormat::MessagePack),
+            "cbor" => Some(SerializationFormat::Cbor),
+            "bincode" => Some(SerializationFormat::Bincode),
+            "protobuf" | "proto" => Some(SerializationFormat::Protobuf),
+            _ => None,
+        }
+    }
+
+    /// Get the config/wire name of the format
+    pub fn name(&self) -> &'static str {
+        match self {
+            SerializationFormat::Json => "json",
+            SerializationFormat::MessagePack => "messagepack",
+            SerializationFormat::Cbor => "cbor",
+            SerializationFormat::Bincode => "bincode",
+            SerializationFormat::Protobuf => "protobuf",
+        }
     }
 
     /// Get format description
@@ -47,6 +106,8 @@ impl SerializationFormat {
             SerializationFormat::Json => "Standard JSON - Human readable, universal support",
             SerializationFormat::MessagePack => "Binary format - Smaller size, faster than JSON",
             SerializationFormat::Cbor => "CBOR binary - RFC 7049, efficient for embedded",
+            SerializationFormat::Bincode => "Bincode - fastest, for backend-to-backend and persistence",
+            SerializationFormat::Protobuf => "Protocol Buffers - schema-first, for external services",
         }
     }
 
@@ -71,6 +132,16 @@ impl SerializationFormat {
                 "Self-describing",
                 "Good for embedded systems",
             ],
+            SerializationFormat::Bincode => &[
+                "Fastest encode/decode of all formats",
+                "Smallest payloads for fixed-shape data",
+                "Zero configuration with serde",
+            ],
+            SerializationFormat::Protobuf => &[
+                "Shared .proto schema for any language",
+                "Compact with forward/backward compatible fields",
+                "Well supported by external services",
+            ],
         }
     }
 
@@ -92,12 +163,62 @@ impl SerializationFormat {
                 "Limited browser support",
                 "Smaller ecosystem than JSON",
             ],
+            SerializationFormat::Bincode => &[
+                "Not self-describing (needs the exact schema to decode)",
+                "No standard browser decoder",
+                "Layout changes break stored data",
+            ],
+            SerializationFormat::Protobuf => &[
+                "Only for types defined in proto/app.proto",
+                "Not usable through the generic serde functions",
+                "Needs a generated client on the consuming side",
+            ],
         }
     }
 }
 
+lazy_static::lazy_static! {
+    static ref ACTIVE_FORMAT: Mutex<SerializationFormat> = Mutex::new(SerializationFormat::Json);
+}
+
+/// Pick the best format supported by both sides.
+///
+/// The configured format wins when the frontend can decode it; otherwise the
+/// first mutually supported format is used, falling back to JSON.
+pub fn negotiate_format(
+    preferred: SerializationFormat,
+    client_formats: &[SerializationFormat],
+) -> SerializationFormat {
+    if client_formats.contains(&preferred) {
+        return preferred;
+    }
+
+    SerializationFormat::available_formats()
+        .iter()
+        .copied()
+        .find(|format| client_formats.contains(format))
+        .unwrap_or(SerializationFormat::Json)
+}
+
+/// `negotiate_format` among the formats the browser bridge can decode
+/// without the Rust type; a schema-bound preference falls back to JSON
+pub fn negotiate_browser_format(
+    preferred: SerializationFormat,
+    client_formats: &[SerializationFormat],
+) -> SerializationFormat {
+    let decodable: Vec<SerializationFormat> = client_formats
+        .iter()
+        .copied()
+        .filter(|format| format.is_self_describing())
+        .collect();
+    let preferred = Some(preferred)
+        .filter(|format| format.is_self_describing())
+        .unwrap_or(SerializationFormat::Json);
+    negotiate_format(preferred, &decodable)
+}
+
 /// Serialization statistics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SerializationStats {
     pub format: String,
     pub total_serializations: u64,
@@ -106,6 +227,13 @@ pub struct SerializationStats {
     pub total_bytes_received: u64,
     pub avg_serialization_time_us: f64,
     pub avg_deserialization_time_us: f64,
+    pub p50_serialization_time_us: f64,
+    pub p95_serialization_time_us: f64,
+    pub p99_serialization_time_us: f64,
+    pub p50_deserialization_time_us: f64,
+    pub p95_deserialization_time_us: f64,
+    pub p99_deserialization_time_us: f64,
+    /// Average encoded size relative to JSON (1.0 when JSON has no samples)
     pub compression_ratio: f64,
 }
 
@@ -119,60 +247,328 @@ impl Default for SerializationStats {
             total_bytes_received: 0,
             avg_serialization_time_us: 0.0,
             avg_deserialization_time_us: 0.0,
+            p50_serialization_time_us: 0.0,
+            p95_serialization_time_us: 0.0,
+            p99_serialization_time_us: 0.0,
+            p50_deserialization_time_us: 0.0,
+            p95_deserialization_time_us: 0.0,
+            p99_deserialization_time_us: 0.0,
             compression_ratio: 1.0,
         }
     }
 }
 
-/// Serialize data to the specified format
+/// Timing samples kept per format and direction for percentiles
+const MAX_TIMING_SAMPLES: usize = 1024;
+
+#[derive(Default)]
+struct TimingRecorder {
+    count: u64,
+    bytes: u64,
+    total_us: f64,
+    samples: VecDeque<f64>,
+}
+
+impl TimingRecorder {
+    fn record(&mut self, bytes: usize, elapsed: Duration) {
+        let us = elapsed.as_secs_f64() * 1_000_000.0;
+        self.count += 1;
+        self.bytes += bytes as u64;
+        self.total_us += us;
+        if self.samples.len() == MAX_TIMING_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(us);
+    }
+
+    fn average(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.total_us / self.count as f64
+        }
+    }
+
+    fn percentiles(&self) -> (f64, f64, f64) {
+        let mut sorted: Vec<f64> = self.samples.iter().copied().collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        (
+            percentile(&sorted, 50.0),
+            percentile(&sorted, 95.0),
+            percentile(&sorted, 99.0),
+        )
+    }
+}
+
+/// Nearest-rank percentile of an ascending slice
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[derive(Default)]
+struct FormatRecorder {
+    serialize: TimingRecorder,
+    deserialize: TimingRecorder,
+}
+
+lazy_static::lazy_static! {
+    static ref STATS_RECORDER: Mutex<HashMap<SerializationFormat, FormatRecorder>> =
+        Mutex::new(HashMap::new());
+}
+
+fn record(format: SerializationFormat, bytes: usize, elapsed: Duration, serializing: bool) {
+    if let Ok(mut recorders) = STATS_RECORDER.lock() {
+        let recorder = recorders.entry(format).or_default();
+        if serializing {
+            recorder.serialize.record(bytes, elapsed);
+        } else {
+            recorder.deserialize.record(bytes, elapsed);
+        }
+    }
+}
+
+fn average_size(recorder: &FormatRecorder) -> Option<f64> {
+    let count = recorder.serialize.count + recorder.deserialize.count;
+    let bytes = recorder.serialize.bytes + recorder.deserialize.bytes;
+    (count > 0).then(|| bytes as f64 / count as f64)
+}
+
+/// Measured statistics for one format
+pub fn stats_for(format: SerializationFormat) -> SerializationStats {
+    let Ok(recorders) = STATS_RECORDER.lock() else {
+        return SerializationStats::default();
+    };
+    let Some(recorder) = recorders.get(&format) else {
+        return SerializationStats {
+            format: format.to_string(),
+            ..SerializationStats::default()
+        };
+    };
+
+    let json_size = recorders
+        .get(&SerializationFormat::Json)
+        .and_then(average_size);
+    let compression_ratio = match (average_size(recorder), json_size) {
+        (Some(size), Some(json)) if json > 0.0 => size / json,
+        _ => 1.0,
+    };
+
+    let (ser_p50, ser_p95, ser_p99) = recorder.serialize.percentiles();
+    let (de_p50, de_p95, de_p99) = recorder.deserialize.percentiles();
+
+    SerializationStats {
+        format: format.to_string(),
+        total_serializations: recorder.serialize.count,
+        total_deserializations: recorder.deserialize.count,
+        total_bytes_sent: recorder.serialize.bytes,
+        total_bytes_received: recorder.deserialize.bytes,
+        avg_serialization_time_us: recorder.serialize.average(),
+        avg_deserialization_time_us: recorder.deserialize.average(),
+        p50_serialization_time_us: ser_p50,
+        p95_serialization_time_us: ser_p95,
+        p99_serialization_time_us: ser_p99,
+        p50_deserialization_time_us: de_p50,
+        p95_deserialization_time_us: de_p95,
+        p99_deserialization_time_us: de_p99,
+        compression_ratio,
+    }
+}
+
+/// Measured statistics for every available format
+pub fn all_stats() -> Vec<SerializationStats> {
+    SerializationFormat::available_formats()
+        .iter()
+        .map(|format| stats_for(*format))
+        .collect()
+}
+
+pub fn reset_stats() {
+    if let Ok(mut recorders) = STATS_RECORDER.lock() {
+        recorders.clear();
+    }
+    if let Ok(mut compression) = COMPRESSION_STATS.lock() {
+        compression.clear();
+    }
+}
+
+/// Gzip savings for one handler's response event
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct CompressionStats {
+    pub handler: String,
+    pub compressed_count: u64,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub bytes_saved: u64,
+}
+
+lazy_static::lazy_static! {
+    static ref COMPRESSION_STATS: Mutex<HashMap<String, CompressionStats>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Gzip `bytes` when they exceed `threshold` (0 disables) and compression
+/// actually shrinks them, recording the savings under `handler`
+pub fn compress_payload(handler: &str, bytes: &[u8], threshold: usize) -> Option<Vec<u8>> {
+    if threshold == 0 || bytes.len() <= threshold {
+        return None;
+    }
+    let compressed = CompressionUtils::compress_gzip(bytes).ok()?;
+    if compressed.len() >= bytes.len() {
+        return None;
+    }
+
+    if let Ok(mut stats) = COMPRESSION_STATS.lock() {
+        let entry = stats
+            .entry(handler.to_string())
+            .or_insert_with(|| CompressionStats {
+                handler: handler.to_string(),
+                ..Default::default()
+            });
+        entry.compressed_count += 1;
+        entry.bytes_before += bytes.len() as u64;
+        entry.bytes_after += compressed.len() as u64;
+        entry.bytes_saved = entry.bytes_before - entry.bytes_after;
+    }
+    Some(compressed)
+}
+
+/// Per-handler compression savings, largest first
+pub fn compression_stats() -> Vec<CompressionStats> {
+    let mut stats: Vec<CompressionStats> = COMPRESSION_STATS
+        .lock()
+        .map(|stats| stats.values().cloned().collect())
+        .unwrap_or_default();
+    stats.sort_by_key(|entry| std::cmp::Reverse(entry.bytes_saved));
+    stats
+}
+
+/// Serialize data to the specified format, recording size and timing
 pub fn serialize<T: Serialize>(value: &T, format: SerializationFormat) -> Result<String, String> {
+    let started = Instant::now();
+    let result = encode(value, format);
+    if let Ok(data) = &result {
+        record(format, data.len(), started.elapsed(), true);
+    }
+    result
+}
+
+/// Deserialize data from the specified format, recording size and timing
+pub fn deserialize<T: for<'de> Deserialize<'de>>(
+    data: &str,
+    format: SerializationFormat,
+) -> Result<T, String> {
+    let started = Instant::now();
+    let result = decode(data, format);
+    if result.is_ok() {
+        record(format, data.len(), started.elapsed(), false);
+    }
+    result
+}
+
+const PROTOBUF_NOT_SERDE: &str =
+    "Protobuf works on prost messages; use serialization::protobuf::encode_message/decode_message";
+
+/// Serialize to raw bytes without the base64 text wrapping, recording size and timing
+pub fn serialize_bytes<T: Serialize>(
+    value: &T,
+    format: SerializationFormat,
+) -> Result<Vec<u8>, String> {
+    let started = Instant::now();
+    let result = encode_bytes(value, format);
+    if let Ok(bytes) = &result {
+        record(format, bytes.len(), started.elapsed(), true);
+    }
+    result
+}
+
+/// Zero-copy fast path: deserialize straight from raw bytes.
+///
+/// `T` may borrow from `bytes` (`&'de str`, `&'de [u8]`, `Cow<'de, str>`), so
+/// large string/blob fields are not copied. Use with [`serialize_bytes`].
+pub fn deserialize_bytes<'de, T: Deserialize<'de>>(
+    bytes: &'de [u8],
+    format: SerializationFormat,
+) -> Result<T, String> {
+    let started = Instant::now();
+    let result = decode_bytes(bytes, format);
+    if result.is_ok() {
+        record(format, bytes.len(), started.elapsed(), false);
+    }
+    result
+}
+
+fn encode<T: Serialize>(value: &T, format: SerializationFormat) -> Result<String, String> {
     match format {
         SerializationFormat::Json => {
             serde_json::to_string(value).map_err(|e| format!("JSON serialize error: {}", e))
         }
+        _ => encode_bytes(value, format).map(|bytes| base64_encode(&bytes)),
+    }
+}
+
+fn encode_bytes<T: Serialize>(value: &T, format: SerializationFormat) -> Result<Vec<u8>, String> {
+    match format {
+        SerializationFormat::Json => {
+            serde_json::to_vec(value).map_err(|e| format!("JSON serialize error: {}", e))
+        }
         SerializationFormat::MessagePack => {
-            let bytes = rmp_serde::to_vec(value)
-                .map_err(|e| format!("MessagePack serialize error: {}", e))?;
-            Ok(base64_encode(&bytes))
+            rmp_serde::to_vec(value).map_err(|e| format!("MessagePack serialize error: {}", e))
         }
         SerializationFormat::Cbor => {
-            let bytes =
-                serde_cbor::to_vec(value).map_err(|e| format!("CBOR serialize error: {}", e))?;
-            Ok(base64_encode(&bytes))
+            serde_cbor::to_vec(value).map_err(|e| format!("CBOR serialize error: {}", e))
+        }
+        SerializationFormat::Bincode => {
+            bincode::serialize(value).map_err(|e| format!("Bincode serialize error: {}", e))
         }
+        SerializationFormat::Protobuf => Err(PROTOBUF_NOT_SERDE.to_string()),
     }
 }
 
-/// Deserialize data from the specified format
-pub fn deserialize<T: for<'de> Deserialize<'de>>(
-    data: &str,
-    format: SerializationFormat,
-) -> Result<T, String> {
+fn decode<T: for<'de> Deserialize<'de>>(data: &str, format: SerializationFormat) -> Result<T, String> {
     match format {
         SerializationFormat::Json => {
             serde_json::from_str(data).map_err(|e| format!("JSON deserialize error: {}", e))
         }
-        SerializationFormat::MessagePack => {
+        SerializationFormat::Protobuf => Err(PROTOBUF_NOT_SERDE.to_string()),
+        _ => {
             let bytes = base64_decode(data)
-                .map_err(|e| format!("MessagePack base64 decode error: {}", e))?;
-            rmp_serde::from_slice(&bytes)
-                .map_err(|e| format!("MessagePack deserialize error: {}", e))
+                .map_err(|e| format!("{} base64 decode error: {}", format, e))?;
+            decode_bytes(&bytes, format)
         }
+    }
+}
+
+fn decode_bytes<'de, T: Deserialize<'de>>(
+    bytes: &'de [u8],
+    format: SerializationFormat,
+) -> Result<T, String> {
+    match format {
+        SerializationFormat::Json => {
+            serde_json::from_slice(bytes).map_err(|e| format!("JSON deserialize error: {}", e))
+        }
+        SerializationFormat::MessagePack => rmp_serde::from_slice(bytes)
+            .map_err(|e| format!("MessagePack deserialize error: {}", e)),
         SerializationFormat::Cbor => {
-            let bytes =
-                base64_decode(data).map_err(|e| format!("CBOR base64 decode error: {}", e))?;
-            serde_cbor::from_slice(&bytes).map_err(|e| format!("CBOR deserialize error: {}", e))
+            serde_cbor::from_slice(bytes).map_err(|e| format!("CBOR deserialize error: {}", e))
+        }
+        SerializationFormat::Bincode => {
+            bincode::deserialize(bytes).map_err(|e| format!("Bincode deserialize error: {}", e))
         }
+        SerializationFormat::Protobuf => Err(PROTOBUF_NOT_SERDE.to_string()),
     }
 }
 
 /// Base64 encode for binary data transport over text protocols
-fn base64_encode(data: &[u8]) -> String {
+pub fn base64_encode(data: &[u8]) -> String {
     STANDARD.encode(data)
 }
 
 /// Base64 decode for binary data transport over text protocols
-fn base64_decode(data: &str) -> Result<Vec<u8>, String> {
+pub fn base64_decode(data: &str) -> Result<Vec<u8>, String> {
     STANDARD
         .decode(data)
         .map_err(|e| format!("Base64 decode error: {}", e))
@@ -205,10 +601,26 @@ pub fn get_format_comparison() -> Vec<FormatComparison> {
             browser_support: "⚠️ Limited".to_string(),
             use_case: "Embedded, IoT".to_string(),
         },
+        FormatComparison {
+            format: "Bincode".to_string(),
+            size_ratio: "~0.5x (fixed-shape data)".to_string(),
+            speed_ratio: "~2-3x faster".to_string(),
+            readability: "❌ Binary, schema-bound".to_string(),
+            browser_support: "❌ Not for the bridge".to_string(),
+            use_case: "Backend-to-backend, persistence".to_string(),
+        },
+        FormatComparison {
+            format: "Protobuf".to_string(),
+            size_ratio: "~0.5x (schema-encoded)".to_string(),
+            speed_ratio: "~2x faster".to_string(),
+            readability: "❌ Binary, schema-bound".to_string(),
+            browser_support: "⚠️ Needs generated client".to_string(),
+            use_case: "External service integration".to_string(),
+        },
     ]
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FormatComparison {
     pub format: String,
     pub size_ratio: String,
@@ -262,4 +674,186 @@ mod tests {
         let deserialized: TestData = deserialize(&serialized, SerializationFormat::Cbor).unwrap();
         assert_eq!(data, deserialized);
     }
+
+    #[test]
+    fn test_bincode_serialization() {
+        let data = TestData {
+            name: "test".to_string(),
+            value: 42,
+        };
+        let serialized = serialize(&data, SerializationFormat::Bincode).unwrap();
+        let deserialized: TestData =
+            deserialize(&serialized, SerializationFormat::Bincode).unwrap();
+        assert_eq!(data, deserialized);
+        assert!(!SerializationFormat::Bincode.is_self_describing());
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Document {
+        title: String,
+        body: String,
+        tags: Vec<String>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct BorrowedDocument<'a> {
+        #[serde(borrow)]
+        title: &'a str,
+        #[serde(borrow)]
+        body: &'a str,
+        #[serde(borrow)]
+        tags: Vec<&'a str>,
+    }
+
+    fn large_document() -> Document {
+        Document {
+            title: "benchmark".to_string(),
+            body: "lorem ipsum dolor sit amet ".repeat(40_000),
+            tags: (0..1_000).map(|i| format!("tag-{}", i)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_zero_copy_borrows_from_input() {
+        let doc = large_document();
+        for format in [
+            SerializationFormat::Json,
+            SerializationFormat::MessagePack,
+            SerializationFormat::Cbor,
+            SerializationFormat::Bincode,
+        ] {
+            let bytes = serialize_bytes(&doc, format).unwrap();
+            let borrowed: BorrowedDocument = deserialize_bytes(&bytes, format).unwrap();
+            assert_eq!(borrowed.body.len(), doc.body.len(), "{}", format);
+            assert_eq!(borrowed.tags.len(), doc.tags.len());
+
+            // The body points into the input buffer rather than a fresh allocation
+            let range = bytes.as_ptr_range();
+            assert!(range.contains(&borrowed.body.as_ptr()), "{} copied the body", format);
+            assert_eq!(borrowed.title, "benchmark");
+        }
+    }
+
+    /// Run with `cargo test bench_zero_copy -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_zero_copy_large_payload() {
+        const ROUNDS: u32 = 50;
+        let doc = large_document();
+
+        for format in [
+            SerializationFormat::Json,
+            SerializationFormat::MessagePack,
+            SerializationFormat::Cbor,
+            SerializationFormat::Bincode,
+        ] {
+            let text = serialize(&doc, format).unwrap();
+            let bytes = serialize_bytes(&doc, format).unwrap();
+
+            let started = Instant::now();
+            for _ in 0..ROUNDS {
+                let owned: Document = deserialize(&text, format).unwrap();
+                assert_eq!(owned.tags.len(), doc.tags.len());
+            }
+            let owned = started.elapsed() / ROUNDS;
+
+            let started = Instant::now();
+            for _ in 0..ROUNDS {
+                let borrowed: BorrowedDocument = deserialize_bytes(&bytes, format).unwrap();
+                assert_eq!(borrowed.tags.len(), doc.tags.len());
+            }
+            let borrowed = started.elapsed() / ROUNDS;
+
+            println!(
+                "{:<12} {:>8} bytes  owned {:>10?}  zero-copy {:>10?}  ({:.1}x)",
+                format.to_string(),
+                bytes.len(),
+                owned,
+                borrowed,
+                owned.as_secs_f64() / borrowed.as_secs_f64().max(f64::EPSILON)
+            );
+        }
+    }
+
+    #[test]
+    fn test_compress_payload() {
+        let small = b"tiny";
+        assert!(compress_payload("test_small", small, 64).is_none());
+
+        let large = "repeated payload ".repeat(500).into_bytes();
+        assert!(compress_payload("test_large", &large, 0).is_none());
+
+        let compressed = compress_payload("test_large", &large, 64).unwrap();
+        assert!(compressed.len() < large.len());
+        assert_eq!(CompressionUtils::decompress_gzip(&compressed).unwrap(), large);
+
+        let stats = compression_stats();
+        let entry = stats.iter().find(|s| s.handler == "test_large").unwrap();
+        assert_eq!(entry.compressed_count, 1);
+        assert_eq!(entry.bytes_saved, (large.len() - compressed.len()) as u64);
+    }
+
+    #[test]
+    fn test_stats_recorded() {
+        let data = TestData {
+            name: "stats".to_string(),
+            value: 7,
+        };
+        let before = stats_for(SerializationFormat::Cbor);
+        let encoded = serialize(&data, SerializationFormat::Cbor).unwrap();
+        let _: TestData = deserialize(&encoded, SerializationFormat::Cbor).unwrap();
+
+        let after = stats_for(SerializationFormat::Cbor);
+        assert_eq!(after.total_serializations, before.total_serializations + 1);
+        assert_eq!(after.total_deserializations, before.total_deserializations + 1);
+        assert!(after.total_bytes_sent >= before.total_bytes_sent + encoded.len() as u64);
+        assert!(after.p99_serialization_time_us >= after.p50_serialization_time_us);
+    }
+
+    #[test]
+    fn test_percentile() {
+        let samples: Vec<f64> = (1..=100).map(|v| v as f64).collect();
+        assert_eq!(percentile(&samples, 50.0), 50.0);
+        assert_eq!(percentile(&samples, 99.0), 99.0);
+        assert_eq!(percentile(&[], 95.0), 0.0);
+    }
+
+    #[test]
+    fn test_negotiate_format() {
+        let client = [SerializationFormat::Json, SerializationFormat::Cbor];
+        assert_eq!(
+            negotiate_format(SerializationFormat::Cbor, &client),
+            SerializationFormat::Cbor
+        );
+        assert_eq!(
+            negotiate_format(SerializationFormat::MessagePack, &client),
+            SerializationFormat::Json
+        );
+        assert_eq!(
+            negotiate_format(SerializationFormat::MessagePack, &[]),
+            SerializationFormat::Json
+        );
+        assert_eq!(
+            SerializationFormat::from_name("MsgPack"),
+            Some(SerializationFormat::MessagePack)
+        );
+    }
+
+    #[test]
+    fn test_browser_negotiation_skips_schema_bound_formats() {
+        let bincode = [SerializationFormat::Bincode];
+        assert_eq!(
+            negotiate_browser_format(SerializationFormat::Json, &bincode),
+            SerializationFormat::Json
+        );
+        let client = [SerializationFormat::Bincode, SerializationFormat::Cbor];
+        assert_eq!(
+            negotiate_browser_format(SerializationFormat::Bincode, &client),
+            SerializationFormat::Cbor
+        );
+        assert_eq!(
+            negotiate_browser_format(SerializationFormat::Protobuf, &[]),
+            SerializationFormat::Json
+        );
+    }
 }
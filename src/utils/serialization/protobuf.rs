@@ -0,0 +1,204 @@
+// src/utils/serialization/protobuf.rs
+// Protobuf wire types (prost) matching proto/app.proto
+
+use prost::Message;
+use std::collections::HashMap;
+use std::time::Instant;
+
+use super::{base64_decode, base64_encode, record, SerializationFormat};
+use crate::core::error::ErrorValue as AppErrorValue;
+use crate::core::infrastructure::database::models;
+use crate::core::infrastructure::event_bus::EventData;
+
+/// Protobuf package of the shared wire types
+pub const PACKAGE: &str = "rustwebui.v1";
+
+#[derive(Clone, PartialEq, Message)]
+pub struct User {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(string, tag = "3")]
+    pub email: String,
+    #[prost(string, tag = "4")]
+    pub role: String,
+    #[prost(string, tag = "5")]
+    pub status: String,
+    #[prost(string, tag = "6")]
+    pub created_at: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct UserList {
+    #[prost(message, repeated, tag = "1")]
+    pub users: Vec<User>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct AppEvent {
+    #[prost(string, tag = "1")]
+    pub event_type: String,
+    #[prost(string, tag = "2")]
+    pub payload_json: String,
+    #[prost(int64, tag = "3")]
+    pub timestamp: i64,
+    #[prost(string, optional, tag = "4")]
+    pub source: Option<String>,
+    #[prost(string, optional, tag = "5")]
+    pub target: Option<String>,
+    #[prost(string, optional, tag = "6")]
+    pub correlation_id: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ErrorValue {
+    #[prost(string, tag = "1")]
+    pub code: String,
+    #[prost(string, tag = "2")]
+    pub message: String,
+    #[prost(string, optional, tag = "3")]
+    pub details: Option<String>,
+    #[prost(string, optional, tag = "4")]
+    pub field: Option<String>,
+    #[prost(string, optional, tag = "5")]
+    pub cause: Option<String>,
+    #[prost(map = "string, string", tag = "6")]
+    pub context: HashMap<String, String>,
+    #[prost(string, optional, tag = "7")]
+    pub correlation_id: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct HandlerResponse {
+    #[prost(bool, tag = "1")]
+    pub success: bool,
+    #[prost(bytes = "vec", tag = "2")]
+    pub data: Vec<u8>,
+    #[prost(string, tag = "3")]
+    pub data_type: String,
+    #[prost(message, optional, tag = "4")]
+    pub error: Option<ErrorValue>,
+}
+
+impl HandlerResponse {
+    /// Successful envelope carrying `data` as the named message type
+    pub fn ok<M: Message>(type_name: &str, data: &M) -> Self {
+        Self {
+            success: true,
+            data: data.encode_to_vec(),
+            data_type: format!("{}.{}", PACKAGE, type_name),
+            error: None,
+        }
+    }
+
+    pub fn err(error: &AppErrorValue) -> Self {
+        Self {
+            success: false,
+            data: Vec::new(),
+            data_type: String::new(),
+            error: Some(error.into()),
+        }
+    }
+}
+
+impl From<&models::User> for User {
+    fn from(user: &models::User) -> Self {
+        Self {
+            id: user.id,
+            name: user.name.clone(),
+            email: user.email.clone(),
+            role: user.role.clone(),
+            status: user.status.clone(),
+            created_at: user.created_at.clone(),
+        }
+    }
+}
+
+impl From<User> for models::User {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            name: user.name,
+            email: user.email,
+            role: user.role,
+            status: user.status,
+            created_at: user.created_at,
+        }
+    }
+}
+
+impl From<&EventData> for AppEvent {
+    fn from(event: &EventData) -> Self {
+        Self {
+            event_type: event.event_type.clone(),
+            payload_json: event.payload.to_string(),
+            timestamp: event.timestamp,
+            source: event.source.clone(),
+            target: event.target.clone(),
+            correlation_id: event.correlation_id.clone(),
+        }
+    }
+}
+
+impl From<&AppErrorValue> for ErrorValue {
+    fn from(error: &AppErrorValue) -> Self {
+        Self {
+            code: error.code.to_string(),
+            message: error.message.clone(),
+            details: error.details.clone(),
+            field: error.field.clone(),
+            cause: error.cause.clone(),
+            context: error.context.clone().unwrap_or_default(),
+            correlation_id: error.correlation_id.clone(),
+        }
+    }
+}
+
+/// Encode a message as base64 text, like the other binary formats
+pub fn encode_message<M: Message>(message: &M) -> String {
+    let started = Instant::now();
+    let encoded = base64_encode(&message.encode_to_vec());
+    record(SerializationFormat::Protobuf, encoded.len(), started.elapsed(), true);
+    encoded
+}
+
+/// Decode a base64 message produced by [`encode_message`]
+pub fn decode_message<M: Message + Default>(data: &str) -> Result<M, String> {
+    let started = Instant::now();
+    let bytes = base64_decode(data).map_err(|e| format!("Protobuf base64 decode error: {}", e))?;
+    let message =
+        M::decode(bytes.as_slice()).map_err(|e| format!("Protobuf decode error: {}", e))?;
+    record(SerializationFormat::Protobuf, data.len(), started.elapsed(), false);
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::error::ErrorCode;
+
+    #[test]
+    fn test_user_roundtrip() {
+        let user = models::User::new(7, "Ada", "ada@example.com", "admin", "active", "2024-01-01");
+        let encoded = encode_message(&User::from(&user));
+        let decoded: User = decode_message(&encoded).unwrap();
+        assert_eq!(models::User::from(decoded).email, "ada@example.com");
+    }
+
+    #[test]
+    fn test_envelope() {
+        let users = UserList {
+            users: vec![User::from(&models::User::new(1, "A", "a@x", "user", "active", "now"))],
+        };
+        let response = HandlerResponse::ok("UserList", &users);
+        let decoded = HandlerResponse::decode(response.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded.data_type, "rustwebui.v1.UserList");
+        assert_eq!(UserList::decode(decoded.data.as_slice()).unwrap(), users);
+
+        let error = AppErrorValue::new(ErrorCode::UserNotFound, "missing").with_field("id");
+        let failed = HandlerResponse::err(&error);
+        assert!(!failed.success);
+        assert_eq!(failed.error.unwrap().field.as_deref(), Some("id"));
+    }
+}
@@ -1,4 +1,8 @@
 #![allow(dead_code)]
+pub mod password;
+
+pub use password::PasswordPolicy;
+
 pub struct ValidationUtils;
 
 impl ValidationUtils {
@@ -0,0 +1,208 @@
+// src/utils/validation/password.rs
+// Password policy: minimum length, required character classes, a list of
+// common passwords, and a rough strength score (0-4) in the spirit of
+// zxcvbn, estimated from the character set and how predictable the
+// sequence is
+
+use crate::core::error::{AppError, ErrorCode, ErrorValue};
+
+/// Passwords that show up first in breach dumps; compared case-insensitively
+const COMMON_PASSWORDS: &[&str] = &[
+    "123456", "123456789", "12345678", "1234567890", "12345", "1234567", "111111", "000000",
+    "123123", "654321", "666666", "121212", "123321", "112233", "7777777", "987654321",
+    "password", "password1", "password123", "passw0rd", "p@ssw0rd", "qwerty", "qwerty123",
+    "qwertyuiop", "1q2w3e4r", "1qaz2wsx", "zaq12wsx", "asdfghjkl", "asdfgh", "zxcvbnm",
+    "abc123", "abcd1234", "iloveyou", "admin", "admin123", "administrator", "root", "toor",
+    "welcome", "welcome1", "letmein", "monkey", "dragon", "football", "baseball", "soccer",
+    "master", "shadow", "sunshine", "princess", "superman", "batman", "trustno1", "freedom",
+    "whatever", "starwars", "michael", "jennifer", "charlie", "hello123", "login", "guest",
+    "changeme", "secret", "default", "test1234", "mustang", "access", "killer", "hunter2",
+];
+
+/// One rule a password broke
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordViolation {
+    /// Rule id, e.g. "min_length" or "uppercase"
+    pub rule: &'static str,
+    pub message: String,
+}
+
+/// What a password must satisfy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    /// Refuse passwords from the common-password list
+    pub reject_common: bool,
+    /// Lowest `strength` score accepted; 0 turns the check off
+    pub min_score: u8,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            require_lowercase: false,
+            require_uppercase: false,
+            require_digit: false,
+            require_symbol: false,
+            reject_common: true,
+            min_score: 2,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Every rule `password` breaks; empty when it is acceptable
+    pub fn check(&self, password: &str) -> Vec<PasswordViolation> {
+        let mut violations = Vec::new();
+        let mut fail = |rule: &'static str, message: String| {
+            violations.push(PasswordViolation { rule, message });
+        };
+
+        let length = password.chars().count();
+        if length < self.min_length {
+            fail(
+                "min_length",
+                format!("Use at least {} characters ({} given)", self.min_length, length),
+            );
+        }
+        // Whether required, rule, what to include, and the test
+        type CharClass = (bool, &'static str, &'static str, fn(char) -> bool);
+        let classes: [CharClass; 4] = [
+            (self.require_lowercase, "lowercase", "a lowercase letter", char::is_lowercase),
+            (self.require_uppercase, "uppercase", "an uppercase letter", char::is_uppercase),
+            (self.require_digit, "digit", "a digit", |c| c.is_ascii_digit()),
+            (self.require_symbol, "symbol", "a symbol", |c| !c.is_alphanumeric()),
+        ];
+        for (required, rule, what, matches) in classes {
+            if required && !password.chars().any(matches) {
+                fail(rule, format!("Include {}", what));
+            }
+        }
+        if self.reject_common && Self::is_common(password) {
+            fail("common", String::from("This password is too common"));
+        } else if self.min_score > 0 {
+            let score = Self::strength(password);
+            if score < self.min_score {
+                fail(
+                    "strength",
+                    format!(
+                        "This password is too easy to guess (strength {} of 4, {} needed)",
+                        score, self.min_score
+                    ),
+                );
+            }
+        }
+        violations
+    }
+
+    /// Ok when `password` passes, else a VALIDATION_FAILED error for `field`
+    /// listing every broken rule
+    pub fn validate(&self, password: &str, field: &str) -> Result<(), AppError> {
+        let violations = self.check(password);
+        if violations.is_empty() {
+            return Ok(());
+        }
+        let messages: Vec<&str> = violations.iter().map(|v| v.message.as_str()).collect();
+        let rules: Vec<&str> = violations.iter().map(|v| v.rule).collect();
+        Err(AppError::Validation(
+            ErrorValue::new(ErrorCode::ValidationFailed, messages.join(". "))
+                .with_field(field)
+                .with_context("rules", rules.join(",")),
+        ))
+    }
+
+    /// Whether `password` is on the common-password list
+    pub fn is_common(password: &str) -> bool {
+        let lower = password.to_lowercase();
+        COMMON_PASSWORDS.contains(&lower.as_str())
+    }
+
+    /// Rough strength from 0 (guessable at once) to 4 (very strong): bits
+    /// of the character set per character, not counting characters that
+    /// repeat or continue a run like "abc" or "321"
+    pub fn strength(password: &str) -> u8 {
+        if Self::is_common(password) {
+            return 0;
+        }
+        let has = |f: fn(&char) -> bool| password.chars().any(|c| f(&c));
+        let charset = [
+            (has(char::is_ascii_lowercase), 26),
+            (has(char::is_ascii_uppercase), 26),
+            (has(char::is_ascii_digit), 10),
+            (has(char::is_ascii_punctuation) || password.contains(' '), 33),
+            (has(|c| !c.is_ascii()), 100),
+        ]
+        .iter()
+        .filter(|(present, _)| *present)
+        .map(|(_, size)| size)
+        .sum::<u32>();
+
+        let chars: Vec<char> = password.chars().collect();
+        let unpredictable = chars
+            .iter()
+            .enumerate()
+            .filter(|&(i, &c)| i == 0 || (c as i64 - chars[i - 1] as i64).abs() > 1)
+            .count();
+        let bits = unpredictable as f64 * f64::from(charset.max(1)).log2();
+        match bits {
+            b if b < 28.0 => 0,
+            b if b < 36.0 => 1,
+            b if b < 60.0 => 2,
+            b if b < 80.0 => 3,
+            _ => 4,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(policy: &PasswordPolicy, password: &str) -> Vec<&'static str> {
+        policy.check(password).into_iter().map(|v| v.rule).collect()
+    }
+
+    #[test]
+    fn test_policy_rules() {
+        let strict = PasswordPolicy {
+            min_length: 12,
+            require_lowercase: true,
+            require_uppercase: true,
+            require_digit: true,
+            require_symbol: true,
+            reject_common: true,
+            min_score: 3,
+        };
+        assert_eq!(
+            rules(&strict, "short"),
+            ["min_length", "uppercase", "digit", "symbol", "strength"]
+        );
+        assert_eq!(rules(&strict, "Password123"), ["min_length", "symbol", "common"]);
+        assert_eq!(
+            rules(&strict, "PASSWORD"),
+            ["min_length", "lowercase", "digit", "symbol", "common"]
+        );
+        assert!(rules(&strict, "Tr0ub4dor&3-horse").is_empty());
+
+        let error = strict.validate("short", "password").unwrap_err();
+        let value = error.to_value();
+        assert_eq!(value.code, ErrorCode::ValidationFailed);
+        assert_eq!(value.field.as_deref(), Some("password"));
+        assert!(value.message.starts_with("Use at least 12 characters (5 given)"));
+    }
+
+    #[test]
+    fn test_strength() {
+        assert_eq!(PasswordPolicy::strength("qwerty"), 0);
+        assert_eq!(PasswordPolicy::strength("abcdefghijkl"), 0);
+        assert_eq!(PasswordPolicy::strength("aaaaaaaaaaaa"), 0);
+        assert!(PasswordPolicy::strength("first password") >= 2);
+        assert_eq!(PasswordPolicy::strength("correct horse battery staple"), 4);
+        assert!(PasswordPolicy::default().check("first password").is_empty());
+    }
+}